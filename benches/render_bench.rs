@@ -2,10 +2,11 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget, widgets::Widget};
 
-use simple_claude_board::data::state::DashboardState;
+use simple_claude_board::data::state::{DashboardState, TaskTiming};
+use simple_claude_board::data::tasks_parser::TaskStatus;
 use simple_claude_board::ui::claude_output::AgentPanel;
 use simple_claude_board::ui::detail::DetailWidget;
-use simple_claude_board::ui::gantt::{GanttState, GanttWidget};
+use simple_claude_board::ui::gantt::{GanttState, GanttViewMode, GanttWidget};
 use simple_claude_board::ui::help::HelpOverlay;
 use simple_claude_board::ui::layout::DashboardLayout;
 use simple_claude_board::ui::statusbar::StatusBar;
@@ -69,6 +70,69 @@ fn bench_gantt_render_large(c: &mut Criterion) {
     });
 }
 
+fn bench_gantt_render_large_cached(c: &mut Criterion) {
+    let state = large_state();
+    let area = Rect::new(0, 0, 120, 50);
+    // Steady state: the row cache is warm from earlier frames
+    let mut gs = GanttState::default();
+
+    c.bench_function("gantt_render cached (1000 tasks)", |b| {
+        b.iter(|| {
+            let widget = GanttWidget::new(&state, true);
+            let mut buf = Buffer::empty(area);
+            widget.render(black_box(area), &mut buf, &mut gs);
+            black_box(buf);
+        })
+    });
+}
+
+/// `large_state` with timings: done tasks finished, in-progress ones running
+fn large_timed_state() -> DashboardState {
+    let mut state = large_state();
+    let start = chrono::Utc::now() - chrono::Duration::hours(20);
+    let tasks: Vec<_> = state
+        .phases
+        .iter()
+        .flat_map(|p| &p.tasks)
+        .map(|t| (t.id.clone(), t.status.clone()))
+        .collect();
+    for (i, (id, status)) in tasks.into_iter().enumerate() {
+        let started_at = start + chrono::Duration::minutes(i as i64);
+        let completed_at = match status {
+            TaskStatus::Completed => Some(started_at + chrono::Duration::minutes(30)),
+            TaskStatus::InProgress => None,
+            _ => continue,
+        };
+        state.task_times.insert(
+            id.into(),
+            TaskTiming {
+                started_at: Some(started_at),
+                completed_at,
+            },
+        );
+    }
+    state
+}
+
+fn bench_gantt_bar_render_large(c: &mut Criterion) {
+    let state = large_timed_state();
+    let area = Rect::new(0, 0, 120, 50);
+    // Steady state: the same state drawn frame after frame
+    let mut gs = GanttState {
+        view_mode: GanttViewMode::HorizontalBar,
+        ..Default::default()
+    };
+
+    c.bench_function("gantt_bar_render (1000 tasks)", |b| {
+        b.iter(|| {
+            let widget = GanttWidget::new(&state, true);
+            let mut buf = Buffer::empty(area);
+            widget.render(black_box(area), &mut buf, &mut gs);
+            black_box(buf);
+        })
+    });
+}
+
 fn bench_detail_render(c: &mut Criterion) {
     let state = sample_state();
     let area = Rect::new(0, 0, 50, 20);
@@ -118,7 +182,7 @@ fn bench_help_overlay_render(c: &mut Criterion) {
     c.bench_function("help_overlay_render", |b| {
         b.iter(|| {
             let mut buf = Buffer::empty(area);
-            HelpOverlay.render(black_box(area), &mut buf);
+            HelpOverlay::default().render(black_box(area), &mut buf);
             black_box(buf);
         })
     });
@@ -166,6 +230,8 @@ criterion_group!(
     benches,
     bench_gantt_render,
     bench_gantt_render_large,
+    bench_gantt_render_large_cached,
+    bench_gantt_bar_render_large,
     bench_detail_render,
     bench_agent_panel_render,
     bench_statusbar_render,
@@ -9,9 +9,19 @@
  *
  * Hook events captured:
  *   PreToolUse[Task]              -> agent_start
- *   PostToolUse[Task]             -> agent_end
+ *   PostToolUse[Task]             -> agent_end (with the subagent's token usage)
  *   PreToolUse[Edit|Write|...]    -> tool_start
- *   PostToolUse[Edit|Write|...]   -> tool_end
+ *   PostToolUse[Edit|Write|...]   -> tool_end (with the tail of the output)
+ *
+ * Before a tool runs, a stop request from the dashboard (a file at
+ * ~/.claude/dashboard/control/<agent>.stop) blocks the call: the file is
+ * removed and the hook exits with code 2 so Claude sees the message.
+ *
+ * Run as `node event-logger.js heartbeat` (e.g. from a loop or a Notification
+ * hook) to append a heartbeat, which tells the dashboard the agent is alive.
+ * Run `node event-logger.js run-start` when an orchestration run begins to
+ * start the dashboard's run clock there, and `node event-logger.js rate-limit
+ * [seconds]` when the agent hits a provider rate limit to show its cooldown.
  */
 
 const fs = require('fs');
@@ -21,6 +31,8 @@ const os = require('os');
 const EVENTS_DIR = path.join(os.homedir(), '.claude', 'dashboard');
 const EVENTS_FILE = path.join(EVENTS_DIR, 'events.jsonl');
 const SESSION_ID_FILE = path.join(os.tmpdir(), 'claude-dashboard-session-id');
+const CONTROL_DIR = path.join(EVENTS_DIR, 'control');
+const MAX_OUTPUT_CHARS = 2000;
 
 // Tools we track (Task is handled separately as agent events)
 const TRACKED_TOOLS = new Set([
@@ -85,6 +97,51 @@ function appendEvent(event) {
   }
 }
 
+/**
+ * Consume a pending stop request for the agent, if any.
+ * File names match the dashboard: anything but [A-Za-z0-9._-] becomes '_'.
+ * @returns {boolean} true if the agent was asked to stop
+ */
+function takeStopRequest(agentId) {
+  const file = path.join(CONTROL_DIR, `${agentId.replace(/[^A-Za-z0-9._-]/g, '_')}.stop`);
+  try {
+    if (!fs.existsSync(file)) return false;
+    fs.unlinkSync(file);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Tail of a tool's response for the dashboard's output preview.
+ * @returns {string|undefined} The last MAX_OUTPUT_CHARS characters, if any
+ */
+function truncateOutput(response) {
+  if (response === undefined || response === null) return undefined;
+  let text = typeof response === 'string'
+    ? response
+    : response.stdout || response.output || response.content || JSON.stringify(response);
+  if (typeof text !== 'string') text = JSON.stringify(text);
+  return text.length > MAX_OUTPUT_CHARS ? text.slice(-MAX_OUTPUT_CHARS) : text;
+}
+
+/**
+ * Token usage of a finished Task for the dashboard's cost tracking.
+ * Cache reads and writes count as input tokens.
+ * @returns {object|undefined} { input_tokens, output_tokens }, if reported
+ */
+function extractUsage(response) {
+  const usage = response && response.usage;
+  if (!usage || typeof usage !== 'object') return undefined;
+  return {
+    input_tokens: (usage.input_tokens || 0)
+      + (usage.cache_creation_input_tokens || 0)
+      + (usage.cache_read_input_tokens || 0),
+    output_tokens: usage.output_tokens || 0,
+  };
+}
+
 /**
  * Determine if this is a Pre or Post hook from the hook_event_name field.
  */
@@ -108,6 +165,30 @@ function extractTaskId(prompt) {
 }
 
 async function main() {
+  if (process.argv[2] === 'heartbeat' || process.argv[2] === 'run-start') {
+    appendEvent({
+      event_type: process.argv[2] === 'heartbeat' ? 'heartbeat' : 'run_start',
+      timestamp: new Date().toISOString(),
+      agent_id: process.env.CLAUDE_AGENT_ROLE || 'main',
+      task_id: 'unknown',
+      session_id: getSessionId(),
+    });
+    return;
+  }
+
+  if (process.argv[2] === 'rate-limit') {
+    const seconds = parseInt(process.argv[3], 10);
+    appendEvent({
+      event_type: 'rate_limit',
+      timestamp: new Date().toISOString(),
+      agent_id: process.env.CLAUDE_AGENT_ROLE || 'main',
+      task_id: 'unknown',
+      session_id: getSessionId(),
+      error_message: seconds > 0 ? `Rate limited, retry after ${seconds}s` : 'Rate limited',
+    });
+    return;
+  }
+
   const input = await readStdin();
   const hookEventName = input.hook_event_name || '';
   const toolInput = input.tool_input || {};
@@ -120,6 +201,11 @@ async function main() {
 
   const pre = isPreHook(hookEventName);
 
+  if (pre && takeStopRequest(agentId)) {
+    process.stderr.write('Stop requested from simple-claude-board: finish up and stop working.\n');
+    process.exit(2);
+  }
+
   // Task tool -> agent_start / agent_end
   if (toolName === 'Task' || hookEventName.includes('Task')) {
     const subagentType = toolInput.subagent_type || 'unknown';
@@ -132,6 +218,7 @@ async function main() {
       task_id: taskId,
       session_id: sessionId,
       tool_name: subagentType,
+      ...(pre ? {} : { usage: extractUsage(input.tool_response) }),
     });
     return;
   }
@@ -145,6 +232,7 @@ async function main() {
       task_id: 'unknown',
       session_id: sessionId,
       tool_name: toolName,
+      ...(pre ? {} : { tool_output: truncateOutput(input.tool_response) }),
     });
     return;
   }
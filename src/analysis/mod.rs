@@ -1 +1,3 @@
+pub mod retry;
 pub mod rules;
+pub mod triage;
@@ -0,0 +1,181 @@
+//! Retry budgets and circuit breaker
+//!
+//! Per-category retry budgets (e.g. "Network: up to 3 attempts") decide whether
+//! a failed task is retried automatically. Once a task exhausts its budget the
+//! circuit opens and the task is parked as Blocked so a human can take over.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::analysis::rules::ErrorCategory;
+
+/// Retry policy loaded from the `[retry]` config section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Retry failed tasks automatically while budget remains
+    pub auto: bool,
+    /// Maximum automatic attempts per error category (unlisted categories: none)
+    pub budgets: HashMap<ErrorCategory, u32>,
+}
+
+/// What the retry subsystem wants to do with a failed task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// Budget remains: retry now (carries the attempt number being started)
+    Retry(u32),
+    /// Budget used up: open the circuit and mark the task Blocked
+    Exhausted,
+    /// No automatic handling for this category; leave it to the human
+    Manual,
+}
+
+impl RetryPolicy {
+    /// Budget for a category, if one is configured
+    pub fn budget_for(&self, category: &ErrorCategory) -> Option<u32> {
+        self.budgets.get(category).copied()
+    }
+
+    /// Decide what to do with a failed task given its prior attempt count
+    pub fn decide(&self, category: &ErrorCategory, attempts: u32) -> RetryDecision {
+        if !self.auto {
+            return RetryDecision::Manual;
+        }
+        match self.budget_for(category) {
+            Some(budget) if attempts < budget => RetryDecision::Retry(attempts + 1),
+            Some(_) => RetryDecision::Exhausted,
+            None => RetryDecision::Manual,
+        }
+    }
+}
+
+/// Per-task retry bookkeeping
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetryState {
+    pub attempts: u32,
+    /// Budget that applied at the last decision (None for manual-only retries)
+    pub budget: Option<u32>,
+    pub category: Option<ErrorCategory>,
+    /// Circuit breaker tripped: automation gave up on this task
+    pub exhausted: bool,
+}
+
+/// Tracks retry attempts and circuit-breaker state across reloads
+#[derive(Debug, Clone, Default)]
+pub struct RetryTracker {
+    tasks: HashMap<String, RetryState>,
+}
+
+impl RetryTracker {
+    pub fn get(&self, task_id: &str) -> Option<&RetryState> {
+        self.tasks.get(task_id)
+    }
+
+    /// Number of retries already made for a task
+    pub fn attempts(&self, task_id: &str) -> u32 {
+        self.tasks.get(task_id).map_or(0, |s| s.attempts)
+    }
+
+    /// Record a retry attempt (automatic or manual)
+    pub fn record_attempt(
+        &mut self,
+        task_id: &str,
+        category: Option<ErrorCategory>,
+        budget: Option<u32>,
+    ) {
+        let entry = self.tasks.entry(task_id.to_string()).or_default();
+        entry.attempts += 1;
+        entry.budget = budget;
+        entry.category = category;
+    }
+
+    /// Open the circuit for a task whose budget is used up
+    pub fn mark_exhausted(&mut self, task_id: &str, category: ErrorCategory, budget: u32) {
+        let entry = self.tasks.entry(task_id.to_string()).or_default();
+        entry.exhausted = true;
+        entry.budget = Some(budget);
+        entry.category = Some(category);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network_policy(budget: u32) -> RetryPolicy {
+        RetryPolicy {
+            auto: true,
+            budgets: HashMap::from([(ErrorCategory::Network, budget)]),
+        }
+    }
+
+    #[test]
+    fn default_policy_is_manual() {
+        let policy = RetryPolicy::default();
+        assert_eq!(
+            policy.decide(&ErrorCategory::Network, 0),
+            RetryDecision::Manual
+        );
+    }
+
+    #[test]
+    fn retry_until_budget_exhausted() {
+        let policy = network_policy(3);
+        assert_eq!(
+            policy.decide(&ErrorCategory::Network, 0),
+            RetryDecision::Retry(1)
+        );
+        assert_eq!(
+            policy.decide(&ErrorCategory::Network, 2),
+            RetryDecision::Retry(3)
+        );
+        assert_eq!(
+            policy.decide(&ErrorCategory::Network, 3),
+            RetryDecision::Exhausted
+        );
+    }
+
+    #[test]
+    fn unlisted_category_is_manual() {
+        let policy = network_policy(3);
+        assert_eq!(
+            policy.decide(&ErrorCategory::Permission, 0),
+            RetryDecision::Manual
+        );
+    }
+
+    #[test]
+    fn zero_budget_exhausts_immediately() {
+        let policy = network_policy(0);
+        assert_eq!(
+            policy.decide(&ErrorCategory::Network, 0),
+            RetryDecision::Exhausted
+        );
+    }
+
+    #[test]
+    fn tracker_counts_attempts_and_trips() {
+        let mut tracker = RetryTracker::default();
+        assert_eq!(tracker.attempts("T1"), 0);
+        tracker.record_attempt("T1", Some(ErrorCategory::Network), Some(2));
+        tracker.record_attempt("T1", Some(ErrorCategory::Network), Some(2));
+        assert_eq!(tracker.attempts("T1"), 2);
+        assert!(!tracker.get("T1").unwrap().exhausted);
+
+        tracker.mark_exhausted("T1", ErrorCategory::Network, 2);
+        let state = tracker.get("T1").unwrap();
+        assert!(state.exhausted);
+        assert_eq!(state.budget, Some(2));
+    }
+
+    #[test]
+    fn policy_from_toml() {
+        let policy: RetryPolicy =
+            toml::from_str("auto = true\n[budgets]\nNetwork = 3\nRuntime = 1\n").unwrap();
+        assert!(policy.auto);
+        assert_eq!(policy.budget_for(&ErrorCategory::Network), Some(3));
+        assert_eq!(policy.budget_for(&ErrorCategory::Runtime), Some(1));
+        assert_eq!(policy.budget_for(&ErrorCategory::Type), None);
+    }
+}
@@ -2,14 +2,32 @@
 //!
 //! Pattern-matching engine that categorizes error messages from hook events
 //! and provides retryable hints and actionable suggestions.
+//!
+//! Suggestions may carry templates whose `{placeholders}` are filled from
+//! named capture groups matched against the original message (port, path,
+//! missing module name, ...).
+//!
+//! Custom rules from the `[[rules]]` config section are checked before the
+//! built-in table and may override category, severity, retryable and the
+//! suggestion.
+//!
+//! Rate-limit errors get their own category: [`retry_after`] reads how long
+//! the provider asked to wait, which starts the agent's cooldown.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Deserialize;
 
 /// Error category derived from pattern matching
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
 pub enum ErrorCategory {
     Type,
     Runtime,
     Network,
     Permission,
+    RateLimit,
     Unknown,
 }
 
@@ -20,17 +38,47 @@ impl std::fmt::Display for ErrorCategory {
             Self::Runtime => write!(f, "Runtime"),
             Self::Network => write!(f, "Network"),
             Self::Permission => write!(f, "Permission"),
+            Self::RateLimit => write!(f, "RateLimit"),
             Self::Unknown => write!(f, "Unknown"),
         }
     }
 }
 
+/// How serious an error is, from informational to run-stopping
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+    Fatal,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => write!(f, "Info"),
+            Self::Warning => write!(f, "Warning"),
+            Self::Error => write!(f, "Error"),
+            Self::Fatal => write!(f, "Fatal"),
+        }
+    }
+}
+
 /// Result of analyzing an error message
 #[derive(Debug, Clone)]
 pub struct ErrorAnalysis {
     pub category: ErrorCategory,
+    pub severity: Severity,
     pub retryable: bool,
     pub suggestion: &'static str,
+    /// Suggestion with details from the message filled in, when a template matched
+    pub hint: Option<String>,
+}
+
+/// Suggestion template: regex with named groups + text with `{group}` placeholders
+struct Template {
+    pattern: &'static str,
+    text: &'static str,
 }
 
 /// Rule entry: pattern to match (lowercase), category, retryable, suggestion
@@ -38,7 +86,10 @@ struct Rule {
     patterns: &'static [&'static str],
     category: ErrorCategory,
     retryable: bool,
+    severity: Severity,
     suggestion: &'static str,
+    /// Tried in order; the first one whose regex matches renders the hint
+    templates: &'static [Template],
 }
 
 const RULES: &[Rule] = &[
@@ -47,79 +98,248 @@ const RULES: &[Rule] = &[
         patterns: &["permission denied"],
         category: ErrorCategory::Permission,
         retryable: false,
+        severity: Severity::Error,
         suggestion: "Check file permissions",
+        templates: &[Template {
+            pattern: r"(?i)permission denied[^/~.\w]*(?P<path>[~./\w-]*/[\w./-]+)",
+            text: "Check file permissions on {path}",
+        }],
     },
     Rule {
         patterns: &["access denied"],
         category: ErrorCategory::Permission,
         retryable: false,
+        severity: Severity::Error,
         suggestion: "Check access rights",
+        templates: &[],
+    },
+    // Rate limits, before timeouts ("retry after the timeout")
+    Rule {
+        patterns: &[
+            "rate limit",
+            "rate_limit",
+            "rate-limit",
+            "too many requests",
+        ],
+        category: ErrorCategory::RateLimit,
+        retryable: true,
+        severity: Severity::Warning,
+        suggestion: "Wait for the cooldown, then retry",
+        templates: &[Template {
+            pattern: r"(?i)(?:retry[- ]after|try again in)\D{0,3}(?P<secs>\d+)",
+            text: "Wait for the cooldown ({secs}s), then retry",
+        }],
     },
     // Network
     Rule {
         patterns: &["connection refused"],
         category: ErrorCategory::Network,
         retryable: true,
+        severity: Severity::Error,
         suggestion: "Check if service is running",
+        templates: &[Template {
+            pattern: r"(?P<host>[\w.-]+):(?P<port>\d{1,5})\b",
+            text: "Check if the service at {host}:{port} is running",
+        }],
     },
     Rule {
         patterns: &["timeout", "timed out"],
         category: ErrorCategory::Network,
         retryable: true,
+        severity: Severity::Warning,
         suggestion: "Retry or increase timeout",
-    },
-    Rule {
-        patterns: &["rate limit"],
-        category: ErrorCategory::Network,
-        retryable: true,
-        suggestion: "Wait and retry",
+        templates: &[Template {
+            pattern: r"(?i)after (?P<secs>\d+)\s*s\b",
+            text: "Retry or increase timeout (currently {secs}s)",
+        }],
     },
     Rule {
         patterns: &["dns", "resolve"],
         category: ErrorCategory::Network,
         retryable: true,
+        severity: Severity::Error,
         suggestion: "Check network connection",
+        templates: &[Template {
+            pattern: r"(?i)(?:for|host|resolve)\s+'?(?P<host>[\w-]+(?:\.[\w-]+)+)",
+            text: "Check network connection to {host}",
+        }],
     },
     // Type
     Rule {
         patterns: &["type error", "type mismatch"],
         category: ErrorCategory::Type,
         retryable: false,
+        severity: Severity::Error,
         suggestion: "Fix type annotations",
+        templates: &[],
     },
     Rule {
         patterns: &["cannot find", "not found"],
         category: ErrorCategory::Type,
         retryable: false,
+        severity: Severity::Error,
         suggestion: "Check imports and paths",
+        templates: &[
+            Template {
+                pattern: r#"(?i)(?:crate|module|package)\s+[`'"]?(?P<name>[\w:@/-]+)"#,
+                text: "Check that `{name}` is installed and imported",
+            },
+            Template {
+                pattern: r"(?P<path>[~.]?[\w.-]*/[\w./-]+)",
+                text: "Check that {path} exists",
+            },
+        ],
     },
     Rule {
         patterns: &["undefined", "unresolved"],
         category: ErrorCategory::Type,
         retryable: false,
+        severity: Severity::Error,
         suggestion: "Check variable/module names",
+        templates: &[Template {
+            pattern: r#"(?i)(?:undefined|unresolved)\s+(?:reference to |variable |name |import )?[`'"]?(?P<name>[\w:]+)"#,
+            text: "Check that `{name}` is defined and spelled correctly",
+        }],
     },
     // Runtime
     Rule {
         patterns: &["out of memory", "oom"],
         category: ErrorCategory::Runtime,
         retryable: false,
+        severity: Severity::Fatal,
         suggestion: "Reduce memory usage",
+        templates: &[],
     },
     Rule {
         patterns: &["stack overflow"],
         category: ErrorCategory::Runtime,
         retryable: false,
+        severity: Severity::Fatal,
         suggestion: "Check for infinite recursion",
+        templates: &[],
     },
     Rule {
         patterns: &["panic", "unwrap"],
         category: ErrorCategory::Runtime,
         retryable: false,
+        severity: Severity::Fatal,
         suggestion: "Add proper error handling",
+        templates: &[],
     },
 ];
 
+/// Compiled template regexes, keyed by pattern source (built once)
+fn template_regexes() -> &'static HashMap<&'static str, Regex> {
+    static CACHE: OnceLock<HashMap<&'static str, Regex>> = OnceLock::new();
+    CACHE.get_or_init(|| {
+        RULES
+            .iter()
+            .flat_map(|r| r.templates)
+            .map(|t| {
+                let re = Regex::new(t.pattern).expect("built-in template regex is valid");
+                (t.pattern, re)
+            })
+            .collect()
+    })
+}
+
+/// Fill `{name}` placeholders in `text` from the regex's named captures.
+/// Returns None if the regex does not match the message.
+fn render_template(re: &Regex, text: &str, message: &str) -> Option<String> {
+    let caps = re.captures(message)?;
+    let mut out = text.to_string();
+    for name in re.capture_names().flatten() {
+        if let Some(value) = caps.name(name) {
+            out = out.replace(&format!("{{{name}}}"), value.as_str());
+        }
+    }
+    Some(out)
+}
+
+/// User-defined rule from the `[[rules]]` config section.
+/// Unset fields keep the built-in analysis of the message.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomRule {
+    /// Case-insensitive substring to look for
+    pub pattern: String,
+    pub category: Option<ErrorCategory>,
+    pub severity: Option<Severity>,
+    pub retryable: Option<bool>,
+    pub suggestion: Option<String>,
+}
+
+impl CustomRule {
+    pub fn matches(&self, message: &str) -> bool {
+        message
+            .to_lowercase()
+            .contains(&self.pattern.to_lowercase())
+    }
+}
+
+/// Cooldown a rate-limit message asks for, from "retry after 30s",
+/// "Retry-After: 30" or "try again in 2 minutes" (seconds when no unit)
+pub fn retry_after(message: &str) -> Option<u64> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(r"(?i)(?:retry[- ]after|try again in)\D{0,3}(?P<n>\d+)\s*(?P<unit>ms|h|m|s)?")
+            .expect("retry-after regex is valid")
+    });
+    let caps = re.captures(message)?;
+    let n: u64 = caps["n"].parse().ok()?;
+    Some(match caps.name("unit").map(|u| u.as_str().to_lowercase()) {
+        Some(unit) if unit == "ms" => n.div_ceil(1000),
+        Some(unit) if unit == "m" => n.saturating_mul(60),
+        Some(unit) if unit == "h" => n.saturating_mul(3600),
+        _ => n,
+    })
+}
+
+/// Analyze with custom rules applied first (first matching custom rule wins).
+pub fn analyze_error_with(message: &str, custom: &[CustomRule]) -> ErrorAnalysis {
+    let mut analysis = analyze_error(message);
+    if let Some(rule) = custom.iter().find(|r| r.matches(message)) {
+        if let Some(ref category) = rule.category {
+            analysis.category = category.clone();
+        }
+        if let Some(severity) = rule.severity {
+            analysis.severity = severity;
+        }
+        if let Some(retryable) = rule.retryable {
+            analysis.retryable = retryable;
+        }
+        if let Some(ref suggestion) = rule.suggestion {
+            analysis.hint = Some(suggestion.clone());
+        }
+    }
+    analysis
+}
+
+/// Analysis of `message` by a built-in rule
+fn apply_rule(rule: &Rule, message: &str) -> ErrorAnalysis {
+    let regexes = template_regexes();
+    let hint = rule
+        .templates
+        .iter()
+        .find_map(|t| render_template(&regexes[t.pattern], t.text, message));
+    ErrorAnalysis {
+        category: rule.category.clone(),
+        severity: rule.severity,
+        retryable: rule.retryable,
+        suggestion: rule.suggestion,
+        hint,
+    }
+}
+
+/// Analysis of a message known to be a rate limit (a `rate_limit` event),
+/// whatever its wording
+pub fn analyze_rate_limit(message: &str) -> ErrorAnalysis {
+    let rule = RULES
+        .iter()
+        .find(|r| r.category == ErrorCategory::RateLimit)
+        .expect("built-in rate limit rule");
+    apply_rule(rule, message)
+}
+
 /// Analyze an error message and return its category, retryable hint, and suggestion.
 ///
 /// Rules are matched in priority order (first match wins) using case-insensitive
@@ -127,20 +347,19 @@ const RULES: &[Rule] = &[
 pub fn analyze_error(message: &str) -> ErrorAnalysis {
     let lower = message.to_lowercase();
 
-    for rule in RULES {
-        if rule.patterns.iter().any(|p| lower.contains(p)) {
-            return ErrorAnalysis {
-                category: rule.category.clone(),
-                retryable: rule.retryable,
-                suggestion: rule.suggestion,
-            };
-        }
+    if let Some(rule) = RULES
+        .iter()
+        .find(|r| r.patterns.iter().any(|p| lower.contains(p)))
+    {
+        return apply_rule(rule, message);
     }
 
     ErrorAnalysis {
         category: ErrorCategory::Unknown,
+        severity: Severity::Error,
         retryable: false,
         suggestion: "Investigate error details",
+        hint: None,
     }
 }
 
@@ -188,9 +407,29 @@ mod tests {
     #[test]
     fn rate_limit() {
         let r = analyze_error("rate limit exceeded: 429");
-        assert_eq!(r.category, ErrorCategory::Network);
+        assert_eq!(r.category, ErrorCategory::RateLimit);
         assert!(r.retryable);
-        assert_eq!(r.suggestion, "Wait and retry");
+        assert_eq!(r.suggestion, "Wait for the cooldown, then retry");
+
+        let r = analyze_error("429 Too Many Requests, retry after 30s timeout");
+        assert_eq!(r.category, ErrorCategory::RateLimit);
+        assert_eq!(
+            r.hint.as_deref(),
+            Some("Wait for the cooldown (30s), then retry")
+        );
+    }
+
+    #[test]
+    fn retry_after_units() {
+        assert_eq!(retry_after("rate limited, retry after 30s"), Some(30));
+        assert_eq!(retry_after("Retry-After: 45"), Some(45));
+        assert_eq!(retry_after("try again in 2 minutes"), Some(120));
+        assert_eq!(retry_after("retry after 1500ms"), Some(2));
+        assert_eq!(retry_after("rate limit exceeded"), None);
+        assert_eq!(
+            retry_after("retry after 99999999999999999h"),
+            Some(u64::MAX)
+        );
     }
 
     #[test]
@@ -273,6 +512,116 @@ mod tests {
         assert_eq!(r.category, ErrorCategory::Permission);
     }
 
+    #[test]
+    fn hint_interpolates_host_and_port() {
+        let r = analyze_error("connection refused: localhost:5432");
+        assert_eq!(r.suggestion, "Check if service is running");
+        assert_eq!(
+            r.hint.as_deref(),
+            Some("Check if the service at localhost:5432 is running")
+        );
+    }
+
+    #[test]
+    fn hint_interpolates_path() {
+        let r = analyze_error("permission denied: /etc/shadow");
+        assert_eq!(
+            r.hint.as_deref(),
+            Some("Check file permissions on /etc/shadow")
+        );
+    }
+
+    #[test]
+    fn hint_interpolates_missing_module() {
+        let r = analyze_error("error: module 'serde_yaml' not found");
+        assert_eq!(
+            r.hint.as_deref(),
+            Some("Check that `serde_yaml` is installed and imported")
+        );
+    }
+
+    #[test]
+    fn hint_falls_back_to_second_template() {
+        let r = analyze_error("file not found: src/data/missing.rs");
+        assert_eq!(
+            r.hint.as_deref(),
+            Some("Check that src/data/missing.rs exists")
+        );
+    }
+
+    #[test]
+    fn hint_timeout_seconds() {
+        let r = analyze_error("request timed out after 30s");
+        assert_eq!(
+            r.hint.as_deref(),
+            Some("Retry or increase timeout (currently 30s)")
+        );
+    }
+
+    #[test]
+    fn hint_none_without_details() {
+        assert!(analyze_error("connection refused").hint.is_none());
+        assert!(analyze_error("stack overflow").hint.is_none());
+        assert!(analyze_error("something odd").hint.is_none());
+    }
+
+    #[test]
+    fn all_template_regexes_compile() {
+        let count: usize = RULES.iter().map(|r| r.templates.len()).sum();
+        assert_eq!(template_regexes().len(), count);
+    }
+
+    #[test]
+    fn severity_inferred_by_rules() {
+        assert_eq!(
+            analyze_error("request timed out").severity,
+            Severity::Warning
+        );
+        assert_eq!(analyze_error("permission denied").severity, Severity::Error);
+        assert_eq!(analyze_error("out of memory").severity, Severity::Fatal);
+        assert_eq!(analyze_error("something odd").severity, Severity::Error);
+        assert!(Severity::Fatal > Severity::Warning);
+    }
+
+    fn custom(pattern: &str) -> CustomRule {
+        CustomRule {
+            pattern: pattern.to_string(),
+            category: None,
+            severity: None,
+            retryable: None,
+            suggestion: None,
+        }
+    }
+
+    #[test]
+    fn custom_rule_overrides_severity_only() {
+        let rules = [CustomRule {
+            severity: Some(Severity::Info),
+            ..custom("Permission Denied: /tmp")
+        }];
+        let r = analyze_error_with("permission denied: /tmp/cache", &rules);
+        assert_eq!(r.severity, Severity::Info);
+        assert_eq!(r.category, ErrorCategory::Permission);
+        assert!(!r.retryable);
+    }
+
+    #[test]
+    fn custom_rule_classifies_unknown() {
+        let rules = [
+            custom("no match here"),
+            CustomRule {
+                category: Some(ErrorCategory::Network),
+                retryable: Some(true),
+                suggestion: Some("Restart the VPN".to_string()),
+                ..custom("vpn")
+            },
+        ];
+        let r = analyze_error_with("VPN tunnel dropped", &rules);
+        assert_eq!(r.category, ErrorCategory::Network);
+        assert!(r.retryable);
+        assert_eq!(r.hint.as_deref(), Some("Restart the VPN"));
+    }
+
     #[test]
     fn resolve_matches_network() {
         let r = analyze_error("could not resolve host");
@@ -0,0 +1,348 @@
+//! Optional LLM-backed error triage
+//!
+//! Error messages the rule engine cannot classify are sent to a user-configured
+//! command (e.g. `claude -p`) on a background thread. Replies are cached per
+//! message so each one is asked about at most once; when the command fails or
+//! is unavailable the rule-based analysis is kept as is. Lookups wait in a
+//! bounded queue and only `concurrency` commands run at once, so a burst of
+//! new errors does not start a burst of LLM calls.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::analysis::rules::ErrorCategory;
+use crate::process;
+
+/// Triage settings loaded from the `[triage]` config section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TriageConfig {
+    /// Program and arguments; the prompt is written to its stdin. Empty disables triage.
+    pub command: Vec<String>,
+    /// Kill the command if it has not answered within this many seconds
+    pub timeout_secs: u64,
+    /// Commands running at once
+    pub concurrency: usize,
+    /// Lookups waiting for a free slot; messages past this are asked about
+    /// once the queue has room again
+    pub max_queued: usize,
+}
+
+impl Default for TriageConfig {
+    fn default() -> Self {
+        Self {
+            command: Vec::new(),
+            timeout_secs: 30,
+            concurrency: 2,
+            max_queued: 32,
+        }
+    }
+}
+
+/// Classification returned by the triage command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriageResult {
+    pub category: ErrorCategory,
+    pub retryable: bool,
+    pub suggestion: String,
+}
+
+/// Build the prompt sent to the triage command
+pub fn build_prompt(message: &str) -> String {
+    format!(
+        "Classify this error from an AI coding agent.\n\
+         Reply with exactly three lines:\n\
+         Category: one of Permission, Network, RateLimit, Type, Runtime, Unknown\n\
+         Retryable: yes or no\n\
+         Suggestion: one short actionable sentence\n\n\
+         Error: {message}\n"
+    )
+}
+
+fn parse_category(value: &str) -> Option<ErrorCategory> {
+    let category = match value.trim().to_lowercase().as_str() {
+        "permission" => ErrorCategory::Permission,
+        "network" => ErrorCategory::Network,
+        "ratelimit" | "rate limit" => ErrorCategory::RateLimit,
+        "type" => ErrorCategory::Type,
+        "runtime" => ErrorCategory::Runtime,
+        "unknown" => ErrorCategory::Unknown,
+        _ => return None,
+    };
+    Some(category)
+}
+
+/// Parse the `Category:` / `Retryable:` / `Suggestion:` reply.
+/// Returns None unless both a known category and a suggestion are present.
+pub fn parse_response(text: &str) -> Option<TriageResult> {
+    let mut category = None;
+    let mut retryable = false;
+    let mut suggestion = None;
+
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim().to_lowercase().as_str() {
+            "category" => category = parse_category(value),
+            "retryable" => retryable = matches!(value.to_lowercase().as_str(), "yes" | "true"),
+            "suggestion" if !value.is_empty() => suggestion = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    Some(TriageResult {
+        category: category?,
+        retryable,
+        suggestion: suggestion?,
+    })
+}
+
+/// Run the triage command for one message (blocking)
+pub fn run_command(config: &TriageConfig, message: &str) -> Result<TriageResult, String> {
+    let (program, args) = config
+        .command
+        .split_first()
+        .ok_or("triage command is empty")?;
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    let stdout = process::run(
+        &mut cmd,
+        build_prompt(message).into_bytes(),
+        Duration::from_secs(config.timeout_secs),
+    )?;
+    parse_response(&stdout).ok_or_else(|| "unrecognized triage reply".to_string())
+}
+
+/// Background triage runner with a per-message cache
+pub struct Triage {
+    config: TriageConfig,
+    /// Finished lookups; None records a failure so it is not retried
+    cache: HashMap<String, Option<TriageResult>>,
+    /// Queued or running lookups
+    pending: HashSet<String>,
+    queue: VecDeque<String>,
+    running: usize,
+    tx: mpsc::Sender<(String, Option<TriageResult>)>,
+    rx: mpsc::Receiver<(String, Option<TriageResult>)>,
+}
+
+impl Triage {
+    /// Create a runner, or None when no command is configured
+    pub fn new(config: TriageConfig) -> Option<Self> {
+        if config.command.is_empty() {
+            return None;
+        }
+        let (tx, rx) = mpsc::channel();
+        Some(Self {
+            config,
+            cache: HashMap::new(),
+            pending: HashSet::new(),
+            queue: VecDeque::new(),
+            running: 0,
+            tx,
+            rx,
+        })
+    }
+
+    /// Cached result for a message, if triage has answered
+    pub fn lookup(&self, message: &str) -> Option<&TriageResult> {
+        self.cache.get(message).and_then(Option::as_ref)
+    }
+
+    /// Queue a lookup unless the message is cached, in flight or the queue
+    /// is full
+    pub fn request(&mut self, message: &str) {
+        if self.cache.contains_key(message)
+            || self.pending.contains(message)
+            || self.queue.len() >= self.config.max_queued
+        {
+            return;
+        }
+        self.pending.insert(message.to_string());
+        self.queue.push_back(message.to_string());
+    }
+
+    /// Take queued lookups while fewer than `concurrency` are running. Each
+    /// one counts as running until [`Triage::poll`] collects its answer, so
+    /// hand every message to [`Triage::spawn`].
+    pub fn start(&mut self) -> Vec<String> {
+        let mut started = Vec::new();
+        while self.running < self.config.concurrency.max(1) {
+            let Some(message) = self.queue.pop_front() else {
+                break;
+            };
+            self.running += 1;
+            started.push(message);
+        }
+        started
+    }
+
+    /// Run the triage command for a started lookup on a background thread
+    pub fn spawn(&self, message: String) {
+        let config = self.config.clone();
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let result = run_command(&config, &message).ok();
+            let _ = tx.send((message, result));
+        });
+    }
+
+    /// Collect finished lookups. Returns true if any new result arrived.
+    pub fn poll(&mut self) -> bool {
+        let mut updated = false;
+        while let Ok((message, result)) = self.rx.try_recv() {
+            self.pending.remove(&message);
+            self.running -= 1;
+            updated |= result.is_some();
+            self.cache.insert(message, result);
+        }
+        updated
+    }
+
+    /// Number of lookups queued or running
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Number of lookups running
+    pub fn running_count(&self) -> usize {
+        self.running
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn sh(script: &str) -> TriageConfig {
+        TriageConfig {
+            command: vec!["sh".into(), "-c".into(), script.into()],
+            timeout_secs: 5,
+            ..TriageConfig::default()
+        }
+    }
+
+    #[test]
+    fn prompt_contains_message() {
+        let prompt = build_prompt("segfault in libfoo");
+        assert!(prompt.contains("Error: segfault in libfoo"));
+        assert!(prompt.contains("Category:"));
+    }
+
+    #[test]
+    fn parse_full_response() {
+        let r =
+            parse_response("Category: Network\nRetryable: yes\nSuggestion: Check the VPN").unwrap();
+        assert_eq!(r.category, ErrorCategory::Network);
+        assert!(r.retryable);
+        assert_eq!(r.suggestion, "Check the VPN");
+    }
+
+    #[test]
+    fn parse_tolerates_case_and_spacing() {
+        let r =
+            parse_response("some preamble\ncategory:  permission\nsuggestion: Back off\n").unwrap();
+        assert_eq!(r.category, ErrorCategory::Permission);
+        assert!(!r.retryable);
+    }
+
+    #[test]
+    fn parse_rejects_incomplete() {
+        assert!(parse_response("Category: Network").is_none());
+        assert!(parse_response("Category: Cosmic\nSuggestion: x").is_none());
+        assert!(parse_response("").is_none());
+    }
+
+    #[test]
+    fn disabled_without_command() {
+        assert!(Triage::new(TriageConfig::default()).is_none());
+    }
+
+    #[test]
+    fn run_command_reads_reply() {
+        let config = sh(
+            "cat >/dev/null; printf 'Category: Runtime\\nRetryable: no\\nSuggestion: Rebuild\\n'",
+        );
+        let r = run_command(&config, "weird").unwrap();
+        assert_eq!(r.category, ErrorCategory::Runtime);
+        assert_eq!(r.suggestion, "Rebuild");
+    }
+
+    #[test]
+    fn run_command_failure_is_error() {
+        assert!(run_command(&sh("exit 1"), "x").is_err());
+        assert!(run_command(&sh("echo nonsense"), "x").is_err());
+    }
+
+    #[test]
+    fn run_command_times_out() {
+        let config = TriageConfig {
+            timeout_secs: 0,
+            ..sh("sleep 5")
+        };
+        assert!(run_command(&config, "x").unwrap_err().contains("timed out"));
+    }
+
+    #[test]
+    fn background_lookup_is_cached() {
+        let mut triage = Triage::new(sh(
+            "cat >/dev/null; printf 'Category: Type\\nSuggestion: Fix it\\n'",
+        ))
+        .unwrap();
+        triage.request("odd error");
+        triage.request("odd error");
+        assert_eq!(triage.pending_count(), 1);
+        for message in triage.start() {
+            triage.spawn(message);
+        }
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !triage.poll() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(triage.pending_count(), 0);
+        assert_eq!(
+            triage.lookup("odd error").map(|r| &r.category),
+            Some(&ErrorCategory::Type)
+        );
+    }
+
+    #[test]
+    fn bursts_are_queued_and_capped() {
+        let mut triage = Triage::new(TriageConfig {
+            concurrency: 1,
+            max_queued: 2,
+            ..sh("cat >/dev/null; printf 'Category: Type\\nSuggestion: Fix it\\n'")
+        })
+        .unwrap();
+        for i in 0..5 {
+            triage.request(&format!("error {i}"));
+        }
+        // Two waiting, the rest left for a later request
+        assert_eq!(triage.pending_count(), 2);
+        assert_eq!(triage.running_count(), 0);
+        let started = triage.start();
+        assert_eq!(started, ["error 0"]);
+        started.into_iter().for_each(|m| triage.spawn(m));
+        assert_eq!(triage.running_count(), 1);
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while triage.pending_count() > 0 && Instant::now() < deadline {
+            triage.poll();
+            triage.start().into_iter().for_each(|m| triage.spawn(m));
+            assert!(triage.running_count() <= 1);
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!((0..2).all(|i| triage.lookup(&format!("error {i}")).is_some()));
+        assert!(triage.lookup("error 2").is_none());
+        triage.request("error 2");
+        assert_eq!(triage.pending_count(), 1);
+    }
+}
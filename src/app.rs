@@ -1,14 +1,59 @@
 //! App state management and event loop
 
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
 
-use crate::data::state::DashboardState;
-use crate::data::tasks_parser::TaskStatus;
-use crate::data::tasks_writer;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::analysis::retry::RetryDecision;
+use crate::analysis::rules::ErrorCategory;
+use crate::analysis::triage::Triage;
+use crate::config::Config;
+use crate::data::audit::AuditEntry;
+use crate::data::clock::Clock;
+use crate::data::context;
+use crate::data::control;
+use crate::data::cost::{self, CostGroup, CostRow, CostSort};
+use crate::data::deps::{self, Suggestion};
+use crate::data::loader::{self, Loaded, Loader};
+use crate::data::roster::{self, Coverage, RosterEntry};
+use crate::data::state::{AgentStatus, DashboardState, ErrorRecord, Timeline};
+use crate::data::task_id;
+use crate::data::tasks_doc::{MoveDirection, TaskSection, TasksDoc};
+use crate::data::tasks_parser::{ParseError, ParseOutcome, TaskStatus};
+use crate::data::tasks_source;
+use crate::data::templates::{self, TaskTemplate};
 use crate::data::watcher::FileChange;
+use crate::event::InputKey;
+use crate::github::Github;
+use crate::jira::Jira;
+use crate::logging;
+use crate::otel::Exporter;
+use crate::perf::FrameStats;
+use crate::prompt::{self, PromptOutput};
+use crate::scripts::{self, Scripts, TriggerRun};
+use crate::ui::error_modal;
 use crate::ui::gantt::GanttState;
-use crate::ui::layout::FocusedPane;
+use crate::ui::input_modal::TextInput;
+use crate::ui::layout::{FocusedPane, LayoutPreset};
+use crate::ui::panel::{Panel, PanelRegistry};
+use crate::ui::timefmt::humanize;
+use crate::ui::toast::Toast;
+use crate::update::Effect;
+
+/// Steps the scrubber takes across the whole span of hook events
+const SCRUB_STEPS: i32 = 50;
+
+/// File changes within a second that count as a burst of hook events
+const BURST_CHANGES: usize = 5;
+
+/// How long a removed tasks file has to reappear before it counts as gone
+const TASKS_GRACE: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Debug log lines kept for the log viewer
+const LOG_TAIL_LINES: usize = 200;
 
 /// Information about a retry target task
 #[derive(Debug, Clone)]
@@ -18,6 +63,91 @@ pub struct RetryTarget {
     pub retryable: bool,
 }
 
+/// The error open in the error modal, with its folding state
+#[derive(Debug, Clone)]
+pub struct ErrorView {
+    pub error: ErrorRecord,
+    /// Frame under the cursor, counted among the message's frames
+    pub cursor: usize,
+    /// Unfolded frames
+    pub expanded: BTreeSet<usize>,
+}
+
+/// The event the ops layout's event log is anchored at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventAnchor {
+    /// Position in `DashboardState::events`
+    pub index: usize,
+    /// Layout to return to when the log goes back to live
+    pub return_layout: LayoutPreset,
+}
+
+/// The task an open modal is about to write, as it was on disk when the
+/// modal opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskWatch {
+    pub task_id: String,
+    snapshot: Option<String>,
+    /// Set once TASKS.md changes that task while the modal is open
+    pub conflict: Option<String>,
+}
+
+/// What a text input modal will do on submit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputPurpose {
+    /// Append a timestamped note to a task body
+    Note { task_id: String },
+    /// Move a task to another phase
+    MoveToPhase { task_id: String },
+    /// Add a task to the end of a phase, shaped by a config template
+    AddTask {
+        phase_id: String,
+        /// ID the task will get, as derived when the modal opened
+        task_id: String,
+        template: Option<TaskTemplate>,
+    },
+}
+
+/// An open text input modal
+#[derive(Debug, Clone)]
+pub struct PendingInput {
+    pub purpose: InputPurpose,
+    pub text: TextInput,
+}
+
+impl PendingInput {
+    pub fn title(&self) -> &'static str {
+        match self.purpose {
+            InputPurpose::Note { .. } => "Add note",
+            InputPurpose::MoveToPhase { .. } => "Move to phase",
+            InputPurpose::AddTask { .. } => "Add task",
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        match &self.purpose {
+            InputPurpose::Note { task_id } => format!("Note for {task_id}"),
+            InputPurpose::MoveToPhase { task_id } => {
+                format!("Move {task_id} to phase (e.g. P2 or 2)")
+            }
+            InputPurpose::AddTask {
+                phase_id,
+                task_id,
+                template: None,
+            } => format!("Name of {task_id} in {phase_id} (Tab for a template)"),
+            InputPurpose::AddTask {
+                task_id,
+                template: Some(template),
+                ..
+            } => format!(
+                "{} {task_id}: {}",
+                template.label,
+                template.task_name("...")
+            ),
+        }
+    }
+}
+
 /// Main application state
 pub struct App {
     pub running: bool,
@@ -27,24 +157,166 @@ pub struct App {
     pub show_help: bool,
     pub show_retry_modal: bool,
     pub retry_target: Option<RetryTarget>,
+    /// Error shown in full by the error modal (e)
+    pub error_view: Option<ErrorView>,
+    /// Error event the event log is showing in context (g)
+    pub event_anchor: Option<EventAnchor>,
     pub tasks_path: Option<PathBuf>,
     pub start_time: Instant,
     pub selected_agent: usize,
+    pub config: Config,
+    /// Background LLM triage (None unless configured)
+    pub triage: Option<Triage>,
+    /// Worker reading and parsing changed files (None reads them in place)
+    pub loader: Option<Loader>,
+    /// Timestamp of the last Fatal error the user dismissed
+    pub dismissed_fatal: Option<DateTime<Utc>>,
+    /// Open text input modal, if any
+    pub input: Option<PendingInput>,
+    /// Task targeted by the open retry or input modal
+    pub modal_watch: Option<TaskWatch>,
+    /// Plugin panels registered by the embedding crate
+    pub panels: PanelRegistry,
+    /// Configured script hooks (None unless configured)
+    pub scripts: Option<Scripts>,
+    /// Jira client for tasks with a `jira` field (None unless configured)
+    pub jira: Option<Jira>,
+    /// Pull request lister (None unless enabled)
+    pub github: Option<Github>,
+    /// OpenTelemetry exporter (None without an endpoint)
+    pub otel: Option<Exporter>,
+    /// Spend is over the `[cost]` budget and the alert went out
+    pub budget_alerted: bool,
+    /// End of the rate-limit cooldown holding back an automatic retry
+    pub retry_wait: Option<DateTime<Utc>>,
+    /// Whether the costs view (`$`) is open
+    pub show_costs: bool,
+    /// What the costs view groups spend by
+    pub cost_group: CostGroup,
+    /// Column the costs view is sorted by
+    pub cost_sort: CostSort,
+    /// First row shown in the costs view
+    pub cost_scroll: usize,
+    /// Where stop requests are written (None disables stopping agents)
+    pub control_dir: Option<PathBuf>,
+    /// Audit file for board-initiated writes (None disables the audit log)
+    pub audit_path: Option<PathBuf>,
+    /// Agent the open stop modal targets
+    pub stop_target: Option<String>,
+    /// Agents whose stop file has not been picked up by their hook yet
+    pub stop_requested: HashSet<String>,
+    /// Expand the selected agent's tool output in the Agents panel
+    pub show_agent_output: bool,
+    /// Past states shown while time-travelling (None when live)
+    pub scrub: Option<Timeline>,
+    /// Last key press or file change, for the idle tick rate
+    pub last_activity: Instant,
+    /// File changes seen in the last second, for the burst tick rate
+    recent_changes: VecDeque<Instant>,
+    /// Whether the F12 debug overlay is shown
+    pub show_debug: bool,
+    /// Draw times and event rate for the debug overlay
+    pub stats: FrameStats,
+    /// Whether the "Next up" dispatch overlay is open
+    pub show_next_up: bool,
+    /// Render without colors or decorative glyphs (`--plain`)
+    pub plain: bool,
+    /// Links in task bodies are clickable (OSC 8)
+    pub hyperlinks: bool,
+    /// Debug log file (`--log-file`), None when logging is off
+    pub log_path: Option<PathBuf>,
+    /// Whether the log viewer overlay is open
+    pub show_log: bool,
+    /// Tail of the debug log, refreshed while the viewer is open
+    pub log_lines: Vec<String>,
+    /// Short-lived notice in the corner, e.g. after a config reload
+    pub toast: Option<Toast>,
+    /// When the tasks file was seen removed, while it may still come back
+    pub tasks_missing_since: Option<Instant>,
+    /// The tasks file stayed away past the grace period
+    pub tasks_gone: bool,
+    /// Where copied context bundles are also saved (None only copies them)
+    pub context_dir: Option<PathBuf>,
+    /// Text waiting for the terminal to put on the clipboard
+    pub clipboard: Option<String>,
+    /// Effects planned while reloading, e.g. triage lookups and Jira
+    /// transitions, handed out by the next [`App::update`] or
+    /// [`App::perform_all`]
+    pub outbox: Vec<Effect>,
+    /// Where prompts go with `[prompt] output = "file"`
+    pub prompt_dir: Option<PathBuf>,
+    /// Task shown full-screen by `watch --task` (None shows the board)
+    pub monitor: Option<String>,
+    /// Current pane arrangement (`o` toggles)
+    pub layout: LayoutPreset,
+    /// Whether the roster coverage view (`w`) is open
+    pub show_roster: bool,
+    /// Config and `AGENTS.md` roster, re-read when the roster or "Next up"
+    /// opens
+    pub roster: Vec<RosterEntry>,
 }
 
 impl App {
     pub fn new() -> Self {
+        let dashboard = DashboardState::default();
+        let now = dashboard.clock.instant();
         Self {
             running: true,
-            dashboard: DashboardState::default(),
+            dashboard,
             gantt_state: GanttState::default(),
             focused: FocusedPane::TaskList,
             show_help: false,
             show_retry_modal: false,
             retry_target: None,
+            error_view: None,
+            event_anchor: None,
             tasks_path: None,
-            start_time: Instant::now(),
+            start_time: now,
             selected_agent: 0,
+            config: Config::default(),
+            triage: None,
+            loader: None,
+            dismissed_fatal: None,
+            input: None,
+            modal_watch: None,
+            panels: PanelRegistry::default(),
+            scripts: None,
+            jira: None,
+            github: None,
+            otel: None,
+            budget_alerted: false,
+            retry_wait: None,
+            show_costs: false,
+            cost_group: CostGroup::default(),
+            cost_sort: CostSort::default(),
+            cost_scroll: 0,
+            control_dir: None,
+            audit_path: None,
+            stop_target: None,
+            stop_requested: HashSet::new(),
+            show_agent_output: false,
+            scrub: None,
+            last_activity: now,
+            recent_changes: VecDeque::new(),
+            show_debug: false,
+            stats: FrameStats::default(),
+            show_next_up: false,
+            plain: false,
+            hyperlinks: false,
+            log_path: None,
+            show_log: false,
+            log_lines: Vec::new(),
+            toast: None,
+            tasks_missing_since: None,
+            tasks_gone: false,
+            context_dir: None,
+            clipboard: None,
+            outbox: Vec::new(),
+            prompt_dir: None,
+            monitor: None,
+            layout: LayoutPreset::default(),
+            show_roster: false,
+            roster: Vec::new(),
         }
     }
 
@@ -53,21 +325,230 @@ impl App {
         self
     }
 
+    /// Tell time by `clock` instead of the system clock; the uptime and the
+    /// idle timer count from its current instant. Install it after
+    /// [`App::with_dashboard`], which brings the clock of the new state.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.start_time = clock.instant();
+        self.last_activity = self.start_time;
+        self.dashboard.clock = clock;
+        self
+    }
+
+    /// Read and parse changed files on a worker thread; finished loads are
+    /// applied as [`Msg::Loaded`](crate::update::Msg::Loaded)
+    pub fn with_loader(mut self, loader: Loader) -> Self {
+        self.loader = Some(loader);
+        self
+    }
+
     pub fn with_tasks_path(mut self, path: PathBuf) -> Self {
         self.tasks_path = Some(path);
         self
     }
 
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.apply_config(config);
+        self
+    }
+
+    /// Install a config, at startup or when the file changes. Line numbers
+    /// only follow the config when its setting changed, so a `#` toggle
+    /// survives unrelated edits.
+    pub fn apply_config(&mut self, config: Config) {
+        self.triage = Triage::new(config.triage.clone());
+        self.scripts = Scripts::new(config.scripts.clone());
+        self.jira = Jira::new(config.jira.clone());
+        self.github = Github::new(config.github.clone());
+        self.otel = Exporter::new(config.otel.clone());
+        if let Some(scripts) = self.scripts.as_mut() {
+            scripts.refresh_segment(&self.dashboard);
+        }
+        if let Some(path) = &self.tasks_path {
+            self.dashboard.tasks_source = tasks_source::for_path(path, config.tasks.format);
+        }
+        self.dashboard.apply_config(&config);
+        if config.display.line_numbers != self.config.display.line_numbers {
+            self.gantt_state.line_numbers = config.display.line_numbers;
+        }
+        if config.display.layout != self.config.display.layout {
+            self.set_layout(config.display.layout);
+        }
+        self.config = config;
+    }
+
+    /// Re-read the config file; a broken file keeps the running config
+    fn reload_config(&mut self, path: &std::path::Path) {
+        match Config::load(path) {
+            Ok(config) => {
+                self.apply_config(config);
+                if let Some(content) = self
+                    .tasks_path
+                    .clone()
+                    .and_then(|p| self.dashboard.read_tasks_file(&p).ok())
+                {
+                    let _ = self.reload_dashboard(&content);
+                }
+                self.gantt_state.sync_with(&self.dashboard);
+                tracing::info!("config reloaded from {}", path.display());
+                self.toast = Some(Toast::info("Config reloaded"));
+            }
+            Err(e) => {
+                tracing::warn!("config not reloaded: {e:#}");
+                self.toast = Some(Toast::error(format!("Config not reloaded: {e:#}")));
+            }
+        }
+    }
+
+    /// Render for screen readers and scraped logs (see [`crate::ui::plain`])
+    pub fn with_plain(mut self, plain: bool) -> Self {
+        self.plain = plain;
+        self
+    }
+
+    /// Make body links clickable (see [`crate::ui::markdown::hyperlink`])
+    pub fn with_hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = hyperlinks;
+        self
+    }
+
+    /// Debug log shown by the log viewer (`L`)
+    pub fn with_log_path(mut self, path: PathBuf) -> Self {
+        self.log_path = Some(path);
+        self
+    }
+
+    /// Enable stop requests, written as files under `dir`
+    pub fn with_control_dir(mut self, dir: PathBuf) -> Self {
+        self.control_dir = Some(dir);
+        self
+    }
+
+    /// Record every write the board makes to `path` (see [`crate::data::audit`])
+    pub fn with_audit_path(mut self, path: PathBuf) -> Self {
+        self.audit_path = Some(path);
+        self
+    }
+
+    /// Save copied context bundles as `<task-id>.md` under `dir`
+    pub fn with_context_dir(mut self, dir: PathBuf) -> Self {
+        self.context_dir = Some(dir);
+        self
+    }
+
+    /// Write file-output prompts under `dir`
+    pub fn with_prompt_dir(mut self, dir: PathBuf) -> Self {
+        self.prompt_dir = Some(dir);
+        self
+    }
+
+    /// Show only `task_id`, full-screen; task actions (retry, approve, notes,
+    /// ...) act on it
+    pub fn with_monitor(mut self, task_id: String) -> Self {
+        self.monitor = Some(task_id);
+        self
+    }
+
+    /// Register a plugin panel; it joins the layout, Tab order, and help
+    pub fn with_panel(mut self, panel: Box<dyn Panel>) -> Self {
+        self.panels.register(panel);
+        self
+    }
+
     pub fn quit(&mut self) {
         self.running = false;
     }
 
+    /// Plain-text recap printed after the TUI closes: progress, failed
+    /// tasks with the latest suggestion for each, run time, the tracked spend
+    /// against the `[cost]` budget, and the statusbar script's output
+    pub fn exit_summary(&self) -> String {
+        let state = &self.dashboard;
+        let mut out = format!(
+            "simple-claude-board: {}/{} tasks done ({:.0}%)",
+            state.completed_tasks,
+            state.total_tasks,
+            state.overall_progress * 100.0
+        );
+        let failed: Vec<_> = state
+            .phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .filter(|t| t.status == TaskStatus::Failed)
+            .collect();
+        if !failed.is_empty() {
+            out.push_str(&format!(", {} failed", failed.len()));
+        }
+        out.push('\n');
+
+        let open = self
+            .dashboard
+            .clock
+            .instant()
+            .saturating_duration_since(self.start_time);
+        let open = Duration::from_std(open).unwrap_or_else(|_| Duration::zero());
+        match state.run_start() {
+            Some(start) => out.push_str(&format!(
+                "  Run time: {} (board open {})\n",
+                humanize(state.now() - start),
+                humanize(open)
+            )),
+            None => out.push_str(&format!("  Board open: {}\n", humanize(open))),
+        }
+
+        let (limits, agents) = state.rate_limit_totals();
+        if limits.hits > 0 {
+            out.push_str(&format!(
+                "  Rate limited: {}x across {agents} agent{}, {} of cooldown\n",
+                limits.hits,
+                if agents == 1 { "" } else { "s" },
+                humanize(limits.waited)
+            ));
+        }
+
+        // Shown like the status bar badge: once there is usage or a budget
+        let cost = &self.config.cost;
+        let spend = state.total_spend();
+        if !spend.is_empty() || cost.budget_usd.is_some() {
+            let spent = spend.usd(cost);
+            let budget = match cost.budget_usd {
+                Some(budget) if spent > budget => {
+                    format!(" of {} budget, over", cost::format_usd(budget))
+                }
+                Some(budget) => format!(" of {} budget", cost::format_usd(budget)),
+                None => String::new(),
+            };
+            out.push_str(&format!(
+                "  Spend: {}{budget} ({} tokens)\n",
+                cost::format_usd(spent),
+                cost::format_tokens(spend.tokens())
+            ));
+        }
+
+        if !failed.is_empty() {
+            out.push_str("  Failed:\n");
+            for task in failed {
+                let suggestion = state
+                    .recent_errors
+                    .iter()
+                    .rfind(|e| e.task_id == task.id)
+                    .map(|e| format!(" - {}", e.display_suggestion()))
+                    .unwrap_or_default();
+                out.push_str(&format!("    {} {}{suggestion}\n", task.id, task.name));
+            }
+        }
+        if let Some(segment) = self.scripts.as_ref().and_then(|s| s.segment()) {
+            out.push_str(&format!("  {segment}\n"));
+        }
+        out
+    }
+
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
     }
 
     pub fn toggle_focus(&mut self) {
-        self.focused = self.focused.toggle();
+        self.focused = self.focused.next(self.panels.len());
     }
 
     pub fn move_down(&mut self) {
@@ -78,9 +559,12 @@ impl App {
         self.gantt_state.select_prev();
     }
 
-    /// Toggle collapse on the currently selected phase header
+    /// Toggle collapse on the currently selected phase header, or on the
+    /// selected agent's tool output when the Agents pane is focused
     pub fn toggle_collapse(&mut self) {
-        if let Some(pi) = self.gantt_state.selected_phase_index(&self.dashboard) {
+        if self.focused == FocusedPane::Agents {
+            self.show_agent_output = !self.show_agent_output;
+        } else if let Some(pi) = self.gantt_state.selected_phase_index(&self.dashboard) {
             self.gantt_state.toggle_collapse(pi);
         }
     }
@@ -90,16 +574,31 @@ impl App {
         self.gantt_state.toggle_view();
     }
 
+    /// Switch between the standard layout and the ops layout
+    pub fn toggle_layout(&mut self) {
+        self.set_layout(match self.layout {
+            LayoutPreset::Standard => LayoutPreset::Ops,
+            LayoutPreset::Ops => LayoutPreset::Standard,
+        });
+    }
+
+    /// The ops layout only shows the task list, so focus returns to it
+    fn set_layout(&mut self, layout: LayoutPreset) {
+        self.layout = layout;
+        if layout == LayoutPreset::Ops {
+            self.focused = FocusedPane::TaskList;
+        }
+    }
+
     /// Get sorted agent IDs (consistent order for UI)
     pub fn sorted_agent_ids(&self) -> Vec<String> {
-        let mut ids: Vec<String> = self.dashboard.agents.keys().cloned().collect();
-        ids.sort();
-        ids
+        let (agents, _) = self.dashboard.panel_agents();
+        agents.iter().map(|a| a.agent_id.clone()).collect()
     }
 
     /// Move agent selection down
     pub fn agent_move_down(&mut self) {
-        let count = self.dashboard.agents.len();
+        let count = self.dashboard.panel_agents().0.len();
         if count > 0 && self.selected_agent < count - 1 {
             self.selected_agent += 1;
         }
@@ -112,6 +611,490 @@ impl App {
         }
     }
 
+    /// Jump between a task and its agent: from the Agents pane to the
+    /// selected agent's current task, from the task list to the selected
+    /// task's worker
+    pub fn follow_link(&mut self) {
+        match self.focused {
+            FocusedPane::Agents => {
+                let (agents, _) = self.dashboard.panel_agents();
+                let Some(task_id) = agents
+                    .get(self.selected_agent)
+                    .and_then(|a| a.current_task.clone())
+                else {
+                    return;
+                };
+                if self.dashboard.task_position(&task_id).is_some() {
+                    self.select_task_by_id(&task_id);
+                    self.focused = FocusedPane::TaskList;
+                }
+            }
+            FocusedPane::TaskList => {
+                let Some(task) = self
+                    .selected_task()
+                    .and_then(|(pi, ti)| self.dashboard.phases.get(pi)?.tasks.get(ti))
+                else {
+                    return;
+                };
+                let Some(worker) = self.dashboard.worker_for(task) else {
+                    return;
+                };
+                // Same match as the panel highlight, preferring an exact ID
+                let ids = self.sorted_agent_ids();
+                let found = ids
+                    .iter()
+                    .position(|id| id == worker)
+                    .or_else(|| ids.iter().position(|id| id.contains(worker)));
+                if let Some(index) = found {
+                    self.selected_agent = index;
+                    self.focused = FocusedPane::Agents;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The state to render: the scrubbed past state, or the live one
+    pub fn view(&self) -> &DashboardState {
+        self.scrub.as_ref().map_or(&self.dashboard, Timeline::state)
+    }
+
+    /// Move the scrub point by `steps` (negative is back in time). Scrubbing
+    /// starts from the latest event; moving past it returns to live.
+    pub fn scrub_by(&mut self, steps: i32) {
+        let mut timeline = self
+            .scrub
+            .take()
+            .unwrap_or_else(|| Timeline::new(&self.dashboard));
+        let Some((first, last)) = timeline.span() else {
+            return;
+        };
+        let step = ((last - first) / SCRUB_STEPS).max(Duration::seconds(1));
+        let from = timeline.scrub_at().unwrap_or(last);
+        let at = from + step * steps;
+        if at <= last {
+            timeline.seek(at.max(first));
+            self.scrub = Some(timeline);
+        }
+    }
+
+    /// Return to the live state
+    pub fn stop_scrub(&mut self) {
+        self.scrub = None;
+    }
+
+    /// Note input or a file change; file changes also feed burst detection
+    pub(crate) fn record_activity(&mut self, file_change: bool) {
+        self.last_activity = self.dashboard.clock.instant();
+        if file_change {
+            self.recent_changes.push_back(self.last_activity);
+        }
+    }
+
+    /// How long the main loop waits for input before the next tick: faster
+    /// during bursts of hook events and while files are loading, slower once no agent is running and
+    /// nothing has changed for a while
+    pub fn tick_interval(&mut self, now: Instant) -> std::time::Duration {
+        let tick = self.config.tick;
+        let second = std::time::Duration::from_secs(1);
+        while self
+            .recent_changes
+            .front()
+            .is_some_and(|&at| now.saturating_duration_since(at) > second)
+        {
+            self.recent_changes.pop_front();
+        }
+        let ms = if self.recent_changes.len() >= BURST_CHANGES || self.stats.loading > 0 {
+            tick.burst_ms
+        } else if now.saturating_duration_since(self.last_activity)
+            >= std::time::Duration::from_secs(tick.idle_after_secs)
+            && !self
+                .dashboard
+                .agents
+                .values()
+                .any(|a| a.status == AgentStatus::Running)
+        {
+            tick.idle_ms
+        } else {
+            tick.interval_ms
+        };
+        std::time::Duration::from_millis(ms.max(1))
+    }
+
+    /// Rebuild the scrubbed state after the live one changed
+    pub(crate) fn refresh_scrub(&mut self) {
+        if let Some(at) = self.scrub.as_ref().and_then(Timeline::scrub_at) {
+            let mut timeline = Timeline::new(&self.dashboard);
+            timeline.seek(at);
+            self.scrub = Some(timeline);
+        }
+    }
+
+    /// Open the stop confirmation for the selected agent
+    pub fn open_stop_modal(&mut self) {
+        if self.focused != FocusedPane::Agents || self.control_dir.is_none() {
+            return;
+        }
+        self.stop_target = self.sorted_agent_ids().get(self.selected_agent).cloned();
+    }
+
+    /// Close the stop modal, asking for the stop
+    pub(crate) fn plan_stop(&mut self) -> Vec<Effect> {
+        let Some(agent_id) = self.stop_target.take() else {
+            return Vec::new();
+        };
+        let Some(agent) = self.dashboard.agents.get(&agent_id) else {
+            return Vec::new();
+        };
+        // An agent is stopped by stopping every hook agent behind it, in
+        // each of its sessions
+        if !agent.members.is_empty() {
+            return agent
+                .members
+                .values()
+                .map(|member| Effect::StopAgent {
+                    agent_id: member.agent_id.clone(),
+                    session_id: Some(member.session_id.clone()),
+                })
+                .collect();
+        }
+        vec![Effect::StopAgent {
+            agent_id,
+            session_id: agent.session_id.clone(),
+        }]
+    }
+
+    pub fn cancel_stop(&mut self) {
+        self.stop_target = None;
+    }
+
+    /// Write the stop request for an agent
+    /// Write a stop request; `true` if it was delivered
+    pub(crate) fn stop_agent(&mut self, agent_id: &str, session_id: Option<&str>) -> bool {
+        let Some(dir) = self.control_dir.as_deref() else {
+            return false;
+        };
+        let pid_dir = self.config.control.pid_dir.as_deref();
+        if control::request_stop(dir, pid_dir, agent_id, session_id).is_err() {
+            return false;
+        }
+        self.stop_requested.insert(agent_id.to_string());
+        true
+    }
+
+    /// Forget stop requests whose file the agent's hook has consumed
+    fn poll_stop_requests(&mut self) {
+        if let Some(dir) = self.control_dir.as_deref() {
+            self.stop_requested
+                .retain(|id| control::stop_file(dir, id).exists());
+        }
+    }
+
+    /// Ready tasks in dispatch order, for the "Next up" overlay. Readiness
+    /// is judged on the whole plan; only tasks the filter shows are offered.
+    pub fn next_up(&self) -> Vec<Suggestion> {
+        deps::next_up(&self.dashboard)
+            .into_iter()
+            .filter(|s| self.dashboard.is_shown(&s.task_id))
+            .collect()
+    }
+
+    /// Agent to hand a suggested task to: the plan's `@agent`, else the
+    /// least busy roster agent with room for it, else the most recently
+    /// active idle agent
+    pub fn dispatch_agent(&self, suggestion: &Suggestion) -> Option<String> {
+        suggestion.agent.clone().or_else(|| {
+            if !self.roster.is_empty() {
+                return self.coverage().pick().map(str::to_string);
+            }
+            let (agents, _) = self.dashboard.panel_agents();
+            agents
+                .iter()
+                .find(|a| a.status == AgentStatus::Idle)
+                .map(|a| a.agent_id.clone())
+        })
+    }
+
+    /// Warn once the tasks file has been missing for longer than an
+    /// editor's save takes. The last loaded plan stays on screen.
+    pub(crate) fn check_tasks_missing(&mut self, now: Instant) {
+        let Some(since) = self.tasks_missing_since else {
+            return;
+        };
+        if now.saturating_duration_since(since) < TASKS_GRACE {
+            return;
+        }
+        self.tasks_missing_since = None;
+        let Some(path) = self.tasks_path.clone() else {
+            return;
+        };
+        match self.dashboard.read_tasks_file(&path) {
+            // Back, but the modify event was missed
+            Ok(content) => {
+                let _ = self.reload_dashboard(&content);
+                self.gantt_state.sync_with(&self.dashboard);
+            }
+            Err(_) => {
+                tracing::warn!("tasks file {} is gone", path.display());
+                self.tasks_gone = true;
+                self.toast = Some(Toast::error(format!(
+                    "{} was removed; showing the last loaded plan",
+                    path.display()
+                )));
+            }
+        }
+    }
+
+    /// Copy the selected task's context bundle (see [`crate::data::context`])
+    /// and save it under `context_dir`
+    pub fn copy_context(&mut self) {
+        let Some((pi, ti)) = self.selected_task() else {
+            return;
+        };
+        let task_id = self.dashboard.phases[pi].tasks[ti].id.clone();
+        let Some(bundle) = context::bundle(&self.dashboard, &task_id) else {
+            return;
+        };
+        let saved = self.context_dir.as_ref().map(|dir| {
+            let path = dir.join(format!("{}.md", control::file_stem(&task_id)));
+            std::fs::create_dir_all(dir)
+                .and_then(|_| std::fs::write(&path, &bundle))
+                .map(|_| path)
+        });
+        self.toast = Some(match saved {
+            Some(Ok(path)) => Toast::info(format!(
+                "Context for {task_id} copied and saved to {}",
+                path.display()
+            )),
+            Some(Err(e)) => {
+                tracing::warn!("cannot save context for {task_id}: {e}");
+                Toast::error(format!("Context for {task_id} copied but not saved: {e}"))
+            }
+            None => Toast::info(format!("Context for {task_id} copied")),
+        });
+        self.clipboard = Some(bundle);
+    }
+
+    /// Copy the tasks of expanded phases as a markdown status table
+    pub fn copy_table(&mut self) {
+        let table = self.gantt_state.markdown_table(self.view());
+        let tasks = table.lines().count().saturating_sub(2);
+        self.toast = Some(Toast::info(format!(
+            "Copied {tasks} task{} as a markdown table",
+            if tasks == 1 { "" } else { "s" }
+        )));
+        self.clipboard = Some(table);
+    }
+
+    /// Render the `[prompt]` template for the selected failed task and hand
+    /// it to the configured output; a command gets it through
+    /// [`Effect::PipePrompt`]
+    pub fn plan_prompt(&mut self) -> Vec<Effect> {
+        let Some((pi, ti)) = self.selected_task() else {
+            return Vec::new();
+        };
+        let task = &self.dashboard.phases[pi].tasks[ti];
+        let task_id = task.id.clone();
+        if !matches!(task.status, TaskStatus::Failed | TaskStatus::Blocked) {
+            self.toast = Some(Toast::error(format!("{task_id} has not failed")));
+            return Vec::new();
+        }
+        let config = &self.config.prompt;
+        let Some(text) = prompt::render(&config.template, &self.dashboard, &task_id) else {
+            return Vec::new();
+        };
+        match config.output {
+            PromptOutput::Clipboard => {
+                self.clipboard = Some(text);
+                self.toast = Some(Toast::info(format!("Prompt for {task_id} copied")));
+            }
+            PromptOutput::File => {
+                let Some(dir) = config.dir.as_ref().or(self.prompt_dir.as_ref()) else {
+                    return Vec::new();
+                };
+                self.toast = Some(match prompt::save(dir, &task_id, &text) {
+                    Ok(path) => Toast::info(format!("Prompt saved to {}", path.display())),
+                    Err(e) => Toast::error(format!("Prompt not saved: {e}")),
+                });
+            }
+            PromptOutput::Command => {
+                return vec![Effect::PipePrompt {
+                    task_id,
+                    command: config.command.clone(),
+                    text,
+                }];
+            }
+        }
+        Vec::new()
+    }
+
+    /// Pipe a rendered prompt to its command and report how it went
+    pub(crate) fn pipe_prompt(&mut self, task_id: &str, command: &[String], text: &str) {
+        self.toast = Some(match prompt::pipe(command, text) {
+            Ok(()) => {
+                tracing::info!(task = %task_id, "prompt sent to {command:?}");
+                Toast::info(format!("Prompt for {task_id} sent to {}", command[0]))
+            }
+            Err(e) => Toast::error(format!("Prompt not sent: {e}")),
+        });
+    }
+
+    /// Alert once when spend passes the run budget: an error toast and the
+    /// webhook, if any, through [`Effect::BudgetAlert`]. Re-arms when spend
+    /// is back under it, e.g. after the budget was raised.
+    pub fn check_budget(&mut self) -> Vec<Effect> {
+        let config = &self.config.cost;
+        let Some(budget) = config.budget_usd else {
+            return Vec::new();
+        };
+        let spent = self.dashboard.total_spend().usd(config);
+        let over = spent > budget;
+        let mut effects = Vec::new();
+        if over && !self.budget_alerted {
+            let message = format!(
+                "Run budget exceeded: {} of {}",
+                cost::format_usd(spent),
+                cost::format_usd(budget)
+            );
+            tracing::warn!("{message}");
+            self.toast = Some(Toast::error(message));
+            if let Some(url) = &config.webhook {
+                effects.push(Effect::BudgetAlert {
+                    url: url.clone(),
+                    spent,
+                    budget,
+                });
+            }
+        }
+        self.budget_alerted = over;
+        effects
+    }
+
+    /// Send the run so far to the OpenTelemetry collector
+    pub fn export_trace(&mut self) {
+        let Some(otel) = self.otel.as_ref() else {
+            self.toast = Some(Toast::error("No [otel] endpoint configured"));
+            return;
+        };
+        self.toast = Some(match otel.export(&self.dashboard) {
+            Ok(spans) => Toast::info(format!("Exporting {spans} spans")),
+            Err(e) => Toast::error(format!("Trace not exported: {e}")),
+        });
+    }
+
+    /// Report finished trace exports
+    pub fn poll_otel(&mut self) {
+        let Some(otel) = self.otel.as_ref() else {
+            return;
+        };
+        for result in otel.poll() {
+            self.toast = Some(match result {
+                Ok(spans) => {
+                    tracing::info!("otel: exported {spans} spans to {}", otel.config().endpoint);
+                    Toast::info(format!("Exported {spans} spans"))
+                }
+                Err(e) => {
+                    tracing::warn!("otel: {e}");
+                    Toast::error(format!("Trace not exported: {e}"))
+                }
+            });
+        }
+    }
+
+    /// Open or close the log viewer
+    pub fn toggle_log(&mut self) {
+        self.show_log = !self.show_log;
+        self.refresh_log();
+    }
+
+    /// Re-read the tail of the debug log while the viewer is open
+    pub(crate) fn refresh_log(&mut self) {
+        if !self.show_log {
+            return;
+        }
+        self.log_lines = self
+            .log_path
+            .as_deref()
+            .and_then(|path| logging::tail(path, LOG_TAIL_LINES).ok())
+            .unwrap_or_default();
+    }
+
+    /// Open or close the costs view
+    pub fn toggle_costs(&mut self) {
+        self.show_costs = !self.show_costs;
+        self.cost_scroll = 0;
+    }
+
+    /// Rows of the costs view, spent ones only
+    pub fn cost_rows(&self) -> Vec<CostRow> {
+        cost::breakdown(
+            self.view(),
+            self.cost_group,
+            self.cost_sort,
+            &self.config.cost,
+        )
+        .into_iter()
+        .filter(|row| row.tokens() > 0 || row.cost_usd > 0.0)
+        .collect()
+    }
+
+    /// Group the costs view by the next of phase, task and agent
+    pub fn cycle_cost_group(&mut self) {
+        self.cost_group = self.cost_group.next();
+        self.cost_scroll = 0;
+    }
+
+    /// Scroll the costs view by `rows`, keeping the last row in view
+    pub fn scroll_costs(&mut self, rows: i32) {
+        let last = self.cost_rows().len().saturating_sub(1);
+        self.cost_scroll = self
+            .cost_scroll
+            .saturating_add_signed(rows as isize)
+            .min(last);
+    }
+
+    pub fn toggle_next_up(&mut self) {
+        self.show_next_up = !self.show_next_up;
+        if self.show_next_up {
+            self.refresh_roster();
+        }
+    }
+
+    /// Open or close the roster coverage view
+    pub fn toggle_roster(&mut self) {
+        self.show_roster = !self.show_roster;
+        if self.show_roster {
+            self.refresh_roster();
+        }
+    }
+
+    /// Re-read the roster from the config and `AGENTS.md`
+    pub fn refresh_roster(&mut self) {
+        self.roster = roster::load(&self.config.agents.roster, self.tasks_path.as_deref());
+    }
+
+    /// How the roster covers the open tasks of the shown plan
+    pub fn coverage(&self) -> Coverage {
+        roster::coverage(self.view(), &self.roster)
+    }
+
+    /// Close the "Next up" overlay, asking to start its top task
+    pub(crate) fn plan_dispatch(&mut self) -> Vec<Effect> {
+        self.show_next_up = false;
+        if self.writable_tasks_path().is_none() {
+            return Vec::new();
+        }
+        let Some(top) = self.next_up().into_iter().next() else {
+            return Vec::new();
+        };
+        // Only write an agent the plan does not name already
+        let agent = self.dispatch_agent(&top).filter(|_| top.agent.is_none());
+        vec![Effect::Dispatch {
+            task_id: top.task_id,
+            agent,
+        }]
+    }
+
     /// Open the retry modal for the currently selected task
     pub fn open_retry_modal(&mut self) {
         if let Some((pi, ti)) = self.selected_task() {
@@ -134,87 +1117,876 @@ impl App {
                 retryable,
             });
             self.show_retry_modal = true;
+            let task_id = task.id.clone();
+            self.watch_task(&task_id);
         }
     }
 
-    /// Confirm retry: update TASKS.md status to InProgress
-    pub fn confirm_retry(&mut self) {
-        if let Some(ref target) = self.retry_target.clone() {
-            if target.retryable {
-                if let Some(ref path) = self.tasks_path {
-                    if let Ok(true) =
-                        tasks_writer::update_task_status(path, &target.task_id, "InProgress")
-                    {
-                        // Reload the tasks to reflect the change
-                        if let Ok(content) = std::fs::read_to_string(path) {
-                            let _ = self.dashboard.reload_tasks(&content);
-                        }
+    /// Open the latest error of the selected task in the error modal
+    pub fn open_error_modal(&mut self) {
+        let Some((pi, ti)) = self.selected_task() else {
+            return;
+        };
+        let task_id = &self.dashboard.phases[pi].tasks[ti].id;
+        match self
+            .dashboard
+            .recent_errors
+            .iter()
+            .rfind(|e| e.task_id == *task_id)
+        {
+            Some(error) => {
+                self.error_view = Some(ErrorView {
+                    error: error.clone(),
+                    cursor: 0,
+                    expanded: BTreeSet::new(),
+                })
+            }
+            None => self.toast = Some(Toast::info(format!("No error recorded for {task_id}"))),
+        }
+    }
+
+    /// Show the events leading up to an error in the event log: the error
+    /// open in the error modal, or the selected task's latest one
+    pub fn jump_to_error_context(&mut self) {
+        let index = match self.error_view.take() {
+            Some(view) => view.error.event_index,
+            None => {
+                let Some((pi, ti)) = self.selected_task() else {
+                    return;
+                };
+                let task_id = &self.dashboard.phases[pi].tasks[ti].id;
+                match self
+                    .dashboard
+                    .recent_errors
+                    .iter()
+                    .rfind(|e| e.task_id == *task_id)
+                {
+                    Some(error) => error.event_index,
+                    None => {
+                        self.toast = Some(Toast::info(format!("No error recorded for {task_id}")));
+                        return;
                     }
                 }
             }
+        };
+        let return_layout = self.event_anchor.map_or(self.layout, |a| a.return_layout);
+        self.event_anchor = Some(EventAnchor {
+            index,
+            return_layout,
+        });
+        self.set_layout(LayoutPreset::Ops);
+    }
+
+    /// Step the anchored event log by `events`
+    pub fn step_event_anchor(&mut self, events: i32) {
+        let last = self.dashboard.events.len().saturating_sub(1);
+        if let Some(anchor) = self.event_anchor.as_mut() {
+            anchor.index = anchor
+                .index
+                .saturating_add_signed(events as isize)
+                .min(last);
         }
-        self.show_retry_modal = false;
-        self.retry_target = None;
     }
 
-    /// Cancel the retry modal
-    pub fn cancel_retry(&mut self) {
-        self.show_retry_modal = false;
-        self.retry_target = None;
+    /// Let the event log follow the newest events again
+    pub fn leave_event_anchor(&mut self) {
+        if let Some(anchor) = self.event_anchor.take() {
+            self.set_layout(anchor.return_layout);
+        }
     }
 
-    /// Get the currently selected task as (phase_idx, task_idx)
-    pub fn selected_task(&self) -> Option<(usize, usize)> {
-        self.gantt_state.selected_task(&self.dashboard)
+    /// Close the error modal
+    pub fn close_error_modal(&mut self) {
+        self.error_view = None;
     }
 
-    /// Handle a file change event from the watcher
-    pub fn handle_file_change(&mut self, change: &FileChange) {
-        match change {
-            FileChange::TasksModified(path) => {
-                if let Ok(content) = std::fs::read_to_string(path) {
-                    let _ = self.dashboard.reload_tasks(&content);
-                }
-            }
-            FileChange::HookEventCreated(path) | FileChange::HookEventModified(path) => {
-                if let Ok(content) = std::fs::read_to_string(path) {
-                    let result = crate::data::hook_parser::parse_hook_events(&content);
-                    self.dashboard.reload_from_events(&result.events);
-                }
+    /// Move the error modal's cursor by `frames`, staying on a frame
+    pub fn move_error_cursor(&mut self, frames: i32) {
+        if let Some(view) = self.error_view.as_mut() {
+            let last = error_modal::frame_count(&view.error.message).saturating_sub(1);
+            view.cursor = view.cursor.saturating_add_signed(frames as isize).min(last);
+        }
+    }
+
+    /// Fold or unfold the frame under the error modal's cursor
+    pub fn toggle_error_frame(&mut self) {
+        if let Some(view) = self.error_view.as_mut() {
+            if !view.expanded.remove(&view.cursor) {
+                view.expanded.insert(view.cursor);
             }
         }
     }
-}
 
-impl Default for App {
-    fn default() -> Self {
-        Self::new()
+    /// The tasks file, if its backend accepts write-back
+    pub(crate) fn writable_tasks_path(&self) -> Option<PathBuf> {
+        self.tasks_path
+            .clone()
+            .filter(|_| self.dashboard.tasks_source.writable())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Current section of a task in TASKS.md on disk
+    pub(crate) fn disk_task(&self, task_id: &str) -> Option<TaskSection> {
+        let content = std::fs::read_to_string(self.tasks_path.as_ref()?).ok()?;
+        TasksDoc::parse(&content).task(task_id).cloned()
+    }
 
-    #[test]
-    fn app_default() {
-        let app = App::new();
-        assert!(app.running);
-        assert!(!app.show_help);
-        assert_eq!(app.focused, FocusedPane::TaskList);
+    /// Remember what the task looks like on disk while a modal is open
+    fn watch_task(&mut self, task_id: &str) {
+        self.modal_watch = Some(TaskWatch {
+            task_id: task_id.to_string(),
+            snapshot: self.disk_task(task_id).map(|t| t.lines.concat()),
+            conflict: None,
+        });
     }
 
-    #[test]
-    fn app_quit() {
-        let mut app = App::new();
-        app.quit();
-        assert!(!app.running);
+    /// Flag the open modal if its task changed on disk since it opened
+    fn check_modal_conflict(&mut self) {
+        if !self.show_retry_modal && self.input.is_none() {
+            self.modal_watch = None;
+        }
+        let Some(watch) = self.modal_watch.as_ref() else {
+            return;
+        };
+        let current = self.disk_task(&watch.task_id).map(|t| t.lines.concat());
+        if current == watch.snapshot {
+            return;
+        }
+        let id = watch.task_id.clone();
+        let message = match current {
+            Some(_) => format!("{id} changed on disk since this opened"),
+            None => format!("{id} was removed from TASKS.md"),
+        };
+        if let Some(watch) = self.modal_watch.as_mut() {
+            watch.conflict = Some(message);
+        }
     }
 
-    #[test]
-    fn app_toggle_help() {
-        let mut app = App::new();
-        assert!(!app.show_help);
+    /// Conflict warning for the open modal, if any
+    pub fn modal_conflict(&self) -> Option<&str> {
+        self.modal_watch.as_ref()?.conflict.as_deref()
+    }
+
+    /// Confirm retry: update TASKS.md status to InProgress.
+    ///
+    /// The task is looked up again on disk first; if an external edit removed
+    /// it or it is no longer Failed/Blocked, nothing is written.
+    pub fn confirm_retry(&mut self) {
+        let effects = self.plan_retry();
+        self.perform_all(effects);
+    }
+
+    /// Close the retry modal, asking for the retry if the target allows one
+    pub(crate) fn plan_retry(&mut self) -> Vec<Effect> {
+        self.modal_watch = None;
+        self.show_retry_modal = false;
+        match self.retry_target.take() {
+            Some(target) if target.retryable && self.writable_tasks_path().is_some() => {
+                vec![Effect::Retry {
+                    task_id: target.task_id,
+                }]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The writes the configured retry policy asks for on Failed tasks.
+    ///
+    /// Tasks with budget left get a [`Effect::Retry`] back to InProgress; tasks
+    /// that used up their budget trip the circuit breaker with an
+    /// [`Effect::Block`]. A task whose latest error is a rate limit waits until
+    /// its cooldown has passed.
+    pub fn plan_retry_policy(&mut self) -> Vec<Effect> {
+        if self.writable_tasks_path().is_none() {
+            return Vec::new();
+        }
+        let now = self.dashboard.clock.now();
+        self.retry_wait = None;
+        // Latest error per task, matched to its task through the index
+        let mut seen = HashSet::new();
+        let mut failed: Vec<_> = self
+            .dashboard
+            .recent_errors
+            .iter()
+            .rev()
+            .filter(|e| seen.insert(e.task_id.clone()))
+            .filter(|e| match e.cooldown_until {
+                Some(until) if until > now => {
+                    self.retry_wait = Some(self.retry_wait.map_or(until, |w| w.min(until)));
+                    false
+                }
+                _ => true,
+            })
+            .filter_map(|e| {
+                let pos = self.dashboard.task_position(&e.task_id)?;
+                let task = self.dashboard.task(&e.task_id)?;
+                (task.status == TaskStatus::Failed)
+                    .then(|| (pos, task.id.clone(), e.category.clone()))
+            })
+            .collect();
+        failed.sort_by_key(|(pos, ..)| *pos);
+
+        failed
+            .into_iter()
+            .filter_map(|(_, task_id, category)| {
+                match self
+                    .config
+                    .retry
+                    .decide(&category, self.retry_attempts(&task_id))
+                {
+                    RetryDecision::Retry(_) => Some(Effect::Retry { task_id }),
+                    RetryDecision::Exhausted => Some(Effect::Block { task_id }),
+                    RetryDecision::Manual => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Attempts used by a task so far. Attempts written to TASKS.md survive
+    /// restarts; the tracker may be fresh.
+    pub(crate) fn retry_attempts(&self, task_id: &str) -> u32 {
+        let recorded = self.dashboard.task(task_id).map_or(0, |t| t.attempts);
+        self.dashboard.retries.attempts(task_id).max(recorded)
+    }
+
+    /// Re-plan the retry policy once a rate-limit cooldown that held back a
+    /// retry has passed
+    pub fn check_retry_wait(&mut self, now: DateTime<Utc>) -> Vec<Effect> {
+        if self.retry_wait.is_some_and(|until| until <= now) {
+            return self.plan_retry_policy();
+        }
+        Vec::new()
+    }
+
+    /// Overlay cached triage results onto unclassified errors and queue
+    /// lookups for the rest, started through [`Effect::Triage`]. Errors keep
+    /// their rule-based analysis until (and unless) the triage command
+    /// answers.
+    pub fn apply_triage(&mut self) {
+        let Some(triage) = self.triage.as_mut() else {
+            return;
+        };
+        for err in &mut self.dashboard.recent_errors {
+            if err.category != ErrorCategory::Unknown {
+                continue;
+            }
+            match triage.lookup(&err.message) {
+                Some(result) => {
+                    err.category = result.category.clone();
+                    err.retryable = result.retryable;
+                    err.hint = Some(result.suggestion.clone());
+                }
+                None => triage.request(&err.message),
+            }
+        }
+        self.start_triage();
+    }
+
+    /// Put queued triage lookups that have a free slot in the outbox
+    fn start_triage(&mut self) {
+        if let Some(triage) = self.triage.as_mut() {
+            let started = triage.start().into_iter();
+            self.outbox
+                .extend(started.map(|message| Effect::Triage { message }));
+        }
+    }
+
+    /// Pick up finished triage lookups, script output and consumed stop
+    /// requests (called on every tick)
+    pub fn poll_triage(&mut self) {
+        self.poll_stop_requests();
+        if self.triage.as_mut().is_some_and(Triage::poll) {
+            self.apply_triage();
+        } else {
+            self.start_triage();
+        }
+        if let Some(scripts) = self.scripts.as_mut() {
+            scripts.poll();
+            for run in scripts.take_runs() {
+                self.record_trigger(run);
+            }
+        }
+    }
+
+    /// Log a finished transition trigger and add it to the audit log
+    fn record_trigger(&self, run: TriggerRun) {
+        let output = match run.result {
+            Ok(out) => {
+                tracing::info!(task = %run.task_id, command = %run.command, "trigger ran: {}", out.trim_end());
+                out
+            }
+            Err(e) => {
+                tracing::warn!(task = %run.task_id, command = %run.command, "trigger failed: {e}");
+                e
+            }
+        };
+        self.audit(
+            AuditEntry::new("trigger", &run.task_id, None, None).with_run(&run.command, output),
+        );
+    }
+
+    /// Plan fetches of linked Jira issues that are due and report finished
+    /// transitions
+    pub fn poll_jira(&mut self, now: Instant) -> Vec<Effect> {
+        let Some(jira) = self.jira.as_mut() else {
+            return Vec::new();
+        };
+        let keys = self
+            .dashboard
+            .phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .filter_map(|t| t.jira.as_deref());
+        let effects = jira
+            .plan_refresh(keys, now)
+            .into_iter()
+            .map(|key| Effect::JiraRefresh { key })
+            .collect();
+        for (key, result) in jira.poll() {
+            self.toast = Some(match result {
+                Ok(()) => {
+                    tracing::info!("jira: moved {key} to {}", self.config.jira.done_transition);
+                    Toast::info(format!(
+                        "Jira {key} moved to {}",
+                        self.config.jira.done_transition
+                    ))
+                }
+                Err(e) => {
+                    tracing::warn!("jira: {e}");
+                    Toast::error(format!("Jira {key} not moved: {e}"))
+                }
+            });
+        }
+        effects
+    }
+
+    /// Take a finished pull request listing and plan the next one when due
+    pub fn poll_github(&mut self, now: Instant) -> Vec<Effect> {
+        let Some(github) = self.github.as_mut() else {
+            return Vec::new();
+        };
+        github.poll();
+        github
+            .plan_refresh(&self.dashboard.phases, now)
+            .map(|numbers| Effect::GithubRefresh { numbers })
+            .into_iter()
+            .collect()
+    }
+
+    /// Put the transition of the Jira issue linked to a task that just
+    /// completed in the outbox
+    pub(crate) fn complete_jira(&mut self, task_id: &str) {
+        let key = self.dashboard.task(task_id).and_then(|t| t.jira.clone());
+        if let (Some(jira), Some(key)) = (self.jira.as_ref(), key) {
+            if jira.completes(&key) {
+                self.outbox.push(Effect::JiraComplete { key });
+            }
+        }
+    }
+
+    fn completed_task_ids(&self) -> HashSet<String> {
+        self.dashboard
+            .phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .filter(|t| t.status == TaskStatus::Completed)
+            .map(|t| t.id.clone())
+            .collect()
+    }
+
+    /// With approval enabled, hold newly completed tasks for review and drop
+    /// review holds on tasks that are no longer Completed.
+    fn track_completions(&mut self, before: &HashSet<String>) {
+        let now = self.completed_task_ids();
+        self.dashboard
+            .awaiting_review
+            .retain(|id| now.contains(id.as_str()));
+        if self.config.approval.enabled {
+            for id in now.difference(before) {
+                let id = self.dashboard.task_ids.intern(id);
+                self.dashboard.awaiting_review.insert(id);
+            }
+        } else {
+            // With approval on, the issue moves once the task is approved
+            for id in now.difference(before) {
+                self.complete_jira(id);
+            }
+        }
+    }
+
+    /// Selected task ID, if it is awaiting review
+    fn selected_review_task(&self) -> Option<String> {
+        let (pi, ti) = self.selected_task()?;
+        let id = &self.dashboard.phases[pi].tasks[ti].id;
+        self.dashboard.is_awaiting_review(id).then(|| id.clone())
+    }
+
+    /// Approve the selected task: confirm Completed in TASKS.md and release the hold
+    pub fn approve_selected(&mut self) {
+        let effects = self.plan_approve();
+        self.perform_all(effects);
+    }
+
+    pub(crate) fn plan_approve(&self) -> Vec<Effect> {
+        match (self.selected_review_task(), self.writable_tasks_path()) {
+            (Some(task_id), Some(_)) => vec![Effect::Approve { task_id }],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Reject the selected task: back to InProgress with a review note
+    pub fn bounce_selected(&mut self) {
+        let effects = self.plan_bounce();
+        self.perform_all(effects);
+    }
+
+    pub(crate) fn plan_bounce(&self) -> Vec<Effect> {
+        match (self.selected_review_task(), self.writable_tasks_path()) {
+            (Some(task_id), Some(_)) => vec![Effect::Bounce {
+                task_id,
+                note: format!(
+                    "review {}: bounced back to InProgress",
+                    self.dashboard
+                        .timezone
+                        .format(self.dashboard.clock.now(), "%H:%M")
+                ),
+            }],
+            _ => Vec::new(),
+        }
+    }
+
+    pub(crate) fn selected_task_id(&self) -> Option<String> {
+        let (pi, ti) = self.selected_task()?;
+        Some(self.dashboard.phases[pi].tasks[ti].id.clone())
+    }
+
+    /// Open the note input for the selected task
+    pub fn open_note_input(&mut self) {
+        if let Some(task_id) = self.selected_task_id() {
+            self.watch_task(&task_id);
+            self.input = Some(PendingInput {
+                purpose: InputPurpose::Note { task_id },
+                text: TextInput::default(),
+            });
+        }
+    }
+
+    /// Open the phase input for moving the selected task
+    pub fn open_move_phase_input(&mut self) {
+        if let Some(task_id) = self.selected_task_id() {
+            self.watch_task(&task_id);
+            self.input = Some(PendingInput {
+                purpose: InputPurpose::MoveToPhase { task_id },
+                text: TextInput::default(),
+            });
+        }
+    }
+
+    /// Open the quick-add input for the phase of the selected row
+    pub fn open_add_task_input(&mut self) {
+        let phase = self
+            .selected_task()
+            .map(|(pi, _)| pi)
+            .or_else(|| self.gantt_state.selected_phase_index(&self.dashboard));
+        if let Some(pi) = phase.filter(|&pi| pi < self.dashboard.phases.len()) {
+            self.input = Some(PendingInput {
+                purpose: InputPurpose::AddTask {
+                    phase_id: self.dashboard.phases[pi].id.clone(),
+                    task_id: task_id::next_in_phase(
+                        &self.dashboard.phases,
+                        pi,
+                        &self.dashboard.planned_tasks,
+                    ),
+                    template: None,
+                },
+                text: TextInput::default(),
+            });
+        }
+    }
+
+    /// Point the selection at a task (expanding its phase if collapsed);
+    /// a task the filter hides leaves the selection alone
+    pub fn select_task_by_id(&mut self, task_id: &str) {
+        let Some((pi, ti)) = self.dashboard.task_position(task_id) else {
+            return;
+        };
+        self.gantt_state.collapsed.remove(&pi);
+        if let Some(row) = self.gantt_state.row_of_task(&self.dashboard, pi, ti) {
+            self.gantt_state.selected = row;
+        }
+    }
+
+    /// Reload the tasks, keeping the selection and collapsed phases on the
+    /// same IDs even when rows were inserted or removed above them
+    pub(crate) fn reload_dashboard(&mut self, content: &str) -> Result<(), ParseError> {
+        // A background parse of an older read must not land on top of this
+        if let Some(loader) = self.loader.as_mut() {
+            loader.supersede_tasks();
+        }
+        let parsed = self.dashboard.tasks_source.parse(content);
+        self.apply_plan(parsed)
+    }
+
+    /// Take a parsed plan, keeping the selection and logging status changes
+    fn apply_plan(&mut self, parsed: Result<ParseOutcome, ParseError>) -> Result<(), ParseError> {
+        let anchor = self.gantt_state.anchor(&self.dashboard);
+        let before = tracing::enabled!(tracing::Level::INFO).then(|| self.task_statuses());
+        if let Err(e) = self.dashboard.apply_tasks(parsed) {
+            tracing::warn!("tasks reload failed: {e}");
+            return Err(e);
+        }
+        for warning in &self.dashboard.parse_warnings {
+            tracing::warn!("tasks parse warning: {warning}");
+        }
+        if let Some(before) = before {
+            for (id, status) in self.task_statuses() {
+                match before.get(&id) {
+                    Some(old) if *old != status => {
+                        tracing::info!(task = %id, from = %old, to = %status, "status change")
+                    }
+                    None => tracing::info!(task = %id, status = %status, "task added"),
+                    _ => {}
+                }
+            }
+        }
+        self.gantt_state.restore(&anchor, &self.dashboard);
+        Ok(())
+    }
+
+    /// Status label of every task, for logging transitions
+    fn task_statuses(&self) -> HashMap<String, String> {
+        self.dashboard
+            .phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .map(|t| (t.id.clone(), t.status.label().to_string()))
+            .collect()
+    }
+
+    pub(crate) fn reload_tasks_and_select(&mut self, path: &std::path::Path, task_id: &str) {
+        if let Ok(content) = self.dashboard.read_tasks_file(path) {
+            if self.reload_dashboard(&content).is_ok() {
+                self.select_task_by_id(task_id);
+            }
+        }
+    }
+
+    /// Move the selected task up or down within its phase (Alt-k / Alt-j)
+    pub fn move_selected_task(&mut self, direction: MoveDirection) {
+        let effects = self.plan_move(direction);
+        self.perform_all(effects);
+    }
+
+    pub(crate) fn plan_move(&self, direction: MoveDirection) -> Vec<Effect> {
+        match (self.selected_task_id(), self.writable_tasks_path()) {
+            (Some(task_id), Some(_)) => vec![Effect::MoveTask { task_id, direction }],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Apply a key to the open input modal
+    pub fn handle_input(&mut self, key: InputKey) {
+        let effects = self.plan_input(key);
+        self.perform_all(effects);
+    }
+
+    pub(crate) fn plan_input(&mut self, key: InputKey) -> Vec<Effect> {
+        let Some(input) = self.input.as_mut() else {
+            return Vec::new();
+        };
+        match key {
+            InputKey::Char(c) => input.text.insert(c),
+            InputKey::Backspace => input.text.backspace(),
+            InputKey::Left => input.text.left(),
+            InputKey::Right => input.text.right(),
+            InputKey::Tab => {
+                if let InputPurpose::AddTask { template, .. } = &mut input.purpose {
+                    *template = templates::next(&self.config.templates, template.as_ref()).cloned();
+                }
+            }
+            InputKey::Submit => return self.submit_input(),
+            InputKey::Cancel => {
+                self.input = None;
+                self.modal_watch = None;
+            }
+            InputKey::None => {}
+        }
+        Vec::new()
+    }
+
+    /// Close the input modal and ask for what it was opened to do
+    fn submit_input(&mut self) -> Vec<Effect> {
+        let Some(input) = self.input.take() else {
+            return Vec::new();
+        };
+        // Writes resolve the task by ID against the file as it is then
+        self.modal_watch = None;
+        let text = input.text.value.trim();
+        if text.is_empty() || self.writable_tasks_path().is_none() {
+            return Vec::new();
+        }
+        let effect = match input.purpose {
+            InputPurpose::Note { task_id } => Effect::AppendNote {
+                task_id,
+                note: format!(
+                    "note {}: {text}",
+                    self.dashboard
+                        .timezone
+                        .format(self.dashboard.clock.now(), "%H:%M")
+                ),
+            },
+            InputPurpose::MoveToPhase { task_id } => Effect::MoveToPhase {
+                task_id,
+                phase_id: if text.starts_with(['P', 'p']) {
+                    text.to_string()
+                } else {
+                    format!("P{text}")
+                },
+            },
+            InputPurpose::AddTask {
+                phase_id,
+                task_id,
+                template,
+            } => {
+                // The plan may have gained tasks since the modal opened
+                let task_id = self
+                    .dashboard
+                    .phases
+                    .iter()
+                    .position(|p| p.id == phase_id)
+                    .map_or(task_id, |pi| {
+                        task_id::next_in_phase(
+                            &self.dashboard.phases,
+                            pi,
+                            &self.dashboard.planned_tasks,
+                        )
+                    });
+                let name = match &template {
+                    Some(template) => template.task_name(text),
+                    None => text.to_string(),
+                };
+                Effect::AddTask {
+                    phase_id,
+                    task_id,
+                    name,
+                    body: template.map(|t| t.body()).unwrap_or_default(),
+                }
+            }
+        };
+        vec![effect]
+    }
+
+    /// Fatal error to show in the banner (newer than the last dismissal)
+    pub fn fatal_banner(&self) -> Option<&ErrorRecord> {
+        self.dashboard
+            .latest_fatal()
+            .filter(|e| self.dismissed_fatal.map_or(true, |t| e.timestamp > t))
+    }
+
+    /// Hide the banner until a newer Fatal error arrives
+    pub fn dismiss_banner(&mut self) {
+        if let Some(err) = self.dashboard.latest_fatal() {
+            self.dismissed_fatal = Some(err.timestamp);
+        }
+    }
+
+    /// Cancel the retry modal
+    pub fn cancel_retry(&mut self) {
+        self.show_retry_modal = false;
+        self.retry_target = None;
+        self.modal_watch = None;
+    }
+
+    /// Get the currently selected task as (phase_idx, task_idx)
+    pub fn selected_task(&self) -> Option<(usize, usize)> {
+        if let Some(task_id) = &self.monitor {
+            return self.dashboard.task_position(task_id);
+        }
+        self.gantt_state.selected_task(&self.dashboard)
+    }
+
+    /// Handle a file change event from the watcher. With a
+    /// [`loader`](Self::loader) the changed file is only queued for reading.
+    pub fn handle_file_change(&mut self, change: &FileChange) {
+        if loader::reads_file(change) {
+            if let Some(loader) = self.loader.as_mut() {
+                loader.request(change.clone(), &self.dashboard);
+                self.stats.loading = loader.pending();
+            } else if let Some(loaded) = loader::load(
+                change,
+                self.dashboard.tasks_source.as_ref(),
+                self.dashboard.hook_read_limit,
+            ) {
+                self.apply_loaded(loaded);
+            }
+            return;
+        }
+        match change {
+            FileChange::TasksRemoved(path) => {
+                // Editors often remove or rename before writing the new file;
+                // the tick decides once the grace period is over
+                if !path.exists() && self.tasks_missing_since.is_none() {
+                    self.tasks_missing_since = Some(self.dashboard.clock.instant());
+                }
+            }
+            FileChange::ConfigModified(path) => self.reload_config(path),
+            FileChange::TasksModified(_)
+            | FileChange::HookEventCreated(_)
+            | FileChange::HookEventModified(_) => {}
+        }
+        self.apply_triage();
+    }
+
+    /// Apply a file read for a change
+    pub fn apply_loaded(&mut self, loaded: Loaded) {
+        match loaded {
+            Loaded::Tasks {
+                path,
+                lossy_lines,
+                parsed,
+            } => {
+                self.dashboard.record_lossy_lines(&path, lossy_lines);
+                self.tasks_missing_since = None;
+                self.tasks_gone = false;
+                // The first load is the baseline, not a batch of completions
+                let initial = self.dashboard.phases.is_empty();
+                let before = self.completed_task_ids();
+                let before_phases = self
+                    .scripts
+                    .is_some()
+                    .then(|| self.dashboard.phases.clone());
+                if self.apply_plan(parsed).is_ok() && !initial {
+                    self.track_completions(&before);
+                    if let (Some(scripts), Some(before_phases)) = (&self.scripts, before_phases) {
+                        scripts.fire(&scripts::detect(
+                            &before_phases,
+                            &self.dashboard.phases,
+                            &self.dashboard.custom_statuses,
+                        ));
+                        scripts.fire(&scripts::transitions(
+                            &before_phases,
+                            &self.dashboard.phases,
+                        ));
+                    }
+                }
+                if let Some(scripts) = self.scripts.as_mut() {
+                    scripts.refresh_segment(&self.dashboard);
+                }
+                self.gantt_state.sync_with(&self.dashboard);
+                self.check_modal_conflict();
+            }
+            Loaded::Hooks { path, result } => {
+                if self
+                    .dashboard
+                    .record_truncation(&path, result.skipped_bytes)
+                {
+                    self.toast = Some(Toast::error(format!(
+                        "{} is too large: showing only its newest events",
+                        path.display()
+                    )));
+                }
+                self.dashboard.record_lossy_lines(&path, result.lossy_lines);
+                for e in &result.errors {
+                    tracing::warn!(
+                        "{}:{}: bad hook event: {}",
+                        path.display(),
+                        e.line_number,
+                        e.error
+                    );
+                }
+                let count = result.events.len();
+                self.dashboard.reload_hook_file(&path, result.events);
+                self.stats
+                    .record_events(count, self.dashboard.clock.instant());
+            }
+        }
+        self.apply_triage();
+    }
+
+    /// Finished background loads, oldest first
+    pub fn take_loads(&mut self) -> Vec<Loaded> {
+        let Some(loader) = self.loader.as_mut() else {
+            return Vec::new();
+        };
+        let loaded = loader.poll();
+        self.stats.loading = loader.pending();
+        loaded
+    }
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_default() {
+        let app = App::new();
+        assert!(app.running);
+        assert!(!app.show_help);
+        assert_eq!(app.focused, FocusedPane::TaskList);
+    }
+
+    #[test]
+    fn activity_is_timed_by_the_installed_clock() {
+        use crate::data::clock::ManualClock;
+
+        let clock = Arc::new(ManualClock::new("2026-02-08T10:00:00Z".parse().unwrap()));
+        let mut app = App::new().with_clock(clock.clone());
+        assert_eq!(app.last_activity, app.start_time);
+        clock.advance(Duration::minutes(5));
+        app.record_activity(false);
+        assert_eq!(
+            app.last_activity.duration_since(app.start_time),
+            std::time::Duration::from_secs(300)
+        );
+    }
+
+    #[test]
+    fn tick_interval_adapts_to_activity() {
+        use crate::data::hook_parser;
+        use std::time::Duration as StdDuration;
+
+        let mut app = App::new();
+        let now = app.last_activity;
+        assert_eq!(app.tick_interval(now).as_millis(), 250);
+
+        // Quiet for longer than idle_after_secs with no running agent
+        let later = now + StdDuration::from_secs(11);
+        assert_eq!(app.tick_interval(later).as_millis(), 1000);
+
+        // A running agent keeps the normal rate
+        let events = hook_parser::parse_hook_events(
+            r#"{"event_type":"agent_start","agent_id":"main","task_id":"T1","session_id":"s1","timestamp":"2026-02-08T00:00:00Z"}"#,
+        );
+        app.dashboard.update_from_events(&events.events);
+        assert_eq!(app.tick_interval(later).as_millis(), 250);
+
+        // A burst of file changes speeds up, then expires after a second
+        for _ in 0..BURST_CHANGES {
+            app.record_activity(true);
+        }
+        let now = app.last_activity;
+        assert_eq!(app.tick_interval(now).as_millis(), 100);
+        let after = now + StdDuration::from_secs(2);
+        assert_eq!(app.tick_interval(after).as_millis(), 250);
+    }
+
+    #[test]
+    fn app_quit() {
+        let mut app = App::new();
+        app.quit();
+        assert!(!app.running);
+    }
+
+    #[test]
+    fn app_toggle_help() {
+        let mut app = App::new();
+        assert!(!app.show_help);
         app.toggle_help();
         assert!(app.show_help);
         app.toggle_help();
@@ -261,6 +2033,18 @@ mod tests {
         assert_eq!(app.selected_agent, 0);
     }
 
+    #[test]
+    fn space_toggles_agent_output_in_agents_pane() {
+        let mut app = App::new();
+        app.toggle_collapse();
+        assert!(!app.show_agent_output);
+        app.focused = FocusedPane::Agents;
+        app.toggle_collapse();
+        assert!(app.show_agent_output);
+        app.toggle_collapse();
+        assert!(!app.show_agent_output);
+    }
+
     #[test]
     fn sorted_agent_ids() {
         let mut app = App::new();
@@ -271,10 +2055,10 @@ mod tests {
 
         let ids = app.sorted_agent_ids();
         assert!(!ids.is_empty());
-        // Should be sorted
-        let mut sorted = ids.clone();
-        sorted.sort();
-        assert_eq!(ids, sorted);
+        // Same order as the agents panel
+        let (agents, _) = app.dashboard.panel_agents();
+        let panel: Vec<&str> = agents.iter().map(|a| a.agent_id.as_str()).collect();
+        assert_eq!(ids, panel);
     }
 
     #[test]
@@ -319,6 +2103,129 @@ mod tests {
         assert_eq!(app.dashboard.total_tasks, 1);
     }
 
+    #[test]
+    fn config_change_applies_live_or_reports_the_error() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config_file = tmp.path().join(".claude-board.toml");
+        std::fs::write(&config_file, "[tick]\ninterval_ms = 500\n").unwrap();
+        let mut app = App::new();
+        app.handle_file_change(&FileChange::ConfigModified(config_file.clone()));
+        assert_eq!(app.config.tick.interval_ms, 500);
+        let toast = app.toast.take().unwrap();
+        assert!(!toast.error);
+        assert_eq!(toast.message, "Config reloaded");
+
+        std::fs::write(&config_file, "[tick]\ninterval_ms = \"fast\"\n").unwrap();
+        app.handle_file_change(&FileChange::ConfigModified(config_file));
+        assert_eq!(app.config.tick.interval_ms, 500);
+        let toast = app.toast.unwrap();
+        assert!(toast.error);
+        assert!(toast.message.contains("interval_ms"), "{}", toast.message);
+    }
+
+    #[test]
+    fn oversized_hook_file_is_cut_with_one_warning() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let hook_file = tmp.path().join("events.jsonl");
+        let event = |agent: &str| {
+            format!(
+                r#"{{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"{agent}","task_id":"T1","session_id":"s1"}}"#
+            ) + "\n"
+        };
+        std::fs::write(&hook_file, event("old") + event("new").as_str()).unwrap();
+        let mut app = App::new();
+        app.dashboard.hook_read_limit = event("new").len() as u64 + 5;
+
+        app.handle_file_change(&FileChange::HookEventModified(hook_file.clone()));
+        assert!(app.dashboard.agents.contains_key("new"));
+        assert!(!app.dashboard.agents.contains_key("old"));
+        assert!(app.toast.take().unwrap().error);
+
+        app.handle_file_change(&FileChange::HookEventModified(hook_file));
+        assert!(app.toast.is_none());
+    }
+
+    #[test]
+    fn removed_tasks_file_warns_only_if_it_stays_gone() {
+        use std::time::Duration as StdDuration;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(&tasks_file, "# Phase 0: Setup\n### [ ] T1: A\n").unwrap();
+        let mut app = App::new().with_tasks_path(tasks_file.clone());
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+
+        // Saved by rename: gone briefly, then back
+        std::fs::remove_file(&tasks_file).unwrap();
+        app.handle_file_change(&FileChange::TasksRemoved(tasks_file.clone()));
+        let since = app.tasks_missing_since.unwrap();
+        std::fs::write(&tasks_file, "# Phase 0: Setup\n### [x] T1: A\n").unwrap();
+        app.check_tasks_missing(since + StdDuration::from_secs(3));
+        assert!(!app.tasks_gone);
+        assert_eq!(app.dashboard.completed_tasks, 1);
+
+        std::fs::remove_file(&tasks_file).unwrap();
+        app.handle_file_change(&FileChange::TasksRemoved(tasks_file.clone()));
+        let since = app.tasks_missing_since.unwrap();
+        app.check_tasks_missing(since + StdDuration::from_secs(1));
+        assert!(!app.tasks_gone);
+        app.check_tasks_missing(since + StdDuration::from_secs(3));
+        assert!(app.tasks_gone);
+        assert!(app.toast.as_ref().is_some_and(|t| t.error));
+        // The last plan stays on screen
+        assert_eq!(app.dashboard.total_tasks, 1);
+
+        std::fs::write(&tasks_file, "# Phase 0: Setup\n### [ ] T1: A\n").unwrap();
+        app.handle_file_change(&FileChange::TasksModified(tasks_file));
+        assert!(!app.tasks_gone);
+    }
+
+    #[test]
+    fn reload_keeps_selection_on_same_task() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 0: Setup\n### [ ] T1: A\n### [ ] T2: B\n",
+        )
+        .unwrap();
+        let mut app = App::new();
+        let change = FileChange::TasksModified(tasks_file.clone());
+        app.handle_file_change(&change);
+        app.gantt_state.selected = 2; // T2
+
+        std::fs::write(
+            &tasks_file,
+            "# Phase 0: Setup\n### [ ] T0: New\n### [ ] T1: A\n### [ ] T2: B\n",
+        )
+        .unwrap();
+        app.handle_file_change(&change);
+        assert_eq!(app.gantt_state.selected, 3);
+        assert_eq!(app.selected_task_id().as_deref(), Some("T2"));
+    }
+
+    #[test]
+    fn shrinking_reload_clamps_selection() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 0: Setup\n### [ ] T1: A\n### [ ] T2: B\n",
+        )
+        .unwrap();
+        let mut app = App::new();
+        let change = FileChange::TasksModified(tasks_file.clone());
+        app.handle_file_change(&change);
+        assert_eq!(app.gantt_state.total_items, 3);
+        app.gantt_state.selected = 2; // T2
+
+        std::fs::write(&tasks_file, "# Phase 0: Setup\n### [ ] T1: A\n").unwrap();
+        app.handle_file_change(&change);
+        assert_eq!(app.gantt_state.total_items, 2);
+        // T2 is gone, so the cursor lands on its phase header
+        assert_eq!(app.gantt_state.selected, 0);
+    }
+
     #[test]
     fn open_retry_modal_on_failed_task() {
         let input = include_str!("../tests/fixtures/sample_tasks.md");
@@ -421,6 +2328,215 @@ mod tests {
         assert!(result.contains("[Failed] T1:"));
     }
 
+    fn retry_fixture(tmp: &tempfile::TempDir) -> (PathBuf, PathBuf) {
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 1: Core\n\n### [Failed] T1: Flaky task\n",
+        )
+        .unwrap();
+        let events_file = tmp.path().join("events.jsonl");
+        std::fs::write(
+            &events_file,
+            r#"{"event_type":"error","timestamp":"2026-02-08T12:00:00Z","agent_id":"a","task_id":"T1","session_id":"s1","error_message":"connection refused"}"#,
+        )
+        .unwrap();
+        (tasks_file, events_file)
+    }
+
+    fn network_budget(budget: u32) -> Config {
+        Config::from_toml_str(&format!(
+            "[retry]\nauto = true\n[retry.budgets]\nNetwork = {budget}\n"
+        ))
+        .unwrap()
+    }
+
+    /// Feed a file change through the reducer and carry out the writes it
+    /// asks for, as the run loop does
+    fn file_changed(app: &mut App, change: FileChange) -> Vec<Effect> {
+        let effects = app.update(crate::update::Msg::FileChanged(change));
+        app.perform_all(effects.clone());
+        effects
+    }
+
+    #[test]
+    fn exit_summary_lists_failed_tasks_with_suggestions() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (tasks_file, events_file) = retry_fixture(&tmp);
+        let mut app = App::new().with_tasks_path(tasks_file.clone());
+        app.handle_file_change(&FileChange::TasksModified(tasks_file));
+        app.handle_file_change(&FileChange::HookEventCreated(events_file));
+        app.dashboard.scrub_at = Some("2026-02-08T13:30:00Z".parse().unwrap());
+
+        let summary = app.exit_summary();
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(
+            lines[0],
+            "simple-claude-board: 0/1 tasks done (0%), 1 failed"
+        );
+        assert!(lines[1].starts_with("  Run time: 1h 30m (board open "));
+        assert_eq!(lines[2], "  Failed:");
+        assert!(lines[3].starts_with("    T1 Flaky task - "), "{summary}");
+    }
+
+    #[test]
+    fn exit_summary_shows_spend_against_the_budget() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (tasks_file, events_file) = retry_fixture(&tmp);
+        std::fs::write(
+            &events_file,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T12:00:00Z","agent_id":"a","task_id":"T1","session_id":"s1","usage":{"input_tokens":40000,"output_tokens":8000,"cost_usd":12.5}}"#,
+        )
+        .unwrap();
+        let mut app = App::new().with_tasks_path(tasks_file.clone());
+        app.handle_file_change(&FileChange::TasksModified(tasks_file));
+        app.handle_file_change(&FileChange::HookEventCreated(events_file));
+        assert!(app
+            .exit_summary()
+            .contains("  Spend: $12.50 (48.0k tokens)\n"));
+
+        app.config.cost.budget_usd = Some(10.0);
+        assert!(app
+            .exit_summary()
+            .contains("  Spend: $12.50 of $10.00 budget, over (48.0k tokens)\n"));
+    }
+
+    /// `action` of every audit log line
+    fn audited_actions(audit: &std::path::Path) -> Vec<String> {
+        std::fs::read_to_string(audit)
+            .unwrap_or_default()
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["action"].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn auto_retry_within_budget() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (tasks_file, events_file) = retry_fixture(&tmp);
+        let audit = tmp.path().join("audit.jsonl");
+        let mut app = App::new()
+            .with_tasks_path(tasks_file.clone())
+            .with_config(network_budget(3))
+            .with_audit_path(audit.clone());
+        file_changed(&mut app, FileChange::TasksModified(tasks_file.clone()));
+        // The reducer only asks for the write; the run loop performs it
+        assert_eq!(
+            file_changed(&mut app, FileChange::HookEventCreated(events_file)),
+            [Effect::Retry {
+                task_id: "T1".to_string()
+            }]
+        );
+
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(result.contains("[InProgress] T1:"));
+        assert!(result.contains("### [InProgress] T1: Flaky task\n- attempts: 1\n"));
+        assert_eq!(app.dashboard.retries.attempts("T1"), 1);
+        assert!(!app.dashboard.retries.get("T1").unwrap().exhausted);
+        assert_eq!(audited_actions(&audit), ["\"retry\""]);
+    }
+
+    #[test]
+    fn rate_limit_holds_auto_retry_until_the_cooldown_passes() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (tasks_file, events_file) = retry_fixture(&tmp);
+        let hit = Utc::now() - Duration::seconds(10);
+        std::fs::write(
+            &events_file,
+            format!(
+                r#"{{"event_type":"error","timestamp":"{}","agent_id":"a","task_id":"T1","session_id":"s1","error_message":"429 rate limit, retry after 600s"}}"#,
+                hit.to_rfc3339()
+            ),
+        )
+        .unwrap();
+        let mut app = App::new().with_tasks_path(tasks_file.clone()).with_config(
+            Config::from_toml_str("[retry]\nauto = true\n[retry.budgets]\nRateLimit = 3\n")
+                .unwrap(),
+        );
+        file_changed(&mut app, FileChange::TasksModified(tasks_file.clone()));
+        file_changed(&mut app, FileChange::HookEventCreated(events_file));
+
+        assert!(std::fs::read_to_string(&tasks_file)
+            .unwrap()
+            .contains("[Failed] T1:"));
+        let until = hit + Duration::seconds(600);
+        assert_eq!(app.retry_wait, Some(until));
+
+        assert!(app
+            .check_retry_wait(until - Duration::seconds(1))
+            .is_empty());
+        // The policy checks the wall clock; let the window be over
+        app.dashboard.recent_errors[0].cooldown_until = Some(hit);
+        let effects = app.check_retry_wait(until);
+        assert_eq!(
+            effects,
+            [Effect::Retry {
+                task_id: "T1".to_string()
+            }]
+        );
+        app.perform_all(effects);
+        assert!(std::fs::read_to_string(&tasks_file)
+            .unwrap()
+            .contains("[InProgress] T1:"));
+        assert_eq!(app.retry_wait, None);
+    }
+
+    #[test]
+    fn circuit_breaker_blocks_after_budget() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (tasks_file, events_file) = retry_fixture(&tmp);
+        let audit = tmp.path().join("audit.jsonl");
+        let mut app = App::new()
+            .with_tasks_path(tasks_file.clone())
+            .with_config(network_budget(1))
+            .with_audit_path(audit.clone());
+        app.dashboard
+            .retries
+            .record_attempt("T1", Some(ErrorCategory::Network), Some(1));
+        file_changed(&mut app, FileChange::TasksModified(tasks_file.clone()));
+        file_changed(&mut app, FileChange::HookEventCreated(events_file));
+
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(result.contains("[Blocked] T1:"));
+        assert!(app.dashboard.retries.get("T1").unwrap().exhausted);
+        assert_eq!(app.dashboard.phases[0].tasks[0].status, TaskStatus::Blocked);
+        assert_eq!(audited_actions(&audit), ["\"block\""]);
+    }
+
+    #[test]
+    fn attempts_in_tasks_file_count_toward_budget() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (tasks_file, events_file) = retry_fixture(&tmp);
+        // A previous session already used the whole budget
+        std::fs::write(
+            &tasks_file,
+            "# Phase 1: Core\n\n### [Failed] T1: Flaky task\n- attempts: 2\n",
+        )
+        .unwrap();
+        let mut app = App::new()
+            .with_tasks_path(tasks_file.clone())
+            .with_config(network_budget(2));
+        file_changed(&mut app, FileChange::TasksModified(tasks_file.clone()));
+        file_changed(&mut app, FileChange::HookEventCreated(events_file));
+
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(result.contains("[Blocked] T1:"));
+        assert!(result.contains("- attempts: 2\n"));
+    }
+
+    #[test]
+    fn no_auto_retry_by_default() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (tasks_file, events_file) = retry_fixture(&tmp);
+        let mut app = App::new().with_tasks_path(tasks_file.clone());
+        file_changed(&mut app, FileChange::TasksModified(tasks_file.clone()));
+        file_changed(&mut app, FileChange::HookEventCreated(events_file));
+
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(result.contains("[Failed] T1:"));
+        assert_eq!(app.dashboard.retries.attempts("T1"), 0);
+    }
+
     #[test]
     fn handle_file_change_hook() {
         let tmp = tempfile::TempDir::new().unwrap();
@@ -438,4 +2554,447 @@ mod tests {
         app.handle_file_change(&change);
         assert!(!app.dashboard.agents.is_empty());
     }
+
+    #[test]
+    fn loader_parses_changes_off_the_update() {
+        use crate::update::Msg;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(&tasks_file, "# Phase 0: Setup\n### [x] T1: A\n").unwrap();
+        let mut app = App::new().with_loader(Loader::spawn());
+
+        app.update(Msg::FileChanged(FileChange::TasksModified(tasks_file)));
+        assert_eq!(app.dashboard.total_tasks, 0);
+        assert_eq!(app.stats.loading, 1);
+
+        let deadline = Instant::now() + std::time::Duration::from_secs(5);
+        while app.stats.loading > 0 && Instant::now() < deadline {
+            for loaded in app.take_loads() {
+                app.update(Msg::Loaded(loaded));
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert_eq!(app.dashboard.total_tasks, 1);
+        assert_eq!(app.dashboard.completed_tasks, 1);
+    }
+
+    #[test]
+    fn triage_reclassifies_unknown_errors() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let events_file = tmp.path().join("events.jsonl");
+        std::fs::write(
+            &events_file,
+            r#"{"event_type":"error","timestamp":"2026-02-08T12:00:00Z","agent_id":"a","task_id":"T1","session_id":"s1","error_message":"flux capacitor desynced"}"#,
+        )
+        .unwrap();
+        let config = Config::from_toml_str(
+            "[triage]\ncommand = [\"sh\", \"-c\", \"cat >/dev/null; printf 'Category: Runtime\\\\nRetryable: yes\\\\nSuggestion: Recalibrate'\"]\n",
+        )
+        .unwrap();
+        let mut app = App::new().with_config(config);
+        app.handle_file_change(&FileChange::HookEventCreated(events_file));
+        // Rule-based result stays until the command answers, which only
+        // runs once the planned lookup is performed
+        assert_eq!(
+            app.dashboard.recent_errors[0].category,
+            ErrorCategory::Unknown
+        );
+        assert_eq!(
+            app.outbox,
+            [Effect::Triage {
+                message: "flux capacitor desynced".to_string()
+            }]
+        );
+        app.perform_all(Vec::new());
+        assert!(app.outbox.is_empty());
+
+        let deadline = Instant::now() + std::time::Duration::from_secs(5);
+        while app.dashboard.recent_errors[0].category == ErrorCategory::Unknown
+            && Instant::now() < deadline
+        {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            app.poll_triage();
+        }
+        let err = &app.dashboard.recent_errors[0];
+        assert_eq!(err.category, ErrorCategory::Runtime);
+        assert!(err.retryable);
+        assert_eq!(err.display_suggestion(), "Recalibrate");
+    }
+
+    #[test]
+    fn triage_disabled_by_default() {
+        let app = App::new().with_config(Config::default());
+        assert!(app.triage.is_none());
+    }
+
+    #[test]
+    fn fatal_banner_dismiss_until_newer() {
+        use crate::data::hook_parser;
+        let mut app = App::new();
+        let oom = r#"{"event_type":"error","timestamp":"2026-02-08T12:00:00Z","agent_id":"a","task_id":"T1","session_id":"s1","error_message":"out of memory"}"#;
+        app.dashboard
+            .update_from_events(&hook_parser::parse_hook_events(oom).events);
+        assert_eq!(app.fatal_banner().unwrap().task_id, "T1");
+
+        app.dismiss_banner();
+        assert!(app.fatal_banner().is_none());
+
+        let overflow = r#"{"event_type":"error","timestamp":"2026-02-08T12:05:00Z","agent_id":"a","task_id":"T2","session_id":"s1","error_message":"stack overflow"}"#;
+        app.dashboard
+            .update_from_events(&hook_parser::parse_hook_events(overflow).events);
+        assert_eq!(app.fatal_banner().unwrap().task_id, "T2");
+    }
+
+    fn approval_app(tmp: &tempfile::TempDir) -> (App, PathBuf) {
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 1: Core\n\n### [InProgress] T1: Build it\n- **담당**: @backend\n",
+        )
+        .unwrap();
+        let config = Config::from_toml_str("[approval]\nenabled = true\n").unwrap();
+        let mut app = App::new()
+            .with_tasks_path(tasks_file.clone())
+            .with_config(config);
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+
+        // Agent marks the task done
+        std::fs::write(
+            &tasks_file,
+            "# Phase 1: Core\n\n### [x] T1: Build it\n- **담당**: @backend\n",
+        )
+        .unwrap();
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+        // Select T1 (row 0 is the phase header)
+        app.gantt_state.selected = 1;
+        (app, tasks_file)
+    }
+
+    #[test]
+    fn structured_plan_loads_but_is_read_only() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("tasks.json");
+        let plan = r#"{"phases": [{"id": "P1", "name": "Core", "tasks": [
+            {"id": "T1", "name": "Build it", "status": "InProgress"}
+        ]}]}"#;
+        std::fs::write(&tasks_file, plan).unwrap();
+        let mut app = App::new().with_tasks_path(tasks_file.clone());
+        app.dashboard.tasks_source =
+            crate::data::tasks_source::for_path(&tasks_file, Default::default());
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+        assert_eq!(app.dashboard.total_tasks, 1);
+
+        app.gantt_state.selected = 1;
+        app.move_selected_task(MoveDirection::Up);
+        app.open_note_input();
+        app.handle_input(InputKey::Char('x'));
+        app.handle_input(InputKey::Submit);
+        assert_eq!(std::fs::read_to_string(&tasks_file).unwrap(), plan);
+    }
+
+    #[test]
+    fn completed_task_awaits_review() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (app, _) = approval_app(&tmp);
+        assert!(app.dashboard.is_awaiting_review("T1"));
+    }
+
+    #[test]
+    fn approve_releases_hold() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (mut app, tasks_file) = approval_app(&tmp);
+        app.approve_selected();
+        assert!(!app.dashboard.is_awaiting_review("T1"));
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(result.contains("### [x] T1: Build it"));
+    }
+
+    #[test]
+    fn bounce_reopens_with_note() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (mut app, tasks_file) = approval_app(&tmp);
+        app.bounce_selected();
+        assert!(!app.dashboard.is_awaiting_review("T1"));
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(result.contains("### [InProgress] T1: Build it"));
+        assert!(result.contains(": bounced back to InProgress"));
+        assert_eq!(
+            app.dashboard.phases[0].tasks[0].status,
+            TaskStatus::InProgress
+        );
+    }
+
+    #[test]
+    fn no_review_hold_when_disabled() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(&tasks_file, "# Phase 1: Core\n\n### [ ] T1: Build it\n").unwrap();
+        let mut app = App::new().with_tasks_path(tasks_file.clone());
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+        std::fs::write(&tasks_file, "# Phase 1: Core\n\n### [x] T1: Build it\n").unwrap();
+        app.handle_file_change(&FileChange::TasksModified(tasks_file));
+        assert!(app.dashboard.awaiting_review.is_empty());
+    }
+
+    #[test]
+    fn note_input_appends_to_task_body() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 1: Core\n\n### [InProgress] T1: Build it\n- **담당**: @backend\n",
+        )
+        .unwrap();
+        let mut app = App::new().with_tasks_path(tasks_file.clone());
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+        app.gantt_state.selected = 1;
+
+        app.open_note_input();
+        assert_eq!(app.input.as_ref().unwrap().prompt(), "Note for T1");
+        for c in "flaky on CI".chars() {
+            app.handle_input(InputKey::Char(c));
+        }
+        app.handle_input(InputKey::Submit);
+
+        assert!(app.input.is_none());
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        let note_line = result.lines().last().unwrap();
+        assert!(note_line.starts_with("- note "));
+        assert!(note_line.ends_with(": flaky on CI"));
+        assert!(app.dashboard.phases[0].tasks[0]
+            .body
+            .contains("flaky on CI"));
+    }
+
+    #[test]
+    fn note_input_cancel_writes_nothing() {
+        let mut app = App::new();
+        app.input = Some(PendingInput {
+            purpose: InputPurpose::Note {
+                task_id: "T1".to_string(),
+            },
+            text: TextInput::with_value("draft"),
+        });
+        app.handle_input(InputKey::Cancel);
+        assert!(app.input.is_none());
+    }
+
+    #[test]
+    fn filtered_board_still_waits_for_hidden_blockers() {
+        let mut app = App::new();
+        app.dashboard = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [ ] T1: a\n# Phase 2: B\n### [ ] T2: b\n- **blocked_by**: T1\n### [ ] T3: c\n",
+        )
+        .unwrap();
+        app.dashboard
+            .set_task_filter(crate::data::filter::TaskFilter {
+                phases: vec!["P2".to_string()],
+                ..Default::default()
+            });
+        app.gantt_state.sync_with(&app.dashboard);
+
+        // T1 is hidden but still open, so T2 is not ready
+        let ids: Vec<String> = app.next_up().into_iter().map(|s| s.task_id).collect();
+        assert_eq!(ids, ["T3"]);
+        // Rows skip the hidden phase but point into the whole plan
+        assert_eq!(app.gantt_state.total_items, 3);
+        app.select_task_by_id("T3");
+        assert_eq!(app.gantt_state.selected, 2);
+        assert_eq!(app.gantt_state.selected_task(&app.dashboard), Some((1, 1)));
+        app.select_task_by_id("T1");
+        assert_eq!(app.gantt_state.selected, 2);
+    }
+
+    fn reorder_app(tmp: &tempfile::TempDir) -> (App, PathBuf) {
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 1: A\n\n### [ ] T1: One\n- one body\n\n### [ ] T2: Two\n\n# Phase 2: B\n\n### [ ] T3: Three\n",
+        )
+        .unwrap();
+        let mut app = App::new().with_tasks_path(tasks_file.clone());
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+        (app, tasks_file)
+    }
+
+    #[test]
+    fn move_selected_task_follows_selection() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (mut app, tasks_file) = reorder_app(&tmp);
+        app.gantt_state.selected = 1; // T1
+        app.move_selected_task(MoveDirection::Down);
+
+        let ids: Vec<_> = app.dashboard.phases[0]
+            .tasks
+            .iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["T2", "T1"]);
+        assert_eq!(app.gantt_state.selected, 2);
+        assert_eq!(app.dashboard.phases[0].tasks[1].body, "- one body");
+        assert!(std::fs::read_to_string(&tasks_file)
+            .unwrap()
+            .contains("### [ ] T2: Two\n\n### [ ] T1: One\n- one body\n"));
+    }
+
+    #[test]
+    fn move_to_phase_via_input() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (mut app, _) = reorder_app(&tmp);
+        app.gantt_state.selected = 1; // T1
+        app.open_move_phase_input();
+        app.handle_input(InputKey::Char('2'));
+        app.handle_input(InputKey::Submit);
+
+        assert_eq!(app.dashboard.phases[0].tasks.len(), 1);
+        let ids: Vec<_> = app.dashboard.phases[1]
+            .tasks
+            .iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["T3", "T1"]);
+        // Phase 1 header, T2, Phase 2 header, T3, T1
+        assert_eq!(app.gantt_state.selected, 4);
+    }
+
+    #[test]
+    fn quick_add_with_template_writes_checklist() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (app, tasks_file) = reorder_app(&tmp);
+        let mut config = Config::default();
+        config.templates.push(TaskTemplate {
+            label: "tests".to_string(),
+            name: "Write tests for {input}".to_string(),
+            agent: Some("qa".to_string()),
+            checklist: vec!["happy path".to_string()],
+        });
+        let mut app = app.with_config(config);
+        app.gantt_state.selected = 3; // Phase 2 header
+        app.open_add_task_input();
+        app.handle_input(InputKey::Tab);
+        assert_eq!(
+            app.input.as_ref().unwrap().prompt(),
+            "tests T4: Write tests for ..."
+        );
+        for c in "login".chars() {
+            app.handle_input(InputKey::Char(c));
+        }
+        app.handle_input(InputKey::Submit);
+
+        assert!(std::fs::read_to_string(&tasks_file).unwrap().ends_with(
+            "### [ ] T3: Three\n\n### [ ] T4: Write tests for login\n- **담당**: @qa\n- [ ] happy path\n"
+        ));
+        assert_eq!(
+            app.dashboard.phases[1].tasks[1].agent.as_deref(),
+            Some("qa")
+        );
+        assert_eq!(app.selected_task_id().as_deref(), Some("T4"));
+    }
+
+    #[test]
+    fn quick_add_skips_an_id_taken_in_another_phase() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (mut app, tasks_file) = reorder_app(&tmp);
+        app.gantt_state.selected = 1; // T1
+        app.open_add_task_input();
+        // Tab without templates keeps the plain form
+        app.handle_input(InputKey::Tab);
+        assert_eq!(
+            app.input.as_ref().unwrap().prompt(),
+            "Name of T4 in P1 (Tab for a template)"
+        );
+        for c in "Three again".chars() {
+            app.handle_input(InputKey::Char(c));
+        }
+        app.handle_input(InputKey::Submit);
+
+        assert!(std::fs::read_to_string(&tasks_file)
+            .unwrap()
+            .contains("### [ ] T2: Two\n\n### [ ] T4: Three again\n\n# Phase 2: B"));
+        assert_eq!(app.selected_task_id().as_deref(), Some("T4"));
+    }
+
+    fn conflict_app(tmp: &tempfile::TempDir) -> (App, PathBuf) {
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 1: A\n\n### [Failed] T1: One\n\n### [ ] T2: Two\n",
+        )
+        .unwrap();
+        let mut app = App::new().with_tasks_path(tasks_file.clone());
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+        app.gantt_state.selected = 1;
+        (app, tasks_file)
+    }
+
+    #[test]
+    fn unrelated_edit_is_not_a_conflict() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (mut app, tasks_file) = conflict_app(&tmp);
+        app.open_retry_modal();
+
+        std::fs::write(
+            &tasks_file,
+            "# Phase 1: A\n\n### [Failed] T1: One\n\n### [x] T2: Two\n",
+        )
+        .unwrap();
+        app.handle_file_change(&FileChange::TasksModified(tasks_file));
+        assert_eq!(app.modal_conflict(), None);
+    }
+
+    #[test]
+    fn external_fix_during_retry_modal_skips_write() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (mut app, tasks_file) = conflict_app(&tmp);
+        app.open_retry_modal();
+        assert!(app.show_retry_modal);
+
+        let edited = "# Phase 1: A\n\n### [x] T1: One\n- fixed by hand\n\n### [ ] T2: Two\n";
+        std::fs::write(&tasks_file, edited).unwrap();
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+        assert!(app.modal_conflict().unwrap().contains("T1 changed on disk"));
+
+        app.confirm_retry();
+        assert_eq!(std::fs::read_to_string(&tasks_file).unwrap(), edited);
+        assert_eq!(app.modal_conflict(), None);
+    }
+
+    #[test]
+    fn removed_task_is_reported_and_note_not_written() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (mut app, tasks_file) = conflict_app(&tmp);
+        app.open_note_input();
+
+        let edited = "# Phase 1: A\n\n### [ ] T2: Two\n";
+        std::fs::write(&tasks_file, edited).unwrap();
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+        assert_eq!(app.modal_conflict(), Some("T1 was removed from TASKS.md"));
+
+        app.handle_input(InputKey::Char('x'));
+        app.handle_input(InputKey::Submit);
+        assert_eq!(std::fs::read_to_string(&tasks_file).unwrap(), edited);
+    }
+
+    #[test]
+    fn note_targets_moved_task_after_external_edit() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let (mut app, tasks_file) = conflict_app(&tmp);
+        app.open_note_input();
+
+        // Someone moves T1 below T2 while the note is being typed
+        std::fs::write(
+            &tasks_file,
+            "# Phase 1: A\n\n### [ ] T2: Two\n\n### [Failed] T1: One\n",
+        )
+        .unwrap();
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+        assert!(app.modal_conflict().is_none());
+
+        app.handle_input(InputKey::Char('x'));
+        app.handle_input(InputKey::Submit);
+        let content = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(content.contains("### [Failed] T1: One\n- note "));
+        assert!(content.starts_with("# Phase 1: A\n\n### [ ] T2: Two\n\n"));
+    }
 }
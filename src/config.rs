@@ -0,0 +1,446 @@
+//! Dashboard configuration
+//!
+//! Loaded from `.claude-board.toml` in the working directory, or from the path
+//! given with `--config`. Every section is optional and a missing file yields
+//! the defaults, which match the built-in behavior.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::analysis::retry::RetryPolicy;
+use crate::analysis::rules::CustomRule;
+use crate::analysis::triage::TriageConfig;
+use crate::data::agent_groups::{AgentGroup, SessionDisplay};
+use crate::data::audit::AuditConfig;
+use crate::data::control::ControlConfig;
+use crate::data::cost::CostConfig;
+use crate::data::roster::RosterEntry;
+use crate::data::statuses::StatusDef;
+use crate::data::tasks_parser::{ProgressWeights, TaskFormat};
+use crate::data::templates::TaskTemplate;
+use crate::data::timezone::DisplayZone;
+use crate::data::wip::WipLimits;
+use crate::github::GithubConfig;
+use crate::jira::JiraConfig;
+use crate::otel::OtelConfig;
+use crate::prompt::PromptConfig;
+use crate::scripts::ScriptsConfig;
+
+/// Default config file name looked up in the working directory
+pub const CONFIG_FILE_NAME: &str = ".claude-board.toml";
+
+/// Top-level configuration
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Per-category retry budgets and circuit breaker
+    pub retry: RetryPolicy,
+    /// Optional command-backed triage of unclassified errors
+    pub triage: TriageConfig,
+    /// Custom error rules, checked before the built-in ones
+    pub rules: Vec<CustomRule>,
+    /// Work-in-progress limits
+    pub wip: WipLimits,
+    /// Extra task statuses beyond the built-in ones
+    pub statuses: Vec<StatusDef>,
+    /// Human approval of agent-completed tasks
+    pub approval: ApprovalConfig,
+    /// Task weighting for progress
+    pub progress: ProgressWeights,
+    /// How TASKS.md is read
+    pub tasks: TasksConfig,
+    /// Commands run on task/phase events and for a status bar segment
+    pub scripts: ScriptsConfig,
+    /// Re-prompt template for failed tasks
+    pub prompt: PromptConfig,
+    /// Jira issue status and transitions for linked tasks
+    pub jira: JiraConfig,
+    /// Pull request status for tasks
+    pub github: GithubConfig,
+    /// OpenTelemetry span export of the run
+    pub otel: OtelConfig,
+    /// Token prices and the run budget
+    pub cost: CostConfig,
+    /// Where agent stop requests go
+    pub control: ControlConfig,
+    /// Where board-initiated writes are recorded
+    pub audit: AuditConfig,
+    /// More hook event directories
+    pub hooks: HooksConfig,
+    /// How timestamps are shown
+    pub display: DisplayConfig,
+    /// How often the screen refreshes
+    pub tick: TickConfig,
+    /// How the agents panel lists agents
+    pub agents: AgentsConfig,
+    /// Task shapes offered by the quick-add modal
+    pub templates: Vec<TaskTemplate>,
+}
+
+/// `[approval]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ApprovalConfig {
+    /// Hold tasks completed by agents as "awaiting review" until approved
+    pub enabled: bool,
+}
+
+/// `[tasks]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TasksConfig {
+    /// Task line forms to recognize: "auto", "headings" or "checkboxes"
+    pub format: TaskFormat,
+}
+
+/// `[display]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// "local" (default), "utc" or a fixed offset like "+09:00"
+    pub timezone: DisplayZone,
+    /// Task pane line numbers at startup: "off", "absolute" or "relative"
+    pub line_numbers: LineNumbers,
+    /// Screen layout at startup: "standard" or "ops"
+    pub layout: LayoutPreset,
+}
+
+/// Line number gutter in the tree and bar views
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineNumbers {
+    #[default]
+    Off,
+    Absolute,
+    /// Distance from the selected row, which shows its own number
+    Relative,
+}
+
+/// Arrangement of the dashboard panes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutPreset {
+    /// Task list left, detail and agents right
+    #[default]
+    Standard,
+    /// Condensed task list on top, live hook event log below
+    Ops,
+}
+
+/// `[hooks]` section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// Hook/event directories watched in addition to `--hooks` and `--events`
+    pub dirs: Vec<PathBuf>,
+    /// Only the last this many MB of a larger hook file are read
+    pub max_file_mb: u64,
+    /// Reuse the parse of hook files unchanged since the last start
+    pub cache: bool,
+    /// Where parses are cached (default: `<events dir>/cache`)
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            dirs: Vec::new(),
+            max_file_mb: 64,
+            cache: true,
+            cache_dir: None,
+        }
+    }
+}
+
+impl HooksConfig {
+    /// `max_file_mb` in bytes
+    pub fn max_file_bytes(&self) -> u64 {
+        self.max_file_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+/// `[agents]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AgentsConfig {
+    /// Hide idle agents whose last event is older than this (counted in a summary line)
+    pub hide_idle_after_mins: Option<u64>,
+    /// `[[agents.groups]]`: agent IDs merged into one display agent
+    pub groups: Vec<AgentGroup>,
+    /// Same agent ID in several sessions: "merge" into one agent or "split"
+    pub sessions: SessionDisplay,
+    /// `[[agents.roster]]`: known agents, merged with `AGENTS.md`
+    pub roster: Vec<RosterEntry>,
+}
+
+/// `[tick]` section
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct TickConfig {
+    /// Refresh interval while agents are working
+    pub interval_ms: u64,
+    /// Slower interval once no agent is running and nothing has changed
+    pub idle_ms: u64,
+    /// Faster interval while hook events arrive in bursts
+    pub burst_ms: u64,
+    /// Quiet time before switching to the idle interval
+    pub idle_after_secs: u64,
+}
+
+impl Default for TickConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: 250,
+            idle_ms: 1000,
+            burst_ms: 100,
+            idle_after_secs: 10,
+        }
+    }
+}
+
+impl Config {
+    /// Parse configuration from TOML text
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        toml::from_str(content).context("invalid config")
+    }
+
+    /// Load configuration from a file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config: {}", path.display()))?;
+        Self::from_toml_str(&content).with_context(|| format!("in {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rules::{ErrorCategory, Severity};
+
+    #[test]
+    fn empty_config_is_default() {
+        let config = Config::from_toml_str("").unwrap();
+        assert!(!config.retry.auto);
+        assert!(config.retry.budgets.is_empty());
+        assert!(config.triage.command.is_empty());
+        assert!(config.hooks.dirs.is_empty());
+    }
+
+    #[test]
+    fn hooks_section_lists_extra_dirs() {
+        let config =
+            Config::from_toml_str("[hooks]\ndirs = [\"/srv/a\", \"team/hooks\"]\n").unwrap();
+        assert_eq!(
+            config.hooks.dirs,
+            vec![PathBuf::from("/srv/a"), PathBuf::from("team/hooks")]
+        );
+        assert_eq!(config.hooks.max_file_bytes(), 64 * 1024 * 1024);
+
+        let config = Config::from_toml_str(
+            "[hooks]
+max_file_mb = 2
+",
+        )
+        .unwrap();
+        assert_eq!(config.hooks.max_file_bytes(), 2 * 1024 * 1024);
+        assert!(config.hooks.cache);
+        assert_eq!(config.hooks.cache_dir, None);
+
+        let config =
+            Config::from_toml_str("[hooks]\ncache = false\ncache_dir = \"/tmp/board\"\n").unwrap();
+        assert!(!config.hooks.cache);
+        assert_eq!(config.hooks.cache_dir, Some(PathBuf::from("/tmp/board")));
+    }
+
+    #[test]
+    fn triage_section_parses() {
+        let config = Config::from_toml_str(
+            "[triage]
+command = [\"claude\", \"-p\"]
+timeout_secs = 10
+concurrency = 1
+",
+        )
+        .unwrap();
+        assert_eq!(config.triage.command, vec!["claude", "-p"]);
+        assert_eq!(config.triage.timeout_secs, 10);
+        assert_eq!(config.triage.concurrency, 1);
+        assert_eq!(config.triage.max_queued, 32);
+    }
+
+    #[test]
+    fn retry_section_parses() {
+        let config =
+            Config::from_toml_str("[retry]\nauto = true\n\n[retry.budgets]\nNetwork = 3\n")
+                .unwrap();
+        assert!(config.retry.auto);
+        assert_eq!(config.retry.budget_for(&ErrorCategory::Network), Some(3));
+    }
+
+    #[test]
+    fn custom_statuses_parse() {
+        let config = Config::from_toml_str(
+            "[[statuses]]\nname = \"Skipped\"\nicon = \"[-]\"\ncolor = \"gray\"\ndone = true\n\n[[statuses]]\nname = \"Review\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.statuses.len(), 2);
+        assert!(config.statuses[0].done);
+        assert_eq!(config.statuses[0].icon.as_deref(), Some("[-]"));
+        assert!(!config.statuses[1].done);
+        assert_eq!(config.statuses[1].color, None);
+    }
+
+    #[test]
+    fn custom_rules_parse() {
+        let config = Config::from_toml_str(
+            "[[rules]]\npattern = \"disk full\"\nseverity = \"Fatal\"\n\n[[rules]]\npattern = \"flaky\"\ncategory = \"Network\"\nretryable = true\n",
+        )
+        .unwrap();
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(config.rules[0].severity, Some(Severity::Fatal));
+        assert_eq!(config.rules[1].category, Some(ErrorCategory::Network));
+    }
+
+    #[test]
+    fn wip_section_parses() {
+        let config = Config::from_toml_str("[wip]\nmax = 4\nper_agent = 1\n").unwrap();
+        assert_eq!(config.wip.max, Some(4));
+        assert_eq!(config.wip.per_agent, Some(1));
+    }
+
+    #[test]
+    fn approval_section_parses() {
+        assert!(!Config::default().approval.enabled);
+        let config = Config::from_toml_str("[approval]\nenabled = true\n").unwrap();
+        assert!(config.approval.enabled);
+    }
+
+    #[test]
+    fn progress_section_parses() {
+        assert!(!Config::default().progress.by_points);
+        let config = Config::from_toml_str("[progress]\nby_points = true\n").unwrap();
+        assert!(config.progress.by_points);
+        assert_eq!(config.progress.default_points, 1);
+    }
+
+    #[test]
+    fn tasks_format_parses() {
+        assert_eq!(Config::default().tasks.format, TaskFormat::Auto);
+        let config = Config::from_toml_str("[tasks]\nformat = \"checkboxes\"\n").unwrap();
+        assert_eq!(config.tasks.format, TaskFormat::Checkboxes);
+        assert!(Config::from_toml_str("[tasks]\nformat = \"yaml\"\n").is_err());
+    }
+
+    #[test]
+    fn scripts_section_parses() {
+        let config = Config::from_toml_str(
+            "[scripts]\non_task_failed = [\"./notify.sh\"]\nstatusbar = [\"sh\", \"-c\", \"echo hi\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.scripts.on_task_failed, vec!["./notify.sh"]);
+        assert_eq!(config.scripts.statusbar.len(), 3);
+        assert!(config.scripts.on_phase_complete.is_empty());
+        assert_eq!(config.scripts.timeout_secs, 10);
+        assert!(config.scripts.on_transition.is_empty());
+
+        let config = Config::from_toml_str(
+            "[[scripts.on_transition]]\nto = \"failed\"\ncommand = [\"./collect-logs.sh\", \"{task_id}\"]\n",
+        )
+        .unwrap();
+        let trigger = &config.scripts.on_transition[0];
+        assert_eq!(trigger.to.as_deref(), Some("failed"));
+        assert!(trigger.from.is_none());
+        assert_eq!(trigger.command, vec!["./collect-logs.sh", "{task_id}"]);
+    }
+
+    #[test]
+    fn display_timezone_parses() {
+        assert_eq!(Config::default().display.timezone, DisplayZone::Local);
+        let config = Config::from_toml_str("[display]\ntimezone = \"utc\"\n").unwrap();
+        assert_eq!(config.display.timezone, DisplayZone::Utc);
+        assert!(Config::from_toml_str("[display]\ntimezone = \"Mars\"\n").is_err());
+    }
+
+    #[test]
+    fn display_line_numbers_parse() {
+        assert_eq!(Config::default().display.line_numbers, LineNumbers::Off);
+        let config = Config::from_toml_str("[display]\nline_numbers = \"relative\"\n").unwrap();
+        assert_eq!(config.display.line_numbers, LineNumbers::Relative);
+    }
+
+    #[test]
+    fn display_layout_parses() {
+        assert_eq!(Config::default().display.layout, LayoutPreset::Standard);
+        let config = Config::from_toml_str("[display]\nlayout = \"ops\"\n").unwrap();
+        assert_eq!(config.display.layout, LayoutPreset::Ops);
+    }
+
+    #[test]
+    fn agents_section_parses() {
+        assert_eq!(Config::default().agents.hide_idle_after_mins, None);
+        let config = Config::from_toml_str("[agents]\nhide_idle_after_mins = 30\n").unwrap();
+        assert_eq!(config.agents.hide_idle_after_mins, Some(30));
+        assert_eq!(config.agents.sessions, SessionDisplay::Merge);
+        let config = Config::from_toml_str("[agents]\nsessions = \"split\"\n").unwrap();
+        assert_eq!(config.agents.sessions, SessionDisplay::Split);
+    }
+
+    #[test]
+    fn agent_groups_parse_and_reject_bad_patterns() {
+        let config = Config::from_toml_str(
+            "[[agents.groups]]\npattern = '^backend-specialist-\\d+$'\ngroup = \"backend\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.agents.groups.len(), 1);
+        assert_eq!(config.agents.groups[0].group, "backend");
+
+        let err = Config::from_toml_str("[[agents.groups]]\npattern = \"(\"\ngroup = \"x\"\n");
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn agent_roster_parses() {
+        let config = Config::from_toml_str(
+            "[[agents.roster]]\nname = \"@backend\"\nrole = \"API\"\nmax_parallel = 2\n\n\
+             [[agents.roster]]\nname = \"frontend\"\n",
+        )
+        .unwrap();
+        let roster = &config.agents.roster;
+        assert_eq!(roster.len(), 2);
+        assert_eq!(roster[0].max_parallel, Some(2));
+        assert_eq!(roster[1].role, None);
+    }
+
+    #[test]
+    fn task_templates_parse() {
+        let config = Config::from_toml_str(
+            "[[templates]]\nlabel = \"tests\"\nname = \"Write tests for {input}\"\n\
+             agent = \"@qa\"\nchecklist = [\"happy path\", \"error cases\"]\n\n\
+             [[templates]]\nlabel = \"spike\"\nname = \"Spike: {input}\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.templates.len(), 2);
+        assert_eq!(config.templates[0].checklist.len(), 2);
+        assert_eq!(config.templates[1].agent, None);
+    }
+
+    #[test]
+    fn tick_section_parses() {
+        assert_eq!(Config::default().tick.interval_ms, 250);
+        let config = Config::from_toml_str("[tick]\nidle_ms = 2000\n").unwrap();
+        assert_eq!(config.tick.idle_ms, 2000);
+        assert_eq!(config.tick.burst_ms, 100);
+    }
+
+    #[test]
+    fn unknown_category_is_an_error() {
+        assert!(Config::from_toml_str("[retry.budgets]\nCosmic = 3\n").is_err());
+    }
+
+    #[test]
+    fn load_missing_file_fails() {
+        assert!(Config::load(Path::new("/nonexistent/.claude-board.toml")).is_err());
+    }
+}
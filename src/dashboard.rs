@@ -0,0 +1,176 @@
+//! Headless dashboard facade
+//!
+//! [`Dashboard`] is the TASKS.md/hook-event engine without the terminal UI:
+//! load the watched paths into a [`DashboardState`], take snapshots, and
+//! subscribe to file changes. Depend on the crate with
+//! `default-features = false` to leave out ratatui and crossterm.
+//!
+//! ```no_run
+//! use simple_claude_board::config::Config;
+//! use simple_claude_board::dashboard::Dashboard;
+//! use simple_claude_board::data::watcher::WatchConfig;
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let paths = WatchConfig::new("TASKS.md".into(), ".claude/hooks".into());
+//! let mut dashboard = Dashboard::load(paths, Config::default());
+//! println!("{:.0}% done", dashboard.state().overall_progress * 100.0);
+//!
+//! let (_watcher, mut changes) = dashboard.subscribe()?;
+//! while let Some(change) = changes.recv().await {
+//!     dashboard.apply(&change);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+use notify::RecommendedWatcher;
+use tokio::sync::mpsc;
+
+use crate::config::Config;
+use crate::data::clock::Clock;
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::ParseError;
+use crate::data::tasks_source;
+use crate::data::watcher::{self, FileChange, WatchConfig, WatcherError};
+
+/// Dashboard state kept in sync with the files it was loaded from
+#[derive(Debug)]
+pub struct Dashboard {
+    paths: WatchConfig,
+    state: DashboardState,
+}
+
+impl Dashboard {
+    /// Load the tasks file and existing hook events. Loading never fails: a
+    /// missing or unparsable tasks file leaves no phases and is reported in
+    /// `state().parse_warnings`, and missing hook directories are skipped.
+    pub fn load(paths: WatchConfig, config: Config) -> Self {
+        let mut state = DashboardState {
+            tasks_source: tasks_source::for_path(&paths.tasks_path, config.tasks.format),
+            ..DashboardState::default()
+        };
+        match state.read_tasks_file(&paths.tasks_path) {
+            Ok(content) => {
+                let _ = state.reload_tasks(&content);
+            }
+            Err(e) => state.parse_warnings = vec![ParseError::Read(e.to_string())],
+        }
+        state.apply_config(&config);
+        for dir in paths.event_dirs() {
+            if dir.is_dir() {
+                let _ = state.load_hook_events(dir);
+            }
+        }
+        Self { paths, state }
+    }
+
+    /// Tell time by `clock`, e.g. a [`ManualClock`](crate::data::clock::ManualClock)
+    /// stepped along with a replay of recorded events
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.state.clock = clock;
+        self
+    }
+
+    /// The current state
+    pub fn state(&self) -> &DashboardState {
+        &self.state
+    }
+
+    /// An owned copy of the current state, e.g. to hand to another thread
+    pub fn snapshot(&self) -> DashboardState {
+        self.state.clone()
+    }
+
+    pub fn into_state(self) -> DashboardState {
+        self.state
+    }
+
+    /// Paths being tracked
+    pub fn paths(&self) -> &WatchConfig {
+        &self.paths
+    }
+
+    /// Start watching the paths. Keep the watcher alive for as long as the
+    /// receiver should get changes, and pass each one to [`apply`](Self::apply).
+    pub fn subscribe(
+        &self,
+    ) -> Result<(RecommendedWatcher, mpsc::UnboundedReceiver<FileChange>), WatcherError> {
+        watcher::start_watching(self.paths.clone())
+    }
+
+    /// Update the state for one file change
+    pub fn apply(&mut self, change: &FileChange) {
+        match change {
+            FileChange::TasksModified(path) => {
+                if let Ok(content) = self.state.read_tasks_file(path) {
+                    let _ = self.state.reload_tasks(&content);
+                }
+            }
+            FileChange::HookEventCreated(path) | FileChange::HookEventModified(path) => {
+                if let Ok(result) = self.state.read_hook_file(path) {
+                    self.state.record_truncation(path, result.skipped_bytes);
+                    self.state.record_lossy_lines(path, result.lossy_lines);
+                    self.state.reload_hook_file(path, result.events);
+                }
+            }
+            // Keep the last plan: a save by rename is followed by a modify
+            FileChange::TasksRemoved(_) => {}
+            FileChange::ConfigModified(path) => {
+                // An invalid config keeps the previous settings
+                if let Ok(config) = Config::load(path) {
+                    self.state.tasks_source =
+                        tasks_source::for_path(&self.paths.tasks_path, config.tasks.format);
+                    self.state.apply_config(&config);
+                    if let Ok(content) = self.state.read_tasks_file(&self.paths.tasks_path) {
+                        let _ = self.state.reload_tasks(&content);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_apply_and_snapshot() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks = tmp.path().join("TASKS.md");
+        let hooks = tmp.path().join("hooks");
+        std::fs::create_dir(&hooks).unwrap();
+        std::fs::write(&tasks, "# Phase 1: A\n### [x] T1: A\n### [ ] T2: B\n").unwrap();
+        std::fs::write(
+            hooks.join("s.jsonl"),
+            r#"{"event_type":"agent_start","agent_id":"main","task_id":"T2","session_id":"s1","timestamp":"2026-02-08T00:00:00Z"}"#,
+        )
+        .unwrap();
+
+        let mut dashboard =
+            Dashboard::load(WatchConfig::new(tasks.clone(), hooks), Config::default());
+        assert_eq!(dashboard.state().completed_tasks, 1);
+        assert!(dashboard.state().agents.contains_key("main"));
+
+        let before = dashboard.snapshot();
+        std::fs::write(&tasks, "# Phase 1: A\n### [x] T1: A\n### [x] T2: B\n").unwrap();
+        dashboard.apply(&FileChange::TasksModified(tasks));
+        assert_eq!(dashboard.state().completed_tasks, 2);
+        assert_eq!(before.completed_tasks, 1);
+    }
+
+    #[test]
+    fn missing_tasks_file_is_a_warning() {
+        let dashboard = Dashboard::load(
+            WatchConfig::new("/nonexistent/TASKS.md".into(), "/nonexistent/hooks".into()),
+            Config::default(),
+        );
+        assert!(dashboard.state().phases.is_empty());
+        assert!(matches!(
+            dashboard.state().parse_warnings[..],
+            [ParseError::Read(_)]
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+//! Agent grouping rules
+//!
+//! Hooks report agents under session-suffixed IDs (`backend-specialist-1`,
+//! `backend-specialist-2`). The `[[agents.groups]]` config section maps IDs
+//! matching a regex to one display group, so the agents panel and per-agent
+//! stats count them as one agent.
+//!
+//! Separate sessions often reuse an agent ID (each one has a `main`). The
+//! state always tracks each (session, agent ID) pair on its own; `sessions`
+//! in `[agents]` only decides whether the panel shows them as one agent
+//! ([`SessionDisplay::Merge`]) or one agent per session.
+
+use std::borrow::Cow;
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+
+/// One rule from the `[[agents.groups]]` config section
+#[derive(Debug, Clone, Deserialize)]
+pub struct AgentGroup {
+    /// Regex matched against the hook's agent ID
+    pub pattern: Pattern,
+    /// Display group; `$1` or `$name` expand to the pattern's captures
+    pub group: String,
+}
+
+/// How agents with the same ID in different sessions are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionDisplay {
+    /// One agent per display ID, its sessions listed as members
+    #[default]
+    Merge,
+    /// One agent per session, shown as `ID@session` (first 8 characters)
+    Split,
+}
+
+/// A regex that deserializes from its source string
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(Self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Display ID for a hook agent ID: the first matching rule's group, or the
+/// ID itself when no rule matches
+pub fn resolve<'a>(groups: &[AgentGroup], agent_id: &'a str) -> Cow<'a, str> {
+    for rule in groups {
+        if let Some(caps) = rule.pattern.0.captures(agent_id) {
+            let mut group = String::new();
+            caps.expand(&rule.group, &mut group);
+            return Cow::Owned(group);
+        }
+    }
+    Cow::Borrowed(agent_id)
+}
+
+/// Display ID for a hook agent ID in a session: [`resolve`]d, and with
+/// [`SessionDisplay::Split`] suffixed with the start of the session ID
+pub fn display_id(
+    groups: &[AgentGroup],
+    sessions: SessionDisplay,
+    agent_id: &str,
+    session_id: &str,
+) -> String {
+    let id = resolve(groups, agent_id);
+    match sessions {
+        SessionDisplay::Merge => id.into_owned(),
+        SessionDisplay::Split => {
+            let short: String = session_id.chars().take(8).collect();
+            format!("{id}@{short}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, group: &str) -> AgentGroup {
+        AgentGroup {
+            pattern: Pattern::new(pattern).unwrap(),
+            group: group.to_string(),
+        }
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let groups = [
+            rule(r"^backend-specialist-\d+$", "backend"),
+            rule(r"^backend", "other-backend"),
+        ];
+        assert_eq!(resolve(&groups, "backend-specialist-2"), "backend");
+        assert_eq!(resolve(&groups, "backend-reviewer"), "other-backend");
+        assert_eq!(resolve(&groups, "frontend-1"), "frontend-1");
+    }
+
+    #[test]
+    fn group_expands_captures() {
+        let groups = [rule(r"^(?P<role>[a-z-]+?)-\d+$", "$role")];
+        assert_eq!(resolve(&groups, "test-writer-3"), "test-writer");
+    }
+
+    #[test]
+    fn documented_config_key_groups_agents() {
+        let config = crate::config::Config::from_toml_str(
+            "[[agents.groups]]\npattern = '^backend-specialist-\\d+$'\ngroup = \"backend\"\n",
+        )
+        .unwrap();
+        let groups = &config.agents.groups;
+        assert_eq!(resolve(groups, "backend-specialist-1"), "backend");
+        assert_eq!(resolve(groups, "backend-specialist-2"), "backend");
+    }
+
+    #[test]
+    fn split_sessions_suffix_the_display_id() {
+        let groups = [rule(r"^backend-\d+$", "backend")];
+        let id = |sessions, agent| display_id(&groups, sessions, agent, "3f2a1b9c-77e0");
+        assert_eq!(id(SessionDisplay::Merge, "main"), "main");
+        assert_eq!(id(SessionDisplay::Split, "main"), "main@3f2a1b9c");
+        assert_eq!(id(SessionDisplay::Split, "backend-2"), "backend@3f2a1b9c");
+    }
+}
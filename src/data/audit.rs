@@ -0,0 +1,186 @@
+//! Audit log of board-initiated writes
+//!
+//! Separate from the debug log: every change the board makes (retry,
+//! approval, bounce, note, move, dispatch, agent stop, and the retry
+//! policy's automatic retries and blocks) is appended to a JSONL
+//! file with who did it, when, and the task before and after. Writes that
+//! turn out to be no-ops (the task was edited away in the meantime) are not
+//! recorded, so each line matches a real change to TASKS.md. Transition
+//! triggers (`[[scripts.on_transition]]`) are recorded too, with the command
+//! and what it printed.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::data::tasks_doc::{Segment, TasksDoc};
+
+/// `[audit]` config section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AuditConfig {
+    /// Record board-initiated writes (default: on)
+    pub enabled: bool,
+    /// Audit file (default: `audit.jsonl` in the events dir)
+    pub file: Option<PathBuf>,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            file: None,
+        }
+    }
+}
+
+/// Where a task sits in TASKS.md and what its section says
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TaskSnapshot {
+    /// Enclosing phase, if the task is under one
+    pub phase: Option<String>,
+    /// Zero-based position among the phase's tasks
+    pub position: usize,
+    /// Header and body lines as written
+    pub section: String,
+}
+
+impl TaskSnapshot {
+    pub fn capture(doc: &TasksDoc, task_id: &str) -> Option<Self> {
+        let mut phase = None;
+        let mut position = 0;
+        for segment in doc.segments() {
+            match segment {
+                Segment::Phase(heading) => {
+                    phase = Some(heading.id.clone());
+                    position = 0;
+                }
+                Segment::Task(task) if task.id == task_id => {
+                    return Some(Self {
+                        phase,
+                        position,
+                        section: task.lines.concat().trim_end().replace("\r\n", "\n"),
+                    });
+                }
+                Segment::Task(_) => position += 1,
+                Segment::Text(_) => {}
+            }
+        }
+        None
+    }
+}
+
+/// One line of the audit file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditEntry {
+    pub at: DateTime<Utc>,
+    /// `$USER` of the board process
+    pub user: Option<String>,
+    /// What was asked for: "retry", "approve", "stop-agent", ...
+    pub action: String,
+    /// Task ID, or agent ID for "stop-agent"
+    pub target: String,
+    pub old: Option<TaskSnapshot>,
+    pub new: Option<TaskSnapshot>,
+    /// Command a "trigger" entry ran
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// What the command printed, or why it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new(
+        action: &str,
+        target: &str,
+        old: Option<TaskSnapshot>,
+        new: Option<TaskSnapshot>,
+    ) -> Self {
+        Self {
+            at: Utc::now(),
+            user: std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .ok(),
+            action: action.to_string(),
+            target: target.to_string(),
+            old,
+            new,
+            command: None,
+            output: None,
+        }
+    }
+
+    /// A command the board ran for `target` and its output
+    pub fn with_run(mut self, command: &str, output: String) -> Self {
+        self.command = Some(command.to_string());
+        self.output = Some(output);
+        self
+    }
+}
+
+/// Append an entry as one JSON line, creating the file and its directory
+pub fn append(path: &Path, entry: &AuditEntry) -> std::io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{json}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "# Phase 1: Core\n### [ ] T1: A\n### [Failed] T2: B\n- note\n\n# Phase 2: UI\n### [ ] T3: C\n";
+
+    #[test]
+    fn snapshot_records_phase_position_and_section() {
+        let doc = TasksDoc::parse(DOC);
+        let t2 = TaskSnapshot::capture(&doc, "T2").unwrap();
+        assert_eq!(t2.phase.as_deref(), Some("P1"));
+        assert_eq!(t2.position, 1);
+        assert_eq!(t2.section, "### [Failed] T2: B\n- note");
+        let t3 = TaskSnapshot::capture(&doc, "T3").unwrap();
+        assert_eq!((t3.phase.as_deref(), t3.position), (Some("P2"), 0));
+        assert!(TaskSnapshot::capture(&doc, "T9").is_none());
+    }
+
+    #[test]
+    fn append_writes_one_json_line_per_entry() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("nested").join("audit.jsonl");
+        let doc = TasksDoc::parse(DOC);
+        let old = TaskSnapshot::capture(&doc, "T2");
+        append(&path, &AuditEntry::new("retry", "T2", old, None)).unwrap();
+        append(&path, &AuditEntry::new("stop-agent", "backend", None, None)).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["action"], "retry");
+        assert_eq!(lines[0]["old"]["section"], "### [Failed] T2: B\n- note");
+        assert!(lines[0]["new"].is_null());
+        assert_eq!(lines[1]["target"], "backend");
+        assert!(lines[1].get("command").is_none());
+    }
+
+    #[test]
+    fn trigger_entry_carries_command_and_output() {
+        let entry = AuditEntry::new("trigger", "T2", None, None)
+            .with_run("./collect-logs.sh T2", "saved 3 files\n".to_string());
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&entry).unwrap()).unwrap();
+        assert_eq!(json["command"], "./collect-logs.sh T2");
+        assert_eq!(json["output"], "saved 3 files\n");
+    }
+}
@@ -0,0 +1,102 @@
+//! Time source
+//!
+//! Everything that asks what time it is (the live `now` of the state, the
+//! uptime and run clocks, retry waits, the tasks-file grace period) asks a
+//! [`Clock`] held by [`DashboardState`](crate::data::state::DashboardState).
+//! The board runs on [`SystemClock`]; tests and replays install a
+//! [`ManualClock`] and move it by hand, so what renders at a given moment
+//! does not depend on when the code happens to run.
+
+use std::fmt::Debug;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Wall-clock and monotonic time
+pub trait Clock: Debug + Send + Sync {
+    /// The current wall-clock time
+    fn now(&self) -> DateTime<Utc>;
+
+    /// The current monotonic instant, for measuring elapsed time
+    fn instant(&self) -> Instant;
+}
+
+/// The system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock standing still until it is set or advanced. Its instants move in
+/// step with its wall-clock time, starting from when it was created.
+#[derive(Debug)]
+pub struct ManualClock {
+    start: DateTime<Utc>,
+    origin: Instant,
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            start: now,
+            origin: Instant::now(),
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Move to `now`; instants never go back past the clock's start
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    /// Move forward (or back, with a negative duration) by `by`
+    pub fn advance(&self, by: Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn instant(&self) -> Instant {
+        let since_start = (self.now() - self.start).to_std().unwrap_or_default();
+        self.origin + since_start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manual_clock_moves_only_when_told() {
+        let start = DateTime::parse_from_rfc3339("2026-02-08T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let clock = ManualClock::new(start);
+        let origin = clock.instant();
+        assert_eq!(clock.now(), start);
+        assert_eq!(clock.instant(), origin);
+
+        clock.advance(Duration::seconds(90));
+        assert_eq!(clock.now(), start + Duration::seconds(90));
+        assert_eq!(clock.instant() - origin, std::time::Duration::from_secs(90));
+
+        // Back before the start: the instant stops at the origin
+        clock.set(start - Duration::hours(1));
+        assert_eq!(clock.instant(), origin);
+    }
+}
@@ -0,0 +1,173 @@
+//! Task context bundle
+//!
+//! Everything the board knows about one task, as a markdown block to paste
+//! into a fresh Claude session when re-prompting: the task body, its
+//! dependencies, errors with suggestions, files the hook events mention and
+//! the latest agent events.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::data::hook_parser::HookEvent;
+use crate::data::state::DashboardState;
+
+/// Agent events included, newest last
+const RECENT_EVENTS: usize = 20;
+
+/// Files listed at most
+const MAX_FILES: usize = 30;
+
+/// Path-like tokens with an extension, e.g. `src/app.rs` or `./web/index.tsx`
+fn path_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?:[~.]?/)?(?:[\w.-]+/)+[\w.-]+\.[A-Za-z0-9]+")
+            .expect("file path regex is valid")
+    })
+}
+
+/// Paths mentioned in the errors and tool output of `events`, first
+/// mention first
+pub fn files_touched(events: &[&HookEvent]) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    let texts = events.iter().flat_map(|e| {
+        [e.error_message.as_deref(), e.tool_output.as_deref()]
+            .into_iter()
+            .flatten()
+    });
+    for text in texts {
+        for found in path_regex().find_iter(text) {
+            let path = found.as_str().trim_end_matches('.');
+            if !files.iter().any(|f| f == path) {
+                files.push(path.to_string());
+            }
+        }
+    }
+    files.truncate(MAX_FILES);
+    files
+}
+
+/// Markdown context for `task_id`, or None if the plan has no such task
+pub fn bundle(state: &DashboardState, task_id: &str) -> Option<String> {
+    let (pi, _) = state.task_position(task_id)?;
+    let phase = &state.phases[pi];
+    let task = state.task(task_id)?;
+    let events: Vec<&HookEvent> = state
+        .events
+        .iter()
+        .filter(|e| e.task_id == task_id)
+        .collect();
+
+    let mut out = format!("# {}: {}\n\n", task.id, task.name);
+    out.push_str(&format!(
+        "- **Phase**: {} - {}\n- **Status**: {}\n",
+        phase.id,
+        phase.name,
+        task.status.label()
+    ));
+    if let Some(worker) = state.worker_for(task) {
+        out.push_str(&format!("- **Agent**: {worker}\n"));
+    }
+
+    let body = task.body.trim();
+    if !body.is_empty() {
+        out.push_str(&format!("\n## Task\n\n{body}\n"));
+    }
+
+    if !task.blocked_by.is_empty() {
+        out.push_str("\n## Dependencies\n\n");
+        for dep in &task.blocked_by {
+            match state.task(dep) {
+                Some(d) => {
+                    out.push_str(&format!("- {}: {} ({})\n", d.id, d.name, d.status.label()))
+                }
+                None => out.push_str(&format!("- {dep} (not in the plan)\n")),
+            }
+        }
+    }
+
+    let errors: Vec<_> = state
+        .recent_errors
+        .iter()
+        .filter(|e| e.task_id == task_id)
+        .collect();
+    if !errors.is_empty() {
+        out.push_str("\n## Errors\n\n");
+        for err in errors {
+            out.push_str(&format!(
+                "- {} [{:?}/{:?}] {}\n  - Suggestion: {}\n",
+                state.timezone.format(err.timestamp, "%Y-%m-%d %H:%M:%S"),
+                err.category,
+                err.severity,
+                err.message.trim(),
+                err.display_suggestion()
+            ));
+        }
+    }
+
+    let files = files_touched(&events);
+    if !files.is_empty() {
+        out.push_str("\n## Files touched\n\n");
+        for file in files {
+            out.push_str(&format!("- {file}\n"));
+        }
+    }
+
+    if !events.is_empty() {
+        out.push_str("\n## Recent agent events\n\n");
+        let skip = events.len().saturating_sub(RECENT_EVENTS);
+        for e in &events[skip..] {
+            let event = serde_json::to_value(&e.event_type)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "- {} {} {event}",
+                state.timezone.format(e.timestamp, "%H:%M:%S"),
+                e.agent_id
+            ));
+            if let Some(tool) = &e.tool_name {
+                out.push_str(&format!(" {tool}"));
+            }
+            out.push('\n');
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+    use crate::data::timezone::DisplayZone;
+
+    #[test]
+    fn bundle_collects_body_deps_errors_files_and_events() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: API\n### [x] T1: Schema\n### [ ] T2: Handlers\n- blocked_by: T1\n- Add the /users route\n",
+        )
+        .unwrap();
+        state.timezone = DisplayZone::Utc;
+        let events = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"backend","task_id":"T2","session_id":"s1"}"#,
+            r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:00:05Z","agent_id":"backend","task_id":"T2","session_id":"s1","tool_name":"Edit","tool_output":"Updated src/routes/users.rs."}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:00:09Z","agent_id":"backend","task_id":"T2","session_id":"s1","error_message":"connection refused at src/db.rs:12"}"#,
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:01:00Z","agent_id":"other","task_id":"T1","session_id":"s2"}"#,
+        ];
+        state.update_from_events(&parse_hook_events(&events.join("\n")).events);
+
+        let md = bundle(&state, "T2").unwrap();
+        assert!(md.starts_with("# T2: Handlers\n\n- **Phase**: P1 - API\n"));
+        assert!(md.contains("- **Agent**: backend\n"));
+        assert!(md.contains("## Task\n\n"));
+        assert!(md.contains("Add the /users route"));
+        assert!(md.contains("## Dependencies\n\n- T1: Schema (Completed)\n"));
+        assert!(md.contains("connection refused at src/db.rs:12\n  - Suggestion: "));
+        assert!(md.contains("## Files touched\n\n- src/routes/users.rs\n- src/db.rs\n"));
+        assert!(md.contains("- 10:00:05 backend tool_end Edit\n"));
+        assert!(!md.contains("other"));
+
+        assert!(bundle(&state, "T9").is_none());
+    }
+}
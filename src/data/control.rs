@@ -0,0 +1,134 @@
+//! Agent control channel
+//!
+//! The board asks an agent to stop by writing `<dir>/<agent>.stop`. The
+//! bundled `event-logger.js` hook checks for that file before each tool call,
+//! blocks the call, and removes the file. When `pid_dir` is configured and
+//! holds `<session>.pid`, that process is also sent SIGTERM.
+
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// `[control]` config section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ControlConfig {
+    /// Where stop files are written (default: `control/` in the events dir)
+    pub dir: Option<PathBuf>,
+    /// Directory of `<session_id>.pid` files to signal on stop
+    pub pid_dir: Option<PathBuf>,
+}
+
+/// Contents of a stop file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct StopRequest<'a> {
+    agent_id: &'a str,
+    session_id: Option<&'a str>,
+    requested_at: String,
+}
+
+/// How a stop request was delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StopOutcome {
+    /// A `<session>.pid` process was sent SIGTERM
+    pub signalled: bool,
+}
+
+/// Agent or task IDs as file names: anything but `[A-Za-z0-9._-]` becomes `_`
+pub(crate) fn file_stem(agent_id: &str) -> String {
+    agent_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Stop file for an agent
+pub fn stop_file(dir: &Path, agent_id: &str) -> PathBuf {
+    dir.join(format!("{}.stop", file_stem(agent_id)))
+}
+
+/// Ask an agent to stop: write its stop file, then signal the session's
+/// process if a pid file for it exists
+pub fn request_stop(
+    dir: &Path,
+    pid_dir: Option<&Path>,
+    agent_id: &str,
+    session_id: Option<&str>,
+) -> std::io::Result<StopOutcome> {
+    std::fs::create_dir_all(dir)?;
+    let request = StopRequest {
+        agent_id,
+        session_id,
+        requested_at: Utc::now().to_rfc3339(),
+    };
+    let json = serde_json::to_string(&request).map_err(std::io::Error::other)?;
+    std::fs::write(stop_file(dir, agent_id), json + "\n")?;
+
+    let pid = pid_dir
+        .zip(session_id)
+        .and_then(|(d, s)| std::fs::read_to_string(d.join(format!("{}.pid", file_stem(s)))).ok())
+        .and_then(|s| s.trim().parse::<u32>().ok());
+    Ok(StopOutcome {
+        signalled: pid.is_some_and(terminate),
+    })
+}
+
+#[cfg(unix)]
+fn terminate(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-TERM", &pid.to_string()])
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+#[cfg(not(unix))]
+fn terminate(_pid: u32) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stop_writes_request_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("control");
+        let outcome = request_stop(&dir, None, "backend/specialist", Some("sess-1")).unwrap();
+        assert!(!outcome.signalled);
+
+        let path = stop_file(&dir, "backend/specialist");
+        assert_eq!(path.file_name().unwrap(), "backend_specialist.stop");
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.contains(r#""agent_id":"backend/specialist""#));
+        assert!(content.contains(r#""session_id":"sess-1""#));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stop_signals_session_pid() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        std::fs::write(tmp.path().join("sess-1.pid"), child.id().to_string()).unwrap();
+
+        let outcome = request_stop(
+            &tmp.path().join("control"),
+            Some(tmp.path()),
+            "main",
+            Some("sess-1"),
+        )
+        .unwrap();
+        assert!(outcome.signalled);
+        assert!(!child.wait().unwrap().success());
+    }
+}
@@ -0,0 +1,489 @@
+//! Token and cost accounting
+//!
+//! A hook event may carry a `usage` object (`input_tokens`, `output_tokens`
+//! and optionally `cost_usd`), as `event-logger.js` sends with `agent_end`.
+//! Usage is summed per task and per agent; tokens without a reported cost are
+//! priced with the `[cost]` per-million-token rates. With a budget set, the
+//! status bar shows spend against it and the board alerts once it is
+//! exceeded. [`breakdown`] groups the spend by phase, task or agent for the
+//! costs view (`$`) and `simple-claude-board export`.
+
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::state::DashboardState;
+
+/// Usage reported on one hook event
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Usage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Cost the hook computed itself; None prices the tokens instead
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+}
+
+/// Accumulated usage of a task, an agent or the whole run
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Spend {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Sum of reported `cost_usd`
+    pub reported_usd: f64,
+    /// Tokens of events without a reported cost, priced on display
+    pub unpriced_input: u64,
+    pub unpriced_output: u64,
+}
+
+impl Spend {
+    pub fn add(&mut self, usage: &Usage) {
+        self.input_tokens += usage.input_tokens;
+        self.output_tokens += usage.output_tokens;
+        match usage.cost_usd {
+            Some(cost) => self.reported_usd += cost,
+            None => {
+                self.unpriced_input += usage.input_tokens;
+                self.unpriced_output += usage.output_tokens;
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: &Spend) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.reported_usd += other.reported_usd;
+        self.unpriced_input += other.unpriced_input;
+        self.unpriced_output += other.unpriced_output;
+    }
+
+    pub fn tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+
+    /// Reported cost plus the unpriced tokens at `config`'s rates
+    pub fn usd(&self, config: &CostConfig) -> f64 {
+        self.reported_usd
+            + self.unpriced_input as f64 * config.input_per_mtok / 1e6
+            + self.unpriced_output as f64 * config.output_per_mtok / 1e6
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tokens() == 0 && self.reported_usd == 0.0
+    }
+}
+
+/// `[cost]` section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CostConfig {
+    /// USD per million input tokens without a reported cost
+    pub input_per_mtok: f64,
+    /// USD per million output tokens without a reported cost
+    pub output_per_mtok: f64,
+    /// Run budget in USD; None shows spend without a limit
+    pub budget_usd: Option<f64>,
+    /// Fraction of the budget at which the status bar turns yellow
+    pub warn_at: f64,
+    /// URL that gets a JSON POST when the budget is exceeded
+    pub webhook: Option<String>,
+}
+
+impl Default for CostConfig {
+    fn default() -> Self {
+        Self {
+            input_per_mtok: 3.0,
+            output_per_mtok: 15.0,
+            budget_usd: None,
+            warn_at: 0.8,
+            webhook: None,
+        }
+    }
+}
+
+/// Spend relative to the budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetLevel {
+    Under,
+    Warning,
+    Over,
+}
+
+impl CostConfig {
+    /// Where `spent` stands against the budget, or None without one
+    pub fn level(&self, spent: f64) -> Option<BudgetLevel> {
+        let budget = self.budget_usd?;
+        Some(if spent > budget {
+            BudgetLevel::Over
+        } else if spent >= budget * self.warn_at {
+            BudgetLevel::Warning
+        } else {
+            BudgetLevel::Under
+        })
+    }
+}
+
+/// `$4.20`, with more precision for small amounts
+pub fn format_usd(usd: f64) -> String {
+    if usd < 1.0 {
+        format!("${usd:.3}")
+    } else {
+        format!("${usd:.2}")
+    }
+}
+
+/// `950`, `48.2k`, `1.30M`
+pub fn format_tokens(tokens: u64) -> String {
+    match tokens {
+        0..=999 => tokens.to_string(),
+        1_000..=999_999 => format!("{:.1}k", tokens as f64 / 1e3),
+        _ => format!("{:.2}M", tokens as f64 / 1e6),
+    }
+}
+
+/// What a cost breakdown is grouped by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CostGroup {
+    #[default]
+    Phase,
+    Task,
+    Agent,
+}
+
+impl CostGroup {
+    pub fn next(self) -> Self {
+        match self {
+            CostGroup::Phase => CostGroup::Task,
+            CostGroup::Task => CostGroup::Agent,
+            CostGroup::Agent => CostGroup::Phase,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CostGroup::Phase => "phase",
+            CostGroup::Task => "task",
+            CostGroup::Agent => "agent",
+        }
+    }
+}
+
+/// Column a cost breakdown is sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CostSort {
+    /// Costliest first
+    #[default]
+    Cost,
+    /// Most tokens first
+    Tokens,
+    /// Plan order; agents by ID
+    Id,
+}
+
+impl CostSort {
+    pub const ALL: [CostSort; 3] = [CostSort::Id, CostSort::Tokens, CostSort::Cost];
+
+    /// The column left (`-1`) or right (`1`) of this one, wrapping
+    pub fn step(self, by: i32) -> Self {
+        let i = Self::ALL.iter().position(|s| *s == self).unwrap_or(0) as i32;
+        Self::ALL[(i + by).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+}
+
+/// One line of a cost breakdown
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CostRow {
+    /// Phase, task or agent ID; `-` for spend outside the plan
+    pub id: String,
+    /// Phase or task name; empty for agents and unplanned tasks
+    pub name: String,
+    /// Phase of a task row
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
+    /// Status of a task row
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl CostRow {
+    fn new(id: &str, name: &str, spend: &Spend, config: &CostConfig) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            phase: None,
+            status: None,
+            input_tokens: spend.input_tokens,
+            output_tokens: spend.output_tokens,
+            cost_usd: spend.usd(config),
+        }
+    }
+
+    pub fn tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+/// Spend grouped by `group` and sorted by `sort`. Every planned phase or
+/// task gets a row, spent or not; usage of task IDs outside the plan (such
+/// as the main agent's `unknown`) shows up as their own task rows and as
+/// one `-` phase row.
+pub fn breakdown(
+    state: &DashboardState,
+    group: CostGroup,
+    sort: CostSort,
+    config: &CostConfig,
+) -> Vec<CostRow> {
+    let planned: BTreeSet<&str> = state
+        .phases
+        .iter()
+        .flat_map(|p| p.tasks.iter().map(|t| t.id.as_str()))
+        .collect();
+    let mut unplanned: Vec<(&str, &Spend)> = state
+        .task_spend
+        .iter()
+        .filter(|(id, _)| !planned.contains(id.as_ref()))
+        .map(|(id, spend)| (id.as_ref(), spend))
+        .collect();
+    unplanned.sort_by_key(|(id, _)| *id);
+
+    let mut rows = Vec::new();
+    match group {
+        CostGroup::Phase => {
+            for phase in &state.phases {
+                let mut spend = Spend::default();
+                for task in &phase.tasks {
+                    if let Some(task_spend) = state.task_spend.get(task.id.as_str()) {
+                        spend.merge(task_spend);
+                    }
+                }
+                rows.push(CostRow::new(&phase.id, &phase.name, &spend, config));
+            }
+            if !unplanned.is_empty() {
+                let mut spend = Spend::default();
+                for (_, task_spend) in &unplanned {
+                    spend.merge(task_spend);
+                }
+                rows.push(CostRow::new("-", "", &spend, config));
+            }
+        }
+        CostGroup::Task => {
+            for phase in &state.phases {
+                for task in &phase.tasks {
+                    let spend = state
+                        .task_spend
+                        .get(task.id.as_str())
+                        .copied()
+                        .unwrap_or_default();
+                    let mut row = CostRow::new(&task.id, &task.name, &spend, config);
+                    row.phase = Some(phase.id.clone());
+                    row.status = Some(task.status.label().to_string());
+                    rows.push(row);
+                }
+            }
+            for (id, spend) in unplanned {
+                rows.push(CostRow::new(id, "", spend, config));
+            }
+        }
+        CostGroup::Agent => {
+            let agents: BTreeSet<&str> = state
+                .agents
+                .keys()
+                .chain(state.agent_spend.keys())
+                .map(String::as_str)
+                .collect();
+            for agent in agents {
+                let spend = state.agent_spend.get(agent).copied().unwrap_or_default();
+                rows.push(CostRow::new(agent, "", &spend, config));
+            }
+        }
+    }
+
+    match sort {
+        CostSort::Cost => rows.sort_by(|a, b| {
+            b.cost_usd
+                .total_cmp(&a.cost_usd)
+                .then(b.tokens().cmp(&a.tokens()))
+        }),
+        CostSort::Tokens => rows.sort_by(|a, b| {
+            b.tokens()
+                .cmp(&a.tokens())
+                .then(b.cost_usd.total_cmp(&a.cost_usd))
+        }),
+        CostSort::Id => {}
+    }
+    rows
+}
+
+/// Write `id,name,phase,status,input_tokens,output_tokens,cost_usd` rows
+pub fn write_csv(rows: &[CostRow], mut out: impl Write) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "id,name,phase,status,input_tokens,output_tokens,cost_usd"
+    )?;
+    for row in rows {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{:.6}",
+            csv_field(&row.id),
+            csv_field(&row.name),
+            csv_field(row.phase.as_deref().unwrap_or("")),
+            csv_field(row.status.as_deref().unwrap_or("")),
+            row.input_tokens,
+            row.output_tokens,
+            row.cost_usd
+        )?;
+    }
+    Ok(())
+}
+
+/// Quote a CSV field that holds a comma, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// POST a budget alert to `url` from a background thread
+pub fn send_alert(url: &str, spent: f64, budget: f64) {
+    let body = serde_json::json!({
+        "event": "budget_exceeded",
+        "spent_usd": spent,
+        "budget_usd": budget,
+        "text": format!("Run budget exceeded: {} of {}", format_usd(spent), format_usd(budget)),
+    })
+    .to_string();
+    let url = url.to_string();
+    std::thread::spawn(move || {
+        let status = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--max-time", "10"])
+            .args(["--header", "Content-Type: application/json"])
+            .args(["--data-binary", &body])
+            .arg(&url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .status();
+        match status {
+            Ok(s) if s.success() => tracing::info!("budget alert sent to {url}"),
+            Ok(s) => tracing::warn!("budget alert to {url} failed: curl exited with {s}"),
+            Err(e) => tracing::warn!("budget alert to {url} failed: {e}"),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reported_cost_wins_over_token_pricing() {
+        let config = CostConfig::default();
+        let mut spend = Spend::default();
+        spend.add(&Usage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cost_usd: None,
+        });
+        spend.add(&Usage {
+            input_tokens: 500,
+            output_tokens: 500,
+            cost_usd: Some(0.25),
+        });
+        assert_eq!(spend.tokens(), 1_101_000);
+        // 3.00 for the input, 1.50 for the output, 0.25 reported
+        assert!((spend.usd(&config) - 4.75).abs() < 1e-9);
+        assert_eq!(format_usd(spend.usd(&config)), "$4.75");
+        assert_eq!(format_usd(0.0421), "$0.042");
+    }
+
+    #[test]
+    fn budget_levels() {
+        let mut config = CostConfig::default();
+        assert_eq!(config.level(100.0), None);
+        config.budget_usd = Some(10.0);
+        assert_eq!(config.level(7.99), Some(BudgetLevel::Under));
+        assert_eq!(config.level(8.0), Some(BudgetLevel::Warning));
+        assert_eq!(config.level(10.0), Some(BudgetLevel::Warning));
+        assert_eq!(config.level(10.01), Some(BudgetLevel::Over));
+    }
+
+    fn spent_state() -> DashboardState {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Setup\n### [x] P1-T1: Init\n### [ ] P1-T2: Config, CI\n# Phase 2: TUI\n### [ ] P2-T1: Board\n",
+        )
+        .unwrap();
+        let input = concat!(
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s","usage":{"input_tokens":1000000}}"#,
+            "\n",
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:01Z","agent_id":"b","task_id":"P2-T1","session_id":"s","usage":{"input_tokens":10,"cost_usd":5.0}}"#,
+            "\n",
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:02Z","agent_id":"main","task_id":"unknown","session_id":"s","usage":{"output_tokens":2000000}}"#,
+        );
+        state.update_from_events(&crate::data::hook_parser::parse_hook_events(input).events);
+        state
+    }
+
+    #[test]
+    fn breakdown_by_phase_task_and_agent() {
+        let state = spent_state();
+        let config = CostConfig::default();
+        let ids = |rows: Vec<CostRow>| -> Vec<String> { rows.into_iter().map(|r| r.id).collect() };
+
+        let phases = breakdown(&state, CostGroup::Phase, CostSort::Id, &config);
+        assert_eq!(ids(phases.clone()), ["P1", "P2", "-"]);
+        assert_eq!(phases[0].cost_usd, 3.0);
+        assert_eq!(phases[2].output_tokens, 2_000_000);
+
+        // Unplanned spend costs most, then the reported $5, then P1
+        let phases = breakdown(&state, CostGroup::Phase, CostSort::Cost, &config);
+        assert_eq!(ids(phases), ["-", "P2", "P1"]);
+        let phases = breakdown(&state, CostGroup::Phase, CostSort::Tokens, &config);
+        assert_eq!(ids(phases), ["-", "P1", "P2"]);
+
+        let tasks = breakdown(&state, CostGroup::Task, CostSort::Id, &config);
+        assert_eq!(ids(tasks.clone()), ["P1-T1", "P1-T2", "P2-T1", "unknown"]);
+        assert_eq!(tasks[1].tokens(), 0);
+        assert_eq!(tasks[0].phase.as_deref(), Some("P1"));
+        assert_eq!(tasks[0].status.as_deref(), Some("Completed"));
+        assert_eq!(tasks[3].phase, None);
+
+        let agents = breakdown(&state, CostGroup::Agent, CostSort::Cost, &config);
+        assert_eq!(ids(agents), ["main", "b", "a"]);
+    }
+
+    #[test]
+    fn csv_quotes_names_and_keeps_empty_columns() {
+        let state = spent_state();
+        let rows = breakdown(
+            &state,
+            CostGroup::Task,
+            CostSort::Id,
+            &CostConfig::default(),
+        );
+        let mut out = Vec::new();
+        write_csv(&rows, &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "id,name,phase,status,input_tokens,output_tokens,cost_usd"
+        );
+        assert_eq!(lines[1], "P1-T1,Init,P1,Completed,1000000,0,3.000000");
+        assert_eq!(lines[2], "P1-T2,\"Config, CI\",P1,Pending,0,0,0.000000");
+        assert_eq!(lines[4], "unknown,,,,0,2000000,30.000000");
+
+        let json = serde_json::to_value(&rows[3]).unwrap();
+        assert_eq!(json["output_tokens"], 2_000_000);
+        assert!(json.get("phase").is_none());
+        assert_eq!(format_tokens(950), "950");
+        assert_eq!(format_tokens(48_210), "48.2k");
+        assert_eq!(format_tokens(1_300_000), "1.30M");
+    }
+}
@@ -0,0 +1,171 @@
+//! Crash dumps
+//!
+//! When the TUI panics, the derived state (task timings, agents, analyzed
+//! errors) would be lost with the process. [`CrashDump`] captures it with
+//! the panic message and the latest hook events, which are written back in
+//! their JSONL shape so the run can be replayed when reporting the crash.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::data::hook_parser::HookEvent;
+use crate::data::state::DashboardState;
+
+/// Hook events kept in a dump, most recent last
+const RECENT_EVENTS: usize = 200;
+
+/// Everything worth keeping from a crashed session
+#[derive(Debug, Serialize)]
+pub struct CrashDump<'a> {
+    pub written_at: DateTime<Utc>,
+    pub version: &'static str,
+    /// Panic message and location
+    pub panic: &'a str,
+    pub completed_tasks: usize,
+    pub total_tasks: usize,
+    pub overall_progress: f32,
+    pub tasks: Vec<TaskDump<'a>>,
+    pub agents: Vec<AgentDump<'a>>,
+    pub errors: Vec<ErrorDump<'a>>,
+    pub recent_events: &'a [HookEvent],
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskDump<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub phase: &'a str,
+    pub status: &'a str,
+    pub agent: Option<&'a str>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AgentDump<'a> {
+    pub agent_id: &'a str,
+    pub status: String,
+    pub current_task: Option<&'a str>,
+    pub event_count: usize,
+    pub error_count: usize,
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorDump<'a> {
+    pub task_id: &'a str,
+    pub agent_id: &'a str,
+    pub category: String,
+    pub message: &'a str,
+    pub suggestion: &'a str,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl<'a> CrashDump<'a> {
+    pub fn new(state: &'a DashboardState, panic: &'a str) -> Self {
+        let tasks = state
+            .phases
+            .iter()
+            .flat_map(|p| p.tasks.iter().map(move |t| (p, t)))
+            .map(|(phase, task)| {
+                let timing = state.task_times.get(task.id.as_str());
+                TaskDump {
+                    id: &task.id,
+                    name: &task.name,
+                    phase: &phase.id,
+                    status: task.status.label(),
+                    agent: task.agent.as_deref(),
+                    started_at: timing.and_then(|t| t.started_at),
+                    completed_at: timing.and_then(|t| t.completed_at),
+                }
+            })
+            .collect();
+        let mut agents: Vec<AgentDump> = state
+            .agents
+            .values()
+            .map(|a| AgentDump {
+                agent_id: &a.agent_id,
+                status: format!("{:?}", a.status),
+                current_task: a.current_task.as_deref(),
+                event_count: a.event_count,
+                error_count: a.error_count,
+                last_seen: a.last_seen,
+            })
+            .collect();
+        agents.sort_by(|a, b| a.agent_id.cmp(b.agent_id));
+        let errors = state
+            .recent_errors
+            .iter()
+            .map(|e| ErrorDump {
+                task_id: &e.task_id,
+                agent_id: &e.agent_id,
+                category: format!("{:?}", e.category),
+                message: &e.message,
+                suggestion: e.display_suggestion(),
+                timestamp: e.timestamp,
+            })
+            .collect();
+        let skip = state.events.len().saturating_sub(RECENT_EVENTS);
+        Self {
+            written_at: Utc::now(),
+            version: env!("CARGO_PKG_VERSION"),
+            panic,
+            completed_tasks: state.completed_tasks,
+            total_tasks: state.total_tasks,
+            overall_progress: state.overall_progress,
+            tasks,
+            agents,
+            errors,
+            recent_events: &state.events[skip..],
+        }
+    }
+
+    /// Write the dump as pretty JSON to `dir/simple-claude-board-crash-<time>.json`
+    pub fn write(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        let path = dir.join(format!(
+            "simple-claude-board-crash-{}.json",
+            self.written_at.format("%Y%m%dT%H%M%S")
+        ));
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(&path, json)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    #[test]
+    fn dump_keeps_tasks_agents_errors_and_events() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n### [InProgress] P1-R3-T1: DB @backend\n",
+        )
+        .unwrap();
+        state.update_from_events(
+            &parse_hook_events(include_str!(
+                "../../tests/fixtures/sample_hooks/error_events.jsonl"
+            ))
+            .events,
+        );
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = CrashDump::new(&state, "boom at src/app.rs:1:1")
+            .write(tmp.path())
+            .unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+        assert_eq!(json["panic"], "boom at src/app.rs:1:1");
+        assert_eq!(json["tasks"][0]["id"], "P1-R3-T1");
+        assert_eq!(json["tasks"][0]["phase"], "P1");
+        assert_eq!(json["agents"][0]["agent_id"], "backend-specialist-2");
+        assert_eq!(json["errors"][0]["task_id"], "P1-R3-T1");
+        let events = json["recent_events"].as_array().unwrap();
+        assert_eq!(events.len(), state.events.len());
+        assert_eq!(events[1]["event_type"], "error");
+    }
+}
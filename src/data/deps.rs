@@ -0,0 +1,371 @@
+//! Task dependencies
+//!
+//! Follows `blocked_by` links through the plan: the transitive chain of
+//! tasks holding one up, when each unfinished task should be done given
+//! the average start-to-finish time of the tasks hook events saw complete,
+//! and which ready task to start next.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::data::gating;
+use crate::data::state::DashboardState;
+use crate::data::statuses;
+use crate::data::tasks_parser::TaskStatus;
+
+/// One task in another task's blocker chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blocker {
+    pub task_id: String,
+    /// 1 for a direct blocker, 2 for a blocker's blocker, ...
+    pub depth: usize,
+    /// None when the ID is not in the plan
+    pub status: Option<TaskStatus>,
+    /// None for done and unknown tasks, and before any task has finished
+    pub expected_finish: Option<DateTime<Utc>>,
+}
+
+/// Mean start-to-finish time of the tasks hook events saw complete
+pub fn average_task_duration(state: &DashboardState) -> Option<Duration> {
+    let spans: Vec<Duration> = state
+        .task_times
+        .values()
+        .filter_map(|t| Some(t.completed_at? - t.started_at?))
+        .collect();
+    if spans.is_empty() {
+        return None;
+    }
+    let total = spans.iter().fold(Duration::zero(), |sum, span| sum + *span);
+    Some(total / spans.len() as i32)
+}
+
+/// When an unfinished task should be done: the average task duration after
+/// it started, or after its last blocker is expected to finish if it has
+/// not started, never earlier than now
+pub fn expected_finish(state: &DashboardState, task_id: &str) -> Option<DateTime<Utc>> {
+    Finishes::new(state).get(task_id)
+}
+
+/// [`expected_finish`] for many tasks of one state: the average duration is
+/// computed once and each task's finish at most once, however many chains
+/// lead through it
+pub struct Finishes<'a> {
+    state: &'a DashboardState,
+    average: Option<Duration>,
+    memo: HashMap<&'a str, Option<DateTime<Utc>>>,
+    path: HashSet<&'a str>,
+}
+
+impl<'a> Finishes<'a> {
+    pub fn new(state: &'a DashboardState) -> Self {
+        Self {
+            state,
+            average: average_task_duration(state),
+            memo: HashMap::new(),
+            path: HashSet::new(),
+        }
+    }
+
+    pub fn get(&mut self, task_id: &str) -> Option<DateTime<Utc>> {
+        let average = self.average?;
+        let state = self.state;
+        let task = state.task(task_id)?;
+        let id = task.id.as_str();
+        if let Some(&finish) = self.memo.get(id) {
+            return finish;
+        }
+        // A cycle never finishes
+        if statuses::is_done(&task.status, &state.custom_statuses) || !self.path.insert(id) {
+            return None;
+        }
+        let now = state.now();
+        let start = match state.task_times.get(id).and_then(|t| t.started_at) {
+            Some(started) => started,
+            None => task
+                .blocked_by
+                .iter()
+                .filter_map(|b| self.get(b))
+                .max()
+                .map_or(now, |last| last.max(now)),
+        };
+        self.path.remove(id);
+        let finish = Some((start + average).max(now));
+        self.memo.insert(id, finish);
+        finish
+    }
+}
+
+/// Every task holding `task_id` up, depth first: its blockers, their
+/// blockers, and so on. Done blockers are listed but not followed, and a
+/// task reached twice is listed once.
+pub fn blocker_chain(state: &DashboardState, task_id: &str) -> Vec<Blocker> {
+    let mut chain = Vec::new();
+    let mut seen = HashSet::from([task_id.to_string()]);
+    let mut finishes = Finishes::new(state);
+    collect(state, task_id, 1, &mut seen, &mut finishes, &mut chain);
+    chain
+}
+
+fn collect(
+    state: &DashboardState,
+    task_id: &str,
+    depth: usize,
+    seen: &mut HashSet<String>,
+    finishes: &mut Finishes,
+    chain: &mut Vec<Blocker>,
+) {
+    let Some(task) = state.task(task_id) else {
+        return;
+    };
+    for id in &task.blocked_by {
+        if !seen.insert(id.clone()) {
+            continue;
+        }
+        let status = state.task(id).map(|b| b.status.clone());
+        let open = status
+            .as_ref()
+            .is_some_and(|s| !statuses::is_done(s, &state.custom_statuses));
+        chain.push(Blocker {
+            task_id: id.clone(),
+            depth,
+            status,
+            expected_finish: finishes.get(id),
+        });
+        if open {
+            collect(state, id, depth + 1, seen, finishes, chain);
+        }
+    }
+}
+
+/// A task ready to start, with what starting it unblocks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub task_id: String,
+    pub name: String,
+    /// Open tasks on the longest chain waiting on this one
+    pub chain: usize,
+    /// Open tasks waiting on this one, directly or not
+    pub unblocks: usize,
+    pub priority: Option<u32>,
+    /// `@agent` from the plan
+    pub agent: Option<String>,
+}
+
+/// Pending tasks whose blockers are all done, outside locked phases, in
+/// dispatch order: longest chain of waiting tasks first, then most tasks
+/// unblocked, then priority, then plan order. A blocker ID that is not in
+/// the plan does not hold a task back.
+pub fn next_up(state: &DashboardState) -> Vec<Suggestion> {
+    let done = |id: &str| {
+        state.task(id).map_or(true, |t| {
+            statuses::is_done(&t.status, &state.custom_statuses)
+        })
+    };
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for task in state.phases.iter().flat_map(|p| &p.tasks) {
+        if done(&task.id) {
+            continue;
+        }
+        for id in &task.blocked_by {
+            dependents.entry(id).or_default().push(&task.id);
+        }
+    }
+
+    let gates = gating::evaluate(state);
+    let mut chains = HashMap::new();
+    let mut ready: Vec<Suggestion> = state
+        .phases
+        .iter()
+        .filter(|p| gates.lock_for(&p.id).is_none())
+        .flat_map(|p| &p.tasks)
+        .filter(|t| t.status == TaskStatus::Pending && t.blocked_by.iter().all(|id| done(id)))
+        .map(|t| {
+            let mut waiting = HashSet::new();
+            collect_dependents(&t.id, &dependents, &mut waiting);
+            Suggestion {
+                task_id: t.id.clone(),
+                name: t.name.clone(),
+                chain: chain_below(&t.id, &dependents, &mut HashSet::new(), &mut chains),
+                unblocks: waiting.len(),
+                priority: t.priority,
+                agent: t.agent.clone(),
+            }
+        })
+        .collect();
+    // Stable: equal tasks keep plan order
+    ready.sort_by(|a, b| {
+        b.chain
+            .cmp(&a.chain)
+            .then(b.unblocks.cmp(&a.unblocks))
+            .then(
+                a.priority
+                    .unwrap_or(u32::MAX)
+                    .cmp(&b.priority.unwrap_or(u32::MAX)),
+            )
+    });
+    ready
+}
+
+/// Length of the longest chain of dependents below `id`; `memo` keeps the
+/// lengths found so far, so shared dependents are walked once
+fn chain_below<'a>(
+    id: &'a str,
+    dependents: &HashMap<&str, Vec<&'a str>>,
+    path: &mut HashSet<&'a str>,
+    memo: &mut HashMap<&'a str, usize>,
+) -> usize {
+    if let Some(&longest) = memo.get(id) {
+        return longest;
+    }
+    if !path.insert(id) {
+        return 0;
+    }
+    let longest = dependents
+        .get(id)
+        .into_iter()
+        .flatten()
+        .map(|d| 1 + chain_below(d, dependents, path, memo))
+        .max()
+        .unwrap_or(0);
+    path.remove(id);
+    memo.insert(id, longest);
+    longest
+}
+
+fn collect_dependents<'a>(
+    id: &str,
+    dependents: &HashMap<&str, Vec<&'a str>>,
+    seen: &mut HashSet<&'a str>,
+) {
+    for d in dependents.get(id).into_iter().flatten() {
+        if seen.insert(d) {
+            collect_dependents(d, dependents, seen);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::state::TaskTiming;
+
+    const PLAN: &str = "# Phase 1: A\n### [x] T0: Setup\n### [/] T1: Parser\n- blocked_by: T0\n### [ ] T2: Watcher\n- blocked_by: T1, T9\n### [Blocked] T3: UI\n- blocked_by: T2, T1\n";
+
+    fn at(time: &str) -> DateTime<Utc> {
+        format!("2026-02-08T{time}:00Z").parse().unwrap()
+    }
+
+    #[test]
+    fn chain_follows_open_blockers_once() {
+        let state = DashboardState::from_tasks_content(PLAN).unwrap();
+        let chain: Vec<(String, usize, Option<TaskStatus>)> = blocker_chain(&state, "T3")
+            .into_iter()
+            .map(|b| (b.task_id, b.depth, b.status))
+            .collect();
+        assert_eq!(
+            chain,
+            vec![
+                ("T2".into(), 1, Some(TaskStatus::Pending)),
+                ("T1".into(), 2, Some(TaskStatus::InProgress)),
+                ("T0".into(), 3, Some(TaskStatus::Completed)),
+                ("T9".into(), 2, None),
+            ]
+        );
+        assert!(blocker_chain(&state, "T0").is_empty());
+    }
+
+    #[test]
+    fn cycles_end_the_chain() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [ ] T1: A\n- blocked_by: T2\n### [ ] T2: B\n- blocked_by: T1\n",
+        )
+        .unwrap();
+        let chain = blocker_chain(&state, "T1");
+        assert_eq!(chain.len(), 1);
+        assert_eq!(chain[0].task_id, "T2");
+    }
+
+    #[test]
+    fn expected_finish_stacks_averages_along_the_chain() {
+        let mut state = DashboardState::from_tasks_content(PLAN).unwrap();
+        assert_eq!(expected_finish(&state, "T1"), None);
+
+        let timing = |start, end: Option<&str>| TaskTiming {
+            started_at: Some(at(start)),
+            completed_at: end.map(at),
+        };
+        state
+            .task_times
+            .insert("T0".into(), timing("09:00", Some("09:30")));
+        state.task_times.insert("T1".into(), timing("09:30", None));
+        state.scrub_at = Some(at("09:40"));
+
+        assert_eq!(average_task_duration(&state), Some(Duration::minutes(30)));
+        assert_eq!(expected_finish(&state, "T0"), None);
+        assert_eq!(expected_finish(&state, "T1"), Some(at("10:00")));
+        assert_eq!(expected_finish(&state, "T2"), Some(at("10:30")));
+        assert_eq!(expected_finish(&state, "T3"), Some(at("11:00")));
+
+        // An overrunning task is expected any moment now
+        state.scrub_at = Some(at("10:15"));
+        assert_eq!(expected_finish(&state, "T1"), Some(at("10:15")));
+    }
+
+    #[test]
+    fn next_up_prefers_the_longest_waiting_chain() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [ ] T1: Leaf\n- priority: 1\n### [ ] T2: Root\n### [ ] T3: Mid\n- blocked_by: T2\n### [ ] T4: Top\n- blocked_by: T3\n### [ ] T5: Side\n### [ ] T6: Fan\n- blocked_by: T5\n### [ ] T7: Fan\n- blocked_by: T5\n### [x] T8: Done\n### [ ] T9: Free\n- blocked_by: T8, T0\n\n# Phase 2: B\ngate: P1\n### [ ] T10: Gated\n",
+        )
+        .unwrap();
+        let order: Vec<(String, usize, usize)> = next_up(&state)
+            .into_iter()
+            .map(|s| (s.task_id, s.chain, s.unblocks))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("T2".into(), 2, 2),
+                ("T5".into(), 1, 2),
+                ("T1".into(), 0, 0),
+                ("T9".into(), 0, 0),
+            ]
+        );
+    }
+
+    /// Layers of two tasks, each blocked by both tasks of the layer above:
+    /// 2^layers paths from bottom to top
+    fn diamonds(layers: usize) -> String {
+        let mut plan = "# Phase 1: A\n### [x] D0: Done\n### [ ] A1: A\n### [ ] B1: B\n".to_string();
+        for i in 2..=layers {
+            for name in ["A", "B"] {
+                plan.push_str(&format!(
+                    "### [ ] {name}{i}: {name}\n- blocked_by: A{}, B{}\n",
+                    i - 1,
+                    i - 1
+                ));
+            }
+        }
+        plan
+    }
+
+    #[test]
+    fn diamond_graphs_are_walked_once_per_task() {
+        let mut state = DashboardState::from_tasks_content(&diamonds(60)).unwrap();
+        state.task_times.insert(
+            "D0".into(),
+            TaskTiming {
+                started_at: Some(at("09:00")),
+                completed_at: Some(at("09:10")),
+            },
+        );
+        state.scrub_at = Some(at("10:00"));
+
+        let last = expected_finish(&state, "A60").unwrap();
+        assert_eq!(last, at("10:00") + Duration::minutes(10 * 60));
+        assert_eq!(blocker_chain(&state, "A60").len(), 118);
+
+        let chains: Vec<usize> = next_up(&state).iter().map(|s| s.chain).collect();
+        assert_eq!(chains, vec![59, 59]);
+    }
+}
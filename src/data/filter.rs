@@ -0,0 +1,155 @@
+//! Startup filters from `watch --phase/--agent/--status`
+//!
+//! A filter narrows what the task views list: tasks that do not match are
+//! left out of the tree, bar, calendar and workload views and the next-up
+//! list. The plan itself stays whole, so dependencies, gates, milestones,
+//! counts and the forecast still see every task. Within one flag the values
+//! are alternatives; across flags all must match.
+
+use crate::data::tasks_parser::{ParsedPhase, ParsedTask, TaskStatus};
+
+/// Phases, agents and statuses to keep (empty = no restriction)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskFilter {
+    /// Phase IDs (`P1`) or names
+    pub phases: Vec<String>,
+    /// Agent names, with or without the leading `@`
+    pub agents: Vec<String>,
+    /// Status names such as `failed` or `inprogress`, including custom ones
+    pub statuses: Vec<String>,
+}
+
+/// Lowercase with spaces, `-` and `_` removed, so `in-progress`,
+/// `In Progress` and `InProgress` compare equal
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Whether a status name given by the user (`failed`, `in-progress`,
+/// `Review`) names `status`
+pub fn status_matches(name: &str, status: &TaskStatus) -> bool {
+    normalize(name) == normalize(status.label())
+}
+
+impl TaskFilter {
+    pub fn is_empty(&self) -> bool {
+        self.phases.is_empty() && self.agents.is_empty() && self.statuses.is_empty()
+    }
+
+    /// Whether `phase` passes the phase filter
+    pub fn keeps_phase(&self, phase: &ParsedPhase) -> bool {
+        self.phases.is_empty()
+            || self.phases.iter().any(|p| {
+                p.eq_ignore_ascii_case(&phase.id) || normalize(p) == normalize(&phase.name)
+            })
+    }
+
+    /// Whether an agent or status filter is set, so phases without a
+    /// matching task are left out too
+    pub fn narrows_tasks(&self) -> bool {
+        !self.agents.is_empty() || !self.statuses.is_empty()
+    }
+
+    /// Whether `task`, worked on by `agent`, passes the agent and status
+    /// filters
+    pub fn keeps(&self, task: &ParsedTask, agent: Option<&str>) -> bool {
+        let agent_ok = self.agents.is_empty()
+            || agent.is_some_and(|agent| {
+                self.agents
+                    .iter()
+                    .any(|a| a.trim_start_matches('@').eq_ignore_ascii_case(agent))
+            });
+        let status_ok = self.statuses.is_empty()
+            || self
+                .statuses
+                .iter()
+                .any(|s| status_matches(s, &task.status));
+        agent_ok && status_ok
+    }
+
+    /// Short description for the status bar, e.g. `P1 @backend failed,inprogress`
+    pub fn describe(&self) -> String {
+        let agents: Vec<String> = self
+            .agents
+            .iter()
+            .map(|a| format!("@{}", a.trim_start_matches('@')))
+            .collect();
+        [
+            self.phases.join(","),
+            agents.join(","),
+            self.statuses.join(","),
+        ]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::state::DashboardState;
+
+    /// The sample plan narrowed by `filter`
+    fn board(filter: TaskFilter) -> DashboardState {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Setup\n\
+             ### [x] T1: A\n- @backend-specialist\n\
+             ### [Failed] T2: B\n- @frontend\n\
+             # Phase 2: Build\n\
+             ### [InProgress] T3: C\n- @backend-specialist\n\
+             ### [ ] T4: D\n",
+        )
+        .unwrap();
+        state.set_task_filter(filter);
+        state
+    }
+
+    fn shown(state: &DashboardState) -> Vec<&str> {
+        state
+            .shown_phases()
+            .flat_map(|(_, p)| state.shown_tasks(p))
+            .map(|(_, t)| t.id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn empty_filter_keeps_everything() {
+        let filter = TaskFilter::default();
+        assert!(filter.is_empty());
+        assert_eq!(shown(&board(filter)).len(), 4);
+    }
+
+    #[test]
+    fn phase_matches_id_or_name() {
+        for phase in ["p2", "Build"] {
+            let state = board(TaskFilter {
+                phases: vec![phase.to_string()],
+                ..Default::default()
+            });
+            assert_eq!(shown(&state), ["T3", "T4"]);
+        }
+    }
+
+    #[test]
+    fn agent_and_status_combine_and_hide_empty_phases() {
+        let filter = TaskFilter {
+            agents: vec!["@backend-specialist".to_string()],
+            statuses: vec!["failed".to_string(), "in-progress".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(filter.describe(), "@backend-specialist failed,in-progress");
+        let state = board(filter);
+        assert_eq!(shown(&state), ["T3"]);
+        assert_eq!(state.shown_phases().count(), 1);
+        // The plan itself stays whole
+        assert_eq!(state.total_tasks, 4);
+        assert!(state.task("T1").is_some());
+        assert!(!state.is_shown("T1"));
+        assert!(state.is_shown("T3"));
+    }
+}
@@ -0,0 +1,153 @@
+//! Completion forecast
+//!
+//! Projects when the plan will be done: the work left, priced at the pace
+//! finished tasks were done at, spread over as many tasks as the run has
+//! been keeping busy at once.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::data::state::DashboardState;
+use crate::data::statuses;
+
+/// Projected finish of the open tasks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Forecast {
+    pub finish: DateTime<Utc>,
+    /// Tasks the run has had in flight on average, at least 1
+    pub parallelism: f64,
+}
+
+/// Forecast the finish of the plan from hook timing. A task's size is its
+/// `points`, or 1 without; each open task is expected to take the average
+/// time per point of the finished ones, less the time it already ran.
+/// None before any task has finished, once every task is done, and when the
+/// finish lies beyond the calendar.
+pub fn forecast(state: &DashboardState) -> Option<Forecast> {
+    let now = state.now();
+    let size = |id: &str| u64::from(state.task(id).and_then(|t| t.points).unwrap_or(1).max(1));
+
+    let mut spent = Duration::zero();
+    let mut done_points: u64 = 0;
+    let mut busy = Duration::zero();
+    let mut first_start: Option<DateTime<Utc>> = None;
+    for (id, timing) in &state.task_times {
+        let Some(started) = timing.started_at else {
+            continue;
+        };
+        first_start = Some(first_start.map_or(started, |first| first.min(started)));
+        busy += timing.completed_at.unwrap_or(now) - started;
+        if let Some(done) = timing.completed_at {
+            spent += done - started;
+            done_points = done_points.saturating_add(size(id));
+        }
+    }
+    if done_points == 0 {
+        return None;
+    }
+    // In seconds: huge `points` estimates overflow a Duration
+    let per_point = spent.num_seconds() as f64 / done_points as f64;
+
+    let mut remaining = 0.0;
+    let mut open = 0;
+    for task in state.phases.iter().flat_map(|p| &p.tasks) {
+        if statuses::is_done(&task.status, &state.custom_statuses) {
+            continue;
+        }
+        open += 1;
+        let estimate = per_point * size(&task.id) as f64;
+        let ran = state
+            .task_times
+            .get(task.id.as_str())
+            .and_then(|t| Some(t.completed_at.unwrap_or(now) - t.started_at?))
+            .unwrap_or_else(Duration::zero);
+        remaining += (estimate - ran.num_seconds() as f64).max(0.0);
+    }
+    if open == 0 {
+        return None;
+    }
+
+    let span = (now - first_start?).num_seconds().max(1) as f64;
+    let parallelism = (busy.num_seconds() as f64 / span).max(1.0);
+    let left = Duration::try_seconds((remaining / parallelism).round() as i64)?;
+    Some(Forecast {
+        finish: now.checked_add_signed(left)?,
+        parallelism,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::state::TaskTiming;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        format!("2026-02-08T{time}:00Z").parse().unwrap()
+    }
+
+    fn timing(start: &str, end: Option<&str>) -> TaskTiming {
+        TaskTiming {
+            started_at: Some(at(start)),
+            completed_at: end.map(at),
+        }
+    }
+
+    #[test]
+    fn forecast_needs_a_finished_task_and_open_work() {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [x] T1: A\n### [ ] T2: B\n")
+                .unwrap();
+        state.scrub_at = Some(at("11:00"));
+        assert_eq!(forecast(&state), None);
+
+        state
+            .task_times
+            .insert("T1".into(), timing("10:00", Some("10:30")));
+        let f = forecast(&state).unwrap();
+        // 30m per task, one at a time over the hour since the first start
+        assert_eq!(f.finish, at("11:30"));
+        assert_eq!(f.parallelism, 1.0);
+
+        state.reload_tasks("# Phase 1: A\n### [x] T1: A\n").unwrap();
+        assert_eq!(forecast(&state), None);
+    }
+
+    #[test]
+    fn forecast_prices_points_and_spreads_over_parallel_work() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [x] T1: A\n- points: 2\n### [x] T2: B\n- points: 2\n### [/] T3: C\n- points: 2\n### [ ] T4: D\n- points: 4\n",
+        )
+        .unwrap();
+        // Two tasks side by side, then one more: 20m per point
+        state
+            .task_times
+            .insert("T1".into(), timing("10:00", Some("10:40")));
+        state
+            .task_times
+            .insert("T2".into(), timing("10:00", Some("10:40")));
+        state.task_times.insert("T3".into(), timing("10:40", None));
+        state.scrub_at = Some(at("11:00"));
+
+        let f = forecast(&state).unwrap();
+        assert!((f.parallelism - 100.0 / 60.0).abs() < 1e-9);
+        // T3 has 20m of its 40m left, T4 needs 80m: 100m over 5/3 in flight
+        assert_eq!(f.finish, at("12:00"));
+    }
+
+    #[test]
+    fn forecast_past_the_calendar_is_none() {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [x] T1: A\n### [ ] T2: B\n")
+                .unwrap();
+        state.phases[0].tasks[1].points = Some(u32::MAX);
+        state
+            .task_times
+            .insert("T1".into(), timing("00:00", Some("23:00")));
+        state.scrub_at = Some(at("23:00"));
+        assert_eq!(forecast(&state), None);
+
+        state.phases[0].tasks[0].points = Some(u32::MAX);
+        state.phases[0].tasks[1].points = Some(1);
+        let f = forecast(&state).unwrap();
+        assert!(f.finish >= at("23:00"));
+    }
+}
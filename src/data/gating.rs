@@ -0,0 +1,123 @@
+//! Phase gating
+//!
+//! A phase is locked while it carries a `[Blocked]` tag in its header or its
+//! `gate:` phase is not yet complete. Tasks that go InProgress inside a locked
+//! phase are reported as premature so the UI can warn about them.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::data::state::DashboardState;
+use crate::data::statuses;
+use crate::data::tasks_parser::TaskStatus;
+
+/// Why a phase is locked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GateLock {
+    /// Header tagged `[Blocked]`
+    Blocked,
+    /// Waiting for this phase to complete
+    Waiting(String),
+}
+
+/// Result of checking phase gates against the current plan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GateReport {
+    /// Locked phase IDs and the reason
+    pub locked: HashMap<String, GateLock>,
+    /// InProgress task IDs inside locked phases
+    pub premature: HashSet<String>,
+}
+
+impl GateReport {
+    pub fn lock_for(&self, phase_id: &str) -> Option<&GateLock> {
+        self.locked.get(phase_id)
+    }
+
+    pub fn is_premature(&self, task_id: &str) -> bool {
+        self.premature.contains(task_id)
+    }
+}
+
+/// Check every phase's header tag and gate.
+///
+/// A gate naming a phase that doesn't exist is ignored.
+pub fn evaluate(state: &DashboardState) -> GateReport {
+    let mut report = GateReport::default();
+    let done = |phase_id: &str| {
+        state
+            .phases
+            .iter()
+            .find(|p| p.id.eq_ignore_ascii_case(phase_id))
+            .map(|p| {
+                p.tasks
+                    .iter()
+                    .all(|t| statuses::is_done(&t.status, &state.custom_statuses))
+            })
+    };
+
+    for phase in &state.phases {
+        let lock = if phase.status == Some(TaskStatus::Blocked) {
+            Some(GateLock::Blocked)
+        } else {
+            phase
+                .gate
+                .as_deref()
+                .filter(|gate| done(gate) == Some(false))
+                .map(|gate| GateLock::Waiting(gate.to_string()))
+        };
+        let Some(lock) = lock else {
+            continue;
+        };
+        report.premature.extend(
+            phase
+                .tasks
+                .iter()
+                .filter(|t| t.status == TaskStatus::InProgress)
+                .map(|t| t.id.clone()),
+        );
+        report.locked.insert(phase.id.clone(), lock);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(plan: &str) -> GateReport {
+        evaluate(&DashboardState::from_tasks_content(plan).unwrap())
+    }
+
+    #[test]
+    fn gate_waits_for_previous_phase() {
+        let r = report(
+            "# Phase 1: A\n### [x] T1: A\n### [ ] T2: B\n\n# Phase 2: B\ngate: P1 complete\n### [InProgress] T3: C\n### [ ] T4: D\n",
+        );
+        assert_eq!(r.lock_for("P2"), Some(&GateLock::Waiting("P1".to_string())));
+        assert_eq!(r.lock_for("P1"), None);
+        assert!(r.is_premature("T3"));
+        assert!(!r.is_premature("T4"));
+    }
+
+    #[test]
+    fn gate_opens_when_complete() {
+        let r = report(
+            "# Phase 1: A\n### [x] T1: A\n\n# Phase 2: B\ngate: P1\n### [InProgress] T2: B\n",
+        );
+        assert!(r.locked.is_empty());
+        assert!(r.premature.is_empty());
+    }
+
+    #[test]
+    fn blocked_header_locks_phase() {
+        let r = report("# Phase 1: A [Blocked]\n### [InProgress] T1: A\n");
+        assert_eq!(r.lock_for("P1"), Some(&GateLock::Blocked));
+        assert!(r.is_premature("T1"));
+    }
+
+    #[test]
+    fn unknown_gate_is_ignored() {
+        let r = report("# Phase 2: B\ngate: P7\n### [InProgress] T1: A\n");
+        assert!(r.locked.is_empty());
+    }
+}
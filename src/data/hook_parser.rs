@@ -1,15 +1,25 @@
 //! Hook event parser (serde_json)
 //!
 //! Parses JSONL (JSON Lines) hook event streams from Claude Code agents.
-//! Handles: agent_start, agent_end, tool_start, tool_end, error events.
+//! Handles: agent_start, agent_end, tool_start, tool_end, error, heartbeat
+//! and run_start events.
 //! Gracefully skips malformed lines.
 
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::Path;
 
+use crate::data::cost::Usage;
+use crate::data::text;
+
+/// Task ID the hook logger writes on events that belong to no task, e.g.
+/// tool calls of the main agent
+pub const NO_TASK: &str = "unknown";
+
 /// Raw event as deserialized from JSON Lines
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct HookEvent {
     pub event_type: EventType,
     pub timestamp: DateTime<Utc>,
@@ -18,12 +28,40 @@ pub struct HookEvent {
     pub session_id: String,
     #[serde(default)]
     pub tool_name: Option<String>,
-    #[serde(default)]
+    /// Multi-line messages (stack traces) keep their line breaks; hooks
+    /// may also send the lines as an array
+    #[serde(default, deserialize_with = "message_lines")]
     pub error_message: Option<String>,
+    /// Tail of a finished tool's output, already truncated by the hook
+    #[serde(default)]
+    pub tool_output: Option<String>,
+    /// Tokens and cost, usually on `agent_end`
+    #[serde(default)]
+    pub usage: Option<Usage>,
+}
+
+/// `error_message` as a string or an array of lines, joined with `\n`,
+/// with CRLF line endings normalized
+fn message_lines<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Message {
+        Text(String),
+        Lines(Vec<String>),
+    }
+    Ok(
+        Option::<Message>::deserialize(deserializer)?.map(|message| {
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Lines(lines) => lines.join("\n"),
+            };
+            text.replace("\r\n", "\n")
+        }),
+    )
 }
 
 /// Known event types from Claude Code hooks
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EventType {
     AgentStart,
@@ -31,6 +69,13 @@ pub enum EventType {
     ToolStart,
     ToolEnd,
     Error,
+    /// Periodic "still here" signal from a hook script
+    Heartbeat,
+    /// Marks the start of an orchestration run
+    RunStart,
+    /// The agent hit a provider rate limit; `error_message` may say how
+    /// long to wait ("retry after 30s")
+    RateLimit,
 }
 
 /// Result of parsing a JSONL file: events + any parse errors
@@ -38,6 +83,11 @@ pub enum EventType {
 pub struct ParseResult {
     pub events: Vec<HookEvent>,
     pub errors: Vec<ParseError>,
+    /// Bytes at the start of the file left unread because of the size
+    /// limit; 0 when the whole file was read
+    pub skipped_bytes: u64,
+    /// Lines that held invalid UTF-8, e.g. binary tool output
+    pub lossy_lines: usize,
 }
 
 /// A single line parse error
@@ -48,34 +98,78 @@ pub struct ParseError {
     pub error: String,
 }
 
-/// Parse a JSONL string into hook events, collecting errors for malformed lines
-pub fn parse_hook_events(input: &str) -> ParseResult {
-    let mut events = Vec::new();
-    let mut errors = Vec::new();
+impl ParseResult {
+    fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            errors: Vec::new(),
+            skipped_bytes: 0,
+            lossy_lines: 0,
+        }
+    }
 
-    for (idx, line) in input.lines().enumerate() {
+    fn push_line(&mut self, line_number: usize, line: &str) {
         let trimmed = line.trim();
         if trimmed.is_empty() {
-            continue;
+            return;
         }
 
         match serde_json::from_str::<HookEvent>(trimmed) {
-            Ok(event) => events.push(event),
-            Err(e) => errors.push(ParseError {
-                line_number: idx + 1,
+            Ok(event) => self.events.push(event),
+            Err(e) => self.errors.push(ParseError {
+                line_number,
                 line_content: trimmed.to_string(),
                 error: e.to_string(),
             }),
         }
     }
+}
 
-    ParseResult { events, errors }
+/// Parse a JSONL string into hook events, collecting errors for malformed lines
+pub fn parse_hook_events(input: &str) -> ParseResult {
+    let mut result = ParseResult::new();
+    for (idx, line) in input.lines().enumerate() {
+        result.push_line(idx + 1, line);
+    }
+    result
 }
 
 /// Parse a JSONL file from disk
 pub fn parse_hook_file(path: &Path) -> Result<ParseResult, std::io::Error> {
-    let content = std::fs::read_to_string(path)?;
-    Ok(parse_hook_events(&content))
+    parse_hook_file_limited(path, u64::MAX)
+}
+
+/// Parse a JSONL file from disk one line at a time, replacing invalid UTF-8
+/// and reading at most
+/// `max_bytes`. A larger file is read from the first whole line of its last
+/// `max_bytes`, since the newest events decide the current agent states;
+/// line numbers then count from there.
+pub fn parse_hook_file_limited(path: &Path, max_bytes: u64) -> Result<ParseResult, std::io::Error> {
+    let mut file = File::open(path)?;
+    let skip = file.metadata()?.len().saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(skip))?;
+    let mut reader = BufReader::new(file);
+
+    let mut result = ParseResult::new();
+    if skip > 0 {
+        // The cut rarely lands on a line boundary: drop the partial line
+        let mut partial = Vec::new();
+        let dropped = reader.read_until(b'\n', &mut partial)?;
+        result.skipped_bytes = skip + dropped as u64;
+    }
+
+    // The file may still be growing; stop at the size it had when opened
+    let mut reader = reader.take(max_bytes);
+    let mut line = Vec::new();
+    let mut line_number = 0;
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        line_number += 1;
+        let (decoded, lossy) = text::decode_line(&line);
+        result.lossy_lines += usize::from(lossy);
+        result.push_line(line_number, &decoded);
+        line.clear();
+    }
+    Ok(result)
 }
 
 /// Filter events by agent ID
@@ -118,6 +212,20 @@ mod tests {
         assert_eq!(result.events[5].event_type, EventType::AgentEnd);
     }
 
+    #[test]
+    fn parse_heartbeat_event() {
+        let input = r#"{"event_type":"heartbeat","timestamp":"2026-02-08T10:00:00Z","agent_id":"main","task_id":"unknown","session_id":"s1"}"#;
+        let result = parse_hook_events(input);
+        assert_eq!(result.events[0].event_type, EventType::Heartbeat);
+    }
+
+    #[test]
+    fn parse_run_start_event() {
+        let input = r#"{"event_type":"run_start","timestamp":"2026-02-08T09:00:00Z","agent_id":"main","task_id":"unknown","session_id":"s1"}"#;
+        let result = parse_hook_events(input);
+        assert_eq!(result.events[0].event_type, EventType::RunStart);
+    }
+
     #[test]
     fn parse_agent_event_fields() {
         let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
@@ -127,6 +235,14 @@ mod tests {
         assert_eq!(first.task_id, "P1-R1-T1");
         assert_eq!(first.session_id, "sess-001");
         assert!(first.tool_name.is_none());
+        assert!(first.tool_output.is_none());
+    }
+
+    #[test]
+    fn parse_tool_output() {
+        let input = r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:00:00Z","agent_id":"main","task_id":"T1","session_id":"s1","tool_name":"Bash","tool_output":"ok\ndone"}"#;
+        let result = parse_hook_events(input);
+        assert_eq!(result.events[0].tool_output.as_deref(), Some("ok\ndone"));
     }
 
     #[test]
@@ -157,6 +273,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn multi_line_error_messages_keep_their_lines() {
+        let input = concat!(
+            r#"{"event_type":"error","timestamp":"2026-02-08T11:00:30Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"TypeError: x is undefined\r\n    at f (a.js:1:2)"}"#,
+            "\n",
+            r#"{"event_type":"error","timestamp":"2026-02-08T11:00:31Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":["TypeError: x is undefined","    at f (a.js:1:2)"]}"#,
+            "\n",
+            r#"{"event_type":"error","timestamp":"2026-02-08T11:00:32Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":null}"#,
+        );
+        let result = parse_hook_events(input);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let messages: Vec<Option<&str>> = result
+            .events
+            .iter()
+            .map(|e| e.error_message.as_deref())
+            .collect();
+        let trace = "TypeError: x is undefined\n    at f (a.js:1:2)";
+        assert_eq!(messages, [Some(trace), Some(trace), None]);
+    }
+
     #[test]
     fn parse_malformed_gracefully() {
         let input = include_str!("../../tests/fixtures/sample_hooks/malformed.jsonl");
@@ -217,6 +353,48 @@ mod tests {
         assert_eq!(result.events.len(), 6);
     }
 
+    #[test]
+    fn oversized_file_is_read_from_its_tail() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("big.jsonl");
+        let line = |agent: &str| {
+            format!(
+                r#"{{"event_type":"heartbeat","timestamp":"2026-02-08T10:00:00Z","agent_id":"{agent}","task_id":"T1","session_id":"s1"}}"#
+            ) + "\n"
+        };
+        let content = [line("a"), line("b"), line("c")].concat();
+        std::fs::write(&path, &content).unwrap();
+
+        let whole = parse_hook_file_limited(&path, content.len() as u64).unwrap();
+        assert_eq!(whole.events.len(), 3);
+        assert_eq!(whole.skipped_bytes, 0);
+
+        // The cut lands inside the first line, which is dropped whole
+        let tail = parse_hook_file_limited(&path, content.len() as u64 - 10).unwrap();
+        let agents: Vec<_> = tail.events.iter().map(|e| e.agent_id.as_str()).collect();
+        assert_eq!(agents, ["b", "c"]);
+        assert!(tail.errors.is_empty());
+        assert_eq!(tail.skipped_bytes, line("a").len() as u64);
+    }
+
+    #[test]
+    fn binary_tool_output_is_decoded_lossily() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("s.jsonl");
+        let mut content = br#"{"event_type":"tool_end","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s1","tool_output":""#.to_vec();
+        content.extend_from_slice(b"\x89PNG\xff\"}\n");
+        content.extend_from_slice(br#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:01Z","agent_id":"a","task_id":"T1","session_id":"s1"}"#);
+        std::fs::write(&path, content).unwrap();
+
+        let result = parse_hook_file(&path).unwrap();
+        assert_eq!(result.events.len(), 2);
+        assert_eq!(result.lossy_lines, 1);
+        assert_eq!(
+            result.events[0].tool_output.as_deref(),
+            Some("\u{fffd}PNG\u{fffd}")
+        );
+    }
+
     #[test]
     fn parse_file_nonexistent() {
         let result = parse_hook_file(Path::new("/nonexistent/path.jsonl"));
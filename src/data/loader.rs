@@ -0,0 +1,309 @@
+//! Background file loading
+//!
+//! Reading and parsing a large TASKS.md or hook file on the render thread
+//! freezes the board until it is done. A [`Loader`] reads and parses the
+//! files behind watcher changes on a worker thread instead; each finished
+//! load comes back as a [`Loaded`] for the caller to apply, in the order the
+//! changes were sent. Only the parsing moves: applying a load to the state is
+//! still done by the owner of the state.
+//!
+//! A load is superseded by a later change to the same file. Changes still
+//! queued behind a newer one are skipped, and a parse that finishes after a
+//! newer one was requested is dropped, so a slow stale parse can never land
+//! on top of newer state. [`Loader::supersede_tasks`] does the same for a
+//! tasks file the caller has just reloaded itself.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc};
+
+use crate::data::hook_parser::{self, ParseResult};
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::{ParseError, ParseOutcome};
+use crate::data::tasks_source::TasksSource;
+use crate::data::text;
+use crate::data::watcher::FileChange;
+
+/// A file read and parsed for a change
+#[derive(Debug)]
+pub enum Loaded {
+    /// The tasks file, parsed with the source the request was made with
+    Tasks {
+        path: PathBuf,
+        /// Lines that held invalid UTF-8
+        lossy_lines: usize,
+        parsed: Result<ParseOutcome, ParseError>,
+    },
+    /// A hook file, read within the read limit of the request
+    Hooks { path: PathBuf, result: ParseResult },
+}
+
+/// Whether a change is a file to read: a tasks or hook file write
+pub fn reads_file(change: &FileChange) -> bool {
+    matches!(
+        change,
+        FileChange::TasksModified(_)
+            | FileChange::HookEventCreated(_)
+            | FileChange::HookEventModified(_)
+    )
+}
+
+/// Read and parse the file behind a change. None for changes that read
+/// nothing and for files that cannot be read (gone again, no permission).
+pub fn load(change: &FileChange, source: &dyn TasksSource, read_limit: u64) -> Option<Loaded> {
+    let loaded = match change {
+        FileChange::TasksModified(path) => text::read_lossy(path).map(|decoded| Loaded::Tasks {
+            path: path.clone(),
+            lossy_lines: decoded.lossy_lines,
+            parsed: source.parse(&decoded.text),
+        }),
+        FileChange::HookEventCreated(path) | FileChange::HookEventModified(path) => {
+            hook_parser::parse_hook_file_limited(path, read_limit).map(|result| Loaded::Hooks {
+                path: path.clone(),
+                result,
+            })
+        }
+        FileChange::TasksRemoved(_) | FileChange::ConfigModified(_) => return None,
+    };
+    match loaded {
+        Ok(loaded) => Some(loaded),
+        Err(e) => {
+            tracing::debug!("cannot read {}: {e}", change.path().display());
+            None
+        }
+    }
+}
+
+/// What a load replaces: the plan, or the events of one hook file
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Slot {
+    Tasks,
+    Hooks(PathBuf),
+}
+
+impl Slot {
+    fn of(change: &FileChange) -> Self {
+        match change {
+            FileChange::TasksModified(_) | FileChange::TasksRemoved(_) => Slot::Tasks,
+            other => Slot::Hooks(other.path().to_path_buf()),
+        }
+    }
+}
+
+/// One change to load, with the settings at the time it was requested
+struct Job {
+    change: FileChange,
+    generation: u64,
+    source: Arc<dyn TasksSource>,
+    read_limit: u64,
+}
+
+/// A finished job; `None` when the file was unreadable or the job skipped
+struct Done {
+    slot: Slot,
+    generation: u64,
+    loaded: Option<Loaded>,
+}
+
+/// Worker thread reading and parsing changed files
+pub struct Loader {
+    jobs: mpsc::Sender<Job>,
+    done: mpsc::Receiver<Done>,
+    pending: usize,
+    generation: u64,
+    /// Newest generation requested per slot; older results are stale
+    latest: HashMap<Slot, u64>,
+    superseded: usize,
+}
+
+impl Loader {
+    /// Start the worker. It stops when the loader is dropped.
+    pub fn spawn() -> Self {
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let (finished, done) = mpsc::channel();
+        std::thread::spawn(move || {
+            while let Ok(first) = queue.recv() {
+                let mut batch = vec![first];
+                batch.extend(queue.try_iter());
+                for (i, job) in batch.iter().enumerate() {
+                    let slot = Slot::of(&job.change);
+                    // A newer change to the same file is queued behind this one
+                    let stale = batch[i + 1..]
+                        .iter()
+                        .any(|later| Slot::of(&later.change) == slot);
+                    let loaded = if stale {
+                        None
+                    } else {
+                        load(&job.change, job.source.as_ref(), job.read_limit)
+                    };
+                    let done = Done {
+                        slot,
+                        generation: job.generation,
+                        loaded,
+                    };
+                    if finished.send(done).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Self {
+            jobs,
+            done,
+            pending: 0,
+            generation: 0,
+            latest: HashMap::new(),
+            superseded: 0,
+        }
+    }
+
+    /// Queue a change, parsed with the tasks source and hook read limit
+    /// `state` has now
+    pub fn request(&mut self, change: FileChange, state: &DashboardState) {
+        self.generation += 1;
+        let slot = Slot::of(&change);
+        let job = Job {
+            change,
+            generation: self.generation,
+            source: Arc::clone(&state.tasks_source),
+            read_limit: state.hook_read_limit,
+        };
+        match self.jobs.send(job) {
+            Ok(()) => {
+                self.pending += 1;
+                self.latest.insert(slot, self.generation);
+            }
+            Err(_) => tracing::warn!("file loader stopped; change dropped"),
+        }
+    }
+
+    /// Drop the tasks file loads in flight, e.g. after reloading the file
+    /// directly: they read it before the reload
+    pub fn supersede_tasks(&mut self) {
+        self.generation += 1;
+        self.latest.insert(Slot::Tasks, self.generation);
+    }
+
+    /// Finished loads, oldest first, without the superseded ones
+    pub fn poll(&mut self) -> Vec<Loaded> {
+        let mut loaded = Vec::new();
+        while let Ok(done) = self.done.try_recv() {
+            self.pending = self.pending.saturating_sub(1);
+            if self.latest.get(&done.slot) != Some(&done.generation) {
+                tracing::debug!(slot = ?done.slot, "dropped a superseded load");
+                self.superseded += 1;
+                continue;
+            }
+            loaded.extend(done.loaded);
+        }
+        loaded
+    }
+
+    /// Changes sent and not yet finished
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+
+    /// Loads dropped so far because a newer one was requested
+    pub fn superseded(&self) -> usize {
+        self.superseded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn wait(loader: &mut Loader) -> Vec<Loaded> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut loaded = Vec::new();
+        while loader.pending() > 0 && Instant::now() < deadline {
+            loaded.extend(loader.poll());
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        loaded
+    }
+
+    #[test]
+    fn loads_come_back_in_request_order() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks = tmp.path().join("TASKS.md");
+        let hooks = tmp.path().join("events.jsonl");
+        std::fs::write(&tasks, "# Phase 1: A\n### [x] T1: A\n").unwrap();
+        std::fs::write(
+            &hooks,
+            r#"{"event_type":"agent_start","agent_id":"main","task_id":"T1","session_id":"s1","timestamp":"2026-02-08T00:00:00Z"}"#,
+        )
+        .unwrap();
+        let state = DashboardState::default();
+        let mut loader = Loader::spawn();
+
+        loader.request(FileChange::HookEventModified(hooks.clone()), &state);
+        loader.request(
+            FileChange::TasksModified(tmp.path().join("missing.md")),
+            &state,
+        );
+        loader.request(FileChange::TasksModified(tasks.clone()), &state);
+        assert_eq!(loader.pending(), 3);
+
+        let loaded = wait(&mut loader);
+        assert_eq!(loader.pending(), 0);
+        // The unreadable file is dropped
+        assert_eq!(loaded.len(), 2);
+        assert!(
+            matches!(&loaded[0], Loaded::Hooks { path, result } if *path == hooks && result.events.len() == 1)
+        );
+        match &loaded[1] {
+            Loaded::Tasks { path, parsed, .. } => {
+                assert_eq!(*path, tasks);
+                assert_eq!(parsed.as_ref().unwrap().phases[0].tasks[0].id, "T1");
+            }
+            other => panic!("expected tasks, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn only_the_newest_load_of_a_file_is_applied() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks = tmp.path().join("TASKS.md");
+        let state = DashboardState::default();
+        let mut loader = Loader::spawn();
+
+        std::fs::write(&tasks, "# Phase 1: A\n### [ ] T1: A\n").unwrap();
+        loader.request(FileChange::TasksModified(tasks.clone()), &state);
+        // Let the first parse finish before the newer one is asked for
+        std::thread::sleep(Duration::from_millis(50));
+        std::fs::write(&tasks, "# Phase 1: A\n### [x] T1: A\n### [ ] T2: B\n").unwrap();
+        loader.request(FileChange::TasksModified(tasks.clone()), &state);
+
+        let loaded = wait(&mut loader);
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loader.superseded(), 1);
+        let Loaded::Tasks { parsed, .. } = &loaded[0] else {
+            panic!("expected tasks");
+        };
+        assert_eq!(parsed.as_ref().unwrap().phases[0].tasks.len(), 2);
+
+        // Reloaded directly meanwhile: the load in flight is stale
+        loader.request(FileChange::TasksModified(tasks), &state);
+        loader.supersede_tasks();
+        assert!(wait(&mut loader).is_empty());
+        assert_eq!(loader.superseded(), 2);
+    }
+
+    #[test]
+    fn only_file_writes_are_loaded() {
+        let path = PathBuf::from("/tmp/TASKS.md");
+        assert!(reads_file(&FileChange::TasksModified(path.clone())));
+        assert!(!reads_file(&FileChange::TasksRemoved(path.clone())));
+        assert!(!reads_file(&FileChange::ConfigModified(path.clone())));
+        let state = DashboardState::default();
+        assert!(load(
+            &FileChange::TasksRemoved(path),
+            state.tasks_source.as_ref(),
+            u64::MAX
+        )
+        .is_none());
+    }
+}
@@ -1,5 +1,29 @@
+pub mod agent_groups;
+pub mod audit;
+pub mod clock;
+pub mod context;
+pub mod control;
+pub mod cost;
+pub mod crash;
+pub mod deps;
+pub mod filter;
+pub mod forecast;
+pub mod gating;
 pub mod hook_parser;
+pub mod loader;
+pub mod parse_cache;
+pub mod plan_diff;
+pub mod roster;
 pub mod state;
+pub mod statuses;
+pub mod task_id;
+pub mod tasks_doc;
+pub mod tasks_format;
 pub mod tasks_parser;
+pub mod tasks_source;
 pub mod tasks_writer;
+pub mod templates;
+pub mod text;
+pub mod timezone;
 pub mod watcher;
+pub mod wip;
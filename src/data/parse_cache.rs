@@ -0,0 +1,292 @@
+//! Hook file parse cache
+//!
+//! Parsing every JSONL file dominates a cold start on a big event
+//! directory, and most of those files have not changed since the last run.
+//! Each hook file parsed at startup is saved under the cache directory
+//! (`[hooks] cache_dir`, by default `<events dir>/cache`) with the size and
+//! modification time it had; at the next start a file with the same size,
+//! mtime and read limit is read back from its entry instead of parsed
+//! again. Entries keep timestamps as nanoseconds and the agent, task,
+//! session and tool names interned, which reads back in about half the time
+//! of the JSONL. Parse errors are not kept: a file is only reported as
+//! malformed when it is actually parsed.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::data::cost::Usage;
+use crate::data::hook_parser::{self, EventType, HookEvent, ParseResult};
+
+/// Bumped when the entry layout changes; older entries are ignored
+const VERSION: u32 = 1;
+
+/// Directory of cached hook file parses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCache {
+    dir: PathBuf,
+}
+
+/// What a hook file looked like when it was parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Stamp {
+    size: u64,
+    modified_secs: u64,
+    modified_nanos: u32,
+}
+
+impl Stamp {
+    fn of(path: &Path) -> std::io::Result<Self> {
+        let meta = std::fs::metadata(path)?;
+        let modified = meta
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Ok(Self {
+            size: meta.len(),
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+        })
+    }
+}
+
+/// One event, its names as indexes into [`Entry::names`]
+#[derive(Serialize, Deserialize)]
+struct Row(
+    EventType,
+    /// Timestamp in nanoseconds
+    i64,
+    u32,
+    u32,
+    u32,
+    Option<u32>,
+    Option<String>,
+    Option<String>,
+    Option<Usage>,
+);
+
+/// A cached parse of one hook file
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    version: u32,
+    path: PathBuf,
+    stamp: Stamp,
+    max_bytes: u64,
+    skipped_bytes: u64,
+    lossy_lines: usize,
+    names: Vec<String>,
+    events: Vec<Row>,
+}
+
+impl Entry {
+    fn new(path: &Path, stamp: Stamp, max_bytes: u64, result: &ParseResult) -> Self {
+        let mut names = Vec::new();
+        let mut index: HashMap<String, u32> = HashMap::new();
+        let mut intern = |name: &str| -> u32 {
+            if let Some(&i) = index.get(name) {
+                return i;
+            }
+            names.push(name.to_string());
+            let i = names.len() as u32 - 1;
+            index.insert(name.to_string(), i);
+            i
+        };
+        let events = result
+            .events
+            .iter()
+            .map(|e| {
+                Row(
+                    e.event_type.clone(),
+                    e.timestamp.timestamp_nanos_opt().unwrap_or_default(),
+                    intern(&e.agent_id),
+                    intern(&e.task_id),
+                    intern(&e.session_id),
+                    e.tool_name.as_deref().map(&mut intern),
+                    e.error_message.clone(),
+                    e.tool_output.clone(),
+                    e.usage.clone(),
+                )
+            })
+            .collect();
+        Self {
+            version: VERSION,
+            path: path.to_path_buf(),
+            stamp,
+            max_bytes,
+            skipped_bytes: result.skipped_bytes,
+            lossy_lines: result.lossy_lines,
+            names,
+            events,
+        }
+    }
+
+    /// The parse result, or `None` when a name index is out of range
+    fn into_result(self) -> Option<ParseResult> {
+        let names = self.names;
+        let name = |i: u32| names.get(i as usize).cloned();
+        let events = self
+            .events
+            .into_iter()
+            .map(|row| {
+                Some(HookEvent {
+                    event_type: row.0,
+                    timestamp: DateTime::from_timestamp_nanos(row.1),
+                    agent_id: name(row.2)?,
+                    task_id: name(row.3)?,
+                    session_id: name(row.4)?,
+                    tool_name: match row.5 {
+                        Some(i) => Some(name(i)?),
+                        None => None,
+                    },
+                    error_message: row.6,
+                    tool_output: row.7,
+                    usage: row.8,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(ParseResult {
+            events,
+            errors: Vec::new(),
+            skipped_bytes: self.skipped_bytes,
+            lossy_lines: self.lossy_lines,
+        })
+    }
+}
+
+impl ParseCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Entry file for a hook file: a hash of its path
+    fn entry_path(&self, path: &Path) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        path.hash(&mut hasher);
+        self.dir.join(format!("{:016x}.json", hasher.finish()))
+    }
+
+    /// The cached parse of `path`, if it is unchanged since it was cached
+    pub fn cached(&self, path: &Path, max_bytes: u64) -> Option<ParseResult> {
+        let stamp = Stamp::of(path).ok()?;
+        self.load(path, stamp, max_bytes)
+    }
+
+    fn load(&self, path: &Path, stamp: Stamp, max_bytes: u64) -> Option<ParseResult> {
+        let bytes = std::fs::read(self.entry_path(path)).ok()?;
+        let entry: Entry = serde_json::from_slice(&bytes).ok()?;
+        let fresh = entry.version == VERSION
+            && entry.path == path
+            && entry.stamp == stamp
+            && entry.max_bytes == max_bytes;
+        fresh.then(|| entry.into_result()).flatten()
+    }
+
+    /// Parse `path` within `max_bytes`, or reuse its cached parse when the
+    /// file is unchanged. A fresh parse is cached unless the file changed
+    /// while it was read; failing to write the cache is only logged.
+    pub fn read(&self, path: &Path, max_bytes: u64) -> std::io::Result<ParseResult> {
+        // Without a modification time there is nothing to key the cache on
+        let Ok(stamp) = Stamp::of(path) else {
+            return hook_parser::parse_hook_file_limited(path, max_bytes);
+        };
+        if let Some(result) = self.load(path, stamp, max_bytes) {
+            tracing::debug!("{} unchanged, read from the parse cache", path.display());
+            return Ok(result);
+        }
+        let result = hook_parser::parse_hook_file_limited(path, max_bytes)?;
+        if Stamp::of(path).ok() == Some(stamp) {
+            if let Err(e) = self.store(path, stamp, max_bytes, &result) {
+                tracing::warn!("cannot cache the parse of {}: {e}", path.display());
+            }
+        }
+        Ok(result)
+    }
+
+    fn store(
+        &self,
+        path: &Path,
+        stamp: Stamp,
+        max_bytes: u64,
+        result: &ParseResult,
+    ) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let entry = Entry::new(path, stamp, max_bytes, result);
+        let target = self.entry_path(path);
+        // Written aside and renamed, so a crash never leaves half an entry
+        let partial = target.with_extension("json.tmp");
+        std::fs::write(&partial, serde_json::to_vec(&entry)?)?;
+        std::fs::rename(&partial, &target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const EVENTS: &str = concat!(
+        r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:00.250Z","agent_id":"backend","task_id":"T1","session_id":"s","tool_name":"Bash"}"#,
+        "\n",
+        r#"{"event_type":"error","timestamp":"2026-02-08T10:00:01Z","agent_id":"backend","task_id":"T1","session_id":"s","error_message":"line 1\nline 2"}"#,
+        "\n",
+        r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:02Z","agent_id":"backend","task_id":"T1","session_id":"s","usage":{"input_tokens":10,"output_tokens":2}}"#,
+        "\n",
+    );
+
+    fn json(result: &ParseResult) -> serde_json::Value {
+        serde_json::to_value(&result.events).unwrap()
+    }
+
+    #[test]
+    fn unchanged_files_read_back_from_the_cache() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let file = tmp.path().join("events.jsonl");
+        std::fs::write(&file, EVENTS).unwrap();
+        let cache = ParseCache::new(tmp.path().join("cache"));
+
+        assert!(cache.cached(&file, u64::MAX).is_none());
+        let parsed = cache.read(&file, u64::MAX).unwrap();
+        let cached = cache.cached(&file, u64::MAX).expect("cached after a read");
+        assert_eq!(json(&cached), json(&parsed));
+        assert_eq!(cached.events.len(), 3);
+        assert_eq!(
+            cached.events[1].error_message.as_deref(),
+            Some("line 1\nline 2")
+        );
+        assert_eq!(json(&cache.read(&file, u64::MAX).unwrap()), json(&parsed));
+
+        // Another read limit is another parse
+        assert!(cache.cached(&file, 100).is_none());
+    }
+
+    #[test]
+    fn a_changed_file_is_parsed_again() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let file = tmp.path().join("events.jsonl");
+        std::fs::write(&file, EVENTS).unwrap();
+        let cache = ParseCache::new(tmp.path().join("cache"));
+        cache.read(&file, u64::MAX).unwrap();
+
+        let mut appended = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&file)
+            .unwrap();
+        appended
+            .write_all(br#"{"event_type":"heartbeat","timestamp":"2026-02-08T10:00:03Z","agent_id":"backend","task_id":"T1","session_id":"s"}"#)
+            .unwrap();
+        drop(appended);
+        assert!(cache.cached(&file, u64::MAX).is_none());
+        assert_eq!(cache.read(&file, u64::MAX).unwrap().events.len(), 4);
+        assert_eq!(cache.cached(&file, u64::MAX).unwrap().events.len(), 4);
+
+        // A corrupt entry is a miss, not an error
+        std::fs::write(cache.entry_path(&file), "{").unwrap();
+        assert!(cache.cached(&file, u64::MAX).is_none());
+        assert_eq!(cache.read(&file, u64::MAX).unwrap().events.len(), 4);
+    }
+}
@@ -0,0 +1,273 @@
+//! Differences between two versions of the plan
+//!
+//! `simple-claude-board diff <old>` compares an earlier tasks file with the
+//! current one: tasks added, removed or renamed (same ID, new name), status
+//! changes with regressions flagged, and `blocked_by` changes. Tasks are
+//! matched by ID.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::data::statuses::{self, StatusDef};
+use crate::data::tasks_parser::{ParsedPhase, ParsedTask, TaskStatus};
+
+/// A task that exists in only one of the versions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TaskRef {
+    pub id: String,
+    pub name: String,
+    pub phase: String,
+}
+
+/// A task whose name changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Rename {
+    pub id: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// A task whose status changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatusChange {
+    pub id: String,
+    pub name: String,
+    pub from: String,
+    pub to: String,
+    /// The task was done and is not anymore, or it newly failed
+    pub regression: bool,
+}
+
+/// A task whose `blocked_by` list changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DepsChange {
+    pub id: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// Everything that changed between two plans, in the new plan's order
+/// (removed tasks in the old plan's order)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PlanDiff {
+    pub added: Vec<TaskRef>,
+    pub removed: Vec<TaskRef>,
+    pub renamed: Vec<Rename>,
+    pub status: Vec<StatusChange>,
+    pub deps: Vec<DepsChange>,
+}
+
+fn tasks(phases: &[ParsedPhase]) -> impl Iterator<Item = (&ParsedPhase, &ParsedTask)> {
+    phases
+        .iter()
+        .flat_map(|phase| phase.tasks.iter().map(move |task| (phase, task)))
+}
+
+fn find<'p>(phases: &'p [ParsedPhase], id: &str) -> Option<&'p ParsedTask> {
+    tasks(phases).map(|(_, t)| t).find(|t| t.id == id)
+}
+
+fn is_regression(from: &TaskStatus, to: &TaskStatus, custom: &[StatusDef]) -> bool {
+    (statuses::is_done(from, custom) && !statuses::is_done(to, custom))
+        || (*to == TaskStatus::Failed && *from != TaskStatus::Failed)
+}
+
+/// Compare the `old` plan with the `new` one
+pub fn diff(old: &[ParsedPhase], new: &[ParsedPhase], custom: &[StatusDef]) -> PlanDiff {
+    let mut result = PlanDiff::default();
+    for (phase, task) in tasks(new) {
+        let Some(before) = find(old, &task.id) else {
+            result.added.push(TaskRef {
+                id: task.id.clone(),
+                name: task.name.clone(),
+                phase: phase.id.clone(),
+            });
+            continue;
+        };
+        if before.name != task.name {
+            result.renamed.push(Rename {
+                id: task.id.clone(),
+                from: before.name.clone(),
+                to: task.name.clone(),
+            });
+        }
+        if before.status != task.status {
+            result.status.push(StatusChange {
+                id: task.id.clone(),
+                name: task.name.clone(),
+                from: before.status.label().to_string(),
+                to: task.status.label().to_string(),
+                regression: is_regression(&before.status, &task.status, custom),
+            });
+        }
+        let added: Vec<String> = task
+            .blocked_by
+            .iter()
+            .filter(|d| !before.blocked_by.contains(d))
+            .cloned()
+            .collect();
+        let removed: Vec<String> = before
+            .blocked_by
+            .iter()
+            .filter(|d| !task.blocked_by.contains(d))
+            .cloned()
+            .collect();
+        if !added.is_empty() || !removed.is_empty() {
+            result.deps.push(DepsChange {
+                id: task.id.clone(),
+                added,
+                removed,
+            });
+        }
+    }
+    for (phase, task) in tasks(old) {
+        if find(new, &task.id).is_none() {
+            result.removed.push(TaskRef {
+                id: task.id.clone(),
+                name: task.name.clone(),
+                phase: phase.id.clone(),
+            });
+        }
+    }
+    result
+}
+
+impl PlanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.status.is_empty()
+            && self.deps.is_empty()
+    }
+
+    pub fn regressions(&self) -> usize {
+        self.status.iter().filter(|c| c.regression).count()
+    }
+
+    /// Print the diff as sections of one line per change
+    pub fn write_text(&self, out: &mut impl Write) -> io::Result<()> {
+        if self.is_empty() {
+            return writeln!(out, "No changes");
+        }
+        if !self.added.is_empty() {
+            writeln!(out, "Added ({})", self.added.len())?;
+            for t in &self.added {
+                writeln!(out, "  + {}  {}  [{}]", t.id, t.name, t.phase)?;
+            }
+        }
+        if !self.removed.is_empty() {
+            writeln!(out, "Removed ({})", self.removed.len())?;
+            for t in &self.removed {
+                writeln!(out, "  - {}  {}  [{}]", t.id, t.name, t.phase)?;
+            }
+        }
+        if !self.renamed.is_empty() {
+            writeln!(out, "Renamed ({})", self.renamed.len())?;
+            for r in &self.renamed {
+                writeln!(out, "  ~ {}  \"{}\" -> \"{}\"", r.id, r.from, r.to)?;
+            }
+        }
+        if !self.status.is_empty() {
+            writeln!(
+                out,
+                "Status ({}, {} regressions)",
+                self.status.len(),
+                self.regressions()
+            )?;
+            for c in &self.status {
+                let flag = if c.regression { "  REGRESSION" } else { "" };
+                writeln!(out, "  {}  {} -> {}{flag}", c.id, c.from, c.to)?;
+            }
+        }
+        if !self.deps.is_empty() {
+            writeln!(out, "Dependencies ({})", self.deps.len())?;
+            for d in &self.deps {
+                let changes: Vec<String> = d
+                    .added
+                    .iter()
+                    .map(|id| format!("+{id}"))
+                    .chain(d.removed.iter().map(|id| format!("-{id}")))
+                    .collect();
+                writeln!(out, "  {}  {}", d.id, changes.join(" "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::tasks_parser::parse_tasks_md;
+
+    const OLD: &str = "# Phase 1: Core\n\
+        ### [x] T1: Login\n\
+        ### [/] T2: Logout\n\
+        ### [ ] T3: Reset\n\
+        - blocked_by: T1\n\
+        ### [ ] T4: Audit\n";
+    const NEW: &str = "# Phase 1: Core\n\
+        ### [/] T1: Sign in\n\
+        ### [Failed] T2: Logout\n\
+        ### [ ] T3: Reset\n\
+        - blocked_by: T2\n\
+        # Phase 2: Extras\n\
+        ### [ ] T5: Export\n";
+
+    fn plan_diff() -> PlanDiff {
+        diff(
+            &parse_tasks_md(OLD).unwrap(),
+            &parse_tasks_md(NEW).unwrap(),
+            &[],
+        )
+    }
+
+    #[test]
+    fn finds_added_removed_renamed_status_and_deps() {
+        let d = plan_diff();
+        assert_eq!(d.added.len(), 1);
+        assert_eq!(
+            (d.added[0].id.as_str(), d.added[0].phase.as_str()),
+            ("T5", "P2")
+        );
+        assert_eq!(d.removed[0].id, "T4");
+        assert_eq!(
+            d.renamed,
+            vec![Rename {
+                id: "T1".to_string(),
+                from: "Login".to_string(),
+                to: "Sign in".to_string(),
+            }]
+        );
+        assert_eq!(d.status.len(), 2);
+        assert!(d.status.iter().all(|c| c.regression));
+        assert_eq!(
+            d.deps,
+            vec![DepsChange {
+                id: "T3".to_string(),
+                added: vec!["T2".to_string()],
+                removed: vec!["T1".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn text_lists_each_section() {
+        let mut out = Vec::new();
+        plan_diff().write_text(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("Added (1)\n  + T5  Export  [P2]\n"), "{text}");
+        assert!(
+            text.contains("  T2  InProgress -> Failed  REGRESSION\n"),
+            "{text}"
+        );
+        assert!(text.contains("Dependencies (1)\n  T3  +T2 -T1\n"), "{text}");
+
+        let same = parse_tasks_md(OLD).unwrap();
+        let mut out = Vec::new();
+        diff(&same, &same, &[]).write_text(&mut out).unwrap();
+        assert_eq!(out, b"No changes\n");
+    }
+}
@@ -0,0 +1,292 @@
+//! Agent roster
+//!
+//! The agents expected to work on the plan, with a role and how many tasks
+//! each may run at once. Entries come from `[[agents.roster]]` in the config
+//! and from an `AGENTS.md` next to the tasks file, one list item per agent:
+//!
+//! ```text
+//! - @backend-specialist: API and database work (max 2)
+//! - @frontend (max 1)
+//! ```
+//!
+//! Coverage checks the plan against the roster: tasks nobody is assigned to,
+//! agents over their limit and agents working outside the roster.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::data::state::DashboardState;
+use crate::data::statuses;
+use crate::data::tasks_parser::TaskStatus;
+
+/// Roster file looked up next to the tasks file
+pub const ROSTER_FILE_NAME: &str = "AGENTS.md";
+
+/// One agent on the roster
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RosterEntry {
+    /// Agent name, with or without the leading `@`
+    pub name: String,
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Tasks the agent may have in progress at once (unset = unlimited)
+    #[serde(default)]
+    pub max_parallel: Option<usize>,
+}
+
+impl RosterEntry {
+    fn id(&self) -> &str {
+        self.name.trim_start_matches('@')
+    }
+}
+
+/// Parse the agent list items of an `AGENTS.md`; other lines are ignored
+pub fn parse_agents_md(content: &str) -> Vec<RosterEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let item = line.trim_start().strip_prefix(['-', '*'])?.trim_start();
+            let rest = item.strip_prefix('@')?;
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == ':')
+                .unwrap_or(rest.len());
+            let (name, rest) = rest.split_at(end);
+            if name.is_empty() {
+                return None;
+            }
+            let mut rest = rest.to_string();
+            let mut max_parallel = None;
+            if let Some(start) = rest.find("(max ") {
+                if let Some(len) = rest[start..].find(')') {
+                    max_parallel = rest[start + 5..start + len].trim().parse().ok();
+                    rest.replace_range(start..=start + len, "");
+                }
+            }
+            let role = rest
+                .trim()
+                .trim_start_matches([':', '-', '\u{2014}'])
+                .trim()
+                .to_string();
+            Some(RosterEntry {
+                name: name.to_string(),
+                role: (!role.is_empty()).then_some(role),
+                max_parallel,
+            })
+        })
+        .collect()
+}
+
+/// The configured entries followed by those of the `AGENTS.md` next to
+/// `tasks_path`. A config entry wins over a file entry of the same name.
+pub fn load(configured: &[RosterEntry], tasks_path: Option<&Path>) -> Vec<RosterEntry> {
+    let mut roster = configured.to_vec();
+    let file = tasks_path
+        .and_then(|p| p.parent())
+        .map(|dir| dir.join(ROSTER_FILE_NAME));
+    let Some(content) = file.and_then(|f| std::fs::read_to_string(f).ok()) else {
+        return roster;
+    };
+    for entry in parse_agents_md(&content) {
+        if !roster
+            .iter()
+            .any(|r| r.id().eq_ignore_ascii_case(entry.id()))
+        {
+            roster.push(entry);
+        }
+    }
+    roster
+}
+
+/// A roster agent and its current load
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentLoad {
+    pub name: String,
+    pub role: Option<String>,
+    /// InProgress tasks worked on by the agent
+    pub active: usize,
+    pub max: Option<usize>,
+}
+
+impl AgentLoad {
+    pub fn is_over(&self) -> bool {
+        self.max.is_some_and(|m| self.active > m)
+    }
+
+    pub fn has_capacity(&self) -> bool {
+        self.max.map_or(true, |m| self.active < m)
+    }
+}
+
+/// How well the roster covers the plan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Coverage {
+    /// Roster agents in roster order
+    pub agents: Vec<AgentLoad>,
+    /// Open tasks without an agent, as (ID, name)
+    pub unassigned: Vec<(String, String)>,
+    /// Agents on open tasks that are not on the roster, with their task IDs
+    pub off_roster: Vec<(String, Vec<String>)>,
+}
+
+impl Coverage {
+    pub fn overloaded(&self) -> impl Iterator<Item = &AgentLoad> {
+        self.agents.iter().filter(|a| a.is_over())
+    }
+
+    /// The least busy roster agent with room for another task, earlier
+    /// entries first on a tie
+    pub fn pick(&self) -> Option<&str> {
+        self.agents
+            .iter()
+            .filter(|a| a.has_capacity())
+            .min_by_key(|a| a.active)
+            .map(|a| a.name.as_str())
+    }
+}
+
+/// Check the open tasks of the plan against `roster`. A task's agent is the
+/// one hook events ran it under, or else its TASKS.md `@agent`.
+pub fn coverage(state: &DashboardState, roster: &[RosterEntry]) -> Coverage {
+    let mut result = Coverage {
+        agents: roster
+            .iter()
+            .map(|r| AgentLoad {
+                name: r.id().to_string(),
+                role: r.role.clone(),
+                active: 0,
+                max: r.max_parallel,
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let mut off_roster: HashMap<String, Vec<String>> = HashMap::new();
+    let open = state
+        .phases
+        .iter()
+        .flat_map(|p| &p.tasks)
+        .filter(|t| !statuses::is_done(&t.status, &state.custom_statuses));
+    for task in open {
+        let Some(agent) = state.worker_for(task).map(|a| a.trim_start_matches('@')) else {
+            result.unassigned.push((task.id.clone(), task.name.clone()));
+            continue;
+        };
+        match result
+            .agents
+            .iter_mut()
+            .find(|a| a.name.eq_ignore_ascii_case(agent))
+        {
+            Some(load) if task.status == TaskStatus::InProgress => load.active += 1,
+            Some(_) => {}
+            None => off_roster
+                .entry(agent.to_string())
+                .or_default()
+                .push(task.id.clone()),
+        }
+    }
+    result.off_roster = off_roster.into_iter().collect();
+    result.off_roster.sort();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = "\
+# Phase 1: Core
+
+### [InProgress] T1: One
+- **담당**: @backend
+
+### [InProgress] T2: Two
+- **담당**: @backend
+
+### [ ] T3: Three
+- **담당**: @qa
+
+### [ ] T4: Four
+
+### [x] T5: Five
+";
+
+    fn entry(name: &str, max: Option<usize>) -> RosterEntry {
+        RosterEntry {
+            name: name.to_string(),
+            role: None,
+            max_parallel: max,
+        }
+    }
+
+    #[test]
+    fn parses_agents_md_items() {
+        let roster = parse_agents_md(
+            "# Agents\n\n\
+             - @backend: API and database work (max 2)\n\
+             * @frontend (max 1)\n\
+             - @docs \u{2014} Guides\n\
+             - not an agent\n",
+        );
+        assert_eq!(
+            roster,
+            vec![
+                RosterEntry {
+                    name: "backend".to_string(),
+                    role: Some("API and database work".to_string()),
+                    max_parallel: Some(2),
+                },
+                entry("frontend", Some(1)),
+                RosterEntry {
+                    name: "docs".to_string(),
+                    role: Some("Guides".to_string()),
+                    max_parallel: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn config_entries_win_over_the_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join(ROSTER_FILE_NAME),
+            "- @backend (max 3)\n- @frontend\n",
+        )
+        .unwrap();
+        let roster = load(
+            &[entry("@backend", Some(1))],
+            Some(&tmp.path().join("TASKS.md")),
+        );
+        assert_eq!(
+            roster,
+            vec![entry("@backend", Some(1)), entry("frontend", None)]
+        );
+    }
+
+    #[test]
+    fn coverage_counts_load_gaps_and_strangers() {
+        let state = DashboardState::from_tasks_content(PLAN).unwrap();
+        let coverage = coverage(
+            &state,
+            &[entry("@backend", Some(1)), entry("frontend", Some(2))],
+        );
+        assert_eq!(coverage.agents[0].active, 2);
+        assert_eq!(
+            coverage
+                .overloaded()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>(),
+            ["backend"]
+        );
+        assert_eq!(
+            coverage.unassigned,
+            [("T4".to_string(), "Four".to_string())]
+        );
+        assert_eq!(
+            coverage.off_roster,
+            [("qa".to_string(), vec!["T3".to_string()])]
+        );
+        assert_eq!(coverage.pick(), Some("frontend"));
+    }
+}
@@ -3,14 +3,31 @@
 //! Combines parsed TASKS.md data, hook events, and file watcher
 //! into a single dashboard state for the TUI to consume.
 
-use std::collections::HashMap;
-use std::path::Path;
-
-use chrono::{DateTime, Utc};
-
-use crate::analysis::rules::{analyze_error, ErrorCategory};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::analysis::retry::RetryTracker;
+use crate::analysis::rules::{
+    analyze_error_with, analyze_rate_limit, retry_after, CustomRule, ErrorCategory, Severity,
+};
+use crate::config::{Config, HooksConfig};
+use crate::data::agent_groups::{self, AgentGroup, SessionDisplay};
+use crate::data::clock::{Clock, SystemClock};
+use crate::data::cost::Spend;
+use crate::data::filter::TaskFilter;
 use crate::data::hook_parser::{self, EventType, HookEvent};
-use crate::data::tasks_parser::{self, ParsedPhase, TaskStatus};
+use crate::data::parse_cache::ParseCache;
+use crate::data::statuses::{self, StatusDef};
+use crate::data::task_id::{TaskId, TaskIds};
+use crate::data::tasks_parser::{
+    ParseError, ParseOutcome, ParsedPhase, ParsedTask, ProgressWeights, TaskStatus,
+};
+use crate::data::tasks_source::{MarkdownSource, TasksSource};
+use crate::data::text;
+use crate::data::timezone::DisplayZone;
 
 /// Agent activity status derived from hook events
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -20,10 +37,21 @@ pub enum AgentStatus {
     Error,
 }
 
+impl AgentStatus {
+    /// Panel order: running first, then failing, then idle
+    fn rank(&self) -> u8 {
+        match self {
+            AgentStatus::Running => 0,
+            AgentStatus::Error => 1,
+            AgentStatus::Idle => 2,
+        }
+    }
+}
+
 /// Record of an agent working on a task
 #[derive(Debug, Clone)]
 pub struct TaskHistoryEntry {
-    pub task_id: String,
+    pub task_id: TaskId,
     pub started_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
 }
@@ -31,12 +59,133 @@ pub struct TaskHistoryEntry {
 /// Maximum number of recent tools to track per agent
 const MAX_RECENT_TOOLS: usize = 10;
 
+/// Maximum number of tool calls kept per agent for the lane timeline
+const MAX_TOOL_HISTORY: usize = 500;
+
+/// One tool invocation, paired from `tool_start`/`tool_end`
+#[derive(Debug, Clone)]
+pub struct ToolCall {
+    pub tool: String,
+    pub started_at: DateTime<Utc>,
+    /// None while the tool is still running
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl ToolCall {
+    /// Time taken so far; a running call counts up to `now`
+    pub fn duration(&self, now: DateTime<Utc>) -> Duration {
+        self.ended_at.unwrap_or(now) - self.started_at
+    }
+}
+
+/// Finished calls a tool needs before a call can be flagged as slow
+const SLOW_MIN_SAMPLES: usize = 3;
+/// A call slower than this many times the tool's average is flagged
+const SLOW_FACTOR: i32 = 3;
+/// Calls shorter than this are never flagged, however fast the average
+const SLOW_MIN_SECS: i64 = 5;
+
+/// Duration statistics of one tool's finished calls across all agents
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolStats {
+    pub calls: usize,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl ToolStats {
+    fn record(&mut self, duration: Duration) {
+        self.calls += 1;
+        self.total += duration;
+        self.max = self.max.max(duration);
+    }
+
+    pub fn average(&self) -> Duration {
+        if self.calls == 0 {
+            return Duration::zero();
+        }
+        self.total / self.calls as i32
+    }
+
+    /// Whether a call that took `duration` is abnormally slow for this tool
+    pub fn is_slow(&self, duration: Duration) -> bool {
+        self.calls >= SLOW_MIN_SAMPLES
+            && duration >= Duration::seconds(SLOW_MIN_SECS)
+            && duration > self.average() * SLOW_FACTOR
+    }
+}
+
+/// Output lines kept from an agent's latest tool call
+pub const OUTPUT_PREVIEW_LINES: usize = 5;
+
+/// The last lines of a finished tool call's output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolOutput {
+    pub tool: Option<String>,
+    pub lines: Vec<String>,
+}
+
+impl ToolOutput {
+    fn from_event(tool: Option<String>, output: &str) -> Self {
+        let lines: Vec<&str> = output.lines().filter(|l| !l.trim().is_empty()).collect();
+        let skip = lines.len().saturating_sub(OUTPUT_PREVIEW_LINES);
+        Self {
+            tool,
+            lines: lines[skip..].iter().map(|l| l.to_string()).collect(),
+        }
+    }
+}
+
+/// Seconds without any event after which a working agent counts as vanished
+pub const HEARTBEAT_TIMEOUT_SECS: i64 = 30;
+
+/// Cooldown of a rate limit whose message does not say how long to wait
+pub const DEFAULT_COOLDOWN_SECS: u64 = 60;
+
+/// Longest cooldown a rate-limit message can ask for
+pub const MAX_COOLDOWN_SECS: u64 = 24 * 3600;
+
+/// Rate-limit hits of an agent or a task
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimits {
+    pub hits: usize,
+    pub last_hit: Option<DateTime<Utc>>,
+    /// End of the latest cooldown window
+    pub until: Option<DateTime<Utc>>,
+    /// Sum of all cooldown windows
+    pub waited: Duration,
+}
+
+impl RateLimits {
+    fn record(&mut self, at: DateTime<Utc>, until: DateTime<Utc>) {
+        self.hits += 1;
+        self.last_hit = self.last_hit.max(Some(at));
+        self.until = self.until.max(Some(until));
+        self.waited += until - at;
+    }
+
+    /// Seconds left of the cooldown at `now`, None once it has passed
+    pub fn remaining(&self, now: DateTime<Utc>) -> Option<i64> {
+        let left = (self.until? - now).num_seconds();
+        (left > 0).then_some(left)
+    }
+}
+
+/// Whether a working agent that sends heartbeats is still there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Liveness {
+    /// Signalled within the timeout; `since` is seconds since the last event
+    Alive { since: i64 },
+    /// Silent for longer than the timeout
+    Vanished { since: i64 },
+}
+
 /// A snapshot of one agent's current state
 #[derive(Debug, Clone)]
 pub struct AgentState {
     pub agent_id: String,
     pub status: AgentStatus,
-    pub current_task: Option<String>,
+    pub current_task: Option<TaskId>,
     pub current_tool: Option<String>,
     pub event_count: usize,
     pub error_count: usize,
@@ -45,7 +194,63 @@ pub struct AgentState {
     pub last_seen: Option<DateTime<Utc>>,
     pub tool_counts: HashMap<String, usize>,
     pub recent_tools: Vec<String>,
+    /// Tool calls in start order, capped at the most recent 500
+    pub tool_history: Vec<ToolCall>,
     pub session_id: Option<String>,
+    /// Latest `heartbeat` event; None if the agent's hooks never send one
+    pub last_heartbeat: Option<DateTime<Utc>>,
+    /// Output of the latest tool call whose `tool_end` carried any
+    pub last_output: Option<ToolOutput>,
+    /// Hook agents behind this agent by (session ID, hook agent ID); more
+    /// than one when grouped or when sessions share an agent ID
+    pub members: BTreeMap<(String, String), AgentMember>,
+    /// Rate limits the agent hit and its current cooldown
+    pub rate_limits: RateLimits,
+}
+
+/// One hook agent ID in one session, merged into a displayed agent. Each
+/// member keeps its own task and tool, so sessions sharing an agent ID do
+/// not finish each other's work.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentMember {
+    pub agent_id: String,
+    pub session_id: String,
+    pub status: AgentStatus,
+    pub current_task: Option<TaskId>,
+    pub current_tool: Option<String>,
+    /// Start times of this member's open tool calls, oldest first
+    open_calls: Vec<(String, DateTime<Utc>)>,
+}
+
+impl AgentMember {
+    fn new(agent_id: &str, session_id: &str) -> Self {
+        Self {
+            agent_id: agent_id.to_string(),
+            session_id: session_id.to_string(),
+            status: AgentStatus::Idle,
+            current_task: None,
+            current_tool: None,
+            open_calls: Vec::new(),
+        }
+    }
+}
+
+impl AgentState {
+    /// Liveness of a working agent. None for idle agents and for agents
+    /// without heartbeats, whose silence says nothing.
+    pub fn liveness(&self, now: DateTime<Utc>) -> Option<Liveness> {
+        let beat = self.last_heartbeat?;
+        if self.status == AgentStatus::Idle {
+            return None;
+        }
+        let last = self.last_seen.map_or(beat, |seen| seen.max(beat));
+        let since = (now - last).num_seconds().max(0);
+        Some(if since > HEARTBEAT_TIMEOUT_SECS {
+            Liveness::Vanished { since }
+        } else {
+            Liveness::Alive { since }
+        })
+    }
 }
 
 /// Timing info for a task derived from hook events
@@ -55,6 +260,20 @@ pub struct TaskTiming {
     pub completed_at: Option<DateTime<Utc>>,
 }
 
+/// Hook activity under a task ID the tasks file does not have, e.g. work an
+/// agent took on outside the plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UntrackedWork {
+    pub task_id: String,
+    /// Agents (after grouping) that worked on it, in order of appearance
+    pub agents: Vec<String>,
+    pub events: usize,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// Started by `agent_start` and not ended yet
+    pub running: bool,
+}
+
 /// Maximum number of recent errors to keep
 const MAX_RECENT_ERRORS: usize = 50;
 
@@ -62,12 +281,35 @@ const MAX_RECENT_ERRORS: usize = 50;
 #[derive(Debug, Clone)]
 pub struct ErrorRecord {
     pub agent_id: String,
-    pub task_id: String,
+    pub task_id: TaskId,
     pub message: String,
     pub category: ErrorCategory,
+    pub severity: Severity,
     pub retryable: bool,
     pub suggestion: &'static str,
+    /// Suggestion with details from the message (port, path, ...) when available
+    pub hint: Option<String>,
     pub timestamp: DateTime<Utc>,
+    /// End of the cooldown a rate-limit error asked for
+    pub cooldown_until: Option<DateTime<Utc>>,
+    /// Position of the error's hook event in `DashboardState::events`
+    pub event_index: usize,
+}
+
+impl ErrorRecord {
+    /// The most specific suggestion available for display
+    pub fn display_suggestion(&self) -> &str {
+        self.hint.as_deref().unwrap_or(self.suggestion)
+    }
+
+    /// First non-blank line of the message, for one-line displays
+    pub fn headline(&self) -> &str {
+        self.message
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+    }
 }
 
 /// The complete dashboard state
@@ -75,14 +317,78 @@ pub struct ErrorRecord {
 pub struct DashboardState {
     pub phases: Vec<ParsedPhase>,
     pub agents: HashMap<String, AgentState>,
-    pub task_times: HashMap<String, TaskTiming>,
+    pub task_times: HashMap<TaskId, TaskTiming>,
     /// Maps task_id → agent_id (from hook events, persists after agent ends)
-    pub task_agents: HashMap<String, String>,
+    pub task_agents: HashMap<TaskId, String>,
     pub total_tasks: usize,
     pub completed_tasks: usize,
     pub failed_tasks: usize,
     pub overall_progress: f32,
     pub recent_errors: Vec<ErrorRecord>,
+    /// Retry attempts and circuit-breaker state (survives reloads)
+    pub retries: RetryTracker,
+    /// User-defined error rules checked before the built-in ones
+    pub custom_rules: Vec<CustomRule>,
+    /// Rules merging hook agent IDs into display groups
+    pub agent_groups: Vec<AgentGroup>,
+    /// Whether sessions sharing an agent ID show as one agent
+    pub session_display: SessionDisplay,
+    /// Custom status vocabulary (icons, colors, which count as done)
+    pub custom_statuses: Vec<StatusDef>,
+    /// How tasks are weighted in `overall_progress` and phase progress
+    pub progress_weights: ProgressWeights,
+    /// Backend that parses the tasks file on (re)load
+    pub tasks_source: Arc<dyn TasksSource>,
+    /// Problems found by the last (re)load of the tasks file
+    pub parse_warnings: Vec<ParseError>,
+    /// Tasks completed by agents that a human has not approved yet (survives reloads)
+    pub awaiting_review: HashSet<TaskId>,
+    /// Per-tool duration statistics from paired tool_start/tool_end events
+    pub tool_stats: HashMap<String, ToolStats>,
+    /// Every hook event applied since the last reload, kept for replay
+    pub events: Vec<HookEvent>,
+    /// Token and cost usage per task, from hook events
+    pub task_spend: HashMap<TaskId, Spend>,
+    /// Token and cost usage per agent (after grouping)
+    pub agent_spend: HashMap<String, Spend>,
+    /// Rate limits hit while working on each task
+    pub task_rate_limits: HashMap<TaskId, RateLimits>,
+    /// Virtual now of a state rebuilt by [`DashboardState::at`]; None when live
+    pub scrub_at: Option<DateTime<Utc>>,
+    /// Timezone timestamps are shown in
+    pub timezone: DisplayZone,
+    /// Latest `run_start` event
+    pub run_started: Option<DateTime<Utc>>,
+    /// Every task ID seen in the plan or in hook events, shared by the maps above
+    pub task_ids: TaskIds,
+    /// Task ID → (phase index, task index) in `phases`, rebuilt on reload
+    pub task_index: HashMap<TaskId, (usize, usize)>,
+    /// Every task ID of the tasks file at its last load, including those the
+    /// task filter hides
+    pub planned_tasks: HashSet<TaskId>,
+    /// Idle agents silent for longer than this are left out of the panel
+    pub hide_idle_after: Option<Duration>,
+    /// Events in each hook file at its last read, for telling sources apart
+    pub event_sources: BTreeMap<PathBuf, usize>,
+    /// Hook file each of `events` was read from; None for events applied
+    /// directly with [`update_from_events`](Self::update_from_events)
+    pub event_origins: Vec<Option<Arc<Path>>>,
+    /// Hook files larger than this are only read from their last this many bytes
+    pub hook_read_limit: u64,
+    /// Parses of hook files reused at load when the files are unchanged
+    pub parse_cache: Option<ParseCache>,
+    /// Hook files cut short at their last read, with the bytes left unread
+    pub truncated_sources: BTreeMap<PathBuf, u64>,
+    /// Lines with invalid UTF-8 in each tasks or hook file at its last read
+    pub lossy_lines: BTreeMap<PathBuf, usize>,
+    /// Phases, agents and statuses the task views are narrowed to; `phases`
+    /// keeps the whole plan
+    pub task_filter: TaskFilter,
+    /// Bumped whenever the phases or task timings change, so views can keep
+    /// what they derive from them until it does
+    pub revision: u64,
+    /// Where the live `now` comes from
+    pub clock: Arc<dyn Clock>,
 }
 
 impl Default for DashboardState {
@@ -97,39 +403,138 @@ impl Default for DashboardState {
             failed_tasks: 0,
             overall_progress: 0.0,
             recent_errors: Vec::new(),
+            retries: RetryTracker::default(),
+            custom_rules: Vec::new(),
+            agent_groups: Vec::new(),
+            session_display: SessionDisplay::default(),
+            custom_statuses: Vec::new(),
+            progress_weights: ProgressWeights::default(),
+            tasks_source: Arc::new(MarkdownSource::default()),
+            parse_warnings: Vec::new(),
+            awaiting_review: HashSet::new(),
+            tool_stats: HashMap::new(),
+            task_spend: HashMap::new(),
+            agent_spend: HashMap::new(),
+            task_rate_limits: HashMap::new(),
+            events: Vec::new(),
+            scrub_at: None,
+            timezone: DisplayZone::default(),
+            run_started: None,
+            task_ids: TaskIds::default(),
+            task_index: HashMap::new(),
+            planned_tasks: HashSet::new(),
+            hide_idle_after: None,
+            event_sources: BTreeMap::new(),
+            event_origins: Vec::new(),
+            hook_read_limit: HooksConfig::default().max_file_bytes(),
+            parse_cache: None,
+            truncated_sources: BTreeMap::new(),
+            lossy_lines: BTreeMap::new(),
+            task_filter: TaskFilter::default(),
+            revision: 0,
+            clock: Arc::new(SystemClock),
         }
     }
 }
 
 impl DashboardState {
     /// Build state from a TASKS.md file path
-    pub fn from_tasks_file(path: &Path) -> Result<Self, String> {
-        let content =
-            std::fs::read_to_string(path).map_err(|e| format!("failed to read tasks: {e}"))?;
-        Self::from_tasks_content(&content)
+    pub fn from_tasks_file(path: &Path) -> Result<Self, ParseError> {
+        let decoded = text::read_lossy(path).map_err(|e| ParseError::Read(e.to_string()))?;
+        let mut state = Self::from_tasks_content(&decoded.text)?;
+        state.record_lossy_lines(path, decoded.lossy_lines);
+        Ok(state)
     }
 
     /// Build state from TASKS.md content string
-    pub fn from_tasks_content(content: &str) -> Result<Self, String> {
-        let phases = tasks_parser::parse_tasks_md(content)?;
-        let mut state = Self::default();
-        state.update_from_phases(phases);
+    pub fn from_tasks_content(content: &str) -> Result<Self, ParseError> {
+        Self::from_tasks_content_with(content, Arc::new(MarkdownSource::default()))
+    }
+
+    /// Build state from tasks file content parsed by `source`
+    pub fn from_tasks_content_with(
+        content: &str,
+        source: Arc<dyn TasksSource>,
+    ) -> Result<Self, ParseError> {
+        let mut state = Self {
+            tasks_source: source,
+            ..Self::default()
+        };
+        state.reload_tasks(content)?;
         Ok(state)
     }
 
+    /// Install the state-level settings of a config: error rules, agent
+    /// groups, statuses, progress weights, timezone, idle-agent hiding, and
+    /// the hook file size limit and parse cache
+    pub fn apply_config(&mut self, config: &Config) {
+        self.custom_rules = config.rules.clone();
+        self.agent_groups = config.agents.groups.clone();
+        self.session_display = config.agents.sessions;
+        self.set_custom_statuses(config.statuses.clone());
+        self.set_progress_weights(config.progress);
+        self.timezone = config.display.timezone;
+        self.hide_idle_after = config
+            .agents
+            .hide_idle_after_mins
+            .map(|m| Duration::minutes(m as i64));
+        self.hook_read_limit = config.hooks.max_file_bytes();
+        self.parse_cache = config
+            .hooks
+            .cache_dir
+            .clone()
+            .filter(|_| config.hooks.cache)
+            .map(ParseCache::new);
+    }
+
+    /// Install the custom status vocabulary and recount progress with it
+    pub fn set_custom_statuses(&mut self, statuses: Vec<StatusDef>) {
+        self.custom_statuses = statuses;
+        let phases = std::mem::take(&mut self.phases);
+        self.update_from_phases(phases);
+    }
+
+    /// Install the progress weighting and recount progress with it
+    pub fn set_progress_weights(&mut self, weights: ProgressWeights) {
+        self.progress_weights = weights;
+        let phases = std::mem::take(&mut self.phases);
+        self.update_from_phases(phases);
+    }
+
+    /// Narrow the task views to the tasks `filter` keeps
+    pub fn set_task_filter(&mut self, filter: TaskFilter) {
+        self.task_filter = filter;
+        self.revision += 1;
+    }
+
+    /// Progress of one phase under the configured statuses and weighting
+    pub fn phase_progress(&self, phase: &ParsedPhase) -> f32 {
+        phase.progress_weighted(&self.custom_statuses, self.progress_weights)
+    }
+
     /// Update task-related fields from parsed phases
     fn update_from_phases(&mut self, phases: Vec<ParsedPhase>) {
+        self.revision += 1;
         let mut total = 0;
         let mut completed = 0;
         let mut failed = 0;
-
-        for phase in &phases {
-            for task in &phase.tasks {
+        let mut weight_total = 0;
+        let mut weight_done = 0;
+
+        self.task_index.clear();
+        for (pi, phase) in phases.iter().enumerate() {
+            for (ti, task) in phase.tasks.iter().enumerate() {
+                let id = self.task_ids.intern(&task.id);
+                // The first of duplicate IDs wins, as in a top-down scan
+                self.task_index.entry(id).or_insert((pi, ti));
                 total += 1;
-                match task.status {
-                    TaskStatus::Completed => completed += 1,
-                    TaskStatus::Failed => failed += 1,
-                    _ => {}
+                let weight = self.progress_weights.weight(task);
+                weight_total += weight;
+                if statuses::is_done(&task.status, &self.custom_statuses) {
+                    completed += 1;
+                    weight_done += weight;
+                } else if task.status == TaskStatus::Failed {
+                    failed += 1;
                 }
             }
         }
@@ -138,21 +543,37 @@ impl DashboardState {
         self.total_tasks = total;
         self.completed_tasks = completed;
         self.failed_tasks = failed;
-        self.overall_progress = if total > 0 {
-            completed as f32 / total as f32
+        self.overall_progress = if weight_total > 0 {
+            weight_done as f32 / weight_total as f32
         } else {
             0.0
         };
     }
 
-    /// Update agent states from hook events
+    /// Update agent states from hook events, applied in the order given
     pub fn update_from_events(&mut self, events: &[HookEvent]) {
-        for event in events {
+        self.revision += 1;
+        let first_index = self.events.len();
+        self.events.extend_from_slice(events);
+        self.event_origins.resize(self.events.len(), None);
+        for (offset, event) in events.iter().enumerate() {
+            // Run markers belong to the run, not to an agent
+            if event.event_type == EventType::RunStart {
+                self.run_started = self.run_started.max(Some(event.timestamp));
+                continue;
+            }
+            let task_id = self.task_ids.intern(&event.task_id);
+            let agent_id = agent_groups::display_id(
+                &self.agent_groups,
+                self.session_display,
+                &event.agent_id,
+                &event.session_id,
+            );
             let agent = self
                 .agents
-                .entry(event.agent_id.clone())
+                .entry(agent_id.clone())
                 .or_insert_with(|| AgentState {
-                    agent_id: event.agent_id.clone(),
+                    agent_id: agent_id.clone(),
                     status: AgentStatus::Idle,
                     current_task: None,
                     current_tool: None,
@@ -163,80 +584,179 @@ impl DashboardState {
                     last_seen: None,
                     tool_counts: HashMap::new(),
                     recent_tools: Vec::new(),
+                    tool_history: Vec::new(),
                     session_id: None,
+                    last_heartbeat: None,
+                    last_output: None,
+                    members: BTreeMap::new(),
+                    rate_limits: RateLimits::default(),
                 });
 
+            // Heartbeats only prove liveness; they are not activity
+            if event.event_type == EventType::Heartbeat {
+                agent.last_heartbeat = agent.last_heartbeat.max(Some(event.timestamp));
+                continue;
+            }
+
+            if let Some(usage) = &event.usage {
+                self.task_spend
+                    .entry(task_id.clone())
+                    .or_default()
+                    .add(usage);
+                self.agent_spend
+                    .entry(agent_id.clone())
+                    .or_default()
+                    .add(usage);
+            }
+
             agent.event_count += 1;
             agent.last_seen = Some(event.timestamp);
             if agent.first_seen.is_none() {
                 agent.first_seen = Some(event.timestamp);
             }
             agent.session_id = Some(event.session_id.clone());
+            let key = (event.session_id.clone(), event.agent_id.clone());
+            let mut member = agent
+                .members
+                .remove(&key)
+                .unwrap_or_else(|| AgentMember::new(&event.agent_id, &event.session_id));
 
             match event.event_type {
                 EventType::AgentStart => {
-                    agent.status = AgentStatus::Running;
-                    agent.current_task = Some(event.task_id.clone());
+                    member.status = AgentStatus::Running;
+                    member.current_task = Some(task_id.clone());
                     agent.task_history.push(TaskHistoryEntry {
-                        task_id: event.task_id.clone(),
+                        task_id: task_id.clone(),
                         started_at: event.timestamp,
                         completed_at: None,
                     });
                     // Persist task → agent mapping
-                    self.task_agents
-                        .insert(event.task_id.clone(), event.agent_id.clone());
-                    let timing = self.task_times.entry(event.task_id.clone()).or_default();
+                    self.task_agents.insert(task_id.clone(), agent_id.clone());
+                    let timing = self.task_times.entry(task_id).or_default();
                     if timing.started_at.is_none() {
                         timing.started_at = Some(event.timestamp);
                     }
                 }
                 EventType::AgentEnd => {
-                    agent.status = AgentStatus::Idle;
-                    if let Some(ref task_id) = agent.current_task {
+                    member.status = AgentStatus::Idle;
+                    if let Some(task_id) = member.current_task.take() {
                         let timing = self.task_times.entry(task_id.clone()).or_default();
                         timing.completed_at = Some(event.timestamp);
-                    }
-                    // Set completed_at on the last task history entry
-                    if let Some(last) = agent.task_history.last_mut() {
-                        if last.completed_at.is_none() {
-                            last.completed_at = Some(event.timestamp);
+                        // Close the member's entry, not another session's
+                        if let Some(entry) = agent
+                            .task_history
+                            .iter_mut()
+                            .rev()
+                            .find(|h| h.task_id == task_id && h.completed_at.is_none())
+                        {
+                            entry.completed_at = Some(event.timestamp);
                         }
                     }
-                    agent.current_task = None;
-                    agent.current_tool = None;
+                    member.current_tool = None;
+                    member.open_calls.clear();
                 }
                 EventType::ToolStart => {
-                    agent.status = AgentStatus::Running;
-                    agent.current_tool = event.tool_name.clone();
+                    member.status = AgentStatus::Running;
+                    member.current_tool = event.tool_name.clone();
                     if let Some(ref name) = event.tool_name {
+                        member.open_calls.push((name.clone(), event.timestamp));
                         *agent.tool_counts.entry(name.clone()).or_insert(0) += 1;
                         agent.recent_tools.push(name.clone());
                         if agent.recent_tools.len() > MAX_RECENT_TOOLS {
                             agent.recent_tools.remove(0);
                         }
+                        agent.tool_history.push(ToolCall {
+                            tool: name.clone(),
+                            started_at: event.timestamp,
+                            ended_at: None,
+                        });
+                        if agent.tool_history.len() > MAX_TOOL_HISTORY {
+                            agent.tool_history.remove(0);
+                        }
                     }
                 }
                 EventType::ToolEnd => {
-                    agent.current_tool = None;
+                    // Close the member's latest open call of this tool (or of
+                    // any tool when the end event carries no name)
+                    let open = member
+                        .open_calls
+                        .iter()
+                        .rposition(|(tool, _)| event.tool_name.as_ref().map_or(true, |n| n == tool))
+                        .map(|i| member.open_calls.remove(i));
+                    if let Some(call) = open.and_then(|(tool, started_at)| {
+                        agent.tool_history.iter_mut().rev().find(|c| {
+                            c.ended_at.is_none() && c.tool == tool && c.started_at == started_at
+                        })
+                    }) {
+                        call.ended_at = Some(event.timestamp);
+                        self.tool_stats
+                            .entry(call.tool.clone())
+                            .or_default()
+                            .record(event.timestamp - call.started_at);
+                    }
+                    if let Some(ref output) = event.tool_output {
+                        agent.last_output =
+                            Some(ToolOutput::from_event(event.tool_name.clone(), output));
+                    }
+                    member.current_tool = None;
                     // Return to Idle only if no active task (subagent)
-                    if agent.current_task.is_none() {
-                        agent.status = AgentStatus::Idle;
+                    if member.current_task.is_none() {
+                        member.status = AgentStatus::Idle;
                     }
                 }
-                EventType::Error => {
-                    agent.status = AgentStatus::Error;
-                    agent.error_count += 1;
+                EventType::Heartbeat | EventType::RunStart => {}
+                EventType::Error | EventType::RateLimit => {
+                    let rate_limited = event.event_type == EventType::RateLimit;
+                    // Waiting out a rate limit is not failing
+                    if !rate_limited {
+                        member.status = AgentStatus::Error;
+                        agent.error_count += 1;
+                    }
 
-                    if let Some(ref msg) = event.error_message {
-                        let analysis = analyze_error(msg);
+                    let message = match (&event.error_message, rate_limited) {
+                        (Some(msg), _) => Some(msg.clone()),
+                        (None, true) => Some("Rate limited".to_string()),
+                        (None, false) => None,
+                    };
+                    if let Some(msg) = message {
+                        let analysis = if rate_limited {
+                            analyze_rate_limit(&msg)
+                        } else {
+                            analyze_error_with(&msg, &self.custom_rules)
+                        };
+                        let cooldown_until =
+                            (analysis.category == ErrorCategory::RateLimit).then(|| {
+                                let secs = retry_after(&msg)
+                                    .unwrap_or(DEFAULT_COOLDOWN_SECS)
+                                    .min(MAX_COOLDOWN_SECS);
+                                let after = |secs: u64| {
+                                    event
+                                        .timestamp
+                                        .checked_add_signed(Duration::seconds(secs as i64))
+                                };
+                                after(secs)
+                                    .or_else(|| after(DEFAULT_COOLDOWN_SECS))
+                                    .unwrap_or(event.timestamp)
+                            });
+                        if let Some(until) = cooldown_until {
+                            agent.rate_limits.record(event.timestamp, until);
+                            self.task_rate_limits
+                                .entry(task_id.clone())
+                                .or_default()
+                                .record(event.timestamp, until);
+                        }
                         self.recent_errors.push(ErrorRecord {
-                            agent_id: event.agent_id.clone(),
-                            task_id: event.task_id.clone(),
+                            agent_id: agent_id.clone(),
+                            task_id,
                             message: msg.clone(),
                             category: analysis.category,
+                            severity: analysis.severity,
                             retryable: analysis.retryable,
                             suggestion: analysis.suggestion,
+                            hint: analysis.hint,
                             timestamp: event.timestamp,
+                            cooldown_until,
+                            event_index: first_index + offset,
                         });
                         if self.recent_errors.len() > MAX_RECENT_ERRORS {
                             self.recent_errors.remove(0);
@@ -244,6 +764,24 @@ impl DashboardState {
                     }
                 }
             }
+
+            // A group works while any member does; it shows the task and
+            // tool of the member that just acted, or else of another one
+            agent.current_task = member
+                .current_task
+                .clone()
+                .or_else(|| agent.members.values().find_map(|m| m.current_task.clone()));
+            agent.current_tool = member
+                .current_tool
+                .clone()
+                .or_else(|| agent.members.values().find_map(|m| m.current_tool.clone()));
+            agent.members.insert(key, member);
+            agent.status = agent
+                .members
+                .values()
+                .map(|m| m.status.clone())
+                .min_by_key(AgentStatus::rank)
+                .unwrap_or(AgentStatus::Idle);
         }
     }
 
@@ -254,36 +792,527 @@ impl DashboardState {
         self.task_times.clear();
         self.task_agents.clear();
         self.recent_errors.clear();
+        self.tool_stats.clear();
+        self.task_spend.clear();
+        self.agent_spend.clear();
+        self.task_rate_limits.clear();
+        self.events.clear();
+        self.event_origins.clear();
+        self.run_started = None;
         self.update_from_events(events);
     }
 
-    /// Load hook events from a directory and update agent states
+    /// Usage of the whole run
+    pub fn total_spend(&self) -> Spend {
+        let mut total = Spend::default();
+        for spend in self.agent_spend.values() {
+            total.merge(spend);
+        }
+        total
+    }
+
+    /// Rate limits of the whole run and how many agents hit one
+    pub fn rate_limit_totals(&self) -> (RateLimits, usize) {
+        let mut total = RateLimits::default();
+        let mut agents = 0;
+        for limits in self.agents.values().map(|a| &a.rate_limits) {
+            if limits.hits == 0 {
+                continue;
+            }
+            agents += 1;
+            total.hits += limits.hits;
+            total.last_hit = total.last_hit.max(limits.last_hit);
+            total.until = total.until.max(limits.until);
+            total.waited += limits.waited;
+        }
+        (total, agents)
+    }
+
+    /// When the orchestration run began: the latest `run_start` event, or
+    /// else the earliest hook event
+    pub fn run_start(&self) -> Option<DateTime<Utc>> {
+        self.run_started
+            .or_else(|| self.event_span().map(|(first, _)| first))
+    }
+
+    /// The clock's time, or the scrub point of a rebuilt past state
+    pub fn now(&self) -> DateTime<Utc> {
+        self.scrub_at.unwrap_or_else(|| self.clock.now())
+    }
+
+    /// Timestamps of the earliest and latest hook events
+    pub fn event_span(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let first = self.events.iter().map(|e| e.timestamp).min()?;
+        let last = self.events.iter().map(|e| e.timestamp).max()?;
+        Some((first, last))
+    }
+
+    /// The board as it was at `instant`: agents, timings and errors replayed
+    /// from the events up to it. Tasks that hook events had not started yet
+    /// show as Pending and unfinished ones as InProgress (or Failed after an
+    /// error); tasks without hook timing keep their current status. Only
+    /// the latest [`MAX_SCRUB_EVENTS`] events are replayed.
+    pub fn at(&self, instant: DateTime<Utc>) -> DashboardState {
+        let mut timeline = Timeline::new(self);
+        timeline.seek(instant);
+        timeline.into_state()
+    }
+
+    /// Load hook events from a directory and update agent states, reusing
+    /// cached parses of unchanged files
     pub fn load_hook_events(&mut self, hooks_dir: &Path) -> Result<(), String> {
         let entries =
             std::fs::read_dir(hooks_dir).map_err(|e| format!("failed to read hooks dir: {e}"))?;
 
+        let mut merged = Vec::new();
         for entry in entries {
             let entry = entry.map_err(|e| format!("failed to read entry: {e}"))?;
             let path = entry.path();
             if path.extension().and_then(|e| e.to_str()) == Some("jsonl") {
-                let result = hook_parser::parse_hook_file(&path)
-                    .map_err(|e| format!("failed to parse {}: {e}", path.display()))?;
-                self.update_from_events(&result.events);
+                let result = match &self.parse_cache {
+                    Some(cache) => cache.read(&path, self.hook_read_limit),
+                    None => self.read_hook_file(&path),
+                }
+                .map_err(|e| format!("failed to parse {}: {e}", path.display()))?;
+                self.record_truncation(&path, result.skipped_bytes);
+                self.record_lossy_lines(&path, result.lossy_lines);
+                self.record_event_source(&path, result.events.len());
+                let origin: Arc<Path> = Arc::from(path);
+                merged.extend(result.events.into_iter().map(|e| (Some(origin.clone()), e)));
             }
         }
+        self.replay_merged(merged);
         Ok(())
     }
 
+    /// Replace the events of hook file `file`. Events appended since its last
+    /// read that are no older than anything applied so far are applied on
+    /// top of the current state; any other change re-processes the events
+    /// of every hook file in time order.
+    pub fn reload_hook_file(&mut self, file: &Path, events: Vec<HookEvent>) {
+        let read_before = self.event_sources.get(file).copied().unwrap_or(0);
+        self.record_event_source(file, events.len());
+        let origin: Arc<Path> = Arc::from(file);
+        if let Some(appended) = self.appended_events(&origin, read_before, &events) {
+            if !appended.is_empty() {
+                self.update_from_events(appended);
+                let start = self.events.len() - appended.len();
+                self.event_origins[start..].fill(Some(origin));
+            }
+            return;
+        }
+
+        let mut merged: Vec<_> = std::mem::take(&mut self.event_origins)
+            .into_iter()
+            .zip(std::mem::take(&mut self.events))
+            .filter(|(o, _)| o.as_deref() != Some(file))
+            .collect();
+        merged.extend(events.into_iter().map(|e| (Some(origin.clone()), e)));
+        self.replay_merged(merged);
+    }
+
+    /// The tail of `events` that `file` gained since it held `read_before`
+    /// events, if applying only that tail gives the same state as a full
+    /// replay: the file was appended to, not rewritten, and the tail sorts
+    /// after every event applied so far
+    fn appended_events<'e>(
+        &self,
+        file: &Arc<Path>,
+        read_before: usize,
+        events: &'e [HookEvent],
+    ) -> Option<&'e [HookEvent]> {
+        if events.len() < read_before {
+            return None;
+        }
+        if read_before > 0 {
+            let last_read = self
+                .events
+                .iter()
+                .zip(&self.event_origins)
+                .rev()
+                .find(|(_, o)| o.as_ref() == Some(file))
+                .map(|(e, _)| e);
+            if last_read != Some(&events[read_before - 1]) {
+                return None;
+            }
+        }
+        let appended = &events[read_before..];
+        let in_order = appended
+            .windows(2)
+            .all(|w| w[0].timestamp <= w[1].timestamp);
+        let after_applied = match (self.events.last(), appended.first()) {
+            (Some(last), Some(first)) => {
+                let last_origin = self.event_origins.last().cloned().flatten();
+                (last.timestamp, last_origin) <= (first.timestamp, Some(file.clone()))
+            }
+            _ => true,
+        };
+        (in_order && after_applied).then_some(appended)
+    }
+
+    /// Re-process events tagged with their hook file, sorted by timestamp.
+    /// The sort is stable, so events at the same instant keep their order
+    /// within a file, and files are taken in path order.
+    fn replay_merged(&mut self, mut merged: Vec<(Option<Arc<Path>>, HookEvent)>) {
+        merged.sort_by(|(a_origin, a), (b_origin, b)| {
+            (a.timestamp, a_origin).cmp(&(b.timestamp, b_origin))
+        });
+        let (origins, events): (Vec<_>, Vec<_>) = merged.into_iter().unzip();
+        self.reload_from_events(&events);
+        self.event_origins = origins;
+    }
+
+    /// Remember how many events `file` held when it was last read
+    pub fn record_event_source(&mut self, file: &Path, events: usize) {
+        tracing::debug!("{events} events from {}", file.display());
+        self.event_sources.insert(file.to_path_buf(), events);
+    }
+
+    /// Read a tasks file, replacing invalid UTF-8
+    pub fn read_tasks_file(&mut self, path: &Path) -> std::io::Result<String> {
+        let decoded = text::read_lossy(path)?;
+        self.record_lossy_lines(path, decoded.lossy_lines);
+        Ok(decoded.text)
+    }
+
+    /// Remember how many lines of `file` held invalid UTF-8 at its last read
+    pub fn record_lossy_lines(&mut self, file: &Path, lines: usize) {
+        if lines == 0 {
+            self.lossy_lines.remove(file);
+        } else {
+            tracing::debug!("{lines} lines of {} were not valid UTF-8", file.display());
+            self.lossy_lines.insert(file.to_path_buf(), lines);
+        }
+    }
+
+    /// Parse a hook file within `hook_read_limit`
+    pub fn read_hook_file(&self, path: &Path) -> std::io::Result<hook_parser::ParseResult> {
+        hook_parser::parse_hook_file_limited(path, self.hook_read_limit)
+    }
+
+    /// Remember whether `file` was cut short at its last read. Returns true
+    /// when it was not cut before, so the warning is shown once per file
+    /// rather than on every change.
+    pub fn record_truncation(&mut self, file: &Path, skipped_bytes: u64) -> bool {
+        if skipped_bytes == 0 {
+            self.truncated_sources.remove(file);
+            return false;
+        }
+        let first = self
+            .truncated_sources
+            .insert(file.to_path_buf(), skipped_bytes)
+            .is_none();
+        if first {
+            tracing::warn!(
+                "{} is over the {} byte read limit; skipped its first {skipped_bytes} bytes",
+                file.display(),
+                self.hook_read_limit
+            );
+        }
+        first
+    }
+
+    /// Event counts summed per source directory
+    pub fn events_by_dir(&self) -> BTreeMap<&Path, usize> {
+        let mut dirs = BTreeMap::new();
+        for (file, &count) in &self.event_sources {
+            *dirs.entry(file.parent().unwrap_or(file)).or_insert(0) += count;
+        }
+        dirs
+    }
+
+    /// Whether a Completed task is still waiting for human approval
+    pub fn is_awaiting_review(&self, task_id: &str) -> bool {
+        self.awaiting_review.contains(task_id)
+    }
+
+    /// Whether the milestones after phase `phase_index` are reached:
+    /// every task in that phase and all earlier ones is done
+    pub fn milestone_reached(&self, phase_index: usize) -> bool {
+        self.phases.iter().take(phase_index + 1).all(|p| {
+            p.tasks
+                .iter()
+                .all(|t| statuses::is_done(&t.status, &self.custom_statuses))
+        })
+    }
+
+    /// Agents in panel order: running, then failing, then idle, each by most
+    /// recent activity. Idle agents past `hide_idle_after` are left out and
+    /// counted in the second value.
+    pub fn panel_agents(&self) -> (Vec<&AgentState>, usize) {
+        let now = self.now();
+        let (mut shown, hidden): (Vec<&AgentState>, Vec<&AgentState>) =
+            self.agents.values().partition(|a| {
+                let stale = self
+                    .hide_idle_after
+                    .is_some_and(|after| a.last_seen.map_or(true, |seen| now - seen > after));
+                a.status != AgentStatus::Idle || !stale
+            });
+        shown.sort_by(|a, b| {
+            a.status
+                .rank()
+                .cmp(&b.status.rank())
+                .then(b.last_seen.cmp(&a.last_seen))
+                .then(a.agent_id.cmp(&b.agent_id))
+        });
+        (shown, hidden.len())
+    }
+
+    /// Most recent Fatal error, if any
+    pub fn latest_fatal(&self) -> Option<&ErrorRecord> {
+        self.recent_errors
+            .iter()
+            .rfind(|e| e.severity == Severity::Fatal)
+    }
+
+    /// Whether a tool call, finished or running, is abnormally slow for its tool
+    pub fn is_slow_call(&self, call: &ToolCall) -> bool {
+        self.tool_stats
+            .get(&call.tool)
+            .is_some_and(|s| s.is_slow(call.duration(self.now())))
+    }
+
+    /// Phase and task index of a task in `phases`
+    pub fn task_position(&self, task_id: &str) -> Option<(usize, usize)> {
+        self.task_index.get(task_id).copied()
+    }
+
+    /// Look up a task by ID
+    pub fn task(&self, task_id: &str) -> Option<&ParsedTask> {
+        let (pi, ti) = self.task_position(task_id)?;
+        self.phases.get(pi)?.tasks.get(ti)
+    }
+
     /// Find the agent assigned to a task (from hook event history)
     pub fn agent_for_task(&self, task_id: &str) -> Option<&str> {
         self.task_agents.get(task_id).map(|s| s.as_str())
     }
 
-    /// Reload tasks from content (used when file watcher detects changes)
-    pub fn reload_tasks(&mut self, content: &str) -> Result<(), String> {
-        let phases = tasks_parser::parse_tasks_md(content)?;
-        self.update_from_phases(phases);
-        Ok(())
+    /// Hook activity on task IDs outside the plan, earliest first. Tasks the
+    /// task filter hides are still part of the plan, and events the hook
+    /// logged without a task are not work on one.
+    pub fn untracked_work(&self) -> Vec<UntrackedWork> {
+        let planned: HashSet<&str> = self
+            .phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .map(|t| t.id.as_str())
+            .chain(self.planned_tasks.iter().map(|id| id.as_ref()))
+            .collect();
+        let mut work: Vec<UntrackedWork> = Vec::new();
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for event in &self.events {
+            if matches!(event.event_type, EventType::RunStart | EventType::Heartbeat)
+                || event.task_id.is_empty()
+                || event.task_id == hook_parser::NO_TASK
+                || planned.contains(event.task_id.as_str())
+            {
+                continue;
+            }
+            let agent = agent_groups::display_id(
+                &self.agent_groups,
+                self.session_display,
+                &event.agent_id,
+                &event.session_id,
+            );
+            let i = *index.entry(&event.task_id).or_insert_with(|| {
+                work.push(UntrackedWork {
+                    task_id: event.task_id.clone(),
+                    agents: Vec::new(),
+                    events: 0,
+                    first_seen: event.timestamp,
+                    last_seen: event.timestamp,
+                    running: false,
+                });
+                work.len() - 1
+            });
+            let entry = &mut work[i];
+            entry.events += 1;
+            entry.first_seen = entry.first_seen.min(event.timestamp);
+            entry.last_seen = entry.last_seen.max(event.timestamp);
+            if !entry.agents.contains(&agent) {
+                entry.agents.push(agent);
+            }
+        }
+        for entry in &mut work {
+            entry.running = self
+                .task_times
+                .get(entry.task_id.as_str())
+                .is_some_and(|t| t.started_at.is_some() && t.completed_at.is_none());
+        }
+        work.sort_by_key(|w| w.first_seen);
+        work
+    }
+
+    /// Who works on a task: the agent hook events ran it under, or else its
+    /// TASKS.md `@agent`
+    pub fn worker_for<'s>(&'s self, task: &'s ParsedTask) -> Option<&'s str> {
+        self.agent_for_task(&task.id).or(task.agent.as_deref())
+    }
+
+    /// Whether the task filter shows `task`
+    pub fn shows_task(&self, task: &ParsedTask) -> bool {
+        self.task_filter.keeps(task, self.worker_for(task))
+    }
+
+    /// Whether the task filter shows `phase`: it is named by the phase filter
+    /// and, under an agent or status filter, has a task that is shown
+    pub fn shows_phase(&self, phase: &ParsedPhase) -> bool {
+        self.task_filter.keeps_phase(phase)
+            && (!self.task_filter.narrows_tasks() || phase.tasks.iter().any(|t| self.shows_task(t)))
+    }
+
+    /// Phases the task filter shows, with their index in `phases`
+    pub fn shown_phases(&self) -> impl Iterator<Item = (usize, &ParsedPhase)> {
+        self.phases
+            .iter()
+            .enumerate()
+            .filter(|(_, phase)| self.shows_phase(phase))
+    }
+
+    /// Tasks of `phase` the task filter shows, with their index in it
+    pub fn shown_tasks<'s>(
+        &'s self,
+        phase: &'s ParsedPhase,
+    ) -> impl Iterator<Item = (usize, &'s ParsedTask)> + 's {
+        phase
+            .tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| self.shows_task(task))
+    }
+
+    /// Whether the task views list the task with this ID
+    pub fn is_shown(&self, task_id: &str) -> bool {
+        self.task_position(task_id).is_some_and(|(pi, ti)| {
+            let phase = &self.phases[pi];
+            self.shows_phase(phase) && self.shows_task(&phase.tasks[ti])
+        })
+    }
+
+    /// Reload tasks from content (used when file watcher detects changes).
+    /// On error the previous phases are kept and the error becomes the only
+    /// parse warning.
+    pub fn reload_tasks(&mut self, content: &str) -> Result<(), ParseError> {
+        let parsed = self.tasks_source.parse(content);
+        self.apply_tasks(parsed)
+    }
+
+    /// Take the phases of an already parsed tasks file, like
+    /// [`reload_tasks`](Self::reload_tasks)
+    pub fn apply_tasks(
+        &mut self,
+        parsed: Result<ParseOutcome, ParseError>,
+    ) -> Result<(), ParseError> {
+        match parsed {
+            Ok(outcome) => {
+                self.parse_warnings = outcome.warnings;
+                self.planned_tasks = outcome
+                    .phases
+                    .iter()
+                    .flat_map(|p| &p.tasks)
+                    .map(|t| self.task_ids.intern(&t.id))
+                    .collect();
+                self.update_from_phases(outcome.phases);
+                Ok(())
+            }
+            Err(e) => {
+                self.parse_warnings = vec![e.clone()];
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Hook events the time-travel scrubber replays, the latest ones kept
+pub const MAX_SCRUB_EVENTS: usize = 50_000;
+
+/// Past states of a board for the time-travel scrubber. The live state is
+/// copied once; a step forward replays only the events since the previous
+/// step into the same state, and a step back replays from the start.
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    /// The live board with everything derived from events cleared
+    base: DashboardState,
+    /// Live events by time, at most [`MAX_SCRUB_EVENTS`] of the latest
+    events: Vec<HookEvent>,
+    /// Tasks the live events timed; only their status is rewound
+    timed: HashSet<TaskId>,
+    /// `base` with the first `applied` events replayed
+    past: DashboardState,
+    applied: usize,
+}
+
+impl Timeline {
+    pub fn new(live: &DashboardState) -> Self {
+        let mut sorted: Vec<&HookEvent> = live.events.iter().collect();
+        sorted.sort_by_key(|e| e.timestamp);
+        let skip = sorted.len().saturating_sub(MAX_SCRUB_EVENTS);
+        let events = sorted[skip..].iter().map(|&e| e.clone()).collect();
+
+        let mut base = live.clone();
+        base.reload_from_events(&[]);
+        Self {
+            past: base.clone(),
+            base,
+            events,
+            timed: live.task_times.keys().cloned().collect(),
+            applied: 0,
+        }
+    }
+
+    /// Earliest and latest replayable events
+    pub fn span(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        Some((
+            self.events.first()?.timestamp,
+            self.events.last()?.timestamp,
+        ))
+    }
+
+    /// The instant last sought
+    pub fn scrub_at(&self) -> Option<DateTime<Utc>> {
+        self.past.scrub_at
+    }
+
+    /// Rebuild the board as it was at `instant`
+    pub fn seek(&mut self, instant: DateTime<Utc>) {
+        let upto = self.events.partition_point(|e| e.timestamp <= instant);
+        if upto < self.applied {
+            self.past = self.base.clone();
+            self.applied = 0;
+        }
+        self.past
+            .update_from_events(&self.events[self.applied..upto]);
+        self.applied = upto;
+        self.past.scrub_at = Some(instant);
+
+        let mut phases = self.base.phases.clone();
+        for task in phases.iter_mut().flat_map(|p| p.tasks.iter_mut()) {
+            if !self.timed.contains(task.id.as_str()) {
+                continue;
+            }
+            let timing = self.past.task_times.get(task.id.as_str());
+            let failed = self.past.recent_errors.iter().any(|e| e.task_id == task.id);
+            task.status = match (
+                timing.and_then(|t| t.started_at),
+                timing.and_then(|t| t.completed_at),
+            ) {
+                (None, _) => TaskStatus::Pending,
+                (Some(_), None) if failed => TaskStatus::Failed,
+                (Some(_), None) => TaskStatus::InProgress,
+                (Some(_), Some(_)) => continue,
+            };
+        }
+        self.past.update_from_phases(phases);
+    }
+
+    /// The board at the instant last sought
+    pub fn state(&self) -> &DashboardState {
+        &self.past
+    }
+
+    pub fn into_state(self) -> DashboardState {
+        self.past
     }
 }
 
@@ -383,6 +1412,8 @@ mod tests {
                 session_id: "sess-cap".to_string(),
                 tool_name: None,
                 error_message: Some(format!("error {i}")),
+                tool_output: None,
+                usage: None,
             })
             .collect();
         state.update_from_events(&events);
@@ -391,6 +1422,26 @@ mod tests {
         assert_eq!(state.recent_errors[0].task_id, "T-5");
     }
 
+    #[test]
+    fn errors_point_at_their_event() {
+        let mut state = DashboardState::default();
+        let batch = |lines: &str| hook_parser::parse_hook_events(lines).events;
+        state.update_from_events(&batch(concat!(
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Bash"}"#,
+            "\n",
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:00:01Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"exit 1"}"#,
+        )));
+        state.update_from_events(&batch(
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:00:02Z","agent_id":"a","task_id":"T2","session_id":"s","error_message":"exit 2"}"#,
+        ));
+        let indexes: Vec<usize> = state.recent_errors.iter().map(|e| e.event_index).collect();
+        assert_eq!(indexes, [1, 2]);
+        assert_eq!(
+            state.events[2].error_message.as_deref(),
+            Some(state.recent_errors[1].message.as_str())
+        );
+    }
+
     #[test]
     fn agent_running_state() {
         let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
@@ -405,6 +1456,184 @@ mod tests {
         assert_eq!(agent.current_task.as_deref(), Some("P1-R1-T1"));
     }
 
+    #[test]
+    fn task_ids_are_interned_once() {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [ ] P1-R1-T1: A\n").unwrap();
+        assert_eq!(state.task_ids.len(), 1);
+        let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+
+        // Replaying the same events adds no new IDs
+        let mut ids: HashSet<&str> = state.events.iter().map(|e| e.task_id.as_str()).collect();
+        ids.insert("P1-R1-T1");
+        assert_eq!(state.task_ids.len(), ids.len());
+        assert!(state.task_times.contains_key("P1-R1-T1"));
+    }
+
+    #[test]
+    fn panel_agents_order_by_status_then_recency() {
+        let event = |agent: &str, event_type, at: &str| HookEvent {
+            event_type,
+            timestamp: at.parse().unwrap(),
+            agent_id: agent.to_string(),
+            task_id: "T-1".to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: None,
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        };
+        let mut state = DashboardState::default();
+        state.update_from_events(&[
+            event("old-idle", EventType::AgentEnd, "2026-02-08T09:00:00Z"),
+            event("new-idle", EventType::AgentEnd, "2026-02-08T10:50:00Z"),
+            event("busy", EventType::AgentStart, "2026-02-08T10:00:00Z"),
+        ]);
+        state.scrub_at = Some("2026-02-08T11:00:00Z".parse().unwrap());
+
+        let ids = |state: &DashboardState| {
+            let (agents, hidden) = state.panel_agents();
+            let ids: Vec<String> = agents.iter().map(|a| a.agent_id.clone()).collect();
+            (ids, hidden)
+        };
+        assert_eq!(
+            ids(&state),
+            (vec!["busy".into(), "new-idle".into(), "old-idle".into()], 0)
+        );
+
+        state.hide_idle_after = Some(Duration::minutes(30));
+        assert_eq!(ids(&state), (vec!["busy".into(), "new-idle".into()], 1));
+    }
+
+    #[test]
+    fn grouped_agents_aggregate_until_the_last_member_stops() {
+        use crate::data::agent_groups::Pattern;
+
+        let event = |agent: &str, event_type| HookEvent {
+            event_type,
+            timestamp: "2026-02-08T10:00:00Z".parse().unwrap(),
+            agent_id: agent.to_string(),
+            task_id: "T-1".to_string(),
+            session_id: format!("sess-{agent}"),
+            tool_name: None,
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        };
+        let mut state = DashboardState {
+            agent_groups: vec![AgentGroup {
+                pattern: Pattern::new(r"^backend-specialist-\d+$").unwrap(),
+                group: "backend".to_string(),
+            }],
+            ..Default::default()
+        };
+        state.update_from_events(&[
+            event("backend-specialist-1", EventType::AgentStart),
+            event("backend-specialist-2", EventType::AgentStart),
+            event("backend-specialist-1", EventType::AgentEnd),
+            event("frontend-1", EventType::AgentStart),
+        ]);
+
+        assert_eq!(state.agents.len(), 2);
+        let backend = &state.agents["backend"];
+        assert_eq!(backend.event_count, 3);
+        assert_eq!(backend.members.len(), 2);
+        assert_eq!(backend.status, AgentStatus::Running);
+        assert_eq!(state.task_agents["T-1"], "frontend-1");
+
+        state.update_from_events(&[event("backend-specialist-2", EventType::AgentEnd)]);
+        assert_eq!(state.agents["backend"].status, AgentStatus::Idle);
+    }
+
+    #[test]
+    fn sessions_sharing_an_agent_id_keep_their_own_work() {
+        let event = |session: &str, secs: i64, event_type, task: &str| HookEvent {
+            event_type,
+            timestamp: "2026-02-08T10:00:00Z".parse::<DateTime<Utc>>().unwrap()
+                + Duration::seconds(secs),
+            agent_id: "main".to_string(),
+            task_id: task.to_string(),
+            session_id: session.to_string(),
+            tool_name: Some("Bash".to_string()),
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        };
+        let events = [
+            event("aaaa1111-s", 0, EventType::AgentStart, "T1"),
+            event("bbbb2222-s", 1, EventType::AgentStart, "T2"),
+            event("aaaa1111-s", 2, EventType::ToolStart, "T1"),
+            event("bbbb2222-s", 3, EventType::ToolStart, "T2"),
+            event("aaaa1111-s", 5, EventType::ToolEnd, "T1"),
+            event("aaaa1111-s", 6, EventType::AgentEnd, "T1"),
+        ];
+
+        let mut state = DashboardState::default();
+        state.update_from_events(&events);
+        assert_eq!(state.agents.len(), 1);
+        let main = &state.agents["main"];
+        assert_eq!(main.members.len(), 2);
+        // The first session finished T1 and its own Bash call only
+        assert!(state.task_times["T1"].completed_at.is_some());
+        assert!(state.task_times["T2"].completed_at.is_none());
+        assert_eq!(main.status, AgentStatus::Running);
+        assert_eq!(main.current_task.as_deref(), Some("T2"));
+        assert_eq!(main.current_tool.as_deref(), Some("Bash"));
+        let open: Vec<i64> = main
+            .tool_history
+            .iter()
+            .filter(|c| c.ended_at.is_none())
+            .map(|c| c.started_at.timestamp() % 60)
+            .collect();
+        assert_eq!(open, vec![3]);
+        assert_eq!(state.tool_stats["Bash"].max, Duration::seconds(3));
+
+        let mut split = DashboardState {
+            session_display: SessionDisplay::Split,
+            ..Default::default()
+        };
+        split.update_from_events(&events);
+        let mut ids: Vec<&str> = split.agents.keys().map(String::as_str).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, ["main@aaaa1111", "main@bbbb2222"]);
+        assert_eq!(split.agents["main@aaaa1111"].status, AgentStatus::Idle);
+        assert_eq!(split.task_agents["T2"], "main@bbbb2222");
+    }
+
+    #[test]
+    fn worker_prefers_hook_agent_over_tasks_md() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [ ] T1: A\n- **담당**: @planner\n",
+        )
+        .unwrap();
+        let task = state.phases[0].tasks[0].clone();
+        assert_eq!(task.agent.as_deref(), Some("planner"));
+        assert_eq!(state.worker_for(&task), Some("planner"));
+
+        state
+            .task_agents
+            .insert("T1".into(), "planner-2".to_string());
+        assert_eq!(state.worker_for(&task), Some("planner-2"));
+    }
+
+    #[test]
+    fn task_index_finds_tasks_after_reload() {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [ ] T1: A\n### [ ] T2: B\n")
+                .unwrap();
+        assert_eq!(state.task_position("T2"), Some((0, 1)));
+        assert_eq!(state.task("T1").unwrap().name, "A");
+
+        state
+            .reload_tasks("# Phase 1: A\n### [ ] T2: B\n\n# Phase 2: C\n### [ ] T3: C\n")
+            .unwrap();
+        assert_eq!(state.task_position("T2"), Some((0, 0)));
+        assert_eq!(state.task_position("T3"), Some((1, 0)));
+        assert!(state.task("T1").is_none());
+    }
+
     #[test]
     fn agent_tool_tracking() {
         let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
@@ -427,6 +1656,202 @@ mod tests {
 
         // Should have agents from both agent_events.jsonl and error_events.jsonl
         assert!(state.agents.len() >= 2);
+        let by_dir = state.events_by_dir();
+        assert_eq!(by_dir.len(), 1);
+        assert_eq!(by_dir[hooks_dir.as_path()], state.events.len());
+    }
+
+    #[test]
+    fn hook_files_are_merged_in_time_order() {
+        let line = |secs: u32, event_type: &str, task: &str, session: &str| {
+            format!(
+                r#"{{"event_type":"{event_type}","timestamp":"2026-02-08T10:00:{secs:02}Z","agent_id":"main","task_id":"{task}","session_id":"{session}"}}"#
+            )
+        };
+        // Each file holds the other file's session's start and its own end,
+        // so reading the files one after another ends sessions before they start
+        let tmp = tempfile::TempDir::new().unwrap();
+        let a = tmp.path().join("a.jsonl");
+        let b = tmp.path().join("b.jsonl");
+        std::fs::write(
+            &a,
+            [
+                line(1, "agent_start", "T2", "sess-2"),
+                line(6, "agent_end", "T1", "sess-1"),
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        let b_lines = [
+            line(0, "agent_start", "T1", "sess-1"),
+            line(5, "agent_end", "T2", "sess-2"),
+        ];
+        std::fs::write(&b, b_lines.join("\n")).unwrap();
+
+        let mut state = DashboardState::default();
+        state.load_hook_events(tmp.path()).unwrap();
+        let main = &state.agents["main"];
+        assert_eq!(main.status, AgentStatus::Idle);
+        assert_eq!(main.current_task, None);
+        let at = |secs| {
+            Some("2026-02-08T10:00:00Z".parse::<DateTime<Utc>>().unwrap() + Duration::seconds(secs))
+        };
+        assert_eq!(state.task_times["T1"].started_at, at(0));
+        assert_eq!(state.task_times["T1"].completed_at, at(6));
+        assert_eq!(state.task_times["T2"].completed_at, at(5));
+        assert!(state
+            .events
+            .windows(2)
+            .all(|w| w[0].timestamp <= w[1].timestamp));
+
+        // Re-reading one file keeps the events of the other
+        let mut lines = b_lines.to_vec();
+        lines.push(line(7, "agent_start", "T3", "sess-1"));
+        let events = hook_parser::parse_hook_events(&lines.join("\n")).events;
+        state.reload_hook_file(&b, events);
+        assert_eq!(state.events.len(), 5);
+        assert_eq!(state.event_sources[&b], 3);
+        let main = &state.agents["main"];
+        assert_eq!(main.status, AgentStatus::Running);
+        assert_eq!(main.current_task.as_deref(), Some("T3"));
+        assert_eq!(state.task_times["T1"].completed_at, at(6));
+        assert_eq!(state.event_origins[4].as_deref(), Some(b.as_path()));
+
+        // An appended event older than the latest one is replayed in its place
+        let revision = state.revision;
+        let events = hook_parser::parse_hook_events(
+            &[
+                line(1, "agent_start", "T2", "sess-2"),
+                line(6, "agent_end", "T1", "sess-1"),
+                line(2, "error", "T2", "sess-2"),
+            ]
+            .join("\n"),
+        )
+        .events;
+        state.reload_hook_file(&a, events);
+        assert_eq!(state.events.len(), 6);
+        assert!(state
+            .events
+            .windows(2)
+            .all(|w| w[0].timestamp <= w[1].timestamp));
+        assert_eq!(state.events[2].event_type, EventType::Error);
+        assert_eq!(state.event_origins[2].as_deref(), Some(a.as_path()));
+        assert!(state.revision > revision);
+    }
+
+    #[test]
+    fn appended_hook_events_are_applied_without_a_replay() {
+        let line = |secs: u32, event_type: &str| {
+            format!(
+                r#"{{"event_type":"{event_type}","timestamp":"2026-02-08T10:00:{secs:02}Z","agent_id":"main","task_id":"T1","session_id":"s"}}"#
+            )
+        };
+        let file = Path::new("/hooks/a.jsonl");
+        let mut state = DashboardState::default();
+        let mut lines = vec![line(0, "agent_start"), line(1, "tool_start")];
+        state.reload_hook_file(
+            file,
+            hook_parser::parse_hook_events(&lines.join("\n")).events,
+        );
+        // A marker a replay would clear
+        state.run_started = Some(DateTime::<Utc>::MIN_UTC);
+
+        lines.push(line(2, "tool_end"));
+        state.reload_hook_file(
+            file,
+            hook_parser::parse_hook_events(&lines.join("\n")).events,
+        );
+        assert_eq!(state.events.len(), 3);
+        assert_eq!(state.run_started, Some(DateTime::<Utc>::MIN_UTC));
+
+        // A rewritten file is replayed from scratch
+        lines.remove(0);
+        state.reload_hook_file(
+            file,
+            hook_parser::parse_hook_events(&lines.join("\n")).events,
+        );
+        assert_eq!(state.events.len(), 2);
+        assert_eq!(state.run_started, None);
+    }
+
+    #[test]
+    fn work_outside_the_plan_is_untracked() {
+        let mut state = DashboardState {
+            task_filter: TaskFilter {
+                phases: vec!["P1".to_string()],
+                ..TaskFilter::default()
+            },
+            ..DashboardState::default()
+        };
+        state
+            .reload_tasks("# Phase 1: A\n### [ ] T1: a\n# Phase 2: B\n### [ ] T2: b\n")
+            .unwrap();
+        assert!(!state.is_shown("T2"));
+        let line = |secs: u32, event_type: &str, agent: &str, task: &str| {
+            format!(
+                r#"{{"event_type":"{event_type}","timestamp":"2026-02-08T10:00:{secs:02}Z","agent_id":"{agent}","task_id":"{task}","session_id":"s1"}}"#
+            )
+        };
+        let lines = [
+            line(0, "agent_start", "backend", "T1"),
+            line(1, "agent_start", "frontend", "T2"),
+            line(2, "tool_start", "main", "unknown"),
+            line(3, "agent_start", "backend", "X1"),
+            line(4, "tool_end", "main", "unknown"),
+            line(5, "tool_start", "main", "X1"),
+            line(6, "heartbeat", "main", "X2"),
+            line(7, "tool_start", "main", ""),
+            line(8, "tool_start", "frontend", "X3"),
+            line(9, "tool_end", "frontend", "X3"),
+        ];
+        state.update_from_events(&hook_parser::parse_hook_events(&lines.join("\n")).events);
+
+        let work = state.untracked_work();
+        let ids: Vec<&str> = work.iter().map(|w| w.task_id.as_str()).collect();
+        // T2 is hidden by the filter, not outside the plan, and tool calls
+        // logged without a task are not work on one
+        assert_eq!(ids, ["X1", "X3"]);
+        assert_eq!(work[0].agents, ["backend", "main"]);
+        assert_eq!((work[0].last_seen - work[0].first_seen).num_seconds(), 2);
+        assert!(work[0].running);
+        assert_eq!(work[1].agents, ["frontend"]);
+        assert_eq!(work[1].events, 2);
+        assert!(!work[1].running);
+    }
+
+    #[test]
+    fn tasks_file_with_invalid_utf8_still_loads() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &path,
+            b"# Phase 1: A\n### [x] T1: Logo \xff\n### [ ] T2: B\n",
+        )
+        .unwrap();
+
+        let mut state = DashboardState::from_tasks_file(&path).unwrap();
+        assert_eq!(state.total_tasks, 2);
+        assert_eq!(state.completed_tasks, 1);
+        assert_eq!(state.lossy_lines[&path], 1);
+
+        std::fs::write(&path, "# Phase 1: A\n### [x] T1: Logo\n").unwrap();
+        let content = state.read_tasks_file(&path).unwrap();
+        state.reload_tasks(&content).unwrap();
+        assert!(state.lossy_lines.is_empty());
+    }
+
+    #[test]
+    fn truncation_is_reported_once_per_file() {
+        let mut state = DashboardState::default();
+        let file = Path::new("hooks/big.jsonl");
+        assert!(state.record_truncation(file, 4096));
+        assert!(!state.record_truncation(file, 8192));
+        assert_eq!(state.truncated_sources[file], 8192);
+
+        // Back under the limit (rotated), then over it again
+        assert!(!state.record_truncation(file, 0));
+        assert!(state.truncated_sources.is_empty());
+        assert!(state.record_truncation(file, 100));
     }
 
     #[test]
@@ -445,6 +1870,37 @@ mod tests {
         assert!((state.overall_progress - 1.0).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn reload_collects_parse_warnings() {
+        let mut state = DashboardState::default();
+        let content = "### [ ] T0: Early\n# Phase 1 Setup\n### [In Progress] T1: Odd\n";
+        state.reload_tasks(content).unwrap();
+        assert_eq!(state.total_tasks, 2);
+        assert_eq!(state.parse_warnings.len(), 3);
+
+        state
+            .reload_tasks("# Phase 1: Setup\n### [ ] T1: Clean\n")
+            .unwrap();
+        assert!(state.parse_warnings.is_empty());
+    }
+
+    #[test]
+    fn failed_reload_keeps_phases() {
+        let mut state = DashboardState::from_tasks_content_with(
+            "{\"phases\": [{\"id\": \"P1\", \"name\": \"A\"}]}",
+            Arc::new(crate::data::tasks_source::StructuredSource {
+                format: crate::data::tasks_source::StructuredFormat::Json,
+            }),
+        )
+        .unwrap();
+        assert!(state.reload_tasks("{\"phases\": [").is_err());
+        assert_eq!(state.phases.len(), 1);
+        assert!(matches!(
+            state.parse_warnings[..],
+            [ParseError::Syntax { line: 1, .. }]
+        ));
+    }
+
     #[test]
     fn tool_start_sets_running_status() {
         let mut state = DashboardState::default();
@@ -456,6 +1912,8 @@ mod tests {
             session_id: "sess-1".to_string(),
             tool_name: Some("Edit".to_string()),
             error_message: None,
+            tool_output: None,
+            usage: None,
         }];
         state.update_from_events(&events);
 
@@ -476,6 +1934,8 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Edit".to_string()),
                 error_message: None,
+                tool_output: None,
+                usage: None,
             },
             HookEvent {
                 event_type: EventType::ToolEnd,
@@ -485,6 +1945,8 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Edit".to_string()),
                 error_message: None,
+                tool_output: None,
+                usage: None,
             },
         ];
         state.update_from_events(&events);
@@ -506,6 +1968,8 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: None,
                 error_message: None,
+                tool_output: None,
+                usage: None,
             },
             HookEvent {
                 event_type: EventType::ToolStart,
@@ -515,6 +1979,8 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Edit".to_string()),
                 error_message: None,
+                tool_output: None,
+                usage: None,
             },
             HookEvent {
                 event_type: EventType::ToolEnd,
@@ -524,6 +1990,8 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Edit".to_string()),
                 error_message: None,
+                tool_output: None,
+                usage: None,
             },
         ];
         state.update_from_events(&events);
@@ -534,6 +2002,49 @@ mod tests {
         assert_eq!(agent.current_task.as_deref(), Some("P1-T1"));
     }
 
+    fn error_event(task_id: &str, message: &str) -> HookEvent {
+        HookEvent {
+            event_type: EventType::Error,
+            timestamp: Utc::now(),
+            agent_id: "main".to_string(),
+            task_id: task_id.to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: None,
+            error_message: Some(message.to_string()),
+            tool_output: None,
+            usage: None,
+        }
+    }
+
+    #[test]
+    fn latest_fatal_finds_most_recent() {
+        let mut state = DashboardState::default();
+        state.update_from_events(&[
+            error_event("T1", "out of memory"),
+            error_event("T2", "stack overflow"),
+            error_event("T3", "request timed out"),
+        ]);
+        assert_eq!(state.recent_errors[2].severity, Severity::Warning);
+        assert_eq!(state.latest_fatal().unwrap().task_id, "T2");
+    }
+
+    #[test]
+    fn custom_rules_apply_on_update() {
+        let mut state = DashboardState {
+            custom_rules: vec![CustomRule {
+                pattern: "timed out".to_string(),
+                category: None,
+                severity: Some(Severity::Fatal),
+                retryable: None,
+                suggestion: None,
+            }],
+            ..Default::default()
+        };
+        state.update_from_events(&[error_event("T1", "request timed out")]);
+        assert_eq!(state.recent_errors[0].severity, Severity::Fatal);
+        assert_eq!(state.recent_errors[0].category, ErrorCategory::Network);
+    }
+
     #[test]
     fn reload_from_events_resets_state() {
         let mut state = DashboardState::default();
@@ -547,6 +2058,8 @@ mod tests {
             session_id: "sess-1".to_string(),
             tool_name: Some("Edit".to_string()),
             error_message: None,
+            tool_output: None,
+            usage: None,
         }];
         state.update_from_events(&events);
         assert_eq!(state.agents.get("main").unwrap().event_count, 1);
@@ -561,6 +2074,8 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Edit".to_string()),
                 error_message: None,
+                tool_output: None,
+                usage: None,
             },
             HookEvent {
                 event_type: EventType::ToolStart,
@@ -570,6 +2085,8 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Bash".to_string()),
                 error_message: None,
+                tool_output: None,
+                usage: None,
             },
         ];
         state.reload_from_events(&events2);
@@ -648,6 +2165,275 @@ mod tests {
         assert_eq!(agent.recent_tools, vec!["Read", "Write"]);
     }
 
+    #[test]
+    fn at_rebuilds_past_state_from_events() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [x] P1-R1-T1: done\n### [x] P1-R1-T2: also done\n### [ ] P1-R1-T3: untracked\n",
+        )
+        .unwrap();
+        let input = concat!(
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-R1-T1","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:01:00Z","agent_id":"a","task_id":"P1-R1-T1","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:02:00Z","agent_id":"a","task_id":"P1-R1-T2","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:05:00Z","agent_id":"a","task_id":"P1-R1-T2","session_id":"s"}"#,
+        );
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+        let at = |hms: &str| {
+            format!("2026-02-08T{hms}Z")
+                .parse::<DateTime<Utc>>()
+                .unwrap()
+        };
+
+        let past = state.at(at("10:03:00"));
+        assert_eq!(past.now(), at("10:03:00"));
+        let statuses: Vec<&TaskStatus> = past.phases[0].tasks.iter().map(|t| &t.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                &TaskStatus::Completed,
+                &TaskStatus::InProgress,
+                &TaskStatus::Pending
+            ]
+        );
+        assert_eq!(past.completed_tasks, 1);
+        assert_eq!(past.agents["a"].status, AgentStatus::Running);
+        assert!(past.task_times["P1-R1-T2"].completed_at.is_none());
+
+        let before = state.at(at("09:00:00"));
+        assert_eq!(before.phases[0].tasks[0].status, TaskStatus::Pending);
+        assert!(before.agents.is_empty());
+        // The live state is untouched
+        assert_eq!(state.completed_tasks, 2);
+        assert_eq!(state.events.len(), 4);
+    }
+
+    #[test]
+    fn timeline_steps_match_a_fresh_replay() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let mut state = DashboardState::default();
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+        let (first, last) = state.event_span().unwrap();
+        let step = (last - first) / 7;
+
+        let mut timeline = Timeline::new(&state);
+        let forward = (0..=7).map(|i| first + step * i);
+        let back = (0..7).rev().map(|i| first + step * i);
+        for instant in forward.chain(back) {
+            timeline.seek(instant);
+            let fresh = state.at(instant);
+            let past = timeline.state();
+            assert_eq!(past.scrub_at, Some(instant));
+            assert_eq!(past.events.len(), fresh.events.len());
+            let times = |s: &DashboardState| {
+                let mut times: Vec<_> = s
+                    .task_times
+                    .iter()
+                    .map(|(id, t)| (id.clone(), t.started_at, t.completed_at))
+                    .collect();
+                times.sort();
+                times
+            };
+            assert_eq!(times(past), times(&fresh));
+            let agents = |s: &DashboardState| -> Vec<(String, AgentStatus, usize)> {
+                let mut agents: Vec<_> = s
+                    .agents
+                    .values()
+                    .map(|a| (a.agent_id.clone(), a.status.clone(), a.event_count))
+                    .collect();
+                agents.sort_by(|a, b| a.0.cmp(&b.0));
+                agents
+            };
+            assert_eq!(agents(past), agents(&fresh));
+        }
+    }
+
+    #[test]
+    fn timeline_keeps_the_latest_events() {
+        let base = "2026-02-08T10:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let events: Vec<HookEvent> = (0..MAX_SCRUB_EVENTS + 10)
+            .map(|i| HookEvent {
+                event_type: EventType::Heartbeat,
+                timestamp: base + Duration::seconds(i as i64),
+                agent_id: "a".to_string(),
+                task_id: "T1".to_string(),
+                session_id: "s".to_string(),
+                tool_name: None,
+                error_message: None,
+                tool_output: None,
+                usage: None,
+            })
+            .collect();
+        let mut state = DashboardState::default();
+        state.update_from_events(&events);
+
+        let timeline = Timeline::new(&state);
+        assert_eq!(
+            timeline.span(),
+            Some((
+                base + Duration::seconds(10),
+                base + Duration::seconds(MAX_SCRUB_EVENTS as i64 + 9)
+            ))
+        );
+    }
+
+    #[test]
+    fn tool_calls_paired_from_start_and_end() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let result = hook_parser::parse_hook_events(input);
+
+        let mut state = DashboardState::default();
+        state.update_from_events(&result.events);
+
+        let calls = &state.agents["backend-specialist-1"].tool_history;
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[1].tool, "Write");
+        let secs = (calls[1].ended_at.unwrap() - calls[1].started_at).num_seconds();
+        assert_eq!(secs, 5);
+    }
+
+    #[test]
+    fn tool_output_keeps_last_lines() {
+        let input = concat!(
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","tool_name":"Bash","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:00:01Z","agent_id":"a","task_id":"T1","tool_name":"Bash","session_id":"s","tool_output":"1\n2\n\n3\n4\n5\n6\n7"}"#,
+            "\n",
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:02Z","agent_id":"a","task_id":"T1","tool_name":"Read","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:00:03Z","agent_id":"a","task_id":"T1","tool_name":"Read","session_id":"s"}"#,
+        );
+        let mut state = DashboardState::default();
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+
+        // The Read call without output leaves the Bash preview in place
+        let output = state.agents["a"].last_output.as_ref().unwrap();
+        assert_eq!(output.tool.as_deref(), Some("Bash"));
+        assert_eq!(output.lines, vec!["3", "4", "5", "6", "7"]);
+    }
+
+    #[test]
+    fn usage_is_summed_per_task_and_agent() {
+        let input = concat!(
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s","usage":{"input_tokens":1000,"output_tokens":200}}"#,
+            "\n",
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:01Z","agent_id":"a","task_id":"T2","session_id":"s","usage":{"input_tokens":10,"output_tokens":20,"cost_usd":0.5}}"#,
+            "\n",
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:02Z","agent_id":"b","task_id":"T1","session_id":"s","usage":{"output_tokens":5}}"#,
+            "\n",
+            r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:00:03Z","agent_id":"b","task_id":"T1","session_id":"s"}"#,
+        );
+        let events = hook_parser::parse_hook_events(input).events;
+        let mut state = DashboardState::default();
+        state.update_from_events(&events);
+
+        assert_eq!(state.task_spend["T1"].tokens(), 1205);
+        assert_eq!(state.task_spend["T2"].reported_usd, 0.5);
+        assert_eq!(state.agent_spend["a"].tokens(), 1230);
+        assert_eq!(state.agent_spend["b"].output_tokens, 5);
+        let total = state.total_spend();
+        assert_eq!(total.tokens(), 1235);
+        assert_eq!(total.unpriced_input, 1000);
+
+        // Re-reading the file does not count usage twice
+        state.reload_from_events(&events);
+        assert_eq!(state.total_spend(), total);
+    }
+
+    #[test]
+    fn rate_limits_start_a_cooldown() {
+        let input = concat!(
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"rate_limit","timestamp":"2026-02-08T10:00:10Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"retry after 30s"}"#,
+            "\n",
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:01:00Z","agent_id":"b","task_id":"T2","session_id":"s","error_message":"429 Too Many Requests"}"#,
+        );
+        let events = hook_parser::parse_hook_events(input).events;
+        let mut state = DashboardState::default();
+        state.update_from_events(&events);
+
+        // Waiting out a rate limit leaves the agent running
+        let a = &state.agents["a"];
+        assert_eq!(a.status, AgentStatus::Running);
+        assert_eq!(a.error_count, 0);
+        let hit = "2026-02-08T10:00:10Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(a.rate_limits.until, Some(hit + Duration::seconds(30)));
+        assert_eq!(
+            a.rate_limits.remaining(hit + Duration::seconds(20)),
+            Some(10)
+        );
+        assert_eq!(a.rate_limits.remaining(hit + Duration::seconds(30)), None);
+        assert_eq!(state.recent_errors[0].category, ErrorCategory::RateLimit);
+
+        // Errors worded as rate limits cool down for the default window
+        let b = &state.agents["b"];
+        assert_eq!(b.status, AgentStatus::Error);
+        assert_eq!(
+            b.rate_limits.waited,
+            Duration::seconds(DEFAULT_COOLDOWN_SECS as i64)
+        );
+        assert_eq!(state.task_rate_limits["T2"].hits, 1);
+        let (total, agents) = state.rate_limit_totals();
+        assert_eq!((total.hits, agents), (2, 2));
+
+        state.reload_from_events(&events);
+        assert_eq!(state.task_rate_limits["T1"].hits, 1);
+    }
+
+    #[test]
+    fn huge_retry_after_is_capped() {
+        let input = concat!(
+            r#"{"event_type":"rate_limit","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"retry after 99999999999999s"}"#,
+            "\n",
+            r#"{"event_type":"rate_limit","timestamp":"2026-02-08T10:00:00Z","agent_id":"b","task_id":"T2","session_id":"s","error_message":"try again in 99999999999999999h"}"#,
+        );
+        let events = hook_parser::parse_hook_events(input).events;
+        let mut state = DashboardState::default();
+        state.update_from_events(&events);
+
+        let cap = Duration::seconds(MAX_COOLDOWN_SECS as i64);
+        assert_eq!(state.agents["a"].rate_limits.waited, cap);
+        assert_eq!(state.agents["b"].rate_limits.waited, cap);
+    }
+
+    #[test]
+    fn tool_stats_flag_slow_calls() {
+        let base = Utc::now() - Duration::minutes(10);
+        let tool_event = |event_type, secs: i64| HookEvent {
+            event_type,
+            timestamp: base + Duration::seconds(secs),
+            agent_id: "agent-1".to_string(),
+            task_id: "T-1".to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: Some("Bash".to_string()),
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        };
+        // Three 2s calls, then one of 60s
+        let mut events = Vec::new();
+        for (start, end) in [(0, 2), (10, 12), (20, 22), (30, 90)] {
+            events.push(tool_event(EventType::ToolStart, start));
+            events.push(tool_event(EventType::ToolEnd, end));
+        }
+        let mut state = DashboardState::default();
+        state.update_from_events(&events);
+
+        let stats = &state.tool_stats["Bash"];
+        assert_eq!(stats.calls, 4);
+        assert_eq!(stats.max, Duration::seconds(60));
+        assert_eq!(stats.average(), Duration::milliseconds(16_500));
+        let calls = &state.agents["agent-1"].tool_history;
+        assert!(!state.is_slow_call(&calls[0]));
+        assert!(state.is_slow_call(&calls[3]));
+
+        state.reload_from_events(&[]);
+        assert!(state.tool_stats.is_empty());
+    }
+
     #[test]
     fn recent_tools_capped_at_max() {
         let mut state = DashboardState::default();
@@ -660,6 +2446,8 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some(format!("Tool{i}")),
                 error_message: None,
+                tool_output: None,
+                usage: None,
             })
             .collect();
         state.update_from_events(&events);
@@ -671,6 +2459,38 @@ mod tests {
         assert_eq!(agent.recent_tools[9], "Tool14");
     }
 
+    #[test]
+    fn heartbeats_track_liveness_without_counting_as_events() {
+        let now = Utc::now();
+        let event = |event_type, ago: i64| HookEvent {
+            event_type,
+            timestamp: now - Duration::seconds(ago),
+            agent_id: "agent-1".to_string(),
+            task_id: "T-1".to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: None,
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        };
+        let mut state = DashboardState::default();
+        state.update_from_events(&[event(EventType::AgentStart, 120)]);
+        // No heartbeats yet: silence says nothing
+        assert_eq!(state.agents["agent-1"].liveness(now), None);
+
+        state.update_from_events(&[event(EventType::Heartbeat, 10)]);
+        let agent = &state.agents["agent-1"];
+        assert_eq!(agent.event_count, 1);
+        assert_eq!(agent.liveness(now), Some(Liveness::Alive { since: 10 }));
+        assert_eq!(
+            agent.liveness(now + Duration::seconds(60)),
+            Some(Liveness::Vanished { since: 70 })
+        );
+
+        state.update_from_events(&[event(EventType::AgentEnd, 5)]);
+        assert_eq!(state.agents["agent-1"].liveness(now), None);
+    }
+
     #[test]
     fn session_id_tracked_from_events() {
         let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
@@ -701,4 +2521,56 @@ mod tests {
         assert!(state.agents.contains_key("backend-specialist-1"));
         assert!(state.agents.contains_key("backend-specialist-2"));
     }
+
+    #[test]
+    fn custom_done_statuses_count_as_completed() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [x] T1: A\n### [Skipped] T2: B\n### [Review] T3: C\n### [ ] T4: D\n",
+        )
+        .unwrap();
+        assert_eq!(state.total_tasks, 4);
+        assert_eq!(state.completed_tasks, 1);
+
+        state.set_custom_statuses(vec![StatusDef {
+            name: "Skipped".to_string(),
+            done: true,
+            ..Default::default()
+        }]);
+        assert_eq!(state.completed_tasks, 2);
+        assert!((state.overall_progress - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn milestone_reached_needs_all_earlier_phases_done() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [x] T1: A\n## Milestone: alpha\n# Phase 2: B\n### [ ] T2: B\n# Phase 3: C\n### [x] T3: C\n## Milestone: beta\n",
+        )
+        .unwrap();
+        assert!(state.milestone_reached(0));
+        assert!(!state.milestone_reached(2));
+    }
+
+    #[test]
+    fn weighted_progress_uses_points() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [x] T1: A\n- **points**: 3\n### [ ] T2: B\n### [ ] T3: C\n- **points**: 6\n",
+        )
+        .unwrap();
+        assert!((state.overall_progress - 1.0 / 3.0).abs() < f32::EPSILON);
+
+        state.set_progress_weights(ProgressWeights {
+            by_points: true,
+            default_points: 1,
+        });
+        assert_eq!(state.completed_tasks, 1);
+        assert!((state.overall_progress - 0.3).abs() < f32::EPSILON);
+        assert!((state.phase_progress(&state.phases[0]) - 0.3).abs() < f32::EPSILON);
+
+        // Unsized tasks at the largest default weight still add up
+        state.set_progress_weights(ProgressWeights {
+            by_points: true,
+            default_points: u32::MAX,
+        });
+        assert!(state.overall_progress > 0.0 && state.overall_progress < 0.01);
+    }
 }
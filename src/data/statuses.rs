@@ -0,0 +1,83 @@
+//! Custom status vocabulary
+//!
+//! Teams can tag tasks with their own statuses (`[Review]`, `[Skipped]`,
+//! `[WontDo]`). The parser accepts any single-word tag; the `[[statuses]]`
+//! config section gives each one an icon, a color and whether it counts as done.
+
+use serde::Deserialize;
+
+use crate::data::tasks_parser::TaskStatus;
+
+/// One custom status from the `[[statuses]]` config section
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct StatusDef {
+    /// Tag as written in TASKS.md, e.g. "Review" for `[Review]`
+    pub name: String,
+    /// Icon shown in the task list (default: first letter in brackets)
+    pub icon: Option<String>,
+    /// Color name or `#rrggbb` (default: cyan)
+    pub color: Option<String>,
+    /// Counts toward completion and progress
+    pub done: bool,
+}
+
+/// Definition for a custom status, matched case-insensitively
+pub fn find<'a>(defs: &'a [StatusDef], name: &str) -> Option<&'a StatusDef> {
+    defs.iter().find(|d| d.name.eq_ignore_ascii_case(name))
+}
+
+/// Whether a status counts as done: `[x]`, or a custom status marked `done`
+pub fn is_done(status: &TaskStatus, defs: &[StatusDef]) -> bool {
+    match status {
+        TaskStatus::Completed => true,
+        TaskStatus::Custom(name) => find(defs, name).is_some_and(|d| d.done),
+        _ => false,
+    }
+}
+
+/// Icon for a custom status
+pub fn icon(name: &str, defs: &[StatusDef]) -> String {
+    match find(defs, name).and_then(|d| d.icon.clone()) {
+        Some(icon) => icon,
+        None => format!("[{}]", name.chars().next().unwrap_or('?')),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defs() -> Vec<StatusDef> {
+        vec![
+            StatusDef {
+                name: "Skipped".to_string(),
+                icon: Some("[-]".to_string()),
+                color: None,
+                done: true,
+            },
+            StatusDef {
+                name: "Review".to_string(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn done_statuses() {
+        let defs = defs();
+        assert!(is_done(&TaskStatus::Completed, &defs));
+        assert!(is_done(&TaskStatus::Custom("skipped".into()), &defs));
+        assert!(!is_done(&TaskStatus::Custom("Review".into()), &defs));
+        assert!(!is_done(&TaskStatus::Custom("Unknown".into()), &defs));
+        assert!(!is_done(&TaskStatus::InProgress, &defs));
+    }
+
+    #[test]
+    fn icons_fall_back_to_initial() {
+        let defs = defs();
+        assert_eq!(icon("Skipped", &defs), "[-]");
+        assert_eq!(icon("Review", &defs), "[R]");
+        assert_eq!(icon("WontDo", &[]), "[W]");
+    }
+}
@@ -0,0 +1,267 @@
+//! Interned task IDs
+//!
+//! The same task ID is held by timings, errors, agent state and review
+//! sets. [`TaskId`] is a shared `Arc<str>`, so holding it again is a
+//! reference count bump, and [`TaskIds`] hands out one allocation per
+//! distinct ID.
+//!
+//! [`next_in_phase`] derives the ID for a task added from the board from the
+//! IDs the phase already uses.
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::data::tasks_parser::ParsedPhase;
+
+/// A cheaply clonable task ID; compares, hashes and derefs as its `str`
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TaskId(Arc<str>);
+
+impl TaskId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for TaskId {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for TaskId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for TaskId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for TaskId {
+    fn from(id: &str) -> Self {
+        Self(Arc::from(id))
+    }
+}
+
+impl From<String> for TaskId {
+    fn from(id: String) -> Self {
+        Self(Arc::from(id))
+    }
+}
+
+impl PartialEq<str> for TaskId {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for TaskId {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for TaskId {
+    fn eq(&self, other: &String) -> bool {
+        *self.0 == **other
+    }
+}
+
+impl PartialEq<TaskId> for String {
+    fn eq(&self, other: &TaskId) -> bool {
+        **self == *other.0
+    }
+}
+
+impl PartialEq<TaskId> for &str {
+    fn eq(&self, other: &TaskId) -> bool {
+        **self == *other.0
+    }
+}
+
+/// Registry of the task IDs a dashboard has seen
+#[derive(Debug, Clone, Default)]
+pub struct TaskIds {
+    ids: HashSet<TaskId>,
+}
+
+impl TaskIds {
+    /// The shared ID for `id`, allocated only the first time it is seen
+    pub fn intern(&mut self, id: &str) -> TaskId {
+        if let Some(existing) = self.ids.get(id) {
+            return existing.clone();
+        }
+        let interned = TaskId::from(id);
+        self.ids.insert(interned.clone());
+        interned
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+/// Split `P2-S1-T09` into (`P2-S1-T`, 9, 2 digits)
+fn trailing_number(id: &str) -> Option<(&str, u64, usize)> {
+    let prefix = id.trim_end_matches(|c: char| c.is_ascii_digit());
+    let digits = &id[prefix.len()..];
+    Some((prefix, digits.parse().ok()?, digits.len()))
+}
+
+/// `id` with its trailing number raised by one, keeping zero padding
+/// (`T09` -> `T10`); an ID without one gets `-2`
+fn increment(id: &str) -> String {
+    match trailing_number(id) {
+        Some((prefix, n, width)) => format!("{prefix}{:0width$}", n + 1),
+        None => format!("{id}-2"),
+    }
+}
+
+/// The ID for a new task at the end of `phases[phase_idx]`, following the
+/// phase's pattern (`P2-S1-T3` -> `P2-S1-T4`). An empty phase borrows the
+/// pattern of another phase, with that phase's ID swapped for its own
+/// (`P1-S1-T5` -> `P3-S1-T1`) or numbering continued for plan-wide IDs
+/// (`T5` -> `T6`). Never returns an ID already in `phases` or in
+/// `planned`, the IDs of the whole tasks file as last parsed.
+pub fn next_in_phase(
+    phases: &[ParsedPhase],
+    phase_idx: usize,
+    planned: &HashSet<TaskId>,
+) -> String {
+    let taken: HashSet<&str> = phases
+        .iter()
+        .flat_map(|p| &p.tasks)
+        .map(|t| t.id.as_str())
+        .chain(planned.iter().map(TaskId::as_str))
+        .collect();
+    let Some(phase) = phases.get(phase_idx) else {
+        return "T1".to_string();
+    };
+    let mut candidate = match phase.tasks.last() {
+        Some(last) => increment(&last.id),
+        None => borrowed_pattern(phases, phase_idx).unwrap_or_else(|| format!("{}-T1", phase.id)),
+    };
+    while taken.contains(candidate.as_str()) {
+        candidate = increment(&candidate);
+    }
+    candidate
+}
+
+/// First ID for an empty phase, modelled on the nearest phase with tasks
+fn borrowed_pattern(phases: &[ParsedPhase], phase_idx: usize) -> Option<String> {
+    let before = phases[..phase_idx].iter().rev();
+    let after = phases[phase_idx + 1..].iter();
+    let model = before.chain(after).find(|p| !p.tasks.is_empty())?;
+    let first = &model.tasks[0].id;
+    let scoped = first
+        .get(..model.id.len())
+        .filter(|head| head.eq_ignore_ascii_case(&model.id))
+        .map(|_| &first[model.id.len()..])
+        .filter(|rest| rest.starts_with(|c: char| !c.is_ascii_alphanumeric()));
+    match scoped {
+        Some(rest) => {
+            let (prefix, _, width) = trailing_number(rest)?;
+            Some(format!("{}{prefix}{:0width$}", phases[phase_idx].id, 1))
+        }
+        None => phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .last()
+            .map(|t| increment(&t.id)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::state::DashboardState;
+    use std::collections::HashMap;
+
+    #[test]
+    fn intern_shares_one_allocation() {
+        let mut ids = TaskIds::default();
+        let a = ids.intern("P1-T1");
+        let b = ids.intern(&String::from("P1-T1"));
+        assert!(Arc::ptr_eq(&a.0, &b.0));
+        assert_eq!(ids.len(), 1);
+        assert_ne!(ids.intern("P1-T2"), a);
+    }
+
+    #[test]
+    fn task_id_behaves_like_str() {
+        let id = TaskId::from("P1-T1");
+        assert_eq!(id, "P1-T1");
+        assert_eq!(id.len(), 5);
+        assert_eq!(format!("{id} {id:?}"), "P1-T1 \"P1-T1\"");
+
+        let map = HashMap::from([(id.clone(), 1)]);
+        assert_eq!(map.get("P1-T1"), Some(&1));
+    }
+
+    fn next_ids(plan: &str) -> Vec<String> {
+        let state = DashboardState::from_tasks_content(plan).unwrap();
+        (0..state.phases.len())
+            .map(|pi| next_in_phase(&state.phases, pi, &state.planned_tasks))
+            .collect()
+    }
+
+    #[test]
+    fn next_id_follows_the_phase_pattern() {
+        let plan = "\
+# Phase 1: Core
+### [x] P1-S1-T1: One
+### [ ] P1-S1-T2: Two
+# Phase 2: UI
+### [ ] P2-S1-T09: Nine
+# Phase 3: Docs
+";
+        assert_eq!(next_ids(plan), ["P1-S1-T3", "P2-S1-T10", "P3-S1-T01"]);
+    }
+
+    #[test]
+    fn next_id_skips_ids_taken_elsewhere() {
+        let plan = "\
+# Phase 1: A
+### [ ] T1: One
+### [ ] T2: Two
+# Phase 2: B
+### [ ] T3: Three
+# Phase 3: C
+### [ ] setup: Setup
+";
+        assert_eq!(next_ids(plan), ["T4", "T4", "setup-2"]);
+        assert_eq!(next_ids("# Phase 1: A\n# Phase 2: B\n"), ["P1-T1", "P2-T1"]);
+    }
+
+    #[test]
+    fn next_id_skips_planned_ids_outside_the_phases() {
+        let state = DashboardState::from_tasks_content("# Phase 1: A\n### [ ] T1: One\n").unwrap();
+        let planned = HashSet::from([TaskId::from("T2"), TaskId::from("T3")]);
+        assert_eq!(next_in_phase(&state.phases, 0, &planned), "T4");
+    }
+}
@@ -0,0 +1,847 @@
+//! Section-aware TASKS.md document model
+//!
+//! Splits TASKS.md into phase headings, task sections (header + body) and the
+//! free text between them. Every line keeps its original bytes, terminator
+//! included, so structural edits move whole sections and untouched lines
+//! serialize back byte-for-byte.
+//!
+//! Edits are either structural (reordering, phase moves) or scoped to one task
+//! section (status, title, body fields); only the lines they touch change.
+
+use crate::data::tasks_parser::{
+    checkbox_task, h1_h2_heading, heading_status, indent_of, parse_phase_header, TaskStatus, BOM,
+};
+
+/// A phase heading line (`# Phase N: Name`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseHeading {
+    pub id: String,
+    pub line: String,
+}
+
+/// A task header line plus its body lines (trailing blank lines excluded)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskSection {
+    pub id: String,
+    pub lines: Vec<String>,
+}
+
+/// One piece of the document, in file order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// Lines outside any task: preamble, group headings, rules, blank separators
+    Text(Vec<String>),
+    Phase(PhaseHeading),
+    Task(TaskSection),
+}
+
+/// Body field holding the owning agent
+pub const AGENT_FIELD: &str = "담당";
+
+/// Body field counting retries
+pub const ATTEMPTS_FIELD: &str = "attempts";
+
+/// Direction for moving a task within its phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+}
+
+/// Parsed TASKS.md that serializes back to the original text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TasksDoc {
+    segments: Vec<Segment>,
+    /// Line terminator used for inserted lines ("\n" or "\r\n")
+    newline: &'static str,
+    /// The source did not end with a newline (one is added internally)
+    missing_final_newline: bool,
+    /// The source started with a UTF-8 byte order mark
+    bom: bool,
+}
+
+/// Task ID from a `### [status] ID: Name` line
+fn task_header_id(trimmed: &str) -> Option<String> {
+    let rest = trimmed.strip_prefix("### ")?;
+    let (remaining, _) = heading_status(rest)?;
+    let remaining = remaining.trim();
+    let id = remaining.split_once(':').map_or(remaining, |(id, _)| id);
+    Some(id.trim().to_string())
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Split a line into its content and terminator
+fn split_ending(line: &str) -> (&str, &str) {
+    let content = line.trim_end_matches(['\r', '\n']);
+    (content, &line[content.len()..])
+}
+
+/// Byte offset just past `key:` in a `- **key**: value` / `- key: value` line
+fn field_value_start(line: &str, key: &str) -> Option<usize> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = line[indent..].strip_prefix("- ")?;
+    let offset = indent + 2;
+    let (name, after) = match rest.strip_prefix("**") {
+        Some(bold) => {
+            // `**key**: value` or `**key:** value`
+            let (name, after) = bold.split_once("**")?;
+            let after = after
+                .strip_prefix(':')
+                .or_else(|| name.ends_with(':').then_some(after))?;
+            (name, after.len())
+        }
+        None => {
+            let (name, after) = rest.split_once(':')?;
+            (name, after.len())
+        }
+    };
+    let name = name.trim().trim_end_matches(':');
+    name.eq_ignore_ascii_case(key)
+        .then(|| offset + rest.len() - after)
+}
+
+impl TaskSection {
+    /// Status from the `[status]` tag in the header line
+    pub fn status(&self) -> Option<TaskStatus> {
+        if let Some((_, status, _, _)) = checkbox_task(&self.lines[0]) {
+            return Some(status);
+        }
+        let rest = self.lines[0].trim().strip_prefix("### ")?;
+        heading_status(rest).map(|(_, status)| status.unwrap_or_else(|custom| custom))
+    }
+
+    /// Indent of the bullet when the header is a `- [ ] ID: Name` line
+    fn checkbox_indent(&self) -> Option<usize> {
+        checkbox_task(&self.lines[0]).map(|(indent, ..)| indent)
+    }
+
+    /// Replace the `[status]` tag in the header line
+    pub fn set_status(&mut self, status: &str) {
+        let header = &self.lines[0];
+        let (Some(open), Some(close)) = (header.find('['), header.find(']')) else {
+            return;
+        };
+        self.lines[0] = format!("{}[{status}]{}", &header[..open], &header[close + 1..]);
+    }
+
+    /// Replace the task name after `ID:` in the header line
+    pub fn rename(&mut self, name: &str) {
+        let (content, ending) = split_ending(&self.lines[0]);
+        let Some(id_at) = content.find(&format!("] {}", self.id)) else {
+            return;
+        };
+        let prefix_end = id_at + 2 + self.id.len();
+        self.lines[0] = format!("{}: {name}{ending}", &content[..prefix_end]);
+    }
+
+    /// Value of a `- **key**: value` body line
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.lines[1..].iter().find_map(|line| {
+            let start = field_value_start(line, key)?;
+            Some(split_ending(&line[start..]).0.trim())
+        })
+    }
+
+    /// Set a `- **key**: value` body line, rewriting only its value if the
+    /// field exists and appending a new bullet otherwise
+    pub fn set_field(&mut self, key: &str, value: &str, newline: &str) {
+        for line in self.lines.iter_mut().skip(1) {
+            if let Some(start) = field_value_start(line, key) {
+                let (_, ending) = split_ending(line);
+                *line = format!("{} {value}{ending}", &line[..start]);
+                return;
+            }
+        }
+        self.push_line(&format!("- **{key}**: {value}"), newline);
+    }
+
+    /// Increment a numeric `- key: N` field (missing or unparsable counts as
+    /// 0) and return the new value
+    pub fn increment_field(&mut self, key: &str, newline: &str) -> u32 {
+        let next = self
+            .field(key)
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(0)
+            + 1;
+        if self.field(key).is_some() {
+            self.set_field(key, &next.to_string(), newline);
+        } else {
+            self.push_line(&format!("- {key}: {next}"), newline);
+        }
+        next
+    }
+
+    /// Append a body line after the last existing one; checkbox task
+    /// bodies are indented under their bullet
+    pub fn push_line(&mut self, text: &str, newline: &str) {
+        let pad = self.checkbox_indent().map_or(0, |indent| indent + 2);
+        self.lines
+            .push(format!("{}{text}{newline}", " ".repeat(pad)));
+    }
+}
+
+impl TasksDoc {
+    pub fn parse(content: &str) -> Self {
+        let bom = content.starts_with(BOM);
+        let content = content.strip_prefix(BOM).unwrap_or(content);
+        // Inserted lines follow whichever terminator the file mostly uses
+        let crlf = content.matches("\r\n").count();
+        let newline = if crlf * 2 > content.matches('\n').count() {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let missing_final_newline = !content.is_empty() && !content.ends_with('\n');
+
+        let mut doc = Self {
+            segments: Vec::new(),
+            newline,
+            missing_final_newline,
+            bom,
+        };
+        let mut current: Option<TaskSection> = None;
+
+        for raw in content.split_inclusive('\n') {
+            let mut line = raw.to_string();
+            if !line.ends_with('\n') {
+                line.push_str(newline);
+            }
+            let trimmed = line.trim();
+
+            if let Some(heading) = h1_h2_heading(trimmed) {
+                doc.close_task(&mut current);
+                match parse_phase_header(heading) {
+                    Some(phase) => doc
+                        .segments
+                        .push(Segment::Phase(PhaseHeading { id: phase.id, line })),
+                    None => doc.push_text(line),
+                }
+            } else if trimmed == "---" || trimmed.starts_with("### ") {
+                doc.close_task(&mut current);
+                match task_header_id(trimmed) {
+                    Some(id) => {
+                        current = Some(TaskSection {
+                            id,
+                            lines: vec![line],
+                        })
+                    }
+                    None => doc.push_text(line),
+                }
+            } else if let Some((_, _, id, _)) = checkbox_task(&line).filter(|_| {
+                current
+                    .as_ref()
+                    .map_or(true, |t| t.checkbox_indent().is_some())
+            }) {
+                let id = id.to_string();
+                doc.close_task(&mut current);
+                current = Some(TaskSection {
+                    id,
+                    lines: vec![line],
+                });
+            } else if let Some(ref mut task) = current {
+                // A non-blank line back at a checkbox bullet's level ends it
+                let dedented = task
+                    .checkbox_indent()
+                    .is_some_and(|indent| !trimmed.is_empty() && indent_of(&line) <= indent);
+                if dedented {
+                    doc.close_task(&mut current);
+                    doc.push_text(line);
+                } else {
+                    task.lines.push(line);
+                }
+            } else {
+                doc.push_text(line);
+            }
+        }
+        doc.close_task(&mut current);
+        doc
+    }
+
+    fn push_text(&mut self, line: String) {
+        if let Some(Segment::Text(lines)) = self.segments.last_mut() {
+            lines.push(line);
+        } else {
+            self.segments.push(Segment::Text(vec![line]));
+        }
+    }
+
+    /// Finish a task section; its trailing blank lines become separator text
+    fn close_task(&mut self, current: &mut Option<TaskSection>) {
+        let Some(mut task) = current.take() else {
+            return;
+        };
+        let keep = task
+            .lines
+            .iter()
+            .rposition(|l| !is_blank(l))
+            .map_or(1, |i| i + 1);
+        let trailing = task.lines.split_off(keep);
+        self.segments.push(Segment::Task(task));
+        for line in trailing {
+            self.push_text(line);
+        }
+    }
+
+    pub fn serialize(&self) -> String {
+        let mut out = String::new();
+        if self.bom {
+            out.push(BOM);
+        }
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(lines) => lines.iter().for_each(|l| out.push_str(l)),
+                Segment::Phase(phase) => out.push_str(&phase.line),
+                Segment::Task(task) => task.lines.iter().for_each(|l| out.push_str(l)),
+            }
+        }
+        if self.missing_final_newline {
+            let trimmed_len = out.trim_end_matches(['\r', '\n']).len();
+            out.truncate(trimmed_len);
+        }
+        out
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    pub fn task(&self, task_id: &str) -> Option<&TaskSection> {
+        self.task_index(task_id)
+            .and_then(|i| match &self.segments[i] {
+                Segment::Task(task) => Some(task),
+                _ => None,
+            })
+    }
+
+    /// Apply `edit` to a task section. Returns false if the task is missing.
+    pub fn edit_task(
+        &mut self,
+        task_id: &str,
+        edit: impl FnOnce(&mut TaskSection, &'static str),
+    ) -> bool {
+        let newline = self.newline;
+        let Some(idx) = self.task_index(task_id) else {
+            return false;
+        };
+        let Segment::Task(task) = &mut self.segments[idx] else {
+            return false;
+        };
+        edit(task, newline);
+        true
+    }
+
+    pub fn set_status(&mut self, task_id: &str, status: &str) -> bool {
+        self.edit_task(task_id, |task, _| task.set_status(status))
+    }
+
+    pub fn rename_task(&mut self, task_id: &str, name: &str) -> bool {
+        self.edit_task(task_id, |task, _| task.rename(name))
+    }
+
+    pub fn set_field(&mut self, task_id: &str, key: &str, value: &str) -> bool {
+        self.edit_task(task_id, |task, newline| task.set_field(key, value, newline))
+    }
+
+    /// Set the owning agent (`- **담당**: @agent`)
+    pub fn set_agent(&mut self, task_id: &str, agent: &str) -> bool {
+        let agent = agent.trim_start_matches('@');
+        self.set_field(task_id, AGENT_FIELD, &format!("@{agent}"))
+    }
+
+    /// Bump the task's `- attempts: N` retry counter, returning the new count
+    pub fn increment_attempts(&mut self, task_id: &str) -> Option<u32> {
+        let mut count = None;
+        self.edit_task(task_id, |task, newline| {
+            count = Some(task.increment_field(ATTEMPTS_FIELD, newline))
+        });
+        count
+    }
+
+    /// Append a `- {text}` bullet to the end of a task body
+    pub fn append_bullet(&mut self, task_id: &str, text: &str) -> bool {
+        self.edit_task(task_id, |task, newline| {
+            task.push_line(&format!("- {text}"), newline)
+        })
+    }
+
+    fn task_index(&self, task_id: &str) -> Option<usize> {
+        self.segments
+            .iter()
+            .position(|s| matches!(s, Segment::Task(t) if t.id == task_id))
+    }
+
+    /// Index of the phase heading governing the segment at `idx`
+    fn phase_of(&self, idx: usize) -> Option<usize> {
+        self.segments[..idx]
+            .iter()
+            .rposition(|s| matches!(s, Segment::Phase(_)))
+    }
+
+    /// Segment indices of the tasks under the phase heading at `phase_idx`
+    fn tasks_in_phase(&self, phase_idx: usize) -> Vec<usize> {
+        self.segments
+            .iter()
+            .enumerate()
+            .skip(phase_idx + 1)
+            .take_while(|(_, s)| !matches!(s, Segment::Phase(_)))
+            .filter(|(_, s)| matches!(s, Segment::Task(_)))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Where a task appended to the phase at `phase_idx` goes: after its
+    /// last task, or after the phase's preamble (`gate:` line, notes) when
+    /// it has none, so the preamble does not become the task's body
+    fn phase_end(&self, phase_idx: usize) -> usize {
+        match self.tasks_in_phase(phase_idx).last() {
+            Some(&last) => last + 1,
+            None => match self.segments.get(phase_idx + 1) {
+                Some(Segment::Text(_)) => phase_idx + 2,
+                _ => phase_idx + 1,
+            },
+        }
+    }
+
+    /// Swap a task with its neighbor in the same phase.
+    /// Returns false if the task is missing or already at that end.
+    pub fn move_task(&mut self, task_id: &str, direction: MoveDirection) -> bool {
+        let Some(idx) = self.task_index(task_id) else {
+            return false;
+        };
+        let Some(phase_idx) = self.phase_of(idx) else {
+            return false;
+        };
+        let siblings = self.tasks_in_phase(phase_idx);
+        let pos = siblings.iter().position(|&i| i == idx).unwrap_or(0);
+        let other = match direction {
+            MoveDirection::Up if pos > 0 => siblings[pos - 1],
+            MoveDirection::Down if pos + 1 < siblings.len() => siblings[pos + 1],
+            _ => return false,
+        };
+        self.segments.swap(idx, other);
+        true
+    }
+
+    /// Move a task to the end of another phase, keeping its body intact.
+    /// Returns false if the task or phase is missing, or it is already there.
+    pub fn move_task_to_phase(&mut self, task_id: &str, phase_id: &str) -> bool {
+        let Some(idx) = self.task_index(task_id) else {
+            return false;
+        };
+        let is_target =
+            |s: &Segment| matches!(s, Segment::Phase(p) if p.id.eq_ignore_ascii_case(phase_id));
+        if !self.segments.iter().any(is_target)
+            || self
+                .phase_of(idx)
+                .is_some_and(|p| is_target(&self.segments[p]))
+        {
+            return false;
+        }
+
+        let task = self.segments.remove(idx);
+        self.remove_blank_separator(idx);
+
+        let Some(phase_idx) = self.segments.iter().position(is_target) else {
+            return false;
+        };
+        let insert_at = self.phase_end(phase_idx);
+        let separator = Segment::Text(vec![self.newline.to_string()]);
+        self.segments.insert(insert_at, task);
+        self.segments.insert(insert_at, separator);
+        self.merge_text();
+        true
+    }
+
+    /// Append a new pending task to the end of a phase, written like the
+    /// phase's existing tasks (`### [ ]` heading or `- [ ]` bullet), with
+    /// `body` lines under it. Returns false if the phase is missing or the
+    /// ID is taken.
+    pub fn add_task(&mut self, phase_id: &str, task_id: &str, name: &str, body: &[String]) -> bool {
+        if self.task_index(task_id).is_some() {
+            return false;
+        }
+        let Some(phase_idx) = self
+            .segments
+            .iter()
+            .position(|s| matches!(s, Segment::Phase(p) if p.id.eq_ignore_ascii_case(phase_id)))
+        else {
+            return false;
+        };
+        let siblings = self.tasks_in_phase(phase_idx);
+        let checkbox_indent = siblings.first().and_then(|&i| match &self.segments[i] {
+            Segment::Task(t) => t.checkbox_indent(),
+            _ => None,
+        });
+        let header = match checkbox_indent {
+            Some(indent) => format!("{}- [ ] {task_id}: {name}", " ".repeat(indent)),
+            None => format!("### [ ] {task_id}: {name}"),
+        };
+        let mut task = TaskSection {
+            id: task_id.to_string(),
+            lines: vec![format!("{header}{}", self.newline)],
+        };
+        for line in body {
+            task.push_line(line, self.newline);
+        }
+
+        let insert_at = self.phase_end(phase_idx);
+        self.segments.insert(insert_at, Segment::Task(task));
+        // Bullet lists stay tight; heading tasks get a blank line between
+        if checkbox_indent.is_none() {
+            self.segments
+                .insert(insert_at, Segment::Text(vec![self.newline.to_string()]));
+        }
+        self.merge_text();
+        true
+    }
+
+    /// After removing a segment at `idx`, drop one blank-only separator next
+    /// to the gap so the source phase doesn't keep a double blank line.
+    fn remove_blank_separator(&mut self, idx: usize) {
+        let blank_only =
+            |s: &Segment| matches!(s, Segment::Text(lines) if lines.iter().all(|l| is_blank(l)));
+        if self.segments.get(idx).is_some_and(blank_only) {
+            self.segments.remove(idx);
+        } else if idx > 0 && blank_only(&self.segments[idx - 1]) {
+            self.segments.remove(idx - 1);
+        }
+        self.merge_text();
+    }
+
+    /// Merge adjacent text segments
+    fn merge_text(&mut self) {
+        let mut merged: Vec<Segment> = Vec::with_capacity(self.segments.len());
+        for segment in self.segments.drain(..) {
+            match (merged.last_mut(), segment) {
+                (Some(Segment::Text(prev)), Segment::Text(lines)) => prev.extend(lines),
+                (_, segment) => merged.push(segment),
+            }
+        }
+        self.segments = merged;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = "\
+# Plan
+
+# Phase 1: Core
+
+### [x] T1: First
+- **담당**: @backend
+- body line
+
+### [ ] T2: Second
+
+### [ ] T3: Third
+- third body
+
+---
+
+# Phase 2: UI
+
+### [ ] T4: Fourth
+";
+
+    fn task_order(doc: &TasksDoc) -> Vec<&str> {
+        doc.segments()
+            .iter()
+            .filter_map(|s| match s {
+                Segment::Task(t) => Some(t.id.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trip_is_identical() {
+        for input in [
+            PLAN,
+            "",
+            "no trailing newline",
+            "### [x] T1: Last\n- body",
+            "# Phase 1: A\r\n\r\n### [ ] T1: B\r\n- c\r\n",
+            "\u{feff}# Phase 1: A\r\n\r\n### [ ] T1: B\r\n- c",
+            "\u{feff}",
+            "# Phase 1: A\r\n\n### [ ] T1: B\n",
+            include_str!("../../tests/fixtures/sample_tasks.md"),
+        ] {
+            assert_eq!(TasksDoc::parse(input).serialize(), input);
+        }
+    }
+
+    #[test]
+    fn sections_are_split() {
+        let doc = TasksDoc::parse(PLAN);
+        assert_eq!(task_order(&doc), vec!["T1", "T2", "T3", "T4"]);
+        let t1 = doc.task("T1").unwrap();
+        assert_eq!(t1.lines.len(), 3);
+        assert!(t1.lines[2].contains("body line"));
+    }
+
+    #[test]
+    fn move_down_swaps_with_next_sibling() {
+        let mut doc = TasksDoc::parse(PLAN);
+        assert!(doc.move_task("T1", MoveDirection::Down));
+        let out = doc.serialize();
+        assert!(out.contains(
+            "# Phase 1: Core\n\n### [ ] T2: Second\n\n### [x] T1: First\n- **담당**: @backend\n- body line\n\n### [ ] T3"
+        ));
+    }
+
+    #[test]
+    fn move_stops_at_phase_edges() {
+        let mut doc = TasksDoc::parse(PLAN);
+        assert!(!doc.move_task("T1", MoveDirection::Up));
+        assert!(!doc.move_task("T3", MoveDirection::Down));
+        assert!(!doc.move_task("T4", MoveDirection::Up));
+        assert!(!doc.move_task("T9", MoveDirection::Up));
+        assert_eq!(doc.serialize(), PLAN);
+    }
+
+    #[test]
+    fn move_up_then_down_restores() {
+        let mut doc = TasksDoc::parse(PLAN);
+        doc.move_task("T3", MoveDirection::Up);
+        doc.move_task("T3", MoveDirection::Down);
+        assert_eq!(doc.serialize(), PLAN);
+    }
+
+    #[test]
+    fn move_to_other_phase_keeps_body() {
+        let mut doc = TasksDoc::parse(PLAN);
+        assert!(doc.move_task_to_phase("T1", "P2"));
+        assert_eq!(
+            doc.serialize(),
+            "\
+# Plan
+
+# Phase 1: Core
+
+### [ ] T2: Second
+
+### [ ] T3: Third
+- third body
+
+---
+
+# Phase 2: UI
+
+### [ ] T4: Fourth
+
+### [x] T1: First
+- **담당**: @backend
+- body line
+"
+        );
+    }
+
+    #[test]
+    fn move_to_empty_phase() {
+        let mut doc = TasksDoc::parse("# Phase 1: A\n\n### [ ] T1: One\n\n# Phase 2: B\n");
+        assert!(doc.move_task_to_phase("T1", "p2"));
+        assert_eq!(
+            doc.serialize(),
+            "# Phase 1: A\n\n# Phase 2: B\n\n### [ ] T1: One\n"
+        );
+    }
+
+    #[test]
+    fn move_to_same_or_missing_phase_is_noop() {
+        let mut doc = TasksDoc::parse(PLAN);
+        assert!(!doc.move_task_to_phase("T1", "P1"));
+        assert!(!doc.move_task_to_phase("T1", "P7"));
+        assert_eq!(doc.serialize(), PLAN);
+    }
+
+    #[test]
+    fn set_status_touches_only_header() {
+        let mut doc = TasksDoc::parse(PLAN);
+        assert!(doc.set_status("T2", "InProgress"));
+        assert!(!doc.set_status("T9", "x"));
+        assert_eq!(
+            doc.serialize(),
+            PLAN.replace("### [ ] T2: Second", "### [InProgress] T2: Second")
+        );
+    }
+
+    #[test]
+    fn rename_keeps_id_and_body() {
+        let mut doc = TasksDoc::parse(PLAN);
+        assert!(doc.rename_task("T1", "Renamed task"));
+        assert_eq!(
+            doc.serialize(),
+            PLAN.replace("T1: First", "T1: Renamed task")
+        );
+
+        let mut bare = TasksDoc::parse(
+            "### [ ] T1
+",
+        );
+        bare.rename_task("T1", "Named");
+        assert_eq!(
+            bare.serialize(),
+            "### [ ] T1: Named
+"
+        );
+    }
+
+    #[test]
+    fn set_agent_rewrites_existing_field() {
+        let mut doc = TasksDoc::parse(PLAN);
+        assert!(doc.set_agent("T1", "@frontend"));
+        assert_eq!(doc.serialize(), PLAN.replace("@backend", "@frontend"));
+        assert_eq!(doc.task("T1").unwrap().field("담당"), Some("@frontend"));
+        assert_eq!(
+            doc.task("T1").unwrap().status(),
+            Some(TaskStatus::Completed)
+        );
+    }
+
+    #[test]
+    fn set_field_appends_when_missing() {
+        let mut doc = TasksDoc::parse(PLAN);
+        assert!(doc.set_agent("T3", "qa"));
+        assert!(doc.set_field("T3", "blocked_by", "T1"));
+        assert!(doc.serialize().contains(
+            "### [ ] T3: Third\n- third body\n- **담당**: @qa\n- **blocked_by**: T1\n\n---"
+        ));
+    }
+
+    #[test]
+    fn field_matching_and_plain_keys() {
+        let doc = TasksDoc::parse("### [ ] T1: A\n  - blocked_by: T0\n- **스펙**: x: y\n");
+        let task = doc.task("T1").unwrap();
+        assert_eq!(task.field("blocked_by"), Some("T0"));
+        assert_eq!(task.field("스펙"), Some("x: y"));
+        assert_eq!(task.field("blocked"), None);
+        let doc2 = TasksDoc::parse("### [ ] T1: A\n- **담당:** @ops\n");
+        assert_eq!(doc2.task("T1").unwrap().field("담당"), Some("@ops"));
+
+        let mut doc = doc.clone();
+        doc.set_field("T1", "blocked_by", "T0, T2");
+        assert!(doc.serialize().contains("  - blocked_by: T0, T2\n"));
+    }
+
+    #[test]
+    fn bom_is_kept_and_first_heading_parsed() {
+        let mut doc = TasksDoc::parse("\u{feff}# Phase 1: A\r\n\r\n### [ ] T1: One\r\n");
+        assert!(matches!(&doc.segments()[0], Segment::Phase(p) if p.id == "P1"));
+        doc.set_status("T1", "x");
+        assert_eq!(
+            doc.serialize(),
+            "\u{feff}# Phase 1: A\r\n\r\n### [x] T1: One\r\n"
+        );
+    }
+
+    #[test]
+    fn mixed_endings_follow_majority() {
+        let mut doc = TasksDoc::parse("### [ ] T1: One\n- a\n- b\r\n");
+        doc.append_bullet("T1", "c");
+        assert_eq!(doc.serialize(), "### [ ] T1: One\n- a\n- b\r\n- c\n");
+    }
+
+    #[test]
+    fn attempts_counter_is_added_then_incremented() {
+        let mut doc = TasksDoc::parse(PLAN);
+        assert_eq!(doc.increment_attempts("T1"), Some(1));
+        assert_eq!(doc.increment_attempts("T1"), Some(2));
+        assert_eq!(doc.increment_attempts("T9"), None);
+        assert_eq!(
+            doc.serialize(),
+            PLAN.replace("- body line\n", "- body line\n- attempts: 2\n")
+        );
+    }
+
+    #[test]
+    fn add_task_appends_to_the_phase() {
+        let mut doc = TasksDoc::parse(PLAN);
+        let body = [
+            "- **담당**: @qa".to_string(),
+            "- [ ] happy path".to_string(),
+        ];
+        assert!(doc.add_task("p1", "T9", "Write tests", &body));
+        assert!(!doc.add_task("P1", "T9", "Again", &[]));
+        assert!(!doc.add_task("P7", "T10", "Nowhere", &[]));
+        let out = doc.serialize();
+        assert!(
+            out.contains(
+                "- third body\n\n### [ ] T9: Write tests\n- **담당**: @qa\n- [ ] happy path\n"
+            ),
+            "{out}"
+        );
+        assert_eq!(task_order(&doc), vec!["T1", "T2", "T3", "T9", "T4"]);
+        assert_eq!(TasksDoc::parse(&out), doc);
+    }
+
+    #[test]
+    fn tasks_added_to_an_empty_phase_go_below_its_gate() {
+        let mut doc =
+            TasksDoc::parse("# Phase 1: A\n\n### [ ] T1: One\n\n# Phase 2: B\ngate: P1\n");
+        assert!(doc.add_task("P2", "T2", "Two", &[]));
+        assert!(doc.move_task_to_phase("T1", "P2"));
+        let out = doc.serialize();
+        assert_eq!(
+            out,
+            "# Phase 1: A\n\n# Phase 2: B\ngate: P1\n\n### [ ] T2: Two\n\n### [ ] T1: One\n"
+        );
+        let phases = crate::data::tasks_parser::parse_tasks_md(&out).unwrap();
+        assert_eq!(phases[1].gate.as_deref(), Some("P1"));
+        assert_eq!(phases[1].tasks[0].body, "");
+    }
+
+    #[test]
+    fn add_task_follows_checkbox_style() {
+        let mut doc =
+            TasksDoc::parse("# Phase 1: A\n\n- [ ] T1: One\n  - [ ] T1.1: Sub\n\nProse\n");
+        assert!(doc.add_task("P1", "T2", "Two", &["- **담당**: @qa".to_string()]));
+        assert_eq!(
+            doc.serialize(),
+            "# Phase 1: A\n\n- [ ] T1: One\n  - [ ] T1.1: Sub\n- [ ] T2: Two\n  - **담당**: @qa\n\nProse\n"
+        );
+    }
+
+    #[test]
+    fn crlf_used_for_inserted_lines() {
+        let input =
+            "# Phase 1: A\r\n\r\n### [ ] T1: One\r\n\r\n# Phase 2: B\r\n\r\n### [ ] T2: Two\r\n";
+        let mut doc = TasksDoc::parse(input);
+        doc.move_task_to_phase("T1", "P2");
+        let out = doc.serialize();
+        assert!(!out.replace("\r\n", "").contains('\n'));
+        assert!(out.ends_with("### [ ] T2: Two\r\n\r\n### [ ] T1: One\r\n"));
+
+        let mut doc = TasksDoc::parse(input);
+        doc.append_bullet("T2", "note");
+        doc.rename_task("T2", "Renamed");
+        assert!(doc
+            .serialize()
+            .ends_with("### [ ] T2: Renamed\r\n- note\r\n"));
+    }
+
+    #[test]
+    fn checkbox_tasks_are_editable() {
+        let input = "# Phase 1: A\n\n- [ ] T1: One\n  - **담당**: @qa\n  - [ ] T1.1: Sub\n- [x] T2: Two\n\nTrailing prose\n";
+        let mut doc = TasksDoc::parse(input);
+        assert_eq!(doc.serialize(), input);
+        assert_eq!(task_order(&doc), vec!["T1", "T1.1", "T2"]);
+        assert_eq!(
+            doc.task("T2").unwrap().status(),
+            Some(TaskStatus::Completed)
+        );
+
+        assert!(doc.set_status("T1.1", "InProgress"));
+        assert_eq!(doc.increment_attempts("T1.1"), Some(1));
+        assert!(doc.rename_task("T2", "Second"));
+        assert_eq!(
+            doc.serialize(),
+            "# Phase 1: A\n\n- [ ] T1: One\n  - **담당**: @qa\n  - [InProgress] T1.1: Sub\n    - attempts: 1\n- [x] T2: Second\n\nTrailing prose\n"
+        );
+    }
+}
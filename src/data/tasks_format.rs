@@ -0,0 +1,419 @@
+//! TASKS.md model round-trip
+//!
+//! [`parse`] reads TASKS.md into phases and [`serialize`] writes phases back
+//! as canonical TASKS.md: a `# Phase N: Name` heading with an optional
+//! `gate:` line, `### [tag] ID: Name` tasks with their body under them, and
+//! `## Milestone:` headings. For any phases [`parse`] returns,
+//! `parse(&serialize(&phases))` gives the same phases back, apart from the
+//! source line numbers [`forget_lines`] clears.
+//!
+//! Editing features write through [`TasksDoc`](crate::data::tasks_doc::TasksDoc),
+//! which keeps the file's own layout. The property tests below generate
+//! plans, serialize them, apply each kind of edit the dashboard writes and
+//! check that the file reads back as exactly the intended change.
+
+use crate::data::tasks_parser::{self, ParseError, ParsedPhase, TaskStatus};
+
+/// Parse TASKS.md into phases, dropping the warnings of recovered problems
+pub fn parse(input: &str) -> Result<Vec<ParsedPhase>, ParseError> {
+    tasks_parser::parse_tasks_md(input)
+}
+
+/// Write phases as canonical TASKS.md. Phase IDs are read back as `P` plus
+/// the number in the heading, so IDs not of that form do not round-trip.
+pub fn serialize(phases: &[ParsedPhase]) -> String {
+    let mut out = String::new();
+    for phase in phases {
+        let number = phase.id.strip_prefix('P').unwrap_or(&phase.id);
+        out.push_str(&format!("# Phase {number}: {}", phase.name));
+        if let Some(status) = &phase.status {
+            out.push_str(&format!(" [{}]", status_tag(status)));
+        }
+        out.push('\n');
+        if let Some(gate) = &phase.gate {
+            out.push_str(&format!("gate: {gate}\n"));
+        }
+        for task in &phase.tasks {
+            out.push_str(&format!(
+                "\n### [{}] {}: {}\n",
+                status_tag(&task.status),
+                task.id,
+                task.name
+            ));
+            if !task.body.is_empty() {
+                out.push_str(&task.body);
+                out.push('\n');
+            }
+        }
+        for name in &phase.milestones {
+            out.push_str(&format!("\n## Milestone: {name}\n"));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// The `[..]` tag written for a status: `x`, a space, or the status name
+pub fn status_tag(status: &TaskStatus) -> &str {
+    match status {
+        TaskStatus::Pending => " ",
+        TaskStatus::Completed => "x",
+        other => other.label(),
+    }
+}
+
+/// Clear the source line numbers, which depend on the layout rather than
+/// the plan
+pub fn forget_lines(phases: &mut [ParsedPhase]) {
+    for phase in phases {
+        phase.line = None;
+        for task in &mut phase.tasks {
+            task.line = None;
+        }
+    }
+}
+
+/// Proptest strategies for plans that [`parse`] can return
+#[cfg(test)]
+pub(crate) mod arb {
+    use chrono::NaiveDate;
+    use proptest::collection::vec;
+    use proptest::option;
+    use proptest::prelude::*;
+
+    use crate::data::tasks_parser::{ParsedPhase, ParsedTask, TaskStatus};
+
+    /// Agent names, note words and the like
+    pub fn word() -> impl Strategy<Value = String> {
+        "[a-z][a-z0-9-]{0,8}"
+    }
+
+    /// Task, phase and milestone names
+    pub fn name() -> impl Strategy<Value = String> {
+        "[A-Za-z][A-Za-z0-9 ,.()-]{0,20}".prop_map(|n| n.trim_end().to_string())
+    }
+
+    /// A built-in status or a custom single-word one
+    pub fn status() -> impl Strategy<Value = TaskStatus> {
+        prop_oneof![
+            Just(TaskStatus::Pending),
+            Just(TaskStatus::InProgress),
+            Just(TaskStatus::Completed),
+            Just(TaskStatus::Failed),
+            Just(TaskStatus::Blocked),
+            "[A-Z][a-z]{2,8}"
+                .prop_filter("built-in tag", |tag| !matches!(
+                    tag.as_str(),
+                    "Failed" | "Blocked"
+                ))
+                .prop_map(TaskStatus::Custom),
+        ]
+    }
+
+    /// A `- note` body line without field syntax
+    pub fn note() -> impl Strategy<Value = String> {
+        "[a-z][a-z0-9 ,.]{0,24}".prop_map(|n| n.trim_end().to_string())
+    }
+
+    fn date() -> impl Strategy<Value = NaiveDate> {
+        (2024..2030i32, 1..=12u32, 1..=28u32)
+            .prop_map(|(y, m, d)| NaiveDate::from_ymd_opt(y, m, d).expect("valid date"))
+    }
+
+    /// A task whose body holds exactly the fields set on it, plus notes.
+    /// The ID is a placeholder; [`phases`] numbers the tasks.
+    pub fn task() -> impl Strategy<Value = ParsedTask> {
+        let fields = (
+            option::of(word()),
+            vec("T[0-9]{1,2}", 0..3),
+            0..4u32,
+            option::of(0..20u32),
+            option::of(0..5u32),
+            option::of(date()),
+            option::of("[A-Z]{2,4}-[1-9][0-9]{0,3}"),
+            option::of(1..10_000u32),
+        );
+        (name(), status(), vec(note(), 0..3), fields).prop_map(|(name, status, notes, fields)| {
+            let (agent, blocked_by, attempts, points, priority, due, jira, pr) = fields;
+            let mut body: Vec<String> = notes.iter().map(|n| format!("- {n}")).collect();
+            if let Some(agent) = &agent {
+                body.push(format!("- **담당**: @{agent}"));
+            }
+            if !blocked_by.is_empty() {
+                body.push(format!("- **blocked_by**: {}", blocked_by.join(", ")));
+            }
+            if attempts > 0 {
+                body.push(format!("- attempts: {attempts}"));
+            }
+            if let Some(points) = points {
+                body.push(format!("- **points**: {points}"));
+            }
+            if let Some(priority) = priority {
+                body.push(format!("- **priority**: {priority}"));
+            }
+            if let Some(due) = due {
+                body.push(format!("- **due**: {}", due.format("%Y-%m-%d")));
+            }
+            if let Some(jira) = &jira {
+                body.push(format!("- **jira**: {jira}"));
+            }
+            if let Some(pr) = pr {
+                body.push(format!("- **pr**: #{pr}"));
+            }
+            ParsedTask {
+                id: String::new(),
+                name,
+                status,
+                agent,
+                blocked_by,
+                attempts,
+                points,
+                priority,
+                due,
+                jira,
+                pr,
+                body: body.join("\n"),
+                line: None,
+            }
+        })
+    }
+
+    /// One to four phases `P1`.. with up to five tasks each, task IDs
+    /// unique across the plan
+    pub fn phases() -> impl Strategy<Value = Vec<ParsedPhase>> {
+        let phase = (
+            name(),
+            option::of(status()),
+            option::of(1..5u32),
+            vec(task(), 0..5),
+            vec(name(), 0..2),
+        );
+        vec(phase, 1..5).prop_map(|phases| {
+            phases
+                .into_iter()
+                .enumerate()
+                .map(|(pi, (name, status, gate, tasks, milestones))| {
+                    let number = pi + 1;
+                    ParsedPhase {
+                        id: format!("P{number}"),
+                        name,
+                        status,
+                        gate: gate.map(|g| format!("P{g}")),
+                        tasks: tasks
+                            .into_iter()
+                            .enumerate()
+                            .map(|(ti, task)| ParsedTask {
+                                id: format!("P{number}-T{}", ti + 1),
+                                ..task
+                            })
+                            .collect(),
+                        milestones,
+                        line: None,
+                    }
+                })
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::tasks_doc::{MoveDirection, TasksDoc};
+    use crate::data::tasks_parser::ParsedTask;
+    use proptest::prelude::*;
+
+    #[test]
+    fn serializes_canonical_markdown() {
+        let input = "\
+# Phase 1: Core [Blocked]
+gate: P0
+
+### [x] T1: First
+- **담당**: @backend
+
+### [Review] T2: Second
+
+## Milestone: v0.1
+
+";
+        let phases = parse(input).unwrap();
+        assert_eq!(serialize(&phases), input);
+        assert_eq!(status_tag(&TaskStatus::Pending), " ");
+        assert_eq!(status_tag(&phases[0].tasks[1].status), "Review");
+    }
+
+    /// A change the dashboard writes to TASKS.md
+    #[derive(Debug, Clone)]
+    enum Edit {
+        Status(usize, TaskStatus),
+        Rename(usize, String),
+        Agent(usize, String),
+        Points(usize, u32),
+        Retry(usize),
+        Note(usize, String),
+        Move(usize, MoveDirection),
+        ToPhase(usize, usize),
+        Add(usize, String),
+    }
+
+    fn edit() -> impl Strategy<Value = Edit> {
+        let task = any::<prop::sample::Index>();
+        prop_oneof![
+            (task.clone(), arb::status()).prop_map(|(i, s)| Edit::Status(i.index(1 << 16), s)),
+            (task.clone(), arb::name()).prop_map(|(i, n)| Edit::Rename(i.index(1 << 16), n)),
+            (task.clone(), arb::word()).prop_map(|(i, a)| Edit::Agent(i.index(1 << 16), a)),
+            (task.clone(), 0..50u32).prop_map(|(i, p)| Edit::Points(i.index(1 << 16), p)),
+            task.clone().prop_map(|i| Edit::Retry(i.index(1 << 16))),
+            (task.clone(), arb::note()).prop_map(|(i, n)| Edit::Note(i.index(1 << 16), n)),
+            (task.clone(), any::<bool>()).prop_map(|(i, up)| Edit::Move(
+                i.index(1 << 16),
+                if up {
+                    MoveDirection::Up
+                } else {
+                    MoveDirection::Down
+                }
+            )),
+            (task, 0..4usize).prop_map(|(i, p)| Edit::ToPhase(i.index(1 << 16), p)),
+            (0..4usize, arb::name()).prop_map(|(p, n)| Edit::Add(p, n)),
+        ]
+    }
+
+    /// Replace the body line starting with `prefix`, or append one
+    fn set_body_line(body: &mut String, prefix: &str, value: &str) {
+        let line = format!("{prefix} {value}");
+        let mut lines: Vec<String> = body.lines().map(String::from).collect();
+        match lines.iter_mut().find(|l| l.starts_with(prefix)) {
+            Some(existing) => *existing = line,
+            None => lines.push(line),
+        }
+        *body = lines.join("\n");
+    }
+
+    /// (phase, task) position of the `n`th task, wrapping around
+    fn nth_task(phases: &[ParsedPhase], n: usize) -> Option<(usize, usize)> {
+        let positions: Vec<_> = phases
+            .iter()
+            .enumerate()
+            .flat_map(|(pi, p)| (0..p.tasks.len()).map(move |ti| (pi, ti)))
+            .collect();
+        (!positions.is_empty()).then(|| positions[n % positions.len()])
+    }
+
+    /// Write `edit` through a [`TasksDoc`] and make the same change to `model`
+    fn apply(edit: &Edit, doc: &mut TasksDoc, model: &mut [ParsedPhase]) {
+        if let Edit::Add(p, name) = edit {
+            let phase = &mut model[p % model.len()];
+            if doc.add_task(&phase.id, "NEW-1", name, &[]) {
+                phase.tasks.push(ParsedTask {
+                    id: "NEW-1".into(),
+                    name: name.clone(),
+                    status: TaskStatus::Pending,
+                    agent: None,
+                    blocked_by: Vec::new(),
+                    attempts: 0,
+                    points: None,
+                    priority: None,
+                    due: None,
+                    jira: None,
+                    pr: None,
+                    body: String::new(),
+                    line: None,
+                });
+            }
+            return;
+        }
+        let index = match edit {
+            Edit::Status(i, _)
+            | Edit::Rename(i, _)
+            | Edit::Agent(i, _)
+            | Edit::Points(i, _)
+            | Edit::Retry(i)
+            | Edit::Note(i, _)
+            | Edit::Move(i, _)
+            | Edit::ToPhase(i, _)
+            | Edit::Add(i, _) => *i,
+        };
+        let Some((pi, ti)) = nth_task(model, index) else {
+            return;
+        };
+        let id = model[pi].tasks[ti].id.clone();
+        let task = &mut model[pi].tasks[ti];
+        match edit {
+            Edit::Status(_, status) => {
+                assert!(doc.set_status(&id, status_tag(status)));
+                task.status = status.clone();
+            }
+            Edit::Rename(_, name) => {
+                assert!(doc.rename_task(&id, name));
+                task.name = name.clone();
+            }
+            Edit::Agent(_, agent) => {
+                assert!(doc.set_agent(&id, agent));
+                set_body_line(&mut task.body, "- **담당**:", &format!("@{agent}"));
+                task.agent = Some(agent.clone());
+            }
+            Edit::Points(_, points) => {
+                assert!(doc.set_field(&id, "points", &points.to_string()));
+                set_body_line(&mut task.body, "- **points**:", &points.to_string());
+                task.points = Some(*points);
+            }
+            Edit::Retry(_) => {
+                assert_eq!(doc.increment_attempts(&id), Some(task.attempts + 1));
+                task.attempts += 1;
+                set_body_line(&mut task.body, "- attempts:", &task.attempts.to_string());
+            }
+            Edit::Note(_, note) => {
+                assert!(doc.append_bullet(&id, note));
+                if !task.body.is_empty() {
+                    task.body.push('\n');
+                }
+                task.body.push_str(&format!("- {note}"));
+            }
+            Edit::Move(_, direction) => {
+                let other = match direction {
+                    MoveDirection::Up => ti.checked_sub(1),
+                    MoveDirection::Down => Some(ti + 1).filter(|&o| o < model[pi].tasks.len()),
+                };
+                assert_eq!(doc.move_task(&id, *direction), other.is_some());
+                if let Some(other) = other {
+                    model[pi].tasks.swap(ti, other);
+                }
+            }
+            Edit::ToPhase(_, target) => {
+                let target = target % model.len();
+                let moved = doc.move_task_to_phase(&id, &model[target].id);
+                assert_eq!(moved, target != pi);
+                if moved {
+                    let task = model[pi].tasks.remove(ti);
+                    model[target].tasks.push(task);
+                }
+            }
+            Edit::Add(..) => unreachable!("handled above"),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn serialized_plans_parse_back_unchanged(phases in arb::phases()) {
+            let mut parsed = parse(&serialize(&phases)).unwrap();
+            forget_lines(&mut parsed);
+            prop_assert_eq!(parsed, phases);
+        }
+
+        #[test]
+        fn written_edits_read_back_as_the_intended_change(
+            phases in arb::phases(),
+            edits in prop::collection::vec(edit(), 1..4),
+        ) {
+            let mut doc = TasksDoc::parse(&serialize(&phases));
+            let mut model = phases;
+            for edit in &edits {
+                apply(edit, &mut doc, &mut model);
+            }
+            let mut parsed = parse(&doc.serialize()).unwrap();
+            forget_lines(&mut parsed);
+            prop_assert_eq!(parsed, model);
+        }
+    }
+}
@@ -1,11 +1,17 @@
 //! TASKS.md parser
 //!
 //! Parses TASKS.md format into structured Phase/Task data.
-//! Supports statuses: [x], [ ], [InProgress], [Failed], [Blocked]
+//! Supports statuses: [x], [ ], [InProgress], [Failed], [Blocked], plus any
+//! other single-word tag as a custom status (see `statuses`).
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::data::statuses::{self, StatusDef};
 
 use nom::{
     branch::alt,
-    bytes::complete::tag,
+    bytes::complete::{tag, take_while1},
     character::complete::{char, space0},
     combinator::map,
     sequence::delimited,
@@ -13,63 +19,165 @@ use nom::{
 };
 
 /// Task status parsed from TASKS.md
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TaskStatus {
     Pending,
     InProgress,
     Completed,
     Failed,
     Blocked,
+    /// Team-defined tag such as `[Review]`
+    Custom(String),
+}
+
+impl TaskStatus {
+    /// Human-readable name ("InProgress", "Review", ...)
+    pub fn label(&self) -> &str {
+        match self {
+            TaskStatus::Pending => "Pending",
+            TaskStatus::InProgress => "InProgress",
+            TaskStatus::Completed => "Completed",
+            TaskStatus::Failed => "Failed",
+            TaskStatus::Blocked => "Blocked",
+            TaskStatus::Custom(name) => name,
+        }
+    }
 }
 
 /// A single task parsed from TASKS.md
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsedTask {
     pub id: String,
     pub name: String,
     pub status: TaskStatus,
     pub agent: Option<String>,
     pub blocked_by: Vec<String>,
+    /// Retry attempts recorded in the body (`- attempts: N`)
+    pub attempts: u32,
+    /// Effort estimate from the body (`- **points**: 5`)
+    pub points: Option<u32>,
+    /// Dispatch priority from the body (`- **priority**: 1`); lower is sooner
+    pub priority: Option<u32>,
+    /// Due date from the body (`- **due**: 2026-03-01`)
+    pub due: Option<NaiveDate>,
+    /// Linked Jira issue key from the body (`- **jira**: PROJ-123`)
+    pub jira: Option<String>,
+    /// Linked pull request number from the body (`- **pr**: #456`)
+    pub pr: Option<u32>,
     pub body: String,
+    /// 1-based line of the task header in the source file (markdown only)
+    pub line: Option<usize>,
 }
 
 /// A phase containing multiple tasks
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsedPhase {
     pub id: String,
     pub name: String,
+    /// Phase-level status from a trailing tag (`# Phase 2: TUI [Blocked]`)
+    pub status: Option<TaskStatus>,
+    /// Phase that must be complete before this one starts (`gate: P1 complete`)
+    pub gate: Option<String>,
     pub tasks: Vec<ParsedTask>,
+    /// Milestones (`## Milestone: v0.2 cut`) that follow this phase
+    pub milestones: Vec<String>,
+    /// 1-based line of the phase heading in the source file (markdown only)
+    pub line: Option<usize>,
 }
 
 impl ParsedPhase {
     /// Calculate progress as completed / total
     pub fn progress(&self) -> f32 {
-        if self.tasks.is_empty() {
+        self.progress_with(&[])
+    }
+
+    /// Progress counting custom statuses marked `done` as completed
+    pub fn progress_with(&self, statuses: &[StatusDef]) -> f32 {
+        self.progress_weighted(statuses, ProgressWeights::default())
+    }
+
+    /// Progress with each task weighted as `weights` says
+    pub fn progress_weighted(&self, statuses: &[StatusDef], weights: ProgressWeights) -> f32 {
+        let total: u64 = self.tasks.iter().map(|t| weights.weight(t)).sum();
+        if total == 0 {
             return 0.0;
         }
-        let completed = self
+        let done: u64 = self
             .tasks
             .iter()
-            .filter(|t| t.status == TaskStatus::Completed)
-            .count();
-        completed as f32 / self.tasks.len() as f32
+            .filter(|t| statuses::is_done(&t.status, statuses))
+            .map(|t| weights.weight(t))
+            .sum();
+        done as f32 / total as f32
+    }
+
+    /// Number of tasks that count as done
+    pub fn completed_with(&self, statuses: &[StatusDef]) -> usize {
+        self.tasks
+            .iter()
+            .filter(|t| statuses::is_done(&t.status, statuses))
+            .count()
+    }
+}
+
+/// Largest `points` estimate a task can have; bigger values are clamped
+pub const MAX_POINTS: u32 = 1_000_000;
+
+/// How tasks count toward progress (`[progress]` config section)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct ProgressWeights {
+    /// Weight tasks by their `points` field instead of counting them
+    pub by_points: bool,
+    /// Weight of a task without a `points` field when `by_points` is on
+    pub default_points: u32,
+}
+
+impl Default for ProgressWeights {
+    fn default() -> Self {
+        Self {
+            by_points: false,
+            default_points: 1,
+        }
+    }
+}
+
+impl ProgressWeights {
+    /// Weight of one task; wide enough that summing a plan cannot overflow
+    pub fn weight(&self, task: &ParsedTask) -> u64 {
+        if self.by_points {
+            u64::from(task.points.unwrap_or(self.default_points))
+        } else {
+            1
+        }
     }
 }
 
 /// Parse a task status tag like [x], [ ], [InProgress], etc.
-fn parse_status(input: &str) -> IResult<&str, TaskStatus> {
-    delimited(
-        char('['),
-        alt((
-            map(tag("x"), |_| TaskStatus::Completed),
-            map(tag("InProgress"), |_| TaskStatus::InProgress),
-            map(tag("Failed"), |_| TaskStatus::Failed),
-            map(tag("Blocked"), |_| TaskStatus::Blocked),
-            map(tag("/"), |_| TaskStatus::InProgress),
-            map(space0, |_| TaskStatus::Pending),
-        )),
-        char(']'),
-    )(input)
+/// Any other single-word tag (`[Review]`) becomes a custom status.
+pub(crate) fn parse_status(input: &str) -> IResult<&str, TaskStatus> {
+    alt((
+        delimited(
+            char('['),
+            alt((
+                map(tag("x"), |_| TaskStatus::Completed),
+                map(tag("InProgress"), |_| TaskStatus::InProgress),
+                map(tag("Failed"), |_| TaskStatus::Failed),
+                map(tag("Blocked"), |_| TaskStatus::Blocked),
+                map(tag("/"), |_| TaskStatus::InProgress),
+                map(space0, |_| TaskStatus::Pending),
+            )),
+            char(']'),
+        ),
+        delimited(
+            char('['),
+            map(
+                take_while1(|c: char| c.is_alphanumeric() || c == '-' || c == '_'),
+                |name: &str| TaskStatus::Custom(name.to_string()),
+            ),
+            char(']'),
+        ),
+    ))(input)
 }
 
 /// Extract @agent-name from task body text
@@ -111,24 +219,186 @@ fn extract_blocked_by(body: &str) -> Vec<String> {
     blocked
 }
 
-/// Parse the entire TASKS.md content into phases
-pub fn parse_tasks_md(input: &str) -> Result<Vec<ParsedPhase>, String> {
+/// Extract a number from a `key: N` / `- **key**: N` body line
+fn extract_number(body: &str, key: &str) -> Option<u32> {
+    extract_text(body, key).and_then(|n| n.parse().ok())
+}
+
+/// Date from a `key: 2026-03-01` / `- **key**: 2026-03-01` body line
+fn extract_date(body: &str, key: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&extract_text(body, key)?, "%Y-%m-%d").ok()
+}
+
+/// Pull request number from a `pr: #456` line, also accepting a bare number
+/// or a pull request URL
+fn extract_pr(body: &str) -> Option<u32> {
+    let field = extract_text(body, "pr")?;
+    field
+        .trim_end_matches('/')
+        .rsplit(['/', '#'])
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Value of the first `key: value` / `- **key**: value` body line
+fn extract_text(body: &str, key: &str) -> Option<String> {
+    body.lines().find_map(|line| {
+        let stripped = line.trim().trim_start_matches("- ").replace("**", "");
+        let value = stripped.strip_prefix(key)?.strip_prefix(':')?.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// Extract the retry count from an `attempts: N` body line
+fn extract_attempts(body: &str) -> u32 {
+    extract_number(body, "attempts").unwrap_or(0)
+}
+
+/// Phase ID from a `gate: P1 complete` / `- **gate**: P1` line
+fn extract_gate(line: &str) -> Option<String> {
+    let stripped = line.trim().trim_start_matches("- ").replace("**", "");
+    let rest = stripped.strip_prefix("gate:")?;
+    let id = rest.split_whitespace().next()?;
+    Some(id.trim_end_matches(',').to_string())
+}
+
+/// Text of an H1 or H2 heading ("# Phase N: Name" / "## Phase N: Name").
+/// Any such heading ends the current task body.
+pub(crate) fn h1_h2_heading(trimmed: &str) -> Option<&str> {
+    if trimmed.starts_with("# ") && !trimmed.starts_with("## ") {
+        Some(&trimmed[2..])
+    } else if trimmed.starts_with("## ") && !trimmed.starts_with("### ") {
+        Some(&trimmed[3..])
+    } else {
+        None
+    }
+}
+
+/// UTF-8 byte order mark some Windows editors prepend
+pub(crate) const BOM: char = '\u{feff}';
+
+/// Which task line forms the parser recognizes (`[tasks] format` config)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskFormat {
+    /// Both forms; checkbox lines inside a `###` task body stay body text
+    #[default]
+    Auto,
+    /// Only `### [ ] T1: Name` headings
+    Headings,
+    /// Only `- [ ] T1: Name` bullets
+    Checkboxes,
+}
+
+/// A GitHub-style checkbox task line `- [ ] T1: Name` (`*` and `+` bullets
+/// too), as (indent, status, id, name). The ID must be a single word.
+pub(crate) fn checkbox_task(line: &str) -> Option<(usize, TaskStatus, &str, &str)> {
+    let content = line.trim_end();
+    let rest = content.trim_start();
+    let indent = indent_of(content);
+    let rest = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|bullet| rest.strip_prefix(bullet))?;
+    let (remaining, status) = parse_status(rest).ok()?;
+    let (id, name) = remaining.trim().split_once(':')?;
+    let id = id.trim();
+    if id.is_empty() || id.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((indent, status, id, name.trim()))
+}
+
+/// Width of a line's leading whitespace, in characters
+pub(crate) fn indent_of(line: &str) -> usize {
+    line.chars().take_while(|c| c.is_whitespace()).count()
+}
+
+/// A task whose body is still being collected
+struct PendingTask {
+    id: String,
+    name: String,
+    status: TaskStatus,
+    /// Indent of the bullet for checkbox tasks; None for `###` tasks
+    checkbox_indent: Option<usize>,
+    line: usize,
+}
+
+/// A problem in a tasks file, with its 1-based position.
+///
+/// Structured files fail to parse on a syntax error. The markdown parser
+/// recovers from everything else and reports it as a warning instead.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    #[error("failed to read tasks: {0}")]
+    Read(String),
+    #[error("line {line}, column {column}: {message}")]
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("line {line}, column {column}: unrecognized status tag `{tag}`")]
+    UnknownStatus {
+        line: usize,
+        column: usize,
+        tag: String,
+    },
+    #[error("line {line}, column {column}: phase heading should read `Phase N: Name`")]
+    MalformedPhase { line: usize, column: usize },
+    #[error("line {line}, column {column}: task {id} comes before any phase heading")]
+    TaskOutsidePhase {
+        line: usize,
+        column: usize,
+        id: String,
+    },
+}
+
+/// Phases parsed from a tasks file plus the problems recovered from
+#[derive(Debug, Clone, Default)]
+pub struct ParseOutcome {
+    pub phases: Vec<ParsedPhase>,
+    pub warnings: Vec<ParseError>,
+}
+
+/// ID of the phase that collects tasks found before any phase heading
+pub const UNPHASED_ID: &str = "P?";
+
+/// Status of a `### [..]` heading. A bracketed tag that is not a valid status
+/// (`[In Progress]`) is kept as a custom status and returned as `Err` so the
+/// caller can warn; the rest of the heading still parses.
+pub(crate) fn heading_status(rest: &str) -> Option<(&str, Result<TaskStatus, TaskStatus>)> {
+    if let Ok((remaining, status)) = parse_status(rest) {
+        return Some((remaining, Ok(status)));
+    }
+    let close = rest.strip_prefix('[').and(rest.find(']'))?;
+    let tag = rest[1..close].trim().to_string();
+    Some((&rest[close + 1..], Err(TaskStatus::Custom(tag))))
+}
+
+/// Parse the entire TASKS.md content into phases, recognizing both task forms
+pub fn parse_tasks_md(input: &str) -> Result<Vec<ParsedPhase>, ParseError> {
+    parse_tasks_md_with(input, TaskFormat::Auto).map(|outcome| outcome.phases)
+}
+
+/// Parse TASKS.md content, recognizing the task forms `format` allows.
+///
+/// A checkbox task's body is the lines indented deeper than its bullet;
+/// nested checkbox tasks are listed as tasks of the same phase. Malformed
+/// headings are recovered from and collected in `warnings`.
+pub fn parse_tasks_md_with(input: &str, format: TaskFormat) -> Result<ParseOutcome, ParseError> {
     let mut phases = Vec::new();
+    let mut warnings = Vec::new();
     let mut current_phase: Option<ParsedPhase> = None;
     let mut current_task_body = String::new();
-    let mut pending_task: Option<(String, String, TaskStatus)> = None;
+    let mut pending_task: Option<PendingTask> = None;
+    let input = input.strip_prefix(BOM).unwrap_or(input);
 
-    for line in input.lines() {
+    for (line_idx, line) in input.lines().enumerate() {
+        let line_no = line_idx + 1;
         let trimmed = line.trim();
 
-        // Phase heading: "# Phase N: Name" (H1) or "## Phase N: Name" (H2)
-        let phase_header = if trimmed.starts_with("# ") && !trimmed.starts_with("## ") {
-            Some(&trimmed[2..])
-        } else if trimmed.starts_with("## ") && !trimmed.starts_with("### ") {
-            Some(&trimmed[3..])
-        } else {
-            None
-        };
+        let phase_header = h1_h2_heading(trimmed);
 
         // Any H1/H2 heading ends the current task body
         if let Some(header) = phase_header {
@@ -136,12 +406,24 @@ pub fn parse_tasks_md(input: &str) -> Result<Vec<ParsedPhase>, String> {
                 &mut pending_task,
                 &mut current_task_body,
                 &mut current_phase,
+                &mut warnings,
             );
-            if let Some(phase) = parse_phase_header(header) {
+            if let Some(mut phase) = parse_phase_header(header) {
                 if let Some(prev) = current_phase.take() {
                     phases.push(prev);
                 }
+                if !header.contains(':') {
+                    warnings.push(ParseError::MalformedPhase {
+                        line: line_no,
+                        column: indent_of(line) + 1,
+                    });
+                }
+                phase.line = Some(line_no);
                 current_phase = Some(phase);
+            } else if let (Some(name), Some(phase)) =
+                (parse_milestone(header), current_phase.as_mut())
+            {
+                phase.milestones.push(name);
             }
             continue;
         }
@@ -152,19 +434,35 @@ pub fn parse_tasks_md(input: &str) -> Result<Vec<ParsedPhase>, String> {
                 &mut pending_task,
                 &mut current_task_body,
                 &mut current_phase,
+                &mut warnings,
             );
             continue;
         }
 
         // H3 heading with status: ### [status] Task-ID: Name
-        if let Some(rest) = trimmed.strip_prefix("### ") {
+        if let Some(rest) = trimmed
+            .strip_prefix("### ")
+            .filter(|_| format != TaskFormat::Checkboxes)
+        {
             flush_task(
                 &mut pending_task,
                 &mut current_task_body,
                 &mut current_phase,
+                &mut warnings,
             );
 
-            if let Ok((remaining, status)) = parse_status(rest) {
+            if let Some((remaining, status)) = heading_status(rest) {
+                let status = status.unwrap_or_else(|custom| {
+                    let TaskStatus::Custom(tag) = &custom else {
+                        unreachable!("heading_status recovers as Custom")
+                    };
+                    warnings.push(ParseError::UnknownStatus {
+                        line: line_no,
+                        column: line.find('[').map_or(1, |i| i + 1),
+                        tag: tag.clone(),
+                    });
+                    custom
+                });
                 let remaining = remaining.trim();
                 let (id, name) = if let Some(colon_pos) = remaining.find(':') {
                     let id = remaining[..colon_pos].trim().to_string();
@@ -173,15 +471,68 @@ pub fn parse_tasks_md(input: &str) -> Result<Vec<ParsedPhase>, String> {
                 } else {
                     (remaining.to_string(), remaining.to_string())
                 };
-                pending_task = Some((id, name, status));
+                pending_task = Some(PendingTask {
+                    id,
+                    name,
+                    status,
+                    checkbox_indent: None,
+                    line: line_no,
+                });
             }
             continue;
         }
 
+        // Checkbox bullet: - [status] Task-ID: Name
+        let checkbox_indent = pending_task.as_ref().map(|t| t.checkbox_indent);
+        if format != TaskFormat::Headings && checkbox_indent.map_or(true, |i| i.is_some()) {
+            if let Some((indent, status, id, name)) = checkbox_task(line) {
+                flush_task(
+                    &mut pending_task,
+                    &mut current_task_body,
+                    &mut current_phase,
+                    &mut warnings,
+                );
+                pending_task = Some(PendingTask {
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    status,
+                    checkbox_indent: Some(indent),
+                    line: line_no,
+                });
+                continue;
+            }
+            // A non-blank line back at the bullet's level ends the task
+            if let Some(Some(indent)) = checkbox_indent {
+                if !trimmed.is_empty() && indent_of(line) <= indent {
+                    flush_task(
+                        &mut pending_task,
+                        &mut current_task_body,
+                        &mut current_phase,
+                        &mut warnings,
+                    );
+                }
+            }
+        }
+
         // Accumulate body lines for current task
-        if pending_task.is_some() {
+        if let Some(task) = &pending_task {
+            let line = match task.checkbox_indent {
+                // Dedent nested bullets to the task's body level
+                Some(indent) => {
+                    let strip = indent_of(line).min(indent + 2);
+                    line.char_indices()
+                        .nth(strip)
+                        .map_or("", |(i, _)| &line[i..])
+                }
+                None => line,
+            };
             current_task_body.push_str(line);
             current_task_body.push('\n');
+        } else if let Some(ref mut phase) = current_phase {
+            // Phase preamble, before its first task
+            if phase.tasks.is_empty() && phase.gate.is_none() {
+                phase.gate = extract_gate(line);
+            }
         }
     }
 
@@ -190,55 +541,124 @@ pub fn parse_tasks_md(input: &str) -> Result<Vec<ParsedPhase>, String> {
         &mut pending_task,
         &mut current_task_body,
         &mut current_phase,
+        &mut warnings,
     );
     if let Some(phase) = current_phase.take() {
         phases.push(phase);
     }
 
-    Ok(phases)
+    Ok(ParseOutcome { phases, warnings })
 }
 
 /// Helper to flush a pending task into its phase
 fn flush_task(
-    pending_task: &mut Option<(String, String, TaskStatus)>,
+    pending_task: &mut Option<PendingTask>,
     body: &mut String,
     phase: &mut Option<ParsedPhase>,
+    warnings: &mut Vec<ParseError>,
 ) {
-    if let Some((id, name, status)) = pending_task.take() {
-        if let Some(ref mut p) = phase {
+    if let Some(PendingTask {
+        id,
+        name,
+        status,
+        line,
+        ..
+    }) = pending_task.take()
+    {
+        // Keep tasks found before any phase heading in a stand-in phase
+        let p = phase.get_or_insert_with(|| ParsedPhase {
+            id: UNPHASED_ID.to_string(),
+            name: "(no phase)".to_string(),
+            status: None,
+            gate: None,
+            tasks: Vec::new(),
+            milestones: Vec::new(),
+            line: None,
+        });
+        if p.id == UNPHASED_ID {
+            warnings.push(ParseError::TaskOutsidePhase {
+                line,
+                column: 1,
+                id: id.clone(),
+            });
+        }
+        {
             let agent = extract_agent(body);
             let blocked_by = extract_blocked_by(body);
+            let attempts = extract_attempts(body);
+            let points = extract_number(body, "points").map(|p| p.min(MAX_POINTS));
+            let priority = extract_number(body, "priority");
+            let due = extract_date(body, "due");
+            let jira = extract_text(body, "jira");
+            let pr = extract_pr(body);
             p.tasks.push(ParsedTask {
                 id,
                 name,
                 status,
                 agent,
                 blocked_by,
+                attempts,
+                points,
+                priority,
+                due,
+                jira,
+                pr,
                 body: body.trim().to_string(),
+                line: Some(line),
             });
         }
         body.clear();
     }
 }
 
+/// Milestone name from heading text like "Milestone: v0.2 cut"
+pub(crate) fn parse_milestone(header: &str) -> Option<String> {
+    let name = header.trim().strip_prefix("Milestone")?.trim_start();
+    let name = name.strip_prefix(':')?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
 /// Parse phase header text like "Phase 0: Setup"
-fn parse_phase_header(header: &str) -> Option<ParsedPhase> {
+pub(crate) fn parse_phase_header(header: &str) -> Option<ParsedPhase> {
     let header = header.trim();
     if !header.starts_with("Phase") {
         return None;
     }
 
-    let colon_pos = header.find(':')?;
-    let id_part = header[..colon_pos].trim();
-    let name_part = header[colon_pos + 1..].trim();
-
-    let phase_num = id_part.strip_prefix("Phase")?.trim();
+    let (phase_num, name_part) = match header.find(':') {
+        Some(colon_pos) => (
+            header[..colon_pos].strip_prefix("Phase")?.trim(),
+            header[colon_pos + 1..].trim(),
+        ),
+        // Recover "Phase 2 TUI Core" when the number starts with a digit
+        None => {
+            let rest = header.strip_prefix("Phase")?.trim_start();
+            let (num, name) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if !num.starts_with(|c: char| c.is_ascii_digit()) {
+                return None;
+            }
+            (num, name.trim())
+        }
+    };
     let id = format!("P{phase_num}");
 
+    // Optional trailing status tag: "TUI Core [Blocked]"
+    let (name, status) = match name_part.rfind(" [") {
+        Some(pos) if name_part.ends_with(']') => match parse_status(&name_part[pos + 1..]) {
+            Ok(("", status)) => (name_part[..pos].trim_end(), Some(status)),
+            _ => (name_part, None),
+        },
+        _ => (name_part, None),
+    };
+
     Some(ParsedPhase {
         id,
-        name: name_part.to_string(),
+        name: name.to_string(),
+        status,
+        gate: None,
         tasks: Vec::new(),
+        milestones: Vec::new(),
+        line: None,
     })
 }
 
@@ -282,6 +702,33 @@ mod tests {
         assert_eq!(s, TaskStatus::InProgress);
     }
 
+    #[test]
+    fn status_custom_word() {
+        let (_, status) = parse_status("[Review]").unwrap();
+        assert_eq!(status, TaskStatus::Custom("Review".to_string()));
+        let (_, status) = parse_status("[WontDo]").unwrap();
+        assert_eq!(status.label(), "WontDo");
+        // Built-in prefixes still win only on an exact tag
+        let (_, status) = parse_status("[xfail]").unwrap();
+        assert_eq!(status, TaskStatus::Custom("xfail".to_string()));
+        assert!(parse_status("[two words]").is_err());
+    }
+
+    #[test]
+    fn custom_done_status_counts_toward_progress() {
+        let input =
+            "# Phase 1: A\n### [x] T1: A\n### [Skipped] T2: B\n### [Review] T3: C\n### [ ] T4: D\n";
+        let phases = parse_tasks_md(input).unwrap();
+        assert_eq!(phases[0].tasks.len(), 4);
+        assert!((phases[0].progress() - 0.25).abs() < f32::EPSILON);
+        let defs = vec![StatusDef {
+            name: "Skipped".to_string(),
+            done: true,
+            ..Default::default()
+        }];
+        assert!((phases[0].progress_with(&defs) - 0.5).abs() < f32::EPSILON);
+    }
+
     #[test]
     fn agent_extraction() {
         let body = "- **담당**: @backend-specialist\n- **스펙**: something";
@@ -310,6 +757,185 @@ mod tests {
         assert!(extract_blocked_by("no deps here").is_empty());
     }
 
+    #[test]
+    fn attempts_extraction() {
+        assert_eq!(extract_attempts("- **담당**: @qa\n- attempts: 3\n"), 3);
+        assert_eq!(extract_attempts("- **attempts**: 2"), 2);
+        assert_eq!(extract_attempts("- attempts: many"), 0);
+        assert_eq!(extract_attempts(""), 0);
+    }
+
+    #[test]
+    fn checkbox_tasks_with_nesting() {
+        let input = "# Phase 1: Data\n\n- [x] T1: Parser\n  - **담당**: @backend\n  - [ ] T1.1: Edge cases\n    - blocked_by: T1\n- [/] T2: Watcher\n- [ ] no id here\n\nNotes after the list\n";
+        let phases = parse_tasks_md(input).unwrap();
+        let tasks = &phases[0].tasks;
+        let ids: Vec<_> = tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["T1", "T1.1", "T2"]);
+        assert_eq!(tasks[0].status, TaskStatus::Completed);
+        assert_eq!(tasks[0].agent.as_deref(), Some("backend"));
+        assert_eq!(tasks[1].name, "Edge cases");
+        assert_eq!(tasks[1].blocked_by, vec!["T1"]);
+        assert_eq!(tasks[1].body, "- blocked_by: T1");
+        assert_eq!(tasks[2].status, TaskStatus::InProgress);
+        assert_eq!(tasks[2].body, "");
+    }
+
+    #[test]
+    fn checkbox_tasks_indented_with_full_width_spaces() {
+        let input = "# Phase 1: A\n\u{3000}- [ ] T1: Wide\n\u{3000}\u{3000}- blocked_by: T0\n\u{3000}\u{3000}\u{3000}- nested\n- [x] T2: Next\n";
+        let phases = parse_tasks_md(input).unwrap();
+        let tasks = &phases[0].tasks;
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].blocked_by, vec!["T0"]);
+        assert_eq!(tasks[0].body, "- blocked_by: T0\n- nested");
+        assert_eq!(tasks[1].status, TaskStatus::Completed);
+    }
+
+    #[test]
+    fn task_format_selects_forms() {
+        let input =
+            "# Phase 1: A\n### [ ] T1: Heading\n- [x] AC1: checklist item\n\n- [ ] T2: Bullet\n";
+        // In a heading task body, checkbox lines stay body text
+        let auto = parse_tasks_md(input).unwrap();
+        assert_eq!(auto[0].tasks.len(), 1);
+        assert!(auto[0].tasks[0].body.contains("- [ ] T2: Bullet"));
+
+        let bullets = parse_tasks_md_with(input, TaskFormat::Checkboxes)
+            .unwrap()
+            .phases;
+        let ids: Vec<_> = bullets[0].tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["AC1", "T2"]);
+
+        let mixed = "# Phase 1: A\n- [ ] T1: Bullet\n### [x] T2: Heading\n";
+        assert_eq!(parse_tasks_md(mixed).unwrap()[0].tasks.len(), 2);
+        let headings = parse_tasks_md_with(mixed, TaskFormat::Headings)
+            .unwrap()
+            .phases;
+        assert_eq!(headings[0].tasks.len(), 1);
+        assert_eq!(headings[0].tasks[0].id, "T2");
+    }
+
+    #[test]
+    fn malformed_headings_are_recovered() {
+        let input = "### [ ] T0: Before any phase\n\
+# Phase 1 Setup\n\
+### [In Progress] T1: Unknown tag\n\
+### [x] T2: Still parsed\n\
+# Phase 2: Next\n\
+### [ ] T3: Fine\n";
+        let outcome = parse_tasks_md_with(input, TaskFormat::Auto).unwrap();
+        let ids: Vec<Vec<&str>> = outcome
+            .phases
+            .iter()
+            .map(|p| p.tasks.iter().map(|t| t.id.as_str()).collect())
+            .collect();
+        assert_eq!(ids, vec![vec!["T0"], vec!["T1", "T2"], vec!["T3"]]);
+        assert_eq!(outcome.phases[0].id, UNPHASED_ID);
+        assert_eq!(
+            outcome.phases[1].tasks[0].status,
+            TaskStatus::Custom("In Progress".to_string())
+        );
+        assert_eq!(
+            outcome.warnings,
+            vec![
+                ParseError::TaskOutsidePhase {
+                    line: 1,
+                    column: 1,
+                    id: "T0".to_string()
+                },
+                ParseError::MalformedPhase { line: 2, column: 1 },
+                ParseError::UnknownStatus {
+                    line: 3,
+                    column: 5,
+                    tag: "In Progress".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            outcome.warnings[2].to_string(),
+            "line 3, column 5: unrecognized status tag `In Progress`"
+        );
+    }
+
+    #[test]
+    fn records_source_lines() {
+        let input = "\u{feff}# Phase 1: A\r\n\r\n### [ ] T1: One\r\n- body\r\n## Phase 2: B\r\n- [x] T2: Two\r\n  - [ ] T3: Nested\r\n";
+        let phases = parse_tasks_md(input).unwrap();
+        assert_eq!(phases[0].line, Some(1));
+        assert_eq!(phases[0].tasks[0].line, Some(3));
+        assert_eq!(phases[1].line, Some(5));
+        let lines: Vec<_> = phases[1].tasks.iter().map(|t| t.line).collect();
+        assert_eq!(lines, vec![Some(6), Some(7)]);
+    }
+
+    #[test]
+    fn jira_key_from_body() {
+        let input =
+            "# Phase 1: A\n### [ ] T1: Login\n- **jira**: AUTH-42\n### [ ] T2: Logout\n- jira:\n";
+        let phases = parse_tasks_md(input).unwrap();
+        assert_eq!(phases[0].tasks[0].jira.as_deref(), Some("AUTH-42"));
+        assert_eq!(phases[0].tasks[1].jira, None);
+    }
+
+    #[test]
+    fn pr_from_body() {
+        let input = "# Phase 1: A\n### [ ] T1: Login\n- **pr**: #456\n- priority: 2\n### [ ] T2: Logout\n- pr: https://github.com/acme/app/pull/78/\n### [ ] T3: Menu\n- pr: soon\n";
+        let tasks = &parse_tasks_md(input).unwrap()[0].tasks;
+        assert_eq!(tasks[0].pr, Some(456));
+        assert_eq!(tasks[0].priority, Some(2));
+        assert_eq!(tasks[1].pr, Some(78));
+        assert_eq!(tasks[2].pr, None);
+    }
+
+    #[test]
+    fn points_weight_phase_progress() {
+        let input = "# Phase 1: A\n### [x] T1: Small\n- **points**: 1\n### [ ] T2: Giant\n- **points**: 8\n### [ ] T3: Unsized\n";
+        let phases = parse_tasks_md(input).unwrap();
+        assert_eq!(phases[0].tasks[0].points, Some(1));
+        assert_eq!(phases[0].tasks[2].points, None);
+
+        let by_points = ProgressWeights {
+            by_points: true,
+            default_points: 1,
+        };
+        assert!((phases[0].progress() - 1.0 / 3.0).abs() < f32::EPSILON);
+        assert!((phases[0].progress_weighted(&[], by_points) - 0.1).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn huge_points_are_clamped_and_summed_without_overflow() {
+        let input = "# Phase 1: A\n### [x] T1: A\n- points: 4000000000\n### [ ] T2: B\n- points: 4000000000\n";
+        let phases = parse_tasks_md(input).unwrap();
+        assert_eq!(phases[0].tasks[0].points, Some(MAX_POINTS));
+
+        let by_points = ProgressWeights {
+            by_points: true,
+            default_points: u32::MAX,
+        };
+        assert!((phases[0].progress_weighted(&[], by_points) - 0.5).abs() < f32::EPSILON);
+        let mut unsized_plan =
+            parse_tasks_md("# Phase 1: A\n### [x] T1: A\n### [ ] T2: B\n").unwrap();
+        let phase = unsized_plan.remove(0);
+        assert!((phase.progress_weighted(&[], by_points) - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn priority_from_body() {
+        let input = "# Phase 1: A\n### [ ] T1: Urgent\n- **priority**: 1\n### [ ] T2: Later\n";
+        let phases = parse_tasks_md(input).unwrap();
+        assert_eq!(phases[0].tasks[0].priority, Some(1));
+        assert_eq!(phases[0].tasks[1].priority, None);
+    }
+
+    #[test]
+    fn due_date_from_body() {
+        let input = "# Phase 1: A\n### [ ] T1: Ship\n- **due**: 2026-03-01\n### [ ] T2: Soon\n- due: next week\n";
+        let phases = parse_tasks_md(input).unwrap();
+        assert_eq!(phases[0].tasks[0].due, NaiveDate::from_ymd_opt(2026, 3, 1));
+        assert_eq!(phases[0].tasks[1].due, None);
+    }
+
     #[test]
     fn phase_header_basic() {
         let p = parse_phase_header("Phase 0: Setup").unwrap();
@@ -324,9 +950,43 @@ mod tests {
         assert_eq!(p.name, "Data Engine (리소스 모듈)");
     }
 
+    #[test]
+    fn phase_header_with_status_tag() {
+        let phase = parse_phase_header("Phase 2: TUI Core [Blocked]").unwrap();
+        assert_eq!(phase.name, "TUI Core");
+        assert_eq!(phase.status, Some(TaskStatus::Blocked));
+
+        let phase = parse_phase_header("Phase 3: Lists [a, b]").unwrap();
+        assert_eq!(phase.name, "Lists [a, b]");
+        assert_eq!(phase.status, None);
+    }
+
+    #[test]
+    fn phase_gate_from_preamble() {
+        let input = "# Phase 1: A\n### [x] T1: A\ngate: P9 ignored in task body\n\n# Phase 2: B\ngate: P1 complete\n\n### [ ] T2: B\n\n## Phase 3: C\n- **gate**: P2\n";
+        let phases = parse_tasks_md(input).unwrap();
+        assert_eq!(phases[0].gate, None);
+        assert_eq!(phases[1].gate.as_deref(), Some("P1"));
+        assert_eq!(phases[2].gate.as_deref(), Some("P2"));
+    }
+
+    #[test]
+    fn milestones_attach_to_preceding_phase() {
+        let input = "## Milestone: orphan\n# Phase 1: A\n### [x] T1: A\n- body\n\n## Milestone: v0.2 cut\n\n# Phase 2: B\n### [ ] T2: B\n## Milestone:\n";
+        let phases = parse_tasks_md(input).unwrap();
+        assert_eq!(phases.len(), 2);
+        assert_eq!(phases[0].milestones, vec!["v0.2 cut"]);
+        assert_eq!(phases[0].tasks[0].body, "- body");
+        assert!(phases[1].milestones.is_empty());
+        assert_eq!(phases[1].tasks.len(), 1);
+    }
+
     #[test]
     fn phase_header_non_phase() {
         assert!(parse_phase_header("Not a phase").is_none());
+        assert!(parse_phase_header("Phases overview").is_none());
+        let p = parse_phase_header("Phase 2 TUI Core").unwrap();
+        assert_eq!((p.id.as_str(), p.name.as_str()), ("P2", "TUI Core"));
     }
 
     #[test]
@@ -431,10 +1091,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn leading_bom_is_ignored() {
+        let phases = parse_tasks_md("\u{feff}# Phase 1: A\r\n\r\n### [x] T1: One\r\n").unwrap();
+        assert_eq!(phases.len(), 1);
+        assert_eq!(phases[0].id, "P1");
+        assert_eq!(phases[0].tasks[0].name, "One");
+    }
+
     #[test]
     fn task_without_body_has_empty_body() {
         let input = "# Phase 0: Setup\n\n### [x] T1: Done\n### [ ] T2: Pending\n";
         let phases = parse_tasks_md(input).unwrap();
         assert!(phases[0].tasks[0].body.is_empty());
     }
+
+    proptest::proptest! {
+        #[test]
+        fn junk_heading_never_drops_later_tasks(junk in "[#\\[\\]:x/ a-zA-Z0-9-]{0,24}") {
+            let input = format!("# Phase 1: A\n{junk}\n### [ ] T9: Survivor\n");
+            let outcome = parse_tasks_md_with(&input, TaskFormat::Auto).unwrap();
+            let found = outcome
+                .phases
+                .iter()
+                .flat_map(|p| &p.tasks)
+                .any(|t| t.id == "T9");
+            proptest::prop_assert!(found);
+        }
+
+        #[test]
+        fn arbitrary_input_never_panics(input in "\\PC{0,200}") {
+            let _ = parse_tasks_md(&input);
+        }
+    }
 }
@@ -0,0 +1,288 @@
+//! Task file backends
+//!
+//! A [`TasksSource`] turns the content of the tasks file into phases. TASKS.md
+//! is read by [`MarkdownSource`]; orchestrators that generate plans as data can
+//! write `tasks.json` or `tasks.toml` instead, read by [`StructuredSource`].
+//! Write-back (status changes, notes, moves) is markdown-only.
+
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::data::tasks_parser::{
+    self, parse_status, ParseError, ParseOutcome, ParsedPhase, ParsedTask, TaskFormat, TaskStatus,
+    MAX_POINTS,
+};
+
+/// Parses the content of a tasks file into phases
+pub trait TasksSource: Debug + Send + Sync {
+    fn parse(&self, content: &str) -> Result<ParseOutcome, ParseError>;
+
+    /// Whether the dashboard may write edits back to the file
+    fn writable(&self) -> bool {
+        false
+    }
+}
+
+/// TASKS.md, parsed with the nom-based markdown parser
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownSource {
+    pub format: TaskFormat,
+}
+
+impl TasksSource for MarkdownSource {
+    fn parse(&self, content: &str) -> Result<ParseOutcome, ParseError> {
+        tasks_parser::parse_tasks_md_with(content, self.format)
+    }
+
+    fn writable(&self) -> bool {
+        true
+    }
+}
+
+/// Encoding of a structured plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructuredFormat {
+    Json,
+    Toml,
+}
+
+/// `tasks.json` / `tasks.toml`: a `phases` list mirroring the markdown model
+#[derive(Debug, Clone, Copy)]
+pub struct StructuredSource {
+    pub format: StructuredFormat,
+}
+
+impl TasksSource for StructuredSource {
+    fn parse(&self, content: &str) -> Result<ParseOutcome, ParseError> {
+        let plan: PlanDoc = match self.format {
+            StructuredFormat::Json => {
+                serde_json::from_str(content).map_err(|e| ParseError::Syntax {
+                    line: e.line(),
+                    column: e.column(),
+                    message: format!("invalid tasks.json: {e}"),
+                })?
+            }
+            StructuredFormat::Toml => toml::from_str(content).map_err(|e| {
+                let (line, column) = line_column(content, e.span().map_or(0, |s| s.start));
+                ParseError::Syntax {
+                    line,
+                    column,
+                    message: format!("invalid tasks.toml: {}", e.message()),
+                }
+            })?,
+        };
+        Ok(ParseOutcome {
+            phases: plan.phases.into_iter().map(PhaseDoc::into_phase).collect(),
+            warnings: Vec::new(),
+        })
+    }
+}
+
+/// 1-based line and column of a byte offset
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Pick the backend from the file extension; anything but `.json` / `.toml`
+/// is markdown
+pub fn for_path(path: &Path, format: TaskFormat) -> Arc<dyn TasksSource> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("json") => Arc::new(StructuredSource {
+            format: StructuredFormat::Json,
+        }),
+        Some(ext) if ext.eq_ignore_ascii_case("toml") => Arc::new(StructuredSource {
+            format: StructuredFormat::Toml,
+        }),
+        _ => Arc::new(MarkdownSource { format }),
+    }
+}
+
+#[derive(Deserialize)]
+struct PlanDoc {
+    #[serde(default)]
+    phases: Vec<PhaseDoc>,
+}
+
+#[derive(Deserialize)]
+struct PhaseDoc {
+    id: String,
+    name: String,
+    status: Option<String>,
+    gate: Option<String>,
+    #[serde(default)]
+    milestones: Vec<String>,
+    #[serde(default)]
+    tasks: Vec<TaskDoc>,
+}
+
+#[derive(Deserialize)]
+struct TaskDoc {
+    id: String,
+    name: String,
+    #[serde(default)]
+    status: String,
+    agent: Option<String>,
+    #[serde(default)]
+    blocked_by: Vec<String>,
+    #[serde(default)]
+    attempts: u32,
+    points: Option<u32>,
+    priority: Option<u32>,
+    due: Option<NaiveDate>,
+    jira: Option<String>,
+    pr: Option<u32>,
+    #[serde(default)]
+    body: String,
+}
+
+impl PhaseDoc {
+    fn into_phase(self) -> ParsedPhase {
+        ParsedPhase {
+            id: self.id,
+            name: self.name,
+            status: self.status.as_deref().map(status_from_str),
+            gate: self.gate,
+            tasks: self.tasks.into_iter().map(TaskDoc::into_task).collect(),
+            milestones: self.milestones,
+            line: None,
+        }
+    }
+}
+
+impl TaskDoc {
+    fn into_task(self) -> ParsedTask {
+        ParsedTask {
+            status: status_from_str(&self.status),
+            id: self.id,
+            name: self.name,
+            agent: self.agent,
+            blocked_by: self.blocked_by,
+            attempts: self.attempts,
+            points: self.points.map(|p| p.min(MAX_POINTS)),
+            priority: self.priority,
+            due: self.due,
+            jira: self.jira,
+            pr: self.pr,
+            body: self.body,
+            line: None,
+        }
+    }
+}
+
+/// A status written either as its label ("Completed") or as the markdown
+/// tag without brackets ("x", "/", "InProgress", "Review")
+fn status_from_str(s: &str) -> TaskStatus {
+    match s.trim() {
+        "Pending" => TaskStatus::Pending,
+        "Completed" => TaskStatus::Completed,
+        tag => match parse_status(&format!("[{tag}]")) {
+            Ok(("", status)) => status,
+            _ => TaskStatus::Custom(tag.to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_plan_parses() {
+        let source = StructuredSource {
+            format: StructuredFormat::Json,
+        };
+        let phases = source
+            .parse(
+                r#"{"phases": [{"id": "P1", "name": "Data", "gate": "P0", "tasks": [
+                    {"id": "T1", "name": "Parser", "status": "x", "agent": "backend", "points": 3},
+                    {"id": "T2", "name": "Watcher", "status": "InProgress", "blocked_by": ["T1"]},
+                    {"id": "T3", "name": "Docs", "priority": 2}
+                ]}]}"#,
+            )
+            .unwrap()
+            .phases;
+        let tasks = &phases[0].tasks;
+        assert_eq!(phases[0].gate.as_deref(), Some("P0"));
+        assert_eq!(tasks[0].status, TaskStatus::Completed);
+        assert_eq!(tasks[0].points, Some(3));
+        assert_eq!(tasks[1].status, TaskStatus::InProgress);
+        assert_eq!(tasks[1].blocked_by, vec!["T1"]);
+        assert_eq!(tasks[2].status, TaskStatus::Pending);
+        assert_eq!(tasks[2].priority, Some(2));
+    }
+
+    #[test]
+    fn toml_plan_parses() {
+        let source = StructuredSource {
+            format: StructuredFormat::Toml,
+        };
+        let phases = source
+            .parse(
+                "[[phases]]\nid = \"P1\"\nname = \"Data\"\nstatus = \"Blocked\"\nmilestones = [\"v0.1\"]\n\n[[phases.tasks]]\nid = \"T1\"\nname = \"Parser\"\nstatus = \"Completed\"\n\n[[phases.tasks]]\nid = \"T2\"\nname = \"Review\"\nstatus = \"Review\"\n",
+            )
+            .unwrap()
+            .phases;
+        assert_eq!(phases[0].status, Some(TaskStatus::Blocked));
+        assert_eq!(phases[0].milestones, vec!["v0.1"]);
+        assert_eq!(phases[0].tasks[0].status, TaskStatus::Completed);
+        assert_eq!(
+            phases[0].tasks[1].status,
+            TaskStatus::Custom("Review".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_structured_plan_is_an_error() {
+        let source = StructuredSource {
+            format: StructuredFormat::Json,
+        };
+        assert!(source.parse("{\"phases\": [{\"id\": 1}]}").is_err());
+    }
+
+    #[test]
+    fn syntax_errors_carry_position() {
+        let json = StructuredSource {
+            format: StructuredFormat::Json,
+        };
+        let err = json.parse("{\"phases\": [\n  {\"id\": }\n]}").unwrap_err();
+        assert!(matches!(err, ParseError::Syntax { line: 2, .. }));
+
+        let toml = StructuredSource {
+            format: StructuredFormat::Toml,
+        };
+        let err = toml
+            .parse("[[phases]]\nid = \"P1\"\nname = \n")
+            .unwrap_err();
+        let ParseError::Syntax { line, column, .. } = err else {
+            panic!("expected a syntax error, got {err:?}");
+        };
+        assert_eq!((line, column), (3, 8));
+    }
+
+    #[test]
+    fn backend_follows_extension() {
+        let md = for_path(Path::new("TASKS.md"), TaskFormat::Auto);
+        assert!(md.writable());
+        assert_eq!(
+            md.parse("# Phase 1: A\n### [ ] T1: B\n").unwrap().phases[0]
+                .tasks
+                .len(),
+            1
+        );
+        assert!(!for_path(Path::new("plan/tasks.JSON"), TaskFormat::Auto).writable());
+        assert!(for_path(Path::new("tasks.toml"), TaskFormat::Auto)
+            .parse("")
+            .unwrap()
+            .phases
+            .is_empty());
+    }
+}
@@ -1,52 +1,85 @@
 //! TASKS.md write-back
 //!
-//! Updates task status in TASKS.md by finding and replacing status tags
-//! in task header lines.
+//! Applies edits to TASKS.md on disk: status changes, renames, body fields,
+//! notes and reordering. All of them go through [`TasksDoc`], so lines an
+//! edit does not touch are written back byte-for-byte.
 
 use std::path::Path;
 
+use crate::data::tasks_doc::{MoveDirection, TasksDoc};
+
+/// Load TASKS.md as a document, apply `edit`, and write it back if it changed anything
+fn edit_doc(path: &Path, edit: impl FnOnce(&mut TasksDoc) -> bool) -> anyhow::Result<bool> {
+    let content = std::fs::read_to_string(path)?;
+    let mut doc = TasksDoc::parse(&content);
+    if !edit(&mut doc) {
+        return Ok(false);
+    }
+    std::fs::write(path, doc.serialize())?;
+    Ok(true)
+}
+
 /// Replace a task's status in TASKS.md.
 ///
-/// Finds lines matching `### [{old_status}] {task_id}:` and replaces
-/// the status tag with `new_status`.
-///
-/// Uses line-by-line string matching (no regex) for safety.
+/// Rewrites the `[status]` tag of the `### [..] {task_id}:` header line;
+/// every other byte of the file is kept as is.
 pub fn update_task_status(path: &Path, task_id: &str, new_status: &str) -> anyhow::Result<bool> {
-    let content = std::fs::read_to_string(path)?;
-    let mut found = false;
-    let mut output = String::with_capacity(content.len());
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.starts_with("### [") && trimmed.contains(task_id) {
-            // Parse: ### [Status] TaskId: ...
-            if let Some(bracket_end) = trimmed.find("] ") {
-                let after_bracket = &trimmed[bracket_end + 2..];
-                // Check that the task_id follows immediately after `] `
-                if after_bracket.starts_with(task_id) {
-                    let prefix = &line[..line.find('[').unwrap_or(0)];
-                    let suffix = &after_bracket;
-                    output.push_str(&format!("{prefix}[{new_status}] {suffix}"));
-                    output.push('\n');
-                    found = true;
-                    continue;
-                }
-            }
-        }
-        output.push_str(line);
-        output.push('\n');
-    }
+    edit_doc(path, |doc| doc.set_status(task_id, new_status))
+}
 
-    // Preserve original trailing newline behavior
-    if !content.ends_with('\n') {
-        output.pop();
-    }
+/// Append a `- {note}` bullet to the end of a task's body in TASKS.md.
+///
+/// The bullet goes after the last non-blank body line so surrounding spacing
+/// is kept.
+pub fn append_task_note(path: &Path, task_id: &str, note: &str) -> anyhow::Result<bool> {
+    edit_doc(path, |doc| doc.append_bullet(task_id, note))
+}
 
-    if found {
-        std::fs::write(path, &output)?;
-    }
+/// Flip a task back to InProgress for a retry and bump its `- attempts: N`
+/// counter. Returns the new attempt count, or None if the task is missing.
+pub fn retry_task(path: &Path, task_id: &str) -> anyhow::Result<Option<u32>> {
+    let mut attempts = None;
+    edit_doc(path, |doc| {
+        attempts = doc.increment_attempts(task_id);
+        attempts.is_some() && doc.set_status(task_id, "InProgress")
+    })?;
+    Ok(attempts)
+}
 
-    Ok(found)
+/// Change a task's name, keeping its ID and status
+pub fn rename_task(path: &Path, task_id: &str, name: &str) -> anyhow::Result<bool> {
+    edit_doc(path, |doc| doc.rename_task(task_id, name))
+}
+
+/// Reassign a task to `agent` (`- **담당**: @agent`)
+pub fn set_task_agent(path: &Path, task_id: &str, agent: &str) -> anyhow::Result<bool> {
+    edit_doc(path, |doc| doc.set_agent(task_id, agent))
+}
+
+/// Set a `- **key**: value` field in a task body, adding it if missing
+pub fn set_task_field(path: &Path, task_id: &str, key: &str, value: &str) -> anyhow::Result<bool> {
+    edit_doc(path, |doc| doc.set_field(task_id, key, value))
+}
+
+/// Move a task one slot up or down within its phase, body included
+pub fn move_task(path: &Path, task_id: &str, direction: MoveDirection) -> anyhow::Result<bool> {
+    edit_doc(path, |doc| doc.move_task(task_id, direction))
+}
+
+/// Move a task to the end of another phase (`phase_id` like "P2"), body included
+pub fn move_task_to_phase(path: &Path, task_id: &str, phase_id: &str) -> anyhow::Result<bool> {
+    edit_doc(path, |doc| doc.move_task_to_phase(task_id, phase_id))
+}
+
+/// Append a new pending task to the end of a phase
+pub fn add_task(
+    path: &Path,
+    phase_id: &str,
+    task_id: &str,
+    name: &str,
+    body: &[String],
+) -> anyhow::Result<bool> {
+    edit_doc(path, |doc| doc.add_task(phase_id, task_id, name, body))
 }
 
 #[cfg(test)]
@@ -113,4 +146,160 @@ mod tests {
         assert!(result.contains("[x] T2: Second"));
         assert!(result.contains("- body"));
     }
+
+    #[test]
+    fn append_note_after_body() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        let content =
+            "# Phase 1\n\n### [x] T1: First\n- **담당**: @backend\n\n### [ ] T2: Second\n";
+        fs::write(&path, content).unwrap();
+
+        assert!(append_task_note(&path, "T1", "note 12:30: looks good").unwrap());
+
+        let result = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            result,
+            "# Phase 1\n\n### [x] T1: First\n- **담당**: @backend\n- note 12:30: looks good\n\n### [ ] T2: Second\n"
+        );
+    }
+
+    #[test]
+    fn append_note_to_last_task_without_body() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(&path, "### [ ] T1: Only").unwrap();
+
+        assert!(append_task_note(&path, "T1", "note 09:00: started").unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert_eq!(result, "### [ ] T1: Only\n- note 09:00: started");
+    }
+
+    #[test]
+    fn append_note_unknown_task() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(&path, "### [ ] T1: Only\n").unwrap();
+        assert!(!append_task_note(&path, "T9", "x").unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "### [ ] T1: Only\n");
+    }
+
+    #[test]
+    fn custom_status_round_trip() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(&path, "### [ ] T1: One\n").unwrap();
+
+        assert!(update_task_status(&path, "T1", "Review").unwrap());
+        assert!(update_task_status(&path, "T1", "x").unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "### [x] T1: One\n");
+    }
+
+    #[test]
+    fn status_update_matches_exact_id() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        let content = "### [ ] T10: Ten\n\n### [ ] T1: One\n";
+        fs::write(&path, content).unwrap();
+
+        assert!(update_task_status(&path, "T1", "x").unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "### [ ] T10: Ten\n\n### [x] T1: One\n"
+        );
+    }
+
+    #[test]
+    fn retry_task_counts_attempts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "### [Failed] T1: One\n- **담당**: @qa\n\n### [ ] T2: Two\n",
+        )
+        .unwrap();
+
+        assert_eq!(retry_task(&path, "T1").unwrap(), Some(1));
+        update_task_status(&path, "T1", "Failed").unwrap();
+        assert_eq!(retry_task(&path, "T1").unwrap(), Some(2));
+        assert_eq!(retry_task(&path, "T9").unwrap(), None);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "### [InProgress] T1: One\n- **담당**: @qa\n- attempts: 2\n\n### [ ] T2: Two\n"
+        );
+    }
+
+    #[test]
+    fn rename_agent_and_field_edits() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        let content = "# Phase 1: A\n\n### [ ] T1: One\n- **담당**: @backend  \n* hand-written *\n\n### [ ] T2: Two\n";
+        fs::write(&path, content).unwrap();
+
+        assert!(rename_task(&path, "T1", "First").unwrap());
+        assert!(set_task_agent(&path, "T1", "frontend").unwrap());
+        assert!(set_task_field(&path, "T2", "blocked_by", "T1").unwrap());
+        assert!(!rename_task(&path, "T9", "x").unwrap());
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Phase 1: A\n\n### [ ] T1: First\n- **담당**: @frontend\n* hand-written *\n\n### [ ] T2: Two\n- **blocked_by**: T1\n"
+        );
+    }
+
+    #[test]
+    fn status_update_keeps_crlf_and_bom() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "\u{feff}# Phase 1: A\r\n\r\n### [Failed] T1: One\r\n- body\r\n",
+        )
+        .unwrap();
+
+        assert!(update_task_status(&path, "T1", "InProgress").unwrap());
+        assert!(append_task_note(&path, "T1", "note 10:00: retried").unwrap());
+        assert_eq!(
+            fs::read(&path).unwrap(),
+            "\u{feff}# Phase 1: A\r\n\r\n### [InProgress] T1: One\r\n- body\r\n- note 10:00: retried\r\n"
+                .as_bytes()
+        );
+    }
+
+    #[test]
+    fn move_task_rewrites_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 1: A\n\n### [ ] T1: One\n- one body\n\n### [ ] T2: Two\n",
+        )
+        .unwrap();
+
+        assert!(move_task(&path, "T2", MoveDirection::Up).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Phase 1: A\n\n### [ ] T2: Two\n\n### [ ] T1: One\n- one body\n"
+        );
+        assert!(!move_task(&path, "T2", MoveDirection::Up).unwrap());
+    }
+
+    #[test]
+    fn move_task_to_phase_rewrites_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 1: A\n\n### [ ] T1: One\n- body\n\n# Phase 2: B\n\n### [ ] T2: Two\n",
+        )
+        .unwrap();
+
+        assert!(move_task_to_phase(&path, "T1", "P2").unwrap());
+        let phases =
+            crate::data::tasks_parser::parse_tasks_md(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(phases[0].tasks.is_empty());
+        let ids: Vec<_> = phases[1].tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["T2", "T1"]);
+        assert_eq!(phases[1].tasks[1].body, "- body");
+    }
 }
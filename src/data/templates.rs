@@ -0,0 +1,102 @@
+//! Task templates
+//!
+//! Recurring task shapes from `[[templates]]` in the config, picked with Tab
+//! in the quick-add modal. A template names the task from what was typed
+//! (`name = "Write tests for {input}"`), assigns a default agent and seeds
+//! the body with a checklist.
+
+use serde::Deserialize;
+
+use crate::data::tasks_doc::AGENT_FIELD;
+
+/// Placeholder in a template name replaced by the typed text
+const INPUT_PLACEHOLDER: &str = "{input}";
+
+/// One `[[templates]]` entry
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TaskTemplate {
+    /// Short name shown in the quick-add modal
+    pub label: String,
+    /// Task name pattern; `{input}` is replaced by the typed text
+    pub name: String,
+    /// Agent assigned to the new task
+    #[serde(default)]
+    pub agent: Option<String>,
+    /// Items written as `- [ ] item` under the task
+    #[serde(default)]
+    pub checklist: Vec<String>,
+}
+
+impl TaskTemplate {
+    /// Task name for the typed text. A pattern without `{input}` is used
+    /// as is.
+    pub fn task_name(&self, input: &str) -> String {
+        self.name.replace(INPUT_PLACEHOLDER, input.trim())
+    }
+
+    /// Body lines for a new task: the agent field, then the checklist
+    pub fn body(&self) -> Vec<String> {
+        self.agent
+            .iter()
+            .map(|agent| format!("- **{AGENT_FIELD}**: @{}", agent.trim_start_matches('@')))
+            .chain(self.checklist.iter().map(|item| format!("- [ ] {item}")))
+            .collect()
+    }
+}
+
+/// The template after `current` in `templates`, or `None` past the last one
+/// (`None` starts from the first)
+pub fn next<'a>(
+    templates: &'a [TaskTemplate],
+    current: Option<&TaskTemplate>,
+) -> Option<&'a TaskTemplate> {
+    let at = match current {
+        None => 0,
+        Some(current) => templates.iter().position(|t| t == current)? + 1,
+    };
+    templates.get(at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(label: &str) -> TaskTemplate {
+        TaskTemplate {
+            label: label.to_string(),
+            name: "Write tests for {input}".to_string(),
+            agent: Some("@qa".to_string()),
+            checklist: vec!["happy path".to_string(), "error cases".to_string()],
+        }
+    }
+
+    #[test]
+    fn renders_name_and_body() {
+        let tests = template("tests");
+        assert_eq!(tests.task_name(" login "), "Write tests for login");
+        assert_eq!(
+            tests.body(),
+            ["- **담당**: @qa", "- [ ] happy path", "- [ ] error cases"]
+        );
+
+        let fixed = TaskTemplate {
+            label: "release".to_string(),
+            name: "Cut the release".to_string(),
+            agent: None,
+            checklist: Vec::new(),
+        };
+        assert_eq!(fixed.task_name("ignored"), "Cut the release");
+        assert!(fixed.body().is_empty());
+    }
+
+    #[test]
+    fn next_cycles_through_and_back_to_none() {
+        let templates = [template("a"), template("b")];
+        let first = next(&templates, None);
+        assert_eq!(first.map(|t| t.label.as_str()), Some("a"));
+        let second = next(&templates, first);
+        assert_eq!(second.map(|t| t.label.as_str()), Some("b"));
+        assert_eq!(next(&templates, second), None);
+        assert_eq!(next(&[], None), None);
+    }
+}
@@ -0,0 +1,71 @@
+//! Lossy text reading
+//!
+//! Hook logs can carry binary tool output, and a stray byte can end up in a
+//! tasks file from a bad paste. Files are read as bytes and decoded one line
+//! at a time, so invalid bytes become U+FFFD instead of failing the whole
+//! read, and the lines that needed it are counted for diagnostics.
+
+use std::borrow::Cow;
+use std::path::Path;
+
+/// A file decoded as text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LossyText {
+    pub text: String,
+    /// Lines that held invalid UTF-8
+    pub lossy_lines: usize,
+}
+
+/// Decode one line, reporting whether any bytes had to be replaced
+pub fn decode_line(bytes: &[u8]) -> (Cow<'_, str>, bool) {
+    match String::from_utf8_lossy(bytes) {
+        Cow::Borrowed(s) => (Cow::Borrowed(s), false),
+        Cow::Owned(s) => (Cow::Owned(s), true),
+    }
+}
+
+/// Decode a whole buffer line by line
+pub fn decode(bytes: Vec<u8>) -> LossyText {
+    let bytes = match String::from_utf8(bytes) {
+        Ok(text) => {
+            return LossyText {
+                text,
+                lossy_lines: 0,
+            }
+        }
+        Err(e) => e.into_bytes(),
+    };
+    let mut text = String::with_capacity(bytes.len());
+    let mut lossy_lines = 0;
+    for line in bytes.split_inclusive(|&b| b == b'\n') {
+        let (decoded, lossy) = decode_line(line);
+        lossy_lines += usize::from(lossy);
+        text.push_str(&decoded);
+    }
+    LossyText { text, lossy_lines }
+}
+
+/// Read a file as text, replacing invalid UTF-8
+pub fn read_lossy(path: &Path) -> std::io::Result<LossyText> {
+    std::fs::read(path).map(decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_bytes_are_replaced_per_line() {
+        let bytes = b"# Phase 1: A\n### [ ] T1: \xff\xfe\n### [ ] T2: B\n\x80".to_vec();
+        let decoded = decode(bytes);
+        assert_eq!(
+            decoded.text,
+            "# Phase 1: A\n### [ ] T1: \u{fffd}\u{fffd}\n### [ ] T2: B\n\u{fffd}"
+        );
+        assert_eq!(decoded.lossy_lines, 2);
+
+        let clean = decode(b"plain\n".to_vec());
+        assert_eq!(clean.text, "plain\n");
+        assert_eq!(clean.lossy_lines, 0);
+    }
+}
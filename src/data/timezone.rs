@@ -0,0 +1,127 @@
+//! Display timezone
+//!
+//! Hook timestamps are stored in UTC. [`DisplayZone`] picks how they are
+//! shown: in the local timezone (the default), kept in UTC, or at a fixed
+//! offset such as `+09:00`, set with `timezone` in the `[display]` section.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, Utc};
+use serde::Deserialize;
+
+/// Timezone used to render timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum DisplayZone {
+    #[default]
+    Local,
+    Utc,
+    Fixed(FixedOffset),
+}
+
+/// A `timezone` value that is not "local", "utc" or `±HH[:MM]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneParseError(String);
+
+impl fmt::Display for ZoneParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown timezone {:?} (expected \"local\", \"utc\" or an offset like \"+09:00\")",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ZoneParseError {}
+
+impl FromStr for DisplayZone {
+    type Err = ZoneParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ZoneParseError(s.to_string());
+        match s.trim().to_ascii_lowercase().as_str() {
+            "local" => return Ok(Self::Local),
+            "utc" | "z" => return Ok(Self::Utc),
+            _ => {}
+        }
+        let trimmed = s.trim();
+        let sign = match trimmed.chars().next() {
+            Some('+') => 1,
+            Some('-') => -1,
+            _ => return Err(err()),
+        };
+        let digits = trimmed[1..].replace(':', "");
+        if digits.is_empty() || digits.len() > 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(err());
+        }
+        let (hours, minutes) = if digits.len() <= 2 {
+            (digits.as_str(), "0")
+        } else {
+            digits.split_at(digits.len() - 2)
+        };
+        let hours: i32 = hours.parse().map_err(|_| err())?;
+        let minutes: i32 = minutes.parse().map_err(|_| err())?;
+        if minutes >= 60 {
+            return Err(err());
+        }
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+            .map(Self::Fixed)
+            .ok_or_else(err)
+    }
+}
+
+impl TryFrom<String> for DisplayZone {
+    type Error = ZoneParseError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl DisplayZone {
+    /// Format a timestamp in this zone with a `strftime` pattern
+    pub fn format(&self, at: DateTime<Utc>, pattern: &str) -> String {
+        match self {
+            Self::Local => at.with_timezone(&Local).format(pattern).to_string(),
+            Self::Utc => at.format(pattern).to_string(),
+            Self::Fixed(offset) => at.with_timezone(offset).format(pattern).to_string(),
+        }
+    }
+
+    /// Wall-clock date and time of a timestamp in this zone
+    pub fn naive(&self, at: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Self::Local => at.with_timezone(&Local).naive_local(),
+            Self::Utc => at.naive_utc(),
+            Self::Fixed(offset) => at.with_timezone(offset).naive_local(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_and_offsets() {
+        assert_eq!("local".parse(), Ok(DisplayZone::Local));
+        assert_eq!("UTC".parse(), Ok(DisplayZone::Utc));
+        let kst = FixedOffset::east_opt(9 * 3600).unwrap();
+        assert_eq!("+09:00".parse(), Ok(DisplayZone::Fixed(kst)));
+        assert_eq!("+9".parse(), Ok(DisplayZone::Fixed(kst)));
+        let nst = FixedOffset::west_opt(3 * 3600 + 1800).unwrap();
+        assert_eq!("-0330".parse(), Ok(DisplayZone::Fixed(nst)));
+        assert!("Asia/Seoul".parse::<DisplayZone>().is_err());
+        assert!("+09:75".parse::<DisplayZone>().is_err());
+    }
+
+    #[test]
+    fn formats_in_zone() {
+        let at: DateTime<Utc> = "2026-02-08T23:30:00Z".parse().unwrap();
+        assert_eq!(DisplayZone::Utc.format(at, "%H:%M"), "23:30");
+        let kst: DisplayZone = "+09:00".parse().unwrap();
+        assert_eq!(kst.format(at, "%d %H:%M %Z"), "09 08:30 +09:00");
+    }
+}
@@ -2,7 +2,13 @@
 //!
 //! Watches TASKS.md and hook event directories for changes.
 //! Sends change notifications via tokio channels for the TUI to react.
+//!
+//! TASKS.md is watched through its directory, not its inode, so editors that
+//! save by writing a new file and renaming it over the old one (vim, many
+//! IDEs) keep being seen: the rename or re-create arrives as a modify, and a
+//! remove is reported so the board can tell whether the file is gone.
 
+use notify::event::{ModifyKind, RenameMode};
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use std::path::{Path, PathBuf};
 use tokio::sync::mpsc;
@@ -12,10 +18,39 @@ use tokio::sync::mpsc;
 pub enum FileChange {
     /// TASKS.md was modified
     TasksModified(PathBuf),
+    /// TASKS.md was removed or renamed away (it may come back)
+    TasksRemoved(PathBuf),
     /// A hook event file was created or modified
     HookEventModified(PathBuf),
     /// A hook event file was created (new session)
     HookEventCreated(PathBuf),
+    /// The config file was written
+    ConfigModified(PathBuf),
+}
+
+impl FileChange {
+    /// The file that changed
+    pub fn path(&self) -> &Path {
+        match self {
+            FileChange::TasksModified(path)
+            | FileChange::TasksRemoved(path)
+            | FileChange::HookEventModified(path)
+            | FileChange::HookEventCreated(path)
+            | FileChange::ConfigModified(path) => path,
+        }
+    }
+}
+
+/// Reduce a drained batch to the latest change per file, ordered by when
+/// each file last changed. Every handler re-reads the whole file, so earlier
+/// changes to the same file add nothing but another full reload.
+pub fn coalesce(changes: Vec<FileChange>) -> Vec<FileChange> {
+    let mut latest: Vec<FileChange> = Vec::with_capacity(changes.len());
+    for change in changes {
+        latest.retain(|c| c.path() != change.path());
+        latest.push(change);
+    }
+    latest
 }
 
 /// Errors from the file watcher
@@ -36,6 +71,10 @@ pub struct WatchConfig {
     pub hooks_dir: PathBuf,
     /// Optional secondary directory for dashboard JSONL events (e.g. ~/.claude/dashboard/)
     pub events_dir: Option<PathBuf>,
+    /// Optional config file to reload on change (need not exist yet)
+    pub config_path: Option<PathBuf>,
+    /// More hook/event directories (repeated `--hooks`, `[hooks] dirs`)
+    pub extra_dirs: Vec<PathBuf>,
 }
 
 impl WatchConfig {
@@ -44,6 +83,8 @@ impl WatchConfig {
             tasks_path,
             hooks_dir,
             events_dir: None,
+            config_path: None,
+            extra_dirs: Vec::new(),
         }
     }
 
@@ -53,6 +94,28 @@ impl WatchConfig {
         self
     }
 
+    /// Add another hook/event directory to watch
+    pub fn with_extra_dir(mut self, dir: PathBuf) -> Self {
+        if !self.event_dirs().any(|d| *d == dir) {
+            self.extra_dirs.push(dir);
+        }
+        self
+    }
+
+    /// Every directory hook events are read from: the hooks dir, the events
+    /// dir, then the extra dirs
+    pub fn event_dirs(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.hooks_dir)
+            .chain(&self.events_dir)
+            .chain(&self.extra_dirs)
+    }
+
+    /// Add a config file to watch
+    pub fn with_config_path(mut self, config_path: PathBuf) -> Self {
+        self.config_path = Some(config_path);
+        self
+    }
+
     /// Validate that watched paths exist (events_dir is optional)
     pub fn validate(&self) -> Result<(), WatcherError> {
         if !self.tasks_path.exists() {
@@ -74,7 +137,20 @@ fn paths_match(a: &Path, b: &Path) -> bool {
     if let (Ok(ca), Ok(cb)) = (a.canonicalize(), b.canonicalize()) {
         return ca == cb;
     }
-    false
+    // A removed file can't be canonicalized: compare names and directories
+    a.file_name().is_some()
+        && a.file_name() == b.file_name()
+        && match (parent_dir(a).canonicalize(), parent_dir(b).canonicalize()) {
+            (Ok(pa), Ok(pb)) => pa == pb,
+            _ => false,
+        }
+}
+
+/// Directory holding `path`; "." for a bare file name
+fn parent_dir(path: &Path) -> &Path {
+    path.parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."))
 }
 
 /// Check if `child` is under `parent` directory (handles symlinks)
@@ -90,6 +166,28 @@ fn is_under_dir(child: &Path, parent: &Path) -> bool {
 
 /// Classify a notify event into our FileChange type
 fn classify_event(event: &Event, config: &WatchConfig) -> Option<FileChange> {
+    match event.kind {
+        // Only TASKS.md going away matters; hook files are append-only
+        EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+            return event
+                .paths
+                .iter()
+                .find(|path| paths_match(path, &config.tasks_path))
+                .map(|path| FileChange::TasksRemoved(path.clone()));
+        }
+        // Renamed from the first path to the second
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+            let (from, to) = (&event.paths[0], &event.paths[1]);
+            if paths_match(to, &config.tasks_path) {
+                return Some(FileChange::TasksModified(to.clone()));
+            }
+            if paths_match(from, &config.tasks_path) {
+                return Some(FileChange::TasksRemoved(from.clone()));
+            }
+        }
+        _ => {}
+    }
+
     let dominated_by_modify = matches!(
         event.kind,
         EventKind::Modify(_) | EventKind::Create(_) | EventKind::Other
@@ -104,21 +202,17 @@ fn classify_event(event: &Event, config: &WatchConfig) -> Option<FileChange> {
             return Some(FileChange::TasksModified(path.clone()));
         }
 
-        if is_under_dir(path, &config.hooks_dir) {
-            if matches!(event.kind, EventKind::Create(_)) {
-                return Some(FileChange::HookEventCreated(path.clone()));
+        if let Some(ref config_path) = config.config_path {
+            if paths_match(path, config_path) {
+                return Some(FileChange::ConfigModified(path.clone()));
             }
-            return Some(FileChange::HookEventModified(path.clone()));
         }
 
-        // Also check the secondary events directory
-        if let Some(ref events_dir) = config.events_dir {
-            if is_under_dir(path, events_dir) {
-                if matches!(event.kind, EventKind::Create(_)) {
-                    return Some(FileChange::HookEventCreated(path.clone()));
-                }
-                return Some(FileChange::HookEventModified(path.clone()));
+        if config.event_dirs().any(|dir| is_under_dir(path, dir)) {
+            if matches!(event.kind, EventKind::Create(_)) {
+                return Some(FileChange::HookEventCreated(path.clone()));
             }
+            return Some(FileChange::HookEventModified(path.clone()));
         }
     }
 
@@ -137,29 +231,41 @@ pub fn start_watching(
     let watch_config = config.clone();
 
     let mut watcher = RecommendedWatcher::new(
-        move |res: Result<Event, notify::Error>| {
-            if let Ok(event) = res {
+        move |res: Result<Event, notify::Error>| match res {
+            Ok(event) => {
                 if let Some(change) = classify_event(&event, &watch_config) {
+                    tracing::debug!(?change, "file change");
                     let _ = tx.send(change);
                 }
             }
+            Err(e) => tracing::warn!("watch error: {e}"),
         },
         Config::default(),
     )?;
 
     // Watch the parent directory of TASKS.md (FSEvents on macOS needs directories)
-    let tasks_parent = config
-        .tasks_path
-        .parent()
-        .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| config.tasks_path.clone());
+    let tasks_parent = parent_dir(&config.tasks_path).to_path_buf();
     watcher.watch(&tasks_parent, RecursiveMode::NonRecursive)?;
     watcher.watch(&config.hooks_dir, RecursiveMode::Recursive)?;
 
-    // Watch the secondary events directory if it exists
-    if let Some(ref events_dir) = config.events_dir {
-        if events_dir.is_dir() {
-            let _ = watcher.watch(events_dir, RecursiveMode::Recursive);
+    // Watch the config file's directory (the file may be created later)
+    if let Some(ref config_path) = config.config_path {
+        let config_parent = parent_dir(config_path);
+        if !paths_match(config_parent, &tasks_parent) {
+            if let Err(e) = watcher.watch(config_parent, RecursiveMode::NonRecursive) {
+                tracing::warn!("cannot watch {}: {e}", config_parent.display());
+            }
+        }
+    }
+
+    // Watch the secondary events directory and extra dirs if they exist
+    for dir in config.event_dirs().skip(1) {
+        if dir.is_dir() {
+            if let Err(e) = watcher.watch(dir, RecursiveMode::Recursive) {
+                tracing::warn!("cannot watch {}: {e}", dir.display());
+            }
+        } else {
+            tracing::warn!("hook events dir {} does not exist", dir.display());
         }
     }
 
@@ -295,6 +401,120 @@ mod tests {
         assert_eq!(change, Some(FileChange::HookEventModified(hook_file)));
     }
 
+    #[test]
+    fn classify_config_modify() {
+        let tmp = TempDir::new().unwrap();
+        let config_path = tmp.path().join(".claude-board.toml");
+        let config = make_config(&tmp).with_config_path(config_path.clone());
+        let event = Event {
+            kind: EventKind::Create(CreateKind::File),
+            paths: vec![config_path.clone()],
+            attrs: Default::default(),
+        };
+        let change = classify_event(&event, &config);
+        assert_eq!(change, Some(FileChange::ConfigModified(config_path)));
+    }
+
+    #[test]
+    fn classify_extra_dir_events() {
+        let tmp = TempDir::new().unwrap();
+        let extra = tmp.path().join("team-hooks");
+        let config = make_config(&tmp)
+            .with_extra_dir(extra.clone())
+            .with_extra_dir(extra.clone());
+        assert_eq!(config.event_dirs().count(), 2);
+        let hook_file = extra.join("worker.jsonl");
+        let event = Event {
+            kind: EventKind::Modify(ModifyKind::Data(notify::event::DataChange::Content)),
+            paths: vec![hook_file.clone()],
+            attrs: Default::default(),
+        };
+        let change = classify_event(&event, &config);
+        assert_eq!(change, Some(FileChange::HookEventModified(hook_file)));
+    }
+
+    #[test]
+    fn classify_editor_save_by_rename() {
+        let tmp = TempDir::new().unwrap();
+        let config = make_config(&tmp);
+        let backup = tmp.path().join("TASKS.md~");
+        let event = |kind, paths: Vec<PathBuf>| Event {
+            kind,
+            paths,
+            attrs: Default::default(),
+        };
+
+        // vim: move the original aside, then write a fresh file
+        fs::rename(&config.tasks_path, &backup).unwrap();
+        let moved_away = event(
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)),
+            vec![config.tasks_path.clone(), backup.clone()],
+        );
+        assert_eq!(
+            classify_event(&moved_away, &config),
+            Some(FileChange::TasksRemoved(config.tasks_path.clone()))
+        );
+        let removed = event(
+            EventKind::Remove(notify::event::RemoveKind::File),
+            vec![config.tasks_path.clone()],
+        );
+        assert_eq!(
+            classify_event(&removed, &config),
+            Some(FileChange::TasksRemoved(config.tasks_path.clone()))
+        );
+
+        fs::write(&config.tasks_path, "# Phase 0: Setup").unwrap();
+        let recreated = event(
+            EventKind::Create(CreateKind::File),
+            vec![config.tasks_path.clone()],
+        );
+        assert_eq!(
+            classify_event(&recreated, &config),
+            Some(FileChange::TasksModified(config.tasks_path.clone()))
+        );
+        let renamed_over = event(
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)),
+            vec![backup, config.tasks_path.clone()],
+        );
+        assert_eq!(
+            classify_event(&renamed_over, &config),
+            Some(FileChange::TasksModified(config.tasks_path.clone()))
+        );
+    }
+
+    #[test]
+    fn removed_relative_tasks_path_still_matches() {
+        let tmp = TempDir::new().unwrap();
+        let config = make_config(&tmp);
+        let relative = WatchConfig::new(PathBuf::from("TASKS.md"), config.hooks_dir.clone());
+        let cwd_file = std::env::current_dir().unwrap().join("TASKS.md");
+        assert!(paths_match(&cwd_file, &relative.tasks_path));
+        assert_eq!(parent_dir(&relative.tasks_path), Path::new("."));
+    }
+
+    #[test]
+    fn coalesce_keeps_the_latest_change_per_file() {
+        let tasks = PathBuf::from("TASKS.md");
+        let a = PathBuf::from("hooks/a.jsonl");
+        let b = PathBuf::from("hooks/b.jsonl");
+        let changes = vec![
+            FileChange::HookEventCreated(a.clone()),
+            FileChange::TasksRemoved(tasks.clone()),
+            FileChange::HookEventModified(b.clone()),
+            FileChange::HookEventModified(a.clone()),
+            FileChange::TasksModified(tasks.clone()),
+            FileChange::HookEventModified(a.clone()),
+        ];
+        assert_eq!(
+            coalesce(changes),
+            vec![
+                FileChange::HookEventModified(b),
+                FileChange::TasksModified(tasks),
+                FileChange::HookEventModified(a),
+            ]
+        );
+    }
+
     #[test]
     fn classify_unrelated_path_ignored() {
         let tmp = TempDir::new().unwrap();
@@ -309,11 +529,13 @@ mod tests {
 
     #[test]
     fn classify_remove_event_ignored() {
+        // Removing TASKS.md is reported (see classify_editor_save_by_rename);
+        // removing a hook file is not
         let tmp = TempDir::new().unwrap();
         let config = make_config(&tmp);
         let event = Event {
             kind: EventKind::Remove(notify::event::RemoveKind::File),
-            paths: vec![config.tasks_path.clone()],
+            paths: vec![config.hooks_dir.join("session.jsonl")],
             attrs: Default::default(),
         };
         assert!(classify_event(&event, &config).is_none());
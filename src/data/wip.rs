@@ -0,0 +1,154 @@
+//! Work-in-progress limits
+//!
+//! Counts InProgress tasks globally and per agent against the `[wip]` config
+//! section. Tasks beyond a limit (in TASKS.md order) are reported as excess so
+//! the UI can highlight them.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Deserialize;
+
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::TaskStatus;
+
+/// Limits loaded from the `[wip]` config section (unset = unlimited)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct WipLimits {
+    /// Maximum InProgress tasks across the whole plan
+    pub max: Option<usize>,
+    /// Maximum InProgress tasks per agent
+    pub per_agent: Option<usize>,
+}
+
+/// Result of checking the current plan against the limits
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WipReport {
+    pub in_progress: usize,
+    pub max: Option<usize>,
+    /// Agents over their limit with their InProgress count, sorted by name
+    pub agents_over: Vec<(String, usize)>,
+    /// Task IDs past a limit
+    pub excess: HashSet<String>,
+}
+
+impl WipReport {
+    pub fn over_global(&self) -> bool {
+        self.max.is_some_and(|m| self.in_progress > m)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.over_global() || !self.agents_over.is_empty()
+    }
+}
+
+/// Check InProgress tasks against the limits.
+///
+/// A task's agent is its `@agent` from TASKS.md, falling back to the agent
+/// seen working on it in hook events.
+pub fn evaluate(state: &DashboardState, limits: &WipLimits) -> WipReport {
+    let mut report = WipReport {
+        max: limits.max,
+        ..Default::default()
+    };
+    let mut per_agent: HashMap<String, usize> = HashMap::new();
+
+    let active = state
+        .phases
+        .iter()
+        .flat_map(|p| &p.tasks)
+        .filter(|t| t.status == TaskStatus::InProgress);
+
+    for task in active {
+        report.in_progress += 1;
+        if limits.max.is_some_and(|m| report.in_progress > m) {
+            report.excess.insert(task.id.clone());
+        }
+
+        let agent = task
+            .agent
+            .as_deref()
+            .or_else(|| state.agent_for_task(&task.id));
+        if let (Some(agent), Some(limit)) = (agent, limits.per_agent) {
+            let count = per_agent.entry(agent.to_string()).or_default();
+            *count += 1;
+            if *count > limit {
+                report.excess.insert(task.id.clone());
+            }
+        }
+    }
+
+    if let Some(limit) = limits.per_agent {
+        report.agents_over = per_agent
+            .into_iter()
+            .filter(|(_, count)| *count > limit)
+            .collect();
+        report.agents_over.sort();
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = "\
+# Phase 1: Core
+
+### [InProgress] T1: One
+- **담당**: @backend
+
+### [InProgress] T2: Two
+- **담당**: @backend
+
+### [InProgress] T3: Three
+- **담당**: @frontend
+
+### [ ] T4: Four
+- **담당**: @backend
+";
+
+    fn state() -> DashboardState {
+        DashboardState::from_tasks_content(PLAN).unwrap()
+    }
+
+    #[test]
+    fn unlimited_by_default() {
+        let report = evaluate(&state(), &WipLimits::default());
+        assert_eq!(report.in_progress, 3);
+        assert!(!report.is_over());
+        assert!(report.excess.is_empty());
+    }
+
+    #[test]
+    fn global_limit_marks_later_tasks() {
+        let limits = WipLimits {
+            max: Some(2),
+            per_agent: None,
+        };
+        let report = evaluate(&state(), &limits);
+        assert!(report.over_global());
+        assert_eq!(report.excess, HashSet::from(["T3".to_string()]));
+    }
+
+    #[test]
+    fn per_agent_limit() {
+        let limits = WipLimits {
+            max: None,
+            per_agent: Some(1),
+        };
+        let report = evaluate(&state(), &limits);
+        assert!(!report.over_global());
+        assert_eq!(report.agents_over, vec![("backend".to_string(), 2)]);
+        assert_eq!(report.excess, HashSet::from(["T2".to_string()]));
+    }
+
+    #[test]
+    fn within_limits() {
+        let limits = WipLimits {
+            max: Some(3),
+            per_agent: Some(2),
+        };
+        assert!(!evaluate(&state(), &limits).is_over());
+    }
+}
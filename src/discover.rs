@@ -0,0 +1,95 @@
+//! Project discovery
+//!
+//! Like git, the board looks for its files in the working directory and then
+//! in each parent, so it can be started from any subdirectory of a repo. The
+//! walk stops at the first directory holding `.git`, so a stray TASKS.md
+//! above the repo is never picked up.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::CONFIG_FILE_NAME;
+
+/// Tasks files in order of preference within one directory
+pub const TASKS_CANDIDATES: &[&str] = &[
+    "TASKS.md",
+    "docs/planning/06-tasks.md",
+    "tasks.json",
+    "tasks.toml",
+];
+
+/// Project-local hooks directory
+pub const HOOKS_DIR: &str = ".claude/hooks";
+
+/// First of `candidates` that exists in `start` or the nearest parent,
+/// checking every candidate in a directory before moving up
+pub fn find_upwards(start: &Path, candidates: &[&str]) -> Option<PathBuf> {
+    for dir in start.ancestors() {
+        if let Some(found) = candidates
+            .iter()
+            .map(|c| dir.join(c))
+            .find(|path| path.exists())
+        {
+            return Some(found);
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// Nearest tasks file at or above `start`
+pub fn tasks_file(start: &Path) -> Option<PathBuf> {
+    find_upwards(start, TASKS_CANDIDATES)
+}
+
+/// Nearest `.claude-board.toml` at or above `start`
+pub fn config_file(start: &Path) -> Option<PathBuf> {
+    find_upwards(start, &[CONFIG_FILE_NAME])
+}
+
+/// Nearest `.claude/hooks` directory at or above `start`
+pub fn hooks_dir(start: &Path) -> Option<PathBuf> {
+    find_upwards(start, &[HOOKS_DIR]).filter(|path| path.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_nearest_file_walking_up() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        let sub = root.join("crates").join("core").join("src");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::create_dir_all(root.join("docs/planning")).unwrap();
+        std::fs::write(root.join("docs/planning/06-tasks.md"), "").unwrap();
+        std::fs::write(root.join(CONFIG_FILE_NAME), "").unwrap();
+
+        assert_eq!(
+            tasks_file(&sub),
+            Some(root.join("docs/planning/06-tasks.md"))
+        );
+        assert_eq!(config_file(&sub), Some(root.join(CONFIG_FILE_NAME)));
+
+        // A closer file wins; TASKS.md beats the fallbacks in the same directory
+        std::fs::write(root.join("crates").join("TASKS.md"), "").unwrap();
+        assert_eq!(tasks_file(&sub), Some(root.join("crates").join("TASKS.md")));
+        assert!(hooks_dir(&sub).is_none());
+    }
+
+    #[test]
+    fn stops_at_the_repo_root() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        let sub = repo.join("src");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::create_dir(repo.join(".git")).unwrap();
+        std::fs::write(tmp.path().join("TASKS.md"), "").unwrap();
+
+        assert!(tasks_file(&sub).is_none());
+        std::fs::create_dir_all(repo.join(HOOKS_DIR)).unwrap();
+        assert_eq!(hooks_dir(&sub), Some(repo.join(HOOKS_DIR)));
+    }
+}
@@ -50,31 +50,114 @@ pub enum Action {
     ToggleHelp,
     ToggleCollapse,
     ToggleView,
+    ToggleLayout,
+    CycleLineNumbers,
     RetryRequest,
+    ShowError,
+    JumpToContext,
+    DismissBanner,
+    Approve,
+    Bounce,
+    AddNote,
+    MoveTaskUp,
+    MoveTaskDown,
+    MoveToPhase,
+    StopAgent,
+    ScrubBack,
+    ScrubForward,
+    ToggleDebug,
+    ToggleLog,
+    CopyContext,
+    CopyTable,
+    SendPrompt,
+    ExportTrace,
+    ToggleCosts,
+    ToggleRoster,
+    QuickAdd,
+    FollowLink,
+    NextUp,
     Confirm,
     Cancel,
     None,
 }
 
 /// Convert a key event into an action
-/// Supports Korean IME fallback: ㅂ=q, ㅓ=j, ㅏ=k
+/// Supports Korean IME fallback: ㅂ=q, ㅓ=j, ㅏ=k, ㅠ=b, ㅁ=a, ㅇ=d, ㅡ=m, ㅔ=p, ㄴ=s, ㅕ=u, ㅊ=c, ㅐ=o, ㅅ=t, ㄷ=e, ㅎ=g
 pub fn key_to_action(key: KeyEvent) -> Action {
     match key.code {
         KeyCode::Char('q' | 'ㅂ') | KeyCode::Esc => Action::Quit,
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+        KeyCode::Char('j' | 'ㅓ') | KeyCode::Down if key.modifiers.contains(KeyModifiers::ALT) => {
+            Action::MoveTaskDown
+        }
+        KeyCode::Char('k' | 'ㅏ') | KeyCode::Up if key.modifiers.contains(KeyModifiers::ALT) => {
+            Action::MoveTaskUp
+        }
         KeyCode::Char('j' | 'ㅓ') | KeyCode::Down => Action::MoveDown,
         KeyCode::Char('k' | 'ㅏ') | KeyCode::Up => Action::MoveUp,
         KeyCode::Tab => Action::ToggleFocus,
         KeyCode::Char('?') => Action::ToggleHelp,
         KeyCode::Char(' ') => Action::ToggleCollapse,
         KeyCode::Char('v' | 'ㅍ') => Action::ToggleView,
+        KeyCode::Char('o' | 'ㅐ') => Action::ToggleLayout,
+        KeyCode::Char('#') => Action::CycleLineNumbers,
         KeyCode::Char('r' | 'ㄱ') => Action::RetryRequest,
+        KeyCode::Char('e' | 'ㄷ') => Action::ShowError,
+        KeyCode::Char('g' | 'ㅎ') => Action::JumpToContext,
+        KeyCode::Char('b' | 'ㅠ') => Action::DismissBanner,
+        KeyCode::Char('a' | 'ㅁ') => Action::Approve,
+        KeyCode::Char('d' | 'ㅇ') => Action::Bounce,
+        KeyCode::Char('m' | 'ㅡ') => Action::AddNote,
+        KeyCode::Char('p' | 'ㅔ') => Action::MoveToPhase,
+        KeyCode::Char('s' | 'ㄴ') => Action::StopAgent,
+        KeyCode::Char('u' | 'ㅕ') => Action::NextUp,
+        KeyCode::Left => Action::ScrubBack,
+        KeyCode::Right => Action::ScrubForward,
+        KeyCode::F(12) => Action::ToggleDebug,
+        KeyCode::Char('L') => Action::ToggleLog,
+        KeyCode::Char('c' | 'ㅊ') => Action::CopyContext,
+        KeyCode::Char('G') => Action::CopyTable,
+        KeyCode::Char('P') => Action::SendPrompt,
+        KeyCode::Char('O') => Action::ExportTrace,
+        KeyCode::Char('$') => Action::ToggleCosts,
+        KeyCode::Char('w' | 'ㅈ') => Action::ToggleRoster,
+        KeyCode::Char('t' | 'ㅅ') => Action::QuickAdd,
+        KeyCode::Enter => Action::FollowLink,
         KeyCode::Char('y') => Action::Confirm,
         KeyCode::Char('n') => Action::Cancel,
         _ => Action::None,
     }
 }
 
+/// Key meaning while a text input modal is open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKey {
+    Char(char),
+    Backspace,
+    Left,
+    Right,
+    /// Next choice, where the modal has any
+    Tab,
+    Submit,
+    Cancel,
+    None,
+}
+
+/// Convert a key event into a text input edit (all printable chars are text)
+pub fn key_to_input(key: KeyEvent) -> InputKey {
+    match key.code {
+        KeyCode::Enter => InputKey::Submit,
+        KeyCode::Esc => InputKey::Cancel,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => InputKey::Cancel,
+        KeyCode::Backspace => InputKey::Backspace,
+        KeyCode::Left => InputKey::Left,
+        KeyCode::Right => InputKey::Right,
+        KeyCode::Tab => InputKey::Tab,
+        KeyCode::Char(c) => InputKey::Char(c),
+        _ => InputKey::None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +244,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hash_cycles_line_numbers() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('#'), KeyModifiers::SHIFT)),
+            Action::CycleLineNumbers
+        );
+    }
+
     #[test]
     fn retry_request_on_r() {
         assert_eq!(
@@ -177,6 +268,246 @@ mod tests {
         );
     }
 
+    #[test]
+    fn dismiss_banner_on_b() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('b'), KeyModifiers::NONE)),
+            Action::DismissBanner
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅠ'), KeyModifiers::NONE)),
+            Action::DismissBanner
+        );
+    }
+
+    #[test]
+    fn approve_and_bounce_keys() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('a'), KeyModifiers::NONE)),
+            Action::Approve
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅇ'), KeyModifiers::NONE)),
+            Action::Bounce
+        );
+    }
+
+    #[test]
+    fn add_note_on_m() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('m'), KeyModifiers::NONE)),
+            Action::AddNote
+        );
+    }
+
+    #[test]
+    fn input_keys_are_text() {
+        assert_eq!(
+            key_to_input(make_key(KeyCode::Char('q'), KeyModifiers::NONE)),
+            InputKey::Char('q')
+        );
+        assert_eq!(
+            key_to_input(make_key(KeyCode::Enter, KeyModifiers::NONE)),
+            InputKey::Submit
+        );
+        assert_eq!(
+            key_to_input(make_key(KeyCode::Esc, KeyModifiers::NONE)),
+            InputKey::Cancel
+        );
+        assert_eq!(
+            key_to_input(make_key(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            InputKey::Cancel
+        );
+        assert_eq!(
+            key_to_input(make_key(KeyCode::Tab, KeyModifiers::NONE)),
+            InputKey::Tab
+        );
+    }
+
+    #[test]
+    fn alt_j_k_move_task() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('j'), KeyModifiers::ALT)),
+            Action::MoveTaskDown
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Up, KeyModifiers::ALT)),
+            Action::MoveTaskUp
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('p'), KeyModifiers::NONE)),
+            Action::MoveToPhase
+        );
+    }
+
+    #[test]
+    fn stop_agent_on_s() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('s'), KeyModifiers::NONE)),
+            Action::StopAgent
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㄴ'), KeyModifiers::NONE)),
+            Action::StopAgent
+        );
+    }
+
+    #[test]
+    fn next_up_on_u() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('u'), KeyModifiers::NONE)),
+            Action::NextUp
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅕ'), KeyModifiers::NONE)),
+            Action::NextUp
+        );
+    }
+
+    #[test]
+    fn show_error_on_e() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('e'), KeyModifiers::NONE)),
+            Action::ShowError
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㄷ'), KeyModifiers::NONE)),
+            Action::ShowError
+        );
+    }
+
+    #[test]
+    fn jump_to_context_on_g() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('g'), KeyModifiers::NONE)),
+            Action::JumpToContext
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅎ'), KeyModifiers::NONE)),
+            Action::JumpToContext
+        );
+    }
+
+    #[test]
+    fn scrub_on_left_right() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Left, KeyModifiers::NONE)),
+            Action::ScrubBack
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Right, KeyModifiers::NONE)),
+            Action::ScrubForward
+        );
+    }
+
+    #[test]
+    fn debug_on_f12() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::F(12), KeyModifiers::NONE)),
+            Action::ToggleDebug
+        );
+    }
+
+    #[test]
+    fn log_viewer_on_shift_l() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('L'), KeyModifiers::SHIFT)),
+            Action::ToggleLog
+        );
+    }
+
+    #[test]
+    fn copy_context_on_c_but_ctrl_c_quits() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('c'), KeyModifiers::NONE)),
+            Action::CopyContext
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅊ'), KeyModifiers::NONE)),
+            Action::CopyContext
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Action::Quit
+        );
+    }
+
+    #[test]
+    fn send_prompt_on_shift_p() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('P'), KeyModifiers::SHIFT)),
+            Action::SendPrompt
+        );
+    }
+
+    #[test]
+    fn export_trace_on_shift_o() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('O'), KeyModifiers::SHIFT)),
+            Action::ExportTrace
+        );
+    }
+
+    #[test]
+    fn copy_table_on_shift_g() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('G'), KeyModifiers::SHIFT)),
+            Action::CopyTable
+        );
+    }
+
+    #[test]
+    fn layout_on_o() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('o'), KeyModifiers::NONE)),
+            Action::ToggleLayout
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅐ'), KeyModifiers::NONE)),
+            Action::ToggleLayout
+        );
+    }
+
+    #[test]
+    fn costs_on_dollar() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('$'), KeyModifiers::SHIFT)),
+            Action::ToggleCosts
+        );
+    }
+
+    #[test]
+    fn roster_on_w() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('w'), KeyModifiers::NONE)),
+            Action::ToggleRoster
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅈ'), KeyModifiers::NONE)),
+            Action::ToggleRoster
+        );
+    }
+
+    #[test]
+    fn quick_add_on_t() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('t'), KeyModifiers::NONE)),
+            Action::QuickAdd
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅅ'), KeyModifiers::NONE)),
+            Action::QuickAdd
+        );
+    }
+
+    #[test]
+    fn follow_link_on_enter() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Enter, KeyModifiers::NONE)),
+            Action::FollowLink
+        );
+    }
+
     #[test]
     fn confirm_on_y() {
         assert_eq!(
@@ -0,0 +1,378 @@
+//! GitHub pull request status
+//!
+//! A task names its pull request with a `- **pr**: #456` body line; without
+//! one, the board looks for a pull request whose branch or title mentions the
+//! task ID. The state and check results show in the detail panel, and tasks
+//! whose checks fail get a badge in the task list. Pull requests are listed
+//! with the `gh` CLI on a background thread, so it uses whatever login and
+//! repository `gh` already resolves.
+
+use std::collections::HashSet;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::data::tasks_parser::ParsedPhase;
+
+/// Fields requested from `gh pr list` / `gh pr view`
+const FIELDS: &str = "number,title,state,headRefName,statusCheckRollup";
+
+/// `[github]` section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct GithubConfig {
+    pub enabled: bool,
+    /// `OWNER/REPO`; empty uses the repository of the working directory
+    pub repo: String,
+    /// Match tasks without a `pr` field by branch name and title
+    pub discover: bool,
+    /// Most recent pull requests searched when discovering
+    pub limit: u32,
+    /// Re-list pull requests this often
+    pub refresh_secs: u64,
+}
+
+impl Default for GithubConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            repo: String::new(),
+            discover: true,
+            limit: 100,
+            refresh_secs: 120,
+        }
+    }
+}
+
+/// Combined result of a pull request's checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Checks {
+    /// No checks reported
+    None,
+    Pending,
+    Passing,
+    Failing,
+}
+
+impl Checks {
+    pub fn label(self) -> &'static str {
+        match self {
+            Checks::None => "no checks",
+            Checks::Pending => "checks pending",
+            Checks::Passing => "checks passing",
+            Checks::Failing => "checks failing",
+        }
+    }
+}
+
+/// What the board shows of a pull request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequest {
+    pub number: u32,
+    pub title: String,
+    /// `OPEN`, `CLOSED` or `MERGED`
+    pub state: String,
+    pub branch: String,
+    pub checks: Checks,
+}
+
+impl PullRequest {
+    /// Open with failing checks
+    pub fn is_red(&self) -> bool {
+        self.state == "OPEN" && self.checks == Checks::Failing
+    }
+}
+
+/// Fold a `statusCheckRollup` array: any failure wins, then anything
+/// still running
+fn rollup(checks: &[Value]) -> Checks {
+    let mut result = Checks::None;
+    for check in checks {
+        let field = |name: &str| check.get(name).and_then(Value::as_str).unwrap_or("");
+        // Check runs carry status + conclusion, commit statuses a state
+        let outcome = match field("state") {
+            "" if field("status") != "COMPLETED" => "PENDING",
+            "" => field("conclusion"),
+            state => state,
+        };
+        match outcome {
+            "FAILURE" | "ERROR" | "TIMED_OUT" | "CANCELLED" | "ACTION_REQUIRED"
+            | "STARTUP_FAILURE" => return Checks::Failing,
+            "PENDING" | "EXPECTED" | "QUEUED" | "IN_PROGRESS" => result = Checks::Pending,
+            _ if result == Checks::None => result = Checks::Passing,
+            _ => {}
+        }
+    }
+    result
+}
+
+fn parse_pull(value: &Value) -> Option<PullRequest> {
+    Some(PullRequest {
+        number: u32::try_from(value.get("number")?.as_u64()?).ok()?,
+        title: value.get("title")?.as_str()?.to_string(),
+        state: value.get("state")?.as_str()?.to_string(),
+        branch: value
+            .get("headRefName")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        checks: rollup(
+            value
+                .get("statusCheckRollup")
+                .and_then(Value::as_array)
+                .map_or(&[], Vec::as_slice),
+        ),
+    })
+}
+
+/// Read pull requests from `gh pr list --json` (an array) or
+/// `gh pr view --json` (one object)
+pub fn parse_pulls(json: &str) -> Vec<PullRequest> {
+    match serde_json::from_str::<Value>(json) {
+        Ok(Value::Array(items)) => items.iter().filter_map(parse_pull).collect(),
+        Ok(item) => parse_pull(&item).into_iter().collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Whether `text` mentions `task_id` on its own, ignoring case: `T1.1`
+/// matches `feat/t1.1-login` but not `T1.10` or `XT1.1`
+pub fn mentions(text: &str, task_id: &str) -> bool {
+    let text = text.to_ascii_lowercase();
+    let id = task_id.to_ascii_lowercase();
+    if id.is_empty() {
+        return false;
+    }
+    let bytes = text.as_bytes();
+    text.match_indices(&id).any(|(at, _)| {
+        let before = at.checked_sub(1).map(|i| bytes[i]);
+        let end = at + id.len();
+        let after = bytes.get(end).copied();
+        let continues = |b: u8| b.is_ascii_alphanumeric();
+        // `T1.1` inside `T1.1.2` names a different task
+        let sub_id = after == Some(b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit);
+        !(before.is_some_and(continues) || after.is_some_and(continues) || sub_id)
+    })
+}
+
+/// Run `gh` (blocking) and return its output
+fn gh(config: &GithubConfig, args: &[&str]) -> Result<String, String> {
+    let mut cmd = Command::new("gh");
+    cmd.args(args).args(["--json", FIELDS]);
+    if !config.repo.is_empty() {
+        cmd.args(["--repo", &config.repo]);
+    }
+    let output = cmd
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("failed to run gh: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("gh {}: {}", args.join(" "), stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Recent pull requests plus the named ones not among them (blocking)
+pub fn fetch_pulls(config: &GithubConfig, numbers: &[u32]) -> Result<Vec<PullRequest>, String> {
+    let mut pulls = if config.discover {
+        let limit = config.limit.to_string();
+        parse_pulls(&gh(
+            config,
+            &["pr", "list", "--state", "all", "--limit", &limit],
+        )?)
+    } else {
+        Vec::new()
+    };
+    for &number in numbers {
+        if pulls.iter().any(|p| p.number == number) {
+            continue;
+        }
+        match gh(config, &["pr", "view", &number.to_string()]) {
+            Ok(json) => pulls.extend(parse_pulls(&json)),
+            Err(e) => tracing::warn!("github: {e}"),
+        }
+    }
+    Ok(pulls)
+}
+
+/// Background pull request lister
+pub struct Github {
+    config: GithubConfig,
+    pulls: Vec<PullRequest>,
+    pending: bool,
+    /// Named PRs as of the last listing, to notice new `pr` fields
+    requested: HashSet<u32>,
+    refreshed_at: Option<Instant>,
+    tx: mpsc::Sender<Result<Vec<PullRequest>, String>>,
+    rx: mpsc::Receiver<Result<Vec<PullRequest>, String>>,
+}
+
+impl Github {
+    /// Create a lister, or None unless enabled
+    pub fn new(config: GithubConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let (tx, rx) = mpsc::channel();
+        Some(Self {
+            config,
+            pulls: Vec::new(),
+            pending: false,
+            requested: HashSet::new(),
+            refreshed_at: None,
+            tx,
+            rx,
+        })
+    }
+
+    /// PR numbers to list with, every `refresh_secs` or sooner when the plan
+    /// names one not listed yet. The listing counts as in flight until
+    /// [`Github::poll`] takes it, so hand the numbers to [`Github::list`].
+    pub fn plan_refresh(&mut self, phases: &[ParsedPhase], now: Instant) -> Option<Vec<u32>> {
+        if self.pending {
+            return None;
+        }
+        let numbers: HashSet<u32> = phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .filter_map(|t| t.pr)
+            .collect();
+        let due = self.refreshed_at.map_or(true, |at| {
+            now.saturating_duration_since(at) >= Duration::from_secs(self.config.refresh_secs)
+        });
+        if !due && numbers.is_subset(&self.requested) {
+            return None;
+        }
+        self.refreshed_at = Some(now);
+        self.pending = true;
+        let mut sorted: Vec<u32> = numbers.iter().copied().collect();
+        sorted.sort_unstable();
+        self.requested = numbers;
+        Some(sorted)
+    }
+
+    /// List pull requests on a background thread, including those numbered
+    pub fn list(&self, numbers: Vec<u32>) {
+        let config = self.config.clone();
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(fetch_pulls(&config, &numbers));
+        });
+    }
+
+    /// Take a finished listing, if any
+    pub fn poll(&mut self) {
+        while let Ok(result) = self.rx.try_recv() {
+            self.pending = false;
+            match result {
+                Ok(pulls) => self.pulls = pulls,
+                Err(e) => tracing::warn!("github: {e}"),
+            }
+        }
+    }
+
+    /// The pull request for a task: the one its `pr` field names, else the
+    /// newest open one (then the newest of any state) mentioning its ID
+    pub fn pull_for(&self, task_id: &str, pr: Option<u32>) -> Option<&PullRequest> {
+        if let Some(number) = pr {
+            return self.pulls.iter().find(|p| p.number == number);
+        }
+        if !self.config.discover {
+            return None;
+        }
+        let mut found = self
+            .pulls
+            .iter()
+            .filter(|p| mentions(&p.branch, task_id) || mentions(&p.title, task_id));
+        let first = found.next()?;
+        if first.state == "OPEN" {
+            return Some(first);
+        }
+        Some(found.find(|p| p.state == "OPEN").unwrap_or(first))
+    }
+
+    /// IDs of tasks whose pull request is red
+    pub fn red_tasks(&self, phases: &[ParsedPhase]) -> HashSet<String> {
+        phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .filter(|t| self.pull_for(&t.id, t.pr).is_some_and(PullRequest::is_red))
+            .map(|t| t.id.to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::tasks_parser::parse_tasks_md;
+
+    const LIST: &str = r#"[
+        {"number":12,"title":"T1.1: login","state":"OPEN","headRefName":"feat/t1.1-login","statusCheckRollup":[
+            {"__typename":"CheckRun","status":"COMPLETED","conclusion":"SUCCESS"},
+            {"__typename":"CheckRun","status":"COMPLETED","conclusion":"FAILURE"}]},
+        {"number":11,"title":"Signup","state":"MERGED","headRefName":"T1.10","statusCheckRollup":[
+            {"__typename":"StatusContext","state":"SUCCESS"}]},
+        {"number":10,"title":"Logout","state":"OPEN","headRefName":"logout","statusCheckRollup":[
+            {"__typename":"CheckRun","status":"IN_PROGRESS","conclusion":""},
+            {"__typename":"StatusContext","state":"SUCCESS"}]}
+    ]"#;
+
+    #[test]
+    fn pull_requests_and_checks_are_parsed() {
+        let pulls = parse_pulls(LIST);
+        assert_eq!(pulls.len(), 3);
+        assert_eq!(pulls[0].checks, Checks::Failing);
+        assert!(pulls[0].is_red());
+        assert_eq!(pulls[1].checks, Checks::Passing);
+        assert_eq!(pulls[2].checks, Checks::Pending);
+        assert_eq!(rollup(&[]), Checks::None);
+
+        let one = parse_pulls(
+            r#"{"number":7,"title":"x","state":"CLOSED","headRefName":"y","statusCheckRollup":[]}"#,
+        );
+        assert_eq!(one[0].number, 7);
+        assert!(parse_pulls("gh: not logged in").is_empty());
+    }
+
+    #[test]
+    fn task_ids_are_matched_whole() {
+        assert!(mentions("feat/t1.1-login", "T1.1"));
+        assert!(mentions("T1.1: login", "T1.1"));
+        assert!(!mentions("T1.10", "T1.1"));
+        assert!(!mentions("XT1.1", "T1.1"));
+        assert!(!mentions("T1.1.2", "T1.1"));
+    }
+
+    #[test]
+    fn tasks_are_matched_to_pull_requests() {
+        let mut github = Github::new(GithubConfig {
+            enabled: true,
+            ..GithubConfig::default()
+        })
+        .unwrap();
+        github.pulls = parse_pulls(LIST);
+        let phases = parse_tasks_md(
+            "# Phase 1: A\n### [ ] T1.1: Login\n### [ ] T1.10: Signup\n### [ ] T2: Logout\n- **pr**: #10\n### [ ] T3: None\n",
+        )
+        .unwrap();
+
+        assert_eq!(github.pull_for("T1.1", None).unwrap().number, 12);
+        assert_eq!(github.pull_for("T1.10", None).unwrap().number, 11);
+        assert_eq!(github.pull_for("T2", Some(10)).unwrap().number, 10);
+        assert!(github.pull_for("T3", None).is_none());
+        assert!(github.pull_for("T3", Some(99)).is_none());
+
+        let red = github.red_tasks(&phases);
+        assert_eq!(red, HashSet::from(["T1.1".to_string()]));
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        assert!(Github::new(GithubConfig::default()).is_none());
+    }
+}
@@ -0,0 +1,292 @@
+//! Jira sync
+//!
+//! A task links its Jira issue with a `- **jira**: PROJ-123` body line. With
+//! `[jira] url` set, the board fetches the status and assignee of every
+//! linked issue for the detail panel, and moves an issue through the
+//! configured transition once its task is Completed (approved, when approval
+//! is on). Requests go through `curl` on background threads, like triage, so
+//! a slow Jira never stalls the UI. The API token is read from an environment
+//! variable and handed to curl on stdin, never on its command line.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::process::{self, curl_quote};
+
+/// `[jira]` section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct JiraConfig {
+    /// Site root, e.g. `https://acme.atlassian.net`; empty disables the sync
+    pub url: String,
+    /// Account email for basic auth
+    pub user: String,
+    /// Environment variable holding the API token
+    pub token_env: String,
+    /// Transition applied when a linked task completes; empty never transitions
+    pub done_transition: String,
+    /// Re-fetch linked issues this often
+    pub refresh_secs: u64,
+    /// Give up on a request after this many seconds
+    pub timeout_secs: u64,
+}
+
+impl Default for JiraConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            user: String::new(),
+            token_env: "JIRA_API_TOKEN".to_string(),
+            done_transition: "Done".to_string(),
+            refresh_secs: 300,
+            timeout_secs: 15,
+        }
+    }
+}
+
+/// What the board shows of a linked issue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JiraIssue {
+    pub key: String,
+    pub status: String,
+    pub assignee: Option<String>,
+}
+
+/// Whether `key` looks like `PROJ-123`; anything else is never sent to Jira
+pub fn is_issue_key(key: &str) -> bool {
+    let Some((project, number)) = key.split_once('-') else {
+        return false;
+    };
+    project.starts_with(|c: char| c.is_ascii_uppercase())
+        && project
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Read an issue from a `GET /rest/api/2/issue/{key}` reply
+pub fn parse_issue(json: &str) -> Option<JiraIssue> {
+    let value: Value = serde_json::from_str(json).ok()?;
+    let fields = value.get("fields")?;
+    Some(JiraIssue {
+        key: value.get("key")?.as_str()?.to_string(),
+        status: fields.pointer("/status/name")?.as_str()?.to_string(),
+        assignee: fields
+            .pointer("/assignee/displayName")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    })
+}
+
+/// ID of the transition named `name` (or leading to a status named `name`)
+/// in a `GET /rest/api/2/issue/{key}/transitions` reply
+pub fn transition_id(json: &str, name: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(json).ok()?;
+    value
+        .get("transitions")?
+        .as_array()?
+        .iter()
+        .find(|t| {
+            let named = |v: Option<&Value>| {
+                v.and_then(Value::as_str)
+                    .is_some_and(|n| n.eq_ignore_ascii_case(name))
+            };
+            named(t.get("name")) || named(t.pointer("/to/name"))
+        })?
+        .get("id")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Run one request with curl (blocking) and return the response body
+fn request(config: &JiraConfig, path: &str, body: Option<&str>) -> Result<String, String> {
+    let token =
+        std::env::var(&config.token_env).map_err(|_| format!("{} is not set", config.token_env))?;
+    let url = format!("{}/rest/api/2/{path}", config.url.trim_end_matches('/'));
+    let auth = format!("{}:{token}", config.user);
+    let credentials = [format!("user = {}", curl_quote(&auth))];
+    process::curl(&url, &credentials, body, config.timeout_secs)
+}
+
+/// Fetch one issue's status and assignee (blocking)
+pub fn fetch_issue(config: &JiraConfig, key: &str) -> Result<JiraIssue, String> {
+    let reply = request(config, &format!("issue/{key}?fields=status,assignee"), None)?;
+    parse_issue(&reply).ok_or_else(|| format!("unrecognized reply for {key}"))
+}
+
+/// Move an issue through `done_transition` (blocking)
+pub fn complete_issue(config: &JiraConfig, key: &str) -> Result<(), String> {
+    let reply = request(config, &format!("issue/{key}/transitions"), None)?;
+    let id = transition_id(&reply, &config.done_transition)
+        .ok_or_else(|| format!("{key} has no \"{}\" transition", config.done_transition))?;
+    let body = serde_json::json!({ "transition": { "id": id } }).to_string();
+    request(config, &format!("issue/{key}/transitions"), Some(&body)).map(|_| ())
+}
+
+enum Reply {
+    /// None records a failed fetch
+    Issue(String, Option<JiraIssue>),
+    Transition(String, Result<(), String>),
+}
+
+/// Background Jira client with an issue cache
+pub struct Jira {
+    config: JiraConfig,
+    issues: HashMap<String, JiraIssue>,
+    /// Keys whose last fetch failed; retried on the next refresh round
+    failed: HashSet<String>,
+    pending: HashSet<String>,
+    refreshed_at: Option<Instant>,
+    tx: mpsc::Sender<Reply>,
+    rx: mpsc::Receiver<Reply>,
+}
+
+impl Jira {
+    /// Create a client, or None when no Jira URL is configured
+    pub fn new(config: JiraConfig) -> Option<Self> {
+        if config.url.is_empty() {
+            return None;
+        }
+        let (tx, rx) = mpsc::channel();
+        Some(Self {
+            config,
+            issues: HashMap::new(),
+            failed: HashSet::new(),
+            pending: HashSet::new(),
+            refreshed_at: None,
+            tx,
+            rx,
+        })
+    }
+
+    /// Last fetched state of an issue
+    pub fn issue(&self, key: &str) -> Option<&JiraIssue> {
+        self.issues.get(key)
+    }
+
+    /// Keys to fetch: those not seen yet, and all of them every
+    /// `refresh_secs`. They count as in flight until [`Jira::poll`] collects
+    /// them, so hand every key to [`Jira::fetch`].
+    pub fn plan_refresh<'k>(
+        &mut self,
+        keys: impl IntoIterator<Item = &'k str>,
+        now: Instant,
+    ) -> Vec<String> {
+        let due = self.refreshed_at.map_or(true, |at| {
+            now.saturating_duration_since(at) >= Duration::from_secs(self.config.refresh_secs)
+        });
+        if due {
+            self.refreshed_at = Some(now);
+            self.failed.clear();
+        }
+        let mut planned = Vec::new();
+        for key in keys {
+            let known = self.issues.contains_key(key) || self.failed.contains(key);
+            if (due || !known) && is_issue_key(key) && self.pending.insert(key.to_string()) {
+                planned.push(key.to_string());
+            }
+        }
+        planned
+    }
+
+    /// Fetch a planned issue on a background thread
+    pub fn fetch(&self, key: String) {
+        let config = self.config.clone();
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let issue = fetch_issue(&config, &key)
+                .map_err(|e| tracing::warn!("jira: {e}"))
+                .ok();
+            let _ = tx.send(Reply::Issue(key, issue));
+        });
+    }
+
+    /// Whether a completed task would move this issue: a done transition is
+    /// configured and the key looks like one
+    pub fn completes(&self, key: &str) -> bool {
+        !self.config.done_transition.is_empty() && is_issue_key(key)
+    }
+
+    /// Transition an issue whose task completed, then re-fetch it
+    pub fn complete(&self, key: &str) {
+        if !self.completes(key) {
+            return;
+        }
+        let config = self.config.clone();
+        let key = key.to_string();
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let result = complete_issue(&config, &key);
+            let issue = fetch_issue(&config, &key).ok();
+            let _ = tx.send(Reply::Transition(key.clone(), result));
+            let _ = tx.send(Reply::Issue(key, issue));
+        });
+    }
+
+    /// Collect finished requests. Returns the transitions that finished,
+    /// for reporting.
+    pub fn poll(&mut self) -> Vec<(String, Result<(), String>)> {
+        let mut transitions = Vec::new();
+        while let Ok(reply) = self.rx.try_recv() {
+            match reply {
+                Reply::Issue(key, Some(issue)) => {
+                    self.pending.remove(&key);
+                    self.failed.remove(&key);
+                    self.issues.insert(key, issue);
+                }
+                Reply::Issue(key, None) => {
+                    self.pending.remove(&key);
+                    self.failed.insert(key);
+                }
+                Reply::Transition(key, result) => transitions.push((key, result)),
+            }
+        }
+        transitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issue_keys_are_validated() {
+        assert!(is_issue_key("PROJ-123"));
+        assert!(is_issue_key("A1_B-7"));
+        assert!(!is_issue_key("proj-1"));
+        assert!(!is_issue_key("PROJ-"));
+        assert!(!is_issue_key("PROJ-1/../../admin"));
+        assert!(!is_issue_key("PROJ"));
+    }
+
+    #[test]
+    fn replies_are_parsed() {
+        let issue = parse_issue(
+            r#"{"key":"AUTH-42","fields":{"status":{"name":"In Progress"},"assignee":{"displayName":"Kim Lee"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(issue.key, "AUTH-42");
+        assert_eq!(issue.status, "In Progress");
+        assert_eq!(issue.assignee.as_deref(), Some("Kim Lee"));
+        let unassigned =
+            parse_issue(r#"{"key":"AUTH-1","fields":{"status":{"name":"To Do"},"assignee":null}}"#)
+                .unwrap();
+        assert_eq!(unassigned.assignee, None);
+        assert!(parse_issue(r#"{"errorMessages":["Issue does not exist"]}"#).is_none());
+
+        let transitions = r#"{"transitions":[{"id":"11","name":"Start","to":{"name":"In Progress"}},{"id":"31","name":"Resolve","to":{"name":"Done"}}]}"#;
+        assert_eq!(transition_id(transitions, "done").as_deref(), Some("31"));
+        assert_eq!(transition_id(transitions, "Start").as_deref(), Some("11"));
+        assert_eq!(transition_id(transitions, "Close"), None);
+    }
+
+    #[test]
+    fn disabled_without_url() {
+        assert!(Jira::new(JiraConfig::default()).is_none());
+    }
+}
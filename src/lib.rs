@@ -1,6 +1,24 @@
 pub mod analysis;
+#[cfg(feature = "tui")]
 pub mod app;
+pub mod config;
+pub mod dashboard;
 pub mod data;
+pub mod discover;
+#[cfg(feature = "tui")]
 pub mod event;
+pub mod github;
 pub mod init;
+pub mod jira;
+pub mod logging;
+pub mod otel;
+pub mod perf;
+pub mod process;
+pub mod prompt;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod scripts;
+#[cfg(feature = "tui")]
 pub mod ui;
+#[cfg(feature = "tui")]
+pub mod update;
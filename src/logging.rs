@@ -0,0 +1,158 @@
+//! Debug log file
+//!
+//! `--log-file` / `--log-level` send `tracing` output to a file, since a
+//! raw-mode TUI leaves no usable stderr. Watcher events, parse warnings,
+//! write-backs and task status changes are logged. The file rolls over to
+//! `<file>.1` once it passes [`MAX_LOG_BYTES`], so a long run keeps at most
+//! two files.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tracing_subscriber::filter::LevelFilter;
+
+/// Size at which the log file is rotated
+pub const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Append-only log file that rotates itself by size
+#[derive(Debug)]
+pub struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    file: File,
+    written: u64,
+}
+
+impl RotatingFile {
+    /// Open `path` for appending, creating it and its directory if needed
+    pub fn open(path: &Path, max_bytes: u64) -> io::Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            max_bytes,
+            file,
+            written,
+        })
+    }
+
+    /// Where the previous file goes on rotation
+    pub fn backup_path(&self) -> PathBuf {
+        let mut name = self.path.as_os_str().to_owned();
+        name.push(".1");
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        std::fs::rename(&self.path, self.backup_path())?;
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Send `tracing` output at `level` and above to `path`
+pub fn init(path: &Path, level: LevelFilter) -> io::Result<()> {
+    let file = RotatingFile::open(path, MAX_LOG_BYTES)?;
+    tracing_subscriber::fmt()
+        .with_writer(Mutex::new(file))
+        .with_max_level(level)
+        .with_ansi(false)
+        .try_init()
+        .map_err(io::Error::other)
+}
+
+/// Bytes read from the end of the log by [`tail`]
+const TAIL_BYTES: u64 = 64 * 1024;
+
+/// Last `max_lines` lines of a log file, oldest first. Only the end of the
+/// file is read, so this stays cheap however large the log grows.
+pub fn tail(path: &Path, max_lines: usize) -> io::Result<Vec<String>> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes);
+    let mut lines: Vec<&str> = text.lines().collect();
+    if start > 0 && !lines.is_empty() {
+        // Cut mid-line
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(max_lines);
+    Ok(lines[skip..].iter().map(|l| l.to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tail_returns_the_last_lines() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("board.log");
+        std::fs::write(&path, "one\ntwo\nthree\n").unwrap();
+        assert_eq!(tail(&path, 2).unwrap(), vec!["two", "three"]);
+        assert_eq!(tail(&path, 10).unwrap().len(), 3);
+        assert!(tail(&tmp.path().join("missing.log"), 5).is_err());
+    }
+
+    #[test]
+    fn rotates_to_a_single_backup_past_the_limit() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("logs").join("board.log");
+        let mut log = RotatingFile::open(&path, 16).unwrap();
+        log.write_all(b"first line 0123\n").unwrap();
+        log.write_all(b"second line\n").unwrap();
+        log.write_all(b"third line\n").unwrap();
+        log.write_all(b"fourth line\n").unwrap();
+        log.flush().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fourth line\n");
+        assert_eq!(
+            std::fs::read_to_string(log.backup_path()).unwrap(),
+            "third line\n"
+        );
+        assert_eq!(
+            log.backup_path(),
+            tmp.path().join("logs").join("board.log.1")
+        );
+    }
+
+    #[test]
+    fn reopening_continues_the_size_count() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("board.log");
+        std::fs::write(&path, "0123456789").unwrap();
+        let mut log = RotatingFile::open(&path, 12).unwrap();
+        log.write_all(b"abc").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "abc");
+    }
+}
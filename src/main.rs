@@ -1,27 +1,58 @@
-use std::io;
-use std::path::PathBuf;
-use std::time::Duration;
+use std::io::{self, Write};
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
 use tokio::sync::mpsc;
+use tracing_subscriber::filter::LevelFilter;
 
 use simple_claude_board::app::App;
-use simple_claude_board::data::state::DashboardState;
+use simple_claude_board::config::{self, Config};
+use simple_claude_board::dashboard::Dashboard;
+use simple_claude_board::data::cost::{self, CostGroup, CostSort};
+use simple_claude_board::data::crash::CrashDump;
+use simple_claude_board::data::filter::TaskFilter;
+use simple_claude_board::data::loader::Loader;
+use simple_claude_board::data::state::Timeline;
+use simple_claude_board::data::tasks_parser::ParsedPhase;
 use simple_claude_board::data::watcher::{self, FileChange, WatchConfig};
-use simple_claude_board::event::{key_to_action, poll_event, Action, AppEvent};
+use simple_claude_board::data::{gating, plan_diff, tasks_source, text, wip};
+use simple_claude_board::discover;
+use simple_claude_board::event::{poll_event, AppEvent};
+use simple_claude_board::logging;
+use simple_claude_board::otel;
+use simple_claude_board::perf::Profile;
+use simple_claude_board::ui::banner::FatalBanner;
 use simple_claude_board::ui::claude_output::AgentPanel;
+use simple_claude_board::ui::clipboard;
+use simple_claude_board::ui::costs::CostsView;
+use simple_claude_board::ui::debug_overlay::DebugOverlay;
 use simple_claude_board::ui::detail::DetailWidget;
+use simple_claude_board::ui::error_modal::ErrorModal;
+use simple_claude_board::ui::event_log::EventLog;
 use simple_claude_board::ui::gantt::GanttWidget;
 use simple_claude_board::ui::help::HelpOverlay;
+use simple_claude_board::ui::input_modal::InputModal;
 use simple_claude_board::ui::layout::{DashboardLayout, FocusedPane};
+use simple_claude_board::ui::log_viewer::LogViewer;
+use simple_claude_board::ui::markdown;
+use simple_claude_board::ui::monitor::TaskMonitor;
+use simple_claude_board::ui::next_up::NextUpOverlay;
+use simple_claude_board::ui::panel::PanelWidget;
+use simple_claude_board::ui::plain;
 use simple_claude_board::ui::retry_modal::RetryModal;
+use simple_claude_board::ui::roster::RosterView;
 use simple_claude_board::ui::statusbar::StatusBar;
+use simple_claude_board::ui::stop_modal::StopModal;
+use simple_claude_board::update::Msg;
 
 /// Claude Code orchestration TUI dashboard
 #[derive(Parser, Debug)]
@@ -34,21 +65,125 @@ struct Cli {
     #[arg(long, global = true)]
     tasks: Option<String>,
 
-    /// Path to Hook events directory
+    /// Path to Hook events directory (repeat to watch several)
     #[arg(long, global = true)]
-    hooks: Option<String>,
+    hooks: Vec<String>,
 
     /// Path to dashboard JSONL events directory (default: ~/.claude/dashboard)
     #[arg(long, global = true)]
     events: Option<String>,
+
+    /// Path to config file (default: ./.claude-board.toml if present)
+    #[arg(long, global = true)]
+    config: Option<String>,
+
+    /// Write per-frame draw and per-update timings to this CSV file on exit
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// No colors or decorative glyphs, status spelled out (for screen readers
+    /// and `tmux capture-pane`)
+    #[arg(long, global = true)]
+    plain: bool,
+
+    /// Debug log verbosity: error, warn, info, debug or trace (default: info
+    /// when --log-file is given)
+    #[arg(long, global = true, value_name = "LEVEL")]
+    log_level: Option<LevelFilter>,
+
+    /// Write a debug log here, rotated at 5 MB (default:
+    /// ~/.claude/dashboard/simple-claude-board.log when --log-level is given)
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<String>,
 }
 
 #[derive(clap::Subcommand, Debug)]
 enum Commands {
     /// Watch TASKS.md and Hook events in real-time (default)
-    Watch,
+    Watch(WatchArgs),
     /// Initialize configuration
     Init,
+    /// Write token usage and cost per phase, task or agent, then exit
+    Export {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// Group rows by
+        #[arg(long, value_enum, default_value_t = ExportGroup::Task)]
+        by: ExportGroup,
+        /// Write here instead of stdout
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+    /// Compare the current plan with an earlier version of the tasks file,
+    /// then exit
+    Diff {
+        /// Earlier tasks file (TASKS.md, tasks.json or tasks.toml)
+        baseline: String,
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Filters narrowing the board to the tasks under review
+#[derive(clap::Args, Debug, Default)]
+struct WatchArgs {
+    /// Only these phases, by ID or name (comma-separated or repeated)
+    #[arg(long, value_delimiter = ',', value_name = "PHASE")]
+    phase: Vec<String>,
+    /// Only tasks worked on by these agents
+    #[arg(long, value_delimiter = ',', value_name = "AGENT")]
+    agent: Vec<String>,
+    /// Only tasks with these statuses, e.g. failed,inprogress
+    #[arg(long, value_delimiter = ',', value_name = "STATUS")]
+    status: Vec<String>,
+    /// Show just this task full-screen: body, dependencies, agent activity,
+    /// errors and timing
+    #[arg(long, value_name = "ID")]
+    task: Option<String>,
+}
+
+impl WatchArgs {
+    fn filter(&self) -> TaskFilter {
+        TaskFilter {
+            phases: self.phase.clone(),
+            agents: self.agent.clone(),
+            statuses: self.status.clone(),
+        }
+    }
+}
+
+/// Per-session options of the TUI
+struct TuiOptions<'a> {
+    profile_path: Option<&'a str>,
+    plain: bool,
+    log_path: Option<PathBuf>,
+    filter: TaskFilter,
+    monitor: Option<String>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum ExportGroup {
+    Phase,
+    Task,
+    Agent,
+}
+
+impl From<ExportGroup> for CostGroup {
+    fn from(group: ExportGroup) -> Self {
+        match group {
+            ExportGroup::Phase => CostGroup::Phase,
+            ExportGroup::Task => CostGroup::Task,
+            ExportGroup::Agent => CostGroup::Agent,
+        }
+    }
 }
 
 /// Get the user's home directory (cross-platform)
@@ -59,89 +194,289 @@ fn home_dir() -> PathBuf {
         .unwrap_or_else(|_| PathBuf::from("."))
 }
 
-/// Resolve the hooks directory: .claude/hooks > ~/.claude/hooks
+/// Resolve the hooks directory: nearest .claude/hooks at or above the
+/// working directory > ~/.claude/hooks
 fn resolve_hooks_path() -> PathBuf {
-    let local = PathBuf::from(".claude/hooks");
-    if local.is_dir() {
+    if let Some(local) = discover::hooks_dir(&current_dir()) {
         return local;
     }
     let global = home_dir().join(".claude").join("hooks");
     if global.is_dir() {
         return global;
     }
-    local
+    PathBuf::from(discover::HOOKS_DIR)
 }
 
-/// Resolve the tasks file path: explicit CLI arg > ./TASKS.md > ./docs/planning/06-tasks.md
+/// Resolve the events directory: CLI arg > default ~/.claude/dashboard
+fn resolve_events_dir(explicit: Option<&str>) -> PathBuf {
+    explicit
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home_dir().join(".claude").join("dashboard"))
+}
+
+/// Resolve the tasks file path: explicit CLI arg > the nearest TASKS.md (or
+/// docs/planning/06-tasks.md, tasks.json, tasks.toml) at or above the
+/// working directory > ./TASKS.md
 fn resolve_tasks_path(explicit: Option<&str>) -> String {
     if let Some(path) = explicit {
         return path.to_string();
     }
-    let primary = "./TASKS.md";
-    if std::path::Path::new(primary).exists() {
-        return primary.to_string();
+    discover::tasks_file(&current_dir())
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "./TASKS.md".to_string())
+}
+
+/// Resolve the config file path: explicit CLI arg > the nearest
+/// .claude-board.toml at or above the working directory > ./.claude-board.toml
+fn resolve_config_path(explicit: Option<&str>) -> PathBuf {
+    if let Some(path) = explicit {
+        return PathBuf::from(path);
     }
-    let fallback = "./docs/planning/06-tasks.md";
-    if std::path::Path::new(fallback).exists() {
-        return fallback.to_string();
+    discover::config_file(&current_dir()).unwrap_or_else(|| PathBuf::from(config::CONFIG_FILE_NAME))
+}
+
+/// Load the config file; only an explicit one has to exist
+fn load_config(path: &Path, explicit: bool) -> Result<Config> {
+    if explicit || path.is_file() {
+        return Config::load(path);
     }
-    primary.to_string()
+    Ok(Config::default())
+}
+
+/// Working directory, or "." if it cannot be read
+fn current_dir() -> PathBuf {
+    std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
     let tasks_path = resolve_tasks_path(cli.tasks.as_deref());
 
-    match cli.command.unwrap_or(Commands::Watch) {
-        Commands::Watch => run_tui(&tasks_path, cli.hooks.as_deref(), cli.events.as_deref()),
+    match cli
+        .command
+        .unwrap_or_else(|| Commands::Watch(WatchArgs::default()))
+    {
+        Commands::Watch(args) => {
+            let log_path = if cli.log_file.is_some() || cli.log_level.is_some() {
+                let path = cli.log_file.map(PathBuf::from).unwrap_or_else(|| {
+                    resolve_events_dir(cli.events.as_deref()).join("simple-claude-board.log")
+                });
+                logging::init(&path, cli.log_level.unwrap_or(LevelFilter::INFO))
+                    .with_context(|| format!("cannot open log file {}", path.display()))?;
+                tracing::info!(version = env!("CARGO_PKG_VERSION"), "starting");
+                Some(path)
+            } else {
+                None
+            };
+            run_tui(
+                &tasks_path,
+                &cli.hooks,
+                cli.events.as_deref(),
+                cli.config.as_deref(),
+                TuiOptions {
+                    profile_path: cli.profile.as_deref(),
+                    plain: cli.plain,
+                    log_path,
+                    filter: args.filter(),
+                    monitor: args.task,
+                },
+            )
+        }
         Commands::Init => simple_claude_board::init::run_init(),
+        Commands::Export { format, by, output } => run_export(
+            &tasks_path,
+            &cli.hooks,
+            cli.events.as_deref(),
+            cli.config.as_deref(),
+            format,
+            by.into(),
+            output.as_deref(),
+        ),
+        Commands::Diff { baseline, json } => {
+            run_diff(&tasks_path, &baseline, cli.config.as_deref(), json)
+        }
+    }
+}
+
+/// Files the board reads: the tasks file, hook directories and the events
+/// directory
+fn watch_paths(
+    tasks_path: &str,
+    hooks_dirs: &[String],
+    events_path: &Path,
+    config: &Config,
+) -> WatchConfig {
+    let hooks_path = hooks_dirs
+        .first()
+        .map(PathBuf::from)
+        .unwrap_or_else(resolve_hooks_path);
+    let mut watch_config = WatchConfig::new(PathBuf::from(tasks_path), hooks_path);
+    if events_path.is_dir() {
+        watch_config = watch_config.with_events_dir(events_path.to_path_buf());
+    }
+    let extra_dirs = hooks_dirs.iter().skip(1).map(PathBuf::from);
+    for dir in extra_dirs.chain(config.hooks.dirs.iter().cloned()) {
+        watch_config = watch_config.with_extra_dir(dir);
     }
+    watch_config
+}
+
+/// Cache hook file parses under the events directory unless the config
+/// names another place
+fn default_cache_dir(config: &mut Config, events_path: &Path) {
+    config
+        .hooks
+        .cache_dir
+        .get_or_insert_with(|| events_path.join("cache"));
 }
 
-/// Install a panic hook that restores the terminal before printing the panic
-fn install_panic_hook() {
+/// Load the plan and hook events once and write the cost breakdown
+fn run_export(
+    tasks_path: &str,
+    hooks_dirs: &[String],
+    events_dir: Option<&str>,
+    config_path: Option<&str>,
+    format: ExportFormat,
+    group: CostGroup,
+    output: Option<&str>,
+) -> Result<()> {
+    let explicit_config = config_path.is_some();
+    let mut config = load_config(&resolve_config_path(config_path), explicit_config)?;
+    let events_path = resolve_events_dir(events_dir);
+    default_cache_dir(&mut config, &events_path);
+    let paths = watch_paths(tasks_path, hooks_dirs, &events_path, &config);
+    let state = Dashboard::load(paths, config.clone()).into_state();
+    let rows = cost::breakdown(&state, group, CostSort::Id, &config.cost);
+
+    let mut out: Box<dyn Write> = match output {
+        Some(path) => Box::new(io::BufWriter::new(
+            std::fs::File::create(path).with_context(|| format!("cannot create {path}"))?,
+        )),
+        None => Box::new(io::stdout().lock()),
+    };
+    match format {
+        ExportFormat::Csv => cost::write_csv(&rows, &mut out)?,
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, &rows)?;
+            writeln!(out)?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn run_diff(tasks_path: &str, baseline: &str, config_path: Option<&str>, json: bool) -> Result<()> {
+    let explicit_config = config_path.is_some();
+    let config = load_config(&resolve_config_path(config_path), explicit_config)?;
+    let parse = |path: &str| -> Result<Vec<ParsedPhase>> {
+        let decoded =
+            text::read_lossy(Path::new(path)).with_context(|| format!("cannot read {path}"))?;
+        let source = tasks_source::for_path(Path::new(path), config.tasks.format);
+        let outcome = source
+            .parse(&decoded.text)
+            .with_context(|| format!("cannot parse {path}"))?;
+        Ok(outcome.phases)
+    };
+    let old = parse(baseline)?;
+    let new = parse(tasks_path)?;
+    let diff = plan_diff::diff(&old, &new, &config.statuses);
+
+    let mut out = io::stdout().lock();
+    if json {
+        serde_json::to_writer_pretty(&mut out, &diff)?;
+        writeln!(out)?;
+    } else {
+        writeln!(out, "{baseline} -> {tasks_path}")?;
+        diff.write_text(&mut out)?;
+    }
+    Ok(())
+}
+
+/// Install a panic hook that restores the terminal before printing the
+/// panic. The returned slot receives the panic message for the crash dump.
+fn install_panic_hook() -> Arc<Mutex<Option<String>>> {
+    let message = Arc::new(Mutex::new(None));
+    let slot = Arc::clone(&message);
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
         let _ = disable_raw_mode();
         let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        if let Ok(mut slot) = slot.lock() {
+            *slot = Some(panic_info.to_string());
+        }
         original_hook(panic_info);
     }));
+    message
 }
 
-fn run_tui(tasks_path: &str, hooks_dir: Option<&str>, events_dir: Option<&str>) -> Result<()> {
-    // Load initial state
-    let dashboard = match std::fs::read_to_string(tasks_path) {
-        Ok(content) => DashboardState::from_tasks_content(&content)
-            .unwrap_or_else(|_| DashboardState::default()),
-        Err(_) => DashboardState::default(),
-    };
-
-    let mut dashboard = dashboard;
-    let hooks_path = hooks_dir
-        .map(PathBuf::from)
-        .unwrap_or_else(resolve_hooks_path);
-
-    // Resolve events directory: CLI arg > default ~/.claude/dashboard
-    let events_path = events_dir
-        .map(PathBuf::from)
-        .unwrap_or_else(|| home_dir().join(".claude").join("dashboard"));
-
-    // Load existing hook events at startup
-    if hooks_path.is_dir() {
-        let _ = dashboard.load_hook_events(&hooks_path);
-    }
-    // Also load events from the dashboard events directory
-    if events_path.is_dir() {
-        let _ = dashboard.load_hook_events(&events_path);
+fn run_tui(
+    tasks_path: &str,
+    hooks_dirs: &[String],
+    events_dir: Option<&str>,
+    config_path: Option<&str>,
+    options: TuiOptions,
+) -> Result<()> {
+    let TuiOptions {
+        profile_path,
+        plain,
+        log_path,
+        filter,
+        monitor,
+    } = options;
+    let explicit_config = config_path.is_some();
+    let config_path = resolve_config_path(config_path);
+    let mut config = load_config(&config_path, explicit_config)?;
+    let events_path = resolve_events_dir(events_dir);
+    default_cache_dir(&mut config, &events_path);
+
+    let control_dir = config
+        .control
+        .dir
+        .clone()
+        .unwrap_or_else(|| events_path.join("control"));
+    let context_dir = events_path.join("context");
+    let prompt_dir = events_path.join("prompts");
+    let audit_path = config.audit.enabled.then(|| {
+        config
+            .audit
+            .file
+            .clone()
+            .unwrap_or_else(|| events_path.join("audit.jsonl"))
+    });
+    let mut watch_config = watch_paths(tasks_path, hooks_dirs, &events_path, &config);
+    // Reload on change, including a config file created after startup
+    watch_config = watch_config.with_config_path(config_path);
+
+    // Load tasks (the file extension picks markdown or json/toml) and the
+    // existing hook events
+    let mut dashboard = Dashboard::load(watch_config.clone(), config.clone()).into_state();
+    if !filter.is_empty() {
+        tracing::info!(filter = %filter.describe(), "filtering the board");
+        dashboard.set_task_filter(filter);
     }
 
     let mut app = App::new()
         .with_dashboard(dashboard)
-        .with_tasks_path(PathBuf::from(tasks_path));
-    let mut watch_config = WatchConfig::new(PathBuf::from(tasks_path), hooks_path);
-    if events_path.is_dir() {
-        watch_config = watch_config.with_events_dir(events_path);
+        .with_loader(Loader::spawn())
+        .with_tasks_path(PathBuf::from(tasks_path))
+        .with_control_dir(control_dir)
+        .with_context_dir(context_dir)
+        .with_prompt_dir(prompt_dir)
+        .with_plain(plain)
+        .with_hyperlinks(!plain && markdown::hyperlinks_supported())
+        .with_config(config);
+    if let Some(path) = audit_path {
+        app = app.with_audit_path(path);
+    }
+    if let Some(path) = log_path {
+        app = app.with_log_path(path);
     }
+    if let Some(task_id) = monitor {
+        app = app.with_monitor(task_id);
+    }
+    app.apply_triage();
+    let effects = app.plan_retry_policy();
+    app.perform_all(effects);
     let watcher_rx = if watch_config.validate().is_ok() {
         match watcher::start_watching(watch_config) {
             Ok((_watcher, rx)) => {
@@ -156,7 +491,7 @@ fn run_tui(tasks_path: &str, hooks_dir: Option<&str>, events_dir: Option<&str>)
     };
 
     // Install panic hook before entering raw mode
-    install_panic_hook();
+    let panic_message = install_panic_hook();
 
     // Setup terminal
     enable_raw_mode()?;
@@ -166,7 +501,27 @@ fn run_tui(tasks_path: &str, hooks_dir: Option<&str>, events_dir: Option<&str>)
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
-    let result = run_loop(&mut terminal, &mut app, watcher_rx);
+    let mut profile = profile_path.map(|_| Profile::new());
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        run_loop(&mut terminal, &mut app, watcher_rx, profile.as_mut())
+    }));
+    let result = match result {
+        Ok(result) => result,
+        Err(payload) => {
+            // The hook already restored the terminal; keep what the run
+            // derived before going down
+            let message = panic_message
+                .lock()
+                .ok()
+                .and_then(|m| m.clone())
+                .unwrap_or_default();
+            match CrashDump::new(&app.dashboard, &message).write(&std::env::temp_dir()) {
+                Ok(path) => eprintln!("Crash dump written to {}", path.display()),
+                Err(e) => eprintln!("Failed to write crash dump: {e}"),
+            }
+            std::panic::resume_unwind(payload);
+        }
+    };
 
     // Restore terminal
     disable_raw_mode()?;
@@ -177,6 +532,25 @@ fn run_tui(tasks_path: &str, hooks_dir: Option<&str>, events_dir: Option<&str>)
         crossterm::cursor::Show
     )?;
 
+    if let (Some(profile), Some(path)) = (profile, profile_path) {
+        profile
+            .save(std::path::Path::new(path))
+            .with_context(|| format!("Failed to write profile: {path}"))?;
+    }
+
+    // Leave a recap in the scrollback
+    if result.is_ok() {
+        print!("{}", app.exit_summary());
+    }
+
+    let otel_config = &app.config.otel;
+    if otel_config.on_exit && !otel_config.endpoint.is_empty() {
+        match otel::export_blocking(otel_config, &app.dashboard) {
+            Ok(spans) => println!("Exported {spans} spans to {}", otel_config.endpoint),
+            Err(e) => eprintln!("Trace not exported: {e}"),
+        }
+    }
+
     result
 }
 
@@ -184,55 +558,113 @@ fn run_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
     mut watcher_rx: Option<mpsc::UnboundedReceiver<FileChange>>,
+    mut profile: Option<&mut Profile>,
 ) -> Result<()> {
-    let tick_rate = Duration::from_millis(250);
-
     while app.running {
         // Draw
+        let draw_started = Instant::now();
         terminal.draw(|frame| {
             let area = frame.area();
-            let layout = DashboardLayout::compute(area);
-
-            // Left panel: Gantt chart
-            let gantt = GanttWidget::new(&app.dashboard, app.focused == FocusedPane::TaskList);
-            frame.render_stateful_widget(gantt, layout.task_list, &mut app.gantt_state);
+            // While scrubbing, every panel shows the reconstructed past state
+            let view = app.scrub.as_ref().map_or(&app.dashboard, Timeline::state);
+            let fatal = app.fatal_banner().cloned();
+            let layout =
+                DashboardLayout::for_preset(area, fatal.is_some(), app.panels.len(), app.layout);
+
+            // Top: fatal error banner
+            if let (Some(err), Some(banner_area)) = (&fatal, layout.banner) {
+                frame.render_widget(FatalBanner::new(err), banner_area);
+            }
 
-            // Right panel: Detail view (content depends on focused pane)
-            let selected_task = app.selected_task();
-            let detail = if app.focused == FocusedPane::Agents {
-                DetailWidget::from_agent_selection(&app.dashboard, app.selected_agent)
+            let wip = wip::evaluate(view, &app.config.wip);
+            let gates = gating::evaluate(view);
+            if let Some(task_id) = app.monitor.as_deref() {
+                // `watch --task`: the one task fills everything between the
+                // banner and the status bar
+                let top = layout.banner.map_or(area.y, |b| b.y + b.height);
+                let board = Rect::new(area.x, top, area.width, layout.status_bar.y - top);
+                frame.render_widget(TaskMonitor::new(view, task_id), board);
             } else {
-                DetailWidget::from_selection(
-                    &app.dashboard,
-                    selected_task,
-                    app.gantt_state.selected,
-                    app.focused == FocusedPane::Detail,
-                )
-            };
-            frame.render_widget(detail, layout.detail);
-
-            // Right bottom: Agent activity (highlights agent for selected task)
-            let selected_agent_name = selected_task
-                .and_then(|(pi, ti)| {
-                    app.dashboard
-                        .phases
-                        .get(pi)
-                        .and_then(|phase| phase.tasks.get(ti))
-                })
-                .and_then(|task| app.dashboard.agent_for_task(&task.id));
-            let agents = AgentPanel::new(&app.dashboard)
-                .with_selected_agent(selected_agent_name)
-                .with_focused(app.focused == FocusedPane::Agents)
-                .with_selected_index(app.selected_agent);
-            frame.render_widget(agents, layout.agents);
+                // Left panel: Gantt chart
+                let red_prs = app
+                    .github
+                    .as_ref()
+                    .map(|g| g.red_tasks(&view.phases))
+                    .unwrap_or_default();
+                let gantt = GanttWidget::new(view, app.focused == FocusedPane::TaskList)
+                    .with_wip_excess(&wip.excess)
+                    .with_red_prs(&red_prs)
+                    .with_gates(&gates)
+                    .with_plain(app.plain);
+                frame.render_stateful_widget(gantt, layout.task_list, &mut app.gantt_state);
+
+                // Ops layout: live hook events under the task list
+                if let Some(events_area) = layout.events {
+                    // Anchors index the live events, not a scrubbed past
+                    let anchor = app
+                        .event_anchor
+                        .filter(|_| app.scrub.is_none())
+                        .map(|a| a.index);
+                    frame.render_widget(EventLog::new(view).with_anchor(anchor), events_area);
+                }
+
+                // Right panel: Detail view (content depends on focused pane)
+                let selected_task = app.selected_task();
+                let detail = if app.focused == FocusedPane::Agents {
+                    DetailWidget::from_agent_selection(view, app.selected_agent)
+                } else {
+                    let jira = selected_task
+                        .and_then(|(pi, ti)| view.phases.get(pi)?.tasks.get(ti)?.jira.as_deref())
+                        .and_then(|key| app.jira.as_ref()?.issue(key));
+                    let pull = selected_task
+                        .and_then(|(pi, ti)| view.phases.get(pi)?.tasks.get(ti))
+                        .and_then(|task| app.github.as_ref()?.pull_for(&task.id, task.pr));
+                    DetailWidget::from_selection(
+                        view,
+                        selected_task,
+                        app.gantt_state.selected,
+                        app.focused == FocusedPane::Detail,
+                    )
+                    .with_jira(jira)
+                    .with_pull(pull)
+                    .with_hyperlinks(app.hyperlinks)
+                };
+                frame.render_widget(detail, layout.detail);
+
+                // Right bottom: Agent activity (highlights agent for selected task)
+                let selected_agent_name = selected_task
+                    .and_then(|(pi, ti)| view.phases.get(pi).and_then(|phase| phase.tasks.get(ti)))
+                    .and_then(|task| view.worker_for(task));
+                let agents = AgentPanel::new(view)
+                    .with_selected_agent(selected_agent_name)
+                    .with_focused(app.focused == FocusedPane::Agents)
+                    .with_selected_index(app.selected_agent)
+                    .with_stop_requested(&app.stop_requested)
+                    .with_output_expanded(app.show_agent_output);
+                frame.render_widget(agents, layout.agents);
+
+                // Right bottom, under the agents: plugin panels
+                for (i, (panel, panel_area)) in app.panels.iter().zip(&layout.panels).enumerate() {
+                    let widget = PanelWidget::new(panel, view)
+                        .with_focused(app.focused == FocusedPane::Panel(i));
+                    frame.render_widget(widget, *panel_area);
+                }
+            }
 
             // Bottom: Status bar
-            let statusbar = StatusBar::new(&app.dashboard, app.start_time);
+            let statusbar = StatusBar::new(view, app.start_time)
+                .with_tasks_gone(app.tasks_gone)
+                .with_wip(&wip)
+                .with_gates(&gates)
+                .with_segment(app.scripts.as_ref().and_then(|s| s.segment()))
+                .with_cost(&app.config.cost)
+                .with_plain(app.plain);
             frame.render_widget(statusbar, layout.status_bar);
 
             // Help overlay (on top if active)
             if app.show_help {
-                frame.render_widget(HelpOverlay, area);
+                let help = HelpOverlay::default().with_entries(app.panels.help_entries());
+                frame.render_widget(help, area);
             }
 
             // Retry modal (on top if active)
@@ -242,58 +674,169 @@ fn run_loop(
                         task_id: target.task_id.clone(),
                         task_name: target.task_name.clone(),
                         retryable: target.retryable,
+                        conflict: app.modal_conflict().map(str::to_string),
                     };
                     frame.render_widget(modal, area);
                 }
             }
+
+            // Error modal (e)
+            if let Some(ref view) = app.error_view {
+                let modal = ErrorModal {
+                    error: &view.error,
+                    timezone: app.dashboard.timezone,
+                    cursor: view.cursor,
+                    expanded: &view.expanded,
+                };
+                frame.render_widget(modal, area);
+            }
+
+            // Stop-agent modal (on top if active)
+            if let Some(ref agent_id) = app.stop_target {
+                let modal = StopModal {
+                    agent_id,
+                    task_id: app
+                        .dashboard
+                        .agents
+                        .get(agent_id)
+                        .and_then(|a| a.current_task.as_deref()),
+                };
+                frame.render_widget(modal, area);
+            }
+
+            // "Next up" dispatch overlay (on top if active)
+            if app.show_next_up {
+                let suggestions = app.next_up();
+                let agent = suggestions.first().and_then(|s| app.dispatch_agent(s));
+                let overlay = NextUpOverlay {
+                    suggestions: &suggestions,
+                    agent: agent.as_deref(),
+                };
+                frame.render_widget(overlay, area);
+            }
+
+            // Costs view ($)
+            if app.show_costs {
+                let rows = app.cost_rows();
+                let costs = CostsView {
+                    rows: &rows,
+                    group: app.cost_group,
+                    sort: app.cost_sort,
+                    scroll: app.cost_scroll,
+                };
+                frame.render_widget(costs, area);
+            }
+
+            // Roster coverage view (w)
+            if app.show_roster {
+                let coverage = app.coverage();
+                frame.render_widget(
+                    RosterView {
+                        coverage: &coverage,
+                    },
+                    area,
+                );
+            }
+
+            // Text input modal (on top if active)
+            if let Some(ref input) = app.input {
+                let prompt = input.prompt();
+                let modal = InputModal {
+                    title: input.title(),
+                    prompt: &prompt,
+                    input: &input.text,
+                    conflict: app.modal_conflict(),
+                };
+                frame.render_widget(modal, area);
+            }
+
+            // Log viewer (L)
+            if app.show_log {
+                let viewer = LogViewer {
+                    path: app.log_path.as_deref(),
+                    lines: &app.log_lines,
+                };
+                frame.render_widget(viewer, area);
+            }
+
+            if let Some(ref toast) = app.toast {
+                frame.render_widget(toast, area);
+            }
+
+            // Debug overlay (F12), above everything else
+            if app.show_debug {
+                frame.render_widget(
+                    DebugOverlay::new(&app.stats, view, view.clock.instant()),
+                    area,
+                );
+            }
+
+            if app.plain {
+                plain::flatten(frame.buffer_mut());
+            }
         })?;
+        let draw_took = draw_started.elapsed();
+        app.stats.record_frame(draw_took);
+        if let Some(profile) = profile.as_deref_mut() {
+            profile.record("draw", draw_started, draw_took);
+        }
 
-        // Process file watcher events (non-blocking)
+        // Process file watcher events (non-blocking): drain the channel and
+        // reload each changed file once per frame
         if let Some(ref mut rx) = watcher_rx {
+            let mut drained = Vec::with_capacity(rx.len());
             while let Ok(change) = rx.try_recv() {
-                app.handle_file_change(&change);
+                drained.push(change);
+            }
+            app.stats.backlog = drained.len();
+            let changes = watcher::coalesce(drained);
+            app.stats.merged = app.stats.backlog - changes.len();
+            if app.stats.merged > 0 {
+                tracing::debug!(
+                    drained = app.stats.backlog,
+                    applied = changes.len(),
+                    "coalesced file changes"
+                );
+            }
+            for change in changes {
+                let started = Instant::now();
+                let effects = app.update(Msg::FileChanged(change));
+                app.perform_all(effects);
+                if let Some(profile) = profile.as_deref_mut() {
+                    profile.record("file", started, started.elapsed());
+                }
             }
         }
 
-        // Handle keyboard events
-        if let Some(event) = poll_event(tick_rate)? {
-            match event {
-                AppEvent::Key(key) => {
-                    if app.show_retry_modal {
-                        // Modal takes priority: only y/n/q/Esc
-                        let retryable = app.retry_target.as_ref().is_some_and(|t| t.retryable);
-                        match key_to_action(key) {
-                            Action::Confirm if retryable => app.confirm_retry(),
-                            Action::Cancel | Action::Quit => app.cancel_retry(),
-                            // Non-retryable: any key closes
-                            _ if !retryable => app.cancel_retry(),
-                            _ => {}
-                        }
-                    } else {
-                        match key_to_action(key) {
-                            Action::Quit => app.quit(),
-                            Action::MoveDown => match app.focused {
-                                FocusedPane::Agents => app.agent_move_down(),
-                                _ => app.move_down(),
-                            },
-                            Action::MoveUp => match app.focused {
-                                FocusedPane::Agents => app.agent_move_up(),
-                                _ => app.move_up(),
-                            },
-                            Action::ToggleFocus => app.toggle_focus(),
-                            Action::ToggleHelp => app.toggle_help(),
-                            Action::ToggleCollapse => app.toggle_collapse(),
-                            Action::ToggleView => app.toggle_view(),
-                            Action::RetryRequest => app.open_retry_modal(),
-                            Action::Confirm | Action::Cancel | Action::None => {}
-                        }
-                    }
-                }
-                AppEvent::Resize(_, _) => {} // terminal auto-handles resize
-                AppEvent::FileChanged(change) => app.handle_file_change(&change),
-                AppEvent::Tick => {}
+        // Apply the files the loader has finished parsing
+        for loaded in app.take_loads() {
+            let started = Instant::now();
+            let effects = app.update(Msg::Loaded(loaded));
+            app.perform_all(effects);
+            if let Some(profile) = profile.as_deref_mut() {
+                profile.record("load", started, started.elapsed());
             }
         }
+
+        // Handle keyboard events
+        let msg = match poll_event(app.tick_interval(app.dashboard.clock.instant()))? {
+            Some(AppEvent::Key(key)) => Msg::Key(key),
+            Some(AppEvent::FileChanged(change)) => Msg::FileChanged(change),
+            Some(AppEvent::Tick) | None => Msg::Tick,
+            Some(AppEvent::Resize(_, _)) => continue, // terminal auto-handles resize
+        };
+        let started = Instant::now();
+        let kind = msg.kind();
+        let effects = app.update(msg);
+        app.perform_all(effects);
+        if let Some(profile) = profile.as_deref_mut() {
+            profile.record(kind, started, started.elapsed());
+        }
+        if let Some(text) = app.clipboard.take() {
+            let backend = terminal.backend_mut();
+            write!(backend, "{}", clipboard::osc52(&text))?;
+            backend.flush()?;
+        }
     }
 
     Ok(())
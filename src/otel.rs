@@ -0,0 +1,417 @@
+//! OpenTelemetry export
+//!
+//! Turns the run into a trace: one span for the run, one per phase, one per
+//! task that started, and one per tool call paired from `tool_start` /
+//! `tool_end` hook events. The spans are sent as OTLP/HTTP JSON to the
+//! `[otel] endpoint` collector (Jaeger, Tempo, an OpenTelemetry Collector),
+//! with `curl` on a background thread like the other integrations. IDs are
+//! derived from the run, so exporting the same run twice yields the same
+//! trace.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::mpsc;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::data::hook_parser::EventType;
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::TaskStatus;
+use crate::process::{self, curl_quote};
+
+/// `[otel]` section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct OtelConfig {
+    /// OTLP/HTTP base URL, e.g. `http://localhost:4318`; empty disables export
+    pub endpoint: String,
+    /// `service.name` of the exported spans
+    pub service_name: String,
+    /// Extra request headers, e.g. an auth token for a hosted collector
+    pub headers: BTreeMap<String, String>,
+    /// Also export when the dashboard exits
+    pub on_exit: bool,
+    pub timeout_secs: u64,
+}
+
+impl Default for OtelConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            service_name: "claude-orchestration".to_string(),
+            headers: BTreeMap::new(),
+            on_exit: false,
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// One span of the run trace
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunSpan {
+    pub span_id: String,
+    pub parent_id: Option<String>,
+    pub name: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub attributes: Vec<(&'static str, String)>,
+    /// Error status message, for failed tasks
+    pub error: Option<String>,
+}
+
+/// Trace of a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunTrace {
+    pub trace_id: String,
+    /// Run span first, then phases, tasks and tool calls
+    pub spans: Vec<RunSpan>,
+}
+
+fn hash_hex(parts: impl Hash, bytes: usize) -> String {
+    let mut out = String::new();
+    for salt in 0..bytes.div_ceil(8) {
+        let mut hasher = DefaultHasher::new();
+        (salt, &parts).hash(&mut hasher);
+        out.push_str(&format!("{:016x}", hasher.finish()));
+    }
+    out.truncate(bytes * 2);
+    out
+}
+
+/// Build the trace of the run in `state`, or None before anything started
+pub fn trace(state: &DashboardState) -> Option<RunTrace> {
+    let now = state.now();
+    let first_event = state.events.iter().map(|e| e.timestamp).min();
+    let run_start = state.run_started.or(first_event)?;
+    let trace_id = hash_hex(("trace", run_start), 16);
+    let span_id = |kind: &str, key: &str| hash_hex((&trace_id, kind, key), 8);
+    let run_id = span_id("run", "");
+
+    let mut phases = Vec::new();
+    let mut tasks = Vec::new();
+    let mut task_ids: HashMap<&str, String> = HashMap::new();
+    for phase in &state.phases {
+        let phase_id = span_id("phase", &phase.id);
+        let mut bounds: Option<(DateTime<Utc>, DateTime<Utc>)> = None;
+        for task in &phase.tasks {
+            let Some(timing) = state.task_times.get(task.id.as_str()) else {
+                continue;
+            };
+            let Some(start) = timing.started_at else {
+                continue;
+            };
+            let end = timing.completed_at.unwrap_or(now).max(start);
+            bounds = Some(bounds.map_or((start, end), |(s, e)| (s.min(start), e.max(end))));
+            let id = span_id("task", &task.id);
+            task_ids.insert(task.id.as_str(), id.clone());
+            let mut attributes = vec![
+                ("board.task.id", task.id.clone()),
+                ("board.task.status", task.status.label().to_string()),
+                ("board.task.attempts", task.attempts.to_string()),
+            ];
+            if let Some(agent) = state.worker_for(task) {
+                attributes.push(("board.agent", agent.to_string()));
+            }
+            let error = matches!(task.status, TaskStatus::Failed).then(|| {
+                state
+                    .recent_errors
+                    .iter()
+                    .rfind(|e| e.task_id == task.id)
+                    .map(|e| e.message.trim().to_string())
+                    .unwrap_or_else(|| "task failed".to_string())
+            });
+            tasks.push(RunSpan {
+                span_id: id,
+                parent_id: Some(phase_id.clone()),
+                name: format!("{}: {}", task.id, task.name),
+                start,
+                end,
+                attributes,
+                error,
+            });
+        }
+        if let Some((start, end)) = bounds {
+            phases.push(RunSpan {
+                span_id: phase_id,
+                parent_id: Some(run_id.clone()),
+                name: format!("{}: {}", phase.id, phase.name),
+                start,
+                end,
+                attributes: vec![
+                    ("board.phase.id", phase.id.clone()),
+                    ("board.phase.name", phase.name.clone()),
+                ],
+                error: None,
+            });
+        }
+    }
+
+    // Pair each tool_end with the oldest open tool_start of the same tool
+    let mut open: HashMap<(&str, &str, &str), VecDeque<DateTime<Utc>>> = HashMap::new();
+    let mut calls = Vec::new();
+    for event in &state.events {
+        let Some(tool) = event.tool_name.as_deref() else {
+            continue;
+        };
+        let key = (event.agent_id.as_str(), event.task_id.as_str(), tool);
+        match event.event_type {
+            EventType::ToolStart => open.entry(key).or_default().push_back(event.timestamp),
+            EventType::ToolEnd => {
+                if let Some(start) = open.get_mut(&key).and_then(VecDeque::pop_front) {
+                    calls.push((key, start, event.timestamp));
+                }
+            }
+            _ => {}
+        }
+    }
+    for (key, starts) in open {
+        calls.extend(starts.into_iter().map(|start| (key, start, now)));
+    }
+    calls.sort_by_key(|&(key, start, _)| (start, key));
+    let tools = calls
+        .into_iter()
+        .enumerate()
+        .map(|(i, ((agent, task, tool), start, end))| RunSpan {
+            span_id: span_id("tool", &i.to_string()),
+            parent_id: Some(task_ids.get(task).unwrap_or(&run_id).clone()),
+            name: tool.to_string(),
+            start,
+            end: end.max(start),
+            attributes: vec![
+                ("board.tool.name", tool.to_string()),
+                ("board.agent", agent.to_string()),
+                ("board.task.id", task.to_string()),
+            ],
+            error: None,
+        });
+
+    let tools: Vec<RunSpan> = tools.collect();
+    let run_end = phases
+        .iter()
+        .chain(&tools)
+        .map(|s| s.end)
+        .max()
+        .unwrap_or(now)
+        .max(run_start);
+    let mut spans = vec![RunSpan {
+        span_id: run_id,
+        parent_id: None,
+        name: "run".to_string(),
+        start: run_start,
+        end: run_end,
+        attributes: vec![(
+            "board.tasks",
+            state
+                .phases
+                .iter()
+                .map(|p| p.tasks.len())
+                .sum::<usize>()
+                .to_string(),
+        )],
+        error: None,
+    }];
+    spans.extend(phases);
+    spans.extend(tasks);
+    spans.extend(tools);
+    Some(RunTrace { trace_id, spans })
+}
+
+fn nanos(at: DateTime<Utc>) -> String {
+    at.timestamp_nanos_opt().unwrap_or_default().to_string()
+}
+
+/// OTLP/HTTP JSON `ExportTraceServiceRequest` body for a trace
+pub fn to_otlp(trace: &RunTrace, service_name: &str) -> Value {
+    let attr = |key: &str, value: &str| json!({ "key": key, "value": { "stringValue": value } });
+    let spans: Vec<Value> = trace
+        .spans
+        .iter()
+        .map(|span| {
+            let mut out = json!({
+                "traceId": trace.trace_id,
+                "spanId": span.span_id,
+                "name": span.name,
+                "kind": 1,
+                "startTimeUnixNano": nanos(span.start),
+                "endTimeUnixNano": nanos(span.end),
+                "attributes": span.attributes.iter().map(|(k, v)| attr(k, v)).collect::<Vec<_>>(),
+            });
+            if let Some(parent) = &span.parent_id {
+                out["parentSpanId"] = json!(parent);
+            }
+            if let Some(message) = &span.error {
+                out["status"] = json!({ "code": 2, "message": message });
+            }
+            out
+        })
+        .collect();
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": [attr("service.name", service_name)] },
+            "scopeSpans": [{
+                "scope": { "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+/// POST a body to `<endpoint>/v1/traces` (blocking). Headers go to curl on
+/// stdin so tokens stay off its command line.
+pub fn send(config: &OtelConfig, body: &str) -> Result<(), String> {
+    let url = format!("{}/v1/traces", config.endpoint.trim_end_matches('/'));
+    let headers: Vec<String> = config
+        .headers
+        .iter()
+        .map(|(name, value)| format!("header = {}", curl_quote(&format!("{name}: {value}"))))
+        .collect();
+    process::curl(&url, &headers, Some(body), config.timeout_secs).map(|_| ())
+}
+
+/// Build and send the run trace (blocking); returns the number of spans
+pub fn export_blocking(config: &OtelConfig, state: &DashboardState) -> Result<usize, String> {
+    let trace = trace(state).ok_or("nothing has run yet")?;
+    send(config, &to_otlp(&trace, &config.service_name).to_string())?;
+    Ok(trace.spans.len())
+}
+
+/// Background exporter
+pub struct Exporter {
+    config: OtelConfig,
+    tx: mpsc::Sender<Result<usize, String>>,
+    rx: mpsc::Receiver<Result<usize, String>>,
+}
+
+impl Exporter {
+    /// Create an exporter, or None when no endpoint is configured
+    pub fn new(config: OtelConfig) -> Option<Self> {
+        if config.endpoint.is_empty() {
+            return None;
+        }
+        let (tx, rx) = mpsc::channel();
+        Some(Self { config, tx, rx })
+    }
+
+    pub fn config(&self) -> &OtelConfig {
+        &self.config
+    }
+
+    /// Build the trace now and send it from a background thread
+    pub fn export(&self, state: &DashboardState) -> Result<usize, String> {
+        let trace = trace(state).ok_or("nothing has run yet")?;
+        let count = trace.spans.len();
+        let body = to_otlp(&trace, &self.config.service_name).to_string();
+        let config = self.config.clone();
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(send(&config, &body).map(|()| count));
+        });
+        Ok(count)
+    }
+
+    /// Results of finished exports
+    pub fn poll(&self) -> Vec<Result<usize, String>> {
+        self.rx.try_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    fn run_state() -> DashboardState {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: API\n### [x] T1: Schema\n### [Failed] T2: Handlers\n### [ ] T3: Docs\n",
+        )
+        .unwrap();
+        let events = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"backend","task_id":"T1","session_id":"s1"}"#,
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:01Z","agent_id":"backend","task_id":"T1","session_id":"s1","tool_name":"Edit"}"#,
+            r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:00:04Z","agent_id":"backend","task_id":"T1","session_id":"s1","tool_name":"Edit"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:05Z","agent_id":"backend","task_id":"T1","session_id":"s1"}"#,
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:06Z","agent_id":"backend","task_id":"T2","session_id":"s1"}"#,
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:07Z","agent_id":"backend","task_id":"T2","session_id":"s1","tool_name":"Bash"}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:00:09Z","agent_id":"backend","task_id":"T2","session_id":"s1","error_message":"connection refused"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:10Z","agent_id":"backend","task_id":"T2","session_id":"s1"}"#,
+        ];
+        state.update_from_events(&parse_hook_events(&events.join("\n")).events);
+        state
+    }
+
+    #[test]
+    fn run_maps_to_nested_spans() {
+        let state = run_state();
+        let trace = trace(&state).unwrap();
+        let names: Vec<&str> = trace.spans.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "run",
+                "P1: API",
+                "T1: Schema",
+                "T2: Handlers",
+                "Edit",
+                "Bash"
+            ]
+        );
+        let by_name = |name: &str| trace.spans.iter().find(|s| s.name == name).unwrap();
+        let run = by_name("run");
+        assert_eq!(run.parent_id, None);
+        assert_eq!(by_name("P1: API").parent_id.as_ref(), Some(&run.span_id));
+        assert_eq!(
+            by_name("Edit").parent_id,
+            Some(by_name("T1: Schema").span_id.clone())
+        );
+        assert_eq!(
+            (by_name("Edit").end - by_name("Edit").start).num_seconds(),
+            3
+        );
+        assert_eq!(
+            by_name("T2: Handlers").error.as_deref(),
+            Some("connection refused")
+        );
+
+        // The same run always maps to the same IDs
+        let ids = |t: &RunTrace| {
+            t.spans
+                .iter()
+                .map(|s| s.span_id.clone())
+                .collect::<Vec<_>>()
+        };
+        let again = super::trace(&state).unwrap();
+        assert_eq!(again.trace_id, trace.trace_id);
+        assert_eq!(ids(&again), ids(&trace));
+        assert_eq!(trace.trace_id.len(), 32);
+        assert_eq!(run.span_id.len(), 16);
+
+        let empty = DashboardState::from_tasks_content("# Phase 1: A\n### [ ] T1: B\n").unwrap();
+        assert!(super::trace(&empty).is_none());
+    }
+
+    #[test]
+    fn otlp_body_has_ids_times_and_status() {
+        let trace = trace(&run_state()).unwrap();
+        let body = to_otlp(&trace, "demo");
+        let resource = &body["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0]["value"]["stringValue"],
+            "demo"
+        );
+        let spans = resource["scopeSpans"][0]["spans"].as_array().unwrap();
+        assert_eq!(spans.len(), trace.spans.len());
+        assert!(spans[0].get("parentSpanId").is_none());
+        assert_eq!(spans[1]["parentSpanId"], spans[0]["spanId"]);
+        assert_eq!(spans[0]["startTimeUnixNano"], "1770544800000000000");
+        let failed = spans.iter().find(|s| s["name"] == "T2: Handlers").unwrap();
+        assert_eq!(failed["status"]["code"], 2);
+    }
+
+    #[test]
+    fn disabled_without_endpoint() {
+        assert!(Exporter::new(OtelConfig::default()).is_none());
+    }
+}
@@ -0,0 +1,196 @@
+//! Frame and event timing
+//!
+//! [`FrameStats`] keeps a short rolling window of draw times and processed
+//! hook events for the F12 debug overlay. [`Profile`] records every draw and
+//! update of a session for `--profile`, written out as CSV on exit.
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Frames kept for the average and maximum draw time
+const FRAME_WINDOW: usize = 60;
+
+/// Rolling draw-time and event-rate counters
+#[derive(Debug, Clone, Default)]
+pub struct FrameStats {
+    frames: VecDeque<Duration>,
+    /// Hook events parsed per file change in the last second
+    events: VecDeque<(Instant, usize)>,
+    /// File changes waiting in the watcher channel before the last drain
+    pub backlog: usize,
+    /// Of those, changes superseded by a later one to the same file
+    pub merged: usize,
+    /// Changed files still being read by the background loader
+    pub loading: usize,
+}
+
+impl FrameStats {
+    /// Record how long one frame took to draw
+    pub fn record_frame(&mut self, took: Duration) {
+        if self.frames.len() == FRAME_WINDOW {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(took);
+    }
+
+    /// Record hook events processed at `at`
+    pub fn record_events(&mut self, count: usize, at: Instant) {
+        self.events.push_back((at, count));
+        self.prune(at);
+    }
+
+    fn prune(&mut self, now: Instant) {
+        let second = Duration::from_secs(1);
+        while self
+            .events
+            .front()
+            .is_some_and(|(at, _)| now.saturating_duration_since(*at) > second)
+        {
+            self.events.pop_front();
+        }
+    }
+
+    /// Draw time of the latest frame
+    pub fn last_frame(&self) -> Option<Duration> {
+        self.frames.back().copied()
+    }
+
+    /// Mean draw time over the window
+    pub fn avg_frame(&self) -> Option<Duration> {
+        let total: Duration = self.frames.iter().sum();
+        (!self.frames.is_empty()).then(|| total / self.frames.len() as u32)
+    }
+
+    /// Slowest draw time in the window
+    pub fn max_frame(&self) -> Option<Duration> {
+        self.frames.iter().max().copied()
+    }
+
+    /// Hook events processed within the second before `now`
+    pub fn events_per_sec(&self, now: Instant) -> usize {
+        self.events
+            .iter()
+            .filter(|(at, _)| now.saturating_duration_since(*at) <= Duration::from_secs(1))
+            .map(|(_, n)| n)
+            .sum()
+    }
+}
+
+/// One timed draw or update
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Sample {
+    /// Since the profile started
+    at: Duration,
+    kind: &'static str,
+    took: Duration,
+}
+
+/// Per-frame draw and per-update costs for a whole session
+#[derive(Debug, Clone)]
+pub struct Profile {
+    started: Instant,
+    samples: Vec<Sample>,
+}
+
+impl Profile {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            samples: Vec::new(),
+        }
+    }
+
+    /// Record a draw or update of `kind` that started at `at`
+    pub fn record(&mut self, kind: &'static str, at: Instant, took: Duration) {
+        self.samples.push(Sample {
+            at: at.saturating_duration_since(self.started),
+            kind,
+            took,
+        });
+    }
+
+    /// Write `elapsed_ms,kind,duration_us` rows
+    pub fn write_csv(&self, mut out: impl Write) -> std::io::Result<()> {
+        writeln!(out, "elapsed_ms,kind,duration_us")?;
+        for sample in &self.samples {
+            writeln!(
+                out,
+                "{},{},{}",
+                sample.at.as_millis(),
+                sample.kind,
+                sample.took.as_micros()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Write the CSV to a file
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        let mut out = std::io::BufWriter::new(file);
+        self.write_csv(&mut out)?;
+        out.flush()
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_window_tracks_last_avg_and_max() {
+        let mut stats = FrameStats::default();
+        assert_eq!(stats.avg_frame(), None);
+        for ms in [2, 4, 12] {
+            stats.record_frame(Duration::from_millis(ms));
+        }
+        assert_eq!(stats.last_frame(), Some(Duration::from_millis(12)));
+        assert_eq!(stats.avg_frame(), Some(Duration::from_millis(6)));
+        assert_eq!(stats.max_frame(), Some(Duration::from_millis(12)));
+
+        for _ in 0..FRAME_WINDOW {
+            stats.record_frame(Duration::from_millis(1));
+        }
+        assert_eq!(stats.max_frame(), Some(Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn event_rate_covers_last_second() {
+        let mut stats = FrameStats::default();
+        let start = Instant::now();
+        stats.record_events(30, start);
+        stats.record_events(20, start + Duration::from_millis(500));
+        assert_eq!(stats.events_per_sec(start + Duration::from_millis(900)), 50);
+        assert_eq!(
+            stats.events_per_sec(start + Duration::from_millis(1200)),
+            20
+        );
+    }
+
+    #[test]
+    fn profile_writes_csv_rows() {
+        let mut profile = Profile::new();
+        let start = profile.started;
+        profile.record("draw", start, Duration::from_micros(1500));
+        profile.record(
+            "file",
+            start + Duration::from_millis(250),
+            Duration::from_micros(40),
+        );
+
+        let mut out = Vec::new();
+        profile.write_csv(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "elapsed_ms,kind,duration_us\n0,draw,1500\n250,file,40\n"
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! External commands with a deadline
+//!
+//! Triage and script hooks hand work to user-configured programs. [`run`]
+//! feeds a command its stdin and collects its stdout on helper threads while
+//! waiting, so a command that writes more than a pipe buffer holds before
+//! exiting cannot stall, and kills it once the timeout has passed.
+//!
+//! The Jira and OpenTelemetry integrations talk HTTP through [`curl`], which
+//! keeps credentials and request bodies off curl's command line.
+
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Run `cmd` to completion (blocking) with `input` on its stdin and return
+/// its stdout; stderr is discarded
+pub fn run(cmd: &mut Command, input: Vec<u8>, timeout: Duration) -> Result<String, String> {
+    let program = cmd.get_program().to_string_lossy().into_owned();
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("failed to run {program}: {e}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that exits without reading is fine; dropping closes the pipe
+        thread::spawn(move || {
+            let _ = stdin.write_all(&input);
+        });
+    }
+    let reader = child.stdout.take().map(|mut stdout| {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            let _ = stdout.read_to_end(&mut buf);
+            buf
+        })
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("{program} timed out"));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+
+    let stdout = reader
+        .map(|r| r.join().unwrap_or_default())
+        .unwrap_or_default();
+    if !status.success() {
+        return Err(format!("{program} exited with {status}"));
+    }
+    Ok(String::from_utf8_lossy(&stdout).into_owned())
+}
+
+/// Quote a value for a curl config file
+pub fn curl_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Request `url` with curl (blocking) and return the response body. `config`
+/// lines (`header = ...`, `user = ...`) go to curl on stdin so secrets stay
+/// off its command line; a `body` is POSTed from a private temporary file, so
+/// its size is not bounded by the argument limit.
+pub fn curl(
+    url: &str,
+    config: &[String],
+    body: Option<&str>,
+    timeout_secs: u64,
+) -> Result<String, String> {
+    let mut cmd = Command::new("curl");
+    cmd.args(["--silent", "--show-error", "--fail", "--config", "-"])
+        .arg("--max-time")
+        .arg(timeout_secs.to_string());
+    // Removed on drop, after curl has finished
+    let body_file = match body {
+        Some(body) => {
+            let mut file = tempfile::NamedTempFile::new()
+                .map_err(|e| format!("failed to stage request body: {e}"))?;
+            file.write_all(body.as_bytes())
+                .map_err(|e| format!("failed to stage request body: {e}"))?;
+            cmd.args([
+                "--header",
+                "Content-Type: application/json",
+                "--data-binary",
+            ])
+            .arg(format!("@{}", file.path().display()));
+            Some(file)
+        }
+        None => None,
+    };
+    let mut child = cmd
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run curl: {e}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        for line in config {
+            writeln!(stdin, "{line}").map_err(|e| format!("failed to configure curl: {e}"))?;
+        }
+    }
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    drop(body_file);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{url}: {}", stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sh(script: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", script]);
+        cmd
+    }
+
+    #[test]
+    fn output_larger_than_a_pipe_buffer_is_collected() {
+        let out = run(
+            &mut sh("head -c 300000 /dev/zero | tr '\\0' x"),
+            Vec::new(),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert_eq!(out.len(), 300_000);
+    }
+
+    #[test]
+    fn large_input_is_echoed_back() {
+        let input = "y".repeat(300_000).into_bytes();
+        let out = run(&mut sh("cat"), input, Duration::from_secs(5)).unwrap();
+        assert_eq!(out.len(), 300_000);
+    }
+
+    #[test]
+    fn failures_and_timeouts_are_errors() {
+        let secs = Duration::from_secs(5);
+        assert!(run(&mut sh("exit 3"), Vec::new(), secs)
+            .unwrap_err()
+            .contains("exited with"));
+        assert!(
+            run(&mut Command::new("scb-no-such-program"), Vec::new(), secs)
+                .unwrap_err()
+                .starts_with("failed to run scb-no-such-program")
+        );
+        assert!(run(&mut sh("sleep 5"), Vec::new(), Duration::ZERO)
+            .unwrap_err()
+            .contains("timed out"));
+    }
+
+    #[test]
+    fn credentials_are_quoted_for_curl() {
+        assert_eq!(curl_quote(r#"a@b.c:t"o\k"#), r#""a@b.c:t\"o\\k""#);
+    }
+
+    /// Accept one HTTP request on a loopback port, answer `ok` and hand back
+    /// the request head and body
+    fn one_shot_server() -> (String, thread::JoinHandle<(String, Vec<u8>)>) {
+        use std::io::{BufRead, BufReader};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1/traces", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let length = head
+                .lines()
+                .find_map(|l| {
+                    l.to_ascii_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|n| n.trim().parse().unwrap())
+                })
+                .unwrap_or(0);
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .unwrap();
+            (head, body)
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn curl_posts_a_large_body_with_headers_from_stdin() {
+        let (url, server) = one_shot_server();
+        let body = format!("{{\"pad\":\"{}\"}}", "x".repeat(300_000));
+        let config = vec![format!("header = {}", curl_quote("X-Token: s3cret"))];
+        assert_eq!(curl(&url, &config, Some(&body), 10).unwrap(), "ok");
+
+        let (head, received) = server.join().unwrap();
+        assert!(head.starts_with("POST /v1/traces"));
+        assert!(head.contains("X-Token: s3cret"));
+        assert_eq!(received, body.as_bytes());
+    }
+}
@@ -0,0 +1,212 @@
+//! Re-prompt templates
+//!
+//! The `[prompt]` config section holds a template that is filled in for the
+//! selected failed task and handed back to a human or an agent: copied to the
+//! clipboard, written to a file, or piped to a command such as `claude -p`.
+//! Placeholders are `{id}`, `{name}`, `{phase}`, `{status}`, `{agent}`,
+//! `{error}`, `{category}`, `{suggestion}`, `{attempts}`, `{body}` and
+//! `{context}` (the full bundle from [`crate::data::context`]); unknown ones
+//! are left as written.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::Deserialize;
+
+use crate::data::context;
+use crate::data::state::DashboardState;
+
+/// Where a rendered prompt goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptOutput {
+    #[default]
+    Clipboard,
+    /// `<dir>/<task-id>.md`
+    File,
+    /// Written to the stdin of `command`
+    Command,
+}
+
+/// `[prompt]` section
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PromptConfig {
+    /// Prompt text with the placeholders listed above
+    pub template: String,
+    pub output: PromptOutput,
+    /// Program and arguments for `output = "command"`
+    pub command: Vec<String>,
+    /// Directory for `output = "file"` (default: `prompts` in the events dir)
+    pub dir: Option<PathBuf>,
+}
+
+impl Default for PromptConfig {
+    fn default() -> Self {
+        Self {
+            template: "Task {id} ({name}) failed.\n\nLast error: {error}\nSuggestion: {suggestion}\n\nFix the cause and finish the task.\n\n{context}".to_string(),
+            output: PromptOutput::default(),
+            command: Vec::new(),
+            dir: None,
+        }
+    }
+}
+
+/// Fill in `template` for `task_id`, or None if the plan has no such task
+pub fn render(template: &str, state: &DashboardState, task_id: &str) -> Option<String> {
+    let task = state.task(task_id)?;
+    let (pi, _) = state.task_position(task_id)?;
+    let error = state.recent_errors.iter().rfind(|e| e.task_id == task_id);
+
+    let mut values = vec![
+        ("id", task.id.clone()),
+        ("name", task.name.clone()),
+        ("phase", state.phases[pi].id.clone()),
+        ("status", task.status.label().to_string()),
+        (
+            "agent",
+            state.worker_for(task).unwrap_or_default().to_string(),
+        ),
+        (
+            "error",
+            error
+                .map(|e| e.message.trim().to_string())
+                .unwrap_or_default(),
+        ),
+        (
+            "category",
+            error
+                .map(|e| format!("{:?}", e.category))
+                .unwrap_or_default(),
+        ),
+        (
+            "suggestion",
+            error
+                .map(|e| e.display_suggestion().to_string())
+                .unwrap_or_default(),
+        ),
+        ("attempts", task.attempts.to_string()),
+        ("body", task.body.trim().to_string()),
+    ];
+    if template.contains("{context}") {
+        values.push(("context", context::bundle(state, task_id)?));
+    }
+
+    let mut out = template.to_string();
+    for (key, value) in values {
+        out = out.replace(&format!("{{{key}}}"), &value);
+    }
+    Some(out)
+}
+
+/// Write a prompt to `<dir>/<task-id>.md` and return the path
+pub fn save(dir: &Path, task_id: &str, prompt: &str) -> std::io::Result<PathBuf> {
+    let path = dir.join(format!("{}.md", crate::data::control::file_stem(task_id)));
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(&path, prompt)?;
+    Ok(path)
+}
+
+/// Start `command` and feed it the prompt on its stdin from a background
+/// thread, which also reaps it and logs a failed exit, so a slow reader never
+/// stalls the UI.
+pub fn pipe(command: &[String], prompt: &str) -> Result<(), String> {
+    let (program, args) = command.split_first().ok_or("prompt command is empty")?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("failed to run {program}: {e}"))?;
+    let stdin = child.stdin.take();
+    let prompt = prompt.to_string();
+    let program = program.clone();
+    std::thread::spawn(move || {
+        if let Some(mut stdin) = stdin {
+            if let Err(e) = stdin.write_all(prompt.as_bytes()) {
+                tracing::warn!("failed to write prompt to {program}: {e}");
+            }
+        }
+        match child.wait() {
+            Ok(status) if !status.success() => {
+                tracing::warn!("prompt command {program} exited with {status}")
+            }
+            Err(e) => tracing::warn!("prompt command {program}: {e}"),
+            Ok(_) => {}
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::data::hook_parser::parse_hook_events;
+
+    fn failed_state() -> DashboardState {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 2: API\n### [Failed] T2.1: Handlers\n- attempts: 1\n",
+        )
+        .unwrap();
+        state.update_from_events(&parse_hook_events(
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:00:09Z","agent_id":"backend","task_id":"T2.1","session_id":"s1","error_message":"connection refused"}"#,
+        ).events);
+        state
+    }
+
+    #[test]
+    fn template_placeholders_are_filled() {
+        let state = failed_state();
+        let prompt = render(
+            "Fix task {id}: {name} in {phase} ({attempts} tries). Last error: {error} [{category}] {unknown}",
+            &state,
+            "T2.1",
+        )
+        .unwrap();
+        assert_eq!(
+            prompt,
+            "Fix task T2.1: Handlers in P2 (1 tries). Last error: connection refused [Network] {unknown}"
+        );
+        assert!(render("{id}", &state, "T9").is_none());
+
+        let default = render(&PromptConfig::default().template, &state, "T2.1").unwrap();
+        assert!(default.starts_with("Task T2.1 (Handlers) failed."));
+        assert!(default.contains("# T2.1: Handlers"));
+    }
+
+    #[test]
+    fn prompt_section_parses() {
+        let config = Config::from_toml_str(
+            "[prompt]\ntemplate = \"Fix {id}\"\noutput = \"command\"\ncommand = [\"claude\", \"-p\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.prompt.template, "Fix {id}");
+        assert_eq!(config.prompt.output, PromptOutput::Command);
+        assert_eq!(config.prompt.command, vec!["claude", "-p"]);
+        assert_eq!(Config::default().prompt.output, PromptOutput::Clipboard);
+    }
+
+    #[test]
+    fn save_and_pipe() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = save(&tmp.path().join("prompts"), "T2.1", "Fix it").unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "Fix it");
+
+        let out = tmp.path().join("piped");
+        let command = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            format!("cat > {}", out.display()),
+        ];
+        pipe(&command, "Fix it").unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while std::fs::read_to_string(&out).map_or(true, |s| s != "Fix it") {
+            assert!(std::time::Instant::now() < deadline, "command never ran");
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert!(pipe(&[], "Fix it").is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! Embedded Rhai hooks
+//!
+//! With the `scripting` feature, `[scripts] rhai` names a Rhai script that
+//! runs inside the board rather than as a separate process. It may define
+//! any of these functions:
+//!
+//! ```text
+//! fn on_task_failed(event) { ... }     // event.task_id, task_name, phase_id
+//! fn on_phase_complete(event) { ... }  // event.phase_id, phase_name
+//! fn statusbar(board) { `${board.completed}/${board.total} done` }
+//! ```
+//!
+//! Events carry the same fields as the JSON command scripts get on stdin.
+//! What a hook returns or `print`s goes to the debug log; `statusbar`
+//! returns the status bar segment, given a summary of the board. Each call
+//! is capped in operations so a runaway loop cannot freeze the UI.
+
+use std::path::Path;
+
+use rhai::{Dynamic, Engine, Scope, AST};
+use serde::Serialize;
+
+use crate::data::state::{AgentStatus, DashboardState};
+use crate::scripts::ScriptEvent;
+
+/// Operations a single hook call may run
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// What `statusbar` sees of the board
+#[derive(Debug, Clone, Serialize)]
+struct BoardSummary {
+    total: usize,
+    completed: usize,
+    failed: usize,
+    /// Weighted progress, 0 to 100
+    progress: f64,
+    running_agents: usize,
+    errors: usize,
+}
+
+impl BoardSummary {
+    fn of(state: &DashboardState) -> Self {
+        Self {
+            total: state.total_tasks,
+            completed: state.completed_tasks,
+            failed: state.failed_tasks,
+            progress: (f64::from(state.overall_progress) * 100.0).round(),
+            running_agents: state
+                .agents
+                .values()
+                .filter(|a| a.status == AgentStatus::Running)
+                .count(),
+            errors: state.recent_errors.len(),
+        }
+    }
+}
+
+/// A compiled hook script
+pub struct ScriptEngine {
+    engine: Engine,
+    ast: AST,
+}
+
+impl std::fmt::Debug for ScriptEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScriptEngine").finish_non_exhaustive()
+    }
+}
+
+impl ScriptEngine {
+    /// Compile the script at `path`
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Self::compile(&source).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Compile a script from source
+    pub fn compile(source: &str) -> Result<Self, String> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.on_print(|text| tracing::info!(target: "scripts", "{text}"));
+        engine.on_debug(|text, _, pos| tracing::debug!(target: "scripts", "{pos}: {text}"));
+        let ast = engine.compile(source).map_err(|e| e.to_string())?;
+        Ok(Self { engine, ast })
+    }
+
+    fn defines(&self, name: &str) -> bool {
+        self.ast
+            .iter_functions()
+            .any(|f| f.name == name && f.params.len() == 1)
+    }
+
+    /// Call `name(arg)` if the script defines it
+    fn call(&self, name: &str, arg: Dynamic) -> Option<Result<Dynamic, String>> {
+        if !self.defines(name) {
+            return None;
+        }
+        Some(
+            self.engine
+                .call_fn::<Dynamic>(&mut Scope::new(), &self.ast, name, (arg,))
+                .map_err(|e| e.to_string()),
+        )
+    }
+
+    /// Run the hook for `event`, if the script has one; a returned value is
+    /// logged
+    pub fn fire(&self, event: &ScriptEvent) {
+        let name = match event {
+            ScriptEvent::TaskFailed { .. } => "on_task_failed",
+            ScriptEvent::PhaseComplete { .. } => "on_phase_complete",
+            ScriptEvent::TaskTransition { .. } => return,
+        };
+        let arg = match rhai::serde::to_dynamic(event) {
+            Ok(arg) => arg,
+            Err(e) => {
+                tracing::warn!("{name}: {e}");
+                return;
+            }
+        };
+        match self.call(name, arg) {
+            Some(Ok(out)) if !out.is_unit() => tracing::info!(target: "scripts", "{name}: {out}"),
+            Some(Err(e)) => tracing::warn!("{name} failed: {e}"),
+            _ => {}
+        }
+    }
+
+    /// The status bar segment from `statusbar(board)`, if the script has one
+    /// and it returned something to show
+    pub fn segment(&self, state: &DashboardState) -> Option<String> {
+        let board = rhai::serde::to_dynamic(BoardSummary::of(state)).ok()?;
+        match self.call("statusbar", board)? {
+            Ok(out) if out.is_unit() => None,
+            Ok(out) => Some(out.to_string().trim().to_string()).filter(|s| !s.is_empty()),
+            Err(e) => {
+                tracing::warn!("statusbar failed: {e}");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statusbar_gets_a_board_summary() {
+        let engine = ScriptEngine::compile(
+            r#"fn statusbar(board) { `${board.completed}/${board.total} done, ${board.failed} failed` }"#,
+        )
+        .unwrap();
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [x] T1: a\n### [Failed] T2: b\n### [ ] T3: c\n",
+        )
+        .unwrap();
+        assert_eq!(
+            engine.segment(&state).as_deref(),
+            Some("1/3 done, 1 failed")
+        );
+    }
+
+    #[test]
+    fn hooks_are_optional_and_get_the_event() {
+        let engine = ScriptEngine::compile(
+            r#"
+            fn on_task_failed(event) { if event.task_id != "T2" { throw "wrong task"; } }
+            "#,
+        )
+        .unwrap();
+        let event = ScriptEvent::TaskFailed {
+            task_id: "T2".to_string(),
+            task_name: "b".to_string(),
+            phase_id: "P1".to_string(),
+        };
+        let arg = rhai::serde::to_dynamic(&event).unwrap();
+        assert!(matches!(engine.call("on_task_failed", arg), Some(Ok(_))));
+        assert!(engine.call("on_phase_complete", Dynamic::UNIT).is_none());
+        assert_eq!(engine.segment(&DashboardState::default()), None);
+    }
+
+    #[test]
+    fn runaway_scripts_are_stopped() {
+        let engine = ScriptEngine::compile("fn statusbar(board) { loop {} }").unwrap();
+        assert_eq!(engine.segment(&DashboardState::default()), None);
+        assert!(ScriptEngine::compile("fn broken(").is_err());
+    }
+}
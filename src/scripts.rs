@@ -0,0 +1,590 @@
+//! Script hooks
+//!
+//! Commands from the `[scripts]` config section react to dashboard changes
+//! without recompiling: `on_task_failed` and `on_phase_complete` run when a
+//! reload shows that transition, `[[scripts.on_transition]]` triggers run on
+//! any matching task status change, and the first stdout line of `statusbar`
+//! is shown as a status bar segment. Scripts run as separate processes on a
+//! background thread, like triage, so any language works; the event arrives
+//! as `SCB_*` environment variables and as JSON on stdin. What a transition
+//! trigger printed comes back through [`Scripts::take_runs`] for the logs.
+//! With the `scripting` feature, `rhai` names a script whose functions handle
+//! the same events in-process (see [`crate::scripting`]).
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::filter;
+use crate::data::state::DashboardState;
+use crate::data::statuses::{self, StatusDef};
+use crate::data::tasks_parser::{ParsedPhase, TaskStatus};
+use crate::process;
+#[cfg(feature = "scripting")]
+use crate::scripting::ScriptEngine;
+
+/// `[scripts]` section; each command is a program plus arguments, empty to
+/// disable it
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ScriptsConfig {
+    pub on_task_failed: Vec<String>,
+    pub on_phase_complete: Vec<String>,
+    /// Commands run when a task's status changes
+    pub on_transition: Vec<TransitionTrigger>,
+    /// Re-run after every tasks reload; its first output line is the segment
+    pub statusbar: Vec<String>,
+    /// Kill a script that has not finished within this many seconds
+    pub timeout_secs: u64,
+    /// Rhai script defining `on_task_failed`, `on_phase_complete` or
+    /// `statusbar` functions (needs the `scripting` feature)
+    pub rhai: Option<PathBuf>,
+}
+
+impl Default for ScriptsConfig {
+    fn default() -> Self {
+        Self {
+            on_task_failed: Vec::new(),
+            on_phase_complete: Vec::new(),
+            on_transition: Vec::new(),
+            statusbar: Vec::new(),
+            timeout_secs: 10,
+            rhai: None,
+        }
+    }
+}
+
+/// `[[scripts.on_transition]]`: a command for tasks moving between statuses
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TransitionTrigger {
+    /// Status the task left, e.g. "InProgress" (any when unset)
+    pub from: Option<String>,
+    /// Status the task entered, e.g. "failed" (any when unset)
+    pub to: Option<String>,
+    /// Program plus arguments; `{task_id}`, `{task_name}`, `{phase_id}`,
+    /// `{from}` and `{to}` are filled in
+    pub command: Vec<String>,
+}
+
+impl TransitionTrigger {
+    fn matches(&self, from: &TaskStatus, to: &TaskStatus) -> bool {
+        self.from
+            .as_deref()
+            .map_or(true, |name| filter::status_matches(name, from))
+            && self
+                .to
+                .as_deref()
+                .map_or(true, |name| filter::status_matches(name, to))
+    }
+}
+
+/// A finished transition trigger and what it printed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggerRun {
+    pub task_id: String,
+    /// The command as run, placeholders filled in
+    pub command: String,
+    /// Stdout, or why the command failed
+    pub result: Result<String, String>,
+}
+
+/// A state change scripts can react to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ScriptEvent {
+    TaskFailed {
+        task_id: String,
+        task_name: String,
+        phase_id: String,
+    },
+    PhaseComplete {
+        phase_id: String,
+        phase_name: String,
+    },
+    TaskTransition {
+        task_id: String,
+        task_name: String,
+        phase_id: String,
+        #[serde(serialize_with = "label")]
+        from: TaskStatus,
+        #[serde(serialize_with = "label")]
+        to: TaskStatus,
+    },
+}
+
+fn label<S: serde::Serializer>(status: &TaskStatus, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(status.label())
+}
+
+impl ScriptEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::TaskFailed { .. } => "task_failed",
+            Self::PhaseComplete { .. } => "phase_complete",
+            Self::TaskTransition { .. } => "task_transition",
+        }
+    }
+
+    fn env(&self) -> Vec<(&'static str, &str)> {
+        match self {
+            Self::TaskFailed {
+                task_id,
+                task_name,
+                phase_id,
+            } => vec![
+                ("SCB_TASK_ID", task_id),
+                ("SCB_TASK_NAME", task_name),
+                ("SCB_PHASE_ID", phase_id),
+            ],
+            Self::PhaseComplete {
+                phase_id,
+                phase_name,
+            } => vec![("SCB_PHASE_ID", phase_id), ("SCB_PHASE_NAME", phase_name)],
+            Self::TaskTransition {
+                task_id,
+                task_name,
+                phase_id,
+                from,
+                to,
+            } => vec![
+                ("SCB_TASK_ID", task_id),
+                ("SCB_TASK_NAME", task_name),
+                ("SCB_PHASE_ID", phase_id),
+                ("SCB_FROM", from.label()),
+                ("SCB_TO", to.label()),
+            ],
+        }
+    }
+
+    /// `command` with the event's `{placeholders}` filled in
+    fn expand(&self, command: &[String]) -> Vec<String> {
+        let Self::TaskTransition {
+            task_id,
+            task_name,
+            phase_id,
+            from,
+            to,
+        } = self
+        else {
+            return command.to_vec();
+        };
+        command
+            .iter()
+            .map(|arg| {
+                arg.replace("{task_id}", task_id)
+                    .replace("{task_name}", task_name)
+                    .replace("{phase_id}", phase_id)
+                    .replace("{from}", from.label())
+                    .replace("{to}", to.label())
+            })
+            .collect()
+    }
+}
+
+fn phase_done(phase: &ParsedPhase, custom: &[StatusDef]) -> bool {
+    !phase.tasks.is_empty()
+        && phase
+            .tasks
+            .iter()
+            .all(|t| statuses::is_done(&t.status, custom))
+}
+
+/// Events implied by a reload from `before` to `after`: tasks that turned
+/// Failed and phases whose tasks are now all done
+pub fn detect(
+    before: &[ParsedPhase],
+    after: &[ParsedPhase],
+    custom: &[StatusDef],
+) -> Vec<ScriptEvent> {
+    let was_failed = |id: &str| {
+        before
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .any(|t| t.id == id && t.status == TaskStatus::Failed)
+    };
+    let mut events = Vec::new();
+    for phase in after {
+        for task in &phase.tasks {
+            if task.status == TaskStatus::Failed && !was_failed(&task.id) {
+                events.push(ScriptEvent::TaskFailed {
+                    task_id: task.id.clone(),
+                    task_name: task.name.clone(),
+                    phase_id: phase.id.clone(),
+                });
+            }
+        }
+    }
+    for phase in after {
+        let was_done = before
+            .iter()
+            .any(|p| p.id == phase.id && phase_done(p, custom));
+        if phase_done(phase, custom) && !was_done {
+            events.push(ScriptEvent::PhaseComplete {
+                phase_id: phase.id.clone(),
+                phase_name: phase.name.clone(),
+            });
+        }
+    }
+    events
+}
+
+/// Status changes of tasks present before and after a reload
+pub fn transitions(before: &[ParsedPhase], after: &[ParsedPhase]) -> Vec<ScriptEvent> {
+    let mut events = Vec::new();
+    for phase in after {
+        for task in &phase.tasks {
+            let Some(old) = before
+                .iter()
+                .flat_map(|p| &p.tasks)
+                .find(|t| t.id == task.id)
+            else {
+                continue;
+            };
+            if old.status != task.status {
+                events.push(ScriptEvent::TaskTransition {
+                    task_id: task.id.clone(),
+                    task_name: task.name.clone(),
+                    phase_id: phase.id.clone(),
+                    from: old.status.clone(),
+                    to: task.status.clone(),
+                });
+            }
+        }
+    }
+    events
+}
+
+/// Run a script to completion (blocking) and return its stdout
+pub fn run_script(
+    command: &[String],
+    timeout: Duration,
+    event: Option<&ScriptEvent>,
+) -> Result<String, String> {
+    let (program, args) = command.split_first().ok_or("script command is empty")?;
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    let mut input = Vec::new();
+    if let Some(event) = event {
+        cmd.env("SCB_EVENT", event.name()).envs(event.env());
+        input = serde_json::to_vec(event).map_err(|e| e.to_string())?;
+        input.push(b'\n');
+    }
+    process::run(&mut cmd, input, timeout)
+}
+
+/// Background runner for the configured scripts
+pub struct Scripts {
+    config: ScriptsConfig,
+    /// Latest statusbar segment; None until the script has answered
+    segment: Option<String>,
+    segment_running: bool,
+    tx: mpsc::Sender<Option<String>>,
+    rx: mpsc::Receiver<Option<String>>,
+    runs_tx: mpsc::Sender<TriggerRun>,
+    runs_rx: mpsc::Receiver<TriggerRun>,
+    #[cfg(feature = "scripting")]
+    engine: Option<ScriptEngine>,
+}
+
+impl Scripts {
+    /// Create a runner, or None when no script is configured
+    pub fn new(config: ScriptsConfig) -> Option<Self> {
+        if config.on_task_failed.is_empty()
+            && config.on_phase_complete.is_empty()
+            && config.on_transition.is_empty()
+            && config.statusbar.is_empty()
+            && config.rhai.is_none()
+        {
+            return None;
+        }
+        #[cfg(feature = "scripting")]
+        let engine = config
+            .rhai
+            .as_deref()
+            .and_then(|path| match ScriptEngine::load(path) {
+                Ok(engine) => Some(engine),
+                Err(e) => {
+                    tracing::warn!("rhai script not loaded: {e}");
+                    None
+                }
+            });
+        #[cfg(not(feature = "scripting"))]
+        if let Some(path) = &config.rhai {
+            tracing::warn!(
+                "{} not loaded: built without the scripting feature",
+                path.display()
+            );
+        }
+        let (tx, rx) = mpsc::channel();
+        let (runs_tx, runs_rx) = mpsc::channel();
+        Some(Self {
+            config,
+            segment: None,
+            segment_running: false,
+            tx,
+            rx,
+            runs_tx,
+            runs_rx,
+            #[cfg(feature = "scripting")]
+            engine,
+        })
+    }
+
+    /// Run the hooks for these events in the background
+    pub fn fire(&self, events: &[ScriptEvent]) {
+        for event in events {
+            #[cfg(feature = "scripting")]
+            if let Some(engine) = &self.engine {
+                engine.fire(event);
+            }
+            let command = match event {
+                ScriptEvent::TaskFailed { .. } => &self.config.on_task_failed,
+                ScriptEvent::PhaseComplete { .. } => &self.config.on_phase_complete,
+                ScriptEvent::TaskTransition { from, to, .. } => {
+                    self.fire_triggers(event, from, to);
+                    continue;
+                }
+            };
+            if command.is_empty() {
+                continue;
+            }
+            let command = command.clone();
+            let event = event.clone();
+            let timeout = Duration::from_secs(self.config.timeout_secs);
+            std::thread::spawn(move || {
+                let _ = run_script(&command, timeout, Some(&event));
+            });
+        }
+    }
+
+    /// Run the transition triggers matching `from` -> `to`; their output is
+    /// collected by [`take_runs`](Self::take_runs)
+    fn fire_triggers(&self, event: &ScriptEvent, from: &TaskStatus, to: &TaskStatus) {
+        let ScriptEvent::TaskTransition { task_id, .. } = event else {
+            return;
+        };
+        for trigger in &self.config.on_transition {
+            if trigger.command.is_empty() || !trigger.matches(from, to) {
+                continue;
+            }
+            let command = event.expand(&trigger.command);
+            let event = event.clone();
+            let task_id = task_id.clone();
+            let timeout = Duration::from_secs(self.config.timeout_secs);
+            let tx = self.runs_tx.clone();
+            std::thread::spawn(move || {
+                let result = run_script(&command, timeout, Some(&event));
+                let _ = tx.send(TriggerRun {
+                    task_id,
+                    command: command.join(" "),
+                    result,
+                });
+            });
+        }
+    }
+
+    /// Transition triggers that finished since the last call
+    pub fn take_runs(&mut self) -> Vec<TriggerRun> {
+        self.runs_rx.try_iter().collect()
+    }
+
+    /// Start the statusbar script unless it is already running. Without a
+    /// statusbar command, the Rhai script's `statusbar(board)` gives the
+    /// segment for `state` right away.
+    pub fn refresh_segment(&mut self, state: &DashboardState) {
+        #[cfg(feature = "scripting")]
+        if self.config.statusbar.is_empty() {
+            if let Some(engine) = &self.engine {
+                self.segment = engine.segment(state);
+            }
+        }
+        #[cfg(not(feature = "scripting"))]
+        let _ = state;
+        if self.config.statusbar.is_empty() || self.segment_running {
+            return;
+        }
+        self.segment_running = true;
+        let command = self.config.statusbar.clone();
+        let timeout = Duration::from_secs(self.config.timeout_secs);
+        let tx = self.tx.clone();
+        std::thread::spawn(move || {
+            let line = run_script(&command, timeout, None)
+                .ok()
+                .and_then(|out| out.lines().next().map(|l| l.trim().to_string()))
+                .filter(|l| !l.is_empty());
+            let _ = tx.send(line);
+        });
+    }
+
+    /// Collect a finished statusbar run. Returns true if the segment changed.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(line) = self.rx.try_recv() {
+            self.segment_running = false;
+            changed |= line != self.segment;
+            self.segment = line;
+        }
+        changed
+    }
+
+    pub fn segment(&self) -> Option<&str> {
+        self.segment.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::tasks_parser::parse_tasks_md;
+    use std::time::Instant;
+
+    fn sh(script: &str) -> Vec<String> {
+        vec!["sh".into(), "-c".into(), script.into()]
+    }
+
+    #[test]
+    fn detects_failures_and_completed_phases() {
+        let before = parse_tasks_md(
+            "# Phase 1: A\n### [x] T1: a\n### [/] T2: b\n# Phase 2: B\n### [Failed] T3: c\n",
+        )
+        .unwrap();
+        let after =
+            parse_tasks_md("# Phase 1: A\n### [x] T1: a\n### [x] T2: b\n# Phase 2: B\n### [Failed] T3: c\n### [Failed] T4: d\n")
+                .unwrap();
+        assert_eq!(
+            detect(&before, &after, &[]),
+            vec![
+                ScriptEvent::TaskFailed {
+                    task_id: "T4".to_string(),
+                    task_name: "d".to_string(),
+                    phase_id: "P2".to_string()
+                },
+                ScriptEvent::PhaseComplete {
+                    phase_id: "P1".to_string(),
+                    phase_name: "A".to_string()
+                },
+            ]
+        );
+        assert!(detect(&after, &after, &[]).is_empty());
+    }
+
+    #[test]
+    fn script_gets_event_in_env_and_stdin() {
+        let event = ScriptEvent::PhaseComplete {
+            phase_id: "P1".to_string(),
+            phase_name: "Setup".to_string(),
+        };
+        let out = run_script(
+            &sh("printf '%s %s ' \"$SCB_EVENT\" \"$SCB_PHASE_ID\"; cat"),
+            Duration::from_secs(5),
+            Some(&event),
+        )
+        .unwrap();
+        assert_eq!(
+            out.trim(),
+            r#"phase_complete P1 {"event":"phase_complete","phase_id":"P1","phase_name":"Setup"}"#
+        );
+    }
+
+    #[test]
+    fn large_script_output_does_not_stall() {
+        let out = run_script(
+            &sh("head -c 200000 /dev/zero | tr '\\0' x"),
+            Duration::from_secs(5),
+            None,
+        )
+        .unwrap();
+        assert_eq!(out.len(), 200_000);
+    }
+
+    #[test]
+    fn transitions_list_changed_tasks() {
+        let before = parse_tasks_md("# Phase 1: A\n### [/] T1: a\n### [ ] T2: b\n").unwrap();
+        let after =
+            parse_tasks_md("# Phase 1: A\n### [Failed] T1: a\n### [ ] T2: b\n### [x] T3: c\n")
+                .unwrap();
+        assert_eq!(
+            transitions(&before, &after),
+            vec![ScriptEvent::TaskTransition {
+                task_id: "T1".to_string(),
+                task_name: "a".to_string(),
+                phase_id: "P1".to_string(),
+                from: TaskStatus::InProgress,
+                to: TaskStatus::Failed,
+            }]
+        );
+    }
+
+    #[test]
+    fn matching_trigger_runs_with_placeholders_and_reports_output() {
+        let mut scripts = Scripts::new(ScriptsConfig {
+            on_transition: vec![
+                TransitionTrigger {
+                    to: Some("failed".to_string()),
+                    command: sh("echo collect {task_id} $SCB_FROM"),
+                    ..Default::default()
+                },
+                TransitionTrigger {
+                    to: Some("completed".to_string()),
+                    command: sh("echo never"),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        })
+        .unwrap();
+        scripts.fire(&[ScriptEvent::TaskTransition {
+            task_id: "T1".to_string(),
+            task_name: "a".to_string(),
+            phase_id: "P1".to_string(),
+            from: TaskStatus::InProgress,
+            to: TaskStatus::Failed,
+        }]);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut runs = Vec::new();
+        while runs.is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+            runs = scripts.take_runs();
+        }
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].task_id, "T1");
+        assert_eq!(runs[0].command, "sh -c echo collect T1 $SCB_FROM");
+        assert_eq!(runs[0].result.as_deref(), Ok("collect T1 InProgress\n"));
+    }
+
+    #[test]
+    fn statusbar_segment_is_first_line() {
+        let mut scripts = Scripts::new(ScriptsConfig {
+            statusbar: sh("echo 'cost $4.20'; echo ignored"),
+            ..Default::default()
+        })
+        .unwrap();
+        scripts.refresh_segment(&DashboardState::default());
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !scripts.poll() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(scripts.segment(), Some("cost $4.20"));
+        assert!(Scripts::new(ScriptsConfig::default()).is_none());
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn rhai_statusbar_gives_the_segment_without_a_command() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let script = tmp.path().join("hooks.rhai");
+        std::fs::write(&script, "fn statusbar(board) { `${board.completed} done` }").unwrap();
+        let mut scripts = Scripts::new(ScriptsConfig {
+            rhai: Some(script),
+            ..Default::default()
+        })
+        .unwrap();
+        let state = DashboardState::from_tasks_content("# Phase 1: A\n### [x] T1: a\n").unwrap();
+        scripts.refresh_segment(&state);
+        assert_eq!(scripts.segment(), Some("1 done"));
+    }
+}
@@ -0,0 +1,97 @@
+//! Fatal error banner
+//!
+//! One-line, full-width alert shown above the dashboard while a Fatal error
+//! is outstanding. Dismissed with `b` until the next Fatal error arrives.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::data::state::ErrorRecord;
+
+/// Banner for the most recent Fatal error
+pub struct FatalBanner<'a> {
+    error: &'a ErrorRecord,
+}
+
+impl<'a> FatalBanner<'a> {
+    pub fn new(error: &'a ErrorRecord) -> Self {
+        Self { error }
+    }
+
+    pub fn build_line(&self) -> Line<'static> {
+        let base = Style::default().bg(Color::Red).fg(Color::White);
+        Line::from(vec![
+            Span::styled(" FATAL ", base.add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!(
+                    " {} ({}): {} ",
+                    self.error.task_id,
+                    self.error.agent_id,
+                    self.error.headline()
+                ),
+                base,
+            ),
+            Span::styled(
+                format!("- {} ", self.error.display_suggestion()),
+                base.add_modifier(Modifier::ITALIC),
+            ),
+            Span::styled("[b] dismiss ", base.fg(Color::Gray)),
+        ])
+    }
+}
+
+impl<'a> Widget for FatalBanner<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let style = Style::default().bg(Color::Red);
+        Paragraph::new(self.build_line())
+            .style(style)
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rules::{ErrorCategory, Severity};
+    use chrono::Utc;
+
+    fn fatal() -> ErrorRecord {
+        ErrorRecord {
+            agent_id: "backend".to_string(),
+            task_id: "T1".into(),
+            message: "out of memory".to_string(),
+            category: ErrorCategory::Runtime,
+            severity: Severity::Fatal,
+            retryable: false,
+            suggestion: "Reduce memory usage",
+            hint: None,
+            timestamp: Utc::now(),
+            cooldown_until: None,
+            event_index: 0,
+        }
+    }
+
+    #[test]
+    fn banner_shows_task_message_and_suggestion() {
+        let err = fatal();
+        let line = FatalBanner::new(&err).build_line();
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.contains("FATAL"));
+        assert!(text.contains("T1 (backend): out of memory"));
+        assert!(text.contains("Reduce memory usage"));
+    }
+
+    #[test]
+    fn banner_renders_red_background() {
+        let err = fatal();
+        let area = Rect::new(0, 0, 60, 1);
+        let mut buf = Buffer::empty(area);
+        FatalBanner::new(&err).render(area, &mut buf);
+        assert_eq!(buf[(0, 0)].bg, Color::Red);
+    }
+}
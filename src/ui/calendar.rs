@@ -0,0 +1,278 @@
+//! Week calendar
+//!
+//! Tasks with a `due` date laid out by day: overdue open tasks first, then
+//! each of the next seven days starting today, then a count of later and
+//! undated tasks. An open task whose expected finish (see
+//! [`crate::data::deps::expected_finish`]) falls after its due date is
+//! marked at risk. Complements the dependency-driven gantt with the dates
+//! the plan promised.
+
+use chrono::{Duration, NaiveDate};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::data::deps;
+use crate::data::state::DashboardState;
+use crate::data::statuses;
+use crate::data::tasks_parser::ParsedTask;
+
+/// Days shown, today included
+const DAYS: i64 = 7;
+
+/// Where a task stands against its due date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DueState {
+    Done,
+    /// Open and due before today
+    Overdue,
+    /// Open and expected to finish after its due date
+    AtRisk,
+    OnTrack,
+}
+
+/// A task with a due date
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scheduled {
+    pub task_id: String,
+    pub name: String,
+    pub due: NaiveDate,
+    /// Expected finish day, for open tasks once a task has finished
+    pub eta: Option<NaiveDate>,
+    pub state: DueState,
+}
+
+/// Every shown task with a due date, soonest first, with `today` in the
+/// display timezone
+pub fn schedule(state: &DashboardState) -> (NaiveDate, Vec<Scheduled>) {
+    let today = state.timezone.naive(state.now()).date();
+    let mut finishes = deps::Finishes::new(state);
+    let mut tasks: Vec<Scheduled> = state
+        .shown_phases()
+        .flat_map(|(_, p)| state.shown_tasks(p))
+        .filter_map(|(_, task)| scheduled(state, task, today, &mut finishes))
+        .collect();
+    tasks.sort_by_key(|t| t.due);
+    (today, tasks)
+}
+
+fn scheduled(
+    state: &DashboardState,
+    task: &ParsedTask,
+    today: NaiveDate,
+    finishes: &mut deps::Finishes,
+) -> Option<Scheduled> {
+    let due = task.due?;
+    let done = statuses::is_done(&task.status, &state.custom_statuses);
+    let eta = (!done)
+        .then(|| finishes.get(&task.id))
+        .flatten()
+        .map(|at| state.timezone.naive(at).date());
+    let due_state = if done {
+        DueState::Done
+    } else if due < today {
+        DueState::Overdue
+    } else if eta.is_some_and(|eta| eta > due) {
+        DueState::AtRisk
+    } else {
+        DueState::OnTrack
+    };
+    Some(Scheduled {
+        task_id: task.id.clone(),
+        name: task.name.clone(),
+        due,
+        eta,
+        state: due_state,
+    })
+}
+
+fn task_line(task: &Scheduled, today: NaiveDate) -> Line<'static> {
+    let (tag, color) = match task.state {
+        DueState::Done => ("done".to_string(), Color::DarkGray),
+        DueState::Overdue => (
+            format!("OVERDUE {}d", (today - task.due).num_days()),
+            Color::Red,
+        ),
+        DueState::AtRisk => (
+            format!(
+                "AT RISK eta {}",
+                task.eta
+                    .map_or(String::new(), |d| d.format("%m-%d").to_string())
+            ),
+            Color::Yellow,
+        ),
+        DueState::OnTrack => (String::new(), Color::Green),
+    };
+    let name_style = if task.state == DueState::Done {
+        Style::default().fg(Color::DarkGray)
+    } else {
+        Style::default()
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("   {:<8} ", task.task_id),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(task.name.clone(), name_style),
+        Span::styled(
+            if tag.is_empty() {
+                tag
+            } else {
+                format!("  {tag}")
+            },
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+    ])
+}
+
+/// Overdue tasks, one heading per day of the week ahead with its tasks,
+/// and a footer counting later and undated tasks
+pub fn build_calendar_lines(state: &DashboardState) -> Vec<Line<'static>> {
+    let (today, tasks) = schedule(state);
+    if tasks.is_empty() {
+        return vec![Line::raw(
+            "  No task has a due date (- **due**: 2026-03-01)",
+        )];
+    }
+    let heading = Style::default().add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+
+    let overdue: Vec<&Scheduled> = tasks
+        .iter()
+        .filter(|t| t.state == DueState::Overdue)
+        .collect();
+    if !overdue.is_empty() {
+        lines.push(Line::styled(
+            format!(" Overdue ({})", overdue.len()),
+            heading.fg(Color::Red),
+        ));
+        lines.extend(overdue.iter().map(|t| task_line(t, today)));
+    }
+
+    for offset in 0..DAYS {
+        let day = today + Duration::days(offset);
+        let mut title = vec![Span::styled(
+            format!(" {}", day.format("%a %m-%d")),
+            heading,
+        )];
+        if offset == 0 {
+            title.push(Span::styled("  today", dim));
+        }
+        lines.push(Line::from(title));
+        lines.extend(
+            tasks
+                .iter()
+                .filter(|t| t.due == day)
+                .map(|t| task_line(t, today)),
+        );
+    }
+
+    let later = tasks
+        .iter()
+        .filter(|t| t.due >= today + Duration::days(DAYS))
+        .count();
+    let undated = state
+        .shown_phases()
+        .flat_map(|(_, p)| state.shown_tasks(p))
+        .filter(|(_, t)| t.due.is_none())
+        .count();
+    let at_risk = tasks.iter().filter(|t| t.state == DueState::AtRisk).count();
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        format!(" Later {later} · no due date {undated} · at risk {at_risk}"),
+        dim,
+    ));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::state::TaskTiming;
+    use crate::data::timezone::DisplayZone;
+
+    const PLAN: &str = "\
+# Phase 1: Core
+
+### [x] T1: Warmup
+- due: 2026-02-08
+
+### [ ] T2: Late
+- due: 2026-02-07
+
+### [InProgress] T3: Tight
+- due: 2026-02-09
+
+### [ ] T4: Comfortable
+- due: 2026-02-12
+
+### [ ] T5: Far
+- due: 2026-03-01
+
+### [ ] T6: Someday
+";
+
+    fn state() -> DashboardState {
+        let mut state = DashboardState::from_tasks_content(PLAN).unwrap();
+        state.timezone = DisplayZone::Utc;
+        state.scrub_at = Some("2026-02-09T12:00:00Z".parse().unwrap());
+        // T1 took two days, so T3 started this morning ends on the 11th
+        state.task_times.insert(
+            "T1".into(),
+            TaskTiming {
+                started_at: Some("2026-02-06T12:00:00Z".parse().unwrap()),
+                completed_at: Some("2026-02-08T12:00:00Z".parse().unwrap()),
+            },
+        );
+        state.task_times.insert(
+            "T3".into(),
+            TaskTiming {
+                started_at: Some("2026-02-09T09:00:00Z".parse().unwrap()),
+                completed_at: None,
+            },
+        );
+        state
+    }
+
+    #[test]
+    fn classifies_against_today_and_eta() {
+        let (today, tasks) = schedule(&state());
+        assert_eq!(today, NaiveDate::from_ymd_opt(2026, 2, 9).unwrap());
+        let states: Vec<(&str, DueState)> = tasks
+            .iter()
+            .map(|t| (t.task_id.as_str(), t.state))
+            .collect();
+        assert_eq!(
+            states,
+            [
+                ("T2", DueState::Overdue),
+                ("T1", DueState::Done),
+                ("T3", DueState::AtRisk),
+                ("T4", DueState::OnTrack),
+                ("T5", DueState::OnTrack),
+            ]
+        );
+    }
+
+    #[test]
+    fn lays_out_the_week() {
+        let text: Vec<String> = build_calendar_lines(&state())
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(text[0], " Overdue (1)");
+        assert_eq!(text[1], "   T2       Late  OVERDUE 2d");
+        assert_eq!(text[2], " Mon 02-09  today");
+        assert_eq!(text[3], "   T3       Tight  AT RISK eta 02-11");
+        assert!(text.contains(&" Thu 02-12".to_string()));
+        assert_eq!(text.last().unwrap(), " Later 1 · no due date 1 · at risk 1");
+    }
+
+    #[test]
+    fn plan_without_dates_explains_the_field() {
+        let state = DashboardState::from_tasks_content("# Phase 1: A\n### [ ] T1: A\n").unwrap();
+        assert!(build_calendar_lines(&state)[0].to_string().contains("due"));
+    }
+}
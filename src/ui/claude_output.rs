@@ -2,6 +2,7 @@
 //!
 //! Shows live agent activity: which agents are running, their current tools,
 //! and recent errors. Highlights the agent assigned to the currently selected task.
+//! The selected agent's latest tool output is shown as a collapsible section.
 
 use ratatui::{
     buffer::Buffer,
@@ -11,7 +12,13 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
 
-use crate::data::state::{AgentState, AgentStatus, DashboardState};
+use std::collections::HashSet;
+
+use crate::data::state::{AgentState, AgentStatus, DashboardState, Liveness};
+use crate::ui::timefmt::{ago, format_duration, humanize};
+
+/// Characters shown per tool output line
+const MAX_OUTPUT_LINE_CHARS: usize = 80;
 
 /// Agent activity panel widget
 pub struct AgentPanel<'a> {
@@ -20,6 +27,10 @@ pub struct AgentPanel<'a> {
     selected_agent: Option<&'a str>,
     focused: bool,
     selected_index: usize,
+    /// Agents with a stop request their hook has not picked up yet
+    stop_requested: Option<&'a HashSet<String>>,
+    /// Show the selected agent's latest tool output instead of a summary line
+    output_expanded: bool,
 }
 
 impl<'a> AgentPanel<'a> {
@@ -29,6 +40,8 @@ impl<'a> AgentPanel<'a> {
             selected_agent: None,
             focused: false,
             selected_index: 0,
+            stop_requested: None,
+            output_expanded: false,
         }
     }
 
@@ -47,7 +60,18 @@ impl<'a> AgentPanel<'a> {
         self
     }
 
-    fn build_lines(&self) -> Vec<Line<'static>> {
+    pub fn with_stop_requested(mut self, agents: &'a HashSet<String>) -> Self {
+        self.stop_requested = Some(agents);
+        self
+    }
+
+    pub fn with_output_expanded(mut self, expanded: bool) -> Self {
+        self.output_expanded = expanded;
+        self
+    }
+
+    /// Lines for a pane `height` rows tall
+    fn build_lines(&self, height: usize) -> Vec<Line<'static>> {
         if self.state.agents.is_empty() && self.selected_agent.is_none() {
             return vec![Line::styled(
                 " No agent activity",
@@ -70,105 +94,328 @@ impl<'a> AgentPanel<'a> {
             ]));
         }
 
-        let mut agents: Vec<&AgentState> = self.state.agents.values().collect();
-        agents.sort_by_key(|a| &a.agent_id);
+        let (mut agents, collapsed) = self.state.panel_agents();
+        // The selected task's worker goes first while browsing tasks; when
+        // focused, rows keep the order the selection index walks
+        if !self.focused {
+            agents.sort_by_key(|agent| !self.is_task_agent(agent));
+        }
+        let now = self.state.now();
+        let blocks: Vec<AgentBlock> = agents
+            .iter()
+            .enumerate()
+            .map(|(idx, agent)| AgentBlock {
+                idx,
+                urgent: agent.status != AgentStatus::Idle
+                    || self.is_task_agent(agent)
+                    || matches!(agent.liveness(now), Some(Liveness::Vanished { .. })),
+                lines: self.agent_lines(idx, agent),
+            })
+            .collect();
+        let reserved = lines.len() + usize::from(collapsed > 0);
+        lines.extend(self.fit_blocks(blocks, height.saturating_sub(reserved)));
+        if let (true, Some(after)) = (collapsed > 0, self.state.hide_idle_after) {
+            lines.push(Line::styled(
+                format!(
+                    " +{collapsed} idle agents collapsed (quiet > {})",
+                    humanize(after)
+                ),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
+        if lines.is_empty() {
+            lines.push(Line::styled(
+                " No agent activity",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
 
-        for (idx, agent) in agents.iter().enumerate() {
-            let is_selected = self.focused && idx == self.selected_index;
-            let is_highlighted = is_selected
-                || self
-                    .selected_agent
-                    .is_some_and(|name| agent.agent_id.contains(name));
+        lines
+    }
 
-            let (status_icon, status_color) = match agent.status {
-                AgentStatus::Running => (">>", Color::Green),
-                AgentStatus::Error => ("!!", Color::Red),
-                AgentStatus::Idle => ("--", Color::DarkGray),
-            };
+    /// Whether an agent is the selected task's worker
+    fn is_task_agent(&self, agent: &AgentState) -> bool {
+        self.selected_agent
+            .is_some_and(|name| agent.agent_id.contains(name))
+    }
 
-            let name_style = if is_highlighted {
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD)
-            };
+    /// Lines for one agent: its status line, latest error and, when
+    /// selected, its tool output
+    fn agent_lines(&self, idx: usize, agent: &AgentState) -> Vec<Line<'static>> {
+        let now = self.state.now();
+        let mut lines = Vec::new();
+        let is_selected = self.focused && idx == self.selected_index;
+        let is_highlighted = is_selected || self.is_task_agent(agent);
+
+        let liveness = agent.liveness(now);
+        let (status_icon, status_color) = match agent.status {
+            _ if matches!(liveness, Some(Liveness::Vanished { .. })) => ("??", Color::Red),
+            AgentStatus::Running => (">>", Color::Green),
+            AgentStatus::Error => ("!!", Color::Red),
+            AgentStatus::Idle => ("--", Color::DarkGray),
+        };
 
-            let prefix = if is_selected { ">" } else { " " };
-            let mut spans = vec![
-                Span::styled(
-                    format!("{prefix}{status_icon} "),
-                    Style::default().fg(status_color),
-                ),
-                Span::styled(agent.agent_id.clone(), name_style),
-            ];
+        let name_style = if is_highlighted {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD)
+        };
 
-            if let Some(ref task) = agent.current_task {
-                spans.push(Span::styled(
-                    format!(" [{task}]"),
-                    Style::default().fg(Color::Cyan),
-                ));
-            }
+        let prefix = if is_selected { ">" } else { " " };
+        let mut spans = vec![
+            Span::styled(
+                format!("{prefix}{status_icon} "),
+                Style::default().fg(status_color),
+            ),
+            Span::styled(agent.agent_id.clone(), name_style),
+        ];
 
-            if let Some(ref tool) = agent.current_tool {
+        if let Some(ref task) = agent.current_task {
+            spans.push(Span::styled(
+                format!(" [{task}]"),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+
+        if let Some(ref tool) = agent.current_tool {
+            spans.push(Span::styled(
+                format!(" -> {tool}"),
+                Style::default().fg(Color::Yellow),
+            ));
+            // Flag a call running far longer than this tool usually takes
+            if let Some(call) = agent
+                .tool_history
+                .iter()
+                .rfind(|c| c.ended_at.is_none() && c.tool == *tool)
+                .filter(|c| self.state.is_slow_call(c))
+            {
                 spans.push(Span::styled(
-                    format!(" -> {tool}"),
-                    Style::default().fg(Color::Yellow),
+                    format!(" {} SLOW", format_duration(call.duration(now))),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                 ));
             }
+        }
 
-            if agent.error_count > 0 {
+        match liveness {
+            Some(Liveness::Alive { since }) => {
+                if agent.current_tool.is_none() {
+                    spans.push(Span::styled(
+                        " thinking",
+                        Style::default().fg(Color::Magenta),
+                    ));
+                }
                 spans.push(Span::styled(
-                    format!(" ({} errs)", agent.error_count),
-                    Style::default().fg(Color::Red),
+                    format!(" \u{2665}{since}s"),
+                    Style::default().fg(Color::DarkGray),
                 ));
             }
+            Some(Liveness::Vanished { since }) => spans.push(Span::styled(
+                format!(" vanished {since}s"),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )),
+            None => {
+                if let Some(seen) = agent.last_seen {
+                    spans.push(Span::styled(
+                        format!(" {}", ago(seen, now)),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+            }
+        }
 
+        // Rate-limit cooldown counting down to when the agent may resume
+        if let Some(left) = agent.rate_limits.remaining(now) {
             spans.push(Span::styled(
-                format!(" ({}ev)", agent.event_count),
-                Style::default().fg(Color::DarkGray),
+                format!(" cooldown {}:{:02}", left / 60, left % 60),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
             ));
+        }
 
-            lines.push(Line::from(spans));
+        if self.stop_requested.is_some_and(|ids| {
+            ids.contains(&agent.agent_id)
+                || agent.members.values().any(|m| ids.contains(&m.agent_id))
+        }) {
+            spans.push(Span::styled(
+                " STOP requested",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
 
-            // Show most recent error for this agent (if any)
-            if let Some(err) = self
-                .state
-                .recent_errors
-                .iter()
-                .rev()
-                .find(|e| e.agent_id == agent.agent_id)
-            {
-                let retry_str = if err.retryable { "retry" } else { "no retry" };
-                let msg_short = if err.message.len() > 40 {
-                    format!("{}...", &err.message[..37])
-                } else {
-                    err.message.clone()
-                };
-                lines.push(Line::from(vec![
-                    Span::styled("    !! ", Style::default().fg(Color::Red)),
-                    Span::styled(msg_short, Style::default().fg(Color::Red)),
-                    Span::styled(
-                        format!(" → {} ({retry_str})", err.category),
-                        Style::default().fg(Color::DarkGray),
+        if agent.error_count > 0 {
+            spans.push(Span::styled(
+                format!(" ({} errs)", agent.error_count),
+                Style::default().fg(Color::Red),
+            ));
+        }
+
+        spans.push(Span::styled(
+            format!(" ({}ev)", agent.event_count),
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        lines.push(Line::from(spans));
+
+        // Show most recent error for this agent (if any)
+        if let Some(err) = self
+            .state
+            .recent_errors
+            .iter()
+            .rev()
+            .find(|e| e.agent_id == agent.agent_id)
+        {
+            let retry_str = if err.retryable { "retry" } else { "no retry" };
+            let headline = err.headline();
+            let msg_short = if headline.chars().count() > 40 {
+                format!("{}...", headline.chars().take(37).collect::<String>())
+            } else {
+                headline.to_string()
+            };
+            lines.push(Line::from(vec![
+                Span::styled("    !! ", Style::default().fg(Color::Red)),
+                Span::styled(msg_short, Style::default().fg(Color::Red)),
+                Span::styled(
+                    format!(
+                        " → {} ({retry_str}) {}",
+                        err.category,
+                        ago(err.timestamp, now)
                     ),
-                ]));
-            }
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
         }
 
-        if lines.is_empty() {
+        // Latest tool output of the selected agent, collapsible
+        if let Some(output) = agent.last_output.as_ref().filter(|_| is_selected) {
+            let tool = output.tool.as_deref().unwrap_or("tool");
+            let (marker, hint) = if self.output_expanded {
+                ("\u{25BE}", "")
+            } else {
+                ("\u{25B8}", ", Space to expand")
+            };
             lines.push(Line::styled(
-                " No agent activity",
+                format!(
+                    "    {marker} {tool} output ({} lines{hint})",
+                    output.lines.len()
+                ),
                 Style::default().fg(Color::DarkGray),
             ));
+            if self.output_expanded {
+                for line in &output.lines {
+                    let short: String = line.chars().take(MAX_OUTPUT_LINE_CHARS).collect();
+                    lines.push(Line::from(vec![
+                        Span::styled("      \u{2502} ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(short),
+                    ]));
+                }
+            }
+        }
+        lines
+    }
+
+    /// Keep the agents that fit in `room` rows. Focused, the list scrolls to
+    /// the selected agent so every agent stays reachable; unfocused, working
+    /// and failing agents go first and the rest are summarized.
+    fn fit_blocks(&self, blocks: Vec<AgentBlock>, room: usize) -> Vec<Line<'static>> {
+        let total: usize = blocks.iter().map(|b| b.lines.len()).sum();
+        if total <= room {
+            return blocks.into_iter().flat_map(|b| b.lines).collect();
+        }
+        // One row is kept for the summary
+        let room = room.saturating_sub(1);
+        let mut shown = vec![false; blocks.len()];
+        let mut used = 0;
+        let mut take = |i: usize| {
+            let len = blocks[i].lines.len();
+            let fits = !shown[i] && used + len <= room;
+            if fits {
+                shown[i] = true;
+                used += len;
+            }
+            fits
+        };
+
+        if self.focused {
+            let selected = self.selected_index.min(blocks.len().saturating_sub(1));
+            take(selected);
+            let (mut before, mut after) = (selected, selected + 1);
+            loop {
+                let grew_down = after < blocks.len() && take(after);
+                if grew_down {
+                    after += 1;
+                }
+                let grew_up = before > 0 && take(before - 1);
+                if grew_up {
+                    before -= 1;
+                }
+                if !grew_down && !grew_up {
+                    break;
+                }
+            }
+        } else {
+            let order: Vec<usize> = (0..blocks.len())
+                .filter(|&i| blocks[i].urgent)
+                .chain((0..blocks.len()).filter(|&i| !blocks[i].urgent))
+                .collect();
+            for i in order {
+                take(i);
+            }
         }
 
+        let hidden: Vec<&AgentBlock> = blocks
+            .iter()
+            .zip(&shown)
+            .filter(|(_, shown)| !**shown)
+            .map(|(b, _)| b)
+            .collect();
+        let summary = if self.focused {
+            let above = hidden
+                .iter()
+                .filter(|b| b.idx < self.selected_index)
+                .count();
+            let below = hidden.len() - above;
+            let mut parts = Vec::new();
+            if above > 0 {
+                parts.push(format!("\u{2191} {above} more"));
+            }
+            if below > 0 {
+                parts.push(format!("\u{2193} {below} more"));
+            }
+            format!(" {}", parts.join("  "))
+        } else if hidden.iter().all(|b| !b.urgent) {
+            format!(" +{} idle agents (Tab to browse)", hidden.len())
+        } else {
+            format!(" +{} more agents (Tab to browse)", hidden.len())
+        };
+
+        let mut lines: Vec<Line<'static>> = blocks
+            .into_iter()
+            .zip(shown)
+            .filter(|(_, shown)| *shown)
+            .flat_map(|(b, _)| b.lines)
+            .collect();
+        lines.push(Line::styled(summary, Style::default().fg(Color::DarkGray)));
         lines
     }
 }
 
+/// One agent's rendered lines, for fitting the list to the pane
+struct AgentBlock {
+    /// Position in the sorted agent list
+    idx: usize,
+    /// Running, failing or vanished: shown before idle agents
+    urgent: bool,
+    lines: Vec<Line<'static>>,
+}
+
 impl<'a> Widget for AgentPanel<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let border_color = if self.focused {
@@ -176,12 +423,20 @@ impl<'a> Widget for AgentPanel<'a> {
         } else {
             Color::DarkGray
         };
+        let count = self.state.panel_agents().0.len();
+        let title = match count {
+            0 => " Agents ".to_string(),
+            _ => format!(
+                " Agents {}/{count} ",
+                self.selected_index.min(count - 1) + 1
+            ),
+        };
         let block = Block::default()
-            .title(" Agents ")
+            .title(title)
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color));
 
-        let lines = self.build_lines();
+        let lines = self.build_lines(block.inner(area).height as usize);
         let paragraph = Paragraph::new(lines)
             .block(block)
             .wrap(Wrap { trim: false });
@@ -193,6 +448,7 @@ impl<'a> Widget for AgentPanel<'a> {
 mod tests {
     use super::*;
     use crate::data::hook_parser;
+    use chrono::Utc;
 
     fn state_with_agents() -> DashboardState {
         let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
@@ -202,6 +458,252 @@ mod tests {
         state
     }
 
+    #[test]
+    fn long_running_tool_is_flagged_slow() {
+        use crate::data::hook_parser::{EventType, HookEvent};
+        use chrono::Duration;
+
+        let now = Utc::now();
+        let event = |event_type, ago: i64| HookEvent {
+            event_type,
+            timestamp: now - Duration::seconds(ago),
+            agent_id: "agent-1".to_string(),
+            task_id: "T-1".to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: Some("Bash".to_string()),
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        };
+        // Three 1s calls, then one still running after 60s
+        let mut events = Vec::new();
+        for start in [300, 200, 100] {
+            events.push(event(EventType::ToolStart, start));
+            events.push(event(EventType::ToolEnd, start - 1));
+        }
+        events.push(event(EventType::ToolStart, 60));
+        let mut state = DashboardState::default();
+        state.update_from_events(&events);
+
+        let text = AgentPanel::new(&state).build_lines(usize::MAX)[0].to_string();
+        assert!(text.contains("-> Bash 1m 00s SLOW"), "{text}");
+    }
+
+    #[test]
+    fn heartbeat_liveness_shown() {
+        use crate::data::hook_parser::{EventType, HookEvent};
+        use chrono::Duration;
+
+        let now = Utc::now();
+        let event = |agent: &str, event_type, ago: i64| HookEvent {
+            event_type,
+            timestamp: now - Duration::seconds(ago),
+            agent_id: agent.to_string(),
+            task_id: "T-1".to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: None,
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        };
+        let mut state = DashboardState::default();
+        state.update_from_events(&[
+            event("a-alive", EventType::AgentStart, 300),
+            event("a-alive", EventType::Heartbeat, 3),
+            event("b-gone", EventType::AgentStart, 300),
+            event("b-gone", EventType::Heartbeat, 120),
+        ]);
+
+        let lines = AgentPanel::new(&state).build_lines(usize::MAX);
+        let alive = lines[0].to_string();
+        assert!(alive.contains("thinking"), "{alive}");
+        assert!(!alive.contains("vanished"));
+        let gone = lines[1].to_string();
+        assert!(gone.starts_with(" ?? b-gone"), "{gone}");
+        assert!(gone.contains("vanished 120s"));
+    }
+
+    #[test]
+    fn rate_limit_cooldown_counts_down() {
+        use crate::data::hook_parser::{EventType, HookEvent};
+        use chrono::Duration;
+
+        let now = Utc::now();
+        let event = |agent: &str, message: &str, ago: i64| HookEvent {
+            event_type: EventType::RateLimit,
+            timestamp: now - Duration::seconds(ago),
+            agent_id: agent.to_string(),
+            task_id: "T-1".to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: None,
+            error_message: Some(message.to_string()),
+            tool_output: None,
+            usage: None,
+        };
+        let mut state = DashboardState::default();
+        state.update_from_events(&[
+            event("a-waiting", "429, retry after 90s", 10),
+            event("b-done", "rate limited", 120),
+        ]);
+
+        let lines = AgentPanel::new(&state).build_lines(usize::MAX);
+        let waiting = lines
+            .iter()
+            .map(|l| l.to_string())
+            .find(|l| l.contains("a-waiting"))
+            .unwrap();
+        assert!(waiting.contains(" cooldown 1:"), "{waiting}");
+        let done = lines
+            .iter()
+            .map(|l| l.to_string())
+            .find(|l| l.contains("b-done"))
+            .unwrap();
+        assert!(!done.contains("cooldown"), "{done}");
+    }
+
+    #[test]
+    fn selected_agent_output_collapses_and_expands() {
+        let input = concat!(
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","tool_name":"Bash","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:00:01Z","agent_id":"a","task_id":"T1","tool_name":"Bash","session_id":"s","tool_output":"running 3 tests\ntest result: ok"}"#,
+        );
+        let mut state = DashboardState::default();
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+
+        // Only the selected agent of the focused panel shows its output
+        assert_eq!(AgentPanel::new(&state).build_lines(usize::MAX).len(), 1);
+        let panel = AgentPanel::new(&state).with_focused(true);
+        let collapsed = panel.build_lines(usize::MAX);
+        assert_eq!(collapsed.len(), 2);
+        assert!(collapsed[1]
+            .to_string()
+            .contains("Bash output (2 lines, Space to expand)"));
+
+        let expanded = panel.with_output_expanded(true).build_lines(usize::MAX);
+        assert_eq!(expanded.len(), 4);
+        assert!(expanded[3].to_string().ends_with("test result: ok"));
+    }
+
+    #[test]
+    fn last_activity_age_shown() {
+        let mut state = state_with_agents();
+        state.scrub_at = Some("2026-02-08T11:13:00Z".parse().unwrap());
+        let text = AgentPanel::new(&state).build_lines(usize::MAX)[0].to_string();
+        assert!(text.contains(" 1h 12m ago"), "{text}");
+    }
+
+    #[test]
+    fn stop_requested_agent_is_marked() {
+        let state = state_with_agents();
+        let requested = HashSet::from(["backend-specialist-1".to_string()]);
+        let lines = AgentPanel::new(&state)
+            .with_stop_requested(&requested)
+            .build_lines(usize::MAX);
+        assert!(lines[0].to_string().contains("STOP requested"));
+    }
+
+    /// Ten idle agents `a0`..`a9` and a running `z-busy`
+    fn crowded_state() -> DashboardState {
+        use crate::data::hook_parser::{EventType, HookEvent};
+
+        let event = |agent: String, event_type| HookEvent {
+            event_type,
+            timestamp: "2026-02-08T10:00:00Z".parse().unwrap(),
+            agent_id: agent,
+            task_id: "T-1".to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: None,
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        };
+        let mut events: Vec<HookEvent> = (0..10)
+            .flat_map(|i| {
+                [
+                    event(format!("a{i}"), EventType::AgentStart),
+                    event(format!("a{i}"), EventType::AgentEnd),
+                ]
+            })
+            .collect();
+        events.push(event("z-busy".to_string(), EventType::AgentStart));
+        let mut state = DashboardState::default();
+        state.update_from_events(&events);
+        state
+    }
+
+    #[test]
+    fn crowded_panel_shows_working_agents_and_summarizes_idle() {
+        let state = crowded_state();
+        let lines: Vec<String> = AgentPanel::new(&state)
+            .build_lines(4)
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].contains("z-busy"), "{lines:?}");
+        assert!(lines[1].contains("a0"));
+        assert_eq!(lines[3], " +8 idle agents (Tab to browse)");
+    }
+
+    #[test]
+    fn long_idle_agents_collapse_into_summary() {
+        let mut state = crowded_state();
+        state.hide_idle_after = Some(chrono::Duration::minutes(30));
+        state.scrub_at = Some("2026-02-08T10:45:00Z".parse().unwrap());
+        let lines: Vec<String> = AgentPanel::new(&state)
+            .build_lines(usize::MAX)
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert!(lines[0].contains("z-busy"), "{lines:?}");
+        assert!(!lines.iter().any(|l| l.contains("a0")));
+        assert_eq!(
+            lines.last().unwrap(),
+            " +10 idle agents collapsed (quiet > 30m)"
+        );
+    }
+
+    #[test]
+    fn task_agent_is_boosted_while_browsing_tasks() {
+        let state = crowded_state();
+        let lines: Vec<String> = AgentPanel::new(&state)
+            .with_selected_agent(Some("a7"))
+            .build_lines(4)
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(lines[0], " Task agent: @a7");
+        assert!(lines[1].contains("a7"), "{lines:?}");
+        assert!(lines[2].contains("z-busy"), "{lines:?}");
+        assert_eq!(lines[3], " +9 idle agents (Tab to browse)");
+
+        // Focused rows stay in selection order
+        let lines: Vec<String> = AgentPanel::new(&state)
+            .with_selected_agent(Some("a7"))
+            .with_focused(true)
+            .build_lines(usize::MAX)
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert!(lines[1].contains("z-busy"), "{lines:?}");
+    }
+
+    #[test]
+    fn focused_crowded_panel_scrolls_to_selection() {
+        let state = crowded_state();
+        let lines: Vec<String> = AgentPanel::new(&state)
+            .with_focused(true)
+            .with_selected_index(6)
+            .build_lines(4)
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().any(|l| l.starts_with(">-- a5")), "{lines:?}");
+        assert_eq!(lines[3], " \u{2191} 5 more  \u{2193} 3 more");
+    }
+
     #[test]
     fn agent_panel_empty() {
         let state = DashboardState::default();
@@ -224,7 +726,7 @@ mod tests {
     fn build_lines_with_agents() {
         let state = state_with_agents();
         let panel = AgentPanel::new(&state);
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(usize::MAX);
         assert!(!lines.is_empty());
     }
 
@@ -232,7 +734,7 @@ mod tests {
     fn build_lines_empty() {
         let state = DashboardState::default();
         let panel = AgentPanel::new(&state);
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(usize::MAX);
         assert_eq!(lines.len(), 1);
     }
 
@@ -240,7 +742,7 @@ mod tests {
     fn with_selected_agent_highlights() {
         let state = state_with_agents();
         let panel = AgentPanel::new(&state).with_selected_agent(Some("backend-specialist"));
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(usize::MAX);
         // Should have header line + agent lines
         assert!(lines.len() >= 2);
     }
@@ -257,7 +759,7 @@ mod tests {
     fn build_lines_shows_error_summary() {
         let state = state_with_errors();
         let panel = AgentPanel::new(&state);
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(usize::MAX);
         // Should have agent line + error summary line
         assert!(lines.len() >= 2);
         let error_line = lines
@@ -270,7 +772,7 @@ mod tests {
     fn error_summary_shows_category() {
         let state = state_with_errors();
         let panel = AgentPanel::new(&state);
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(usize::MAX);
         let has_category = lines.iter().any(|l| {
             l.spans
                 .iter()
@@ -285,7 +787,7 @@ mod tests {
         let panel = AgentPanel::new(&state)
             .with_focused(true)
             .with_selected_index(0);
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(usize::MAX);
         // Should have selection indicator ">" on the first agent line
         let has_selector = lines
             .iter()
@@ -299,7 +801,7 @@ mod tests {
         let panel = AgentPanel::new(&state)
             .with_focused(false)
             .with_selected_index(0);
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(usize::MAX);
         let has_selector = lines
             .iter()
             .any(|l| l.spans.iter().any(|s| s.content.starts_with('>')));
@@ -310,7 +812,7 @@ mod tests {
     fn selected_agent_no_match_still_shows_header() {
         let state = DashboardState::default();
         let panel = AgentPanel::new(&state).with_selected_agent(Some("nonexistent"));
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(usize::MAX);
         // Header line + "No agent activity" would be empty agents but header exists
         assert!(!lines.is_empty());
     }
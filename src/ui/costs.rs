@@ -0,0 +1,225 @@
+//! Costs view
+//!
+//! `$` opens a popup breaking the run's spend down by phase, task or agent
+//! (Tab cycles), sorted by ID, tokens or cost (←/→ pick the column), with
+//! each row's share of the total. Rows without any usage are left out.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::data::cost::{self, CostGroup, CostRow, CostSort};
+
+/// Width of the ID column
+const ID_WIDTH: usize = 14;
+
+/// Costs view widget
+pub struct CostsView<'a> {
+    /// Spent rows, already sorted
+    pub rows: &'a [CostRow],
+    pub group: CostGroup,
+    pub sort: CostSort,
+    /// First row shown
+    pub scroll: usize,
+}
+
+impl<'a> CostsView<'a> {
+    fn centered_rect(area: Rect) -> Rect {
+        let width = (area.width * 9 / 10).min(90);
+        let height = (area.height * 7 / 10).max(6).min(area.height);
+        let x = area.x + (area.width.saturating_sub(width)) / 2;
+        let y = area.y + (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    /// Group tabs, column header and the rows that fit `rows` lines
+    fn build_lines(&self, width: usize, rows: usize) -> Vec<Line<'static>> {
+        let dim = Style::default().fg(Color::DarkGray);
+        let mut tabs = vec![Span::styled(" By ", dim)];
+        for (i, group) in [CostGroup::Phase, CostGroup::Task, CostGroup::Agent]
+            .into_iter()
+            .enumerate()
+        {
+            if i > 0 {
+                tabs.push(Span::styled(" · ", dim));
+            }
+            let style = if group == self.group {
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                dim
+            };
+            tabs.push(Span::styled(group.label(), style));
+        }
+        let mut lines = vec![Line::from(tabs)];
+
+        // ID, name, tokens (9), cost (10), share (6)
+        let name_width = width.saturating_sub(ID_WIDTH + 9 + 10 + 6 + 2);
+        let header = |label: &str, sort: CostSort, width: usize, right: bool| {
+            let label = if sort == self.sort {
+                format!("{label}▼")
+            } else {
+                label.to_string()
+            };
+            let text = if right {
+                format!("{label:>width$}")
+            } else {
+                format!("{label:<width$}")
+            };
+            let style = if sort == self.sort {
+                Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            } else {
+                Style::default().add_modifier(Modifier::BOLD)
+            };
+            Span::styled(text, style)
+        };
+        lines.push(Line::from(vec![
+            Span::raw(" "),
+            header("ID", CostSort::Id, ID_WIDTH, false),
+            Span::raw(format!("{:name_width$}", "")),
+            header("Tokens", CostSort::Tokens, 9, true),
+            header("Cost", CostSort::Cost, 10, true),
+            Span::styled(
+                format!("{:>6}", "Share"),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ]));
+
+        if self.rows.is_empty() {
+            lines.push(Line::styled(" No token usage reported yet", dim));
+            return lines;
+        }
+        let total: f64 = self.rows.iter().map(|r| r.cost_usd).sum();
+        for row in self
+            .rows
+            .iter()
+            .skip(self.scroll)
+            .take(rows.saturating_sub(2))
+        {
+            let share = if total > 0.0 {
+                format!("{:>5.0}%", row.cost_usd / total * 100.0)
+            } else {
+                format!("{:>6}", "-")
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(" {:<ID_WIDTH$}", truncate(&row.id, ID_WIDTH - 1)),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(format!(
+                    "{:<name_width$}",
+                    truncate(&row.name, name_width.saturating_sub(1))
+                )),
+                Span::raw(format!("{:>9}", cost::format_tokens(row.tokens()))),
+                Span::styled(
+                    format!("{:>10}", cost::format_usd(row.cost_usd)),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(share, dim),
+            ]));
+        }
+        lines
+    }
+}
+
+/// Cut `text` to `max` characters, marking the cut with `…`
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max.saturating_sub(1)).collect();
+    format!("{kept}…")
+}
+
+impl<'a> Widget for CostsView<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = Self::centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let total: f64 = self.rows.iter().map(|r| r.cost_usd).sum();
+        let block = Block::default()
+            .title(format!(" Costs: {} ", cost::format_usd(total)))
+            .title_bottom(" Tab group  ←/→ sort  j/k scroll  $/Esc close ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow));
+        let width = popup_area.width.saturating_sub(2) as usize;
+        let rows = popup_area.height.saturating_sub(2) as usize;
+
+        Paragraph::new(self.build_lines(width, rows))
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(id: &str, name: &str, tokens: u64, cost_usd: f64) -> CostRow {
+        CostRow {
+            id: id.to_string(),
+            name: name.to_string(),
+            phase: None,
+            status: None,
+            input_tokens: tokens,
+            output_tokens: 0,
+            cost_usd,
+        }
+    }
+
+    #[test]
+    fn marks_the_sort_column_and_shows_shares() {
+        let rows = [row("P2", "TUI", 48_200, 3.0), row("P1", "Setup", 950, 1.0)];
+        let view = CostsView {
+            rows: &rows,
+            group: CostGroup::Phase,
+            sort: CostSort::Cost,
+            scroll: 0,
+        };
+        let text: Vec<String> = view
+            .build_lines(60, 10)
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(text[0], " By phase · task · agent");
+        assert!(text[1].contains("Cost▼"));
+        assert!(!text[1].contains("Tokens▼"));
+        assert!(text[2].starts_with(" P2"));
+        assert!(text[2].ends_with("48.2k     $3.00   75%"));
+        assert!(text[3].ends_with("950     $1.00   25%"));
+
+        let area = Rect::new(0, 0, 60, 12);
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+    }
+
+    #[test]
+    fn scrolls_and_explains_an_empty_run() {
+        let rows = [row("a", "", 10, 0.5), row("b", "", 5, 0.25)];
+        let view = CostsView {
+            rows: &rows,
+            group: CostGroup::Agent,
+            sort: CostSort::Id,
+            scroll: 1,
+        };
+        let text = view.build_lines(40, 10);
+        assert_eq!(text.len(), 3);
+        assert!(text[2].to_string().starts_with(" b "));
+
+        let empty = CostsView {
+            rows: &[],
+            group: CostGroup::Task,
+            sort: CostSort::Id,
+            scroll: 0,
+        };
+        assert_eq!(
+            empty.build_lines(40, 10)[2].to_string(),
+            " No token usage reported yet"
+        );
+    }
+}
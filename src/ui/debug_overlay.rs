@@ -0,0 +1,184 @@
+//! Debug overlay
+//!
+//! Hidden F12 popup in the top-right corner with draw times, the hook event
+//! rate, state sizes, the watcher backlog and files still parsing, lines that
+//! were not valid UTF-8 and the event count per hook directory, for
+//! diagnosing slow boards and misrouted hooks.
+
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::data::state::DashboardState;
+use crate::perf::FrameStats;
+
+/// Hook directories listed at most
+const MAX_SOURCES: usize = 4;
+
+/// Debug overlay widget
+pub struct DebugOverlay<'a> {
+    stats: &'a FrameStats,
+    state: &'a DashboardState,
+    now: Instant,
+}
+
+impl<'a> DebugOverlay<'a> {
+    pub fn new(stats: &'a FrameStats, state: &'a DashboardState, now: Instant) -> Self {
+        Self { stats, state, now }
+    }
+
+    fn corner_rect(&self, area: Rect) -> Rect {
+        let sources = self.state.events_by_dir().len().min(MAX_SOURCES) as u16;
+        let lossy = u16::from(!self.state.lossy_lines.is_empty());
+        let loading = u16::from(self.stats.loading > 0);
+        let width = 36.min(area.width);
+        let height = (8 + sources + lossy + loading).min(area.height);
+        Rect::new(area.x + area.width - width, area.y, width, height)
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let ms = |d: Option<Duration>| {
+            d.map_or("-".to_string(), |d| {
+                format!("{:.1}ms", d.as_secs_f64() * 1000.0)
+            })
+        };
+        let row = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!(" {label:<8}"), Style::default().fg(Color::DarkGray)),
+                Span::raw(value),
+            ])
+        };
+        let state = self.state;
+        let mut lines = vec![
+            row(
+                "frame",
+                format!(
+                    "{} (avg {}, max {})",
+                    ms(self.stats.last_frame()),
+                    ms(self.stats.avg_frame()),
+                    ms(self.stats.max_frame())
+                ),
+            ),
+            row(
+                "events",
+                format!("{}/s", self.stats.events_per_sec(self.now)),
+            ),
+            row(
+                "tasks",
+                format!("{} in {} phases", state.total_tasks, state.phases.len()),
+            ),
+            row(
+                "agents",
+                format!(
+                    "{}  errors {}",
+                    state.agents.len(),
+                    state.recent_errors.len()
+                ),
+            ),
+            row("history", format!("{} hook events", state.events.len())),
+            row(
+                "backlog",
+                format!(
+                    "{} file changes ({} merged)",
+                    self.stats.backlog, self.stats.merged
+                ),
+            ),
+        ];
+        if self.stats.loading > 0 {
+            lines.push(row(
+                "loading",
+                format!("{} files parsing", self.stats.loading),
+            ));
+        }
+        if !state.lossy_lines.is_empty() {
+            let lines_total: usize = state.lossy_lines.values().sum();
+            lines.push(row(
+                "lossy",
+                format!(
+                    "{lines_total} non-UTF-8 lines in {} files",
+                    state.lossy_lines.len()
+                ),
+            ));
+        }
+        // Where the events came from, when hooks are spread over directories
+        for (dir, count) in state.events_by_dir().into_iter().take(MAX_SOURCES) {
+            lines.push(row("source", format!("{count:>5} {}", dir.display())));
+        }
+        lines
+    }
+}
+
+impl<'a> Widget for DebugOverlay<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = self.corner_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Debug ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta));
+
+        Paragraph::new(self.build_lines())
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_overlay_shows_stats_and_sizes() {
+        let mut stats = FrameStats::default();
+        stats.record_frame(Duration::from_millis(4));
+        let now = Instant::now();
+        stats.record_events(12, now);
+        stats.backlog = 3;
+        let state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [ ] T1: A\n### [ ] T2: B\n")
+                .unwrap();
+
+        let overlay = DebugOverlay::new(&stats, &state, now);
+        let lines: Vec<String> = overlay
+            .build_lines()
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert!(lines[0].contains("4.0ms (avg 4.0ms, max 4.0ms)"));
+        assert!(lines[1].contains("12/s"));
+        assert!(lines[2].contains("2 in 1 phases"));
+        assert!(lines[5].contains("3 file changes"));
+        assert_eq!(lines.len(), 6);
+
+        let mut state = state;
+        state.record_event_source(std::path::Path::new("/srv/hooks/a.jsonl"), 4);
+        state.record_event_source(std::path::Path::new("/srv/hooks/b.jsonl"), 3);
+        let overlay = DebugOverlay::new(&stats, &state, now);
+        let lines = overlay.build_lines();
+        assert_eq!(lines[6].to_string(), " source      7 /srv/hooks");
+
+        state.record_lossy_lines(std::path::Path::new("/srv/hooks/a.jsonl"), 2);
+        let overlay = DebugOverlay::new(&stats, &state, now);
+        let lines = overlay.build_lines();
+        assert_eq!(
+            lines[6].to_string(),
+            " lossy   2 non-UTF-8 lines in 1 files"
+        );
+
+        stats.loading = 2;
+        let overlay = DebugOverlay::new(&stats, &state, now);
+        let lines = overlay.build_lines();
+        assert_eq!(lines[6].to_string(), " loading 2 files parsing");
+
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+    }
+}
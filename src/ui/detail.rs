@@ -10,65 +10,293 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
 
-use chrono::Utc;
-
-use crate::data::state::{AgentState, AgentStatus, DashboardState, ErrorRecord};
-use crate::data::tasks_parser::{ParsedPhase, ParsedTask, TaskStatus};
-
-/// Parse a markdown line into styled spans.
-/// Handles **bold**, `code`, and plain text segments.
-fn parse_md_spans(line: &str) -> Vec<Span<'static>> {
-    let mut spans = Vec::new();
-    let mut rest = line;
-
-    while !rest.is_empty() {
-        // Bold: **text**
-        if let Some(start) = rest.find("**") {
-            if start > 0 {
-                spans.push(Span::raw(rest[..start].to_string()));
-            }
-            let after = &rest[start + 2..];
-            if let Some(end) = after.find("**") {
-                spans.push(Span::styled(
-                    after[..end].to_string(),
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                ));
-                rest = &after[end + 2..];
-            } else {
-                spans.push(Span::raw(rest[start..].to_string()));
-                return spans;
-            }
-        // Code: `text`
-        } else if let Some(start) = rest.find('`') {
-            if start > 0 {
-                spans.push(Span::raw(rest[..start].to_string()));
-            }
-            let after = &rest[start + 1..];
-            if let Some(end) = after.find('`') {
-                spans.push(Span::styled(
-                    after[..end].to_string(),
-                    Style::default().fg(Color::Yellow),
-                ));
-                rest = &after[end + 1..];
-            } else {
-                spans.push(Span::raw(rest[start..].to_string()));
-                return spans;
-            }
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::analysis::retry::RetryState;
+use crate::analysis::rules::Severity;
+use crate::data::deps::{self, Blocker};
+use crate::data::state::{
+    AgentState, AgentStatus, DashboardState, ErrorRecord, RateLimits, TaskTiming, ToolStats,
+};
+use crate::data::statuses::StatusDef;
+use crate::data::tasks_parser::{ParsedPhase, ParsedTask, ProgressWeights, TaskStatus};
+use crate::data::timezone::DisplayZone;
+use crate::github::{Checks, PullRequest};
+use crate::jira::JiraIssue;
+use crate::ui::gantt::status_color;
+use crate::ui::markdown;
+use crate::ui::timefmt::{ago, format_duration, humanize};
+
+/// Display color for an error severity
+pub fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Info => Color::Blue,
+        Severity::Warning => Color::Yellow,
+        Severity::Error => Color::Red,
+        Severity::Fatal => Color::LightRed,
+    }
+}
+
+/// "Errors:" section, each entry color-coded by severity, after a summary
+/// of the rate limits hit
+fn error_lines(
+    errors: &[&ErrorRecord],
+    rate_limits: &RateLimits,
+    now: DateTime<Utc>,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::styled(
+        "Errors:",
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+    )];
+    if rate_limits.hits > 0 {
+        let mut summary = format!(
+            "  Rate limited {}x, waited {}",
+            rate_limits.hits,
+            humanize(rate_limits.waited)
+        );
+        if let Some(last) = rate_limits.last_hit {
+            summary.push_str(&format!(", last {}", ago(last, now)));
+        }
+        let mut spans = vec![Span::styled(summary, Style::default().fg(Color::Yellow))];
+        if let Some(left) = rate_limits.remaining(now) {
+            spans.push(Span::styled(
+                format!(" (cooldown {}:{:02})", left / 60, left % 60),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    for err in errors {
+        let headline = err.headline();
+        let msg_short = if headline.chars().count() > 50 {
+            format!("{}...", headline.chars().take(47).collect::<String>())
         } else {
-            spans.push(Span::raw(rest.to_string()));
-            return spans;
+            headline.to_string()
+        };
+        let mut marker_style = Style::default().fg(severity_color(err.severity));
+        if err.severity == Severity::Fatal {
+            marker_style = marker_style.add_modifier(Modifier::BOLD);
         }
+        lines.push(Line::from(vec![
+            Span::styled("  !! ", marker_style),
+            Span::styled(msg_short, Style::default().fg(Color::White)),
+            Span::styled(
+                format!("  {}", ago(err.timestamp, now)),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        let retry_str = if err.retryable { "Retry" } else { "No retry" };
+        lines.push(Line::from(vec![
+            Span::styled("     ", Style::default()),
+            Span::styled(format!("{}", err.severity), marker_style),
+            Span::styled(" | ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{}", err.category),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                format!(" | {retry_str} | {}", err.display_suggestion()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        lines.extend(markdown::fenced_blocks(&err.message));
     }
-    spans
+    lines
+}
+
+/// Average/max per tool the agent used, then its slow calls, newest first
+fn tool_timing_lines(
+    agent: &AgentState,
+    stats: &HashMap<String, ToolStats>,
+    now: DateTime<Utc>,
+    zone: DisplayZone,
+) -> Vec<Line<'static>> {
+    let mut used: Vec<(&String, &ToolStats)> = stats
+        .iter()
+        .filter(|(name, s)| s.calls > 0 && agent.tool_counts.contains_key(*name))
+        .collect();
+    if used.is_empty() {
+        return Vec::new();
+    }
+    used.sort_by(|a, b| b.1.max.cmp(&a.1.max).then(a.0.cmp(b.0)));
+    let timing = used
+        .iter()
+        .take(4)
+        .map(|(name, s)| {
+            format!(
+                "{name} {}/{}",
+                format_duration(s.average()),
+                format_duration(s.max)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Timing: ", Style::default().fg(Color::DarkGray)),
+        Span::raw(timing),
+        Span::styled(" (avg/max)", Style::default().fg(Color::DarkGray)),
+    ])];
+
+    let slow: Vec<_> = agent
+        .tool_history
+        .iter()
+        .rev()
+        .filter(|c| {
+            stats
+                .get(&c.tool)
+                .is_some_and(|s| s.is_slow(c.duration(now)))
+        })
+        .take(MAX_SLOW_CALLS)
+        .collect();
+    for call in slow {
+        let running = if call.ended_at.is_none() {
+            " (running)"
+        } else {
+            ""
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                "  SLOW ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "{} {}{running}",
+                    call.tool,
+                    format_duration(call.duration(now))
+                ),
+                Style::default().fg(Color::Red),
+            ),
+            Span::styled(
+                format!(" at {}", zone.format(call.started_at, "%H:%M:%S")),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+    lines
+}
+
+/// First 8 characters of a session ID
+fn short_session(sid: &str) -> &str {
+    sid.char_indices()
+        .nth(8)
+        .map_or(sid, |(end, _)| &sid[..end])
+}
+
+/// Render retry attempts and the circuit-breaker state for a task
+fn retry_lines(retry: &RetryState) -> Vec<Line<'static>> {
+    let budget = retry.budget.map(|b| format!("/{b}")).unwrap_or_default();
+    let category = retry
+        .category
+        .as_ref()
+        .map(|c| format!(" ({c})"))
+        .unwrap_or_default();
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Retries:", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!(" {}{budget}{category}", retry.attempts),
+            Style::default().fg(Color::Yellow),
+        ),
+    ])];
+    if retry.exhausted {
+        lines.push(Line::styled(
+            format!(
+                "Circuit open: auto-retry gave up after {} attempts",
+                retry.attempts
+            ),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    lines
+}
+
+/// Render a task's pull request: the one found, or a named one not
+/// listed yet
+fn pull_line(named: Option<u32>, pull: Option<&PullRequest>) -> Option<Line<'static>> {
+    let label = Span::styled("PR:     ", Style::default().fg(Color::DarkGray));
+    let Some(pull) = pull else {
+        return named.map(|number| {
+            Line::from(vec![
+                label,
+                Span::styled(format!("#{number}"), Style::default().fg(Color::Blue)),
+                Span::styled(" (not fetched)", Style::default().fg(Color::DarkGray)),
+            ])
+        });
+    };
+    let checks_color = match pull.checks {
+        Checks::Failing => Color::Red,
+        Checks::Pending => Color::Yellow,
+        Checks::Passing => Color::Green,
+        Checks::None => Color::DarkGray,
+    };
+    Some(Line::from(vec![
+        label,
+        Span::styled(
+            format!("#{}", pull.number),
+            Style::default().fg(Color::Blue),
+        ),
+        Span::raw(format!(" {}", pull.state.to_lowercase())),
+        Span::styled(
+            format!(", {}", pull.checks.label()),
+            Style::default().fg(checks_color),
+        ),
+    ]))
 }
 
+/// Render a task's blocker chain as an indented tree with each blocker's
+/// status and expected finish
+fn blocker_lines(
+    blockers: &[Blocker],
+    statuses: &[StatusDef],
+    now: DateTime<Utc>,
+    zone: DisplayZone,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::styled(
+        "Blocked by:",
+        Style::default().fg(Color::DarkGray),
+    )];
+    for blocker in blockers {
+        let mut spans = vec![
+            Span::raw(format!("{}└ ", "  ".repeat(blocker.depth))),
+            Span::styled(blocker.task_id.clone(), Style::default().fg(Color::Magenta)),
+        ];
+        match &blocker.status {
+            Some(status) => spans.push(Span::styled(
+                format!(" {}", status.label()),
+                Style::default().fg(status_color(status, statuses)),
+            )),
+            None => spans.push(Span::styled(
+                " not in plan",
+                Style::default().fg(Color::DarkGray),
+            )),
+        }
+        if let Some(eta) = blocker.expected_finish {
+            spans.push(Span::styled(
+                format!(
+                    "  ETA {} (in {})",
+                    zone.format(eta, "%H:%M"),
+                    humanize(eta - now)
+                ),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// Slow calls listed in the agent drill-down
+const MAX_SLOW_CALLS: usize = 5;
+
 /// What the detail panel is showing
 pub enum DetailContent<'a> {
     Phase(&'a ParsedPhase),
     Task(&'a ParsedTask, &'a str, Vec<&'a ErrorRecord>), // task + phase name + errors
-    Agent(&'a AgentState, Vec<&'a ErrorRecord>, &'a [ParsedPhase]),
+    Agent(&'a AgentState, Vec<&'a ErrorRecord>, &'a DashboardState),
     None,
 }
 
@@ -76,36 +304,130 @@ pub enum DetailContent<'a> {
 pub struct DetailWidget<'a> {
     content: DetailContent<'a>,
     focused: bool,
+    /// Retry / circuit-breaker state for the shown task
+    retry: Option<&'a RetryState>,
+    /// Shown task is Completed but not yet approved
+    awaiting_review: bool,
+    /// Custom status vocabulary for colors and progress
+    statuses: &'a [StatusDef],
+    /// Task weighting for phase progress
+    weights: ProgressWeights,
+    /// Per-tool durations for the agent drill-down
+    tool_stats: Option<&'a HashMap<String, ToolStats>>,
+    /// Reference time for running calls and durations
+    now: DateTime<Utc>,
+    /// Timezone for clock times
+    zone: DisplayZone,
+    /// Hook timing of the shown task
+    timing: Option<&'a TaskTiming>,
+    /// Transitive blockers of a shown Blocked/Pending task
+    blockers: Vec<Blocker>,
+    /// Fetched state of the shown task's Jira issue
+    jira: Option<&'a JiraIssue>,
+    /// The shown task's pull request, named or discovered
+    pull: Option<&'a PullRequest>,
+    /// Rate limits hit by the shown task or agent
+    rate_limits: RateLimits,
+    /// Make body links clickable with OSC 8 escapes
+    hyperlinks: bool,
 }
 
 impl<'a> DetailWidget<'a> {
-    pub fn new(content: DetailContent<'a>, focused: bool) -> Self {
-        Self { content, focused }
+    /// A panel for `content` with ages and running times measured up to
+    /// `now`, usually the state's [`now`](DashboardState::now)
+    pub fn new(content: DetailContent<'a>, focused: bool, now: DateTime<Utc>) -> Self {
+        Self {
+            content,
+            focused,
+            retry: None,
+            awaiting_review: false,
+            statuses: &[],
+            weights: ProgressWeights::default(),
+            tool_stats: None,
+            now,
+            zone: DisplayZone::default(),
+            timing: None,
+            blockers: Vec::new(),
+            jira: None,
+            pull: None,
+            rate_limits: RateLimits::default(),
+            hyperlinks: false,
+        }
+    }
+
+    pub fn with_focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    pub fn with_statuses(mut self, statuses: &'a [StatusDef]) -> Self {
+        self.statuses = statuses;
+        self
+    }
+
+    pub fn with_weights(mut self, weights: ProgressWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    pub fn with_retry_state(mut self, retry: Option<&'a RetryState>) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_awaiting_review(mut self, awaiting: bool) -> Self {
+        self.awaiting_review = awaiting;
+        self
+    }
+
+    pub fn with_jira(mut self, issue: Option<&'a JiraIssue>) -> Self {
+        self.jira = issue;
+        self
+    }
+
+    pub fn with_pull(mut self, pull: Option<&'a PullRequest>) -> Self {
+        self.pull = pull;
+        self
+    }
+
+    pub fn with_hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = hyperlinks;
+        self
     }
 
     pub fn from_agent_selection(state: &'a DashboardState, selected_agent: usize) -> Self {
-        let mut ids: Vec<&String> = state.agents.keys().collect();
-        ids.sort();
-
-        let content = if let Some(agent_id) = ids.get(selected_agent) {
-            if let Some(agent) = state.agents.get(*agent_id) {
-                let errors: Vec<&ErrorRecord> = state
-                    .recent_errors
-                    .iter()
-                    .filter(|e| e.agent_id == agent.agent_id)
-                    .rev()
-                    .take(3)
-                    .collect();
-                DetailContent::Agent(agent, errors, &state.phases)
-            } else {
-                DetailContent::None
-            }
+        let (agents, _) = state.panel_agents();
+
+        let mut rate_limits = RateLimits::default();
+        let content = if let Some(agent) = agents.get(selected_agent).copied() {
+            rate_limits = agent.rate_limits;
+            let errors: Vec<&ErrorRecord> = state
+                .recent_errors
+                .iter()
+                .filter(|e| e.agent_id == agent.agent_id)
+                .rev()
+                .take(3)
+                .collect();
+            DetailContent::Agent(agent, errors, state)
         } else {
             DetailContent::None
         };
         Self {
             content,
             focused: true,
+            retry: None,
+            awaiting_review: false,
+            statuses: &state.custom_statuses,
+            weights: state.progress_weights,
+            tool_stats: Some(&state.tool_stats),
+            now: state.now(),
+            zone: state.timezone,
+            timing: None,
+            blockers: Vec::new(),
+            jira: None,
+            pull: None,
+            rate_limits,
+            hyperlinks: false,
         }
     }
 
@@ -115,6 +437,11 @@ impl<'a> DetailWidget<'a> {
         selected_index: usize,
         focused: bool,
     ) -> Self {
+        let mut retry = None;
+        let mut awaiting_review = false;
+        let mut timing = None;
+        let mut blockers = Vec::new();
+        let mut rate_limits = RateLimits::default();
         let content = if let Some((pi, ti)) = selected_task {
             let phase = &state.phases[pi];
             let task = &phase.tasks[ti];
@@ -125,6 +452,17 @@ impl<'a> DetailWidget<'a> {
                 .rev()
                 .take(3)
                 .collect();
+            retry = state.retries.get(&task.id);
+            awaiting_review = state.is_awaiting_review(&task.id);
+            rate_limits = state
+                .task_rate_limits
+                .get(task.id.as_str())
+                .copied()
+                .unwrap_or_default();
+            timing = state.task_times.get(task.id.as_str());
+            if matches!(task.status, TaskStatus::Blocked | TaskStatus::Pending) {
+                blockers = deps::blocker_chain(state, &task.id);
+            }
             DetailContent::Task(task, &phase.name, errors)
         } else {
             // Check if a phase header is selected
@@ -142,7 +480,23 @@ impl<'a> DetailWidget<'a> {
                 None => DetailContent::None,
             }
         };
-        Self { content, focused }
+        Self {
+            content,
+            focused,
+            retry,
+            awaiting_review,
+            statuses: &state.custom_statuses,
+            weights: state.progress_weights,
+            tool_stats: None,
+            now: state.now(),
+            zone: state.timezone,
+            timing,
+            blockers,
+            jira: None,
+            pull: None,
+            rate_limits,
+            hyperlinks: false,
+        }
     }
 
     fn build_lines(&self) -> Vec<Line<'static>> {
@@ -154,13 +508,9 @@ impl<'a> DetailWidget<'a> {
                 )]
             }
             DetailContent::Phase(phase) => {
-                let pct = (phase.progress() * 100.0) as u8;
-                let completed = phase
-                    .tasks
-                    .iter()
-                    .filter(|t| t.status == TaskStatus::Completed)
-                    .count();
-                vec![
+                let pct = (phase.progress_weighted(self.statuses, self.weights) * 100.0) as u8;
+                let completed = phase.completed_with(self.statuses);
+                let mut lines = vec![
                     Line::from(vec![
                         Span::styled("Phase: ", Style::default().fg(Color::DarkGray)),
                         Span::styled(
@@ -182,9 +532,25 @@ impl<'a> DetailWidget<'a> {
                         Span::styled("Tasks:    ", Style::default().fg(Color::DarkGray)),
                         Span::raw(format!("{}", phase.tasks.len())),
                     ]),
-                ]
+                ];
+                if let Some(ref status) = phase.status {
+                    lines.push(Line::from(vec![
+                        Span::styled("Status:   ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(
+                            status.label().to_string(),
+                            Style::default().fg(status_color(status, self.statuses)),
+                        ),
+                    ]));
+                }
+                if let Some(ref gate) = phase.gate {
+                    lines.push(Line::from(vec![
+                        Span::styled("Gate:     ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(format!("after {gate} completes")),
+                    ]));
+                }
+                lines
             }
-            DetailContent::Agent(agent, errors, phases) => {
+            DetailContent::Agent(agent, errors, state) => {
                 let status_str = format!("{:?}", agent.status);
                 let status_color = match agent.status {
                     AgentStatus::Running => Color::Green,
@@ -220,30 +586,50 @@ impl<'a> DetailWidget<'a> {
                     ]),
                 ];
 
-                // Session ID
-                if let Some(ref sid) = agent.session_id {
-                    let short = if sid.len() > 8 { &sid[..8] } else { sid };
+                // Hook agent IDs merged by [[agents.groups]]
+                let mut names: Vec<&str> = agent
+                    .members
+                    .values()
+                    .map(|m| m.agent_id.as_str())
+                    .collect();
+                names.sort_unstable();
+                names.dedup();
+                if names.len() > 1 {
+                    lines.push(Line::from(vec![
+                        Span::styled("Members:", Style::default().fg(Color::DarkGray)),
+                        Span::raw(format!(" {}", names.join(", "))),
+                    ]));
+                }
+
+                // Session ID, or all of them when sessions share the agent
+                let mut sessions: Vec<&str> = agent
+                    .members
+                    .keys()
+                    .map(|(sid, _)| short_session(sid))
+                    .collect();
+                sessions.dedup();
+                if sessions.len() > 1 {
+                    lines.push(Line::from(vec![
+                        Span::styled("Sessions:", Style::default().fg(Color::DarkGray)),
+                        Span::raw(format!(" {}", sessions.join(", "))),
+                    ]));
+                } else if let Some(ref sid) = agent.session_id {
                     lines.push(Line::from(vec![
                         Span::styled("Session:", Style::default().fg(Color::DarkGray)),
-                        Span::raw(format!(" {short}")),
+                        Span::raw(format!(" {}", short_session(sid))),
                     ]));
                 }
 
                 // Active duration
                 if let Some(first) = agent.first_seen {
-                    let last = agent.last_seen.unwrap_or_else(Utc::now);
-                    let duration = last.signed_duration_since(first);
-                    let secs = duration.num_seconds();
-                    let active_str = if secs >= 3600 {
-                        format!("{}h {}m", secs / 3600, (secs % 3600) / 60)
-                    } else if secs >= 60 {
-                        format!("{}m {}s", secs / 60, secs % 60)
-                    } else {
-                        format!("{secs}s")
-                    };
+                    let last = agent.last_seen.unwrap_or(self.now);
                     lines.push(Line::from(vec![
                         Span::styled("Active: ", Style::default().fg(Color::DarkGray)),
-                        Span::raw(active_str),
+                        Span::raw(humanize(last - first)),
+                        Span::styled(
+                            format!(" (last event {})", ago(last, self.now)),
+                            Style::default().fg(Color::DarkGray),
+                        ),
                     ]));
                 }
 
@@ -279,6 +665,10 @@ impl<'a> DetailWidget<'a> {
                     ]));
                 }
 
+                if let Some(stats) = self.tool_stats {
+                    lines.extend(tool_timing_lines(agent, stats, self.now, self.zone));
+                }
+
                 // Task history
                 if !agent.task_history.is_empty() {
                     lines.push(Line::raw(""));
@@ -289,22 +679,17 @@ impl<'a> DetailWidget<'a> {
                             .add_modifier(Modifier::BOLD),
                     ));
                     for entry in &agent.task_history {
-                        let task_name = phases
-                            .iter()
-                            .flat_map(|p| &p.tasks)
-                            .find(|t| t.id == entry.task_id)
+                        let task_name = state
+                            .task(&entry.task_id)
                             .map(|t| t.name.as_str())
                             .unwrap_or("");
-                        let start = entry.started_at.format("%H:%M");
+                        let start = self.zone.format(entry.started_at, "%H:%M");
                         let end_str = if let Some(end) = entry.completed_at {
-                            let dur = end.signed_duration_since(entry.started_at);
-                            let secs = dur.num_seconds();
-                            let dur_str = if secs >= 60 {
-                                format!("{}m", secs / 60)
-                            } else {
-                                format!("{secs}s")
-                            };
-                            format!("{} ({dur_str})", end.format("%H:%M"))
+                            format!(
+                                "{} ({})",
+                                self.zone.format(end, "%H:%M"),
+                                humanize(end - entry.started_at)
+                            )
                         } else {
                             "(running)".to_string()
                         };
@@ -315,7 +700,10 @@ impl<'a> DetailWidget<'a> {
                         };
                         lines.push(Line::from(vec![
                             Span::styled("  ", Style::default()),
-                            Span::styled(entry.task_id.clone(), Style::default().fg(Color::Cyan)),
+                            Span::styled(
+                                entry.task_id.to_string(),
+                                Style::default().fg(Color::Cyan),
+                            ),
                             Span::styled(name_part, Style::default().fg(Color::White)),
                             Span::styled(
                                 format!("  {start} → {end_str}"),
@@ -328,46 +716,14 @@ impl<'a> DetailWidget<'a> {
                 // Errors section
                 if !errors.is_empty() {
                     lines.push(Line::raw(""));
-                    lines.push(Line::styled(
-                        "Errors:",
-                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                    ));
-                    for err in errors {
-                        let msg_short = if err.message.len() > 50 {
-                            format!("{}...", &err.message[..47])
-                        } else {
-                            err.message.clone()
-                        };
-                        lines.push(Line::from(vec![
-                            Span::styled("  !! ", Style::default().fg(Color::Red)),
-                            Span::styled(msg_short, Style::default().fg(Color::White)),
-                        ]));
-                        let retry_str = if err.retryable { "Retry" } else { "No retry" };
-                        lines.push(Line::from(vec![
-                            Span::styled("     ", Style::default()),
-                            Span::styled(
-                                format!("{}", err.category),
-                                Style::default().fg(Color::Yellow),
-                            ),
-                            Span::styled(
-                                format!(" | {retry_str} | {}", err.suggestion),
-                                Style::default().fg(Color::DarkGray),
-                            ),
-                        ]));
-                    }
+                    lines.extend(error_lines(errors, &self.rate_limits, self.now));
                 }
 
                 lines
             }
             DetailContent::Task(task, phase_name, errors) => {
-                let status_str = format!("{:?}", task.status);
-                let status_color = match task.status {
-                    TaskStatus::Completed => Color::Green,
-                    TaskStatus::InProgress => Color::Yellow,
-                    TaskStatus::Pending => Color::DarkGray,
-                    TaskStatus::Failed => Color::Red,
-                    TaskStatus::Blocked => Color::Magenta,
-                };
+                let status_str = task.status.label().to_string();
+                let status_color = status_color(&task.status, self.statuses);
 
                 let mut lines = vec![
                     Line::from(vec![
@@ -392,6 +748,47 @@ impl<'a> DetailWidget<'a> {
                         Span::styled(status_str, Style::default().fg(status_color)),
                     ]),
                 ];
+                if self.awaiting_review {
+                    if let Some(status_line) = lines.last_mut() {
+                        status_line.spans.push(Span::styled(
+                            " (awaiting review: a approve / d bounce)",
+                            Style::default().fg(Color::Cyan),
+                        ));
+                    }
+                }
+
+                if let Some(line_no) = task.line {
+                    lines.push(Line::from(vec![
+                        Span::styled("Line:   ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(line_no.to_string(), Style::default().fg(Color::DarkGray)),
+                    ]));
+                }
+
+                if let Some(started) = self.timing.and_then(|t| t.started_at) {
+                    let took = match self.timing.and_then(|t| t.completed_at) {
+                        Some(done) => format!(", took {}", humanize(done - started)),
+                        None => format!(", running {}", humanize(self.now - started)),
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled("Start:  ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(self.zone.format(started, "%H:%M")),
+                        Span::styled(
+                            format!(" ({}{took})", ago(started, self.now)),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                    ]));
+                    // Exact span, as the bar view only labels it roughly
+                    let end = self.timing.and_then(|t| t.completed_at);
+                    lines.push(Line::from(vec![
+                        Span::styled("Span:   ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(format!(
+                            "{} \u{2013} {} ({})",
+                            self.zone.format(started, "%H:%M:%S"),
+                            end.map_or("now".to_string(), |e| self.zone.format(e, "%H:%M:%S")),
+                            format_duration(end.unwrap_or(self.now) - started)
+                        )),
+                    ]));
+                }
 
                 if let Some(ref agent) = task.agent {
                     lines.push(Line::from(vec![
@@ -400,7 +797,41 @@ impl<'a> DetailWidget<'a> {
                     ]));
                 }
 
-                if !task.blocked_by.is_empty() {
+                if let Some(ref key) = task.jira {
+                    let mut spans = vec![
+                        Span::styled("Jira:   ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(key.clone(), Style::default().fg(Color::Blue)),
+                    ];
+                    match self.jira.filter(|issue| issue.key == *key) {
+                        Some(issue) => {
+                            spans.push(Span::raw(format!(" {}", issue.status)));
+                            if let Some(ref assignee) = issue.assignee {
+                                spans.push(Span::styled(
+                                    format!(" ({assignee})"),
+                                    Style::default().fg(Color::DarkGray),
+                                ));
+                            }
+                        }
+                        None => spans.push(Span::styled(
+                            " (not fetched)",
+                            Style::default().fg(Color::DarkGray),
+                        )),
+                    }
+                    lines.push(Line::from(spans));
+                }
+
+                if let Some(line) = pull_line(task.pr, self.pull) {
+                    lines.push(line);
+                }
+
+                if !self.blockers.is_empty() {
+                    lines.extend(blocker_lines(
+                        &self.blockers,
+                        self.statuses,
+                        self.now,
+                        self.zone,
+                    ));
+                } else if !task.blocked_by.is_empty() {
                     lines.push(Line::from(vec![
                         Span::styled("Deps:   ", Style::default().fg(Color::DarkGray)),
                         Span::styled(
@@ -410,42 +841,18 @@ impl<'a> DetailWidget<'a> {
                     ]));
                 }
 
+                if let Some(retry) = self.retry {
+                    lines.extend(retry_lines(retry));
+                }
+
                 if !task.body.is_empty() {
                     lines.push(Line::raw(""));
-                    for body_line in task.body.lines() {
-                        lines.push(Line::from(parse_md_spans(body_line)));
-                    }
+                    lines.extend(markdown::render(&task.body, self.hyperlinks).lines);
                 }
 
                 if !errors.is_empty() {
                     lines.push(Line::raw(""));
-                    lines.push(Line::styled(
-                        "Errors:",
-                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                    ));
-                    for err in errors {
-                        let msg_short = if err.message.len() > 50 {
-                            format!("{}...", &err.message[..47])
-                        } else {
-                            err.message.clone()
-                        };
-                        lines.push(Line::from(vec![
-                            Span::styled("  !! ", Style::default().fg(Color::Red)),
-                            Span::styled(msg_short, Style::default().fg(Color::White)),
-                        ]));
-                        let retry_str = if err.retryable { "Retry" } else { "No retry" };
-                        lines.push(Line::from(vec![
-                            Span::styled("     ", Style::default()),
-                            Span::styled(
-                                format!("{}", err.category),
-                                Style::default().fg(Color::Yellow),
-                            ),
-                            Span::styled(
-                                format!(" | {retry_str} | {}", err.suggestion),
-                                Style::default().fg(Color::DarkGray),
-                            ),
-                        ]));
-                    }
+                    lines.extend(error_lines(errors, &self.rate_limits, self.now));
                 }
 
                 lines
@@ -467,11 +874,17 @@ impl<'a> Widget for DetailWidget<'a> {
             .borders(Borders::ALL)
             .border_style(border_style);
 
+        let inner = block.inner(area);
         let lines = self.build_lines();
         let paragraph = Paragraph::new(lines)
             .block(block)
             .wrap(Wrap { trim: false });
         paragraph.render(area, buf);
+        if let DetailContent::Task(task, ..) = &self.content {
+            if self.hyperlinks {
+                markdown::hyperlink(buf, inner, &markdown::render(&task.body, true).links);
+            }
+        }
     }
 }
 
@@ -486,7 +899,7 @@ mod tests {
 
     #[test]
     fn detail_none_renders() {
-        let widget = DetailWidget::new(DetailContent::None, false);
+        let widget = DetailWidget::new(DetailContent::None, false, Utc::now());
         let area = Rect::new(0, 0, 40, 10);
         let mut buf = Buffer::empty(area);
         widget.render(area, &mut buf);
@@ -495,7 +908,7 @@ mod tests {
     #[test]
     fn detail_phase_renders() {
         let state = sample_state();
-        let widget = DetailWidget::new(DetailContent::Phase(&state.phases[0]), true);
+        let widget = DetailWidget::new(DetailContent::Phase(&state.phases[0]), true, state.now());
         let area = Rect::new(0, 0, 40, 10);
         let mut buf = Buffer::empty(area);
         widget.render(area, &mut buf);
@@ -505,7 +918,11 @@ mod tests {
     fn detail_task_renders() {
         let state = sample_state();
         let task = &state.phases[0].tasks[0];
-        let widget = DetailWidget::new(DetailContent::Task(task, "Setup", vec![]), true);
+        let widget = DetailWidget::new(
+            DetailContent::Task(task, "Setup", vec![]),
+            true,
+            state.now(),
+        );
         let area = Rect::new(0, 0, 40, 10);
         let mut buf = Buffer::empty(area);
         widget.render(area, &mut buf);
@@ -519,6 +936,58 @@ mod tests {
         assert!(lines.len() >= 4);
     }
 
+    #[test]
+    fn task_shows_start_time_and_age() {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [x] P1-R1-T1: A\n").unwrap();
+        let events = crate::data::hook_parser::parse_hook_events(include_str!(
+            "../../tests/fixtures/sample_hooks/agent_events.jsonl"
+        ));
+        state.update_from_events(&events.events);
+        state.timezone = DisplayZone::Utc;
+        state.scrub_at = Some("2026-02-08T10:03:00Z".parse().unwrap());
+
+        let widget = DetailWidget::from_selection(&state, Some((0, 0)), 1, true);
+        let text: Vec<String> = widget.build_lines().iter().map(|l| l.to_string()).collect();
+        assert!(
+            text.contains(&"Start:  10:00 (3m ago, took 1m)".to_string()),
+            "{text:?}"
+        );
+        assert!(
+            text.contains(&"Span:   10:00:00 \u{2013} 10:01:00 (1m 00s)".to_string()),
+            "{text:?}"
+        );
+    }
+
+    #[test]
+    fn phase_shows_status_and_gate() {
+        let state =
+            DashboardState::from_tasks_content("# Phase 2: B [Blocked]\ngate: P1\n### [ ] T1: A\n")
+                .unwrap();
+        let widget = DetailWidget::new(DetailContent::Phase(&state.phases[0]), true, state.now());
+        let text: Vec<String> = widget
+            .build_lines()
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text.iter().any(|l| l == "Status:   Blocked"));
+        assert!(text.iter().any(|l| l == "Gate:     after P1 completes"));
+    }
+
+    #[test]
+    fn task_shows_source_line() {
+        let state =
+            DashboardState::from_tasks_content("# Phase 1: A\n\n### [ ] T1: A\n- body\n").unwrap();
+        let task = &state.phases[0].tasks[0];
+        let widget = DetailWidget::new(DetailContent::Task(task, "A", vec![]), true, state.now());
+        let text: Vec<String> = widget
+            .build_lines()
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text.iter().any(|l| l == "Line:   3"));
+    }
+
     #[test]
     fn from_selection_phase() {
         let state = sample_state();
@@ -545,14 +1014,22 @@ mod tests {
         let task = &state.phases[0].tasks[0];
         let err = ErrorRecord {
             agent_id: "test-agent".to_string(),
-            task_id: task.id.clone(),
+            task_id: task.id.as_str().into(),
             message: "permission denied: /etc/shadow".to_string(),
             category: ErrorCategory::Permission,
+            severity: Severity::Error,
             retryable: false,
             suggestion: "Check file permissions",
+            hint: Some("Check file permissions on /etc/shadow".to_string()),
             timestamp: Utc::now(),
+            cooldown_until: None,
+            event_index: 0,
         };
-        let widget = DetailWidget::new(DetailContent::Task(task, "Setup", vec![&err]), false);
+        let widget = DetailWidget::new(
+            DetailContent::Task(task, "Setup", vec![&err]),
+            false,
+            state.now(),
+        );
         let lines = widget.build_lines();
         let has_errors_header = lines
             .iter()
@@ -562,6 +1039,43 @@ mod tests {
             .iter()
             .any(|l| l.spans.iter().any(|s| s.content.contains("Permission")));
         assert!(has_permission, "should show Permission category");
+        let has_hint = lines.iter().any(|l| {
+            l.spans
+                .iter()
+                .any(|s| s.content.contains("Check file permissions on /etc/shadow"))
+        });
+        assert!(has_hint, "should prefer the context-aware hint");
+    }
+
+    #[test]
+    fn error_with_a_fenced_trace_shows_the_code() {
+        use crate::analysis::rules::ErrorCategory;
+        use crate::data::state::ErrorRecord;
+
+        let state = sample_state();
+        let task = &state.phases[0].tasks[0];
+        let err = ErrorRecord {
+            agent_id: "test-agent".to_string(),
+            task_id: task.id.as_str().into(),
+            message: "테스트 실패: 빌드가 중단되었습니다 — 아래 스택을 확인하세요\n```\nTypeError: x\n    at run (src/a.js:1:2)\n```".to_string(),
+            category: ErrorCategory::Type,
+            severity: Severity::Error,
+            retryable: true,
+            suggestion: "",
+            hint: None,
+            timestamp: Utc::now(),
+            cooldown_until: None,
+            event_index: 0,
+        };
+        let widget = DetailWidget::new(
+            DetailContent::Task(task, "Setup", vec![&err]),
+            false,
+            state.now(),
+        );
+        let text: Vec<String> = widget.build_lines().iter().map(|l| l.to_string()).collect();
+        // The headline is cut on a char boundary and stops at the first line
+        assert!(text.iter().any(|l| l.starts_with("  !! 테스트 실패")));
+        assert!(text.iter().any(|l| l.contains("at run (src/a.js:1:2)")));
     }
 
     #[test]
@@ -681,6 +1195,75 @@ mod tests {
         assert!(has_arrow, "should show arrow separator in recent tools");
     }
 
+    #[test]
+    fn detail_agent_shows_tool_timing_and_slow_calls() {
+        use crate::data::hook_parser::{EventType, HookEvent};
+        use chrono::Duration;
+
+        let base = Utc::now() - Duration::minutes(10);
+        let event = |event_type, secs: i64| HookEvent {
+            event_type,
+            timestamp: base + Duration::seconds(secs),
+            agent_id: "agent-1".to_string(),
+            task_id: "T-1".to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: Some("Bash".to_string()),
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        };
+        let mut events = Vec::new();
+        for (start, end) in [(0, 2), (10, 12), (20, 22), (30, 90)] {
+            events.push(event(EventType::ToolStart, start));
+            events.push(event(EventType::ToolEnd, end));
+        }
+        let mut state = DashboardState::default();
+        state.update_from_events(&events);
+
+        let lines = DetailWidget::from_agent_selection(&state, 0).build_lines();
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        assert!(text.contains(&"Timing: Bash 16.5s/1m 00s (avg/max)".to_string()));
+        let slow: Vec<_> = text.iter().filter(|l| l.contains("SLOW")).collect();
+        assert_eq!(slow.len(), 1);
+        assert!(slow[0].contains("Bash 1m 00s"));
+    }
+
+    #[test]
+    fn detail_errors_summarize_rate_limits() {
+        use crate::data::hook_parser::{EventType, HookEvent};
+        use chrono::Duration;
+
+        let now = Utc::now();
+        let event = |message: &str, ago: i64| HookEvent {
+            event_type: EventType::RateLimit,
+            timestamp: now - Duration::seconds(ago),
+            agent_id: "agent-1".to_string(),
+            task_id: "T-1".to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: None,
+            error_message: Some(message.to_string()),
+            tool_output: None,
+            usage: None,
+        };
+        let mut state = DashboardState::default();
+        state.update_from_events(&[
+            event("rate limited, retry after 60s", 600),
+            event("rate limited, retry after 300s", 120),
+        ]);
+
+        let lines = DetailWidget::from_agent_selection(&state, 0).build_lines();
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        let summary = text
+            .iter()
+            .find(|l| l.starts_with("  Rate limited"))
+            .unwrap();
+        assert!(
+            summary.starts_with("  Rate limited 2x, waited 6m, last 2m ago (cooldown 2:"),
+            "{summary}"
+        );
+        assert!(text.iter().any(|l| l.contains("RateLimit")));
+    }
+
     #[test]
     fn detail_agent_shows_task_name_from_phases() {
         use crate::data::hook_parser;
@@ -708,13 +1291,86 @@ mod tests {
         let _ = has_quoted_name; // use the variable
     }
 
+    #[test]
+    fn task_with_jira_link_shows_issue_state() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [ ] T1: Login\n- jira: AUTH-42\n",
+        )
+        .unwrap();
+        let task = &state.phases[0].tasks[0];
+        let jira_line = |widget: DetailWidget| {
+            widget
+                .build_lines()
+                .iter()
+                .map(|l| l.to_string())
+                .find(|l| l.starts_with("Jira:"))
+                .unwrap()
+        };
+        let widget = DetailWidget::new(DetailContent::Task(task, "A", vec![]), false, state.now());
+        assert_eq!(jira_line(widget), "Jira:   AUTH-42 (not fetched)");
+
+        let issue = JiraIssue {
+            key: "AUTH-42".to_string(),
+            status: "In Review".to_string(),
+            assignee: Some("Kim Lee".to_string()),
+        };
+        let widget = DetailWidget::new(DetailContent::Task(task, "A", vec![]), false, state.now())
+            .with_jira(Some(&issue));
+        assert_eq!(jira_line(widget), "Jira:   AUTH-42 In Review (Kim Lee)");
+    }
+
+    #[test]
+    fn task_pull_request_shows_state_and_checks() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [ ] T1: Login\n- pr: #12\n### [ ] T2: Logout\n",
+        )
+        .unwrap();
+        let pr_line = |widget: DetailWidget| {
+            widget
+                .build_lines()
+                .iter()
+                .map(|l| l.to_string())
+                .find(|l| l.starts_with("PR:"))
+        };
+        let named = &state.phases[0].tasks[0];
+        let widget = DetailWidget::new(DetailContent::Task(named, "A", vec![]), false, state.now());
+        assert_eq!(pr_line(widget).unwrap(), "PR:     #12 (not fetched)");
+
+        let unlinked = &state.phases[0].tasks[1];
+        let widget = DetailWidget::new(
+            DetailContent::Task(unlinked, "A", vec![]),
+            false,
+            state.now(),
+        );
+        assert_eq!(pr_line(widget), None);
+
+        let pull = PullRequest {
+            number: 15,
+            title: "T2: logout".to_string(),
+            state: "OPEN".to_string(),
+            branch: "t2-logout".to_string(),
+            checks: Checks::Failing,
+        };
+        let widget = DetailWidget::new(
+            DetailContent::Task(unlinked, "A", vec![]),
+            false,
+            state.now(),
+        )
+        .with_pull(Some(&pull));
+        assert_eq!(pr_line(widget).unwrap(), "PR:     #15 open, checks failing");
+    }
+
     #[test]
     fn task_with_deps_shows_deps() {
         let state = sample_state();
         // Phase 1, task 0 has blocked_by
         let task = &state.phases[1].tasks[0];
         assert!(!task.blocked_by.is_empty());
-        let widget = DetailWidget::new(DetailContent::Task(task, "Data Engine", vec![]), false);
+        let widget = DetailWidget::new(
+            DetailContent::Task(task, "Data Engine", vec![]),
+            false,
+            state.now(),
+        );
         let lines = widget.build_lines();
         let has_deps = lines.iter().any(|l| {
             l.spans
@@ -724,12 +1380,94 @@ mod tests {
         assert!(has_deps);
     }
 
+    #[test]
+    fn blocked_task_shows_blocker_chain_with_eta() {
+        use crate::data::state::TaskTiming;
+
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [x] T0: Setup\n### [/] T1: Parser\n- blocked_by: T0\n### [Blocked] T2: UI\n- blocked_by: T1, T9\n",
+        )
+        .unwrap();
+        let at = |t: &str| format!("2026-02-08T{t}:00Z").parse().unwrap();
+        state.task_times.insert(
+            "T0".into(),
+            TaskTiming {
+                started_at: Some(at("09:00")),
+                completed_at: Some(at("09:30")),
+            },
+        );
+        state.task_times.insert(
+            "T1".into(),
+            TaskTiming {
+                started_at: Some(at("09:30")),
+                completed_at: None,
+            },
+        );
+        state.scrub_at = Some(at("09:40"));
+        state.timezone = DisplayZone::Utc;
+
+        let widget = DetailWidget::from_selection(&state, Some((0, 2)), 3, true);
+        let text: Vec<String> = widget.build_lines().iter().map(|l| l.to_string()).collect();
+        let start = text.iter().position(|l| l == "Blocked by:").unwrap();
+        assert_eq!(
+            text[start + 1..start + 4],
+            [
+                "  └ T1 InProgress  ETA 10:00 (in 20m)",
+                "    └ T0 Completed",
+                "  └ T9 not in plan",
+            ]
+        );
+        assert!(!text.iter().any(|l| l.starts_with("Deps:")));
+    }
+
+    #[test]
+    fn awaiting_review_shown_in_status() {
+        let state = sample_state();
+        let task = &state.phases[0].tasks[0];
+        let widget = DetailWidget::new(
+            DetailContent::Task(task, "Setup", vec![]),
+            false,
+            state.now(),
+        )
+        .with_awaiting_review(true);
+        let lines = widget.build_lines();
+        assert!(lines.iter().any(|l| l
+            .spans
+            .iter()
+            .any(|s| s.content.contains("awaiting review"))));
+    }
+
+    #[test]
+    fn task_with_exhausted_retries_shows_circuit_open() {
+        use crate::analysis::rules::ErrorCategory;
+
+        let mut state = sample_state();
+        state
+            .retries
+            .record_attempt("P1-R3-T1", Some(ErrorCategory::Network), Some(1));
+        state
+            .retries
+            .mark_exhausted("P1-R3-T1", ErrorCategory::Network, 1);
+        let widget = DetailWidget::from_selection(&state, Some((1, 2)), 6, true);
+        let lines = widget.build_lines();
+        let text: String = lines
+            .iter()
+            .flat_map(|l| l.spans.iter().map(|s| s.content.to_string()))
+            .collect();
+        assert!(text.contains("1/1 (Network)"), "got: {text}");
+        assert!(text.contains("Circuit open"));
+    }
+
     #[test]
     fn task_with_body_shows_body_lines() {
         let state = sample_state();
         let task = &state.phases[0].tasks[0];
         assert!(!task.body.is_empty(), "fixture task should have body");
-        let widget = DetailWidget::new(DetailContent::Task(task, "Setup", vec![]), false);
+        let widget = DetailWidget::new(
+            DetailContent::Task(task, "Setup", vec![]),
+            false,
+            state.now(),
+        );
         let lines = widget.build_lines();
         let has_spec = lines
             .iter()
@@ -0,0 +1,353 @@
+//! Error modal
+//!
+//! `e` opens the selected task's latest error in full instead of the
+//! one-line headline the other panes show. Each line of a multi-line
+//! payload stays on its own line; stack frames are folded to their first
+//! line, and the lines under a frame (its `at file:line`, the quoted source)
+//! unfold one frame at a time with `Space` / `Enter` on the frame under the
+//! cursor.
+
+use std::collections::BTreeSet;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::data::state::ErrorRecord;
+use crate::data::timezone::DisplayZone;
+use crate::ui::highlight;
+
+/// One line of an error message, or a stack frame with the lines under it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceItem {
+    Text(String),
+    Frame {
+        /// The frame's own line (`at f (a.js:1:2)`, `File "a.py", line 3`)
+        head: String,
+        /// More indented lines that follow it, hidden while folded
+        body: Vec<String>,
+    },
+}
+
+/// Split a message into plain lines and stack frames. A frame owns the
+/// non-blank lines indented deeper than its own line; fence lines of a
+/// quoted block are dropped.
+pub fn fold(message: &str) -> Vec<TraceItem> {
+    let mut items = Vec::new();
+    // Indent of the frame that can still take body lines
+    let mut open: Option<usize> = None;
+    for line in message.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            open = None;
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+        if let (Some(head), Some(TraceItem::Frame { body, .. })) = (open, items.last_mut()) {
+            if indent > head && !trimmed.is_empty() {
+                body.push(line.to_string());
+                continue;
+            }
+        }
+        if highlight::is_frame(line) {
+            open = Some(indent);
+            items.push(TraceItem::Frame {
+                head: line.to_string(),
+                body: Vec::new(),
+            });
+        } else {
+            open = None;
+            items.push(TraceItem::Text(line.to_string()));
+        }
+    }
+    items
+}
+
+/// Number of stack frames in a message
+pub fn frame_count(message: &str) -> usize {
+    fold(message)
+        .iter()
+        .filter(|item| matches!(item, TraceItem::Frame { .. }))
+        .count()
+}
+
+/// Error modal widget
+pub struct ErrorModal<'a> {
+    pub error: &'a ErrorRecord,
+    pub timezone: DisplayZone,
+    /// Frame under the cursor, counted among the message's frames
+    pub cursor: usize,
+    /// Unfolded frames
+    pub expanded: &'a BTreeSet<usize>,
+}
+
+impl<'a> ErrorModal<'a> {
+    fn centered_rect(area: Rect) -> Rect {
+        let width = (area.width * 9 / 10).min(100);
+        let height = (area.height * 8 / 10).max(6).min(area.height);
+        let x = area.x + (area.width.saturating_sub(width)) / 2;
+        let y = area.y + (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    /// The modal's lines for an inner `width`, with the range of lines the
+    /// cursor frame takes. Plain messages wrap; trace lines are kept whole.
+    fn build_lines(&self, width: usize) -> (Vec<Line<'static>>, (usize, usize)) {
+        let dim = Style::default().fg(Color::DarkGray);
+        let err = self.error;
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(
+                    format!(" {} ", err.task_id),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("@{}  ", err.agent_id), dim),
+                Span::styled(
+                    format!("{} {}", err.severity, err.category),
+                    Style::default().fg(Color::Red),
+                ),
+                Span::styled(self.timezone.format(err.timestamp, "  %H:%M:%S"), dim),
+            ]),
+            Line::raw(""),
+        ];
+
+        let items = fold(&err.message);
+        // Without frames, words after "in"/"at" are not function names
+        let traced = items
+            .iter()
+            .any(|item| matches!(item, TraceItem::Frame { .. }));
+        let mut cursor_lines = (0, 0);
+        let mut frame = 0;
+        for item in items {
+            match item {
+                TraceItem::Text(text) if traced => {
+                    let mut spans = vec![Span::raw(" ")];
+                    spans.extend(highlight::highlight_trace(&text));
+                    lines.push(Line::from(spans));
+                }
+                TraceItem::Text(text) => {
+                    let chars: Vec<char> = text.chars().collect();
+                    let wrapped = chars.chunks(width.saturating_sub(2).max(1));
+                    if chars.is_empty() {
+                        lines.push(Line::raw(""));
+                    }
+                    lines.extend(
+                        wrapped.map(|chunk| {
+                            Line::raw(format!(" {}", chunk.iter().collect::<String>()))
+                        }),
+                    );
+                }
+                TraceItem::Frame { head, body } => {
+                    let selected = frame == self.cursor;
+                    let expanded = self.expanded.contains(&frame) && !body.is_empty();
+                    let marker = match (body.is_empty(), expanded) {
+                        (true, _) => "   ",
+                        (false, true) => " ▾ ",
+                        (false, false) => " ▸ ",
+                    };
+                    let mut spans = vec![Span::styled(marker, dim)];
+                    spans.extend(highlight::highlight_trace(head.trim_start()));
+                    if !body.is_empty() && !expanded {
+                        spans.push(Span::styled(format!("  +{}", body.len()), dim));
+                    }
+                    if selected {
+                        let highlight = Style::default().bg(Color::DarkGray);
+                        spans = spans
+                            .into_iter()
+                            .map(|span| {
+                                let style = span.style.patch(highlight);
+                                span.style(style)
+                            })
+                            .collect();
+                        cursor_lines.0 = lines.len();
+                    }
+                    lines.push(Line::from(spans));
+                    if expanded {
+                        for line in body {
+                            let mut spans = vec![Span::raw("     ")];
+                            spans.extend(highlight::highlight_trace(line.trim_start()));
+                            lines.push(Line::from(spans));
+                        }
+                    }
+                    if selected {
+                        cursor_lines.1 = lines.len() - 1;
+                    }
+                    frame += 1;
+                }
+            }
+        }
+
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!(" \u{2192} {}", err.display_suggestion()),
+            dim,
+        ));
+        (lines, cursor_lines)
+    }
+}
+
+/// First line to show so the cursor frame fits in `rows`, its head first
+/// when it does not
+fn scroll_for(cursor: (usize, usize), rows: usize) -> usize {
+    let (head, end) = cursor;
+    (end + 1).saturating_sub(rows).min(head)
+}
+
+impl<'a> Widget for ErrorModal<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = Self::centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Error ")
+            .title_bottom(" j/k frame  Space/Enter unfold  g events  e/Esc close ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red));
+        let rows = popup_area.height.saturating_sub(2) as usize;
+        let (lines, cursor) = self.build_lines(popup_area.width.saturating_sub(2) as usize);
+        let scroll = scroll_for(cursor, rows);
+
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((scroll as u16, 0))
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rules::{ErrorCategory, Severity};
+
+    const PYTHON: &str = "\
+Traceback (most recent call last):
+  File \"app.py\", line 12, in main
+    run()
+  File \"app.py\", line 8, in run
+    connect(port)
+ConnectionError: refused";
+
+    fn error(message: &str) -> ErrorRecord {
+        ErrorRecord {
+            agent_id: "backend".to_string(),
+            task_id: "T1".into(),
+            message: message.to_string(),
+            category: ErrorCategory::Network,
+            severity: Severity::Error,
+            retryable: true,
+            suggestion: "Check the service",
+            hint: None,
+            timestamp: "2026-02-08T12:00:00Z".parse().unwrap(),
+            cooldown_until: None,
+            event_index: 0,
+        }
+    }
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn frames_own_their_deeper_lines() {
+        let items = fold(PYTHON);
+        assert_eq!(items.len(), 4);
+        assert_eq!(
+            items[1],
+            TraceItem::Frame {
+                head: "  File \"app.py\", line 12, in main".to_string(),
+                body: vec!["    run()".to_string()],
+            }
+        );
+        assert_eq!(
+            items[3],
+            TraceItem::Text("ConnectionError: refused".to_string())
+        );
+
+        // Same-indent JS frames stay separate, with nothing to unfold
+        let js = fold("TypeError: x\n    at f (a.js:1:2)\n    at g (a.js:3:4)");
+        assert_eq!(js.len(), 3);
+        assert!(matches!(&js[2], TraceItem::Frame { body, .. } if body.is_empty()));
+
+        // Rust backtraces: the location line belongs to its numbered frame
+        let rust = "   4: app::run\n             at ./src/main.rs:8:5\n   5: app::main";
+        assert_eq!(frame_count(rust), 2);
+        assert_eq!(frame_count("connection refused"), 0);
+    }
+
+    #[test]
+    fn frames_start_folded_and_unfold_one_at_a_time() {
+        let err = error(PYTHON);
+        let folded = BTreeSet::new();
+        let modal = ErrorModal {
+            error: &err,
+            timezone: DisplayZone::Utc,
+            cursor: 1,
+            expanded: &folded,
+        };
+        let (lines, cursor) = modal.build_lines(60);
+        let shown = text(&lines);
+        assert_eq!(shown[0], " T1 @backend  Error Network  12:00:00");
+        assert_eq!(shown[2], " Traceback (most recent call last):");
+        assert_eq!(shown[3], " ▸ File \"app.py\", line 12, in main  +1");
+        assert_eq!(shown[4], " ▸ File \"app.py\", line 8, in run  +1");
+        assert_eq!(shown[5], " ConnectionError: refused");
+        assert_eq!(cursor, (4, 4));
+        assert_eq!(lines[4].spans[0].style.bg, Some(Color::DarkGray));
+
+        let second: BTreeSet<usize> = [1].into();
+        let modal = ErrorModal {
+            expanded: &second,
+            ..modal
+        };
+        let (lines, cursor) = modal.build_lines(60);
+        let shown = text(&lines);
+        assert_eq!(shown[3], " ▸ File \"app.py\", line 12, in main  +1");
+        assert_eq!(shown[4], " ▾ File \"app.py\", line 8, in run");
+        assert_eq!(shown[5], "     connect(port)");
+        assert_eq!(cursor, (4, 5));
+        assert_eq!(shown.last().unwrap(), " \u{2192} Check the service");
+    }
+
+    #[test]
+    fn plain_messages_wrap() {
+        let err = error(&format!("{}\n\nsecond", "x".repeat(30)));
+        let expanded = BTreeSet::new();
+        let modal = ErrorModal {
+            error: &err,
+            timezone: DisplayZone::Utc,
+            cursor: 0,
+            expanded: &expanded,
+        };
+        let shown = text(&modal.build_lines(22).0);
+        assert_eq!(shown[2], format!(" {}", "x".repeat(20)));
+        assert_eq!(shown[3], format!(" {}", "x".repeat(10)));
+        assert_eq!(shown[4], "");
+        assert_eq!(shown[5], " second");
+    }
+
+    #[test]
+    fn scrolls_the_cursor_frame_into_view() {
+        assert_eq!(scroll_for((3, 4), 10), 0);
+        assert_eq!(scroll_for((12, 14), 10), 5);
+        // A frame taller than the modal shows its head first
+        assert_eq!(scroll_for((12, 30), 10), 12);
+
+        let err = error(PYTHON);
+        let expanded = BTreeSet::new();
+        let area = Rect::new(0, 0, 60, 8);
+        let mut buf = Buffer::empty(area);
+        ErrorModal {
+            error: &err,
+            timezone: DisplayZone::Utc,
+            cursor: 0,
+            expanded: &expanded,
+        }
+        .render(area, &mut buf);
+    }
+}
@@ -0,0 +1,228 @@
+//! Live hook event log
+//!
+//! The bottom half of the ops layout (`o`): one line per hook event with
+//! its time, agent, type, task and tool or message, newest at the bottom.
+//! Errors and rate limits are colored.
+//!
+//! `g` on a task's error anchors the log at that error's event instead:
+//! the events leading up to it fill most of the panel, the error line is
+//! highlighted and other agents' events are dimmed, so the tool calls that
+//! failed can be read without grepping the JSONL.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use crate::data::hook_parser::{EventType, HookEvent};
+use crate::data::state::DashboardState;
+
+/// Share of an anchored panel kept for events after the anchor
+const AFTER_ANCHOR: usize = 4;
+
+/// Event log widget
+pub struct EventLog<'a> {
+    state: &'a DashboardState,
+    /// Event to show in context instead of following the newest
+    anchor: Option<usize>,
+}
+
+impl<'a> EventLog<'a> {
+    pub fn new(state: &'a DashboardState) -> Self {
+        Self {
+            state,
+            anchor: None,
+        }
+    }
+
+    /// Show the events around `events[index]` instead of the newest
+    pub fn with_anchor(mut self, index: Option<usize>) -> Self {
+        self.anchor = index;
+        self
+    }
+
+    /// Lines for the newest events that fit `rows`, newest last, or for
+    /// the events around the anchor
+    fn build_lines(&self, rows: usize) -> Vec<Line<'static>> {
+        let events = &self.state.events;
+        if events.is_empty() {
+            return vec![Line::styled(
+                " No hook events yet",
+                Style::default().fg(Color::DarkGray),
+            )];
+        }
+        let Some(anchor) = self.anchor.map(|i| i.min(events.len() - 1)) else {
+            let skip = events.len().saturating_sub(rows);
+            return events[skip..].iter().map(|e| self.event_line(e)).collect();
+        };
+        let (start, end) = window(events.len(), anchor, rows);
+        let agent = &events[anchor].agent_id;
+        (start..end)
+            .map(|i| {
+                let line = self.event_line(&events[i]);
+                if i == anchor {
+                    line.patch_style(
+                        Style::default()
+                            .bg(Color::DarkGray)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else if events[i].agent_id != *agent {
+                    line.patch_style(Style::default().fg(Color::DarkGray))
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    fn event_line(&self, event: &HookEvent) -> Line<'static> {
+        let (kind, color) = match event.event_type {
+            EventType::AgentStart => ("agent_start", Color::Green),
+            EventType::AgentEnd => ("agent_end", Color::Green),
+            EventType::ToolStart => ("tool_start", Color::Cyan),
+            EventType::ToolEnd => ("tool_end", Color::Cyan),
+            EventType::Error => ("error", Color::Red),
+            EventType::Heartbeat => ("heartbeat", Color::DarkGray),
+            EventType::RunStart => ("run_start", Color::Magenta),
+            EventType::RateLimit => ("rate_limit", Color::Yellow),
+        };
+        let detail = event
+            .error_message
+            .as_deref()
+            .or(event.tool_name.as_deref())
+            .unwrap_or("")
+            .replace('\n', " ");
+        Line::from(vec![
+            Span::styled(
+                format!(
+                    " {} ",
+                    self.state.timezone.format(event.timestamp, "%H:%M:%S")
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::raw(format!("{:<20} ", event.agent_id)),
+            Span::styled(format!("{kind:<11} "), Style::default().fg(color)),
+            Span::styled(
+                format!("{:<12} ", event.task_id),
+                Style::default().fg(Color::White),
+            ),
+            Span::raw(detail),
+        ])
+    }
+}
+
+/// Range of events shown for `anchor` in `rows`: mostly what came before
+/// it, a few after
+fn window(len: usize, anchor: usize, rows: usize) -> (usize, usize) {
+    let after = (rows / AFTER_ANCHOR).min(len - anchor - 1);
+    let end = (anchor + after + 1).min(len);
+    let start = end.saturating_sub(rows);
+    (start, (start + rows).min(len))
+}
+
+impl<'a> Widget for EventLog<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut block = Block::default()
+            .title(format!(" Events ({}) ", self.state.events.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+        if let Some(anchor) = self.anchor {
+            block = block
+                .title(format!(" at #{} ", anchor + 1))
+                .title_bottom(" j/k step  Esc live ")
+                .border_style(Style::default().fg(Color::Yellow));
+        }
+        let rows = area.height.saturating_sub(2) as usize;
+        Paragraph::new(self.build_lines(rows))
+            .block(block)
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    #[test]
+    fn shows_the_newest_events_that_fit() {
+        let mut state = DashboardState {
+            timezone: crate::data::timezone::DisplayZone::Utc,
+            ..Default::default()
+        };
+        state.update_from_events(
+            &parse_hook_events(concat!(
+                r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"backend","task_id":"T1","session_id":"s"}"#,
+                "\n",
+                r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:05Z","agent_id":"backend","task_id":"T1","session_id":"s","tool_name":"Bash"}"#,
+                "\n",
+                r#"{"event_type":"error","timestamp":"2026-02-08T10:00:09Z","agent_id":"backend","task_id":"T1","session_id":"s","error_message":"connection refused"}"#,
+            ))
+            .events,
+        );
+        let log = EventLog::new(&state);
+        let lines = log.build_lines(2);
+        assert_eq!(lines.len(), 2);
+        let first = lines[0].to_string();
+        assert!(first.starts_with(" 10:00:05 backend"), "{first}");
+        assert!(first.ends_with("Bash"), "{first}");
+        assert!(lines[1].to_string().ends_with("connection refused"));
+        assert_eq!(lines[1].spans[2].style.fg, Some(Color::Red));
+
+        let area = Rect::new(0, 0, 80, 6);
+        let mut buf = Buffer::empty(area);
+        log.render(area, &mut buf);
+    }
+
+    #[test]
+    fn anchor_shows_the_events_before_an_error() {
+        let mut state = DashboardState {
+            timezone: crate::data::timezone::DisplayZone::Utc,
+            ..Default::default()
+        };
+        let lines: Vec<String> = (0..10)
+            .map(|i| {
+                let (agent, kind) = match i {
+                    6 => ("backend", r#""event_type":"error","error_message":"exit 1""#),
+                    3 => ("frontend", r#""event_type":"tool_start","tool_name":"Edit""#),
+                    _ => ("backend", r#""event_type":"tool_start","tool_name":"Bash""#),
+                };
+                format!(
+                    r#"{{{kind},"timestamp":"2026-02-08T10:00:0{i}Z","agent_id":"{agent}","task_id":"T1","session_id":"s"}}"#
+                )
+            })
+            .collect();
+        state.update_from_events(&parse_hook_events(&lines.join("\n")).events);
+        let anchor = state.recent_errors[0].event_index;
+        assert_eq!(anchor, 6);
+
+        let log = EventLog::new(&state).with_anchor(Some(anchor));
+        let shown = log.build_lines(8);
+        // Five events before the error, the error, two after
+        assert_eq!(shown.len(), 8);
+        assert!(shown[0].to_string().starts_with(" 10:00:01"));
+        assert!(shown[5].to_string().ends_with("exit 1"));
+        assert_eq!(shown[5].style.bg, Some(Color::DarkGray));
+        assert_eq!(shown[2].style.fg, Some(Color::DarkGray));
+        assert_eq!(shown[1].style.fg, None);
+
+        assert_eq!(window(10, 6, 8), (1, 9));
+        assert_eq!(window(10, 9, 8), (2, 10));
+        assert_eq!(window(10, 1, 8), (0, 8));
+        assert_eq!(window(3, 1, 8), (0, 3));
+
+        let area = Rect::new(0, 0, 80, 10);
+        let mut buf = Buffer::empty(area);
+        log.render(area, &mut buf);
+    }
+
+    #[test]
+    fn empty_log_says_so() {
+        let state = DashboardState::default();
+        let lines = EventLog::new(&state).build_lines(5);
+        assert_eq!(lines[0].to_string(), " No hook events yet");
+    }
+}
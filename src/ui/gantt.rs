@@ -1,10 +1,18 @@
 //! Gantt chart widget
 //!
-//! Two view modes:
-//! - Tree: phases with `▼`/`▶` collapse, tree connectors `├─`/`└─`, progress bars
+//! Six view modes:
+//! - Tree: phases with `▼`/`▶` collapse, tree connectors `├─`/`└─`, progress bars,
+//!   then hook activity on task IDs outside the plan
 //! - HorizontalBar: time-based horizontal bar chart per task
+//! - AgentLanes: one activity lane per agent (see [`crate::ui::lanes`])
+//! - Heatmap: hook events per hour of day (see [`crate::ui::heatmap`])
+//! - Workload: open tasks and points per agent (see [`crate::ui::workload`])
+//! - Calendar: the week ahead by due date (see [`crate::ui::calendar`])
 
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 
 use chrono::{DateTime, Utc};
 use ratatui::{
@@ -15,8 +23,13 @@ use ratatui::{
     widgets::{Block, Borders, StatefulWidget, Widget},
 };
 
-use crate::data::state::DashboardState;
-use crate::data::tasks_parser::TaskStatus;
+pub use crate::config::LineNumbers;
+use crate::data::gating::{GateLock, GateReport};
+use crate::data::state::{DashboardState, UntrackedWork};
+use crate::data::statuses::{self, StatusDef};
+use crate::data::tasks_parser::{ParsedPhase, ParsedTask, TaskStatus};
+use crate::ui::timefmt::humanize;
+use crate::ui::{calendar, heatmap, lanes, workload};
 
 /// View mode for the gantt panel
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -24,6 +37,29 @@ pub enum GanttViewMode {
     #[default]
     Tree,
     HorizontalBar,
+    /// Per-agent timeline; rows are not selectable
+    AgentLanes,
+    /// Hour-of-day activity; rows are not selectable
+    Heatmap,
+    /// Open work per agent; rows are not selectable
+    Workload,
+    /// Tasks by due date; rows are not selectable
+    Calendar,
+}
+
+/// A gantt row by identity rather than position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemKey {
+    Phase(String),
+    Task { phase: String, id: String },
+}
+
+/// Selection and collapse state keyed on phase/task IDs, taken before a
+/// reload and restored after it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GanttAnchor {
+    pub selected: Option<ItemKey>,
+    pub collapsed: HashSet<String>,
 }
 
 /// Selection state for the gantt view
@@ -39,6 +75,181 @@ pub struct GanttState {
     pub collapsed: HashSet<usize>,
     /// Current view mode
     pub view_mode: GanttViewMode,
+    /// Tree task rows kept between frames
+    pub row_cache: RowCache,
+    /// Bar view time scale kept between frames
+    pub bar_cache: BarCache,
+    /// Tree view untracked work kept between frames
+    pub untracked_cache: UntrackedCache,
+    pub line_numbers: LineNumbers,
+}
+
+/// What a cached tree task row was rendered from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct RowKey {
+    /// Hash of everything drawn on the row
+    content: u64,
+    selected: bool,
+    focused: bool,
+    width: u16,
+}
+
+/// Rendered tree task rows, so an unchanged plan is not re-formatted every
+/// frame. Cleared whenever the plan is reloaded.
+#[derive(Debug, Clone, Default)]
+pub struct RowCache {
+    rows: HashMap<RowKey, Line<'static>>,
+}
+
+impl RowCache {
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+/// Width of the bar area of the bar view, in cells
+const BAR_WIDTH: usize = 30;
+/// A full row of each bar cell, sliced for bars and padding instead of
+/// building a string per row
+const BLANK_CELLS: &str = "                              ";
+const SOLID_CELLS: &str = "\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}\u{2588}";
+const SHADED_CELLS: &str = "\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}\u{2591}";
+const SPAN_CELLS: &str = "\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}\u{2501}";
+
+/// The first `n` cells (at most [`BAR_WIDTH`]) of a row of cells
+fn cells(row: &'static str, n: usize) -> &'static str {
+    let cell = row.chars().next().map_or(1, char::len_utf8);
+    &row[..n.min(BAR_WIDTH) * cell]
+}
+
+/// One started phase on the bar view's time scale
+#[derive(Debug, Clone, Copy)]
+struct PhaseSpan {
+    phase: usize,
+    start: DateTime<Utc>,
+    /// Last completion among its started tasks
+    end: Option<DateTime<Utc>>,
+    /// Whether a started task is still open, so the span runs up to now
+    open: bool,
+}
+
+impl PhaseSpan {
+    fn end(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        match self.end {
+            Some(end) if self.open => end.max(now),
+            Some(end) => end,
+            None => now,
+        }
+    }
+}
+
+/// What the bar view's time scale is derived from, before `now` is applied
+#[derive(Debug, Clone, Default)]
+struct BarBounds {
+    first_start: Option<DateTime<Utc>>,
+    last_end: Option<DateTime<Utc>>,
+    /// Whether any started task is still open
+    running: bool,
+    /// Widest task ID plus a space
+    label_width: usize,
+    spans: Vec<PhaseSpan>,
+}
+
+impl BarBounds {
+    fn of(state: &DashboardState) -> Self {
+        let mut bounds = BarBounds::default();
+        let mut widest = None;
+        for (pi, phase) in state.shown_phases() {
+            let mut span: Option<PhaseSpan> = None;
+            for (_, task) in state.shown_tasks(phase) {
+                widest = widest.max(Some(task.id.len()));
+                let Some(timing) = state.task_times.get(task.id.as_str()) else {
+                    continue;
+                };
+                let Some(started) = timing.started_at else {
+                    if let Some(c) = timing.completed_at {
+                        bounds.last_end = bounds.last_end.max(Some(c));
+                    }
+                    continue;
+                };
+                bounds.first_start = Some(bounds.first_start.map_or(started, |s| s.min(started)));
+                let span = span.get_or_insert(PhaseSpan {
+                    phase: pi,
+                    start: started,
+                    end: None,
+                    open: false,
+                });
+                span.start = span.start.min(started);
+                match timing.completed_at {
+                    Some(c) => {
+                        bounds.last_end = bounds.last_end.max(Some(c));
+                        span.end = span.end.max(Some(c));
+                    }
+                    None => {
+                        bounds.running = true;
+                        span.open = true;
+                    }
+                }
+            }
+            bounds.spans.extend(span);
+        }
+        bounds.label_width = widest.unwrap_or(8) + 1;
+        bounds
+    }
+}
+
+/// Bar view time scale, rebuilt only when the state's revision changes
+/// rather than from every task's timing on every frame
+#[derive(Debug, Clone, Default)]
+pub struct BarCache {
+    /// Revision and scrub point the bounds were derived at
+    key: Option<(u64, Option<DateTime<Utc>>)>,
+    bounds: BarBounds,
+}
+
+impl BarCache {
+    fn bounds(&mut self, state: &DashboardState) -> &BarBounds {
+        let key = (state.revision, state.scrub_at);
+        if self.key != Some(key) {
+            self.bounds = BarBounds::of(state);
+            self.key = Some(key);
+        }
+        &self.bounds
+    }
+}
+
+/// Work outside the plan, rebuilt only when the state's revision changes
+/// rather than from every hook event on every frame
+#[derive(Debug, Clone, Default)]
+pub struct UntrackedCache {
+    /// Revision and scrub point the work was collected at
+    key: Option<(u64, Option<DateTime<Utc>>)>,
+    work: Vec<UntrackedWork>,
+}
+
+impl UntrackedCache {
+    fn work(&mut self, state: &DashboardState) -> &[UntrackedWork] {
+        let key = (state.revision, state.scrub_at);
+        if self.key != Some(key) {
+            self.work = state.untracked_work();
+            self.key = Some(key);
+        }
+        &self.work
+    }
+}
+
+/// A tree row before cached rows are borrowed
+enum TreeRow {
+    Owned(Line<'static>),
+    Cached(RowKey),
 }
 
 impl GanttState {
@@ -61,11 +272,25 @@ impl GanttState {
         }
     }
 
-    /// Toggle the view mode between Tree and HorizontalBar
+    /// Cycle the view mode: Tree, HorizontalBar, AgentLanes, Heatmap,
+    /// Workload, Calendar
     pub fn toggle_view(&mut self) {
         self.view_mode = match self.view_mode {
             GanttViewMode::Tree => GanttViewMode::HorizontalBar,
-            GanttViewMode::HorizontalBar => GanttViewMode::Tree,
+            GanttViewMode::HorizontalBar => GanttViewMode::AgentLanes,
+            GanttViewMode::AgentLanes => GanttViewMode::Heatmap,
+            GanttViewMode::Heatmap => GanttViewMode::Workload,
+            GanttViewMode::Workload => GanttViewMode::Calendar,
+            GanttViewMode::Calendar => GanttViewMode::Tree,
+        };
+    }
+
+    /// Cycle the line numbers: off, absolute, relative
+    pub fn cycle_line_numbers(&mut self) {
+        self.line_numbers = match self.line_numbers {
+            LineNumbers::Off => LineNumbers::Absolute,
+            LineNumbers::Absolute => LineNumbers::Relative,
+            LineNumbers::Relative => LineNumbers::Off,
         };
     }
 
@@ -73,13 +298,13 @@ impl GanttState {
     /// Accounts for collapsed phases hiding their tasks.
     pub fn selected_phase_index(&self, state: &DashboardState) -> Option<usize> {
         let mut idx = 0;
-        for (pi, phase) in state.phases.iter().enumerate() {
+        for (pi, phase) in state.shown_phases() {
             if idx == self.selected {
                 return Some(pi);
             }
             idx += 1;
             if !self.collapsed.contains(&pi) {
-                idx += phase.tasks.len();
+                idx += state.shown_tasks(phase).count();
             }
         }
         None
@@ -89,13 +314,13 @@ impl GanttState {
     /// Returns None if a phase header is selected or out of range.
     pub fn selected_task(&self, state: &DashboardState) -> Option<(usize, usize)> {
         let mut idx = 0;
-        for (pi, phase) in state.phases.iter().enumerate() {
+        for (pi, phase) in state.shown_phases() {
             if idx == self.selected {
                 return None; // phase header selected
             }
             idx += 1;
             if !self.collapsed.contains(&pi) {
-                for ti in 0..phase.tasks.len() {
+                for (ti, _) in state.shown_tasks(phase) {
                     if idx == self.selected {
                         return Some((pi, ti));
                     }
@@ -105,27 +330,173 @@ impl GanttState {
         }
         None
     }
+
+    /// Row of task `ti` of phase `pi`, or None when the filter hides it or
+    /// its phase is collapsed
+    pub fn row_of_task(&self, state: &DashboardState, pi: usize, ti: usize) -> Option<usize> {
+        let mut idx = 0;
+        for (shown_pi, phase) in state.shown_phases() {
+            idx += 1;
+            let expanded = !self.collapsed.contains(&shown_pi);
+            for (shown_ti, _) in state.shown_tasks(phase).filter(|_| expanded) {
+                if (shown_pi, shown_ti) == (pi, ti) {
+                    return Some(idx);
+                }
+                idx += 1;
+            }
+        }
+        None
+    }
+
+    /// Capture the selection and collapsed phases by ID
+    pub fn anchor(&self, state: &DashboardState) -> GanttAnchor {
+        let selected = match self.selected_task(state) {
+            Some((pi, ti)) => Some(ItemKey::Task {
+                phase: state.phases[pi].id.clone(),
+                id: state.phases[pi].tasks[ti].id.clone(),
+            }),
+            None => self
+                .selected_phase_index(state)
+                .map(|pi| ItemKey::Phase(state.phases[pi].id.clone())),
+        };
+        let collapsed = self
+            .collapsed
+            .iter()
+            .filter_map(|&pi| state.phases.get(pi).map(|p| p.id.clone()))
+            .collect();
+        GanttAnchor {
+            selected,
+            collapsed,
+        }
+    }
+
+    /// Re-point indices at the anchored IDs after `state` changed, then
+    /// [`sync_with`](Self::sync_with). A removed task falls back to its phase
+    /// header; a removed phase keeps the old position, clamped.
+    pub fn restore(&mut self, anchor: &GanttAnchor, state: &DashboardState) {
+        self.collapsed = state
+            .phases
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| anchor.collapsed.contains(&p.id))
+            .map(|(pi, _)| pi)
+            .collect();
+
+        let mut phase_row = None;
+        let mut task_row = None;
+        let mut idx = 0;
+        for (pi, phase) in state.shown_phases() {
+            let expanded = !self.collapsed.contains(&pi);
+            match &anchor.selected {
+                Some(ItemKey::Phase(id)) | Some(ItemKey::Task { phase: id, .. })
+                    if phase_row.is_none() && phase.id == *id =>
+                {
+                    phase_row = Some(idx);
+                }
+                _ => {}
+            }
+            if let Some(ItemKey::Task { id, .. }) = &anchor.selected {
+                if expanded && task_row.is_none() {
+                    if let Some(row) = state.shown_tasks(phase).position(|(_, t)| t.id == *id) {
+                        task_row = Some(idx + 1 + row);
+                    }
+                }
+            }
+            idx += 1;
+            if expanded {
+                idx += state.shown_tasks(phase).count();
+            }
+        }
+
+        if let Some(row) = task_row.or(phase_row) {
+            self.selected = row;
+        }
+        self.sync_with(state);
+    }
+
+    /// Number of selectable rows: phase headers plus tasks of expanded phases
+    pub fn visible_rows(&self, state: &DashboardState) -> usize {
+        state
+            .shown_phases()
+            .map(|(pi, p)| {
+                1 + if self.collapsed.contains(&pi) {
+                    0
+                } else {
+                    state.shown_tasks(p).count()
+                }
+            })
+            .sum()
+    }
+
+    /// Tasks of expanded phases as a markdown table (ID, name, status,
+    /// agent, duration) for pasting a status snapshot elsewhere
+    pub fn markdown_table(&self, state: &DashboardState) -> String {
+        let now = state.now();
+        let cell = |text: &str| text.replace('|', "\\|").replace('\n', " ");
+        let mut out =
+            String::from("| ID | Name | Status | Agent | Duration |\n|---|---|---|---|---|\n");
+        for (pi, phase) in state.shown_phases() {
+            if self.collapsed.contains(&pi) {
+                continue;
+            }
+            for (_, task) in state.shown_tasks(phase) {
+                let agent = state
+                    .agent_for_task(&task.id)
+                    .or(task.agent.as_deref())
+                    .unwrap_or("");
+                let duration = state
+                    .task_times
+                    .get(task.id.as_str())
+                    .and_then(|t| Some(t.completed_at.unwrap_or(now) - t.started_at?))
+                    .map(humanize)
+                    .unwrap_or_default();
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    cell(&task.id),
+                    cell(&task.name),
+                    task.status.label(),
+                    cell(agent),
+                    duration
+                ));
+            }
+        }
+        out
+    }
+
+    /// Bring `total_items` up to date and clamp the selection into range, so
+    /// it never points past the end between a reload and the next render
+    pub fn sync_with(&mut self, state: &DashboardState) {
+        self.collapsed.retain(|&pi| pi < state.phases.len());
+        self.row_cache.clear();
+        self.total_items = self.visible_rows(state);
+        self.selected = self.selected.min(self.total_items.saturating_sub(1));
+    }
 }
 
-/// Color for a task status
-fn status_color(status: &TaskStatus) -> Color {
+/// Color for a task status (custom statuses use their configured color)
+pub fn status_color(status: &TaskStatus, statuses: &[StatusDef]) -> Color {
     match status {
         TaskStatus::Completed => Color::Green,
         TaskStatus::InProgress => Color::Yellow,
         TaskStatus::Pending => Color::DarkGray,
         TaskStatus::Failed => Color::Red,
         TaskStatus::Blocked => Color::Magenta,
+        TaskStatus::Custom(name) => statuses::find(statuses, name)
+            .and_then(|d| d.color.as_deref())
+            .and_then(|c| c.parse().ok())
+            .unwrap_or(Color::Cyan),
     }
 }
 
 /// Status icon character
-fn status_icon(status: &TaskStatus) -> &'static str {
+fn status_icon(status: &TaskStatus, statuses: &[StatusDef]) -> String {
     match status {
-        TaskStatus::Completed => "[x]",
-        TaskStatus::InProgress => "[/]",
-        TaskStatus::Pending => "[ ]",
-        TaskStatus::Failed => "[!]",
-        TaskStatus::Blocked => "[B]",
+        TaskStatus::Completed => "[x]".to_string(),
+        TaskStatus::InProgress => "[/]".to_string(),
+        TaskStatus::Pending => "[ ]".to_string(),
+        TaskStatus::Failed => "[!]".to_string(),
+        TaskStatus::Blocked => "[B]".to_string(),
+        TaskStatus::Custom(name) => statuses::icon(name, statuses),
     }
 }
 
@@ -143,26 +514,108 @@ fn progress_bar(ratio: f32, width: usize) -> String {
     bar
 }
 
+/// Highlight for tasks over a WIP limit
+fn wip_excess_style() -> Style {
+    Style::default()
+        .fg(Color::Black)
+        .bg(Color::LightRed)
+        .add_modifier(Modifier::BOLD)
+}
+
 /// The Gantt widget renders the dashboard state as a scrollable task list
 pub struct GanttWidget<'a> {
     state: &'a DashboardState,
     focused: bool,
+    /// Tasks past a WIP limit, highlighted as a warning
+    wip_excess: Option<&'a HashSet<String>>,
+    /// Locked phases and premature tasks
+    gates: Option<&'a GateReport>,
+    /// Spell out each task's status (see [`crate::ui::plain`])
+    plain: bool,
+    /// Tasks whose pull request has failing checks
+    red_prs: Option<&'a HashSet<String>>,
 }
 
 impl<'a> GanttWidget<'a> {
     pub fn new(state: &'a DashboardState, focused: bool) -> Self {
-        Self { state, focused }
+        Self {
+            state,
+            focused,
+            wip_excess: None,
+            gates: None,
+            plain: false,
+            red_prs: None,
+        }
+    }
+
+    pub fn with_plain(mut self, plain: bool) -> Self {
+        self.plain = plain;
+        self
+    }
+
+    /// ` (Completed)` after a task row in plain mode, where colors are gone
+    fn status_word(&self, status: &TaskStatus) -> Option<Span<'static>> {
+        self.plain
+            .then(|| Span::raw(format!(" ({})", status.label())))
+    }
+
+    pub fn with_gates(mut self, gates: &'a GateReport) -> Self {
+        self.gates = Some(gates);
+        self
+    }
+
+    /// Header badge for a locked phase
+    fn gate_badge(&self, phase_id: &str) -> Option<Span<'static>> {
+        let text = match self.gates?.lock_for(phase_id)? {
+            GateLock::Blocked => " BLOCKED ".to_string(),
+            GateLock::Waiting(gate) => format!(" LOCKED until {gate} "),
+        };
+        Some(Span::styled(
+            text,
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ))
+    }
+
+    fn is_premature(&self, task_id: &str) -> bool {
+        self.gates.is_some_and(|g| g.is_premature(task_id))
+    }
+
+    pub fn with_wip_excess(mut self, excess: &'a HashSet<String>) -> Self {
+        self.wip_excess = Some(excess);
+        self
+    }
+
+    fn is_wip_excess(&self, task_id: &str) -> bool {
+        self.wip_excess.is_some_and(|e| e.contains(task_id))
     }
 
-    /// Build lines for the tree view (with collapse, connectors, progress bars)
-    fn build_tree_lines(&self, gantt_state: &GanttState) -> Vec<(Line<'static>, bool)> {
+    pub fn with_red_prs(mut self, red: &'a HashSet<String>) -> Self {
+        self.red_prs = Some(red);
+        self
+    }
+
+    fn is_pr_red(&self, task_id: &str) -> bool {
+        self.red_prs.is_some_and(|r| r.contains(task_id))
+    }
+
+    /// Build lines for the tree view (with collapse, connectors, progress
+    /// bars). Task rows come from `cache` when nothing on them changed.
+    fn build_tree_lines<'c>(
+        &self,
+        gantt_state: &GanttState,
+        cache: &'c mut RowCache,
+        width: u16,
+    ) -> Vec<(Cow<'c, Line<'static>>, bool)> {
         let mut lines = Vec::new();
         let mut idx = 0;
 
-        for (pi, phase) in self.state.phases.iter().enumerate() {
+        for (pi, phase) in self.state.shown_phases() {
             let is_selected = idx == gantt_state.selected;
             let is_collapsed = gantt_state.collapsed.contains(&pi);
-            let progress = phase.progress();
+            let progress = self.state.phase_progress(phase);
             let pct = (progress * 100.0) as u8;
             let arrow = if is_collapsed { "\u{25B6}" } else { "\u{25BC}" };
             let bar = progress_bar(progress, 6);
@@ -183,98 +636,276 @@ impl<'a> GanttWidget<'a> {
                 Span::styled(bar, Style::default().fg(Color::Green)),
                 Span::styled(format!(" {pct}%"), Style::default().fg(Color::DarkGray)),
             ]);
-            lines.push((header, is_selected));
+            let mut header = header;
+            if let Some(badge) = self.gate_badge(&phase.id) {
+                header.spans.push(Span::raw(" "));
+                header.spans.push(badge);
+            }
+            lines.push((TreeRow::Owned(header), is_selected));
             idx += 1;
 
-            if is_collapsed {
+            if !is_collapsed {
+                self.push_tree_tasks(phase, gantt_state, cache, width, &mut idx, &mut lines);
+            }
+            for name in &phase.milestones {
+                let reached = self.state.milestone_reached(pi);
+                let marker = milestone_line(" ", name, reached, 32);
+                lines.push((TreeRow::Owned(marker), false));
+            }
+        }
+
+        // Drop rows for tasks that changed since they were cached
+        let used: HashSet<RowKey> = lines
+            .iter()
+            .filter_map(|(row, _)| match row {
+                TreeRow::Cached(key) => Some(*key),
+                TreeRow::Owned(_) => None,
+            })
+            .collect();
+        if cache.rows.len() > used.len() * 2 {
+            cache.rows.retain(|key, _| used.contains(key));
+        }
+
+        let cache = &*cache;
+        lines
+            .into_iter()
+            .map(|(row, selected)| match row {
+                TreeRow::Owned(line) => (Cow::Owned(line), selected),
+                TreeRow::Cached(key) => (Cow::Borrowed(&cache.rows[&key]), selected),
+            })
+            .collect()
+    }
+
+    /// Task rows under an expanded phase in the tree view
+    fn push_tree_tasks(
+        &self,
+        phase: &ParsedPhase,
+        gantt_state: &GanttState,
+        cache: &mut RowCache,
+        width: u16,
+        idx: &mut usize,
+        lines: &mut Vec<(TreeRow, bool)>,
+    ) {
+        let statuses = &self.state.custom_statuses;
+        let tasks: Vec<&ParsedTask> = self.state.shown_tasks(phase).map(|(_, t)| t).collect();
+        for (row, task) in tasks.iter().enumerate() {
+            let is_selected = *idx == gantt_state.selected;
+            let is_last = row == tasks.len() - 1;
+            let wip_excess = self.is_wip_excess(&task.id);
+            let premature = self.is_premature(&task.id);
+            let review = self.state.is_awaiting_review(&task.id);
+            let pr_red = self.is_pr_red(&task.id);
+
+            let mut hasher = DefaultHasher::new();
+            (&task.id, &task.name, &task.status, &task.agent).hash(&mut hasher);
+            (is_last, wip_excess, premature, review, pr_red, self.plain).hash(&mut hasher);
+            let key = RowKey {
+                content: hasher.finish(),
+                selected: is_selected,
+                focused: self.focused,
+                width,
+            };
+            *idx += 1;
+            if cache.rows.contains_key(&key) {
+                lines.push((TreeRow::Cached(key), is_selected));
                 continue;
             }
 
-            let task_count = phase.tasks.len();
-            for (ti, task) in phase.tasks.iter().enumerate() {
-                let is_selected = idx == gantt_state.selected;
-                let icon = status_icon(&task.status);
-                let color = status_color(&task.status);
-                let connector = if ti == task_count - 1 {
-                    "\u{2514}\u{2500}"
-                } else {
-                    "\u{251C}\u{2500}"
-                };
-                let agent_str = task
-                    .agent
-                    .as_deref()
-                    .map(|a| format!(" @{a}"))
-                    .unwrap_or_default();
+            let icon = status_icon(&task.status, statuses);
+            let color = status_color(&task.status, statuses);
+            let connector = if is_last {
+                "\u{2514}\u{2500}"
+            } else {
+                "\u{251C}\u{2500}"
+            };
+            let agent_str = task
+                .agent
+                .as_deref()
+                .map(|a| format!(" @{a}"))
+                .unwrap_or_default();
+
+            let mut line = Line::from(vec![
+                Span::styled(
+                    format!("  {connector} "),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(icon.to_string(), Style::default().fg(color)),
+                Span::raw(" "),
+                Span::styled(
+                    task.id.clone(),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(": "),
+                Span::raw(task.name.clone()),
+                Span::styled(agent_str, Style::default().fg(Color::Blue)),
+            ]);
+            if wip_excess {
+                line.spans.push(Span::styled(" WIP!", wip_excess_style()));
+            }
+            if premature {
+                line.spans.push(Span::styled(" GATED!", wip_excess_style()));
+            }
+            if review {
+                line.spans.push(Span::styled(
+                    " REVIEW",
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            if pr_red {
+                line.spans
+                    .push(Span::styled(" PR\u{2717}", wip_excess_style()));
+            }
+            line.spans.extend(self.status_word(&task.status));
+            cache.rows.insert(key, line);
+            lines.push((TreeRow::Cached(key), is_selected));
+        }
+    }
 
-                let line = Line::from(vec![
-                    Span::styled(
-                        format!("  {connector} "),
-                        Style::default().fg(Color::DarkGray),
-                    ),
-                    Span::styled(icon.to_string(), Style::default().fg(color)),
-                    Span::raw(" "),
-                    Span::styled(
-                        task.id.clone(),
-                        Style::default()
-                            .fg(Color::White)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::raw(": "),
-                    Span::raw(task.name.clone()),
-                    Span::styled(agent_str, Style::default().fg(Color::Blue)),
-                ]);
-                lines.push((line, is_selected));
-                idx += 1;
+    /// Section below the tree listing `work` on task IDs the plan does not
+    /// have, one row per ID; none of its rows are selectable
+    fn build_untracked_lines(&self, work: &[UntrackedWork]) -> Vec<Line<'static>> {
+        if work.is_empty() {
+            return Vec::new();
+        }
+        let now = self.state.now();
+        let mut lines = Vec::with_capacity(work.len() + 1);
+        lines.push(Line::from(vec![
+            Span::styled(" \u{25C7} ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "Untracked work",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(" {}", work.len()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        for (i, w) in work.iter().enumerate() {
+            let connector = if i == work.len() - 1 {
+                "\u{2514}\u{2500}"
+            } else {
+                "\u{251C}\u{2500}"
+            };
+            let (icon, color) = if w.running {
+                ("[/]", Color::Yellow)
+            } else {
+                ("[?]", Color::DarkGray)
+            };
+            let agents: Vec<String> = w.agents.iter().map(|a| format!("@{a}")).collect();
+            let took = humanize(if w.running { now } else { w.last_seen } - w.first_seen);
+            let events = if w.events == 1 { "event" } else { "events" };
+            let mut line = Line::from(vec![
+                Span::styled(
+                    format!("  {connector} "),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(icon, Style::default().fg(color)),
+                Span::raw(" "),
+                Span::styled(
+                    w.task_id.clone(),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(" {}", agents.join(" ")),
+                    Style::default().fg(Color::Blue),
+                ),
+                Span::styled(
+                    format!("  {} {events}, {took}", w.events),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]);
+            if w.running {
+                line.spans.extend(self.status_word(&TaskStatus::InProgress));
             }
+            lines.push(line);
         }
         lines
     }
 
-    /// Build lines for the horizontal bar view
-    fn build_bar_lines(&self, gantt_state: &GanttState) -> Vec<(Line<'static>, bool)> {
-        if self.state.phases.is_empty() {
+    /// Build lines for the horizontal bar view on the time scale of `bounds`
+    fn build_bar_lines(
+        &self,
+        gantt_state: &GanttState,
+        bounds: &BarBounds,
+    ) -> Vec<(Line<'static>, bool)> {
+        if self.state.shown_phases().next().is_none() {
             return vec![(Line::raw("  No tasks"), false)];
         }
-
-        // Collect timing info for time bounds calculation
-        let now = Utc::now();
-        let mut all_starts: Vec<DateTime<Utc>> = Vec::new();
-        let mut all_ends: Vec<DateTime<Utc>> = Vec::new();
-        for phase in &self.state.phases {
-            for task in &phase.tasks {
-                if let Some(timing) = self.state.task_times.get(&task.id) {
-                    if let Some(s) = timing.started_at {
-                        all_starts.push(s);
-                    }
-                    if let Some(c) = timing.completed_at {
-                        all_ends.push(c);
-                    }
-                }
-            }
+        let statuses = &self.state.custom_statuses;
+
+        let now = self.state.now();
+        let earliest = bounds.first_start.unwrap_or(now);
+        let mut latest = bounds.last_end.unwrap_or(now);
+        // Open bars run up to now, so they grow every tick; a past state's
+        // stop at the scrub point
+        if bounds.running || self.state.scrub_at.is_some() {
+            latest = latest.max(now);
         }
-        let earliest = all_starts.iter().copied().min().unwrap_or(now);
-        let latest = all_ends.iter().copied().max().unwrap_or(now);
+        // The right edge is now: mark it past the end of the bars
+        let now_marker = (latest == now && bounds.first_start.is_some())
+            .then(|| Span::styled("\u{2502}", Style::default().fg(Color::Yellow)));
         let total_secs = (latest - earliest).num_seconds().max(1) as f64;
+        let offset = |t: DateTime<Utc>| (t - earliest).num_seconds().max(0) as f64 / total_secs;
 
-        // Determine label width from all task IDs
-        let label_width = self
-            .state
-            .phases
-            .iter()
-            .flat_map(|p| p.tasks.iter().map(|t| t.id.len()))
-            .max()
-            .unwrap_or(8)
-            + 1;
-
-        let bar_area_width = 30usize;
+        let label_width = bounds.label_width;
+        let bar_area_width = BAR_WIDTH;
         let duration_mins = total_secs / 60.0;
-        let time_header = build_time_header(label_width, bar_area_width, duration_mins);
-        let mut lines: Vec<(Line<'static>, bool)> = vec![(time_header, false)];
+        let start_clock = bounds
+            .first_start
+            .is_some()
+            .then(|| self.state.timezone.format(earliest, "%H:%M"));
+        let time_header = build_time_header(
+            label_width,
+            bar_area_width,
+            duration_mins,
+            start_clock.as_deref(),
+        );
+        let rows = 1
+            + bounds.spans.len()
+            + self.state.total_tasks
+            + self.state.phases.len()
+            + self.milestone_rows();
+        let mut lines: Vec<(Line<'static>, bool)> = Vec::with_capacity(rows);
+        lines.push((time_header, false));
         let mut line_idx = 1usize;
 
-        for phase in &self.state.phases {
+        // Phase spans above the task bars, on the same time scale
+        for span in &bounds.spans {
+            let phase = &self.state.phases[span.phase];
+            let id: String = phase.id.chars().take(label_width).collect();
+            let s_off = offset(span.start);
+            let e_off = offset(span.end(now));
+            let col = ((s_off * bar_area_width as f64) as usize).min(bar_area_width - 1);
+            let len = (((e_off - s_off) * bar_area_width as f64).ceil() as usize)
+                .clamp(1, bar_area_width - col);
+            let color = if self.state.phase_progress(phase) >= 1.0 {
+                Color::Green
+            } else {
+                Color::Cyan
+            };
+            let mut band = Line::from(vec![
+                Span::styled(format!("  {id:>label_width$} "), Style::default().fg(color)),
+                Span::raw(cells(BLANK_CELLS, col)),
+                Span::styled(cells(SPAN_CELLS, len), Style::default().fg(color)),
+            ]);
+            if let Some(marker) = &now_marker {
+                band.spans
+                    .push(Span::raw(cells(BLANK_CELLS, bar_area_width - col - len)));
+                band.spans.push(marker.clone());
+            }
+            lines.push((band, false));
+        }
+
+        for (pi, phase) in self.state.shown_phases() {
             // Phase separator header
-            let pct = (phase.progress() * 100.0) as u8;
+            let pct = (self.state.phase_progress(phase) * 100.0) as u8;
             let phase_line = Line::from(vec![
                 Span::styled(
                     format!(" {} ", phase.id),
@@ -288,39 +919,39 @@ impl<'a> GanttWidget<'a> {
                 ),
                 Span::styled(format!(" {pct}%"), Style::default().fg(Color::DarkGray)),
             ]);
+            let mut phase_line = phase_line;
+            if let Some(badge) = self.gate_badge(&phase.id) {
+                phase_line.spans.push(Span::raw(" "));
+                phase_line.spans.push(badge);
+            }
             let is_selected = line_idx == gantt_state.selected;
             lines.push((phase_line, is_selected));
             line_idx += 1;
 
             // Task bar rows
-            for (ti, task) in phase.tasks.iter().enumerate() {
+            let tasks: Vec<&ParsedTask> = self.state.shown_tasks(phase).map(|(_, t)| t).collect();
+            for (ti, task) in tasks.iter().enumerate() {
                 let is_selected = line_idx == gantt_state.selected;
-                let color = status_color(&task.status);
-                let timing = self.state.task_times.get(&task.id);
+                let color = status_color(&task.status, statuses);
+                let done = statuses::is_done(&task.status, statuses);
+                let timing = self.state.task_times.get(task.id.as_str());
                 let started = timing.and_then(|t| t.started_at);
                 let completed = timing.and_then(|t| t.completed_at);
 
                 let label = format!("{:>width$} ", task.id, width = label_width);
 
-                let (bar_start, bar_len) = match (started, completed) {
-                    (Some(s), Some(c)) => {
-                        let s_off = (s - earliest).num_seconds().max(0) as f64 / total_secs;
-                        let e_off = (c - earliest).num_seconds().max(0) as f64 / total_secs;
-                        let col = (s_off * bar_area_width as f64) as usize;
-                        let len = ((e_off - s_off) * bar_area_width as f64).ceil() as usize;
-                        (col, len.max(1))
-                    }
-                    (Some(s), None) => {
-                        let s_off = (s - earliest).num_seconds().max(0) as f64 / total_secs;
-                        let e_off = (now - earliest).num_seconds().max(0) as f64 / total_secs;
+                let (bar_start, bar_len) = match started {
+                    Some(s) => {
+                        let s_off = offset(s);
+                        let e_off = offset(completed.unwrap_or(now));
                         let col = (s_off * bar_area_width as f64) as usize;
                         let len = ((e_off - s_off) * bar_area_width as f64).ceil() as usize;
                         (col, len.max(1))
                     }
-                    _ => {
+                    None => {
                         // No timing: show status bar from left
                         let len = match task.status {
-                            TaskStatus::Completed => bar_area_width,
+                            _ if done => bar_area_width,
                             TaskStatus::InProgress => bar_area_width / 2,
                             _ => 2,
                         };
@@ -328,43 +959,135 @@ impl<'a> GanttWidget<'a> {
                     }
                 };
 
-                let bar_char = match task.status {
-                    TaskStatus::Completed | TaskStatus::InProgress => '\u{2588}',
-                    _ => '\u{2591}',
+                let bar_cells = if done || task.status == TaskStatus::InProgress {
+                    SOLID_CELLS
+                } else {
+                    SHADED_CELLS
                 };
 
-                let connector = if ti == phase.tasks.len() - 1 {
+                let connector = if ti == tasks.len() - 1 {
                     "\u{2514} "
                 } else {
                     "\u{251C} "
                 };
 
-                let mut bar = String::new();
-                for i in 0..bar_area_width {
-                    if i >= bar_start && i < bar_start + bar_len {
-                        bar.push(bar_char);
-                    } else {
-                        bar.push(' ');
+                let bar_end = (bar_start + bar_len).min(bar_area_width);
+                let bar_start = bar_start.min(bar_end);
+                // Measured duration after the bar, or `…` when it does not
+                // fit; the detail panel has the exact times
+                let room = bar_area_width - bar_end;
+                let tail = match started.map(|s| completed.unwrap_or(now) - s) {
+                    Some(took) => {
+                        let text = format!(" {}", humanize(took));
+                        if text.chars().count() <= room {
+                            Cow::Owned(text)
+                        } else if room >= 2 {
+                            Cow::Borrowed(" \u{2026}")
+                        } else {
+                            Cow::Borrowed("")
+                        }
                     }
-                }
+                    None => Cow::Borrowed(""),
+                };
+                let pad = room - tail.chars().count();
 
-                let line = Line::from(vec![
-                    Span::styled(connector.to_string(), Style::default().fg(Color::DarkGray)),
-                    Span::styled(label, Style::default().fg(Color::White)),
-                    Span::styled(bar, Style::default().fg(color)),
+                let mut line = Line::from(vec![
+                    Span::styled(connector, Style::default().fg(Color::DarkGray)),
+                    Span::styled(
+                        label,
+                        if self.is_wip_excess(&task.id) || self.is_premature(&task.id) {
+                            wip_excess_style()
+                        } else {
+                            Style::default().fg(Color::White)
+                        },
+                    ),
+                    Span::styled(cells(BLANK_CELLS, bar_start), Style::default().fg(color)),
+                    Span::styled(
+                        cells(bar_cells, bar_end - bar_start),
+                        Style::default().fg(color),
+                    ),
+                    Span::styled(tail, Style::default().fg(Color::DarkGray)),
+                    Span::styled(
+                        cells(BLANK_CELLS, pad),
+                        Style::default().fg(Color::DarkGray),
+                    ),
                 ]);
+                if let Some(marker) = &now_marker {
+                    line.spans.push(marker.clone());
+                }
+                line.spans.extend(self.status_word(&task.status));
                 lines.push((line, is_selected));
                 line_idx += 1;
             }
+
+            for name in &phase.milestones {
+                let reached = self.state.milestone_reached(pi);
+                let lead = " ".repeat(label_width + 2);
+                lines.push((milestone_line(&lead, name, reached, bar_area_width), false));
+            }
         }
 
         lines
     }
+
+    /// Number of milestone marker rows, which are not selectable
+    fn milestone_rows(&self) -> usize {
+        self.state
+            .shown_phases()
+            .map(|(_, p)| p.milestones.len())
+            .sum()
+    }
+
+    /// Which rows of the tree or bar view are numbered: phase and task
+    /// rows, not the ruler, the `spans` phase span rows or milestone markers
+    fn numbered_rows(&self, gantt_state: &GanttState, spans: usize) -> Vec<bool> {
+        let bar = gantt_state.view_mode == GanttViewMode::HorizontalBar;
+        let mut rows = Vec::new();
+        if bar {
+            rows.push(false);
+            rows.extend((0..spans).map(|_| false));
+        }
+        for (pi, phase) in self.state.shown_phases() {
+            rows.push(true);
+            if bar || !gantt_state.collapsed.contains(&pi) {
+                rows.extend(self.state.shown_tasks(phase).map(|_| true));
+            }
+            rows.extend(phase.milestones.iter().map(|_| false));
+        }
+        rows
+    }
 }
 
-/// Build a time header for the horizontal bar view
-fn build_time_header(label_width: usize, bar_width: usize, total_mins: f64) -> Line<'static> {
-    let padding = " ".repeat(label_width + 1);
+/// A `◆ name ────` marker row; green once the milestone is reached
+fn milestone_line(lead: &str, name: &str, reached: bool, width: usize) -> Line<'static> {
+    let color = if reached { Color::Green } else { Color::Yellow };
+    let label = format!("\u{25C6} {name} ");
+    let rule = "\u{2500}".repeat(width.saturating_sub(label.chars().count()));
+    Line::from(vec![
+        Span::raw(lead.to_string()),
+        Span::styled(
+            label,
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(rule, Style::default().fg(color)),
+    ])
+}
+
+/// Build a time header for the horizontal bar view. `start` is the clock
+/// time of the left edge, shown in the label column when it fits.
+pub(crate) fn build_time_header(
+    label_width: usize,
+    bar_width: usize,
+    total_mins: f64,
+    start: Option<&str>,
+) -> Line<'static> {
+    let padding = match start {
+        Some(clock) if clock.len() <= label_width => Span::styled(
+            format!("{clock:>label_width$} "),
+            Style::default().fg(Color::DarkGray),
+        ),
+        _ => Span::raw(" ".repeat(label_width + 1)),
+    };
     if total_mins < 1.0 {
         let secs = (total_mins * 60.0) as u64;
         let mid = secs / 2;
@@ -379,7 +1102,7 @@ fn build_time_header(label_width: usize, bar_width: usize, total_mins: f64) -> L
         }
         scale.push_str(&format!("{secs}s"));
         Line::from(vec![
-            Span::raw(padding),
+            padding,
             Span::styled(scale, Style::default().fg(Color::DarkGray)),
         ])
     } else {
@@ -396,28 +1119,60 @@ fn build_time_header(label_width: usize, bar_width: usize, total_mins: f64) -> L
         }
         scale.push_str(&format!("{total}m"));
         Line::from(vec![
-            Span::raw(padding),
+            padding,
             Span::styled(scale, Style::default().fg(Color::DarkGray)),
         ])
     }
 }
 
+/// Line number gutter for each row, all the same width; empty when line
+/// numbers are off. `current` is the selected row's number.
+fn gutter_labels(numbered: &[bool], current: Option<usize>, mode: LineNumbers) -> Vec<String> {
+    if mode == LineNumbers::Off {
+        return Vec::new();
+    }
+    let total = numbered.iter().filter(|&&n| n).count();
+    let width = total.max(1).to_string().len();
+    let mut n = 0;
+    numbered
+        .iter()
+        .map(|&is_numbered| {
+            if !is_numbered {
+                return " ".repeat(width + 1);
+            }
+            n += 1;
+            let label = match (mode, current) {
+                (LineNumbers::Relative, Some(c)) if c != n => c.abs_diff(n),
+                _ => n,
+            };
+            format!("{label:>width$} ")
+        })
+        .collect()
+}
+
 /// Shared rendering logic for both view modes
 fn render_lines(
-    lines: &[(Line<'_>, bool)],
+    lines: &[(Cow<'_, Line<'_>>, bool)],
+    gutter: &[String],
+    selectable: usize,
     inner: Rect,
     buf: &mut Buffer,
     gantt_state: &mut GanttState,
     focused: bool,
 ) {
-    gantt_state.total_items = lines.len();
+    gantt_state.total_items = selectable;
 
-    // Adjust scroll offset to keep selection visible
+    // Adjust scroll offset to keep the selected row visible; marker rows
+    // mean it can sit below its selection index
     let visible_height = inner.height as usize;
-    if gantt_state.selected < gantt_state.offset {
-        gantt_state.offset = gantt_state.selected;
-    } else if gantt_state.selected >= gantt_state.offset + visible_height {
-        gantt_state.offset = gantt_state.selected - visible_height + 1;
+    let selected = lines
+        .iter()
+        .position(|(_, is_selected)| *is_selected)
+        .unwrap_or(gantt_state.selected);
+    if selected < gantt_state.offset {
+        gantt_state.offset = selected;
+    } else if selected >= gantt_state.offset + visible_height {
+        gantt_state.offset = selected - visible_height + 1;
     }
 
     for (i, (line, is_selected)) in lines
@@ -440,8 +1195,23 @@ fn render_lines(
             );
         }
 
-        let line_area = Rect::new(inner.x, y, inner.width, 1);
-        Widget::render(line.clone(), line_area, buf);
+        let mut line_area = Rect::new(inner.x, y, inner.width, 1);
+        if let Some(label) = gutter.get(gantt_state.offset + i) {
+            let color = if *is_selected {
+                Color::Yellow
+            } else {
+                Color::DarkGray
+            };
+            let width = (label.len() as u16).min(line_area.width);
+            Widget::render(
+                Span::styled(label.as_str(), Style::default().fg(color)),
+                Rect::new(line_area.x, y, width, 1),
+                buf,
+            );
+            line_area.x += width;
+            line_area.width -= width;
+        }
+        Widget::render(line.as_ref(), line_area, buf);
     }
 }
 
@@ -458,21 +1228,98 @@ impl<'a> StatefulWidget for GanttWidget<'a> {
         let view_label = match gantt_state.view_mode {
             GanttViewMode::Tree => " Tasks (Tree) ",
             GanttViewMode::HorizontalBar => " Tasks (Gantt) ",
+            GanttViewMode::AgentLanes => " Agents (Lanes) ",
+            GanttViewMode::Heatmap => " Activity (Heatmap) ",
+            GanttViewMode::Workload => " Agents (Workload) ",
+            GanttViewMode::Calendar => " Tasks (Week) ",
         };
 
         let block = Block::default()
-            .title(view_label)
             .borders(Borders::ALL)
             .border_style(border_style);
         let inner = block.inner(area);
-        block.render(area, buf);
 
+        let mut cache = std::mem::take(&mut gantt_state.row_cache);
+        let mut bar_cache = std::mem::take(&mut gantt_state.bar_cache);
+        let mut untracked_cache = std::mem::take(&mut gantt_state.untracked_cache);
+        let bar = gantt_state.view_mode == GanttViewMode::HorizontalBar;
+        let spans = if bar {
+            bar_cache.bounds(self.state).spans.len()
+        } else {
+            0
+        };
+        let untracked = if gantt_state.view_mode == GanttViewMode::Tree {
+            self.build_untracked_lines(untracked_cache.work(self.state))
+        } else {
+            Vec::new()
+        };
+        let untracked_rows = untracked.len();
         let lines = match gantt_state.view_mode {
-            GanttViewMode::Tree => self.build_tree_lines(gantt_state),
-            GanttViewMode::HorizontalBar => self.build_bar_lines(gantt_state),
+            GanttViewMode::Tree => {
+                let mut lines = self.build_tree_lines(gantt_state, &mut cache, inner.width);
+                lines.extend(untracked.into_iter().map(|line| (Cow::Owned(line), false)));
+                lines
+            }
+            GanttViewMode::HorizontalBar => self
+                .build_bar_lines(gantt_state, bar_cache.bounds(self.state))
+                .into_iter()
+                .map(|(line, selected)| (Cow::Owned(line), selected))
+                .collect(),
+            GanttViewMode::AgentLanes
+            | GanttViewMode::Heatmap
+            | GanttViewMode::Workload
+            | GanttViewMode::Calendar => {
+                block.title(view_label).render(area, buf);
+                // Drawn without touching the task selection, which the
+                // detail panel keeps using
+                let width = inner.width as usize;
+                let rows = match gantt_state.view_mode {
+                    GanttViewMode::Heatmap => heatmap::build_heatmap_lines(self.state, width),
+                    GanttViewMode::Workload => workload::build_workload_lines(self.state, width),
+                    GanttViewMode::Calendar => calendar::build_calendar_lines(self.state),
+                    _ => lanes::build_lane_lines(self.state, width),
+                };
+                for (i, line) in rows.into_iter().take(inner.height as usize).enumerate() {
+                    let row = Rect::new(inner.x, inner.y + i as u16, inner.width, 1);
+                    Widget::render(line, row, buf);
+                }
+                gantt_state.row_cache = cache;
+                gantt_state.bar_cache = bar_cache;
+                gantt_state.untracked_cache = untracked_cache;
+                return;
+            }
         };
 
-        render_lines(&lines, inner, buf, gantt_state, self.focused);
+        // "12/87" position among the phase and task rows
+        let numbered = self.numbered_rows(gantt_state, spans);
+        let total = numbered.iter().filter(|&&n| n).count();
+        let current = lines
+            .iter()
+            .position(|(_, is_selected)| *is_selected)
+            .filter(|&i| numbered.get(i) == Some(&true))
+            .map(|i| numbered[..=i].iter().filter(|&&n| n).count());
+        let title = match current {
+            Some(n) => format!("{view_label}{n}/{total} "),
+            None => view_label.to_string(),
+        };
+        block.title(title).render(area, buf);
+
+        let gutter = gutter_labels(&numbered, current, gantt_state.line_numbers);
+        let fixed = self.milestone_rows() + spans + untracked_rows;
+        let selectable = lines.len().saturating_sub(fixed);
+        render_lines(
+            &lines,
+            &gutter,
+            selectable,
+            inner,
+            buf,
+            gantt_state,
+            self.focused,
+        );
+        drop(lines);
+        gantt_state.row_cache = cache;
+        gantt_state.bar_cache = bar_cache;
+        gantt_state.untracked_cache = untracked_cache;
     }
 }
 
@@ -484,6 +1331,20 @@ mod tests {
         DashboardState::from_tasks_content(input).unwrap()
     }
 
+    /// Bar lines on a freshly derived time scale
+    fn bar_lines(widget: &GanttWidget, gs: &GanttState) -> Vec<(Line<'static>, bool)> {
+        widget.build_bar_lines(gs, &BarBounds::of(widget.state))
+    }
+
+    /// Tree lines built without a warm cache
+    fn tree_lines(widget: &GanttWidget, gs: &GanttState) -> Vec<(Line<'static>, bool)> {
+        widget
+            .build_tree_lines(gs, &mut RowCache::default(), 40)
+            .into_iter()
+            .map(|(line, selected)| (line.into_owned(), selected))
+            .collect()
+    }
+
     #[test]
     fn gantt_state_navigation() {
         let mut gs = GanttState {
@@ -499,6 +1360,34 @@ mod tests {
         assert_eq!(gs.selected, 0);
     }
 
+    #[test]
+    fn markdown_table_lists_tasks_of_expanded_phases() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [x] T1: Login | OAuth\n### [ ] T2: Logout\n- @ops\n# Phase 2: B\n### [ ] T3: Hidden\n",
+        )
+        .unwrap();
+        let events = crate::data::hook_parser::parse_hook_events(concat!(
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"backend","task_id":"T1","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:12:00Z","agent_id":"backend","task_id":"T1","session_id":"s"}"#,
+        ))
+        .events;
+        state.update_from_events(&events);
+        let mut gs = GanttState::default();
+        gs.toggle_collapse(1);
+
+        let table = gs.markdown_table(&state);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "| ID | Name | Status | Agent | Duration |");
+        assert_eq!(lines[1], "|---|---|---|---|---|");
+        assert_eq!(
+            lines[2],
+            "| T1 | Login \\| OAuth | Completed | backend | 12m |"
+        );
+        assert_eq!(lines[3], "| T2 | Logout | Pending | ops |  |");
+        assert_eq!(lines.len(), 4);
+    }
+
     #[test]
     fn gantt_state_max_bound() {
         let mut gs = GanttState {
@@ -553,20 +1442,36 @@ mod tests {
 
     #[test]
     fn status_colors_all_mapped() {
-        assert_eq!(status_color(&TaskStatus::Completed), Color::Green);
-        assert_eq!(status_color(&TaskStatus::InProgress), Color::Yellow);
-        assert_eq!(status_color(&TaskStatus::Pending), Color::DarkGray);
-        assert_eq!(status_color(&TaskStatus::Failed), Color::Red);
-        assert_eq!(status_color(&TaskStatus::Blocked), Color::Magenta);
+        assert_eq!(status_color(&TaskStatus::Completed, &[]), Color::Green);
+        assert_eq!(status_color(&TaskStatus::InProgress, &[]), Color::Yellow);
+        assert_eq!(status_color(&TaskStatus::Pending, &[]), Color::DarkGray);
+        assert_eq!(status_color(&TaskStatus::Failed, &[]), Color::Red);
+        assert_eq!(status_color(&TaskStatus::Blocked, &[]), Color::Magenta);
     }
 
     #[test]
     fn status_icons_all_mapped() {
-        assert_eq!(status_icon(&TaskStatus::Completed), "[x]");
-        assert_eq!(status_icon(&TaskStatus::InProgress), "[/]");
-        assert_eq!(status_icon(&TaskStatus::Pending), "[ ]");
-        assert_eq!(status_icon(&TaskStatus::Failed), "[!]");
-        assert_eq!(status_icon(&TaskStatus::Blocked), "[B]");
+        assert_eq!(status_icon(&TaskStatus::Completed, &[]), "[x]");
+        assert_eq!(status_icon(&TaskStatus::InProgress, &[]), "[/]");
+        assert_eq!(status_icon(&TaskStatus::Pending, &[]), "[ ]");
+        assert_eq!(status_icon(&TaskStatus::Failed, &[]), "[!]");
+        assert_eq!(status_icon(&TaskStatus::Blocked, &[]), "[B]");
+    }
+
+    #[test]
+    fn custom_status_icon_and_color() {
+        let defs = vec![StatusDef {
+            name: "Review".to_string(),
+            icon: Some("[?]".to_string()),
+            color: Some("lightblue".to_string()),
+            done: false,
+        }];
+        let review = TaskStatus::Custom("Review".to_string());
+        assert_eq!(status_icon(&review, &defs), "[?]");
+        assert_eq!(status_color(&review, &defs), Color::LightBlue);
+        let other = TaskStatus::Custom("WontDo".to_string());
+        assert_eq!(status_icon(&other, &defs), "[W]");
+        assert_eq!(status_color(&other, &defs), Color::Cyan);
     }
 
     #[test]
@@ -574,7 +1479,7 @@ mod tests {
         let state = sample_state();
         let widget = GanttWidget::new(&state, true);
         let gs = GanttState::default();
-        let lines = widget.build_tree_lines(&gs);
+        let lines = tree_lines(&widget, &gs);
         // 3 phases + 8 tasks = 11 lines
         assert_eq!(lines.len(), 11);
     }
@@ -585,7 +1490,7 @@ mod tests {
         let widget = GanttWidget::new(&state, true);
         let mut gs = GanttState::default();
         gs.collapsed.insert(0); // collapse phase 0 (2 tasks hidden)
-        let lines = widget.build_tree_lines(&gs);
+        let lines = tree_lines(&widget, &gs);
         // 3 phases + (0 + 3 + 3) tasks = 9 lines
         assert_eq!(lines.len(), 9);
     }
@@ -608,6 +1513,436 @@ mod tests {
         assert_eq!(gs.selected_task(&state), Some((1, 0)));
     }
 
+    #[test]
+    fn wip_excess_tasks_are_flagged() {
+        let state = sample_state();
+        let excess = HashSet::from(["P1-R1-T1".to_string()]);
+        let widget = GanttWidget::new(&state, true).with_wip_excess(&excess);
+        let lines = tree_lines(&widget, &GanttState::default());
+        let flagged: Vec<_> = lines
+            .iter()
+            .filter(|(l, _)| l.spans.iter().any(|s| s.content == " WIP!"))
+            .collect();
+        assert_eq!(flagged.len(), 1);
+        assert!(flagged[0].0.spans.iter().any(|s| s.content == "P1-R1-T1"));
+    }
+
+    #[test]
+    fn locked_phase_and_premature_task_are_flagged() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [ ] T1: A\n\n# Phase 2: B\ngate: P1\n### [InProgress] T2: B\n",
+        )
+        .unwrap();
+        let gates = crate::data::gating::evaluate(&state);
+        let widget = GanttWidget::new(&state, true).with_gates(&gates);
+        let text = |l: &Line| {
+            l.spans
+                .iter()
+                .map(|s| s.content.as_ref())
+                .collect::<String>()
+        };
+
+        let lines = tree_lines(&widget, &GanttState::default());
+        assert!(!text(&lines[0].0).contains("LOCKED"));
+        assert!(text(&lines[2].0).contains("LOCKED until P1"));
+        assert!(text(&lines[3].0).contains("GATED!"));
+
+        let bars = bar_lines(&widget, &GanttState::default());
+        assert!(bars
+            .iter()
+            .any(|(l, _)| text(l).contains("LOCKED until P1")));
+    }
+
+    #[test]
+    fn bar_ruler_starts_at_clock_time_in_display_zone() {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [/] P1-R1-T1: A\n").unwrap();
+        let events = crate::data::hook_parser::parse_hook_events(include_str!(
+            "../../tests/fixtures/sample_hooks/agent_events.jsonl"
+        ));
+        state.update_from_events(&events.events);
+        state.timezone = "+09:00".parse().unwrap();
+
+        let bars = bar_lines(&GanttWidget::new(&state, true), &GanttState::default());
+        assert!(
+            bars[0].0.to_string().starts_with("    19:00 0m"),
+            "{}",
+            bars[0].0
+        );
+    }
+
+    #[test]
+    fn bar_view_heads_task_bars_with_phase_spans() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [x] T1: A\n### [x] T2: B\n# Phase 2: B\n### [/] T3: C\n# Phase 3: C\n### [ ] T4: D\n",
+        )
+        .unwrap();
+        let at = |time: &str| {
+            format!("2026-02-08T{time}:00Z")
+                .parse::<DateTime<Utc>>()
+                .ok()
+        };
+        let timing = |start, end: Option<&str>| crate::data::state::TaskTiming {
+            started_at: at(start),
+            completed_at: end.and_then(at),
+        };
+        state
+            .task_times
+            .insert("T1".into(), timing("10:00", Some("10:20")));
+        state
+            .task_times
+            .insert("T2".into(), timing("10:10", Some("10:30")));
+        state.task_times.insert("T3".into(), timing("10:30", None));
+        state.scrub_at = at("11:00");
+
+        let widget = GanttWidget::new(&state, true);
+        let bars = bar_lines(&widget, &GanttState::default());
+        let row = |i: usize| bars[i].0.to_string();
+        // 30 columns over the hour: P1 spans the first half, P2 the rest,
+        // up to the now marker
+        let half = "\u{2501}".repeat(15);
+        assert_eq!(row(1), format!("   P1 {half}{}\u{2502}", " ".repeat(15)));
+        assert_eq!(row(2), format!("   P2 {}{half}\u{2502}", " ".repeat(15)));
+        assert!(row(3).contains("P1"));
+        assert!(!bars[1].1 && !bars[2].1);
+        // Bars end in their measured duration when there is room for it
+        assert!(row(4).contains("\u{2588} 20m"), "{}", row(4));
+        assert!(row(7).ends_with("\u{2588}\u{2502}"), "{}", row(7));
+
+        let mut gs = GanttState {
+            view_mode: GanttViewMode::HorizontalBar,
+            ..Default::default()
+        };
+        let area = Rect::new(0, 0, 80, 20);
+        widget.render(area, &mut Buffer::empty(area), &mut gs);
+        // Time header + 3 phases + 4 tasks; the span rows are not selectable
+        assert_eq!(gs.total_items, 8);
+    }
+
+    #[test]
+    fn bar_scale_is_rederived_only_when_the_state_changes() {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [ ] T1: A\n").unwrap();
+        let mut gs = GanttState {
+            view_mode: GanttViewMode::HorizontalBar,
+            ..Default::default()
+        };
+        let area = Rect::new(0, 0, 80, 10);
+        let render = |state: &DashboardState, gs: &mut GanttState| {
+            GanttWidget::new(state, true).render(area, &mut Buffer::empty(area), gs);
+            gs.bar_cache.bounds.clone()
+        };
+        let bounds = render(&state, &mut gs);
+        assert!(bounds.first_start.is_none() && bounds.spans.is_empty());
+        assert_eq!(bounds.label_width, 3);
+
+        let events = crate::data::hook_parser::parse_hook_events(
+            r#"{"event_type":"agent_start","agent_id":"main","task_id":"T1","session_id":"s1","timestamp":"2026-02-08T10:00:00Z"}"#,
+        );
+        let revision = state.revision;
+        state.update_from_events(&events.events);
+        assert!(state.revision > revision);
+        let bounds = render(&state, &mut gs);
+        assert!(bounds.running);
+        assert_eq!(bounds.spans.len(), 1);
+
+        // Unchanged state keeps the derived scale, even if its inputs were
+        // edited behind the state's back
+        state.task_times.clear();
+        assert!(render(&state, &mut gs).running);
+    }
+
+    #[test]
+    fn now_marker_shows_while_a_bar_is_running() {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [x] T1: A\n### [/] T2: B\n")
+                .unwrap();
+        let now = Utc::now();
+        state.task_times.insert(
+            "T1".into(),
+            crate::data::state::TaskTiming {
+                started_at: Some(now - chrono::Duration::minutes(30)),
+                completed_at: Some(now - chrono::Duration::minutes(20)),
+            },
+        );
+        let marked = |state: &DashboardState| {
+            bar_lines(&GanttWidget::new(state, true), &GanttState::default())
+                .iter()
+                .any(|(l, _)| l.to_string().ends_with('\u{2502}'))
+        };
+        // Finished work alone ends at the last completion, not now
+        assert!(!marked(&state));
+
+        state.task_times.insert(
+            "T2".into(),
+            crate::data::state::TaskTiming {
+                started_at: Some(now - chrono::Duration::minutes(10)),
+                completed_at: None,
+            },
+        );
+        assert!(marked(&state));
+    }
+
+    #[test]
+    fn running_bars_follow_the_state_clock() {
+        use crate::data::clock::ManualClock;
+        use std::sync::Arc;
+
+        let start: DateTime<Utc> = "2026-02-08T10:00:00Z".parse().unwrap();
+        let clock = Arc::new(ManualClock::new(start + chrono::Duration::minutes(20)));
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [x] T1: A\n### [/] T2: B\n")
+                .unwrap();
+        state.clock = clock.clone();
+        for (id, started, completed) in [("T1", 0, Some(10)), ("T2", 10, None)] {
+            state.task_times.insert(
+                id.into(),
+                crate::data::state::TaskTiming {
+                    started_at: Some(start + chrono::Duration::minutes(started)),
+                    completed_at: completed.map(|m| start + chrono::Duration::minutes(m)),
+                },
+            );
+        }
+        let mut gs = GanttState::default();
+        let mut first_bar = || {
+            let widget = GanttWidget::new(&state, true);
+            let bounds = gs.bar_cache.bounds(&state).clone();
+            let lines = widget.build_bar_lines(&gs, &bounds);
+            let row = lines
+                .iter()
+                .map(|(l, _)| l.to_string())
+                .find(|l| l.contains("T1"));
+            row.unwrap().matches('\u{2588}').count()
+        };
+        // T1 took the first half of the 30 cells, then a quarter (rounded)
+        // once T2 has run on for longer
+        assert_eq!(first_bar(), 15);
+        assert_eq!(first_bar(), 15);
+        clock.advance(chrono::Duration::minutes(20));
+        assert_eq!(first_bar(), 8);
+    }
+
+    #[test]
+    fn untracked_work_is_listed_below_the_tree() {
+        use crate::data::hook_parser::parse_hook_events;
+        use crate::ui::testing::render_stateful_to_string;
+
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [ ] T1: A\n").unwrap();
+        let events = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"backend","task_id":"T1","session_id":"s1"}"#,
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"backend","task_id":"X1","session_id":"s1"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:02:00Z","agent_id":"backend","task_id":"X1","session_id":"s1"}"#,
+        ];
+        state.update_from_events(&parse_hook_events(&events.join("\n")).events);
+
+        let mut gs = GanttState::default();
+        let text = render_stateful_to_string(GanttWidget::new(&state, true), &mut gs, 50, 8);
+        assert!(text.contains("Untracked work 1"), "{text}");
+        assert!(text.contains("[?] X1 @backend  2 events, 2m"), "{text}");
+        // Only the phase and its task can be selected
+        assert_eq!(gs.total_items, 2);
+        gs.select_next();
+        gs.select_next();
+        assert_eq!(gs.selected_task(&state), Some((0, 0)));
+    }
+
+    #[test]
+    fn plain_rows_spell_out_the_status() {
+        let state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [x] T1: A\n### [Failed] T2: B\n")
+                .unwrap();
+        let widget = GanttWidget::new(&state, true).with_plain(true);
+        let lines = tree_lines(&widget, &GanttState::default());
+        assert!(lines[1].0.to_string().ends_with("T1: A (Completed)"));
+        let bars = bar_lines(&widget, &GanttState::default());
+        let last = bars.last().unwrap().0.to_string();
+        assert!(last.ends_with(" (Failed)"), "{last:?}");
+    }
+
+    #[test]
+    fn gutter_numbers_phase_and_task_rows() {
+        let rows = [false, true, true, false, true];
+        let labels = |mode| gutter_labels(&rows, Some(2), mode);
+        assert!(labels(LineNumbers::Off).is_empty());
+        assert_eq!(
+            labels(LineNumbers::Absolute),
+            ["  ", "1 ", "2 ", "  ", "3 "]
+        );
+        assert_eq!(
+            labels(LineNumbers::Relative),
+            ["  ", "1 ", "2 ", "  ", "1 "]
+        );
+    }
+
+    #[test]
+    fn title_shows_position_and_rows_get_numbers() {
+        let state = sample_state();
+        let widget = GanttWidget::new(&state, true);
+        let mut gs = GanttState {
+            selected: 2,
+            line_numbers: LineNumbers::Absolute,
+            ..Default::default()
+        };
+        let area = Rect::new(0, 0, 60, 20);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf, &mut gs);
+        let row = |y: u16| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        };
+        assert!(row(0).contains(" Tasks (Tree) 3/11 "), "{}", row(0));
+        assert!(row(1).starts_with("\u{2502} 1  \u{25BC}"), "{}", row(1));
+        assert!(row(11).starts_with("\u{2502}11 "), "{}", row(11));
+
+        gs.cycle_line_numbers();
+        assert_eq!(gs.line_numbers, LineNumbers::Relative);
+        gs.cycle_line_numbers();
+        assert_eq!(gs.line_numbers, LineNumbers::Off);
+    }
+
+    #[test]
+    fn restore_follows_ids_across_inserts() {
+        let before = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [ ] T1: a\n### [ ] T2: b\n# Phase 2: B\n### [ ] T3: c\n",
+        )
+        .unwrap();
+        let mut gs = GanttState {
+            selected: 2, // T3, with phase 1 collapsed
+            ..Default::default()
+        };
+        gs.collapsed.insert(0);
+        let anchor = gs.anchor(&before);
+        assert_eq!(
+            anchor.selected,
+            Some(ItemKey::Task {
+                phase: "P2".to_string(),
+                id: "T3".to_string()
+            })
+        );
+
+        // A new phase above shifts both the collapsed phase and the selection
+        let after = DashboardState::from_tasks_content(
+            "# Phase 0: New\n### [ ] T0: z\n# Phase 1: A\n### [ ] T1: a\n### [ ] T2: b\n# Phase 2: B\n### [ ] T3: c\n",
+        )
+        .unwrap();
+        gs.restore(&anchor, &after);
+        assert_eq!(gs.collapsed, HashSet::from([1]));
+        assert_eq!(gs.selected_task(&after), Some((2, 0)));
+
+        // A removed task falls back to its phase header
+        let removed = DashboardState::from_tasks_content(
+            "# Phase 0: New\n# Phase 1: A\n### [ ] T1: a\n# Phase 2: B\n",
+        )
+        .unwrap();
+        gs.restore(&anchor, &removed);
+        assert_eq!(gs.selected_phase_index(&removed), Some(2));
+    }
+
+    #[test]
+    fn sync_with_clamps_after_shrink() {
+        let state = DashboardState::from_tasks_content("# Phase 1: A\n### [ ] T1: a\n").unwrap();
+        let mut gs = GanttState {
+            selected: 7,
+            total_items: 9,
+            ..Default::default()
+        };
+        gs.collapsed.insert(3);
+        gs.sync_with(&state);
+        assert_eq!((gs.selected, gs.total_items), (1, 2));
+        assert!(gs.collapsed.is_empty());
+        assert_eq!(gs.selected_task(&state), Some((0, 0)));
+
+        gs.sync_with(&DashboardState::default());
+        assert_eq!((gs.selected, gs.total_items), (0, 0));
+    }
+
+    #[test]
+    fn milestones_render_as_unselectable_rows() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [x] T1: A\n## Milestone: v0.2 cut\n# Phase 2: B\n### [ ] T2: B\n",
+        )
+        .unwrap();
+        let widget = GanttWidget::new(&state, true);
+        let text = |l: &Line| {
+            l.spans
+                .iter()
+                .map(|s| s.content.as_ref())
+                .collect::<String>()
+        };
+
+        // P1, T1, marker, P2, T2; selecting index 2 lands on P2
+        let gs = GanttState {
+            selected: 2,
+            ..Default::default()
+        };
+        let lines = tree_lines(&widget, &gs);
+        assert_eq!(lines.len(), 5);
+        assert!(text(&lines[2].0).contains("\u{25C6} v0.2 cut"));
+        assert!(!lines[2].1);
+        assert!(lines[3].1);
+
+        let bars = bar_lines(&widget, &gs);
+        assert!(bars.iter().any(|(l, _)| text(l).contains("v0.2 cut")));
+
+        let mut gs = GanttState::default();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 60, 10));
+        GanttWidget::new(&state, true).render(buf.area, &mut buf, &mut gs);
+        assert_eq!(gs.total_items, 4);
+    }
+
+    #[test]
+    fn awaiting_review_tasks_are_flagged() {
+        let mut state = sample_state();
+        state.awaiting_review.insert("P0-T0.1".into());
+        let widget = GanttWidget::new(&state, true);
+        let lines = tree_lines(&widget, &GanttState::default());
+        let flagged = lines
+            .iter()
+            .filter(|(l, _)| l.spans.iter().any(|s| s.content == " REVIEW"))
+            .count();
+        assert_eq!(flagged, 1);
+    }
+
+    #[test]
+    fn red_pull_requests_are_flagged() {
+        let state = sample_state();
+        let red = HashSet::from(["P0-T0.1".to_string()]);
+        let widget = GanttWidget::new(&state, true).with_red_prs(&red);
+        let lines = tree_lines(&widget, &GanttState::default());
+        let flagged = lines
+            .iter()
+            .filter(|(l, _)| l.spans.iter().any(|s| s.content == " PR\u{2717}"))
+            .count();
+        assert_eq!(flagged, 1);
+    }
+
+    #[test]
+    fn tree_rows_are_cached_until_reload() {
+        let mut state = sample_state();
+        let mut gs = GanttState::default();
+        let area = Rect::new(0, 0, 60, 20);
+        let mut buf = Buffer::empty(area);
+        GanttWidget::new(&state, true).render(area, &mut buf, &mut gs);
+        // One row per task; headers are rebuilt every frame
+        assert_eq!(gs.row_cache.len(), 8);
+
+        // Moving the selection adds keys only for the rows it touched
+        gs.selected = 2;
+        GanttWidget::new(&state, true).render(area, &mut buf, &mut gs);
+        assert_eq!(gs.row_cache.len(), 9);
+
+        // A changed task renders fresh, not from its old row
+        state.phases[0].tasks[0].name = "Renamed".to_string();
+        let mut cache = gs.row_cache.clone();
+        let lines = GanttWidget::new(&state, true).build_tree_lines(&gs, &mut cache, 58);
+        assert!(lines[1].0.to_string().contains("Renamed"));
+
+        gs.sync_with(&state);
+        assert!(gs.row_cache.is_empty());
+    }
+
     #[test]
     fn toggle_collapse() {
         let mut gs = GanttState::default();
@@ -625,6 +1960,14 @@ mod tests {
         gs.toggle_view();
         assert_eq!(gs.view_mode, GanttViewMode::HorizontalBar);
         gs.toggle_view();
+        assert_eq!(gs.view_mode, GanttViewMode::AgentLanes);
+        gs.toggle_view();
+        assert_eq!(gs.view_mode, GanttViewMode::Heatmap);
+        gs.toggle_view();
+        assert_eq!(gs.view_mode, GanttViewMode::Workload);
+        gs.toggle_view();
+        assert_eq!(gs.view_mode, GanttViewMode::Calendar);
+        gs.toggle_view();
         assert_eq!(gs.view_mode, GanttViewMode::Tree);
     }
 
@@ -661,8 +2004,10 @@ mod tests {
     fn render_bar_does_not_panic() {
         let state = sample_state();
         let widget = GanttWidget::new(&state, true);
-        let mut gs = GanttState::default();
-        gs.view_mode = GanttViewMode::HorizontalBar;
+        let mut gs = GanttState {
+            view_mode: GanttViewMode::HorizontalBar,
+            ..Default::default()
+        };
         let area = Rect::new(0, 0, 80, 20);
         let mut buf = Buffer::empty(area);
         widget.render(area, &mut buf, &mut gs);
@@ -674,8 +2019,10 @@ mod tests {
     fn render_bar_empty_state() {
         let state = DashboardState::default();
         let widget = GanttWidget::new(&state, true);
-        let mut gs = GanttState::default();
-        gs.view_mode = GanttViewMode::HorizontalBar;
+        let mut gs = GanttState {
+            view_mode: GanttViewMode::HorizontalBar,
+            ..Default::default()
+        };
         let area = Rect::new(0, 0, 80, 20);
         let mut buf = Buffer::empty(area);
         widget.render(area, &mut buf, &mut gs);
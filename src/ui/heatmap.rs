@@ -0,0 +1,186 @@
+//! Activity heatmap
+//!
+//! Hook events counted into hour-of-day buckets, one row per day of the run
+//! and a row summing all days, shaded from `·` (no events) to `█` (the
+//! busiest hour). Shows when long autonomous runs actually make progress.
+
+use std::collections::BTreeMap;
+
+use chrono::{NaiveDate, Timelike};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+use crate::data::hook_parser::EventType;
+use crate::data::state::DashboardState;
+
+/// Days shown, most recent last
+const MAX_DAYS: usize = 14;
+
+/// Shades for counts above zero, lightest first
+const SHADES: [char; 4] = ['\u{2591}', '\u{2592}', '\u{2593}', '\u{2588}'];
+
+/// Event counts per hour of day (in the display timezone), per day.
+/// Heartbeats only prove liveness and are not counted.
+pub fn hourly_counts(state: &DashboardState) -> BTreeMap<NaiveDate, [usize; 24]> {
+    let mut days: BTreeMap<NaiveDate, [usize; 24]> = BTreeMap::new();
+    for event in &state.events {
+        if event.event_type == EventType::Heartbeat {
+            continue;
+        }
+        let at = state.timezone.naive(event.timestamp);
+        days.entry(at.date()).or_insert([0; 24])[at.hour() as usize] += 1;
+    }
+    days
+}
+
+/// Heatmap rows with an hour header and a legend. `width` is the inner
+/// width of the panel; each hour gets up to three columns.
+pub fn build_heatmap_lines(state: &DashboardState, width: usize) -> Vec<Line<'static>> {
+    let days = hourly_counts(state);
+    if days.is_empty() {
+        return vec![Line::raw("  No hook events")];
+    }
+    let mut total = [0; 24];
+    for counts in days.values() {
+        for (sum, n) in total.iter_mut().zip(counts) {
+            *sum += n;
+        }
+    }
+
+    let label_width = 6;
+    let cell = (width.saturating_sub(label_width) / 24).clamp(1, 3);
+    let step = if cell == 1 { 6 } else { 3 };
+    let mut header = " ".repeat(label_width);
+    for hour in (0..24).step_by(step) {
+        header.push_str(&format!("{:<w$}", format!("{hour:02}"), w = cell * step));
+    }
+    let mut lines = vec![Line::styled(
+        header.trim_end().to_string(),
+        Style::default().fg(Color::DarkGray),
+    )];
+
+    let shown = days.len().min(MAX_DAYS);
+    let day_max = days.values().flatten().copied().max().unwrap_or(0);
+    for (date, counts) in days.iter().skip(days.len() - shown) {
+        lines.push(heat_row(
+            &date.format("%m-%d").to_string(),
+            counts,
+            day_max,
+            cell,
+        ));
+    }
+    let total_max = total.iter().copied().max().unwrap_or(0);
+    lines.push(heat_row("all", &total, total_max, cell));
+
+    let peak = (0..24)
+        .max_by_key(|&h| (total[h], std::cmp::Reverse(h)))
+        .unwrap_or(0);
+    let mut legend = vec![Span::raw(" ".repeat(label_width))];
+    legend.push(Span::styled(
+        "\u{00B7}",
+        Style::default().fg(Color::DarkGray),
+    ));
+    legend.push(Span::styled(
+        " none  ",
+        Style::default().fg(Color::DarkGray),
+    ));
+    for shade in SHADES {
+        legend.push(Span::styled(
+            shade.to_string(),
+            Style::default().fg(Color::Green),
+        ));
+    }
+    legend.push(Span::styled(
+        format!(" busiest  peak {peak:02}:00 ({} events)", total[peak]),
+        Style::default().fg(Color::DarkGray),
+    ));
+    lines.push(Line::from(legend));
+    lines
+}
+
+/// One labelled row of 24 shaded hour cells, scaled to `max`
+fn heat_row(label: &str, counts: &[usize; 24], max: usize, cell: usize) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        format!("{label:>5} "),
+        Style::default().fg(Color::White),
+    )];
+    for &count in counts {
+        let span = match count {
+            0 => Span::styled(
+                format!("{:<cell$}", "\u{00B7}"),
+                Style::default().fg(Color::DarkGray),
+            ),
+            _ => {
+                let level = (count * SHADES.len())
+                    .div_ceil(max.max(1))
+                    .clamp(1, SHADES.len());
+                Span::styled(
+                    SHADES[level - 1].to_string().repeat(cell),
+                    Style::default().fg(Color::Green),
+                )
+            }
+        };
+        spans.push(span);
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+    use crate::data::timezone::DisplayZone;
+
+    fn event(event_type: &str, at: &str) -> String {
+        format!(
+            r#"{{"event_type":"{event_type}","timestamp":"{at}","agent_id":"a","task_id":"T1","session_id":"s"}}"#
+        )
+    }
+
+    fn state_with(events: &[String]) -> DashboardState {
+        let mut state = DashboardState {
+            timezone: DisplayZone::Utc,
+            ..Default::default()
+        };
+        state.update_from_events(&parse_hook_events(&events.join("\n")).events);
+        state
+    }
+
+    #[test]
+    fn counts_events_by_day_and_hour_without_heartbeats() {
+        let state = state_with(&[
+            event("tool_start", "2026-02-07T23:10:00Z"),
+            event("tool_end", "2026-02-08T09:05:00Z"),
+            event("tool_start", "2026-02-08T09:55:00Z"),
+            event("heartbeat", "2026-02-08T09:56:00Z"),
+        ]);
+        let days = hourly_counts(&state);
+        assert_eq!(days.len(), 2);
+        let feb8 = NaiveDate::from_ymd_opt(2026, 2, 8).unwrap();
+        assert_eq!(days[&feb8][9], 2);
+        assert_eq!(days[&feb8].iter().sum::<usize>(), 2);
+    }
+
+    #[test]
+    fn rows_shade_relative_to_the_busiest_hour() {
+        let mut events = vec![event("tool_start", "2026-02-08T02:00:00Z")];
+        for _ in 0..4 {
+            events.push(event("tool_start", "2026-02-08T03:00:00Z"));
+        }
+        let lines = build_heatmap_lines(&state_with(&events), 30);
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(text.len(), 4);
+        assert_eq!(text[0], "      00    06    12    18");
+        assert!(text[1].starts_with("02-08 \u{00B7}\u{00B7}\u{2591}\u{2588}\u{00B7}"));
+        assert!(text[2].starts_with("  all "));
+        assert!(text[3].ends_with("peak 03:00 (4 events)"));
+    }
+
+    #[test]
+    fn no_events_shows_placeholder() {
+        let lines = build_heatmap_lines(&DashboardState::default(), 40);
+        assert_eq!(lines[0].to_string(), "  No hook events");
+    }
+}
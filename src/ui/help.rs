@@ -11,21 +11,31 @@ use ratatui::{
 };
 
 /// Help overlay widget
-pub struct HelpOverlay;
+#[derive(Debug, Default)]
+pub struct HelpOverlay {
+    /// Extra `(key, description)` rows from plugin panels; an empty key
+    /// starts a new titled group
+    extra: Vec<(String, String)>,
+}
 
 impl HelpOverlay {
+    pub fn with_entries(mut self, entries: Vec<(String, String)>) -> Self {
+        self.extra = entries;
+        self
+    }
+
     /// Calculate a centered rect for the help popup
-    fn centered_rect(area: Rect) -> Rect {
+    fn centered_rect(&self, area: Rect) -> Rect {
         let width = 40.min(area.width.saturating_sub(4));
-        let height = 16.min(area.height.saturating_sub(4));
+        let height = (35 + self.extra.len() as u16).min(area.height.saturating_sub(4));
         let x = (area.width.saturating_sub(width)) / 2;
         let y = (area.height.saturating_sub(height)) / 2;
         Rect::new(x, y, width, height)
     }
 
-    fn help_lines() -> Vec<Line<'static>> {
+    fn help_lines(&self) -> Vec<Line<'static>> {
         let version = env!("CARGO_PKG_VERSION");
-        vec![
+        let mut lines = vec![
             Line::from(vec![Span::styled(
                 format!(" simple-claude-board v{version} "),
                 Style::default()
@@ -54,16 +64,103 @@ impl HelpOverlay {
             ]),
             Line::from(vec![
                 Span::styled("  Space     ", Style::default().fg(Color::Yellow)),
-                Span::raw("Collapse/expand phase"),
+                Span::raw("Collapse phase / agent output"),
             ]),
             Line::from(vec![
                 Span::styled("  v         ", Style::default().fg(Color::Yellow)),
-                Span::raw("Switch view (Tree/Gantt)"),
+                Span::raw("Views: tree/bar/lanes/heat"),
+            ]),
+            Line::from(vec![
+                Span::styled("  o         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Layout: standard / ops log"),
+            ]),
+            Line::from(vec![
+                Span::styled("  #         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Line numbers off/abs/rel"),
             ]),
             Line::from(vec![
                 Span::styled("  r         ", Style::default().fg(Color::Yellow)),
                 Span::raw("Retry failed task"),
             ]),
+            Line::from(vec![
+                Span::styled("  e         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Error in full (Space unfolds a frame)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  g         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Events leading up to the error"),
+            ]),
+            Line::from(vec![
+                Span::styled("  a / d     ", Style::default().fg(Color::Yellow)),
+                Span::raw("Approve / bounce review"),
+            ]),
+            Line::from(vec![
+                Span::styled("  Alt-j/k   ", Style::default().fg(Color::Yellow)),
+                Span::raw("Move task down/up"),
+            ]),
+            Line::from(vec![
+                Span::styled("  p         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Move task to phase"),
+            ]),
+            Line::from(vec![
+                Span::styled("  m         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Add note to task"),
+            ]),
+            Line::from(vec![
+                Span::styled("  t         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Quick-add task (Tab: template)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  Enter     ", Style::default().fg(Color::Yellow)),
+                Span::raw("Jump between task and agent"),
+            ]),
+            Line::from(vec![
+                Span::styled("  s         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Stop selected agent (Agents)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  u         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Next up: start a ready task"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "  \u{2190} / \u{2192}   ",
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw("Scrub back/forward in time"),
+            ]),
+            Line::from(vec![
+                Span::styled("  c         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Copy task context for Claude"),
+            ]),
+            Line::from(vec![
+                Span::styled("  G         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Copy tasks as markdown table"),
+            ]),
+            Line::from(vec![
+                Span::styled("  P         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Re-prompt failed task"),
+            ]),
+            Line::from(vec![
+                Span::styled("  O         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Export run trace (OTLP)"),
+            ]),
+            Line::from(vec![
+                Span::styled("  $         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Costs by phase/task/agent"),
+            ]),
+            Line::from(vec![
+                Span::styled("  w         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Agent roster and coverage"),
+            ]),
+            Line::from(vec![
+                Span::styled("  L         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Show the board's own log"),
+            ]),
+            Line::from(vec![
+                Span::styled("  b         ", Style::default().fg(Color::Yellow)),
+                Span::raw("Dismiss fatal banner"),
+            ]),
             Line::from(vec![
                 Span::styled("  ?         ", Style::default().fg(Color::Yellow)),
                 Span::raw("Close help"),
@@ -72,13 +169,29 @@ impl HelpOverlay {
                 Span::styled("  q / Esc   ", Style::default().fg(Color::Yellow)),
                 Span::raw("Quit"),
             ]),
-        ]
+        ];
+        for (key, desc) in &self.extra {
+            if key.is_empty() {
+                lines.push(Line::from(vec![Span::styled(
+                    format!(" {desc} "),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                )]));
+            } else {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {key:<10}"), Style::default().fg(Color::Yellow)),
+                    Span::raw(desc.clone()),
+                ]));
+            }
+        }
+        lines
     }
 }
 
 impl Widget for HelpOverlay {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let popup_area = Self::centered_rect(area);
+        let popup_area = self.centered_rect(area);
 
         // Clear the area behind the popup
         Clear.render(popup_area, buf);
@@ -88,7 +201,7 @@ impl Widget for HelpOverlay {
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Cyan));
 
-        let lines = Self::help_lines();
+        let lines = self.help_lines();
         let paragraph = Paragraph::new(lines).block(block);
         paragraph.render(popup_area, buf);
     }
@@ -102,29 +215,46 @@ mod tests {
     fn help_overlay_renders() {
         let area = Rect::new(0, 0, 80, 30);
         let mut buf = Buffer::empty(area);
-        HelpOverlay.render(area, &mut buf);
+        HelpOverlay::default().render(area, &mut buf);
     }
 
     #[test]
     fn help_centered_rect() {
         let area = Rect::new(0, 0, 80, 30);
-        let popup = HelpOverlay::centered_rect(area);
+        let popup = HelpOverlay::default().centered_rect(area);
         assert!(popup.x > 0);
         assert!(popup.y > 0);
         assert!(popup.width <= 40);
-        assert!(popup.height <= 16);
+        assert!(popup.height <= 30);
     }
 
     #[test]
     fn help_small_terminal() {
         let area = Rect::new(0, 0, 20, 8);
         let mut buf = Buffer::empty(area);
-        HelpOverlay.render(area, &mut buf);
+        HelpOverlay::default().render(area, &mut buf);
     }
 
     #[test]
     fn help_lines_not_empty() {
-        let lines = HelpOverlay::help_lines();
+        let lines = HelpOverlay::default().help_lines();
         assert!(lines.len() >= 5);
     }
+
+    #[test]
+    fn panel_entries_extend_help() {
+        let base = HelpOverlay::default();
+        let help = HelpOverlay::default().with_entries(vec![
+            (String::new(), "Cost".to_string()),
+            ("c".to_string(), "Reset budget".to_string()),
+        ]);
+        let lines = help.help_lines();
+        assert_eq!(lines.len(), base.help_lines().len() + 2);
+        assert_eq!(
+            lines.last().unwrap().to_string(),
+            "  c         Reset budget"
+        );
+        let area = Rect::new(0, 0, 80, 50);
+        assert_eq!(help.centered_rect(area).height, 37);
+    }
 }
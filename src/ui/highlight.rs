@@ -0,0 +1,509 @@
+//! Code highlighting
+//!
+//! A small line-at-a-time lexer for the fenced code blocks in task bodies
+//! and error messages: keywords, strings, numbers, comments and type-like
+//! names for the languages plans usually quote, plus stack traces, whose
+//! frames get their function names and `file:line` locations picked out.
+//! Constructs spanning lines (block comments, multi-line strings) are not
+//! tracked; each line is colored on its own.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+
+/// How a language's lines are tokenized
+struct Syntax {
+    keywords: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    quotes: &'static [char],
+    /// `key:` / `key =` at the start of a line is a key (config formats)
+    keys: bool,
+    /// Keywords match in any case
+    any_case: bool,
+}
+
+const RUST: Syntax = Syntax {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+        "type", "unsafe", "use", "where", "while",
+    ],
+    line_comments: &["//"],
+    quotes: &['"'],
+    keys: false,
+    any_case: false,
+};
+
+const JS: Syntax = Syntax {
+    keywords: &[
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "else",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "of",
+        "return",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "type",
+        "typeof",
+        "undefined",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    line_comments: &["//"],
+    quotes: &['"', '\'', '`'],
+    keys: false,
+    any_case: false,
+};
+
+const PYTHON: Syntax = Syntax {
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "False", "finally", "for", "from", "if", "import", "in", "is",
+        "lambda", "None", "not", "or", "pass", "raise", "return", "True", "try", "while", "with",
+        "yield",
+    ],
+    line_comments: &["#"],
+    quotes: &['"', '\''],
+    keys: false,
+    any_case: false,
+};
+
+const GO: Syntax = Syntax {
+    keywords: &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "false",
+        "for",
+        "func",
+        "go",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "nil",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "true",
+        "type",
+        "var",
+    ],
+    line_comments: &["//"],
+    quotes: &['"', '`'],
+    keys: false,
+    any_case: false,
+};
+
+const SHELL: Syntax = Syntax {
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+        "in", "local", "return", "then", "until", "while",
+    ],
+    line_comments: &["#"],
+    quotes: &['"', '\''],
+    keys: false,
+    any_case: false,
+};
+
+const SQL: Syntax = Syntax {
+    keywords: &[
+        "and", "as", "by", "create", "delete", "from", "group", "insert", "into", "join", "left",
+        "limit", "not", "null", "on", "or", "order", "select", "set", "table", "update", "values",
+        "where",
+    ],
+    line_comments: &["--"],
+    quotes: &['\''],
+    keys: false,
+    any_case: true,
+};
+
+const CONFIG: Syntax = Syntax {
+    keywords: &["true", "false", "null", "yes", "no"],
+    line_comments: &["#"],
+    quotes: &['"', '\''],
+    keys: true,
+    any_case: true,
+};
+
+const JSON: Syntax = Syntax {
+    keywords: &["true", "false", "null"],
+    line_comments: &[],
+    quotes: &['"'],
+    keys: false,
+    any_case: false,
+};
+
+fn syntax(lang: &str) -> Option<&'static Syntax> {
+    let syntax = match lang.to_ascii_lowercase().as_str() {
+        "rust" | "rs" => &RUST,
+        "js" | "javascript" | "jsx" | "ts" | "typescript" | "tsx" | "mjs" => &JS,
+        "python" | "py" => &PYTHON,
+        "go" | "golang" => &GO,
+        "sh" | "bash" | "shell" | "zsh" | "console" => &SHELL,
+        "sql" => &SQL,
+        "toml" | "yaml" | "yml" | "ini" => &CONFIG,
+        "json" | "jsonc" => &JSON,
+        _ => return None,
+    };
+    Some(syntax)
+}
+
+const KEYWORD: Color = Color::Magenta;
+const STRING: Color = Color::Green;
+const NUMBER: Color = Color::Cyan;
+const COMMENT: Color = Color::DarkGray;
+const TYPE: Color = Color::Yellow;
+const KEY: Color = Color::Blue;
+const LOCATION: Color = Color::Cyan;
+const FUNCTION: Color = Color::Yellow;
+
+/// Whether a fenced block is a stack trace: tagged `trace` / `stacktrace` /
+/// `backtrace`, or untagged with mostly frame-shaped lines
+pub fn is_trace(lang: &str, lines: &[&str]) -> bool {
+    if matches!(
+        lang.to_ascii_lowercase().as_str(),
+        "trace" | "stacktrace" | "backtrace" | "traceback"
+    ) {
+        return true;
+    }
+    if !lang.is_empty() || lines.is_empty() {
+        return false;
+    }
+    let frames = lines.iter().filter(|l| is_frame(l)).count();
+    frames * 2 >= lines.len()
+}
+
+/// A stack frame or its location line: `at f (a.js:1:2)`, `File "a.py",
+/// line 3, in f`, `  4: crate::f`, `a/b.go:12 +0x1d`
+pub fn is_frame(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("at ") || trimmed.starts_with("File \"") {
+        return true;
+    }
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 && trimmed[digits..].starts_with(": ") {
+        return true;
+    }
+    trimmed.split_whitespace().next().is_some_and(is_location)
+}
+
+/// `path/file.ext:12` or `file.ext:12:5`, optionally in parentheses or
+/// followed by a comma
+fn is_location(word: &str) -> bool {
+    let word = word.trim_start_matches('(').trim_end_matches([')', ',']);
+    let mut parts = word.split(':');
+    let file = parts.next().unwrap_or_default();
+    let numbers: Vec<&str> = parts.collect();
+    file.contains('.')
+        && !numbers.is_empty()
+        && numbers.len() <= 2
+        && numbers
+            .iter()
+            .all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Spans for one line of a `lang` block; unknown languages stay plain
+pub fn highlight(lang: &str, line: &str) -> Vec<Span<'static>> {
+    match syntax(lang) {
+        Some(syntax) => tokenize(syntax, line),
+        None => vec![Span::raw(line.to_string())],
+    }
+}
+
+fn tokenize(syntax: &Syntax, line: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let flush = |spans: &mut Vec<Span<'static>>, plain: &mut String| {
+        if !plain.is_empty() {
+            spans.push(Span::raw(std::mem::take(plain)));
+        }
+    };
+
+    let mut rest = line;
+    if syntax.keys {
+        let indent = line.len() - line.trim_start().len();
+        let body = &line[indent..];
+        let key_len = body
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')))
+            .unwrap_or(body.len());
+        let after = body[key_len..].trim_start();
+        if key_len > 0 && (after.starts_with(':') || after.starts_with('=')) {
+            plain.push_str(&line[..indent]);
+            flush(&mut spans, &mut plain);
+            spans.push(Span::styled(
+                body[..key_len].to_string(),
+                Style::default().fg(KEY),
+            ));
+            rest = &body[key_len..];
+        } else if body.starts_with('[') {
+            return vec![Span::styled(line.to_string(), Style::default().fg(TYPE))];
+        }
+    }
+
+    while let Some(c) = rest.chars().next() {
+        if syntax.line_comments.iter().any(|m| rest.starts_with(m)) {
+            flush(&mut spans, &mut plain);
+            spans.push(Span::styled(rest.to_string(), Style::default().fg(COMMENT)));
+            return spans;
+        }
+        if syntax.quotes.contains(&c) {
+            let end = string_end(rest, c);
+            flush(&mut spans, &mut plain);
+            spans.push(Span::styled(
+                rest[..end].to_string(),
+                Style::default().fg(STRING),
+            ));
+            rest = &rest[end..];
+            continue;
+        }
+        if c.is_ascii_digit() {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '_'))
+                .unwrap_or(rest.len());
+            flush(&mut spans, &mut plain);
+            spans.push(Span::styled(
+                rest[..end].to_string(),
+                Style::default().fg(NUMBER),
+            ));
+            rest = &rest[end..];
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            let keyword = if syntax.any_case {
+                syntax.keywords.iter().any(|k| k.eq_ignore_ascii_case(word))
+            } else {
+                syntax.keywords.contains(&word)
+            };
+            if keyword {
+                flush(&mut spans, &mut plain);
+                spans.push(Span::styled(
+                    word.to_string(),
+                    Style::default().fg(KEYWORD).add_modifier(Modifier::BOLD),
+                ));
+            } else if word.starts_with(char::is_uppercase) && !syntax.keys {
+                flush(&mut spans, &mut plain);
+                spans.push(Span::styled(word.to_string(), Style::default().fg(TYPE)));
+            } else {
+                plain.push_str(word);
+            }
+            rest = &rest[end..];
+            continue;
+        }
+        plain.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    flush(&mut spans, &mut plain);
+    spans
+}
+
+/// Byte length of the string literal opening `rest`, closing quote
+/// included; an unterminated string runs to the end of the line
+fn string_end(rest: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, c) in rest.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return i + c.len_utf8();
+        }
+    }
+    rest.len()
+}
+
+/// Spans for one line of a stack trace: frame keywords dimmed, function
+/// names and `file:line` locations colored, the error headline in red
+pub fn highlight_trace(line: &str) -> Vec<Span<'static>> {
+    let trimmed = line.trim_start();
+    if !is_frame(line) && !trimmed.is_empty() {
+        let headline = trimmed.starts_with("Traceback")
+            || trimmed.contains("panicked at")
+            || trimmed
+                .split(':')
+                .next()
+                .is_some_and(|head| head.ends_with("Error") || head.ends_with("Exception"));
+        if headline {
+            return vec![Span::styled(
+                line.to_string(),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )];
+        }
+    }
+
+    let dim = Style::default().fg(COMMENT);
+    // `4:` numbering a Rust backtrace frame
+    let frame_index = |word: &str| {
+        word.strip_suffix(':')
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    };
+    let mut spans = Vec::new();
+    // The previous word introduces a function name
+    let mut after_marker = false;
+    let mut rest = line;
+    while !rest.is_empty() {
+        let space = rest.len() - rest.trim_start().len();
+        if space > 0 {
+            spans.push(Span::raw(rest[..space].to_string()));
+            rest = &rest[space..];
+            continue;
+        }
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let word = &rest[..end];
+        let bare = word.trim_end_matches(',');
+        let style = if frame_index(word) {
+            dim
+        } else if is_location(word)
+            || (bare.len() > 1 && bare.starts_with('"') && bare.ends_with('"'))
+        {
+            Style::default().fg(LOCATION)
+        } else if matches!(bare, "at" | "in" | "File" | "line") {
+            dim
+        } else if after_marker {
+            Style::default().fg(FUNCTION)
+        } else if bare.parse::<u32>().is_ok() {
+            Style::default().fg(NUMBER)
+        } else {
+            Style::default()
+        };
+        after_marker = matches!(bare, "at" | "in") || frame_index(word);
+        spans.push(Span::styled(word.to_string(), style));
+        rest = &rest[end..];
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colored(spans: &[Span<'static>]) -> Vec<(String, Option<Color>)> {
+        spans
+            .iter()
+            .filter(|s| s.style.fg.is_some())
+            .map(|s| (s.content.to_string(), s.style.fg))
+            .collect()
+    }
+
+    #[test]
+    fn rust_tokens() {
+        let spans = highlight("rust", "let name: String = \"a \\\"b\\\"\"; // 42 here");
+        assert_eq!(
+            colored(&spans),
+            [
+                ("let".to_string(), Some(KEYWORD)),
+                ("String".to_string(), Some(TYPE)),
+                ("\"a \\\"b\\\"\"".to_string(), Some(STRING)),
+                ("// 42 here".to_string(), Some(COMMENT)),
+            ]
+        );
+        let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "let name: String = \"a \\\"b\\\"\"; // 42 here");
+    }
+
+    #[test]
+    fn config_keys_and_unknown_languages() {
+        assert_eq!(
+            colored(&highlight("toml", "retries = 3 # max")),
+            [
+                ("retries".to_string(), Some(KEY)),
+                ("3".to_string(), Some(NUMBER)),
+                ("# max".to_string(), Some(COMMENT)),
+            ]
+        );
+        assert_eq!(
+            colored(&highlight("toml", "[retry.budgets]")),
+            [("[retry.budgets]".to_string(), Some(TYPE))]
+        );
+        assert!(colored(&highlight("text", "let x = 1")).is_empty());
+        assert_eq!(
+            colored(&highlight("sql", "SELECT id FROM t")),
+            [
+                ("SELECT".to_string(), Some(KEYWORD)),
+                ("FROM".to_string(), Some(KEYWORD)),
+            ]
+        );
+    }
+
+    #[test]
+    fn trace_frames_and_detection() {
+        let js = [
+            "TypeError: x is undefined",
+            "    at render (src/app.js:12:5)",
+        ];
+        assert!(is_trace("", &js));
+        assert!(!is_trace("", &["let a = 1;", "a"]));
+        assert!(is_trace("backtrace", &["anything"]));
+
+        assert_eq!(highlight_trace(js[0])[0].style.fg, Some(Color::Red));
+        assert_eq!(
+            colored(&highlight_trace(js[1])),
+            [
+                ("at".to_string(), Some(COMMENT)),
+                ("render".to_string(), Some(FUNCTION)),
+                ("(src/app.js:12:5)".to_string(), Some(LOCATION)),
+            ]
+        );
+        assert_eq!(
+            colored(&highlight_trace("  File \"app.py\", line 3, in main")),
+            [
+                ("File".to_string(), Some(COMMENT)),
+                ("\"app.py\",".to_string(), Some(LOCATION)),
+                ("line".to_string(), Some(COMMENT)),
+                ("3,".to_string(), Some(NUMBER)),
+                ("in".to_string(), Some(COMMENT)),
+                ("main".to_string(), Some(FUNCTION)),
+            ]
+        );
+        assert_eq!(
+            colored(&highlight_trace("   4: board::main")),
+            [
+                ("4:".to_string(), Some(COMMENT)),
+                ("board::main".to_string(), Some(FUNCTION)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! One-line text input modal
+//!
+//! Centered popup with a title, a prompt and an editable single line.
+//! Enter submits, Esc cancels. Reused by every modal that needs free text.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+/// Editable single-line buffer with a cursor (in chars)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    pub value: String,
+    pub cursor: usize,
+}
+
+impl TextInput {
+    pub fn with_value(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+            cursor: value.chars().count(),
+        }
+    }
+
+    fn byte_index(&self, char_idx: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_idx)
+            .map_or(self.value.len(), |(i, _)| i)
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let at = self.byte_index(self.cursor);
+        self.value.insert(at, c);
+        self.cursor += 1;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+        self.cursor -= 1;
+        let at = self.byte_index(self.cursor);
+        self.value.remove(at);
+    }
+
+    pub fn left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.value.chars().count());
+    }
+}
+
+/// Input modal widget
+pub struct InputModal<'a> {
+    pub title: &'a str,
+    pub prompt: &'a str,
+    pub input: &'a TextInput,
+    /// Warning shown when TASKS.md changed on disk while the modal was open
+    pub conflict: Option<&'a str>,
+}
+
+impl<'a> InputModal<'a> {
+    fn centered_rect(&self, area: Rect) -> Rect {
+        let width = 60.min(area.width.saturating_sub(4));
+        let height = (7 + u16::from(self.conflict.is_some())).min(area.height.saturating_sub(2));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let (before, after) = self
+            .input
+            .value
+            .split_at(self.input.byte_index(self.input.cursor));
+        let mut after = after.chars();
+        let at_cursor = after.next().map_or(" ".to_string(), String::from);
+        let mut lines = vec![
+            Line::styled(
+                format!("  {}", self.prompt),
+                Style::default().fg(Color::Yellow),
+            ),
+            Line::raw(""),
+            Line::from(vec![
+                Span::styled("  > ", Style::default().fg(Color::Cyan)),
+                Span::raw(before.to_string()),
+                Span::styled(at_cursor, Style::default().add_modifier(Modifier::REVERSED)),
+                Span::raw(after.as_str().to_string()),
+            ]),
+            Line::raw(""),
+            Line::from(vec![
+                Span::styled("  [Enter]", Style::default().fg(Color::Green)),
+                Span::raw(" Save  "),
+                Span::styled("[Esc]", Style::default().fg(Color::Red)),
+                Span::raw(" Cancel"),
+            ]),
+        ];
+        if let Some(conflict) = self.conflict {
+            lines.insert(
+                0,
+                Line::styled(
+                    format!("  ⚠ {conflict}"),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ),
+            );
+        }
+        lines
+    }
+}
+
+impl<'a> Widget for InputModal<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = self.centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(format!(" {} ", self.title))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+
+        Paragraph::new(self.build_lines())
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_at_cursor() {
+        let mut input = TextInput::default();
+        for c in "helo".chars() {
+            input.insert(c);
+        }
+        input.left();
+        input.insert('l');
+        assert_eq!(input.value, "hello");
+        input.right();
+        input.backspace();
+        assert_eq!(input.value, "hell");
+        assert_eq!(input.cursor, 4);
+    }
+
+    #[test]
+    fn multibyte_chars() {
+        let mut input = TextInput::with_value("메모");
+        assert_eq!(input.cursor, 2);
+        input.backspace();
+        input.insert('리');
+        assert_eq!(input.value, "메리");
+    }
+
+    #[test]
+    fn cursor_bounds() {
+        let mut input = TextInput::with_value("ab");
+        input.right();
+        assert_eq!(input.cursor, 2);
+        input.left();
+        input.left();
+        input.left();
+        assert_eq!(input.cursor, 0);
+        input.backspace();
+        assert_eq!(input.value, "ab");
+    }
+
+    #[test]
+    fn modal_shows_prompt_and_value() {
+        let input = TextInput::with_value("flaky test");
+        let modal = InputModal {
+            title: "Add note",
+            prompt: "Note for T1",
+            input: &input,
+            conflict: None,
+        };
+        let lines = modal.build_lines();
+        assert!(lines[0].spans[0].content.contains("Note for T1"));
+        let text: String = lines[2].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.contains("flaky test"));
+
+        let area = Rect::new(0, 0, 80, 24);
+        let mut buf = Buffer::empty(area);
+        modal.render(area, &mut buf);
+    }
+
+    #[test]
+    fn conflict_warning_grows_modal() {
+        let input = TextInput::default();
+        let modal = InputModal {
+            title: "Add note",
+            prompt: "Note for T1",
+            input: &input,
+            conflict: Some("T1 changed on disk"),
+        };
+        let lines = modal.build_lines();
+        assert!(lines[0].spans[0].content.contains("T1 changed on disk"));
+        assert_eq!(modal.centered_rect(Rect::new(0, 0, 80, 24)).height, 8);
+    }
+}
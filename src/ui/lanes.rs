@@ -0,0 +1,181 @@
+//! Agent lane timeline
+//!
+//! One horizontal lane per agent over the span of its hook events: tool calls
+//! in yellow, the rest of a task interval in green, and idle gaps as dots,
+//! with the share of time the agent was busy at the end of the lane.
+
+use chrono::{DateTime, Duration, Utc};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+use crate::data::state::{AgentState, DashboardState};
+use crate::ui::gantt::build_time_header;
+
+/// What an agent was doing during one lane cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Activity {
+    Idle,
+    Task,
+    Tool,
+}
+
+/// `[start, end)` overlaps the cell `[from, to)`; an open interval runs to `end`
+fn overlaps(
+    start: DateTime<Utc>,
+    stop: Option<DateTime<Utc>>,
+    end: DateTime<Utc>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> bool {
+    start < to && stop.unwrap_or(end).max(start + Duration::seconds(1)) > from
+}
+
+fn lane_cells(
+    agent: &AgentState,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    width: usize,
+) -> Vec<Activity> {
+    let span = (end - start).num_milliseconds().max(1) as f64;
+    let at = |i: usize| start + Duration::milliseconds((span * i as f64 / width as f64) as i64);
+    (0..width)
+        .map(|i| {
+            let (from, to) = (at(i), at(i + 1));
+            if agent
+                .tool_history
+                .iter()
+                .any(|c| overlaps(c.started_at, c.ended_at, end, from, to))
+            {
+                Activity::Tool
+            } else if agent
+                .task_history
+                .iter()
+                .any(|t| overlaps(t.started_at, t.completed_at, end, from, to))
+            {
+                Activity::Task
+            } else {
+                Activity::Idle
+            }
+        })
+        .collect()
+}
+
+/// Lane rows for every agent, sorted by ID, with a time header and legend.
+/// `width` is the inner width of the panel.
+pub fn build_lane_lines(state: &DashboardState, width: usize) -> Vec<Line<'static>> {
+    if state.agents.is_empty() {
+        return vec![Line::raw("  No agent activity")];
+    }
+    let mut agents: Vec<&AgentState> = state.agents.values().collect();
+    agents.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+
+    let now = state.now();
+    let start = agents
+        .iter()
+        .filter_map(|a| a.first_seen)
+        .min()
+        .unwrap_or(now);
+    let end = agents
+        .iter()
+        .filter_map(|a| a.last_seen)
+        .max()
+        .unwrap_or(now)
+        .max(start + Duration::seconds(1));
+
+    let label_width = agents.iter().map(|a| a.agent_id.len()).max().unwrap_or(8) + 1;
+    // Room for the label and a trailing " 100%"
+    let bar_width = width.saturating_sub(label_width + 6).max(10);
+    let total_mins = (end - start).num_seconds() as f64 / 60.0;
+
+    let start_clock = state.timezone.format(start, "%H:%M");
+    let mut lines = vec![build_time_header(
+        label_width,
+        bar_width,
+        total_mins,
+        Some(&start_clock),
+    )];
+    for agent in agents {
+        let cells = lane_cells(agent, start, end, bar_width);
+        let busy = cells.iter().filter(|c| **c != Activity::Idle).count();
+        let mut spans = vec![Span::styled(
+            format!("{:>label_width$} ", agent.agent_id),
+            Style::default().fg(Color::White),
+        )];
+        // Merge runs of the same activity into one span
+        let mut run = (cells[0], 0);
+        for cell in cells {
+            if cell == run.0 {
+                run.1 += 1;
+            } else {
+                spans.push(activity_span(run.0, run.1));
+                run = (cell, 1);
+            }
+        }
+        spans.push(activity_span(run.0, run.1));
+        spans.push(Span::styled(
+            format!(" {:>3}%", busy * 100 / bar_width),
+            Style::default().fg(Color::DarkGray),
+        ));
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(vec![
+        Span::raw(" ".repeat(label_width + 1)),
+        activity_span(Activity::Tool, 1),
+        Span::styled(" tool  ", Style::default().fg(Color::DarkGray)),
+        activity_span(Activity::Task, 1),
+        Span::styled(" task  ", Style::default().fg(Color::DarkGray)),
+        activity_span(Activity::Idle, 1),
+        Span::styled(" idle", Style::default().fg(Color::DarkGray)),
+    ]));
+    lines
+}
+
+fn activity_span(activity: Activity, len: usize) -> Span<'static> {
+    let (ch, color) = match activity {
+        Activity::Tool => ('\u{2588}', Color::Yellow),
+        Activity::Task => ('\u{2592}', Color::Green),
+        Activity::Idle => ('\u{00B7}', Color::DarkGray),
+    };
+    Span::styled(ch.to_string().repeat(len), Style::default().fg(color))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser;
+
+    #[test]
+    fn lanes_show_tools_tasks_and_idle_gaps() {
+        let input = concat!(
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","tool_name":"Bash","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:00:10Z","agent_id":"a","task_id":"T1","tool_name":"Bash","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:20Z","agent_id":"a","task_id":"T1","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:30Z","agent_id":"b","task_id":"T2","session_id":"s"}"#,
+            "\n",
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:40Z","agent_id":"b","task_id":"T2","session_id":"s"}"#,
+        );
+        let mut state = DashboardState::default();
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+
+        // 10 cells of 4s each over the 40s span
+        let lines = build_lane_lines(&state, 18);
+        assert_eq!(lines.len(), 4);
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(text[1], " a \u{2588}\u{2588}\u{2588}\u{2592}\u{2592}\u{00B7}\u{00B7}\u{00B7}\u{00B7}\u{00B7}  50%");
+        assert_eq!(text[2], " b \u{00B7}\u{00B7}\u{00B7}\u{00B7}\u{00B7}\u{00B7}\u{00B7}\u{2592}\u{2592}\u{2592}  30%");
+        assert!(text[3].contains("tool"));
+    }
+
+    #[test]
+    fn no_agents_shows_placeholder() {
+        let lines = build_lane_lines(&DashboardState::default(), 40);
+        assert_eq!(lines[0].to_string(), "  No agent activity");
+    }
+}
@@ -1,24 +1,36 @@
 //! Screen split layout
 //!
 //! Defines the main dashboard layout: task list (left), detail panel (right),
-//! and status bar (bottom).
+//! and status bar (bottom), and the ops preset: task list over a live event
+//! log.
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
+pub use crate::config::LayoutPreset;
+
 /// The pane that currently has focus
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FocusedPane {
     TaskList,
     Detail,
     Agents,
+    /// A registered plugin panel, by registration index
+    Panel(usize),
 }
 
 impl FocusedPane {
     pub fn toggle(self) -> Self {
+        self.next(0)
+    }
+
+    /// Next pane in Tab order, visiting `panels` plugin panels after Agents
+    pub fn next(self, panels: usize) -> Self {
         match self {
             Self::TaskList => Self::Detail,
             Self::Detail => Self::Agents,
-            Self::Agents => Self::TaskList,
+            Self::Agents if panels > 0 => Self::Panel(0),
+            Self::Panel(i) if i + 1 < panels => Self::Panel(i + 1),
+            Self::Agents | Self::Panel(_) => Self::TaskList,
         }
     }
 }
@@ -29,6 +41,12 @@ pub struct DashboardLayout {
     pub detail: Rect,
     pub agents: Rect,
     pub status_bar: Rect,
+    /// Top row reserved for the fatal error banner, when shown
+    pub banner: Option<Rect>,
+    /// One area per plugin panel, stacked under the agents panel
+    pub panels: Vec<Rect>,
+    /// Live hook event log (ops preset only)
+    pub events: Option<Rect>,
 }
 
 impl DashboardLayout {
@@ -45,6 +63,20 @@ impl DashboardLayout {
     /// +------------------------------------+
     /// ```
     pub fn compute(area: Rect) -> Self {
+        Self::compute_with_banner(area, false)
+    }
+
+    /// Compute layout, optionally reserving the top row for a banner
+    pub fn compute_with_banner(area: Rect, show_banner: bool) -> Self {
+        Self::compute_with_panels(area, show_banner, 0)
+    }
+
+    /// Compute layout with room for `panels` plugin panels. With any panels the
+    /// right column is split in half: detail on top, agents and the plugin
+    /// panels sharing the bottom equally.
+    pub fn compute_with_panels(area: Rect, show_banner: bool, panels: usize) -> Self {
+        let (banner, area) = split_banner(area, show_banner);
+
         let vertical = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(3), Constraint::Length(1)])
@@ -56,20 +88,86 @@ impl DashboardLayout {
             .split(vertical[0]);
 
         // Split right panel: detail (top 70%) + agents (bottom 30%)
+        let detail_pct = if panels == 0 { 70 } else { 50 };
         let right_split = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+            .constraints([
+                Constraint::Percentage(detail_pct),
+                Constraint::Percentage(100 - detail_pct),
+            ])
             .split(horizontal[1]);
+        let bottom = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Ratio(1, panels as u32 + 1); panels + 1])
+            .split(right_split[1]);
 
         Self {
             task_list: horizontal[0],
             detail: right_split[0],
-            agents: right_split[1],
+            agents: bottom[0],
             status_bar: vertical[1],
+            banner,
+            panels: bottom[1..].to_vec(),
+            events: None,
+        }
+    }
+
+    /// Compute the layout for `preset`
+    pub fn for_preset(area: Rect, show_banner: bool, panels: usize, preset: LayoutPreset) -> Self {
+        match preset {
+            LayoutPreset::Standard => Self::compute_with_panels(area, show_banner, panels),
+            LayoutPreset::Ops => Self::compute_ops(area, show_banner),
+        }
+    }
+
+    /// Ops preset: the task list and the event log share the screen; detail,
+    /// agents and plugin panels are hidden (empty areas)
+    ///
+    /// ```text
+    /// +------------------------------------+
+    /// |             Task List              |
+    /// +------------------------------------+
+    /// |             Event Log              |
+    /// +------------------------------------+
+    /// |            Status Bar              |
+    /// +------------------------------------+
+    /// ```
+    pub fn compute_ops(area: Rect, show_banner: bool) -> Self {
+        let (banner, area) = split_banner(area, show_banner);
+        let vertical = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(50),
+                Constraint::Min(3),
+                Constraint::Length(1),
+            ])
+            .split(area);
+
+        Self {
+            task_list: vertical[0],
+            detail: Rect::default(),
+            agents: Rect::default(),
+            status_bar: vertical[2],
+            banner,
+            panels: Vec::new(),
+            events: Some(vertical[1]),
         }
     }
 }
 
+/// Reserve the top row for the banner when shown and there is room
+fn split_banner(area: Rect, show_banner: bool) -> (Option<Rect>, Rect) {
+    if show_banner && area.height > 2 {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        (Some(split[0]), split[1])
+    } else {
+        (None, area)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,6 +179,39 @@ mod tests {
         assert_eq!(FocusedPane::Agents.toggle(), FocusedPane::TaskList);
     }
 
+    #[test]
+    fn focus_cycles_through_panels() {
+        assert_eq!(FocusedPane::Agents.next(2), FocusedPane::Panel(0));
+        assert_eq!(FocusedPane::Panel(0).next(2), FocusedPane::Panel(1));
+        assert_eq!(FocusedPane::Panel(1).next(2), FocusedPane::TaskList);
+        // A stale index after panels were removed goes back to the tasks
+        assert_eq!(FocusedPane::Panel(3).next(1), FocusedPane::TaskList);
+    }
+
+    #[test]
+    fn layout_stacks_plugin_panels() {
+        let area = Rect::new(0, 0, 120, 40);
+        let layout = DashboardLayout::compute_with_panels(area, false, 2);
+        assert_eq!(layout.panels.len(), 2);
+        assert_eq!(layout.panels[0].y, layout.agents.y + layout.agents.height);
+        assert_eq!(layout.panels[1].x, layout.agents.x);
+        assert!(DashboardLayout::compute(area).panels.is_empty());
+    }
+
+    #[test]
+    fn ops_layout_splits_tasks_and_events() {
+        let area = Rect::new(0, 0, 120, 41);
+        let layout = DashboardLayout::for_preset(area, true, 2, LayoutPreset::Ops);
+        let events = layout.events.unwrap();
+        assert_eq!(layout.task_list.y, 1);
+        assert_eq!(layout.task_list.width, 120);
+        assert_eq!(events.y, layout.task_list.y + layout.task_list.height);
+        assert_eq!(events.y + events.height, layout.status_bar.y);
+        assert_eq!(layout.detail.area(), 0);
+        assert!(layout.panels.is_empty());
+        assert!(DashboardLayout::compute(area).events.is_none());
+    }
+
     #[test]
     fn layout_standard_size() {
         let area = Rect::new(0, 0, 120, 40);
@@ -107,4 +238,15 @@ mod tests {
         let layout = DashboardLayout::compute(area);
         assert_eq!(layout.status_bar.y, area.height - 1);
     }
+
+    #[test]
+    fn layout_banner_takes_top_row() {
+        let area = Rect::new(0, 0, 80, 30);
+        let layout = DashboardLayout::compute_with_banner(area, true);
+        let banner = layout.banner.unwrap();
+        assert_eq!((banner.y, banner.height, banner.width), (0, 1, 80));
+        assert_eq!(layout.task_list.y, 1);
+        assert_eq!(layout.status_bar.y, area.height - 1);
+        assert!(DashboardLayout::compute(area).banner.is_none());
+    }
 }
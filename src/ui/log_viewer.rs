@@ -0,0 +1,128 @@
+//! Log viewer overlay
+//!
+//! `L` opens a popup with the tail of the board's own debug log (see
+//! [`crate::logging`]): watcher failures, parse warnings and write-backs,
+//! without leaving the TUI to find the file. Levels are colored; the newest
+//! line is at the bottom.
+
+use std::path::Path;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+/// Log viewer widget
+pub struct LogViewer<'a> {
+    /// Log file, None when logging is off
+    pub path: Option<&'a Path>,
+    /// Tail of the file, oldest first
+    pub lines: &'a [String],
+}
+
+impl<'a> LogViewer<'a> {
+    fn centered_rect(area: Rect) -> Rect {
+        let width = (area.width * 9 / 10).min(120);
+        let height = (area.height * 7 / 10).max(5).min(area.height);
+        let x = area.x + (area.width.saturating_sub(width)) / 2;
+        let y = area.y + (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    /// Lines that fit `rows`, newest last
+    fn build_lines(&self, rows: usize) -> Vec<Line<'static>> {
+        if self.path.is_none() {
+            return vec![
+                Line::styled(" Logging is off.", Style::default().fg(Color::DarkGray)),
+                Line::styled(
+                    " Start with --log-file <PATH> or --log-level <LEVEL>.",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ];
+        }
+        if self.lines.is_empty() {
+            return vec![Line::styled(
+                " Nothing logged yet",
+                Style::default().fg(Color::DarkGray),
+            )];
+        }
+        let skip = self.lines.len().saturating_sub(rows);
+        self.lines[skip..]
+            .iter()
+            .map(|line| Line::styled(format!(" {line}"), level_style(line)))
+            .collect()
+    }
+}
+
+/// Color by the level field of a `tracing` fmt line
+fn level_style(line: &str) -> Style {
+    let level = line.split_whitespace().nth(1).unwrap_or("");
+    match level {
+        "ERROR" => Style::default().fg(Color::Red),
+        "WARN" => Style::default().fg(Color::Yellow),
+        "DEBUG" | "TRACE" => Style::default().fg(Color::DarkGray),
+        _ => Style::default(),
+    }
+}
+
+impl<'a> Widget for LogViewer<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = Self::centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let title = match self.path {
+            Some(path) => format!(" Log: {} ", path.display()),
+            None => " Log ".to_string(),
+        };
+        let block = Block::default()
+            .title(title)
+            .title_bottom(" L/Esc close ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta));
+        let rows = popup_area.height.saturating_sub(2) as usize;
+
+        Paragraph::new(self.build_lines(rows))
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_the_newest_lines_colored_by_level() {
+        let lines = vec![
+            "2026-02-08T10:00:00Z  INFO simple_claude_board: starting".to_string(),
+            "2026-02-08T10:00:01Z  WARN simple_claude_board::app: tasks parse warning".to_string(),
+            "2026-02-08T10:00:02Z ERROR simple_claude_board: boom".to_string(),
+        ];
+        let viewer = LogViewer {
+            path: Some(Path::new("/tmp/board.log")),
+            lines: &lines,
+        };
+        let shown = viewer.build_lines(2);
+        assert_eq!(shown.len(), 2);
+        assert!(shown[0].to_string().ends_with("tasks parse warning"));
+        assert_eq!(shown[0].style.fg, Some(Color::Yellow));
+        assert_eq!(shown[1].style.fg, Some(Color::Red));
+
+        let area = Rect::new(0, 0, 60, 12);
+        let mut buf = Buffer::empty(area);
+        viewer.render(area, &mut buf);
+    }
+
+    #[test]
+    fn logging_off_explains_how_to_enable_it() {
+        let viewer = LogViewer {
+            path: None,
+            lines: &[],
+        };
+        let shown = viewer.build_lines(10);
+        assert!(shown[1].to_string().contains("--log-file"));
+    }
+}
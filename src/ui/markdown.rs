@@ -0,0 +1,540 @@
+//! Markdown in task bodies
+//!
+//! Renders the markdown plans use in the detail pane: headings, bullet and
+//! numbered lists with their nesting, checkboxes, fenced code blocks on a
+//! shaded background (highlighted, see [`crate::ui::highlight`]), and inline **bold**, `code` and [links](url). Links
+//! are underlined; on terminals that understand OSC 8 the frame is patched
+//! afterwards so they can be clicked ([`hyperlink`]), elsewhere the URL is
+//! printed after the text.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::ui::highlight;
+
+/// Background of fenced code blocks
+const CODE_BG: Color = Color::Indexed(236);
+
+/// List bullets by nesting level
+const BULLETS: [&str; 3] = ["\u{2022}", "\u{25E6}", "\u{25AA}"];
+
+/// A link in rendered text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub text: String,
+    pub url: String,
+}
+
+/// Styled lines of a body and the links in them
+#[derive(Debug, Default)]
+pub struct Rendered {
+    pub lines: Vec<Line<'static>>,
+    pub links: Vec<Link>,
+}
+
+/// A run of body lines: ordinary text, or a fenced code block
+enum Block<'a> {
+    Text(&'a str),
+    Code {
+        lang: String,
+        /// Indent of the opening fence
+        indent: usize,
+        lines: Vec<&'a str>,
+    },
+}
+
+/// Split text into text lines and fenced code blocks; an unterminated
+/// fence runs to the end
+fn blocks(body: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut fence: Option<(usize, String, Vec<&str>)> = None;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if let Some(info) = trimmed.strip_prefix("```") {
+            match fence.take() {
+                Some((indent, lang, lines)) => blocks.push(Block::Code {
+                    lang,
+                    indent,
+                    lines,
+                }),
+                None => {
+                    fence = Some((
+                        line.len() - trimmed.len(),
+                        info.trim().to_string(),
+                        Vec::new(),
+                    ))
+                }
+            }
+        } else if let Some((_, _, lines)) = fence.as_mut() {
+            lines.push(line);
+        } else {
+            blocks.push(Block::Text(line));
+        }
+    }
+    if let Some((indent, lang, lines)) = fence {
+        blocks.push(Block::Code {
+            lang,
+            indent,
+            lines,
+        });
+    }
+    blocks
+}
+
+/// Render a task body. With `hyperlinks` off, link URLs are spelled out.
+pub fn render(body: &str, hyperlinks: bool) -> Rendered {
+    let mut out = Rendered::default();
+    for block in blocks(body) {
+        match block {
+            Block::Text(line) => out.lines.push(text_line(line, &mut out.links, hyperlinks)),
+            Block::Code {
+                lang,
+                indent,
+                lines,
+            } => out.lines.extend(code_block(&lang, indent, &lines)),
+        }
+    }
+    out
+}
+
+/// Only the fenced code blocks of `text`, rendered as in a body (for error
+/// messages that quote code or a stack trace)
+pub fn fenced_blocks(text: &str) -> Vec<Line<'static>> {
+    blocks(text)
+        .into_iter()
+        .flat_map(|block| match block {
+            Block::Text(_) => Vec::new(),
+            Block::Code {
+                lang,
+                indent,
+                lines,
+            } => code_block(&lang, indent, &lines),
+        })
+        .collect()
+}
+
+/// Width of leading whitespace, a tab counting as four columns
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+fn text_line(line: &str, links: &mut Vec<Link>, hyperlinks: bool) -> Line<'static> {
+    let indent = indent_width(line);
+    let rest = line.trim_start();
+
+    let hashes = rest.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&hashes) && rest[hashes..].starts_with(' ') {
+        let style = if hashes <= 2 {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().add_modifier(Modifier::BOLD)
+        };
+        return Line::from(inline(rest[hashes..].trim(), links, hyperlinks)).style(style);
+    }
+
+    let pad = " ".repeat(indent);
+    let Some((marker, item)) = list_item(rest) else {
+        let mut spans = vec![Span::raw(pad)];
+        spans.extend(inline(rest, links, hyperlinks));
+        return Line::from(spans);
+    };
+    let nested = "  ".repeat(indent / 2);
+    let mut spans = match marker {
+        Some(number) => vec![Span::styled(
+            format!("{nested}{number} "),
+            Style::default().fg(Color::DarkGray),
+        )],
+        None => vec![Span::styled(
+            format!("{nested}{} ", BULLETS[(indent / 2) % BULLETS.len()]),
+            Style::default().fg(Color::DarkGray),
+        )],
+    };
+    let item = if let Some(open) = item.strip_prefix("[ ] ") {
+        spans.push(Span::raw("\u{2610} "));
+        open
+    } else if let Some(done) = item
+        .strip_prefix("[x] ")
+        .or_else(|| item.strip_prefix("[X] "))
+    {
+        spans.push(Span::styled("\u{2611} ", Style::default().fg(Color::Green)));
+        done
+    } else {
+        item
+    };
+    spans.extend(inline(item, links, hyperlinks));
+    Line::from(spans)
+}
+
+/// `- item` / `* item` / `+ item` as (None, item); `3. item` / `3) item`
+/// as (Some("3."), item)
+fn list_item(rest: &str) -> Option<(Option<&str>, &str)> {
+    if let Some(item) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|bullet| rest.strip_prefix(bullet))
+    {
+        return Some((None, item));
+    }
+    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+    let after = &rest[digits..];
+    (digits > 0 && (after.starts_with(". ") || after.starts_with(") ")))
+        .then(|| (Some(&rest[..digits + 1]), &rest[digits + 2..]))
+}
+
+/// Split a line into styled spans: **bold**, `code`, [text](url) and plain
+/// text
+fn inline(text: &str, links: &mut Vec<Link>, hyperlinks: bool) -> Vec<Span<'static>> {
+    let bold = Style::default()
+        .fg(Color::White)
+        .add_modifier(Modifier::BOLD);
+    let code = Style::default().fg(Color::Yellow);
+    let link = Style::default()
+        .fg(Color::Blue)
+        .add_modifier(Modifier::UNDERLINED);
+
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("**") {
+            if let Some(end) = after.find("**") {
+                flush(&mut spans, &mut plain);
+                spans.push(Span::styled(after[..end].to_string(), bold));
+                rest = &after[end + 2..];
+                continue;
+            }
+        } else if let Some(after) = rest.strip_prefix('`') {
+            if let Some(end) = after.find('`') {
+                flush(&mut spans, &mut plain);
+                spans.push(Span::styled(after[..end].to_string(), code));
+                rest = &after[end + 1..];
+                continue;
+            }
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let target = after.split_once("](").and_then(|(label, tail)| {
+                let (url, tail) = tail.split_once(')')?;
+                (!label.is_empty() && !url.is_empty()).then_some((label, url, tail))
+            });
+            if let Some((label, url, tail)) = target {
+                flush(&mut spans, &mut plain);
+                spans.push(Span::styled(label.to_string(), link));
+                if hyperlinks {
+                    links.push(Link {
+                        text: label.to_string(),
+                        url: url.to_string(),
+                    });
+                } else {
+                    spans.push(Span::styled(
+                        format!(" <{url}>"),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                rest = tail;
+                continue;
+            }
+        }
+        let mut chars = rest.chars();
+        plain.extend(chars.next());
+        rest = chars.as_str();
+    }
+    flush(&mut spans, &mut plain);
+    spans
+}
+
+fn flush(spans: &mut Vec<Span<'static>>, plain: &mut String) {
+    if !plain.is_empty() {
+        spans.push(Span::raw(std::mem::take(plain)));
+    }
+}
+
+/// A fenced block: an optional language label, then the highlighted code
+/// padded to its widest line on a shaded background. `indent` columns of
+/// the fence's own indentation are removed from each line.
+fn code_block(lang: &str, indent: usize, code: &[&str]) -> Vec<Line<'static>> {
+    let code: Vec<String> = code
+        .iter()
+        .map(|line| {
+            let strip = line
+                .char_indices()
+                .take(indent)
+                .take_while(|(_, c)| *c == ' ')
+                .count();
+            line[strip..].replace('\t', "    ")
+        })
+        .collect();
+    let width = code
+        .iter()
+        .map(|line| Span::raw(line.as_str()).width())
+        .max()
+        .unwrap_or(0);
+    let trace = highlight::is_trace(lang, &code.iter().map(String::as_str).collect::<Vec<_>>());
+    let base = Style::default().fg(Color::Gray).bg(CODE_BG);
+    let mut lines = Vec::new();
+    if !lang.is_empty() {
+        lines.push(Line::styled(
+            format!("  {lang}"),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    for line in code {
+        let fill = width - Span::raw(line.as_str()).width();
+        let tokens = if trace {
+            highlight::highlight_trace(&line)
+        } else {
+            highlight::highlight(lang, &line)
+        };
+        let mut spans = vec![Span::raw("  "), Span::styled(" ", base)];
+        spans.extend(
+            tokens
+                .into_iter()
+                .map(|token| Span::styled(token.content, base.patch(token.style))),
+        );
+        spans.push(Span::styled(" ".repeat(fill + 1), base));
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// Whether the terminal is likely to understand OSC 8 hyperlinks.
+/// `FORCE_HYPERLINK=1` / `FORCE_HYPERLINK=0` overrides the guess.
+pub fn hyperlinks_supported() -> bool {
+    supports_hyperlinks(|key| std::env::var(key).ok())
+}
+
+fn supports_hyperlinks(var: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(force) = var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if [
+        "WT_SESSION",
+        "KITTY_WINDOW_ID",
+        "KONSOLE_VERSION",
+        "DOMTERM",
+    ]
+    .iter()
+    .any(|key| var(key).is_some())
+    {
+        return true;
+    }
+    // GNOME Terminal and other VTE terminals since 0.50
+    if var("VTE_VERSION")
+        .and_then(|v| v.parse::<u32>().ok())
+        .is_some_and(|v| v >= 5000)
+    {
+        return true;
+    }
+    matches!(
+        var("TERM_PROGRAM").as_deref(),
+        Some("iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper")
+    ) || matches!(
+        var("TERM").as_deref(),
+        Some("xterm-kitty" | "alacritty" | "foot" | "xterm-ghostty")
+    )
+}
+
+/// Make rendered links in `area` clickable with OSC 8 escapes.
+///
+/// ratatui has no hyperlink support, so this rewrites cells after the
+/// widget has drawn: each pair of link cells becomes one cell holding the
+/// escape around both characters (the backend counts the escape as wide
+/// and skips the second cell). Only underlined runs matching a link's text
+/// on one row are linked; a link wrapped across rows stays plain.
+pub fn hyperlink(buf: &mut Buffer, area: Rect, links: &[Link]) {
+    let area = area.intersection(buf.area);
+    for y in area.top()..area.bottom() {
+        // Cell positions of the row, minus the cells a wide symbol covers
+        let mut cells = Vec::new();
+        let mut x = area.left();
+        while x < area.right() {
+            cells.push(x);
+            x += (Span::raw(buf[(x, y)].symbol()).width() as u16).max(1);
+        }
+        for link in links {
+            let chars: Vec<String> = link.text.chars().map(String::from).collect();
+            let mut start = 0;
+            while start + chars.len() <= cells.len() {
+                let run = &cells[start..start + chars.len()];
+                let matches = run.iter().zip(&chars).all(|(&x, c)| {
+                    let cell = &buf[(x, y)];
+                    cell.symbol() == c && cell.modifier.contains(Modifier::UNDERLINED)
+                });
+                if !matches {
+                    start += 1;
+                    continue;
+                }
+                link_run(buf, y, run, cells.get(start + chars.len()), &link.url);
+                start += chars.len();
+            }
+        }
+    }
+}
+
+/// Wrap the cells of one matched link in escapes, two columns per cell
+fn link_run(buf: &mut Buffer, y: u16, run: &[u16], after: Option<&u16>, url: &str) {
+    let wide = |buf: &Buffer, x: u16| Span::raw(buf[(x, y)].symbol()).width() > 1;
+    let mut i = 0;
+    while i < run.len() {
+        let x = run[i];
+        let mut text = buf[(x, y)].symbol().to_string();
+        if !wide(buf, x) {
+            // Pair with the next narrow cell, borrowing the one after the
+            // link for an odd count; a lone narrow cell stays plain
+            let partner = run.get(i + 1).or(after.filter(|_| i + 1 == run.len()));
+            match partner.filter(|&&next| !wide(buf, next)) {
+                Some(&next) => {
+                    text.push_str(buf[(next, y)].symbol());
+                    i += 1;
+                }
+                None => {
+                    i += 1;
+                    continue;
+                }
+            }
+        }
+        buf[(x, y)].set_symbol(&format!("\x1b]8;;{url}\x07{text}\x1b]8;;\x07"));
+        i += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::widgets::{Paragraph, Widget};
+
+    fn text(rendered: &Rendered) -> Vec<String> {
+        rendered.lines.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn headings_lists_and_checkboxes() {
+        let body = "## Spec\n- **담당**: @qa\n  - nested `code`\n    * deeper\n1. first\n- [ ] open\n- [x] done\nplain";
+        let rendered = render(body, false);
+        assert_eq!(
+            text(&rendered),
+            [
+                "Spec",
+                "\u{2022} 담당: @qa",
+                "  \u{25E6} nested code",
+                "    \u{25AA} deeper",
+                "1. first",
+                "\u{2022} \u{2610} open",
+                "\u{2022} \u{2611} done",
+                "plain",
+            ]
+        );
+        assert_eq!(rendered.lines[0].style.fg, Some(Color::Cyan));
+        assert_eq!(rendered.lines[1].spans[1].style.fg, Some(Color::White));
+        assert_eq!(rendered.lines[2].spans[2].style.fg, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn fenced_code_is_padded_on_a_background() {
+        let body = "Steps:\n  ```rust\n  let a = 1;\n  a\n  ```\nafter";
+        let rendered = render(body, false);
+        assert_eq!(
+            text(&rendered),
+            [
+                "Steps:",
+                "  rust",
+                "   let a = 1; ",
+                "   a          ",
+                "after"
+            ]
+        );
+        assert_eq!(rendered.lines[2].spans[1].style.bg, Some(CODE_BG));
+        // `let` is highlighted and keeps the block background
+        let keyword = &rendered.lines[2].spans[2];
+        assert_eq!(keyword.content, "let");
+        assert_eq!(keyword.style.bg, Some(CODE_BG));
+        assert_ne!(keyword.style.fg, Some(Color::Gray));
+        // Markers inside code are left alone
+        assert_eq!(text(&render("```\n- **x**\n", false))[0], "   - **x** ");
+    }
+
+    #[test]
+    fn fenced_blocks_skip_the_prose() {
+        let message = "Build failed:\n```\nError: boom\n    at main (src/index.js:3:7)\n```";
+        let lines = fenced_blocks(message);
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            text,
+            [
+                "   Error: boom                    ",
+                "       at main (src/index.js:3:7) "
+            ]
+        );
+        // Detected as a trace: the location is colored
+        assert!(lines[1]
+            .spans
+            .iter()
+            .any(|s| s.content == "(src/index.js:3:7)" && s.style.fg == Some(Color::Cyan)));
+    }
+
+    #[test]
+    fn links_spell_out_the_url_without_hyperlinks() {
+        let rendered = render("See [the spec](https://example.com/spec) [x] now", false);
+        assert_eq!(
+            text(&rendered),
+            ["See the spec <https://example.com/spec> [x] now"]
+        );
+        assert!(rendered.links.is_empty());
+
+        let rendered = render("See [the spec](https://example.com/spec)", true);
+        assert_eq!(text(&rendered), ["See the spec"]);
+        assert_eq!(
+            rendered.links,
+            [Link {
+                text: "the spec".to_string(),
+                url: "https://example.com/spec".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn hyperlink_wraps_cell_pairs() {
+        let rendered = render("Go [docs](https://d.io) now", true);
+        let area = Rect::new(0, 0, 20, 1);
+        let mut buf = Buffer::empty(area);
+        Paragraph::new(rendered.lines).render(area, &mut buf);
+        hyperlink(&mut buf, area, &rendered.links);
+        assert_eq!(
+            buf[(3, 0)].symbol(),
+            "\x1b]8;;https://d.io\x07do\x1b]8;;\x07"
+        );
+        assert_eq!(
+            buf[(5, 0)].symbol(),
+            "\x1b]8;;https://d.io\x07cs\x1b]8;;\x07"
+        );
+        // Plain text with the same letters is not linked
+        assert_eq!(buf[(0, 0)].symbol(), "G");
+        assert_eq!(buf[(8, 0)].symbol(), "n");
+    }
+
+    #[test]
+    fn detects_hyperlink_terminals() {
+        let env = |pairs: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                pairs
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert!(supports_hyperlinks(env(&[("TERM_PROGRAM", "WezTerm")])));
+        assert!(supports_hyperlinks(env(&[("VTE_VERSION", "6003")])));
+        assert!(!supports_hyperlinks(env(&[("VTE_VERSION", "4601")])));
+        assert!(!supports_hyperlinks(env(&[("TERM", "xterm-256color")])));
+        assert!(!supports_hyperlinks(env(&[
+            ("TERM", "xterm-kitty"),
+            ("FORCE_HYPERLINK", "0")
+        ])));
+    }
+}
@@ -1,7 +1,30 @@
+pub mod banner;
+pub mod calendar;
 pub mod claude_output;
+pub mod clipboard;
+pub mod costs;
+pub mod debug_overlay;
 pub mod detail;
+pub mod error_modal;
+pub mod event_log;
 pub mod gantt;
+pub mod heatmap;
 pub mod help;
+pub mod highlight;
+pub mod input_modal;
+pub mod lanes;
 pub mod layout;
+pub mod log_viewer;
+pub mod markdown;
+pub mod monitor;
+pub mod next_up;
+pub mod panel;
+pub mod plain;
 pub mod retry_modal;
+pub mod roster;
 pub mod statusbar;
+pub mod stop_modal;
+pub mod testing;
+pub mod timefmt;
+pub mod toast;
+pub mod workload;
@@ -0,0 +1,203 @@
+//! Full-screen view of a single task (`watch --task`)
+//!
+//! ```text
+//! +-------- 60% --------+------ 40% ------+
+//! |                     | Agent activity  |
+//! | Task detail         +-----------------+
+//! |                     | Errors          |
+//! +---------------------+-----------------+
+//! ```
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+
+use crate::analysis::rules::Severity;
+use crate::data::state::{DashboardState, ErrorRecord};
+use crate::ui::detail::{severity_color, DetailWidget};
+use crate::ui::timefmt::ago;
+
+/// The monitored task with its agent and error stream
+pub struct TaskMonitor<'a> {
+    state: &'a DashboardState,
+    task_id: &'a str,
+}
+
+impl<'a> TaskMonitor<'a> {
+    pub fn new(state: &'a DashboardState, task_id: &'a str) -> Self {
+        Self { state, task_id }
+    }
+
+    /// Index in the agents panel order of the agent working on the task
+    fn agent_index(&self) -> Option<usize> {
+        let worker = self
+            .state
+            .task(self.task_id)
+            .and_then(|task| self.state.worker_for(task));
+        let (agents, _) = self.state.panel_agents();
+        agents
+            .iter()
+            .position(|a| Some(a.agent_id.as_str()) == worker)
+            .or_else(|| {
+                agents
+                    .iter()
+                    .position(|a| a.current_task.as_deref() == Some(self.task_id))
+            })
+    }
+
+    /// Every recorded error of the task, newest first, messages in full
+    fn error_lines(&self) -> Vec<Line<'static>> {
+        let now = self.state.now();
+        let errors: Vec<&ErrorRecord> = self
+            .state
+            .recent_errors
+            .iter()
+            .filter(|e| e.task_id == self.task_id)
+            .rev()
+            .collect();
+        if errors.is_empty() {
+            return vec![Line::styled(
+                "No errors",
+                Style::default().fg(Color::DarkGray),
+            )];
+        }
+        let mut lines = Vec::new();
+        for err in errors {
+            let mut style = Style::default().fg(severity_color(err.severity));
+            if err.severity == Severity::Fatal {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            lines.push(Line::from(vec![
+                Span::styled(
+                    self.state.timezone.format(err.timestamp, "%H:%M:%S "),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(format!("{} {}", err.severity, err.category), style),
+                Span::styled(
+                    format!("  {} @{}", ago(err.timestamp, now), err.agent_id),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+            lines.push(Line::styled(
+                format!("  {}", err.headline()),
+                Style::default().fg(Color::White),
+            ));
+            lines.push(Line::styled(
+                format!("  \u{2192} {}", err.display_suggestion()),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines
+    }
+}
+
+impl<'a> Widget for TaskMonitor<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(position) = self.state.task_position(self.task_id) else {
+            let block = Block::default()
+                .title(format!(" {} ", self.task_id))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray));
+            Paragraph::new(Line::styled(
+                format!("{} is not in the plan", self.task_id),
+                Style::default().fg(Color::DarkGray),
+            ))
+            .block(block)
+            .render(area, buf);
+            return;
+        };
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(area);
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(columns[1]);
+
+        DetailWidget::from_selection(self.state, Some(position), 0, true).render(columns[0], buf);
+
+        match self.agent_index() {
+            Some(index) => DetailWidget::from_agent_selection(self.state, index)
+                .with_focused(false)
+                .render(right[0], buf),
+            None => Paragraph::new(Line::styled(
+                "No agent has picked this task up yet",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .block(
+                Block::default()
+                    .title(" Agent ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            )
+            .render(right[0], buf),
+        }
+
+        Paragraph::new(self.error_lines())
+            .block(
+                Block::default()
+                    .title(" Errors ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            )
+            .wrap(Wrap { trim: false })
+            .render(right[1], buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    fn monitored() -> DashboardState {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [InProgress] T1: Risky\n")
+                .unwrap();
+        state.update_from_events(
+            &parse_hook_events(concat!(
+                r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"backend","task_id":"T1","session_id":"s"}"#,
+                "\n",
+                r#"{"event_type":"error","timestamp":"2026-02-08T10:01:00Z","agent_id":"backend","task_id":"T1","session_id":"s","error_message":"connection refused on port 5432"}"#,
+            ))
+            .events,
+        );
+        state
+    }
+
+    fn rendered(state: &DashboardState, task_id: &str) -> String {
+        let area = Rect::new(0, 0, 120, 30);
+        let mut buf = Buffer::empty(area);
+        TaskMonitor::new(state, task_id).render(area, &mut buf);
+        buf.content().iter().map(|c| c.symbol()).collect()
+    }
+
+    #[test]
+    fn shows_task_agent_and_full_error() {
+        let state = monitored();
+        let monitor = TaskMonitor::new(&state, "T1");
+        assert_eq!(monitor.agent_index(), Some(0));
+        let text: String = monitor
+            .error_lines()
+            .iter()
+            .flat_map(|l| l.spans.iter().map(|s| s.content.to_string()))
+            .collect();
+        assert!(text.contains("connection refused on port 5432"), "{text}");
+
+        let screen = rendered(&state, "T1");
+        assert!(screen.contains("Risky"));
+        assert!(screen.contains("backend"));
+    }
+
+    #[test]
+    fn unknown_task_says_so() {
+        let state = monitored();
+        assert!(rendered(&state, "T9").contains("T9 is not in the plan"));
+    }
+}
@@ -0,0 +1,156 @@
+//! "Next up" dispatch overlay
+//!
+//! Lists the tasks ready to start, most urgent first, and offers to start
+//! the top one. Follows the same pattern as `StopModal`.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::data::deps::Suggestion;
+
+/// Ready tasks listed in the overlay
+const MAX_SHOWN: usize = 8;
+
+/// "Next up" overlay widget
+pub struct NextUpOverlay<'a> {
+    pub suggestions: &'a [Suggestion],
+    /// Agent the top task would be handed to, if any
+    pub agent: Option<&'a str>,
+}
+
+impl<'a> NextUpOverlay<'a> {
+    fn centered_rect(&self, area: Rect) -> Rect {
+        let shown = self.suggestions.len().clamp(1, MAX_SHOWN) as u16;
+        let width = 56.min(area.width.saturating_sub(4));
+        let height = (shown + 5).min(area.height.saturating_sub(4));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let Some(top) = self.suggestions.first() else {
+            return vec![
+                Line::styled(
+                    "  No task is ready to start",
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Line::raw(""),
+                Line::from(vec![
+                    Span::styled("  [n]", Style::default().fg(Color::Red)),
+                    Span::raw(" Close"),
+                ]),
+            ];
+        };
+        let mut lines: Vec<Line<'static>> = self
+            .suggestions
+            .iter()
+            .take(MAX_SHOWN)
+            .enumerate()
+            .map(|(i, s)| {
+                let mut impact = vec![format!("chain {}", s.chain)];
+                if s.unblocks > 0 {
+                    impact.push(format!("unblocks {}", s.unblocks));
+                }
+                if let Some(priority) = s.priority {
+                    impact.push(format!("priority {priority}"));
+                }
+                let id_style = if i == 0 {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Cyan)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("  {}. ", i + 1),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(s.task_id.clone(), id_style),
+                    Span::raw(format!(" {}", s.name)),
+                    Span::styled(
+                        format!("  {}", impact.join(" · ")),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])
+            })
+            .collect();
+        let assignee = self.agent.map(|a| format!(" as @{a}")).unwrap_or_default();
+        lines.push(Line::raw(""));
+        lines.push(Line::from(vec![
+            Span::styled("  [y]", Style::default().fg(Color::Green)),
+            Span::raw(format!(" Start {}{assignee}  ", top.task_id)),
+            Span::styled("[n]", Style::default().fg(Color::Red)),
+            Span::raw(" Close"),
+        ]));
+        lines
+    }
+}
+
+impl<'a> Widget for NextUpOverlay<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = self.centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Next up ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green));
+
+        Paragraph::new(self.build_lines())
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggestion(id: &str, chain: usize, unblocks: usize) -> Suggestion {
+        Suggestion {
+            task_id: id.to_string(),
+            name: format!("Task {id}"),
+            chain,
+            unblocks,
+            priority: None,
+            agent: None,
+        }
+    }
+
+    #[test]
+    fn lists_ready_tasks_and_offers_the_top_one() {
+        let suggestions = [suggestion("T2", 2, 3), suggestion("T1", 0, 0)];
+        let overlay = NextUpOverlay {
+            suggestions: &suggestions,
+            agent: Some("builder"),
+        };
+        let text: Vec<String> = overlay
+            .build_lines()
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(text[0], "  1. T2 Task T2  chain 2 · unblocks 3");
+        assert_eq!(text[1], "  2. T1 Task T1  chain 0");
+        assert_eq!(text[3], "  [y] Start T2 as @builder  [n] Close");
+
+        let area = Rect::new(0, 0, 20, 8);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+    }
+
+    #[test]
+    fn empty_overlay_says_nothing_is_ready() {
+        let overlay = NextUpOverlay {
+            suggestions: &[],
+            agent: None,
+        };
+        assert!(overlay.build_lines()[0].to_string().contains("No task"));
+    }
+}
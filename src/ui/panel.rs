@@ -0,0 +1,178 @@
+//! Plugin panels
+//!
+//! Downstream crates add their own panels (say, a cost panel) by implementing
+//! [`Panel`] and registering it with `App::with_panel`. Registered panels are
+//! stacked under the agents panel, join Tab focus cycling after Agents, and
+//! list their keys in the help overlay.
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+
+use crate::data::state::DashboardState;
+
+/// A custom dashboard panel
+pub trait Panel: Send {
+    /// Shown in the panel border
+    fn title(&self) -> &str;
+
+    /// Content for the current state; `width` is the inner width
+    fn build_lines(&self, state: &DashboardState, width: u16) -> Vec<Line<'static>>;
+
+    /// Handle a key while the panel has focus. Return `true` to consume it;
+    /// unconsumed keys fall through to the global bindings.
+    fn handle_key(&mut self, _key: KeyEvent, _state: &DashboardState) -> bool {
+        false
+    }
+
+    /// `(key, description)` pairs for the help overlay
+    fn help(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+/// Registered panels, in focus and display order
+#[derive(Default)]
+pub struct PanelRegistry {
+    panels: Vec<Box<dyn Panel>>,
+}
+
+impl PanelRegistry {
+    pub fn register(&mut self, panel: Box<dyn Panel>) {
+        self.panels.push(panel);
+    }
+
+    pub fn len(&self) -> usize {
+        self.panels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.panels.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&dyn Panel> {
+        self.panels.get(index).map(|p| p.as_ref())
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut (dyn Panel + 'static)> {
+        self.panels.get_mut(index).map(|p| p.as_mut())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Panel> {
+        self.panels.iter().map(|p| p.as_ref())
+    }
+
+    /// Help entries of every panel, each group headed by the panel title
+    pub fn help_entries(&self) -> Vec<(String, String)> {
+        self.iter()
+            .flat_map(|p| {
+                let entries = p.help();
+                let header = (!entries.is_empty()).then(|| (String::new(), p.title().to_string()));
+                header.into_iter().chain(entries)
+            })
+            .collect()
+    }
+}
+
+/// Renders a [`Panel`] in the same frame style as the built-in panels
+pub struct PanelWidget<'a> {
+    panel: &'a dyn Panel,
+    state: &'a DashboardState,
+    focused: bool,
+}
+
+impl<'a> PanelWidget<'a> {
+    pub fn new(panel: &'a dyn Panel, state: &'a DashboardState) -> Self {
+        Self {
+            panel,
+            state,
+            focused: false,
+        }
+    }
+
+    pub fn with_focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+}
+
+impl<'a> Widget for PanelWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_color = if self.focused {
+            Color::Cyan
+        } else {
+            Color::DarkGray
+        };
+        let block = Block::default()
+            .title(format!(" {} ", self.panel.title()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color));
+
+        let lines = self
+            .panel
+            .build_lines(self.state, area.width.saturating_sub(2));
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    /// Counts `+` presses and shows the task total
+    pub(crate) struct Counter(pub usize);
+
+    impl Panel for Counter {
+        fn title(&self) -> &str {
+            "Counter"
+        }
+
+        fn build_lines(&self, state: &DashboardState, _width: u16) -> Vec<Line<'static>> {
+            vec![Line::raw(format!("{} / {}", self.0, state.total_tasks))]
+        }
+
+        fn handle_key(&mut self, key: KeyEvent, _state: &DashboardState) -> bool {
+            let hit = key.code == KeyCode::Char('+');
+            self.0 += usize::from(hit);
+            hit
+        }
+
+        fn help(&self) -> Vec<(String, String)> {
+            vec![("+".to_string(), "Count".to_string())]
+        }
+    }
+
+    #[test]
+    fn registry_renders_and_routes_keys() {
+        let mut registry = PanelRegistry::default();
+        registry.register(Box::new(Counter(0)));
+        let state = DashboardState::default();
+        let plus = KeyEvent::new(KeyCode::Char('+'), KeyModifiers::NONE);
+        assert!(registry.get_mut(0).unwrap().handle_key(plus, &state));
+        let j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert!(!registry.get_mut(0).unwrap().handle_key(j, &state));
+
+        let area = Rect::new(0, 0, 20, 3);
+        let mut buf = Buffer::empty(area);
+        PanelWidget::new(registry.get(0).unwrap(), &state).render(area, &mut buf);
+        let text: String = buf.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("Counter"));
+        assert!(text.contains("1 / 0"));
+        assert_eq!(
+            registry.help_entries(),
+            vec![
+                (String::new(), "Counter".to_string()),
+                ("+".to_string(), "Count".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Plain rendering (`--plain`)
+//!
+//! A last pass over the finished frame for screen readers and for logs
+//! scraped with `tmux capture-pane`: colors and text modifiers are dropped,
+//! decorative glyphs become ASCII, and the highlighted row is prefixed with a
+//! `>` so the selection survives without a background color. Widgets add the
+//! status words colors used to carry; the layout itself is left alone, so
+//! every frame keeps the same line structure.
+
+use ratatui::{buffer::Buffer, style::Color, style::Style};
+
+/// Strip styling and decorative glyphs from a rendered frame
+pub fn flatten(buf: &mut Buffer) {
+    let area = buf.area;
+    for y in area.top()..area.bottom() {
+        mark_selection(buf, y);
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            if let Some(ascii) = ascii(cell.symbol()) {
+                cell.set_symbol(ascii);
+            }
+            cell.set_style(Style::reset());
+        }
+    }
+}
+
+/// Prefix the selection highlight on row `y` (see `gantt::render_lines`)
+/// with `>`, shifting the highlighted text right into the first blank cell
+/// at or past its end so no character is lost
+fn mark_selection(buf: &mut Buffer, y: u16) {
+    let area = buf.area;
+    let highlighted = |buf: &Buffer, x: u16| buf[(x, y)].bg == Color::DarkGray;
+    let Some(start) = (area.left()..area.right()).find(|&x| highlighted(buf, x)) else {
+        return;
+    };
+    let end = (start..area.right())
+        .find(|&x| !highlighted(buf, x))
+        .unwrap_or(area.right());
+    let gap = (end - 1..area.right())
+        .find(|&x| buf[(x, y)].symbol() == " ")
+        .unwrap_or(area.right() - 1);
+    for x in (start + 1..=gap).rev() {
+        buf[(x, y)] = buf[(x - 1, y)].clone();
+    }
+    buf[(start, y)].set_symbol(">");
+}
+
+/// ASCII stand-in for a decorative glyph
+fn ascii(symbol: &str) -> Option<&'static str> {
+    let replacement = match symbol {
+        "\u{2500}" | "\u{2501}" => "-",
+        "\u{2502}" => "|",
+        "\u{250C}" | "\u{2510}" | "\u{2514}" | "\u{2518}" | "\u{251C}" | "\u{2524}" => "+",
+        "\u{2588}" | "\u{2593}" => "#",
+        "\u{2592}" => "+",
+        "\u{2591}" | "\u{00B7}" => ".",
+        "\u{25BC}" | "\u{25BE}" => "v",
+        "\u{25B6}" | "\u{25B8}" => ">",
+        "\u{25C6}" => "*",
+        "\u{2026}" => "~",
+        "\u{2665}" => " ",
+        "\u{26A0}" => "!",
+        "\u{23EA}" | "\u{2190}" => "<",
+        "\u{2192}" => ">",
+        _ => return None,
+    };
+    Some(replacement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Modifier;
+
+    #[test]
+    fn flatten_drops_color_and_glyphs_and_marks_the_selection() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 2));
+        buf.set_string(
+            0,
+            0,
+            "\u{251C}\u{2500} T1",
+            Style::default().fg(Color::Green),
+        );
+        buf.set_string(
+            0,
+            1,
+            " \u{2588}\u{2588}\u{2591} ok",
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        flatten(&mut buf);
+
+        let row = |y: u16| {
+            (0..8)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        };
+        assert_eq!(row(0), "+- T1   ");
+        assert_eq!(row(1), "> ##. ok");
+        assert!((0..8).all(|x| {
+            let cell = &buf[(x, 1)];
+            cell.bg == Color::Reset && cell.modifier.is_empty()
+        }));
+    }
+}
@@ -16,6 +16,8 @@ pub struct RetryModal {
     pub task_id: String,
     pub task_name: String,
     pub retryable: bool,
+    /// Warning shown when TASKS.md changed on disk while the modal was open
+    pub conflict: Option<String>,
 }
 
 impl RetryModal {
@@ -45,6 +47,16 @@ impl RetryModal {
             Line::raw(""),
         ];
 
+        if let Some(ref conflict) = self.conflict {
+            lines.insert(
+                0,
+                Line::styled(
+                    format!("  ⚠ {conflict}"),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ),
+            );
+        }
+
         if self.retryable {
             lines.push(Line::styled(
                 "  Retry this task?",
@@ -99,6 +111,7 @@ mod tests {
             task_id: "P1-R3-T1".to_string(),
             task_name: "File watcher".to_string(),
             retryable: true,
+            conflict: None,
         };
         let area = Rect::new(0, 0, 80, 30);
         let mut buf = Buffer::empty(area);
@@ -111,6 +124,7 @@ mod tests {
             task_id: "P1-R3-T1".to_string(),
             task_name: "File watcher".to_string(),
             retryable: false,
+            conflict: None,
         };
         let area = Rect::new(0, 0, 80, 30);
         let mut buf = Buffer::empty(area);
@@ -123,6 +137,7 @@ mod tests {
             task_id: "T1".to_string(),
             task_name: "Test".to_string(),
             retryable: true,
+            conflict: None,
         };
         let area = Rect::new(0, 0, 20, 8);
         let mut buf = Buffer::empty(area);
@@ -135,6 +150,7 @@ mod tests {
             task_id: "T1".to_string(),
             task_name: "Test".to_string(),
             retryable: true,
+            conflict: None,
         };
         let lines = modal.build_lines();
         let has_yes = lines
@@ -149,6 +165,7 @@ mod tests {
             task_id: "T1".to_string(),
             task_name: "Test".to_string(),
             retryable: false,
+            conflict: None,
         };
         let lines = modal.build_lines();
         let has_warning = lines
@@ -156,4 +173,19 @@ mod tests {
             .any(|l| l.spans.iter().any(|s| s.content.contains("Not retryable")));
         assert!(has_warning);
     }
+
+    #[test]
+    fn conflict_warning_is_first_line() {
+        let modal = RetryModal {
+            task_id: "T1".to_string(),
+            task_name: "Test".to_string(),
+            retryable: true,
+            conflict: Some("T1 changed on disk".to_string()),
+        };
+        let lines = modal.build_lines();
+        assert!(lines[0].spans[0].content.contains("T1 changed on disk"));
+        let area = Rect::new(0, 0, 80, 30);
+        let mut buf = Buffer::empty(area);
+        modal.render(area, &mut buf);
+    }
 }
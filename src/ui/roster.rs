@@ -0,0 +1,185 @@
+//! Roster coverage view
+//!
+//! `w` opens a popup listing the roster agents with their role and load
+//! (in progress / max parallel), followed by the open tasks nobody is
+//! assigned to and the agents working outside the roster.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+use crate::data::roster::{AgentLoad, Coverage};
+
+/// Width of the agent column
+const NAME_WIDTH: usize = 22;
+
+/// Roster coverage widget
+pub struct RosterView<'a> {
+    pub coverage: &'a Coverage,
+}
+
+impl<'a> RosterView<'a> {
+    fn centered_rect(area: Rect) -> Rect {
+        let width = (area.width * 9 / 10).min(80);
+        let height = (area.height * 7 / 10).max(6).min(area.height);
+        let x = area.x + (area.width.saturating_sub(width)) / 2;
+        let y = area.y + (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    fn agent_line(agent: &AgentLoad) -> Line<'static> {
+        let max = agent.max.map_or("-".to_string(), |m| m.to_string());
+        let (flag, color) = if agent.is_over() {
+            ("  OVER", Color::Red)
+        } else if !agent.has_capacity() {
+            ("  full", Color::Yellow)
+        } else {
+            ("", Color::Green)
+        };
+        Line::from(vec![
+            Span::styled(
+                format!(" @{:<width$}", agent.name, width = NAME_WIDTH - 1),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(
+                format!("{:>3}/{max:<3}", agent.active),
+                Style::default().fg(color),
+            ),
+            Span::raw(agent.role.clone().unwrap_or_default()),
+            Span::styled(
+                flag,
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+        ])
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let dim = Style::default().fg(Color::DarkGray);
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let coverage = self.coverage;
+        let mut lines = Vec::new();
+        if coverage.agents.is_empty() {
+            lines.push(Line::styled(
+                " No roster: add [[agents.roster]] to the config or an AGENTS.md",
+                dim,
+            ));
+        } else {
+            lines.push(Line::styled(
+                format!(" {:<NAME_WIDTH$}{:<8}Role", "Agent", "Load"),
+                bold,
+            ));
+            lines.extend(coverage.agents.iter().map(Self::agent_line));
+        }
+
+        lines.push(Line::raw(""));
+        if coverage.unassigned.is_empty() {
+            lines.push(Line::styled(" Every open task has an agent", dim));
+        } else {
+            lines.push(Line::styled(
+                format!(" Unassigned ({})", coverage.unassigned.len()),
+                bold.fg(Color::Yellow),
+            ));
+            for (id, name) in &coverage.unassigned {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("   {id}"), Style::default().fg(Color::Cyan)),
+                    Span::raw(format!(" {name}")),
+                ]));
+            }
+        }
+
+        if !coverage.off_roster.is_empty() {
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(
+                format!(" Not on the roster ({})", coverage.off_roster.len()),
+                bold.fg(Color::Magenta),
+            ));
+            for (agent, tasks) in &coverage.off_roster {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("   @{agent}"), Style::default().fg(Color::Cyan)),
+                    Span::styled(format!(" {}", tasks.join(", ")), dim),
+                ]));
+            }
+        }
+        lines
+    }
+}
+
+impl<'a> Widget for RosterView<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = Self::centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let over = self.coverage.overloaded().count();
+        let title = if over > 0 {
+            format!(" Roster: {over} over limit ")
+        } else {
+            " Roster ".to_string()
+        };
+        let block = Block::default()
+            .title(title)
+            .title_bottom(" w/Esc close ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+
+        Paragraph::new(self.build_lines())
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_load_unassigned_and_off_roster() {
+        let coverage = Coverage {
+            agents: vec![
+                AgentLoad {
+                    name: "backend".to_string(),
+                    role: Some("API".to_string()),
+                    active: 3,
+                    max: Some(2),
+                },
+                AgentLoad {
+                    name: "frontend".to_string(),
+                    role: None,
+                    active: 0,
+                    max: None,
+                },
+            ],
+            unassigned: vec![("T4".to_string(), "Four".to_string())],
+            off_roster: vec![("qa".to_string(), vec!["T3".to_string()])],
+        };
+        let view = RosterView {
+            coverage: &coverage,
+        };
+        let text: Vec<String> = view.build_lines().iter().map(|l| l.to_string()).collect();
+        assert!(text[1].starts_with(" @backend "), "{}", text[1]);
+        assert!(text[1].ends_with("  3/2  API  OVER"), "{}", text[1]);
+        assert!(text[2].contains("  0/-"), "{}", text[2]);
+        assert_eq!(text[4], " Unassigned (1)");
+        assert_eq!(text[5], "   T4 Four");
+        assert_eq!(text[8], "   @qa T3");
+
+        let area = Rect::new(0, 0, 60, 16);
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+    }
+
+    #[test]
+    fn explains_a_missing_roster() {
+        let coverage = Coverage::default();
+        let text = RosterView {
+            coverage: &coverage,
+        }
+        .build_lines();
+        assert!(text[0].to_string().contains("No roster"));
+        assert_eq!(text[2].to_string(), " Every open task has an agent");
+    }
+}
@@ -1,6 +1,9 @@
 //! Status bar widget
 //!
-//! Shows per-status counters, progress %, uptime, and keybinding hints.
+//! Shows per-status counters, progress % with the forecast finish, the run
+//! clock and dashboard uptime, spend against the run budget, and keybinding
+//! hints.
+//! While time-travelling, a badge shows the scrub point.
 
 use std::time::Instant;
 
@@ -12,18 +15,155 @@ use ratatui::{
     widgets::Widget,
 };
 
+use crate::data::cost::{self, BudgetLevel, CostConfig};
+use crate::data::forecast;
+use crate::data::gating::GateReport;
 use crate::data::state::DashboardState;
-use crate::data::tasks_parser::TaskStatus;
+use crate::data::statuses;
+use crate::data::tasks_parser::{ParseError, TaskStatus};
+use crate::data::wip::WipReport;
 
 /// Status bar at the bottom of the screen
 pub struct StatusBar<'a> {
     state: &'a DashboardState,
     start_time: Instant,
+    wip: Option<&'a WipReport>,
+    gates: Option<&'a GateReport>,
+    /// Output of the configured statusbar script
+    segment: Option<&'a str>,
+    /// Counters as words rather than glyphs (see [`crate::ui::plain`])
+    plain: bool,
+    /// The tasks file was removed and has not come back
+    tasks_gone: bool,
+    /// Token prices and budget for the spend badge
+    cost: Option<&'a CostConfig>,
 }
 
 impl<'a> StatusBar<'a> {
     pub fn new(state: &'a DashboardState, start_time: Instant) -> Self {
-        Self { state, start_time }
+        Self {
+            state,
+            start_time,
+            wip: None,
+            gates: None,
+            segment: None,
+            plain: false,
+            tasks_gone: false,
+            cost: None,
+        }
+    }
+
+    pub fn with_wip(mut self, wip: &'a WipReport) -> Self {
+        self.wip = Some(wip);
+        self
+    }
+
+    pub fn with_gates(mut self, gates: &'a GateReport) -> Self {
+        self.gates = Some(gates);
+        self
+    }
+
+    pub fn with_segment(mut self, segment: Option<&'a str>) -> Self {
+        self.segment = segment;
+        self
+    }
+
+    pub fn with_plain(mut self, plain: bool) -> Self {
+        self.plain = plain;
+        self
+    }
+
+    pub fn with_tasks_gone(mut self, gone: bool) -> Self {
+        self.tasks_gone = gone;
+        self
+    }
+
+    pub fn with_cost(mut self, cost: &'a CostConfig) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+
+    /// Spend badge text, " $4.20/$10.00 ", and where it stands against the
+    /// budget; None until there is usage or a budget to show
+    fn spend_badge(&self) -> Option<(String, Option<BudgetLevel>)> {
+        let config = self.cost?;
+        let spend = self.state.total_spend();
+        if spend.is_empty() && config.budget_usd.is_none() {
+            return None;
+        }
+        let spent = spend.usd(config);
+        let level = config.level(spent);
+        let text = match config.budget_usd {
+            Some(budget) => {
+                let over = if level == Some(BudgetLevel::Over) {
+                    " over budget"
+                } else {
+                    ""
+                };
+                format!(
+                    " {}/{}{over} ",
+                    cost::format_usd(spent),
+                    cost::format_usd(budget)
+                )
+            }
+            None => format!(" {} ", cost::format_usd(spent)),
+        };
+        Some((text, level))
+    }
+
+    /// Warning badge text when tasks started inside a locked phase
+    fn gate_badge(&self) -> Option<String> {
+        let gates = self.gates.filter(|g| !g.premature.is_empty())?;
+        let mut ids: Vec<&str> = gates.premature.iter().map(String::as_str).collect();
+        ids.sort();
+        Some(format!(" \u{26A0} GATED {} ", ids.join(", ")))
+    }
+
+    /// Badge text for a scrubbed past state
+    fn scrub_badge(&self) -> Option<String> {
+        let at = self.state.scrub_at?;
+        Some(format!(
+            " \u{23EA} {}  \u{2190}/\u{2192} Esc ",
+            self.state.timezone.format(at, "%H:%M:%S %Z")
+        ))
+    }
+
+    /// Badge text while the board is narrowed by `watch` filter flags
+    fn filter_badge(&self) -> Option<String> {
+        let filter = &self.state.task_filter;
+        (!filter.is_empty()).then(|| format!(" FILTER {} ", filter.describe()))
+    }
+
+    /// Warning badge text when the tasks file had parse problems; shows the
+    /// first one's line
+    fn parse_badge(&self) -> Option<String> {
+        if self.tasks_gone {
+            return Some(" \u{26A0} TASKS file missing ".to_string());
+        }
+        let first = self.state.parse_warnings.first()?;
+        let at = match first {
+            ParseError::Read(_) => String::new(),
+            ParseError::Syntax { line, .. }
+            | ParseError::UnknownStatus { line, .. }
+            | ParseError::MalformedPhase { line, .. }
+            | ParseError::TaskOutsidePhase { line, .. } => format!(" (line {line})"),
+        };
+        let n = self.state.parse_warnings.len();
+        let noun = if n == 1 { "issue" } else { "issues" };
+        Some(format!(" \u{26A0} TASKS {n} {noun}{at} "))
+    }
+
+    /// Warning badge text when a WIP limit is exceeded
+    fn wip_badge(&self) -> Option<String> {
+        let wip = self.wip.filter(|w| w.is_over())?;
+        let mut parts = Vec::new();
+        if let (true, Some(max)) = (wip.over_global(), wip.max) {
+            parts.push(format!("{}/{max}", wip.in_progress));
+        }
+        for (agent, count) in &wip.agents_over {
+            parts.push(format!("@{agent} {count}"));
+        }
+        Some(format!(" \u{26A0} WIP {} ", parts.join(", ")))
     }
 
     /// Count tasks by status across all phases
@@ -31,15 +171,17 @@ impl<'a> StatusBar<'a> {
         let mut completed = 0;
         let mut in_progress = 0;
         let mut failed = 0;
-        let mut rest = 0; // pending + blocked
+        let mut rest = 0; // pending + blocked + custom statuses not done
 
         for phase in &self.state.phases {
             for task in &phase.tasks {
                 match task.status {
-                    TaskStatus::Completed => completed += 1,
+                    _ if statuses::is_done(&task.status, &self.state.custom_statuses) => {
+                        completed += 1
+                    }
                     TaskStatus::InProgress => in_progress += 1,
                     TaskStatus::Failed => failed += 1,
-                    TaskStatus::Pending | TaskStatus::Blocked => rest += 1,
+                    _ => rest += 1,
                 }
             }
         }
@@ -47,14 +189,43 @@ impl<'a> StatusBar<'a> {
         (completed, in_progress, failed, rest)
     }
 
-    /// Format elapsed duration as HH:MM:SS
+    /// Projected finish as " ETA 16:40", with the date when it is not today
+    fn format_eta(&self) -> Option<String> {
+        let finish = forecast::forecast(self.state)?.finish;
+        let zone = self.state.timezone;
+        let format = if zone.format(finish, "%F") == zone.format(self.state.now(), "%F") {
+            "%H:%M"
+        } else {
+            "%m-%d %H:%M"
+        };
+        Some(format!("ETA {}", zone.format(finish, format)))
+    }
+
+    /// Format the dashboard's own uptime as HH:MM:SS
     fn format_uptime(&self) -> String {
-        let elapsed = self.start_time.elapsed().as_secs();
-        let hours = elapsed / 3600;
-        let minutes = (elapsed % 3600) / 60;
-        let seconds = elapsed % 60;
-        format!("{hours:02}:{minutes:02}:{seconds:02}")
+        let uptime = self
+            .state
+            .clock
+            .instant()
+            .saturating_duration_since(self.start_time);
+        format_clock(uptime.as_secs())
     }
+
+    /// Time since the orchestration run started, from the hook events; the
+    /// dashboard is often restarted mid-run, so this can exceed the uptime
+    fn format_run_clock(&self) -> Option<String> {
+        let start = self.state.run_start()?;
+        let elapsed = (self.state.now() - start).num_seconds().max(0);
+        Some(format_clock(elapsed as u64))
+    }
+}
+
+/// Format seconds as HH:MM:SS
+fn format_clock(elapsed: u64) -> String {
+    let hours = elapsed / 3600;
+    let minutes = (elapsed % 3600) / 60;
+    let seconds = elapsed % 60;
+    format!("{hours:02}:{minutes:02}:{seconds:02}")
 }
 
 impl<'a> Widget for StatusBar<'a> {
@@ -63,10 +234,19 @@ impl<'a> Widget for StatusBar<'a> {
         let pct = (self.state.overall_progress * 100.0) as u8;
         let uptime = self.format_uptime();
 
-        let counters =
-            format!(" \u{2714}{completed} \u{25C0}{in_progress} \u{2718}{failed} \u{2298}{rest} ");
-        let progress = format!(" {pct}% ");
-        let uptime_str = format!(" uptime: {uptime} ");
+        let counters = if self.plain {
+            format!(" done {completed} running {in_progress} failed {failed} other {rest} ")
+        } else {
+            format!(" \u{2714}{completed} \u{25C0}{in_progress} \u{2718}{failed} \u{2298}{rest} ")
+        };
+        let progress = match self.format_eta() {
+            Some(eta) => format!(" {pct}% {eta} "),
+            None => format!(" {pct}% "),
+        };
+        let uptime_str = match self.format_run_clock() {
+            Some(run) => format!(" run: {run}  uptime: {uptime} "),
+            None => format!(" uptime: {uptime} "),
+        };
         let hints = " j/k Tab Space v ? q ";
 
         let mut spans = vec![
@@ -86,6 +266,54 @@ impl<'a> Widget for StatusBar<'a> {
                 Style::default().fg(Color::Black).bg(Color::Cyan),
             ),
         ];
+        if let Some(badge) = self.scrub_badge() {
+            spans.push(Span::styled(
+                badge,
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        if let Some(badge) = self.filter_badge() {
+            spans.push(Span::styled(
+                badge,
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        if let Some((badge, level)) = self.spend_badge() {
+            let style = match level {
+                Some(BudgetLevel::Over) => Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+                Some(BudgetLevel::Warning) => Style::default().fg(Color::Black).bg(Color::Yellow),
+                Some(BudgetLevel::Under) => Style::default().fg(Color::Black).bg(Color::Green),
+                None => Style::default().fg(Color::Black).bg(Color::Gray),
+            };
+            spans.push(Span::styled(badge, style));
+        }
+        if let Some(segment) = self.segment {
+            spans.push(Span::styled(
+                format!(" {segment} "),
+                Style::default().fg(Color::Black).bg(Color::Blue),
+            ));
+        }
+        for badge in [self.wip_badge(), self.gate_badge(), self.parse_badge()]
+            .into_iter()
+            .flatten()
+        {
+            spans.push(Span::styled(
+                badge,
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
 
         // Fill remaining width with keybinding hints
         let used_width: usize = spans.iter().map(|s| s.content.len()).sum();
@@ -139,6 +367,116 @@ mod tests {
         assert_eq!(completed + in_progress + failed + rest, state.total_tasks);
     }
 
+    #[test]
+    fn custom_statuses_counted() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [Skipped] T1: A\n### [Review] T2: B\n### [/] T3: C\n",
+        )
+        .unwrap();
+        state.set_custom_statuses(vec![crate::data::statuses::StatusDef {
+            name: "Skipped".to_string(),
+            done: true,
+            ..Default::default()
+        }]);
+        let bar = StatusBar::new(&state, Instant::now());
+        assert_eq!(bar.count_by_status(), (1, 1, 0, 1));
+    }
+
+    #[test]
+    fn gate_badge_lists_premature_tasks() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: A [Blocked]\n### [InProgress] T2: B\n### [InProgress] T1: A\n",
+        )
+        .unwrap();
+        let gates = crate::data::gating::evaluate(&state);
+        let bar = StatusBar::new(&state, Instant::now()).with_gates(&gates);
+        assert_eq!(bar.gate_badge().unwrap(), " \u{26A0} GATED T1, T2 ");
+        assert!(StatusBar::new(&state, Instant::now())
+            .gate_badge()
+            .is_none());
+    }
+
+    #[test]
+    fn scrub_badge_shows_scrub_point() {
+        let mut state = sample_state();
+        state.timezone = crate::data::timezone::DisplayZone::Utc;
+        assert!(StatusBar::new(&state, Instant::now())
+            .scrub_badge()
+            .is_none());
+        state.scrub_at = Some("2026-02-08T10:32:15Z".parse().unwrap());
+        let badge = StatusBar::new(&state, Instant::now())
+            .scrub_badge()
+            .unwrap();
+        assert!(badge.contains("10:32:15 UTC"), "{badge}");
+    }
+
+    #[test]
+    fn filter_badge_describes_the_filter() {
+        let mut state = sample_state();
+        assert!(StatusBar::new(&state, Instant::now())
+            .filter_badge()
+            .is_none());
+        state.set_task_filter(crate::data::filter::TaskFilter {
+            phases: vec!["P1".to_string()],
+            statuses: vec!["failed".to_string()],
+            ..Default::default()
+        });
+        let bar = StatusBar::new(&state, Instant::now());
+        assert_eq!(bar.filter_badge().unwrap(), " FILTER P1 failed ");
+        // Counts still cover the whole plan
+        let unfiltered = sample_state();
+        assert_eq!(
+            bar.count_by_status(),
+            StatusBar::new(&unfiltered, Instant::now()).count_by_status()
+        );
+    }
+
+    #[test]
+    fn parse_badge_points_at_first_problem() {
+        let state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [??] T1: A\n### [!] T2: B\n")
+                .unwrap();
+        let bar = StatusBar::new(&state, Instant::now());
+        assert_eq!(
+            bar.parse_badge().unwrap(),
+            " \u{26A0} TASKS 2 issues (line 2) "
+        );
+        let clean = DashboardState::default();
+        assert!(StatusBar::new(&clean, Instant::now())
+            .parse_badge()
+            .is_none());
+    }
+
+    #[test]
+    fn spend_badge_against_budget() {
+        let mut state = sample_state();
+        let mut config = CostConfig::default();
+        let bar = StatusBar::new(&state, Instant::now()).with_cost(&config);
+        assert_eq!(bar.spend_badge(), None);
+
+        state.update_from_events(&crate::data::hook_parser::parse_hook_events(
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s","usage":{"cost_usd":8.5}}"#,
+        ).events);
+        let bar = StatusBar::new(&state, Instant::now()).with_cost(&config);
+        assert_eq!(bar.spend_badge(), Some((" $8.50 ".to_string(), None)));
+
+        config.budget_usd = Some(10.0);
+        let bar = StatusBar::new(&state, Instant::now()).with_cost(&config);
+        assert_eq!(
+            bar.spend_badge(),
+            Some((" $8.50/$10.00 ".to_string(), Some(BudgetLevel::Warning)))
+        );
+        config.budget_usd = Some(5.0);
+        let bar = StatusBar::new(&state, Instant::now()).with_cost(&config);
+        assert_eq!(
+            bar.spend_badge(),
+            Some((
+                " $8.50/$5.00 over budget ".to_string(),
+                Some(BudgetLevel::Over)
+            ))
+        );
+    }
+
     #[test]
     fn format_uptime_zero() {
         let state = DashboardState::default();
@@ -146,4 +484,116 @@ mod tests {
         let uptime = bar.format_uptime();
         assert_eq!(uptime, "00:00:00");
     }
+
+    #[test]
+    fn run_clock_counts_from_run_start_or_first_event() {
+        use crate::data::hook_parser::parse_hook_events;
+
+        let mut state = DashboardState::default();
+        assert!(StatusBar::new(&state, Instant::now())
+            .format_run_clock()
+            .is_none());
+        state.update_from_events(
+            &parse_hook_events(include_str!(
+                "../../tests/fixtures/sample_hooks/agent_events.jsonl"
+            ))
+            .events,
+        );
+        state.scrub_at = Some("2026-02-08T12:30:05Z".parse().unwrap());
+        let bar = StatusBar::new(&state, Instant::now());
+        assert_eq!(bar.format_run_clock().as_deref(), Some("02:30:05"));
+
+        let run_start = r#"{"event_type":"run_start","timestamp":"2026-02-08T09:00:00Z","agent_id":"main","task_id":"unknown","session_id":"s"}"#;
+        state.update_from_events(&parse_hook_events(run_start).events);
+        let bar = StatusBar::new(&state, Instant::now());
+        assert_eq!(bar.format_run_clock().as_deref(), Some("03:30:05"));
+        assert!(!state.agents.contains_key("main"));
+    }
+
+    #[test]
+    fn clocks_follow_the_state_clock() {
+        use crate::data::clock::{Clock, ManualClock};
+        use std::sync::Arc;
+
+        let clock = Arc::new(ManualClock::new("2026-02-08T10:00:00Z".parse().unwrap()));
+        let mut state = sample_state();
+        state.clock = clock.clone();
+        state.run_started = Some("2026-02-08T09:00:00Z".parse().unwrap());
+        let start = clock.instant();
+
+        clock.advance(chrono::Duration::seconds(3723));
+        let bar = StatusBar::new(&state, start);
+        assert_eq!(bar.format_uptime(), "01:02:03");
+        assert_eq!(bar.format_run_clock().as_deref(), Some("02:02:03"));
+    }
+
+    #[test]
+    fn eta_shows_forecast_finish() {
+        use crate::data::state::TaskTiming;
+        use crate::data::timezone::DisplayZone;
+
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [x] T1: A\n### [ ] T2: B\n")
+                .unwrap();
+        state.timezone = DisplayZone::Utc;
+        state.scrub_at = Some("2026-02-08T16:10:00Z".parse().unwrap());
+        assert!(StatusBar::new(&state, Instant::now())
+            .format_eta()
+            .is_none());
+
+        state.task_times.insert(
+            "T1".into(),
+            TaskTiming {
+                started_at: Some("2026-02-08T15:40:00Z".parse().unwrap()),
+                completed_at: Some("2026-02-08T16:10:00Z".parse().unwrap()),
+            },
+        );
+        let bar = StatusBar::new(&state, Instant::now());
+        assert_eq!(bar.format_eta().as_deref(), Some("ETA 16:40"));
+
+        state.scrub_at = Some("2026-02-08T23:50:00Z".parse().unwrap());
+        let bar = StatusBar::new(&state, Instant::now());
+        assert_eq!(bar.format_eta().as_deref(), Some("ETA 02-09 00:20"));
+    }
+
+    #[test]
+    fn plain_counters_spell_out_statuses() {
+        let state =
+            DashboardState::from_tasks_content("# Phase 1: A\n### [x] T1: A\n### [ ] T2: B\n")
+                .unwrap();
+        let area = Rect::new(0, 0, 80, 1);
+        let mut buf = Buffer::empty(area);
+        StatusBar::new(&state, Instant::now())
+            .with_plain(true)
+            .render(area, &mut buf);
+        let text: String = (0..area.width)
+            .map(|x| buf[(x, 0)].symbol().to_string())
+            .collect();
+        assert!(
+            text.starts_with(" done 1 running 0 failed 0 other 1 "),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn wip_badge_only_when_over() {
+        let state = sample_state();
+        let mut wip = WipReport {
+            in_progress: 2,
+            max: Some(3),
+            ..Default::default()
+        };
+        assert!(StatusBar::new(&state, Instant::now())
+            .with_wip(&wip)
+            .wip_badge()
+            .is_none());
+
+        wip.in_progress = 5;
+        wip.agents_over = vec![("backend".to_string(), 2)];
+        let badge = StatusBar::new(&state, Instant::now())
+            .with_wip(&wip)
+            .wip_badge()
+            .unwrap();
+        assert!(badge.contains("WIP 5/3, @backend 2"));
+    }
 }
@@ -0,0 +1,94 @@
+//! Stop-agent confirmation modal
+//!
+//! Asks before a stop request is sent to an agent. Follows the same pattern
+//! as `RetryModal`.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+/// Stop confirmation modal widget
+pub struct StopModal<'a> {
+    pub agent_id: &'a str,
+    /// Task the agent is working on, if known
+    pub task_id: Option<&'a str>,
+}
+
+impl<'a> StopModal<'a> {
+    fn centered_rect(area: Rect) -> Rect {
+        let width = 40.min(area.width.saturating_sub(4));
+        let height = 9.min(area.height.saturating_sub(4));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        vec![
+            Line::from(vec![
+                Span::styled("  Agent: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    self.agent_id.to_string(),
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("  Task:  ", Style::default().fg(Color::DarkGray)),
+                Span::raw(self.task_id.unwrap_or("-").to_string()),
+            ]),
+            Line::raw(""),
+            Line::styled(
+                "  Ask this agent to stop?",
+                Style::default().fg(Color::Yellow),
+            ),
+            Line::raw(""),
+            Line::from(vec![
+                Span::styled("  [y]", Style::default().fg(Color::Green)),
+                Span::raw(" Yes  "),
+                Span::styled("[n]", Style::default().fg(Color::Red)),
+                Span::raw(" No"),
+            ]),
+        ]
+    }
+}
+
+impl<'a> Widget for StopModal<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = Self::centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Stop agent ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red));
+
+        Paragraph::new(self.build_lines())
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stop_modal_renders_agent_and_prompt() {
+        let modal = StopModal {
+            agent_id: "backend-specialist",
+            task_id: Some("P1-T2"),
+        };
+        let lines = modal.build_lines();
+        assert!(lines[0].to_string().contains("backend-specialist"));
+        assert!(lines[1].to_string().contains("P1-T2"));
+        let area = Rect::new(0, 0, 20, 8);
+        let mut buf = Buffer::empty(area);
+        modal.render(area, &mut buf);
+    }
+}
@@ -0,0 +1,89 @@
+//! Rendered-frame helpers for tests
+//!
+//! Render a widget into an off-screen buffer and read it back as text, one
+//! line per row, so tests (and `insta` snapshots) can assert on what a
+//! frame shows. Lines read like the terminal shows them: the cell a wide
+//! character covers is skipped, and trailing spaces are kept, so every line
+//! of a `width`-wide frame is `width` columns wide.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::Span,
+    widgets::{StatefulWidget, Widget},
+};
+
+/// Text of one buffer row
+pub fn row_text(buf: &Buffer, y: u16) -> String {
+    let area = buf.area;
+    let mut text = String::new();
+    let mut x = area.left();
+    while x < area.right() {
+        let symbol = buf[(x, y)].symbol();
+        text.push_str(symbol);
+        x += (Span::raw(symbol).width() as u16).max(1);
+    }
+    text
+}
+
+/// Text of a whole buffer, every row ending in a newline
+pub fn buffer_text(buf: &Buffer) -> String {
+    let area = buf.area;
+    (area.top()..area.bottom())
+        .map(|y| row_text(buf, y) + "\n")
+        .collect()
+}
+
+/// Render `widget` into a `width` x `height` buffer
+pub fn render<W: Widget>(widget: W, width: u16, height: u16) -> Buffer {
+    let area = Rect::new(0, 0, width, height);
+    let mut buf = Buffer::empty(area);
+    widget.render(area, &mut buf);
+    buf
+}
+
+/// Render `widget` with `state` into a `width` x `height` buffer
+pub fn render_stateful<W: StatefulWidget>(
+    widget: W,
+    state: &mut W::State,
+    width: u16,
+    height: u16,
+) -> Buffer {
+    let area = Rect::new(0, 0, width, height);
+    let mut buf = Buffer::empty(area);
+    widget.render(area, &mut buf, state);
+    buf
+}
+
+/// Text of `widget` rendered into a `width` x `height` frame
+pub fn render_to_string<W: Widget>(widget: W, width: u16, height: u16) -> String {
+    buffer_text(&render(widget, width, height))
+}
+
+/// Text of `widget` rendered with `state` into a `width` x `height` frame
+pub fn render_stateful_to_string<W: StatefulWidget>(
+    widget: W,
+    state: &mut W::State,
+    width: u16,
+    height: u16,
+) -> String {
+    buffer_text(&render_stateful(widget, state, width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::widgets::{Block, Borders, Paragraph};
+
+    #[test]
+    fn renders_a_widget_as_lines_of_cells() {
+        let widget = Paragraph::new("hi 日本").block(Block::default().borders(Borders::ALL));
+        assert_eq!(
+            render_to_string(widget, 10, 3),
+            "┌────────┐\n│hi 日本 │\n└────────┘\n"
+        );
+
+        let buf = render(Paragraph::new("ab\ncd"), 3, 2);
+        assert_eq!(row_text(&buf, 1), "cd ");
+    }
+}
@@ -0,0 +1,71 @@
+//! Human-readable durations
+//!
+//! Shared by the agent and detail panels: [`format_duration`] for tool call
+//! timings, [`humanize`] for coarse spans and [`ago`] for how long ago
+//! something happened.
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Short duration label: `4.2s` under a minute, `3m 05s` above
+pub fn format_duration(duration: Duration) -> String {
+    let ms = duration.num_milliseconds().max(0);
+    if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{}m {:02}s", ms / 60_000, (ms / 1000) % 60)
+    }
+}
+
+/// Coarse span in its two largest units: `45s`, `3m`, `1h 12m`, `2d 3h`
+pub fn humanize(duration: Duration) -> String {
+    let secs = duration.num_seconds().max(0);
+    let (days, hours, mins) = (secs / 86_400, (secs % 86_400) / 3600, (secs % 3600) / 60);
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{mins}m"),
+        3600..=86_399 if mins == 0 => format!("{hours}h"),
+        3600..=86_399 => format!("{hours}h {mins}m"),
+        _ if hours == 0 => format!("{days}d"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
+
+/// How long before `now` an instant was: `3m ago`, or `just now` within
+/// five seconds (and for instants after `now`)
+pub fn ago(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let elapsed = now - at;
+    if elapsed < Duration::seconds(5) {
+        "just now".to_string()
+    } else {
+        format!("{} ago", humanize(elapsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn humanize_uses_two_largest_units() {
+        assert_eq!(humanize(Duration::seconds(45)), "45s");
+        assert_eq!(humanize(Duration::seconds(185)), "3m");
+        assert_eq!(humanize(Duration::minutes(72)), "1h 12m");
+        assert_eq!(humanize(Duration::hours(2)), "2h");
+        assert_eq!(humanize(Duration::hours(51)), "2d 3h");
+        assert_eq!(humanize(Duration::seconds(-3)), "0s");
+    }
+
+    #[test]
+    fn ago_is_relative_to_now() {
+        let now = Utc::now();
+        assert_eq!(ago(now - Duration::minutes(3), now), "3m ago");
+        assert_eq!(ago(now - Duration::seconds(2), now), "just now");
+        assert_eq!(ago(now + Duration::minutes(1), now), "just now");
+    }
+
+    #[test]
+    fn format_duration_switches_to_minutes() {
+        assert_eq!(format_duration(Duration::milliseconds(4200)), "4.2s");
+        assert_eq!(format_duration(Duration::seconds(185)), "3m 05s");
+    }
+}
@@ -0,0 +1,100 @@
+//! Toast notifications
+//!
+//! A one-line box in the bottom-right corner that confirms something the
+//! board did on its own (a config reload) or reports why it could not.
+//! Errors stay up longer than confirmations.
+
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+/// How long a confirmation stays up
+const INFO_TTL: Duration = Duration::from_secs(3);
+
+/// How long an error stays up
+const ERROR_TTL: Duration = Duration::from_secs(10);
+
+/// A message shown for a few seconds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    pub message: String,
+    pub error: bool,
+    pub shown_at: Instant,
+}
+
+impl Toast {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            error: false,
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            error: true,
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub fn expired(&self, now: Instant) -> bool {
+        let ttl = if self.error { ERROR_TTL } else { INFO_TTL };
+        now.saturating_duration_since(self.shown_at) >= ttl
+    }
+
+    /// Box above the status bar in the bottom-right corner
+    fn corner_rect(&self, area: Rect) -> Rect {
+        let width = (self.message.chars().count() as u16 + 4).min(area.width);
+        let height = 3.min(area.height);
+        let y = area.bottom().saturating_sub(height + 1).max(area.y);
+        Rect::new(area.right() - width, y, width, height)
+    }
+}
+
+impl Widget for &Toast {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = self.corner_rect(area);
+        Clear.render(popup_area, buf);
+
+        let color = if self.error { Color::Red } else { Color::Green };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(color));
+        Paragraph::new(format!(" {}", self.message))
+            .style(Style::default().fg(color))
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_outlive_confirmations() {
+        let info = Toast::info("Config reloaded");
+        let error = Toast::error("bad config");
+        let later = info.shown_at + Duration::from_secs(5);
+        assert!(info.expired(later));
+        assert!(!error.expired(later));
+    }
+
+    #[test]
+    fn toast_sits_above_the_status_bar() {
+        let toast = Toast::info("Config reloaded");
+        let area = Rect::new(0, 0, 80, 24);
+        assert_eq!(toast.corner_rect(area), Rect::new(61, 20, 19, 3));
+        let mut buf = Buffer::empty(area);
+        (&toast).render(area, &mut buf);
+        assert_eq!(buf[(62, 21)].symbol(), " ");
+        assert_eq!(buf[(63, 21)].symbol(), "C");
+    }
+}
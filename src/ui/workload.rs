@@ -0,0 +1,239 @@
+//! Workload balance
+//!
+//! Open (not done) tasks per TASKS.md `@agent`, with their summed `points`,
+//! as one horizontal bar per agent. Bars measure points when the plan has
+//! estimates and task counts otherwise; an agent carrying more than half
+//! again the average load is drawn in red, so lopsided assignments show up
+//! before the run starts.
+
+use std::collections::HashMap;
+
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+use crate::data::state::DashboardState;
+use crate::data::statuses;
+
+/// Width of the agent column
+const NAME_WIDTH: usize = 20;
+
+/// Load above this multiple of the average is flagged
+const HEAVY: f64 = 1.5;
+
+/// Open work assigned to one agent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentWorkload {
+    /// `None` for tasks without an `@agent`
+    pub agent: Option<String>,
+    pub tasks: usize,
+    /// Summed `points` of the tasks that have them
+    pub points: u64,
+    /// Tasks without `points`
+    pub unestimated: usize,
+}
+
+/// Open work per agent among the shown tasks, heaviest first, unassigned
+/// tasks last
+pub fn workloads(state: &DashboardState) -> Vec<AgentWorkload> {
+    let mut by_agent: HashMap<Option<&str>, AgentWorkload> = HashMap::new();
+    let open = state
+        .shown_phases()
+        .flat_map(|(_, p)| state.shown_tasks(p))
+        .map(|(_, t)| t)
+        .filter(|t| !statuses::is_done(&t.status, &state.custom_statuses));
+    for task in open {
+        let agent = task.agent.as_deref().map(|a| a.trim_start_matches('@'));
+        let load = by_agent.entry(agent).or_insert_with(|| AgentWorkload {
+            agent: agent.map(str::to_string),
+            tasks: 0,
+            points: 0,
+            unestimated: 0,
+        });
+        load.tasks += 1;
+        match task.points {
+            Some(points) => load.points = load.points.saturating_add(u64::from(points)),
+            None => load.unestimated += 1,
+        }
+    }
+    let mut loads: Vec<AgentWorkload> = by_agent.into_values().collect();
+    loads.sort_by(|a, b| {
+        a.agent
+            .is_none()
+            .cmp(&b.agent.is_none())
+            .then(b.points.cmp(&a.points))
+            .then(b.tasks.cmp(&a.tasks))
+            .then(a.agent.cmp(&b.agent))
+    });
+    loads
+}
+
+/// Header, one bar per agent and a balance summary. `width` is the inner
+/// width of the panel.
+pub fn build_workload_lines(state: &DashboardState, width: usize) -> Vec<Line<'static>> {
+    let loads = workloads(state);
+    if loads.is_empty() {
+        return vec![Line::raw("  No open tasks")];
+    }
+    let by_points = loads.iter().any(|l| l.points > 0);
+    let measure = |l: &AgentWorkload| {
+        if by_points {
+            l.points
+        } else {
+            l.tasks as u64
+        }
+    };
+    let assigned: Vec<&AgentWorkload> = loads.iter().filter(|l| l.agent.is_some()).collect();
+    let average = if assigned.is_empty() {
+        0.0
+    } else {
+        assigned.iter().map(|l| measure(l) as f64).sum::<f64>() / assigned.len() as f64
+    };
+    let max = loads.iter().map(measure).max().unwrap_or(0).max(1);
+    let bar_width = width.saturating_sub(NAME_WIDTH + 12).max(1);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![Line::styled(
+        format!(
+            " {:<w$}{:>5} {:>5}",
+            "Agent",
+            "Tasks",
+            "Pts",
+            w = NAME_WIDTH - 1
+        ),
+        dim,
+    )];
+    for load in &loads {
+        let (name, color) = match &load.agent {
+            Some(agent) if average > 0.0 && measure(load) as f64 > average * HEAVY => {
+                (format!("@{agent}"), Color::Red)
+            }
+            Some(agent) => (format!("@{agent}"), Color::Green),
+            None => ("(unassigned)".to_string(), Color::DarkGray),
+        };
+        let name: String = name.chars().take(NAME_WIDTH - 2).collect();
+        let points = match (load.points, load.unestimated) {
+            (0, _) => "-".to_string(),
+            (p, 0) => p.to_string(),
+            (p, _) => format!("{p}+"),
+        };
+        let filled = (measure(load) as f64 / max as f64 * bar_width as f64).ceil() as usize;
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {name:<w$}", w = NAME_WIDTH - 1),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw(format!("{:>5} {points:>5} ", load.tasks)),
+            Span::styled("\u{2588}".repeat(filled), Style::default().fg(color)),
+        ]));
+    }
+
+    let unit = if by_points { "pts" } else { "tasks" };
+    let summary = match assigned.first() {
+        Some(top) if average > 0.0 => format!(
+            " Average {average:.1} {unit}; @{} carries {:.1}x",
+            top.agent.as_deref().unwrap_or_default(),
+            measure(top) as f64 / average
+        ),
+        _ => " No task has an @agent yet".to_string(),
+    };
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(summary, dim));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAN: &str = "\
+# Phase 1: Core
+
+### [ ] T1: One
+- @backend
+- **points**: 8
+
+### [InProgress] T2: Two
+- @backend
+- **points**: 5
+
+### [ ] T3: Three
+- @backend
+
+### [ ] T4: Four
+- @frontend
+- **points**: 2
+
+### [x] T5: Five
+- @frontend
+- **points**: 13
+
+### [ ] T6: Six
+";
+
+    #[test]
+    fn sums_open_tasks_and_points_per_agent() {
+        let state = DashboardState::from_tasks_content(PLAN).unwrap();
+        let loads = workloads(&state);
+        assert_eq!(
+            loads,
+            vec![
+                AgentWorkload {
+                    agent: Some("backend".to_string()),
+                    tasks: 3,
+                    points: 13,
+                    unestimated: 1,
+                },
+                AgentWorkload {
+                    agent: Some("frontend".to_string()),
+                    tasks: 1,
+                    points: 2,
+                    unestimated: 0,
+                },
+                AgentWorkload {
+                    agent: None,
+                    tasks: 1,
+                    points: 0,
+                    unestimated: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn bars_flag_the_heavy_agent() {
+        let state = DashboardState::from_tasks_content(PLAN).unwrap();
+        let lines = build_workload_lines(&state, 52);
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        assert!(text[1].starts_with(" @backend "), "{}", text[1]);
+        assert!(text[1].contains("    3   13+ "), "{}", text[1]);
+        // 13 of 13 points fill the 20 columns left for bars
+        assert_eq!(lines[1].spans[2].content.chars().count(), 20);
+        assert_eq!(lines[1].spans[2].style.fg, Some(Color::Red));
+        assert_eq!(lines[2].spans[2].style.fg, Some(Color::Green));
+        assert!(text[3].starts_with(" (unassigned)"));
+        assert_eq!(text[5], " Average 7.5 pts; @backend carries 1.7x");
+    }
+
+    #[test]
+    fn huge_estimates_do_not_overflow() {
+        let mut state = DashboardState::from_tasks_content(PLAN).unwrap();
+        for task in state.phases.iter_mut().flat_map(|p| &mut p.tasks) {
+            task.points = Some(u32::MAX);
+        }
+        let loads = workloads(&state);
+        assert_eq!(loads[0].points, 3 * u64::from(u32::MAX));
+        let lines = build_workload_lines(&state, 52);
+        assert_eq!(lines[1].spans[2].content.chars().count(), 20);
+    }
+
+    #[test]
+    fn empty_plan_says_so() {
+        let state = DashboardState::default();
+        assert_eq!(
+            build_workload_lines(&state, 40)[0].to_string(),
+            "  No open tasks"
+        );
+    }
+}
@@ -0,0 +1,1360 @@
+//! Message/effect API over [`App`]
+//!
+//! [`App::update`] applies a [`Msg`] to the state and hands back the writes it
+//! wants as [`Effect`]s instead of touching the tasks file, so a host TUI can
+//! run, batch, or drop them, and key sequences can be tested without a file.
+//! Outside calls (triage lookups, Jira and GitHub requests, the budget
+//! webhook and prompt commands) come back as effects too.
+//! [`App::perform`] carries an effect out the way the dashboard does.
+
+use std::path::PathBuf;
+
+use crossterm::event::KeyEvent;
+
+use crate::app::App;
+use crate::data::audit::{self, AuditEntry, TaskSnapshot};
+use crate::data::cost;
+use crate::data::loader::Loaded;
+use crate::data::tasks_doc::{MoveDirection, TasksDoc};
+use crate::data::tasks_parser::TaskStatus;
+use crate::data::tasks_writer;
+use crate::data::watcher::FileChange;
+use crate::event::{key_to_action, key_to_input, Action, InputKey};
+use crate::ui::layout::{FocusedPane, LayoutPreset};
+
+/// Something that happened to the dashboard
+#[derive(Debug)]
+pub enum Msg {
+    /// A key press, routed to the open modal or mapped to an [`Action`]
+    Key(KeyEvent),
+    /// An already-mapped action
+    Action(Action),
+    /// A key for the open text input
+    Input(InputKey),
+    /// A watched file changed. Unlike key messages this reads the files
+    /// directly, or queues the read on the loader; the retry policy's writes
+    /// and triage lookups come back as effects.
+    FileChanged(FileChange),
+    /// A file the loader finished reading
+    Loaded(Loaded),
+    /// Periodic tick: pick up finished triage lookups and consumed stop
+    /// requests, and plan retries whose rate-limit cooldown has passed, due
+    /// Jira and GitHub refreshes and the budget alert
+    Tick,
+}
+
+impl Msg {
+    /// Short name for profiles: "key", "action", "input", "file", "load" or
+    /// "tick"
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Msg::Key(_) => "key",
+            Msg::Action(_) => "action",
+            Msg::Input(_) => "input",
+            Msg::FileChanged(_) => "file",
+            Msg::Loaded(_) => "load",
+            Msg::Tick => "tick",
+        }
+    }
+}
+
+/// A write or outside call requested by [`App::update`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+    /// Flip a Failed/Blocked task back to InProgress and count the attempt
+    Retry { task_id: String },
+    /// Park a Failed task that used up its retry budget as Blocked
+    Block { task_id: String },
+    /// Confirm a task held for review as Completed
+    Approve { task_id: String },
+    /// Send a task held for review back to InProgress with a note
+    Bounce { task_id: String, note: String },
+    /// Append a note to a task body
+    AppendNote { task_id: String, note: String },
+    /// Swap a task with its neighbour in the phase
+    MoveTask {
+        task_id: String,
+        direction: MoveDirection,
+    },
+    /// Move a task under another phase
+    MoveToPhase { task_id: String, phase_id: String },
+    /// Append a new pending task to a phase, `body` lines under its header
+    AddTask {
+        phase_id: String,
+        task_id: String,
+        name: String,
+        body: Vec<String>,
+    },
+    /// Start a ready task: InProgress, assigned to `agent` when given
+    Dispatch {
+        task_id: String,
+        agent: Option<String>,
+    },
+    /// Ask an agent to stop through the control channel (not a tasks write)
+    StopAgent {
+        agent_id: String,
+        session_id: Option<String>,
+    },
+    /// Run the triage command for an unclassified error message
+    Triage { message: String },
+    /// Fetch a linked Jira issue's status and assignee
+    JiraRefresh { key: String },
+    /// Move the Jira issue of a completed task through the done transition
+    JiraComplete { key: String },
+    /// List pull requests, including those the plan names by number
+    GithubRefresh { numbers: Vec<u32> },
+    /// Post the over-budget alert to the cost webhook
+    BudgetAlert {
+        url: String,
+        spent: f64,
+        budget: f64,
+    },
+    /// Pipe a rendered prompt to the `[prompt]` command
+    PipePrompt {
+        task_id: String,
+        command: Vec<String>,
+        text: String,
+    },
+}
+
+impl Effect {
+    /// Name recorded in the audit log
+    pub fn action_name(&self) -> &'static str {
+        match self {
+            Effect::Retry { .. } => "retry",
+            Effect::Block { .. } => "block",
+            Effect::Approve { .. } => "approve",
+            Effect::Bounce { .. } => "bounce",
+            Effect::AppendNote { .. } => "note",
+            Effect::MoveTask {
+                direction: MoveDirection::Up,
+                ..
+            } => "move-up",
+            Effect::MoveTask {
+                direction: MoveDirection::Down,
+                ..
+            } => "move-down",
+            Effect::MoveToPhase { .. } => "move-to-phase",
+            Effect::AddTask { .. } => "add-task",
+            Effect::Dispatch { .. } => "dispatch",
+            Effect::StopAgent { .. } => "stop-agent",
+            Effect::Triage { .. } => "triage",
+            Effect::JiraRefresh { .. } => "jira-refresh",
+            Effect::JiraComplete { .. } => "jira-complete",
+            Effect::GithubRefresh { .. } => "github-refresh",
+            Effect::BudgetAlert { .. } => "budget-alert",
+            Effect::PipePrompt { .. } => "pipe-prompt",
+        }
+    }
+
+    /// Task the effect writes to or is about, or the agent for a stop
+    /// request; None for outside calls about no task
+    pub fn task_id(&self) -> Option<&str> {
+        let id = match self {
+            Effect::Retry { task_id }
+            | Effect::Block { task_id }
+            | Effect::Approve { task_id }
+            | Effect::Bounce { task_id, .. }
+            | Effect::AppendNote { task_id, .. }
+            | Effect::MoveTask { task_id, .. }
+            | Effect::MoveToPhase { task_id, .. }
+            | Effect::AddTask { task_id, .. }
+            | Effect::Dispatch { task_id, .. }
+            | Effect::PipePrompt { task_id, .. } => task_id,
+            Effect::StopAgent { agent_id, .. } => agent_id,
+            Effect::Triage { .. }
+            | Effect::JiraRefresh { .. }
+            | Effect::JiraComplete { .. }
+            | Effect::GithubRefresh { .. }
+            | Effect::BudgetAlert { .. } => return None,
+        };
+        Some(id)
+    }
+}
+
+impl App {
+    /// Apply a message to the state; file writes and outside calls come
+    /// back as effects, along with any left in the outbox
+    pub fn update(&mut self, msg: Msg) -> Vec<Effect> {
+        let mut effects = self.update_state(msg);
+        effects.append(&mut self.outbox);
+        effects
+    }
+
+    fn update_state(&mut self, msg: Msg) -> Vec<Effect> {
+        match msg {
+            // A finished load was counted when its change came in
+            Msg::Tick | Msg::Loaded(_) => {}
+            Msg::FileChanged(_) => self.record_activity(true),
+            _ => self.record_activity(false),
+        }
+        match msg {
+            Msg::Key(key) if self.input.is_some() => self.plan_input(key_to_input(key)),
+            Msg::Key(key)
+                if !self.show_retry_modal
+                    && self.error_view.is_none()
+                    && !self.show_next_up
+                    && !self.show_costs
+                    && !self.show_roster
+                    && self.stop_target.is_none()
+                    && self.panel_key(key) =>
+            {
+                Vec::new()
+            }
+            Msg::Key(key) => self.update_action(key_to_action(key)),
+            Msg::Action(action) => self.update_action(action),
+            Msg::Input(key) => self.plan_input(key),
+            Msg::FileChanged(change) => {
+                self.handle_file_change(&change);
+                self.refresh_scrub();
+                self.plan_retry_policy()
+            }
+            Msg::Loaded(loaded) => {
+                self.apply_loaded(loaded);
+                self.refresh_scrub();
+                self.plan_retry_policy()
+            }
+            Msg::Tick => {
+                self.poll_triage();
+                self.refresh_log();
+                self.check_tasks_missing(self.dashboard.clock.instant());
+                let now = self.dashboard.clock.instant();
+                let mut effects = self.poll_jira(now);
+                effects.extend(self.poll_github(now));
+                self.poll_otel();
+                effects.extend(self.check_budget());
+                if self
+                    .toast
+                    .as_ref()
+                    .is_some_and(|t| t.expired(std::time::Instant::now()))
+                {
+                    self.toast = None;
+                }
+                effects.extend(self.check_retry_wait(self.dashboard.clock.now()));
+                effects
+            }
+        }
+    }
+
+    /// Offer a key to the focused plugin panel; `true` if it took it
+    fn panel_key(&mut self, key: KeyEvent) -> bool {
+        let FocusedPane::Panel(i) = self.focused else {
+            return false;
+        };
+        self.panels
+            .get_mut(i)
+            .is_some_and(|panel| panel.handle_key(key, &self.dashboard))
+    }
+
+    fn update_action(&mut self, action: Action) -> Vec<Effect> {
+        if action == Action::ToggleDebug {
+            // Works over modals and while scrubbing
+            self.show_debug = !self.show_debug;
+            return Vec::new();
+        }
+        if self.stop_target.is_some() {
+            match action {
+                Action::Confirm => return self.plan_stop(),
+                Action::Cancel | Action::Quit => self.cancel_stop(),
+                _ => {}
+            }
+            return Vec::new();
+        }
+        if self.show_log {
+            if matches!(action, Action::Cancel | Action::Quit | Action::ToggleLog) {
+                self.toggle_log();
+            }
+            return Vec::new();
+        }
+        if self.show_costs {
+            match action {
+                Action::Cancel | Action::Quit | Action::ToggleCosts => self.toggle_costs(),
+                Action::ToggleFocus => self.cycle_cost_group(),
+                Action::ScrubBack => self.cost_sort = self.cost_sort.step(-1),
+                Action::ScrubForward => self.cost_sort = self.cost_sort.step(1),
+                Action::MoveDown => self.scroll_costs(1),
+                Action::MoveUp => self.scroll_costs(-1),
+                _ => {}
+            }
+            return Vec::new();
+        }
+        if self.error_view.is_some() {
+            match action {
+                Action::Cancel | Action::Quit | Action::ShowError => self.close_error_modal(),
+                Action::MoveDown => self.move_error_cursor(1),
+                Action::MoveUp => self.move_error_cursor(-1),
+                Action::ToggleCollapse | Action::FollowLink => self.toggle_error_frame(),
+                Action::JumpToContext => self.jump_to_error_context(),
+                _ => {}
+            }
+            return Vec::new();
+        }
+        if self.event_anchor.is_some() {
+            // The anchored log takes j/k; Esc/q return it to live
+            match action {
+                Action::MoveDown => {
+                    self.step_event_anchor(1);
+                    return Vec::new();
+                }
+                Action::MoveUp => {
+                    self.step_event_anchor(-1);
+                    return Vec::new();
+                }
+                Action::Cancel | Action::Quit => {
+                    self.leave_event_anchor();
+                    return Vec::new();
+                }
+                // The log shows the live events only
+                Action::ToggleLayout | Action::ScrubBack | Action::ScrubForward => {
+                    self.leave_event_anchor()
+                }
+                _ => {}
+            }
+        }
+        if self.show_roster {
+            if matches!(action, Action::Cancel | Action::Quit | Action::ToggleRoster) {
+                self.toggle_roster();
+            }
+            return Vec::new();
+        }
+        if self.show_next_up {
+            match action {
+                Action::Confirm => return self.plan_dispatch(),
+                Action::Cancel | Action::Quit | Action::NextUp => self.toggle_next_up(),
+                _ => {}
+            }
+            return Vec::new();
+        }
+        if self.show_retry_modal {
+            // Modal takes priority: only y/n/q/Esc; a non-retryable target
+            // closes on any key
+            let retryable = self.retry_target.as_ref().is_some_and(|t| t.retryable);
+            match action {
+                Action::Confirm if retryable => return self.plan_retry(),
+                Action::Cancel | Action::Quit => self.cancel_retry(),
+                _ if !retryable => self.cancel_retry(),
+                _ => {}
+            }
+            return Vec::new();
+        }
+        if self.scrub.is_some() {
+            // The past is read-only: Esc/q returns to live, writes are ignored
+            match action {
+                Action::Quit | Action::Cancel => {
+                    self.stop_scrub();
+                    return Vec::new();
+                }
+                Action::RetryRequest
+                | Action::DismissBanner
+                | Action::Approve
+                | Action::Bounce
+                | Action::AddNote
+                | Action::MoveTaskUp
+                | Action::MoveTaskDown
+                | Action::MoveToPhase
+                | Action::QuickAdd
+                | Action::StopAgent
+                | Action::JumpToContext
+                | Action::NextUp => return Vec::new(),
+                _ => {}
+            }
+        }
+        if self.monitor.is_some()
+            && matches!(
+                action,
+                Action::MoveDown
+                    | Action::MoveUp
+                    | Action::ToggleFocus
+                    | Action::ToggleCollapse
+                    | Action::ToggleView
+                    | Action::ToggleLayout
+                    | Action::CycleLineNumbers
+                    | Action::FollowLink
+                    | Action::CopyTable
+                    | Action::JumpToContext
+            )
+        {
+            // Only the monitored task is on screen; there is no board to move on
+            return Vec::new();
+        }
+        match action {
+            Action::Quit => self.quit(),
+            Action::MoveDown => match self.focused {
+                FocusedPane::Agents => self.agent_move_down(),
+                _ => self.move_down(),
+            },
+            Action::MoveUp => match self.focused {
+                FocusedPane::Agents => self.agent_move_up(),
+                _ => self.move_up(),
+            },
+            // The ops layout has no other pane to move to
+            Action::ToggleFocus if self.layout == LayoutPreset::Ops => {}
+            Action::ToggleFocus => self.toggle_focus(),
+            Action::ToggleHelp => self.toggle_help(),
+            Action::ToggleCollapse => self.toggle_collapse(),
+            Action::ToggleView => self.toggle_view(),
+            Action::ToggleLayout => self.toggle_layout(),
+            Action::CycleLineNumbers => self.gantt_state.cycle_line_numbers(),
+            Action::RetryRequest => self.open_retry_modal(),
+            Action::ShowError => self.open_error_modal(),
+            Action::JumpToContext => self.jump_to_error_context(),
+            Action::DismissBanner => self.dismiss_banner(),
+            Action::Approve => return self.plan_approve(),
+            Action::Bounce => return self.plan_bounce(),
+            Action::AddNote => self.open_note_input(),
+            Action::MoveTaskUp => return self.plan_move(MoveDirection::Up),
+            Action::MoveTaskDown => return self.plan_move(MoveDirection::Down),
+            Action::MoveToPhase => self.open_move_phase_input(),
+            Action::QuickAdd => self.open_add_task_input(),
+            Action::StopAgent => self.open_stop_modal(),
+            Action::FollowLink => self.follow_link(),
+            Action::NextUp => self.toggle_next_up(),
+            Action::ToggleLog => self.toggle_log(),
+            Action::CopyContext => self.copy_context(),
+            Action::CopyTable => self.copy_table(),
+            Action::SendPrompt => return self.plan_prompt(),
+            Action::ExportTrace => self.export_trace(),
+            Action::ToggleCosts => self.toggle_costs(),
+            Action::ToggleRoster => self.toggle_roster(),
+            Action::ScrubBack => self.scrub_by(-1),
+            Action::ScrubForward => self.scrub_by(1),
+            Action::ToggleDebug | Action::Confirm | Action::Cancel | Action::None => {}
+        }
+        Vec::new()
+    }
+
+    /// Write an effect to the tasks file and reload it, or make the outside
+    /// call it asks for. Writes are dropped when the tasks file is missing or
+    /// read-only.
+    pub fn perform(&mut self, effect: Effect) {
+        tracing::info!(?effect, "write-back");
+        let effect = match effect {
+            Effect::StopAgent {
+                agent_id,
+                session_id,
+            } => {
+                if self.stop_agent(&agent_id, session_id.as_deref()) {
+                    self.audit(AuditEntry::new("stop-agent", &agent_id, None, None));
+                }
+                return;
+            }
+            Effect::Triage { message } => {
+                if let Some(triage) = &self.triage {
+                    triage.spawn(message);
+                }
+                return;
+            }
+            Effect::JiraRefresh { key } => {
+                if let Some(jira) = &self.jira {
+                    jira.fetch(key);
+                }
+                return;
+            }
+            Effect::JiraComplete { key } => {
+                if let Some(jira) = &self.jira {
+                    jira.complete(&key);
+                }
+                return;
+            }
+            Effect::GithubRefresh { numbers } => {
+                if let Some(github) = &self.github {
+                    github.list(numbers);
+                }
+                return;
+            }
+            Effect::BudgetAlert { url, spent, budget } => {
+                cost::send_alert(&url, spent, budget);
+                return;
+            }
+            Effect::PipePrompt {
+                task_id,
+                command,
+                text,
+            } => {
+                self.pipe_prompt(&task_id, &command, &text);
+                return;
+            }
+            effect => effect,
+        };
+        let Some(path) = self.writable_tasks_path() else {
+            return;
+        };
+        let action = effect.action_name();
+        let task_id = effect.task_id().unwrap_or_default().to_string();
+        let old = self.task_snapshot(&task_id);
+        self.apply(effect, path);
+        let new = self.task_snapshot(&task_id);
+        if old != new {
+            self.audit(AuditEntry::new(action, &task_id, old, new));
+        }
+    }
+
+    /// Task as it is on disk now, for the audit log
+    fn task_snapshot(&self, task_id: &str) -> Option<TaskSnapshot> {
+        self.audit_path.as_ref()?;
+        let content = std::fs::read_to_string(self.tasks_path.as_ref()?).ok()?;
+        TaskSnapshot::capture(&TasksDoc::parse(&content), task_id)
+    }
+
+    pub(crate) fn audit(&self, entry: AuditEntry) {
+        if let Some(path) = &self.audit_path {
+            if let Err(e) = audit::append(path, &entry) {
+                tracing::warn!("cannot write audit log {}: {e}", path.display());
+            }
+        }
+    }
+
+    fn apply(&mut self, effect: Effect, path: PathBuf) {
+        match effect {
+            Effect::Retry { task_id } => {
+                // An external edit may have removed the task or moved it on
+                let still_retryable = self
+                    .disk_task(&task_id)
+                    .and_then(|t| t.status())
+                    .is_some_and(|s| matches!(s, TaskStatus::Failed | TaskStatus::Blocked));
+                if !still_retryable {
+                    return;
+                }
+                if let Ok(Some(_)) = tasks_writer::retry_task(&path, &task_id) {
+                    let category = self
+                        .dashboard
+                        .recent_errors
+                        .iter()
+                        .rfind(|e| e.task_id == task_id)
+                        .map(|e| e.category.clone());
+                    let budget = category
+                        .as_ref()
+                        .and_then(|c| self.config.retry.budget_for(c));
+                    self.dashboard
+                        .retries
+                        .record_attempt(&task_id, category, budget);
+                    self.reload_from(&path);
+                }
+            }
+            Effect::Block { task_id } => {
+                let still_failed = self
+                    .disk_task(&task_id)
+                    .and_then(|t| t.status())
+                    .is_some_and(|s| s == TaskStatus::Failed);
+                if !still_failed {
+                    return;
+                }
+                let attempts = self.retry_attempts(&task_id);
+                if let Ok(true) = tasks_writer::update_task_status(&path, &task_id, "Blocked") {
+                    if let Some(category) = self
+                        .dashboard
+                        .recent_errors
+                        .iter()
+                        .rfind(|e| e.task_id == task_id)
+                        .map(|e| e.category.clone())
+                    {
+                        let budget = self.config.retry.budget_for(&category);
+                        self.dashboard.retries.mark_exhausted(
+                            &task_id,
+                            category,
+                            budget.unwrap_or(attempts),
+                        );
+                    }
+                    self.reload_from(&path);
+                }
+            }
+            Effect::Approve { task_id } => {
+                if let Ok(true) = tasks_writer::update_task_status(&path, &task_id, "x") {
+                    self.dashboard.awaiting_review.remove(task_id.as_str());
+                    self.reload_from(&path);
+                    self.complete_jira(&task_id);
+                }
+            }
+            Effect::Bounce { task_id, note } => {
+                if let Ok(true) = tasks_writer::update_task_status(&path, &task_id, "InProgress") {
+                    let _ = tasks_writer::append_task_note(&path, &task_id, &note);
+                    self.dashboard.awaiting_review.remove(task_id.as_str());
+                    self.reload_from(&path);
+                }
+            }
+            Effect::AppendNote { task_id, note } => {
+                if let Ok(true) = tasks_writer::append_task_note(&path, &task_id, &note) {
+                    self.reload_from(&path);
+                }
+            }
+            Effect::MoveTask { task_id, direction } => {
+                if let Ok(true) = tasks_writer::move_task(&path, &task_id, direction) {
+                    self.reload_tasks_and_select(&path, &task_id);
+                }
+            }
+            Effect::MoveToPhase { task_id, phase_id } => {
+                if let Ok(true) = tasks_writer::move_task_to_phase(&path, &task_id, &phase_id) {
+                    self.reload_tasks_and_select(&path, &task_id);
+                }
+            }
+            Effect::AddTask {
+                phase_id,
+                task_id,
+                name,
+                body,
+            } => {
+                if let Ok(true) = tasks_writer::add_task(&path, &phase_id, &task_id, &name, &body) {
+                    self.reload_tasks_and_select(&path, &task_id);
+                }
+            }
+            Effect::Dispatch { task_id, agent } => {
+                // Someone may have started it since the overlay opened
+                let still_pending = self
+                    .disk_task(&task_id)
+                    .and_then(|t| t.status())
+                    .is_some_and(|s| s == TaskStatus::Pending);
+                if !still_pending {
+                    return;
+                }
+                if let Ok(true) = tasks_writer::update_task_status(&path, &task_id, "InProgress") {
+                    if let Some(agent) = agent {
+                        let _ = tasks_writer::set_task_agent(&path, &task_id, &agent);
+                    }
+                    self.reload_tasks_and_select(&path, &task_id);
+                }
+            }
+            Effect::StopAgent { .. }
+            | Effect::Triage { .. }
+            | Effect::JiraRefresh { .. }
+            | Effect::JiraComplete { .. }
+            | Effect::GithubRefresh { .. }
+            | Effect::BudgetAlert { .. }
+            | Effect::PipePrompt { .. } => {}
+        }
+    }
+
+    /// Perform effects in order, then whatever they left in the outbox
+    pub fn perform_all(&mut self, effects: Vec<Effect>) {
+        let mut effects = effects;
+        effects.append(&mut self.outbox);
+        while !effects.is_empty() {
+            for effect in effects {
+                self.perform(effect);
+            }
+            effects = std::mem::take(&mut self.outbox);
+        }
+    }
+
+    fn reload_from(&mut self, path: &std::path::Path) {
+        if let Ok(content) = self.dashboard.read_tasks_file(path) {
+            let _ = self.reload_dashboard(&content);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::data::cost::{CostGroup, CostSort};
+    use crate::data::state::DashboardState;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn key(c: char) -> Msg {
+        Msg::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    fn app_with(content: &str) -> App {
+        // The path is never opened: update only plans writes
+        App::new()
+            .with_dashboard(DashboardState::from_tasks_content(content).unwrap())
+            .with_tasks_path("TASKS.md".into())
+    }
+
+    #[test]
+    fn note_sequence_yields_effect_without_writing() {
+        let mut app = app_with("# Phase 1: A\n### [ ] T1: A\n");
+        app.gantt_state.total_items = 2;
+        assert!(app.update(key('j')).is_empty());
+        assert!(app.update(key('m')).is_empty());
+        assert!(app.update(key('h')).is_empty());
+        assert!(app.update(key('i')).is_empty());
+        let effects = app.update(Msg::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+        let [Effect::AppendNote { task_id, note }] = &effects[..] else {
+            panic!("expected a note, got {effects:?}");
+        };
+        assert_eq!(task_id, "T1");
+        assert!(note.ends_with(": hi"));
+        assert!(app.input.is_none());
+    }
+
+    #[test]
+    fn time_comes_from_the_dashboard_clock() {
+        use crate::data::clock::ManualClock;
+        use crate::data::timezone::DisplayZone;
+        use std::sync::Arc;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(&tasks_file, "# Phase 1: A\n### [ ] T1: A\n").unwrap();
+        let clock = Arc::new(ManualClock::new("2026-02-08T10:05:00Z".parse().unwrap()));
+        let mut app = app_with("# Phase 1: A\n### [ ] T1: A\n")
+            .with_tasks_path(tasks_file.clone())
+            .with_clock(clock.clone());
+        app.dashboard.timezone = DisplayZone::Utc;
+
+        app.gantt_state.total_items = 2;
+        for c in ['j', 'm', 'h', 'i'] {
+            app.update(key(c));
+        }
+        let effects = app.update(Msg::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+        assert!(
+            matches!(&effects[..], [Effect::AppendNote { note, .. }] if note == "note 10:05: hi")
+        );
+
+        // The tasks file grace period runs on the clock, not on the ticks
+        std::fs::remove_file(&tasks_file).unwrap();
+        app.update(Msg::FileChanged(FileChange::TasksRemoved(tasks_file)));
+        app.update(Msg::Tick);
+        app.update(Msg::Tick);
+        assert!(!app.tasks_gone);
+        clock.advance(chrono::Duration::seconds(3));
+        app.update(Msg::Tick);
+        assert!(app.tasks_gone);
+    }
+
+    #[test]
+    fn f12_toggles_debug_over_modals() {
+        let mut app = app_with("# Phase 1: A\n### [Failed] T1: A\n");
+        app.gantt_state.selected = 1;
+        app.update(key('r'));
+        let f12 = || Msg::Key(KeyEvent::new(KeyCode::F(12), KeyModifiers::NONE));
+        app.update(f12());
+        assert!(app.show_debug);
+        assert!(app.show_retry_modal);
+        app.update(f12());
+        assert!(!app.show_debug);
+    }
+
+    #[test]
+    fn enter_jumps_between_agent_and_task() {
+        use crate::data::hook_parser::{EventType, HookEvent};
+
+        let start = |agent: &str, task: &str| HookEvent {
+            event_type: EventType::AgentStart,
+            timestamp: "2026-02-08T10:00:00Z".parse().unwrap(),
+            agent_id: agent.to_string(),
+            task_id: task.to_string(),
+            session_id: "s".to_string(),
+            tool_name: None,
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        };
+        let mut app = app_with("# Phase 1: A\n### [/] T1: A\n### [/] T2: B\n### [ ] T3: C\n");
+        app.dashboard
+            .update_from_events(&[start("aaa", "T1"), start("builder", "T2")]);
+        let enter = || Msg::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        app.focused = FocusedPane::Agents;
+        app.selected_agent = 1;
+        app.update(enter());
+        assert_eq!(app.focused, FocusedPane::TaskList);
+        assert_eq!(app.gantt_state.selected, 2);
+
+        app.gantt_state.selected = 1;
+        app.update(enter());
+        assert_eq!(app.focused, FocusedPane::Agents);
+        assert_eq!(app.selected_agent, 0);
+
+        // A task nobody works on stays put
+        app.focused = FocusedPane::TaskList;
+        app.gantt_state.selected = 3;
+        app.update(enter());
+        assert_eq!(app.focused, FocusedPane::TaskList);
+    }
+
+    #[test]
+    fn next_up_starts_the_top_task_with_an_idle_agent() {
+        use crate::data::hook_parser::{EventType, HookEvent};
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        let plan = "# Phase 1: A\n### [ ] T1: Leaf\n### [ ] T2: Root\n### [ ] T3: Next\n- blocked_by: T2\n";
+        std::fs::write(&path, plan).unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(plan).unwrap())
+            .with_tasks_path(path.clone());
+        app.dashboard.update_from_events(&[HookEvent {
+            event_type: EventType::AgentEnd,
+            timestamp: "2026-02-08T10:00:00Z".parse().unwrap(),
+            agent_id: "builder".to_string(),
+            task_id: "T0".to_string(),
+            session_id: "s".to_string(),
+            tool_name: None,
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        }]);
+
+        app.update(key('u'));
+        assert!(app.show_next_up);
+        // Navigation is swallowed while the overlay is open
+        app.update(key('j'));
+        assert_eq!(app.gantt_state.selected, 0);
+        let effects = app.update(key('y'));
+        assert_eq!(
+            effects,
+            vec![Effect::Dispatch {
+                task_id: "T2".to_string(),
+                agent: Some("builder".to_string())
+            }]
+        );
+        assert!(!app.show_next_up);
+
+        app.perform_all(effects);
+        let task = app.dashboard.task("T2").unwrap();
+        assert_eq!(task.status, TaskStatus::InProgress);
+        assert_eq!(task.agent.as_deref(), Some("builder"));
+        assert_eq!(app.selected_task_id().as_deref(), Some("T2"));
+    }
+
+    #[test]
+    fn next_up_hands_the_task_to_a_roster_agent_with_room() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        let plan = "# Phase 1: A\n### [InProgress] T1: Busy\n- @backend\n### [ ] T2: Free\n";
+        std::fs::write(&path, plan).unwrap();
+        std::fs::write(
+            tmp.path().join("AGENTS.md"),
+            "- @backend (max 1)\n- @frontend\n",
+        )
+        .unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(plan).unwrap())
+            .with_tasks_path(path);
+
+        app.update(key('w'));
+        assert!(app.show_roster);
+        assert_eq!(app.roster.len(), 2);
+        app.update(key('w'));
+        assert!(!app.show_roster);
+
+        app.update(key('u'));
+        assert_eq!(
+            app.update(key('y')),
+            vec![Effect::Dispatch {
+                task_id: "T2".to_string(),
+                agent: Some("frontend".to_string())
+            }]
+        );
+    }
+
+    #[test]
+    fn retry_modal_routes_keys() {
+        let mut app = app_with("# Phase 1: A\n### [Failed] T1: A\n");
+        app.gantt_state.selected = 1;
+        app.update(key('r'));
+        assert!(app.show_retry_modal);
+        // Navigation is swallowed while the modal is open
+        assert!(app.update(key('j')).is_empty());
+        assert_eq!(app.gantt_state.selected, 1);
+        assert_eq!(
+            app.update(key('y')),
+            vec![Effect::Retry {
+                task_id: "T1".to_string()
+            }]
+        );
+        assert!(!app.show_retry_modal);
+    }
+
+    #[test]
+    fn focused_panel_gets_keys_first() {
+        use crate::ui::panel::tests::Counter;
+        let mut app = App::new().with_panel(Box::new(Counter(0)));
+        for _ in 0..3 {
+            app.update(Msg::Action(Action::ToggleFocus));
+        }
+        assert_eq!(app.focused, FocusedPane::Panel(0));
+        app.update(key('+'));
+        assert!(!app.show_help);
+        // Keys the panel ignores still reach the global bindings
+        app.update(key('?'));
+        assert!(app.show_help);
+        app.update(Msg::Action(Action::ToggleFocus));
+        assert_eq!(app.focused, FocusedPane::TaskList);
+        let lines = app.panels.get(0).unwrap().build_lines(&app.dashboard, 20);
+        assert_eq!(lines[0].to_string(), "1 / 0");
+    }
+
+    #[test]
+    fn stop_modal_confirms_stop_for_selected_agent() {
+        use crate::data::hook_parser;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let events = hook_parser::parse_hook_events(include_str!(
+            "../tests/fixtures/sample_hooks/agent_events.jsonl"
+        ));
+        let mut dashboard = DashboardState::default();
+        dashboard.update_from_events(&events.events);
+        let mut app = App::new()
+            .with_dashboard(dashboard)
+            .with_control_dir(tmp.path().to_path_buf());
+
+        // Only the agents pane opens it
+        app.update(key('s'));
+        assert!(app.stop_target.is_none());
+        app.focused = FocusedPane::Agents;
+        app.update(key('s'));
+        assert_eq!(app.stop_target.as_deref(), Some("backend-specialist-1"));
+        assert!(app.update(key('j')).is_empty());
+
+        let effects = app.update(key('y'));
+        assert_eq!(
+            effects,
+            vec![Effect::StopAgent {
+                agent_id: "backend-specialist-1".to_string(),
+                session_id: Some("sess-001".to_string())
+            }]
+        );
+        app.perform_all(effects);
+        assert!(tmp.path().join("backend-specialist-1.stop").exists());
+        assert!(app.stop_requested.contains("backend-specialist-1"));
+
+        // The hook consumed the request
+        std::fs::remove_file(tmp.path().join("backend-specialist-1.stop")).unwrap();
+        app.update(Msg::Tick);
+        assert!(app.stop_requested.is_empty());
+    }
+
+    #[test]
+    fn stopping_a_group_stops_every_member_in_every_session() {
+        use crate::data::agent_groups::{AgentGroup, Pattern};
+        use crate::data::hook_parser::{EventType, HookEvent};
+
+        let start = |agent: &str| HookEvent {
+            event_type: EventType::AgentStart,
+            timestamp: "2026-02-08T10:00:00Z".parse().unwrap(),
+            agent_id: agent.to_string(),
+            task_id: "T-1".to_string(),
+            session_id: format!("sess-{agent}"),
+            tool_name: None,
+            error_message: None,
+            tool_output: None,
+            usage: None,
+        };
+        let mut dashboard = DashboardState {
+            agent_groups: vec![AgentGroup {
+                pattern: Pattern::new(r"^backend-\d$").unwrap(),
+                group: "backend".to_string(),
+            }],
+            ..Default::default()
+        };
+        // backend-1 also runs in a second session
+        let other = HookEvent {
+            session_id: "sess-b".to_string(),
+            ..start("backend-1")
+        };
+        dashboard.update_from_events(&[start("backend-1"), start("backend-2"), other]);
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut app = App::new()
+            .with_dashboard(dashboard)
+            .with_control_dir(tmp.path().to_path_buf());
+        app.focused = FocusedPane::Agents;
+
+        app.update(key('s'));
+        assert_eq!(app.stop_target.as_deref(), Some("backend"));
+        let effects = app.update(key('y'));
+        assert_eq!(
+            effects,
+            vec![
+                Effect::StopAgent {
+                    agent_id: "backend-1".to_string(),
+                    session_id: Some("sess-b".to_string())
+                },
+                Effect::StopAgent {
+                    agent_id: "backend-1".to_string(),
+                    session_id: Some("sess-backend-1".to_string())
+                },
+                Effect::StopAgent {
+                    agent_id: "backend-2".to_string(),
+                    session_id: Some("sess-backend-2".to_string())
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn scrubbing_shows_past_state_read_only() {
+        use crate::data::hook_parser;
+
+        let mut app = app_with("# Phase 1: A\n### [x] P1-R1-T1: A\n");
+        let events = hook_parser::parse_hook_events(include_str!(
+            "../tests/fixtures/sample_hooks/agent_events.jsonl"
+        ));
+        app.dashboard.update_from_events(&events.events);
+        let arrow = |code| Msg::Key(KeyEvent::new(code, KeyModifiers::NONE));
+
+        // Right from live stays live; Left steps back 1/50 of the 60s span
+        app.update(arrow(KeyCode::Right));
+        assert!(app.scrub.is_none());
+        app.update(arrow(KeyCode::Left));
+        let past = app.view();
+        assert_eq!(
+            past.scrub_at.unwrap().to_rfc3339(),
+            "2026-02-08T10:00:58.800+00:00"
+        );
+        assert_eq!(past.phases[0].tasks[0].status, TaskStatus::InProgress);
+        assert_eq!(
+            app.dashboard.phases[0].tasks[0].status,
+            TaskStatus::Completed
+        );
+
+        // Writes are ignored, and stepping past the last event returns to live
+        app.gantt_state.selected = 1;
+        assert!(app.update(key('a')).is_empty());
+        app.update(arrow(KeyCode::Right));
+        assert!(app.scrub.is_some());
+        app.update(arrow(KeyCode::Right));
+        assert!(app.scrub.is_none());
+
+        // Esc leaves scrubbing without quitting
+        app.update(arrow(KeyCode::Left));
+        app.update(arrow(KeyCode::Esc));
+        assert!(app.scrub.is_none());
+        assert!(app.running);
+    }
+
+    #[test]
+    fn performed_writes_are_audited_with_old_and_new_task() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        let audit = tmp.path().join("audit.jsonl");
+        let plan = "# Phase 1: A\n### [Failed] T1: A\n### [ ] T2: B\n";
+        std::fs::write(&path, plan).unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(plan).unwrap())
+            .with_tasks_path(path)
+            .with_audit_path(audit.clone());
+
+        app.perform_all(vec![
+            Effect::Retry {
+                task_id: "T1".to_string(),
+            },
+            // Already started: nothing is written, nothing is recorded
+            Effect::Dispatch {
+                task_id: "T1".to_string(),
+                agent: None,
+            },
+            Effect::MoveTask {
+                task_id: "T2".to_string(),
+                direction: MoveDirection::Up,
+            },
+        ]);
+
+        let entries: Vec<serde_json::Value> = std::fs::read_to_string(&audit)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["action"], "retry");
+        assert_eq!(entries[0]["target"], "T1");
+        assert_eq!(entries[0]["old"]["section"], "### [Failed] T1: A");
+        assert!(entries[0]["new"]["section"]
+            .as_str()
+            .unwrap()
+            .starts_with("### [InProgress] T1: A"));
+        assert_eq!(entries[1]["action"], "move-up");
+        assert_eq!(entries[1]["old"]["position"], 1);
+        assert_eq!(entries[1]["new"]["position"], 0);
+    }
+
+    #[test]
+    fn log_viewer_tails_the_log_and_swallows_keys() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let log = tmp.path().join("board.log");
+        std::fs::write(&log, "a\nb\n").unwrap();
+        let mut app = app_with("# Phase 1: A\n### [ ] T1: A\n").with_log_path(log.clone());
+        app.gantt_state.total_items = 2;
+
+        app.update(key('L'));
+        assert!(app.show_log);
+        assert_eq!(app.log_lines, vec!["a", "b"]);
+        std::fs::write(&log, "a\nb\nc\n").unwrap();
+        app.update(Msg::Tick);
+        assert_eq!(app.log_lines.len(), 3);
+
+        app.update(key('j'));
+        assert_eq!(app.gantt_state.selected, 0);
+        app.update(key('q'));
+        assert!(!app.show_log);
+        assert!(app.running);
+    }
+
+    #[test]
+    fn error_modal_unfolds_frames_and_swallows_keys() {
+        let mut app = app_with("# Phase 1: A\n### [Failed] T1: A\n### [ ] T2: B\n");
+        app.gantt_state.total_items = 3;
+        let events = crate::data::hook_parser::parse_hook_events(
+            r#"{"event_type":"error","timestamp":"2026-02-08T12:00:00Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":["Traceback (most recent call last):","  File \"a.py\", line 3, in main","    run()","  File \"a.py\", line 9, in run","    connect()","ConnectionError: refused"]}"#,
+        );
+        app.dashboard.update_from_events(&events.events);
+
+        // No error on the header row's phase: a toast, no modal
+        app.update(key('e'));
+        assert!(app.error_view.is_none());
+        app.update(key('j'));
+        app.update(key('e'));
+        let view = app.error_view.as_ref().unwrap();
+        assert_eq!(view.error.message.lines().count(), 6);
+
+        app.update(key('j'));
+        app.update(key('j'));
+        app.update(key(' '));
+        let view = app.error_view.as_ref().unwrap();
+        assert_eq!(view.cursor, 1);
+        assert_eq!(view.expanded, [1].into());
+        app.update(Msg::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+        assert!(app.error_view.as_ref().unwrap().expanded.is_empty());
+        assert_eq!(app.gantt_state.selected, 1);
+
+        app.update(key('e'));
+        assert!(app.error_view.is_none());
+        assert!(app.running);
+    }
+
+    #[test]
+    fn jump_to_context_anchors_the_ops_log_at_the_error() {
+        let mut app = app_with("# Phase 1: A\n### [Failed] T1: A\n");
+        app.gantt_state.total_items = 2;
+        app.gantt_state.selected = 1;
+        let events = crate::data::hook_parser::parse_hook_events(concat!(
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T12:00:00Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Bash"}"#,
+            "\n",
+            r#"{"event_type":"error","timestamp":"2026-02-08T12:00:01Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"exit 1"}"#,
+            "\n",
+            r#"{"event_type":"tool_end","timestamp":"2026-02-08T12:00:02Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Bash"}"#,
+        ));
+        app.dashboard.update_from_events(&events.events);
+
+        app.update(key('g'));
+        assert_eq!(app.layout, LayoutPreset::Ops);
+        assert_eq!(app.event_anchor.map(|a| a.index), Some(1));
+        app.update(key('k'));
+        app.update(key('k'));
+        assert_eq!(app.event_anchor.map(|a| a.index), Some(0));
+        assert_eq!(app.gantt_state.selected, 1);
+
+        app.update(key('q'));
+        assert!(app.event_anchor.is_none());
+        assert_eq!(app.layout, LayoutPreset::Standard);
+        assert!(app.running);
+
+        // From the error modal, which closes on the way
+        app.update(key('e'));
+        app.update(key('g'));
+        assert!(app.error_view.is_none());
+        assert_eq!(app.event_anchor.map(|a| a.index), Some(1));
+    }
+
+    #[test]
+    fn copy_context_fills_the_clipboard_and_saves_the_bundle() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut app = app_with("# Phase 1: A\n### [ ] T1.1: Login\n- Use OAuth\n")
+            .with_context_dir(tmp.path().join("context"));
+        app.gantt_state.total_items = 2;
+
+        // A phase row has no task context
+        app.update(key('c'));
+        assert!(app.clipboard.is_none());
+
+        app.update(key('j'));
+        app.update(key('c'));
+        let copied = app.clipboard.take().unwrap();
+        assert!(copied.starts_with("# T1.1: Login\n"));
+        assert!(copied.contains("Use OAuth"));
+        let saved = tmp.path().join("context").join("T1.1.md");
+        assert_eq!(std::fs::read_to_string(saved).unwrap(), copied);
+        assert!(!app.toast.unwrap().error);
+    }
+
+    #[test]
+    fn status_table_goes_to_the_clipboard() {
+        let mut app = app_with("# Phase 1: A\n### [ ] T1: Login\n### [ ] T2: Logout\n");
+        app.update(Msg::Key(KeyEvent::new(
+            KeyCode::Char('G'),
+            KeyModifiers::SHIFT,
+        )));
+        let table = app.clipboard.take().unwrap();
+        assert!(table.contains("| T2 | Logout | Pending |  |  |\n"));
+        assert_eq!(
+            app.toast.unwrap().message,
+            "Copied 2 tasks as a markdown table"
+        );
+    }
+
+    #[test]
+    fn ops_layout_toggles_and_keeps_focus_on_tasks() {
+        let mut app = app_with("# Phase 1: A\n### [ ] T1: Login\n");
+        app.focused = FocusedPane::Agents;
+        app.update(key('o'));
+        assert_eq!(app.layout, LayoutPreset::Ops);
+        assert_eq!(app.focused, FocusedPane::TaskList);
+        app.update(Msg::Key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE)));
+        assert_eq!(app.focused, FocusedPane::TaskList);
+        app.update(key('o'));
+        assert_eq!(app.layout, LayoutPreset::Standard);
+    }
+
+    #[test]
+    fn monitor_mode_acts_on_the_monitored_task() {
+        let mut app = app_with("# Phase 1: A\n### [ ] T1: Login\n### [ ] T2: Logout\n")
+            .with_monitor("T2".to_string());
+        app.gantt_state.total_items = 3;
+        app.update(key('j'));
+        assert_eq!(app.gantt_state.selected, 0);
+        app.update(key('c'));
+        assert_eq!(app.toast.unwrap().message, "Context for T2 copied");
+    }
+
+    #[test]
+    fn prompt_is_rendered_only_for_failed_tasks() {
+        let mut app = app_with("# Phase 1: A\n### [Failed] T1: Login\n### [ ] T2: Logout\n");
+        app.config.prompt.template = "Fix task {id}: {name}".to_string();
+        app.gantt_state.total_items = 3;
+
+        app.update(key('j'));
+        app.update(key('P'));
+        assert_eq!(app.clipboard.take().as_deref(), Some("Fix task T1: Login"));
+
+        app.update(key('j'));
+        app.update(key('P'));
+        assert!(app.clipboard.is_none());
+        assert!(app.toast.take().unwrap().error);
+
+        // A command gets the prompt when the effect is performed
+        app.config.prompt.output = crate::prompt::PromptOutput::Command;
+        app.config.prompt.command = vec!["cat".to_string()];
+        app.update(key('k'));
+        assert_eq!(
+            app.update(key('P')),
+            [Effect::PipePrompt {
+                task_id: "T1".to_string(),
+                command: vec!["cat".to_string()],
+                text: "Fix task T1: Login".to_string(),
+            }]
+        );
+        assert!(app.toast.is_none());
+    }
+
+    #[test]
+    fn ticks_plan_jira_and_github_requests_without_making_them() {
+        let mut app =
+            app_with("# Phase 1: A\n### [ ] T1: Login\n- **jira**: PROJ-1\n- **pr**: #4\n");
+        let mut config = Config::default();
+        config.jira.url = "http://localhost:9".to_string();
+        config.github.enabled = true;
+        app.apply_config(config);
+
+        assert_eq!(
+            app.update(Msg::Tick),
+            [
+                Effect::JiraRefresh {
+                    key: "PROJ-1".to_string()
+                },
+                Effect::GithubRefresh { numbers: vec![4] },
+            ]
+        );
+        // Still in flight until performed and answered
+        assert!(app.update(Msg::Tick).is_empty());
+    }
+
+    #[test]
+    fn trace_export_needs_an_endpoint_and_a_run() {
+        let mut app = app_with("# Phase 1: A\n### [ ] T1: Login\n");
+        app.update(key('O'));
+        assert_eq!(
+            app.toast.take().unwrap().message,
+            "No [otel] endpoint configured"
+        );
+
+        let mut config = Config::default();
+        config.otel.endpoint = "http://localhost:4318".to_string();
+        app.apply_config(config);
+        app.update(key('O'));
+        assert_eq!(
+            app.toast.take().unwrap().message,
+            "Trace not exported: nothing has run yet"
+        );
+    }
+
+    #[test]
+    fn budget_alert_fires_once_when_exceeded() {
+        let mut app = app_with("# Phase 1: A\n### [ ] T1: Login\n");
+        app.config.cost.budget_usd = Some(1.0);
+        let spend = |cost: f64| {
+            crate::data::hook_parser::parse_hook_events(&format!(
+                r#"{{"event_type":"agent_end","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s","usage":{{"cost_usd":{cost}}}}}"#
+            ))
+            .events
+        };
+        app.dashboard.update_from_events(&spend(0.5));
+        app.update(Msg::Tick);
+        assert!(app.toast.is_none());
+
+        app.config.cost.webhook = Some("http://localhost:9/alert".to_string());
+        app.dashboard.update_from_events(&spend(0.75));
+        assert_eq!(
+            app.update(Msg::Tick),
+            [Effect::BudgetAlert {
+                url: "http://localhost:9/alert".to_string(),
+                spent: 1.25,
+                budget: 1.0,
+            }]
+        );
+        let toast = app.toast.take().unwrap();
+        assert!(toast.error);
+        assert_eq!(toast.message, "Run budget exceeded: $1.25 of $1.00");
+        assert!(app.update(Msg::Tick).is_empty());
+        assert!(app.toast.is_none());
+
+        // Raising the budget re-arms the alert
+        app.config.cost.budget_usd = Some(2.0);
+        app.update(Msg::Tick);
+        assert!(!app.budget_alerted);
+    }
+
+    #[test]
+    fn costs_view_regroups_sorts_and_keeps_keys_to_itself() {
+        let mut app = app_with("# Phase 1: A\n### [ ] T1: Login\n### [ ] T2: Logout\n");
+        let events = crate::data::hook_parser::parse_hook_events(concat!(
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s","usage":{"cost_usd":0.5}}"#,
+            "\n",
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:00:01Z","agent_id":"b","task_id":"T2","session_id":"s","usage":{"cost_usd":1.5}}"#,
+        ))
+        .events;
+        app.dashboard.update_from_events(&events);
+
+        app.update(Msg::Action(Action::ToggleCosts));
+        assert!(app.show_costs);
+        assert_eq!(app.cost_rows().len(), 1);
+        app.update(Msg::Action(Action::ToggleFocus));
+        assert_eq!(app.cost_group, CostGroup::Task);
+        let ids: Vec<String> = app.cost_rows().into_iter().map(|r| r.id).collect();
+        assert_eq!(ids, ["T2", "T1"]);
+        app.update(Msg::Action(Action::ScrubBack));
+        assert_eq!(app.cost_sort, CostSort::Tokens);
+        app.update(Msg::Action(Action::MoveDown));
+        app.update(Msg::Action(Action::MoveDown));
+        assert_eq!(app.cost_scroll, 1);
+
+        // Tab did not move focus and q closes the view, not the board
+        assert_eq!(app.focused, FocusedPane::TaskList);
+        app.update(Msg::Action(Action::Quit));
+        assert!(!app.show_costs);
+        assert!(app.running);
+    }
+
+    #[test]
+    fn no_tasks_file_plans_nothing() {
+        let mut app = App::new().with_dashboard(
+            DashboardState::from_tasks_content("# Phase 1: A\n### [ ] T1: A\n").unwrap(),
+        );
+        app.gantt_state.total_items = 2;
+        app.update(Msg::Action(Action::MoveDown));
+        assert!(app.update(Msg::Action(Action::MoveTaskDown)).is_empty());
+        app.update(Msg::Action(Action::Quit));
+        assert!(!app.running);
+    }
+}
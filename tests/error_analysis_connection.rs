@@ -3,11 +3,12 @@
 //! Validates error analysis fields flow through detail panel and retry modal,
 //! navigation between detail→modal→TASKS.md, and end-to-end error data flow.
 
+#![cfg(feature = "tui")]
+
 use chrono::Utc;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
-use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
 
-use simple_claude_board::analysis::rules::{analyze_error, ErrorCategory};
+use simple_claude_board::analysis::rules::{analyze_error, ErrorCategory, Severity};
 use simple_claude_board::app::{App, RetryTarget};
 use simple_claude_board::data::hook_parser;
 use simple_claude_board::data::state::{DashboardState, ErrorRecord};
@@ -15,17 +16,7 @@ use simple_claude_board::data::tasks_parser::TaskStatus;
 use simple_claude_board::event::{key_to_action, Action};
 use simple_claude_board::ui::detail::{DetailContent, DetailWidget};
 use simple_claude_board::ui::retry_modal::RetryModal;
-
-fn buffer_text(buf: &Buffer) -> String {
-    let mut text = String::new();
-    for y in 0..buf.area.height {
-        for x in 0..buf.area.width {
-            text.push_str(buf[(x, y)].symbol());
-        }
-        text.push('\n');
-    }
-    text
-}
+use simple_claude_board::ui::testing::render_to_string;
 
 fn full_state() -> DashboardState {
     let tasks_input = include_str!("fixtures/sample_tasks.md");
@@ -56,12 +47,9 @@ fn error_analysis_fields_in_detail_panel() {
     let widget = DetailWidget::new(
         DetailContent::Task(task, &state.phases[1].name, errors),
         true,
+        state.now(),
     );
-    let area = Rect::new(0, 0, 80, 25);
-    let mut buf = Buffer::empty(area);
-    widget.render(area, &mut buf);
-
-    let text = buffer_text(&buf);
+    let text = render_to_string(widget, 80, 25);
     // error_message (truncated)
     assert!(
         text.contains("permission denied"),
@@ -88,21 +76,23 @@ fn error_analysis_retryable_in_detail() {
     // Permission error (not retryable)
     let err_perm = ErrorRecord {
         agent_id: "test".to_string(),
-        task_id: "P1-R3-T1".to_string(),
+        task_id: "P1-R3-T1".into(),
         message: "permission denied: /etc/shadow".to_string(),
         category: ErrorCategory::Permission,
+        severity: Severity::Error,
         retryable: false,
         suggestion: "Check file permissions",
+        hint: None,
         timestamp: Utc::now(),
+        cooldown_until: None,
+        event_index: 0,
     };
     let widget = DetailWidget::new(
         DetailContent::Task(task, "Data Engine", vec![&err_perm]),
         true,
+        state.now(),
     );
-    let area = Rect::new(0, 0, 80, 25);
-    let mut buf = Buffer::empty(area);
-    widget.render(area, &mut buf);
-    let text = buffer_text(&buf);
+    let text = render_to_string(widget, 80, 25);
     assert!(
         text.contains("No retry"),
         "Permission error should show No retry"
@@ -111,20 +101,23 @@ fn error_analysis_retryable_in_detail() {
     // Network error (retryable)
     let err_net = ErrorRecord {
         agent_id: "test".to_string(),
-        task_id: "P1-R3-T1".to_string(),
+        task_id: "P1-R3-T1".into(),
         message: "connection refused: localhost:5432".to_string(),
         category: ErrorCategory::Network,
+        severity: Severity::Error,
         retryable: true,
         suggestion: "Check if service is running",
+        hint: None,
         timestamp: Utc::now(),
+        cooldown_until: None,
+        event_index: 0,
     };
-    let mut buf2 = Buffer::empty(area);
     let widget2 = DetailWidget::new(
         DetailContent::Task(task, "Data Engine", vec![&err_net]),
         true,
+        state.now(),
     );
-    widget2.render(area, &mut buf2);
-    let text2 = buffer_text(&buf2);
+    let text2 = render_to_string(widget2, 80, 25);
     assert!(text2.contains("Retry"), "Network error should show Retry");
     assert!(
         !text2.contains("No retry"),
@@ -203,7 +196,7 @@ fn modal_y_updates_tasks_md() {
 
     app.show_retry_modal = true;
     app.retry_target = Some(RetryTarget {
-        task_id: "P1-R3-T1".to_string(),
+        task_id: "P1-R3-T1".into(),
         task_name: "File watcher module".to_string(),
         retryable: true,
     });
@@ -312,6 +305,10 @@ fn end_to_end_error_flow() {
     assert_eq!(err1.category, ErrorCategory::Network);
     assert!(err1.retryable);
     assert_eq!(err1.suggestion, "Check if service is running");
+    assert_eq!(
+        err1.display_suggestion(),
+        "Check if the service at localhost:5432 is running"
+    );
 
     // 4. Verify analyze_error results match
     let analysis0 = analyze_error(&err0.message);
@@ -332,12 +329,9 @@ fn end_to_end_error_flow() {
     let widget = DetailWidget::new(
         DetailContent::Task(task, &state.phases[1].name, errors),
         true,
+        state.now(),
     );
-    let area = Rect::new(0, 0, 80, 30);
-    let mut buf = Buffer::empty(area);
-    widget.render(area, &mut buf);
-
-    let text = buffer_text(&buf);
+    let text = render_to_string(widget, 80, 30);
     assert!(
         text.contains("Permission"),
         "Detail should show Permission category"
@@ -355,15 +349,12 @@ fn end_to_end_error_flow() {
 fn retry_modal_renders_with_error_fields() {
     // Retryable modal
     let modal = RetryModal {
-        task_id: "P1-R3-T1".to_string(),
+        task_id: "P1-R3-T1".into(),
         task_name: "File watcher".to_string(),
         retryable: true,
+        conflict: None,
     };
-    let area = Rect::new(0, 0, 80, 30);
-    let mut buf = Buffer::empty(area);
-    modal.render(area, &mut buf);
-
-    let text = buffer_text(&buf);
+    let text = render_to_string(modal, 80, 30);
     assert!(text.contains("P1-R3-T1"), "Should show task_id");
     assert!(text.contains("File watcher"), "Should show task_name");
     assert!(text.contains("Retry"), "Should show Retry title");
@@ -372,14 +363,12 @@ fn retry_modal_renders_with_error_fields() {
 
     // Non-retryable modal
     let modal2 = RetryModal {
-        task_id: "P1-R3-T1".to_string(),
+        task_id: "P1-R3-T1".into(),
         task_name: "File watcher".to_string(),
         retryable: false,
+        conflict: None,
     };
-    let mut buf2 = Buffer::empty(area);
-    modal2.render(area, &mut buf2);
-
-    let text2 = buffer_text(&buf2);
+    let text2 = render_to_string(modal2, 80, 30);
     assert!(text2.contains("P1-R3-T1"), "Should show task_id");
     assert!(text2.contains("Not retryable"), "Should show Not retryable");
     assert!(text2.contains("Press any key"), "Should show close hint");
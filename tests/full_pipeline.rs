@@ -4,6 +4,8 @@
 //! TASKS.md → parse → state → UI render, Hook events → parse → agent panel,
 //! Error flow → analysis → retry → write-back, and keyboard interaction.
 
+#![cfg(feature = "tui")]
+
 use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget, widgets::Widget};
 
 use simple_claude_board::app::App;
@@ -18,17 +20,7 @@ use simple_claude_board::ui::help::HelpOverlay;
 use simple_claude_board::ui::layout::{DashboardLayout, FocusedPane};
 use simple_claude_board::ui::retry_modal::RetryModal;
 use simple_claude_board::ui::statusbar::StatusBar;
-
-fn buffer_text(buf: &Buffer) -> String {
-    let mut text = String::new();
-    for y in 0..buf.area.height {
-        for x in 0..buf.area.width {
-            text.push_str(buf[(x, y)].symbol());
-        }
-        text.push('\n');
-    }
-    text
-}
+use simple_claude_board::ui::testing::{buffer_text, render_stateful_to_string, render_to_string};
 
 // ===== Pipeline 1: TASKS.md → parse → state → gantt =====
 
@@ -68,12 +60,8 @@ fn tasks_file_to_gantt_render() {
     assert_eq!(app.dashboard.completed_tasks, 1);
 
     // Render gantt
-    let area = Rect::new(0, 0, 80, 20);
-    let mut buf = Buffer::empty(area);
     let gantt = GanttWidget::new(&app.dashboard, true);
-    gantt.render(area, &mut buf, &mut app.gantt_state);
-
-    let text = buffer_text(&buf);
+    let text = render_stateful_to_string(gantt, &mut app.gantt_state, 80, 20);
     assert!(text.contains("P0-T1"), "Gantt should show task P0-T1");
     assert!(text.contains("P1-T1"), "Gantt should show task P1-T1");
     assert!(text.contains("[x]"), "Gantt should show completed icon");
@@ -143,12 +131,7 @@ fn hook_events_to_agent_panel() {
     assert_eq!(agent.current_tool.as_deref(), Some("Bash"));
 
     // Render agent panel
-    let area = Rect::new(0, 0, 60, 10);
-    let mut buf = Buffer::empty(area);
-    let panel = AgentPanel::new(&app.dashboard);
-    panel.render(area, &mut buf);
-
-    let text = buffer_text(&buf);
+    let text = render_to_string(AgentPanel::new(&app.dashboard), 60, 10);
     assert!(text.contains("main"), "Agent panel should show 'main'");
     assert!(text.contains(">>"), "Agent panel should show Running (>>)");
     assert!(
@@ -343,13 +326,14 @@ fn full_render_pipeline_no_panic() {
     statusbar.render(layout.status_bar, &mut buf);
 
     // Help overlay
-    HelpOverlay.render(area, &mut buf);
+    HelpOverlay::default().render(area, &mut buf);
 
     // Retry modal
     let modal = RetryModal {
         task_id: "T1".to_string(),
         task_name: "Test".to_string(),
         retryable: true,
+        conflict: None,
     };
     modal.render(area, &mut buf);
 
@@ -3,11 +3,14 @@
 //! Verifies Phase/Task tree display, cursor navigation,
 //! selection mapping, and live update behavior.
 
-use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+#![cfg(feature = "tui")]
+
+use ratatui::buffer::Buffer;
 
 use simple_claude_board::app::App;
 use simple_claude_board::data::state::DashboardState;
 use simple_claude_board::ui::gantt::{GanttState, GanttWidget};
+use simple_claude_board::ui::testing::{buffer_text, render_stateful, render_stateful_to_string};
 
 fn sample_state() -> DashboardState {
     let input = include_str!("fixtures/sample_tasks.md");
@@ -15,11 +18,7 @@ fn sample_state() -> DashboardState {
 }
 
 fn render_gantt(state: &DashboardState, gs: &mut GanttState) -> Buffer {
-    let widget = GanttWidget::new(state, true);
-    let area = Rect::new(0, 0, 60, 20);
-    let mut buf = Buffer::empty(area);
-    widget.render(area, &mut buf, gs);
-    buf
+    render_stateful(GanttWidget::new(state, true), gs, 60, 20)
 }
 
 // --- Scenario: Phase/Task tree display ---
@@ -201,25 +200,18 @@ fn reload_with_fewer_tasks_clamps_cursor() {
     // Re-render would set total_items=2, but selected stays at 10
     // Next render pass will adjust — verify state is consistent
     let mut gs = app.gantt_state.clone();
-    let widget = GanttWidget::new(&app.dashboard, true);
-    let area = Rect::new(0, 0, 60, 20);
-    let mut buf = Buffer::empty(area);
-    widget.render(area, &mut buf, &mut gs);
+    render_stateful(GanttWidget::new(&app.dashboard, true), &mut gs, 60, 20);
 
     // total_items updated to 2 (1 phase + 1 task)
     assert_eq!(gs.total_items, 2);
 }
 
-// --- Helper ---
+// --- Snapshot ---
 
-fn buffer_text(buf: &Buffer) -> String {
-    let mut text = String::new();
-    for y in 0..buf.area.height {
-        for x in 0..buf.area.width {
-            let cell = &buf[(x, y)];
-            text.push_str(cell.symbol());
-        }
-        text.push('\n');
-    }
-    text
+#[test]
+fn tree_view_snapshot() {
+    let state = sample_state();
+    let mut gs = GanttState::default();
+    let text = render_stateful_to_string(GanttWidget::new(&state, true), &mut gs, 60, 16);
+    insta::assert_snapshot!(text);
 }
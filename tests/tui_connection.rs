@@ -3,6 +3,8 @@
 //! Validates field coverage, navigation flow, and shared component
 //! consistency across all UI panels.
 
+#![cfg(feature = "tui")]
+
 use std::time::Instant;
 
 use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget, widgets::Widget};
@@ -18,6 +20,7 @@ use simple_claude_board::ui::gantt::{GanttState, GanttWidget};
 use simple_claude_board::ui::help::HelpOverlay;
 use simple_claude_board::ui::layout::{DashboardLayout, FocusedPane};
 use simple_claude_board::ui::statusbar::StatusBar;
+use simple_claude_board::ui::testing::{buffer_text, render_stateful_to_string, render_to_string};
 
 fn full_state() -> DashboardState {
     let tasks_input = include_str!("fixtures/sample_tasks.md");
@@ -28,17 +31,6 @@ fn full_state() -> DashboardState {
     state
 }
 
-fn buffer_text(buf: &Buffer) -> String {
-    let mut text = String::new();
-    for y in 0..buf.area.height {
-        for x in 0..buf.area.width {
-            text.push_str(buf[(x, y)].symbol());
-        }
-        text.push('\n');
-    }
-    text
-}
-
 // ===== Field Coverage: tasks → Gantt chart =====
 
 #[test]
@@ -46,11 +38,7 @@ fn gantt_shows_phase_id_and_name() {
     let state = full_state();
     let mut gs = GanttState::default();
     let widget = GanttWidget::new(&state, true);
-    let area = Rect::new(0, 0, 80, 30);
-    let mut buf = Buffer::empty(area);
-    widget.render(area, &mut buf, &mut gs);
-
-    let text = buffer_text(&buf);
+    let text = render_stateful_to_string(widget, &mut gs, 80, 30);
     for phase in &state.phases {
         assert!(text.contains(&phase.id), "Missing phase_id: {}", phase.id);
         assert!(
@@ -66,11 +54,7 @@ fn gantt_shows_task_id_and_status() {
     let state = full_state();
     let mut gs = GanttState::default();
     let widget = GanttWidget::new(&state, true);
-    let area = Rect::new(0, 0, 80, 30);
-    let mut buf = Buffer::empty(area);
-    widget.render(area, &mut buf, &mut gs);
-
-    let text = buffer_text(&buf);
+    let text = render_stateful_to_string(widget, &mut gs, 80, 30);
     for phase in &state.phases {
         for task in &phase.tasks {
             assert!(text.contains(&task.id), "Missing task_id: {}", task.id);
@@ -83,11 +67,7 @@ fn gantt_shows_agent_names() {
     let state = full_state();
     let mut gs = GanttState::default();
     let widget = GanttWidget::new(&state, true);
-    let area = Rect::new(0, 0, 80, 30);
-    let mut buf = Buffer::empty(area);
-    widget.render(area, &mut buf, &mut gs);
-
-    let text = buffer_text(&buf);
+    let text = render_stateful_to_string(widget, &mut gs, 80, 30);
     // sample_tasks has @backend-specialist
     assert!(text.contains("@backend-specialist"));
 }
@@ -101,12 +81,9 @@ fn detail_shows_task_fields() {
     let widget = DetailWidget::new(
         DetailContent::Task(task, &state.phases[0].name, vec![]),
         true,
+        state.now(),
     );
-    let area = Rect::new(0, 0, 50, 15);
-    let mut buf = Buffer::empty(area);
-    widget.render(area, &mut buf);
-
-    let text = buffer_text(&buf);
+    let text = render_to_string(widget, 50, 15);
     assert!(text.contains(&task.id), "Missing task_id in detail");
     assert!(text.contains(&task.name), "Missing task_name in detail");
     assert!(text.contains("Status"), "Missing status label in detail");
@@ -122,12 +99,9 @@ fn detail_shows_blocked_by() {
     let widget = DetailWidget::new(
         DetailContent::Task(task, &state.phases[1].name, vec![]),
         true,
+        state.now(),
     );
-    let area = Rect::new(0, 0, 50, 15);
-    let mut buf = Buffer::empty(area);
-    widget.render(area, &mut buf);
-
-    let text = buffer_text(&buf);
+    let text = render_to_string(widget, 50, 15);
     assert!(text.contains("Deps"), "Missing deps label");
     for dep in &task.blocked_by {
         assert!(text.contains(dep), "Missing blocked_by: {dep}");
@@ -140,11 +114,7 @@ fn detail_shows_blocked_by() {
 fn agent_panel_shows_agent_id() {
     let state = full_state();
     let panel = AgentPanel::new(&state);
-    let area = Rect::new(0, 0, 60, 10);
-    let mut buf = Buffer::empty(area);
-    panel.render(area, &mut buf);
-
-    let text = buffer_text(&buf);
+    let text = render_to_string(panel, 60, 10);
     assert!(text.contains("backend-specialist-1"));
 }
 
@@ -154,11 +124,7 @@ fn agent_panel_shows_agent_id() {
 fn statusbar_shows_progress_and_counters() {
     let state = full_state();
     let bar = StatusBar::new(&state, Instant::now());
-    let area = Rect::new(0, 0, 80, 1);
-    let mut buf = Buffer::empty(area);
-    bar.render(area, &mut buf);
-
-    let text = buffer_text(&buf);
+    let text = render_to_string(bar, 80, 1);
     // Should show percentage
     assert!(text.contains("25%"), "Missing progress percentage");
     // Should show uptime
@@ -197,11 +163,7 @@ fn gantt_selection_updates_detail() {
     // Verify detail can render with this selection
     let detail =
         DetailWidget::from_selection(&app.dashboard, selected, app.gantt_state.selected, true);
-    let area = Rect::new(0, 0, 50, 15);
-    let mut buf = Buffer::empty(area);
-    detail.render(area, &mut buf);
-
-    let text = buffer_text(&buf);
+    let text = render_to_string(detail, 50, 15);
     assert!(text.contains("P0-T0.1"));
 }
 
@@ -251,7 +213,7 @@ fn help_toggle_shows_overlay() {
     // Verify help overlay renders
     let area = Rect::new(0, 0, 80, 30);
     let mut buf = Buffer::empty(area);
-    HelpOverlay.render(area, &mut buf);
+    HelpOverlay::default().render(area, &mut buf);
     let text = buffer_text(&buf);
     assert!(text.contains("Help"));
 }
@@ -280,10 +242,7 @@ fn status_representation_consistent() {
     // Gantt renders status icons
     let mut gs = GanttState::default();
     let gantt = GanttWidget::new(&state, true);
-    let area = Rect::new(0, 0, 80, 30);
-    let mut buf = Buffer::empty(area);
-    gantt.render(area, &mut buf, &mut gs);
-    let gantt_text = buffer_text(&buf);
+    let gantt_text = render_stateful_to_string(gantt, &mut gs, 80, 30);
     assert!(gantt_text.contains("[x]"), "Gantt missing completed icon");
     assert!(gantt_text.contains("[!]"), "Gantt missing failed icon");
 
@@ -296,6 +255,7 @@ fn status_representation_consistent() {
     let detail = DetailWidget::new(
         DetailContent::Task(completed_task, &state.phases[0].name, vec![]),
         true,
+        state.now(),
     );
     let detail_area = Rect::new(0, 0, 50, 15);
     let mut detail_buf = Buffer::empty(detail_area);
@@ -340,11 +300,7 @@ fn agent_panel_shows_running_for_tool_events() {
     state.update_from_events(&result.events);
 
     let panel = AgentPanel::new(&state);
-    let area = Rect::new(0, 0, 60, 10);
-    let mut buf = Buffer::empty(area);
-    panel.render(area, &mut buf);
-
-    let text = buffer_text(&buf);
+    let text = render_to_string(panel, 60, 10);
     // Agent "main" should appear with ">>" (Running) and "Bash" tool
     assert!(text.contains("main"), "Agent 'main' should appear");
     assert!(text.contains(">>"), "Should show Running status (>>)");
@@ -361,11 +317,7 @@ fn agent_panel_shows_idle_after_tool_end() {
     state.update_from_events(&result.events);
 
     let panel = AgentPanel::new(&state);
-    let area = Rect::new(0, 0, 60, 10);
-    let mut buf = Buffer::empty(area);
-    panel.render(area, &mut buf);
-
-    let text = buffer_text(&buf);
+    let text = render_to_string(panel, 60, 10);
     assert!(text.contains("main"), "Agent 'main' should appear");
     assert!(text.contains("--"), "Should show Idle status (--)");
 }
@@ -422,5 +374,5 @@ fn full_dashboard_renders_without_panic() {
     statusbar.render(layout.status_bar, &mut buf);
 
     // Help overlay
-    HelpOverlay.render(area, &mut buf);
+    HelpOverlay::default().render(area, &mut buf);
 }